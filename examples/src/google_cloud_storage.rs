@@ -25,6 +25,7 @@ async fn main() -> anyhow::Result<()> {
     println!("Writing 1000 keys without waiting for flush");
     let write_options = slatedb::config::WriteOptions {
         await_durable: false,
+        ..Default::default()
     };
     for i in 0..1000 {
         db.put_with_options(
@@ -134,6 +134,42 @@ pub(crate) enum CliCommands {
         round: FindOption,
     },
 
+    /// Compacts the L0 SSTs and sorted runs that overlap a key range, e.g. to reclaim the space
+    /// of deleted keys. The compaction is submitted to the running compactor, and the command
+    /// reports its progress until the output of the compaction is written to the manifest. A
+    /// compactor must be running.
+    Compact {
+        /// Optionally specify the first key (inclusive) of the range to compact. If not
+        /// specified, the range starts at the first key of the db.
+        #[arg(short, long)]
+        start: Option<String>,
+
+        /// Optionally specify the end key (exclusive) of the range to compact. If not
+        /// specified, the range ends after the last key of the db.
+        #[arg(short, long)]
+        end: Option<String>,
+
+        /// How often to check the progress of the compaction, in a human-friendly format,
+        /// e.g. "1s" or "500ms".
+        #[arg(long, default_value = "1s")]
+        #[clap(value_parser = humantime::parse_duration)]
+        poll_interval: Duration,
+
+        /// Optionally specify how long to wait for the compaction to finish, in a
+        /// human-friendly format, e.g. "10m". If not specified, the command waits until the
+        /// compaction finishes.
+        #[arg(long)]
+        #[clap(value_parser = humantime::parse_duration)]
+        timeout: Option<Duration>,
+    },
+
+    /// Lists every version of a key that the db still retains, including tombstones and
+    /// merge operands, along with the WAL or compacted SST and block each one lives in.
+    InspectKey {
+        /// The key to inspect.
+        key: String,
+    },
+
     /// Schedules a period garbage collection job
     #[command(group(
     ArgGroup::new("gc_config")
@@ -1,12 +1,13 @@
 use crate::args::{parse_args, CliArgs, CliCommands, GcResource, GcSchedule};
 use chrono::{TimeZone, Utc};
 use object_store::path::Path;
-use slatedb::admin::{self, Admin, AdminBuilder};
+use slatedb::admin::{self, Admin, AdminBuilder, CompactRangeProgress, KeyVersionLayer};
 use slatedb::config::{
     CheckpointOptions, GarbageCollectorDirectoryOptions, GarbageCollectorOptions,
 };
-use slatedb::FindOption;
+use slatedb::{ChangeValue, FindOption};
 use std::error::Error;
+use std::ops::Bound;
 use std::time::Duration;
 use tokio_util::sync::CancellationToken;
 use tracing::debug;
@@ -69,6 +70,13 @@ async fn main() -> Result<(), Box<dyn Error>> {
         CliCommands::TsToSeq { ts_secs, round } => {
             exec_ts_to_seq(&admin, ts_secs, matches!(round, FindOption::RoundUp)).await?
         }
+        CliCommands::Compact {
+            start,
+            end,
+            poll_interval,
+            timeout,
+        } => exec_compact(&admin, start, end, poll_interval, timeout).await?,
+        CliCommands::InspectKey { key } => exec_inspect_key(&admin, key).await?,
     }
 
     Ok(())
@@ -216,3 +224,73 @@ async fn exec_ts_to_seq(admin: &Admin, ts_secs: i64, round_up: bool) -> Result<(
     }
     Ok(())
 }
+
+async fn exec_compact(
+    admin: &Admin,
+    start: Option<String>,
+    end: Option<String>,
+    poll_interval: Duration,
+    timeout: Option<Duration>,
+) -> Result<(), Box<dyn Error>> {
+    let start = start.map_or(Bound::Unbounded, |k| Bound::Included(k.into_bytes()));
+    let end = end.map_or(Bound::Unbounded, |k| Bound::Excluded(k.into_bytes()));
+    admin
+        .compact_range(
+            (start, end),
+            poll_interval,
+            timeout,
+            |progress| match progress {
+                CompactRangeProgress::NothingToCompact => println!("nothing to compact in range"),
+                CompactRangeProgress::Submitted => {
+                    println!("compaction submitted, waiting for the compactor to start it")
+                }
+                CompactRangeProgress::Running { output_ssts } => {
+                    println!("compaction running, {} output SSTs written", output_ssts)
+                }
+                CompactRangeProgress::Finished { manifest_id } => {
+                    println!("compaction finished, written to manifest {}", manifest_id)
+                }
+                progress => println!("{:?}", progress),
+            },
+        )
+        .await?;
+    Ok(())
+}
+
+async fn exec_inspect_key(admin: &Admin, key: String) -> Result<(), Box<dyn Error>> {
+    let versions = admin.key_history(key.as_bytes()).await?;
+    if versions.is_empty() {
+        println!("no versions found");
+    }
+    let format_ts = |ts: Option<i64>| ts.map_or("none".to_string(), |ts| ts.to_string());
+    for version in versions {
+        let layer = match version.layer {
+            KeyVersionLayer::Wal { wal_id } => format!("wal {}", wal_id),
+            KeyVersionLayer::L0 { sst_id } => format!("l0 {}", sst_id),
+            KeyVersionLayer::SortedRun {
+                sorted_run_id,
+                sst_id,
+            } => format!("sr {} {}", sorted_run_id, sst_id),
+            layer => format!("{:?}", layer),
+        };
+        let value = match version.value {
+            ChangeValue::Value(value) => format!("value {:?}", String::from_utf8_lossy(&value)),
+            ChangeValue::Merge(value) => format!("merge {:?}", String::from_utf8_lossy(&value)),
+            ChangeValue::Tombstone => "tombstone".to_string(),
+            ChangeValue::RangeDelete { start, end } => {
+                format!("range delete {:?}..{:?}", start, end)
+            }
+            value => format!("{:?}", value),
+        };
+        println!(
+            "{} block={} seq={} create_ts={} expire_ts={} {}",
+            layer,
+            version.block,
+            version.seq,
+            format_ts(version.create_ts),
+            format_ts(version.expire_ts),
+            value
+        );
+    }
+    Ok(())
+}
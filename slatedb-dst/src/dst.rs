@@ -425,6 +425,7 @@ impl DefaultDstDistribution {
         let mut rng = self.rand.rng();
         WriteOptions {
             await_durable: rng.random_bool(0.5),
+            ..WriteOptions::default()
         }
     }
 
@@ -157,6 +157,52 @@ func (b *WriteBatch) Delete(key []byte) error {
 	return nil
 }
 
+// AddPrecondition requires the key's current value to equal expected for the batch to be
+// written. A nil expected value requires the key to be absent. If any precondition does
+// not hold, none of the batch's writes are applied and Write returns ErrPreconditionFailed.
+//
+// Example:
+//
+//	batch.AddPrecondition([]byte("user:1"), nil)             // user:1 must not exist
+//	batch.AddPrecondition([]byte("version"), []byte("41"))   // version must be "41"
+//	batch.Put([]byte("user:1"), []byte("alice"))
+//	batch.Put([]byte("version"), []byte("42"))
+func (b *WriteBatch) AddPrecondition(key, expected []byte) error {
+	if b.closed {
+		return errors.New("batch is closed")
+	}
+	if b.consumed {
+		return errors.New("batch already consumed")
+	}
+	if len(key) == 0 {
+		return errors.New("key cannot be empty")
+	}
+
+	keyPtr := (*C.uint8_t)(unsafe.Pointer(&key[0]))
+
+	// A null pointer means "absent", so an empty (non-nil) expected value still needs a
+	// non-null pointer.
+	var expectedPtr *C.uint8_t
+	if expected != nil {
+		var empty C.uint8_t
+		expectedPtr = &empty
+		if len(expected) > 0 {
+			expectedPtr = (*C.uint8_t)(unsafe.Pointer(&expected[0]))
+		}
+	}
+
+	result := C.slatedb_write_batch_add_precondition(
+		b.ptr,
+		keyPtr, C.size_t(len(key)),
+		expectedPtr, C.size_t(len(expected)),
+	)
+
+	if err := resultToError(result); err != nil {
+		return fmt.Errorf("failed to add precondition: %w", err)
+	}
+	return nil
+}
+
 // Close releases the resources associated with the WriteBatch
 // This must always be called to prevent memory leaks, even if the batch was consumed by Write()
 func (b *WriteBatch) Close() error {
@@ -171,6 +171,47 @@ var _ = Describe("WriteBatch", func() {
 			Expect(value).To(Equal([]byte("value1")))
 		})
 
+		It("should apply nothing when a precondition fails", func() {
+			err := db.Put([]byte("pre_key1"), []byte("value1"))
+			Expect(err).NotTo(HaveOccurred())
+
+			batch, err := slatedb.NewWriteBatch()
+			Expect(err).NotTo(HaveOccurred())
+			defer batch.Close()
+
+			err = batch.AddPrecondition([]byte("pre_key1"), []byte("value1"))
+			Expect(err).NotTo(HaveOccurred())
+			err = batch.AddPrecondition([]byte("pre_key2"), []byte("missing"))
+			Expect(err).NotTo(HaveOccurred())
+			err = batch.Delete([]byte("pre_key1"))
+			Expect(err).NotTo(HaveOccurred())
+
+			err = db.Write(batch)
+			Expect(err).To(MatchError(slatedb.ErrPreconditionFailed))
+
+			value, err := db.Get([]byte("pre_key1"))
+			Expect(err).NotTo(HaveOccurred())
+			Expect(value).To(Equal([]byte("value1")))
+		})
+
+		It("should write when preconditions hold", func() {
+			batch, err := slatedb.NewWriteBatch()
+			Expect(err).NotTo(HaveOccurred())
+			defer batch.Close()
+
+			err = batch.AddPrecondition([]byte("pre_new"), nil)
+			Expect(err).NotTo(HaveOccurred())
+			err = batch.Put([]byte("pre_new"), []byte("value"))
+			Expect(err).NotTo(HaveOccurred())
+
+			err = db.Write(batch)
+			Expect(err).NotTo(HaveOccurred())
+
+			value, err := db.Get([]byte("pre_new"))
+			Expect(err).NotTo(HaveOccurred())
+			Expect(value).To(Equal([]byte("value")))
+		})
+
 		It("should validate empty keys", func() {
 			batch, err := slatedb.NewWriteBatch()
 			Expect(err).NotTo(HaveOccurred())
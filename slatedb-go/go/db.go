@@ -23,6 +23,10 @@ var (
 	ErrInternalError   = errors.New("internal error")
 	ErrNullPointer     = errors.New("null pointer")
 	ErrInvalidHandle   = errors.New("invalid handle")
+
+	// ErrPreconditionFailed is returned when a write precondition (PutIfAbsent,
+	// CompareAndSet or WriteBatch.AddPrecondition) does not hold
+	ErrPreconditionFailed = errors.New("precondition failed")
 )
 
 // DB represents a SlateDB database connection
@@ -36,6 +40,37 @@ type KeyValue struct {
 	Value []byte
 }
 
+// KeyValueWithMetadata represents a key-value pair along with the metadata of the row
+// it was read from
+type KeyValueWithMetadata struct {
+	Key      []byte
+	Value    []byte
+	Seq      uint64 // Sequence number of the write that produced the row
+	CreateTs *int64 // Time the row was written at in milliseconds, if known
+	ExpireTs *int64 // Time the row expires at in milliseconds, nil if it doesn't expire
+}
+
+// newKeyValueWithMetadata copies a C key, value and row metadata to Go memory
+func newKeyValueWithMetadata(key []byte, value C.CSdbValue, metadata C.CSdbRowMetadata) KeyValueWithMetadata {
+	kv := KeyValueWithMetadata{
+		Key:   key,
+		Value: []byte{},
+		Seq:   uint64(metadata.seq),
+	}
+	if value.data != nil && value.len > 0 {
+		kv.Value = C.GoBytes(unsafe.Pointer(value.data), C.int(value.len))
+	}
+	if metadata.has_create_ts {
+		createTs := int64(metadata.create_ts)
+		kv.CreateTs = &createTs
+	}
+	if metadata.has_expire_ts {
+		expireTs := int64(metadata.expire_ts)
+		kv.ExpireTs = &expireTs
+	}
+	return kv
+}
+
 // ScanResult represents the result of a scan operation
 type ScanResult struct {
 	Items        []KeyValue
@@ -64,6 +99,8 @@ func resultToError(result C.struct_CSdbResult) error {
 		baseErr = ErrNullPointer
 	case C.InvalidHandle:
 		baseErr = ErrInvalidHandle
+	case C.PreconditionFailed:
+		baseErr = ErrPreconditionFailed
 	default:
 		baseErr = ErrInternalError
 	}
@@ -119,6 +156,90 @@ func (db *DB) Delete(key []byte) error {
 	return db.DeleteWithOptions(key, nil)
 }
 
+// PutIfAbsent stores a key-value pair only if the key does not currently exist
+// Returns ErrPreconditionFailed if the key already exists
+//
+// Example:
+//
+//	err := db.PutIfAbsent([]byte("lock:job-1"), []byte("worker-a"))
+//	if errors.Is(err, slatedb.ErrPreconditionFailed) {
+//	    // another worker holds the lock
+//	}
+func (db *DB) PutIfAbsent(key, value []byte) error {
+	if len(key) == 0 {
+		return ErrInvalidArgument
+	}
+
+	keyPtr := (*C.uint8_t)(unsafe.Pointer(&key[0]))
+
+	// Empty values are valid, but the FFI rejects null pointers, so point at a
+	// zero-length buffer instead.
+	var empty C.uint8_t
+	valuePtr := &empty
+	if len(value) > 0 {
+		valuePtr = (*C.uint8_t)(unsafe.Pointer(&value[0]))
+	}
+
+	result := C.slatedb_put_if_absent(
+		db.handle,
+		keyPtr,
+		C.uintptr_t(len(key)),
+		valuePtr,
+		C.uintptr_t(len(value)),
+	)
+	defer C.slatedb_free_result(result)
+
+	if result.error != C.Success {
+		return resultToError(result)
+	}
+
+	return nil
+}
+
+// CompareAndSet stores a key-value pair only if the key's current value equals expected
+// Returns ErrPreconditionFailed if the key is missing or holds a different value
+//
+// Example:
+//
+//	err := db.CompareAndSet([]byte("counter"), []byte("1"), []byte("2"))
+func (db *DB) CompareAndSet(key, expected, value []byte) error {
+	if len(key) == 0 {
+		return ErrInvalidArgument
+	}
+
+	keyPtr := (*C.uint8_t)(unsafe.Pointer(&key[0]))
+
+	// Empty values are valid, but the FFI rejects null pointers, so point at a
+	// zero-length buffer instead.
+	var empty C.uint8_t
+	expectedPtr := &empty
+	if len(expected) > 0 {
+		expectedPtr = (*C.uint8_t)(unsafe.Pointer(&expected[0]))
+	}
+
+	valuePtr := &empty
+	if len(value) > 0 {
+		valuePtr = (*C.uint8_t)(unsafe.Pointer(&value[0]))
+	}
+
+	result := C.slatedb_compare_and_set(
+		db.handle,
+		keyPtr,
+		C.uintptr_t(len(key)),
+		expectedPtr,
+		C.uintptr_t(len(expected)),
+		valuePtr,
+		C.uintptr_t(len(value)),
+	)
+	defer C.slatedb_free_result(result)
+
+	if result.error != C.Success {
+		return resultToError(result)
+	}
+
+	return nil
+}
+
 // PutWithOptions stores a key-value pair in the database with custom put and write options
 // This provides control over TTL and durability behavior
 //
@@ -245,6 +366,50 @@ func (db *DB) GetWithOptions(key []byte, readOpts *ReadOptions) ([]byte, error)
 	return goValue, nil
 }
 
+// GetWithMetadata retrieves a value by key along with the sequence number, creation time
+// and expiration time of the row it was read from
+// Returns ErrNotFound if the key doesn't exist
+//
+// Example:
+//
+//	kv, err := db.GetWithMetadata([]byte("user:123"), &slatedb.ReadOptions{})
+//	if err == nil && kv.ExpireTs != nil {
+//	    fmt.Printf("expires at %d\n", *kv.ExpireTs)
+//	}
+func (db *DB) GetWithMetadata(key []byte, readOpts *ReadOptions) (KeyValueWithMetadata, error) {
+	if len(key) == 0 {
+		return KeyValueWithMetadata{}, ErrInvalidArgument
+	}
+
+	keyPtr := (*C.uint8_t)(unsafe.Pointer(&key[0]))
+	var value C.CSdbValue
+	var metadata C.CSdbRowMetadata
+	cReadOpts := convertToCReadOptions(readOpts)
+
+	result := C.slatedb_get_with_metadata(
+		db.handle,
+		keyPtr,
+		C.uintptr_t(len(key)),
+		cReadOpts,
+		&value,
+		&metadata,
+	)
+	defer C.slatedb_free_result(result)
+
+	if result.error == C.NotFound {
+		return KeyValueWithMetadata{}, ErrNotFound
+	}
+
+	if result.error != C.Success {
+		return KeyValueWithMetadata{}, resultToError(result)
+	}
+
+	kv := newKeyValueWithMetadata(append([]byte(nil), key...), value, metadata)
+	C.slatedb_free_value(value)
+
+	return kv, nil
+}
+
 // Write executes a WriteBatch atomically with default WriteOptions
 //
 // The batch is consumed by this operation and cannot be reused.
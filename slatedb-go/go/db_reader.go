@@ -129,6 +129,43 @@ func (r *DbReader) GetWithOptions(key []byte, opts *ReadOptions) ([]byte, error)
 	return goValue, nil
 }
 
+// GetWithMetadata retrieves a value by key along with the sequence number, creation time
+// and expiration time of the row it was read from
+// Returns ErrNotFound if the key doesn't exist
+func (r *DbReader) GetWithMetadata(key []byte, opts *ReadOptions) (KeyValueWithMetadata, error) {
+	if len(key) == 0 {
+		return KeyValueWithMetadata{}, ErrInvalidArgument
+	}
+
+	keyPtr := (*C.uint8_t)(unsafe.Pointer(&key[0]))
+	var value C.CSdbValue
+	var metadata C.CSdbRowMetadata
+	cOpts := convertToCReadOptions(opts)
+
+	result := C.slatedb_reader_get_with_metadata(
+		r.handle,
+		keyPtr,
+		C.uintptr_t(len(key)),
+		cOpts,
+		&value,
+		&metadata,
+	)
+	defer C.slatedb_free_result(result)
+
+	if result.error == C.NotFound {
+		return KeyValueWithMetadata{}, ErrNotFound
+	}
+
+	if result.error != C.Success {
+		return KeyValueWithMetadata{}, resultToError(result)
+	}
+
+	kv := newKeyValueWithMetadata(append([]byte(nil), key...), value, metadata)
+	C.slatedb_free_value(value)
+
+	return kv, nil
+}
+
 // Scan creates a streaming iterator for the specified range with default options
 // This provides full parity with Rust's range syntax:
 //
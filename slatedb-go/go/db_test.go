@@ -69,6 +69,59 @@ var _ = Describe("DB", func() {
 		})
 	})
 
+	Describe("Conditional Writes", func() {
+		It("should put only if the key is absent", func() {
+			key := []byte("cond_absent")
+
+			err := db.PutIfAbsent(key, []byte("value1"))
+			Expect(err).NotTo(HaveOccurred())
+
+			err = db.PutIfAbsent(key, []byte("value2"))
+			Expect(err).To(MatchError(slatedb.ErrPreconditionFailed))
+
+			retrievedValue, err := db.Get(key)
+			Expect(err).NotTo(HaveOccurred())
+			Expect(retrievedValue).To(Equal([]byte("value1")))
+		})
+
+		It("should compare and set", func() {
+			key := []byte("cond_cas")
+
+			err := db.CompareAndSet(key, []byte("v1"), []byte("v2"))
+			Expect(err).To(MatchError(slatedb.ErrPreconditionFailed))
+
+			err = db.Put(key, []byte("v1"))
+			Expect(err).NotTo(HaveOccurred())
+
+			err = db.CompareAndSet(key, []byte("v0"), []byte("v2"))
+			Expect(err).To(MatchError(slatedb.ErrPreconditionFailed))
+
+			err = db.CompareAndSet(key, []byte("v1"), []byte("v2"))
+			Expect(err).NotTo(HaveOccurred())
+
+			retrievedValue, err := db.Get(key)
+			Expect(err).NotTo(HaveOccurred())
+			Expect(retrievedValue).To(Equal([]byte("v2")))
+		})
+
+		It("should handle empty expected and new values", func() {
+			key := []byte("cond_empty")
+
+			err := db.PutIfAbsent(key, []byte{})
+			Expect(err).NotTo(HaveOccurred())
+
+			err = db.CompareAndSet(key, []byte{}, []byte("v1"))
+			Expect(err).NotTo(HaveOccurred())
+
+			err = db.CompareAndSet(key, []byte("v1"), []byte{})
+			Expect(err).NotTo(HaveOccurred())
+
+			retrievedValue, err := db.Get(key)
+			Expect(err).NotTo(HaveOccurred())
+			Expect(retrievedValue).To(BeEmpty())
+		})
+	})
+
 	Describe("Operations with Options", func() {
 		It("should put with custom options", func() {
 			key := []byte("options_test")
@@ -102,6 +155,27 @@ var _ = Describe("DB", func() {
 			Expect(retrievedValue).To(Equal(value))
 		})
 
+		It("should get with metadata", func() {
+			key := []byte("metadata_test")
+			value := []byte("metadata_value")
+
+			putOpts := &slatedb.PutOptions{TTLType: slatedb.TTLExpireAfter, TTLValue: 60_000}
+			err := db.PutWithOptions(key, value, putOpts, &slatedb.WriteOptions{AwaitDurable: true})
+			Expect(err).NotTo(HaveOccurred())
+
+			kv, err := db.GetWithMetadata(key, &slatedb.ReadOptions{})
+			Expect(err).NotTo(HaveOccurred())
+			Expect(kv.Key).To(Equal(key))
+			Expect(kv.Value).To(Equal(value))
+			Expect(kv.Seq).To(BeNumerically(">", 0))
+			Expect(kv.CreateTs).NotTo(BeNil())
+			Expect(kv.ExpireTs).NotTo(BeNil())
+			Expect(*kv.ExpireTs).To(Equal(*kv.CreateTs + 60_000))
+
+			_, err = db.GetWithMetadata([]byte("missing"), &slatedb.ReadOptions{})
+			Expect(err).To(Equal(slatedb.ErrNotFound))
+		})
+
 		It("should delete with custom options", func() {
 			key := []byte("delete_options_test")
 			value := []byte("delete_options_value")
@@ -70,6 +70,40 @@ func (iter *Iterator) Next() (KeyValue, error) {
 	}, nil
 }
 
+// NextWithMetadata returns the next key-value pair along with the metadata of the row it
+// was read from. Returns io.EOF when iteration is complete.
+func (iter *Iterator) NextWithMetadata() (KeyValueWithMetadata, error) {
+	if iter.closed {
+		return KeyValueWithMetadata{}, errors.New("iterator is closed")
+	}
+
+	if iter.ptr == nil {
+		return KeyValueWithMetadata{}, errors.New("invalid iterator")
+	}
+
+	var cKeyValue C.CSdbKeyValue
+	var metadata C.CSdbRowMetadata
+	result := C.slatedb_iterator_next_with_metadata(iter.ptr, &cKeyValue, &metadata)
+	defer C.slatedb_free_result(result)
+
+	if result.error == C.NotFound {
+		return KeyValueWithMetadata{}, io.EOF // End of iteration
+	}
+
+	if result.error != C.Success {
+		return KeyValueWithMetadata{}, resultToError(result)
+	}
+
+	keyData := C.GoBytes(unsafe.Pointer(cKeyValue.key.data), C.int(cKeyValue.key.len))
+	kv := newKeyValueWithMetadata(keyData, cKeyValue.value, metadata)
+
+	// Free the C memory allocated in Rust
+	C.slatedb_free_value(cKeyValue.key)
+	C.slatedb_free_value(cKeyValue.value)
+
+	return kv, nil
+}
+
 // Seek moves the iterator to the specified key position
 // After seek, Next() will return records starting from the seek key or the next available key
 //
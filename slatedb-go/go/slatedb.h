@@ -21,6 +21,7 @@ typedef enum CSdbError {
     InternalError = 5,
     NullPointer = 6,
     InvalidHandle = 7,
+    PreconditionFailed = 8,
 } CSdbError;
 
 // Internal struct for managing database iterators in FFI
@@ -79,6 +80,14 @@ typedef struct CSdbValue {
     uintptr_t len;
 } CSdbValue;
 
+typedef struct CSdbRowMetadata {
+    uint64_t seq;
+    bool has_create_ts;
+    int64_t create_ts;
+    bool has_expire_ts;
+    int64_t expire_ts;
+} CSdbRowMetadata;
+
 typedef struct CSdbScanOptions {
     int32_t durability_filter;
     bool dirty;
@@ -127,6 +136,26 @@ typedef struct CSdbScanResult {
 
 #define SsTableInfo_VT_COMPRESSION_FORMAT 14
 
+#define SsTableInfo_VT_RANGE_TOMBSTONES 16
+
+#define SsTableInfo_VT_COLUMN_FAMILY_KEYS 18
+
+#define SsTableInfo_VT_PREFIX_EXTRACTOR 20
+
+#define SsTableInfo_VT_BLOB_REFS 22
+
+#define SsTableInfo_VT_ENCRYPTION_KEY_ID 24
+
+#define RangeTombstone_VT_RANGE 4
+
+#define RangeTombstone_VT_SEQ 6
+
+#define RangeTombstone_VT_CREATE_TS 8
+
+#define BlobFileRef_VT_ID 4
+
+#define BlobFileRef_VT_LIVE_BYTES 6
+
 #define BlockMeta_VT_OFFSET 4
 
 #define SsTableIndex_VT_BLOCK_META 4
@@ -135,8 +164,6 @@ typedef struct CSdbScanResult {
 
 #define CompactedSstId_VT_LOW 6
 
-#define CompactedSsTable_VT_ID 4
-
 #define CompactedSsTable_VT_INFO 6
 
 #define CompactedSsTable_VT_VISIBLE_RANGE 8
@@ -191,6 +218,16 @@ typedef struct CSdbScanResult {
 
 #define ManifestV1_VT_SEQUENCE_TRACKER 34
 
+#define ManifestV1_VT_COLUMN_FAMILIES 36
+
+#define ManifestV1_VT_COMPARATOR 38
+
+#define ManifestV1_VT_WRITER_HANDOFF 40
+
+#define WriterHandoff_VT_LAST_SEQ 6
+
+#define ColumnFamily_VT_NAME 6
+
 #define WriterCheckpoint_VT_EPOCH 4
 
 #define Checkpoint_VT_CHECKPOINT_EXPIRE_TIME_S 8
@@ -201,7 +238,33 @@ typedef struct CSdbScanResult {
 
 #define Checkpoint_VT_METADATA 14
 
-#define Checkpoint_VT_NAME 16
+#define CompactionSource_VT_SST_ID 4
+
+#define CompactionSource_VT_SORTED_RUN_ID 6
+
+#define Compaction_VT_COLUMN_FAMILY 6
+
+#define Compaction_VT_SOURCES 8
+
+#define Compaction_VT_DESTINATION 10
+
+#define Compaction_VT_OUTPUT_SSTS 12
+
+#define CompactionsV1_VT_COMPACTIONS 6
+
+#define CompactionsV1_VT_SUBMITTED 8
+
+#define CompactionJob_VT_COMPACTION_ID 6
+
+#define CompactionJob_VT_SORTED_RUNS 14
+
+#define CompactionJob_VT_COMPACTION_LOGICAL_CLOCK_TICK 16
+
+#define CompactionJob_VT_IS_DEST_LAST_RUN 18
+
+#define CompactionJob_VT_RETENTION_MIN_SEQ 20
+
+#define CompactionJobResult_VT_ERROR 8
 
 // Initialize logging for SlateDB Go bindings
 // This should be called once before using any other SlateDB functions
@@ -260,6 +323,18 @@ struct CSdbResult slatedb_write_batch_delete(struct CSdbWriteBatch *batch,
                                              const uint8_t *key,
                                              uintptr_t key_len);
 
+// # Safety
+//
+// - `batch` must be a valid pointer to a WriteBatch
+// - `key` must point to valid memory of at least `key_len` bytes
+// - `expected` must point to valid memory of at least `expected_len` bytes, or be null
+//   to require that the key is absent
+struct CSdbResult slatedb_write_batch_add_precondition(struct CSdbWriteBatch *batch,
+                                                       const uint8_t *key,
+                                                       uintptr_t key_len,
+                                                       const uint8_t *expected,
+                                                       uintptr_t expected_len);
+
 // # Safety
 //
 // - `handle` must contain a valid database handle pointer
@@ -291,6 +366,31 @@ struct CSdbResult slatedb_put_with_options(struct CSdbHandle handle,
                                            const struct CSdbPutOptions *put_options,
                                            const struct CSdbWriteOptions *write_options);
 
+// # Safety
+//
+// - `handle` must contain a valid database handle pointer
+// - `key` must point to valid memory of at least `key_len` bytes
+// - `value` must point to valid memory of at least `value_len` bytes
+struct CSdbResult slatedb_put_if_absent(struct CSdbHandle handle,
+                                        const uint8_t *key,
+                                        uintptr_t key_len,
+                                        const uint8_t *value,
+                                        uintptr_t value_len);
+
+// # Safety
+//
+// - `handle` must contain a valid database handle pointer
+// - `key` must point to valid memory of at least `key_len` bytes
+// - `expected` must point to valid memory of at least `expected_len` bytes
+// - `value` must point to valid memory of at least `value_len` bytes
+struct CSdbResult slatedb_compare_and_set(struct CSdbHandle handle,
+                                          const uint8_t *key,
+                                          uintptr_t key_len,
+                                          const uint8_t *expected,
+                                          uintptr_t expected_len,
+                                          const uint8_t *value,
+                                          uintptr_t value_len);
+
 // # Safety
 //
 // - `handle` must contain a valid database handle pointer
@@ -313,6 +413,20 @@ struct CSdbResult slatedb_get_with_options(struct CSdbHandle handle,
                                            const struct CSdbReadOptions *read_options,
                                            struct CSdbValue *value_out);
 
+// # Safety
+//
+// - `handle` must contain a valid database handle pointer
+// - `key` must point to valid memory of at least `key_len` bytes
+// - `read_options` must be a valid pointer to CSdbReadOptions or null
+// - `value_out` must be a valid pointer to a location where a value can be stored
+// - `metadata_out` must be a valid pointer to a location where row metadata can be stored
+struct CSdbResult slatedb_get_with_metadata(struct CSdbHandle handle,
+                                            const uint8_t *key,
+                                            uintptr_t key_len,
+                                            const struct CSdbReadOptions *read_options,
+                                            struct CSdbValue *value_out,
+                                            struct CSdbRowMetadata *metadata_out);
+
 struct CSdbResult slatedb_flush(struct CSdbHandle handle);
 
 struct CSdbResult slatedb_close(struct CSdbHandle handle);
@@ -381,6 +495,20 @@ struct CSdbResult slatedb_reader_get_with_options(struct CSdbReaderHandle handle
                                                   const struct CSdbReadOptions *read_options,
                                                   struct CSdbValue *value_out);
 
+// # Safety
+//
+// - `handle` must contain a valid reader handle pointer
+// - `key` must point to valid memory of at least `key_len` bytes
+// - `read_options` must be a valid pointer to CSdbReadOptions or null
+// - `value_out` must be a valid pointer to a location where a value can be stored
+// - `metadata_out` must be a valid pointer to a location where row metadata can be stored
+struct CSdbResult slatedb_reader_get_with_metadata(struct CSdbReaderHandle handle,
+                                                   const uint8_t *key,
+                                                   uintptr_t key_len,
+                                                   const struct CSdbReadOptions *read_options,
+                                                   struct CSdbValue *value_out,
+                                                   struct CSdbRowMetadata *metadata_out);
+
 // # Safety
 //
 // - `handle` must contain a valid reader handle pointer
@@ -404,6 +532,15 @@ struct CSdbResult slatedb_reader_close(struct CSdbReaderHandle handle);
 // - `kv_out` must be a valid pointer to a location where a key-value pair can be stored
 struct CSdbResult slatedb_iterator_next(struct CSdbIterator *iter, struct CSdbKeyValue *kv_out);
 
+// # Safety
+//
+// - `iter` must be a valid pointer to a CSdbIterator
+// - `kv_out` must be a valid pointer to a location where a key-value pair can be stored
+// - `metadata_out` must be a valid pointer to a location where row metadata can be stored
+struct CSdbResult slatedb_iterator_next_with_metadata(struct CSdbIterator *iter,
+                                                      struct CSdbKeyValue *kv_out,
+                                                      struct CSdbRowMetadata *metadata_out);
+
 // # Safety
 //
 // - `iter` must be a valid pointer to a CSdbIterator
@@ -3,7 +3,7 @@ use crate::error::{
     create_error_result, create_success_result, slate_error_to_code, CSdbError, CSdbResult,
 };
 use crate::types::{CSdbHandle, CSdbPutOptions, CSdbWriteBatch, CSdbWriteOptions};
-use slatedb::WriteBatch;
+use slatedb::{Precondition, WriteBatch};
 
 // ============================================================================
 // WriteBatch Functions
@@ -173,6 +173,53 @@ pub unsafe extern "C" fn slatedb_write_batch_delete(
     }
 }
 
+/// # Safety
+///
+/// - `batch` must be a valid pointer to a WriteBatch
+/// - `key` must point to valid memory of at least `key_len` bytes
+/// - `expected` must point to valid memory of at least `expected_len` bytes, or be null
+///   to require that the key is absent
+#[no_mangle]
+pub unsafe extern "C" fn slatedb_write_batch_add_precondition(
+    batch: *mut CSdbWriteBatch,
+    key: *const u8,
+    key_len: usize,
+    expected: *const u8,
+    expected_len: usize,
+) -> CSdbResult {
+    if batch.is_null() {
+        return create_error_result(CSdbError::NullPointer, "WriteBatch pointer is null");
+    }
+    if key.is_null() {
+        return create_error_result(CSdbError::NullPointer, "Key pointer is null");
+    }
+
+    let batch_ref = unsafe { &mut *batch };
+
+    if let Some(ref mut wb) = batch_ref.batch {
+        let key_slice = unsafe { std::slice::from_raw_parts(key, key_len) };
+
+        // Validate key size (match Rust core validation)
+        if key_slice.is_empty() {
+            return create_error_result(CSdbError::InvalidArgument, "Key cannot be empty");
+        }
+        if key_len > u16::MAX as usize {
+            return create_error_result(CSdbError::InvalidArgument, "Key size must be <= u16::MAX");
+        }
+
+        let precondition = if expected.is_null() {
+            Precondition::Absent
+        } else {
+            let expected_slice = unsafe { std::slice::from_raw_parts(expected, expected_len) };
+            Precondition::ValueEquals(expected_slice.to_vec().into())
+        };
+        wb.add_precondition(key_slice, precondition);
+        create_success_result()
+    } else {
+        create_error_result(CSdbError::InvalidArgument, "WriteBatch already consumed")
+    }
+}
+
 /// # Safety
 ///
 /// - `handle` must contain a valid database handle pointer
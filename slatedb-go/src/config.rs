@@ -137,12 +137,14 @@ pub fn convert_write_options(c_opts: *const CSdbWriteOptions) -> WriteOptions {
     if c_opts.is_null() {
         return WriteOptions {
             await_durable: true,
+            ..WriteOptions::default()
         };
     }
 
     let opts = unsafe { &*c_opts };
     WriteOptions {
         await_durable: opts.await_durable,
+        ..WriteOptions::default()
     }
 }
 
@@ -176,10 +178,9 @@ pub fn convert_read_options(c_opts: *const CSdbReadOptions) -> ReadOptions {
         _ => DurabilityLevel::Memory, // fallback
     };
 
-    ReadOptions {
-        durability_filter,
-        dirty: opts.dirty,
-    }
+    ReadOptions::new()
+        .with_durability_filter(durability_filter)
+        .with_dirty(opts.dirty)
 }
 
 // Convert C range bounds to Rust range bounds
@@ -240,6 +241,9 @@ pub fn convert_reader_options(c_opts: *const CSdbReaderOptions) -> DbReaderOptio
         max_memtable_bytes,
         block_cache: defaults.block_cache,
         merge_operator: defaults.merge_operator,
+        prefix_extractor: defaults.prefix_extractor,
+        comparator: defaults.comparator,
+        encryption_provider: defaults.encryption_provider,
     }
 }
 
@@ -12,9 +12,10 @@ use crate::error::{
     create_error_result, create_success_result, safe_str_from_ptr, slate_error_to_code, CSdbError,
     CSdbResult,
 };
+use crate::memory::bytes_to_value;
 use crate::types::{
-    CSdbHandle, CSdbIterator, CSdbPutOptions, CSdbReadOptions, CSdbScanOptions, CSdbValue,
-    CSdbWriteOptions, SlateDbFFI,
+    CSdbHandle, CSdbIterator, CSdbPutOptions, CSdbReadOptions, CSdbRowMetadata, CSdbScanOptions,
+    CSdbValue, CSdbWriteOptions, SlateDbFFI,
 };
 use slatedb::config::{Settings, SstBlockSize};
 
@@ -123,6 +124,88 @@ pub unsafe extern "C" fn slatedb_put_with_options(
     }
 }
 
+/// # Safety
+///
+/// - `handle` must contain a valid database handle pointer
+/// - `key` must point to valid memory of at least `key_len` bytes
+/// - `value` must point to valid memory of at least `value_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn slatedb_put_if_absent(
+    mut handle: CSdbHandle,
+    key: *const u8,
+    key_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> CSdbResult {
+    if handle.is_null() {
+        return create_error_result(CSdbError::InvalidHandle, "Invalid database handle");
+    }
+
+    if key.is_null() || value.is_null() {
+        return create_error_result(CSdbError::NullPointer, "Key or value is null");
+    }
+
+    let key_slice = unsafe { std::slice::from_raw_parts(key, key_len) };
+    let value_slice = unsafe { std::slice::from_raw_parts(value, value_len) };
+
+    let inner = handle.as_inner();
+    match inner.block_on(inner.db.put_if_absent(key_slice, value_slice)) {
+        Ok(_) => create_success_result(),
+        Err(e) => {
+            let error_code = slate_error_to_code(&e);
+            create_error_result(
+                error_code,
+                &format!("Put if absent operation failed: {}", e),
+            )
+        }
+    }
+}
+
+/// # Safety
+///
+/// - `handle` must contain a valid database handle pointer
+/// - `key` must point to valid memory of at least `key_len` bytes
+/// - `expected` must point to valid memory of at least `expected_len` bytes
+/// - `value` must point to valid memory of at least `value_len` bytes
+#[no_mangle]
+pub unsafe extern "C" fn slatedb_compare_and_set(
+    mut handle: CSdbHandle,
+    key: *const u8,
+    key_len: usize,
+    expected: *const u8,
+    expected_len: usize,
+    value: *const u8,
+    value_len: usize,
+) -> CSdbResult {
+    if handle.is_null() {
+        return create_error_result(CSdbError::InvalidHandle, "Invalid database handle");
+    }
+
+    if key.is_null() || expected.is_null() || value.is_null() {
+        return create_error_result(CSdbError::NullPointer, "Key, expected or value is null");
+    }
+
+    let key_slice = unsafe { std::slice::from_raw_parts(key, key_len) };
+    let expected_slice = unsafe { std::slice::from_raw_parts(expected, expected_len) };
+    let value_slice = unsafe { std::slice::from_raw_parts(value, value_len) };
+
+    let inner = handle.as_inner();
+    match inner.block_on(
+        inner
+            .db
+            .compare_and_set(key_slice, expected_slice, value_slice),
+    ) {
+        Ok(_) => create_success_result(),
+        Err(e) => {
+            let error_code = slate_error_to_code(&e);
+            create_error_result(
+                error_code,
+                &format!("Compare and set operation failed: {}", e),
+            )
+        }
+    }
+}
+
 /// # Safety
 ///
 /// - `handle` must contain a valid database handle pointer
@@ -216,6 +299,60 @@ pub unsafe extern "C" fn slatedb_get_with_options(
     }
 }
 
+/// # Safety
+///
+/// - `handle` must contain a valid database handle pointer
+/// - `key` must point to valid memory of at least `key_len` bytes
+/// - `read_options` must be a valid pointer to CSdbReadOptions or null
+/// - `value_out` must be a valid pointer to a location where a value can be stored
+/// - `metadata_out` must be a valid pointer to a location where row metadata can be stored
+#[no_mangle]
+pub unsafe extern "C" fn slatedb_get_with_metadata(
+    mut handle: CSdbHandle,
+    key: *const u8,
+    key_len: usize,
+    read_options: *const CSdbReadOptions,
+    value_out: *mut CSdbValue,
+    metadata_out: *mut CSdbRowMetadata,
+) -> CSdbResult {
+    if handle.is_null() {
+        return create_error_result(CSdbError::InvalidHandle, "Invalid database handle");
+    }
+
+    if key.is_null() || value_out.is_null() || metadata_out.is_null() {
+        return create_error_result(
+            CSdbError::NullPointer,
+            "Key, value_out or metadata_out is null",
+        );
+    }
+
+    let key_slice = unsafe { std::slice::from_raw_parts(key, key_len) };
+    let rust_read_opts = convert_read_options(read_options);
+
+    let inner = handle.as_inner();
+    match inner.block_on(
+        inner
+            .db
+            .get_with_metadata_with_options(key_slice, &rust_read_opts),
+    ) {
+        Ok(Some(row)) => {
+            unsafe {
+                *value_out = bytes_to_value(&row.value);
+                *metadata_out = CSdbRowMetadata::from(&row);
+            }
+            create_success_result()
+        }
+        Ok(None) => create_error_result(CSdbError::NotFound, "Key not found"),
+        Err(e) => {
+            let error_code = slate_error_to_code(&e);
+            create_error_result(
+                error_code,
+                &format!("Get with metadata operation failed: {}", e),
+            )
+        }
+    }
+}
+
 #[no_mangle]
 pub extern "C" fn slatedb_flush(mut handle: CSdbHandle) -> CSdbResult {
     if handle.is_null() {
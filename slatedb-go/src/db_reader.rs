@@ -16,7 +16,10 @@ use crate::error::{
     create_error_result, create_success_result, safe_str_from_ptr, slate_error_to_code, CSdbError,
     CSdbResult,
 };
-use crate::types::{CSdbIterator, CSdbReadOptions, CSdbScanOptions, CSdbValue, SlateDbFFI};
+use crate::memory::bytes_to_value;
+use crate::types::{
+    CSdbIterator, CSdbReadOptions, CSdbRowMetadata, CSdbScanOptions, CSdbValue, SlateDbFFI,
+};
 
 /// Internal struct that owns a Tokio runtime and a SlateDB DbReader instance.
 /// Similar to SlateDbFFI but for read-only operations.
@@ -237,6 +240,57 @@ pub unsafe extern "C" fn slatedb_reader_get_with_options(
     }
 }
 
+/// # Safety
+///
+/// - `handle` must contain a valid reader handle pointer
+/// - `key` must point to valid memory of at least `key_len` bytes
+/// - `read_options` must be a valid pointer to CSdbReadOptions or null
+/// - `value_out` must be a valid pointer to a location where a value can be stored
+/// - `metadata_out` must be a valid pointer to a location where row metadata can be stored
+#[no_mangle]
+pub unsafe extern "C" fn slatedb_reader_get_with_metadata(
+    mut handle: CSdbReaderHandle,
+    key: *const u8,
+    key_len: usize,
+    read_options: *const CSdbReadOptions,
+    value_out: *mut CSdbValue,
+    metadata_out: *mut CSdbRowMetadata,
+) -> CSdbResult {
+    if handle.is_null() {
+        return create_error_result(CSdbError::InvalidHandle, "Invalid reader handle");
+    }
+
+    if key.is_null() || value_out.is_null() || metadata_out.is_null() {
+        return create_error_result(
+            CSdbError::NullPointer,
+            "Key, value_out or metadata_out is null",
+        );
+    }
+
+    let key_slice = unsafe { std::slice::from_raw_parts(key, key_len) };
+    let rust_read_opts = convert_read_options(read_options);
+
+    let inner = handle.as_inner();
+    match inner.block_on(
+        inner
+            .reader
+            .get_with_metadata_with_options(key_slice, &rust_read_opts),
+    ) {
+        Ok(Some(row)) => {
+            unsafe {
+                *value_out = bytes_to_value(&row.value);
+                *metadata_out = CSdbRowMetadata::from(&row);
+            }
+            create_success_result()
+        }
+        Ok(None) => create_error_result(CSdbError::NotFound, "Key not found"),
+        Err(e) => {
+            let error_code = slate_error_to_code(&e);
+            create_error_result(error_code, &format!("Get operation failed: {}", e))
+        }
+    }
+}
+
 /// # Safety
 ///
 /// - `handle` must contain a valid reader handle pointer
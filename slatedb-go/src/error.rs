@@ -1,4 +1,4 @@
-use slatedb::Error as SlateError;
+use slatedb::{Error as SlateError, ErrorKind};
 use std::ffi::{CStr, CString};
 use std::os::raw::c_char;
 
@@ -14,6 +14,7 @@ pub enum CSdbError {
     InternalError = 5,
     NullPointer = 6,
     InvalidHandle = 7,
+    PreconditionFailed = 8,
 }
 
 // Result type for returning both error codes and messages
@@ -53,6 +54,9 @@ pub fn safe_str_from_ptr(ptr: *const c_char) -> Result<&'static str, CSdbError>
 }
 
 pub fn slate_error_to_code(error: &SlateError) -> CSdbError {
+    if error.kind() == ErrorKind::Precondition {
+        return CSdbError::PreconditionFailed;
+    }
     // Use string matching since we can't pattern match on the exact variants
     let error_str = format!("{:?}", error);
     if error_str.contains("NotFound") {
@@ -1,7 +1,8 @@
 use crate::error::{
     create_error_result, create_success_result, slate_error_to_code, CSdbError, CSdbResult,
 };
-use crate::types::{CSdbIterator, CSdbKeyValue};
+use crate::memory::bytes_to_value;
+use crate::types::{CSdbIterator, CSdbKeyValue, CSdbRowMetadata};
 
 // ============================================================================
 // Iterator Functions
@@ -66,6 +67,54 @@ pub unsafe extern "C" fn slatedb_iterator_next(
     }
 }
 
+/// # Safety
+///
+/// - `iter` must be a valid pointer to a CSdbIterator
+/// - `kv_out` must be a valid pointer to a location where a key-value pair can be stored
+/// - `metadata_out` must be a valid pointer to a location where row metadata can be stored
+#[no_mangle]
+pub unsafe extern "C" fn slatedb_iterator_next_with_metadata(
+    iter: *mut CSdbIterator,
+    kv_out: *mut CSdbKeyValue,
+    metadata_out: *mut CSdbRowMetadata,
+) -> CSdbResult {
+    if iter.is_null() {
+        return create_error_result(CSdbError::NullPointer, "Iterator pointer is null");
+    }
+
+    if kv_out.is_null() || metadata_out.is_null() {
+        return create_error_result(CSdbError::NullPointer, "Output pointer is null");
+    }
+
+    let iter_ffi = unsafe { &mut *iter };
+
+    // Validate DB pointer is still alive (basic check)
+    if iter_ffi.db_ptr.is_null() {
+        return create_error_result(CSdbError::InvalidHandle, "Invalid database handle");
+    }
+
+    let db_ffi = unsafe { &*iter_ffi.db_ptr };
+
+    match db_ffi.block_on(iter_ffi.iter.next_with_metadata()) {
+        Ok(Some(row)) => {
+            unsafe {
+                (*kv_out).key = bytes_to_value(&row.key);
+                (*kv_out).value = bytes_to_value(&row.value);
+                *metadata_out = CSdbRowMetadata::from(&row);
+            }
+            create_success_result()
+        }
+        Ok(None) => {
+            // End of iteration - return NotFound to indicate end
+            create_error_result(CSdbError::NotFound, "End of iteration")
+        }
+        Err(e) => {
+            let error_code = slate_error_to_code(&e);
+            create_error_result(error_code, &format!("Iterator next failed: {}", e))
+        }
+    }
+}
+
 /// # Safety
 ///
 /// - `iter` must be a valid pointer to a CSdbIterator
@@ -13,6 +13,13 @@ pub extern "C" fn slatedb_free_result(result: CSdbResult) {
     }
 }
 
+/// Copies `bytes` into memory owned by the caller, to be freed with `slatedb_free_value`.
+pub(crate) fn bytes_to_value(bytes: &[u8]) -> CSdbValue {
+    let len = bytes.len();
+    let data = Box::into_raw(bytes.to_vec().into_boxed_slice()) as *mut u8;
+    CSdbValue { data, len }
+}
+
 #[no_mangle]
 pub extern "C" fn slatedb_free_value(value: CSdbValue) {
     if !value.data.is_null() && value.len > 0 {
@@ -1,5 +1,5 @@
-use slatedb::DbIterator;
 use slatedb::{Db, WriteBatch};
+use slatedb::{DbIterator, KeyValueWithMetadata};
 use std::ptr;
 use tokio::runtime::Runtime;
 
@@ -56,6 +56,29 @@ pub struct CSdbKeyValue {
     pub value: CSdbValue,
 }
 
+// Metadata of the row a value was read from
+#[repr(C)]
+#[derive(Clone, Copy)]
+pub struct CSdbRowMetadata {
+    pub seq: u64,
+    pub has_create_ts: bool, // Whether create_ts is set
+    pub create_ts: i64,      // Time the value was written at, in milliseconds
+    pub has_expire_ts: bool, // Whether expire_ts is set
+    pub expire_ts: i64,      // Time the value expires at, in milliseconds
+}
+
+impl From<&KeyValueWithMetadata> for CSdbRowMetadata {
+    fn from(row: &KeyValueWithMetadata) -> Self {
+        CSdbRowMetadata {
+            seq: row.seq,
+            has_create_ts: row.create_ts.is_some(),
+            create_ts: row.create_ts.unwrap_or(0),
+            has_expire_ts: row.expire_ts.is_some(),
+            expire_ts: row.expire_ts.unwrap_or(0),
+        }
+    }
+}
+
 // Scan result containing multiple key-value pairs
 #[repr(C)]
 pub struct CSdbScanResult {
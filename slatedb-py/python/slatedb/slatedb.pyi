@@ -31,6 +31,10 @@ class TransactionError(Exception):
     """Raised when a transaction conflict occurs (retryable)."""
 
 
+class PreconditionError(Exception):
+    """Raised when a write precondition (put-if-absent, compare-and-set) does not hold."""
+
+
 class ClosedError(Exception):
     """Raised when an operation targets a closed or fenced resource."""
 
@@ -220,6 +224,67 @@ class SlateDB:
         """
         ...
 
+    def put_if_absent(self, key: bytes, value: bytes) -> None:
+        """
+        Store a key-value pair only if the key does not currently exist.
+
+        Args:
+            key: Non-empty key.
+            value: Value bytes.
+
+        Raises:
+            PreconditionError: If the key already exists.
+
+        Example:
+            >>> db.put_if_absent(b"k", b"v")
+        """
+        ...
+
+    async def put_if_absent_async(self, key: bytes, value: bytes) -> None:
+        """
+        Async variant of ``put_if_absent``.
+
+        Args:
+            key: Non-empty key.
+            value: Value bytes.
+
+        Examples:
+            >>> await db.put_if_absent_async(b"k", b"v")
+        """
+        ...
+
+    def compare_and_set(self, key: bytes, expected: bytes, value: bytes) -> None:
+        """
+        Store a key-value pair only if the key's current value equals ``expected``.
+
+        Args:
+            key: Non-empty key.
+            expected: Value the key must currently have.
+            value: Value bytes to write.
+
+        Raises:
+            PreconditionError: If the key is missing or holds a different value.
+
+        Example:
+            >>> db.put(b"k", b"v1")
+            >>> db.compare_and_set(b"k", b"v1", b"v2")
+        """
+        ...
+
+    async def compare_and_set_async(self, key: bytes, expected: bytes, value: bytes) -> None:
+        """
+        Async variant of ``compare_and_set``.
+
+        Args:
+            key: Non-empty key.
+            expected: Value the key must currently have.
+            value: Value bytes to write.
+
+        Examples:
+            >>> await db.compare_and_set_async(b"k", b"v1", b"v2")
+        """
+        ...
+
     def get(self, key: bytes) -> bytes | None:
         """
         Get a value by key.
@@ -301,6 +366,57 @@ class SlateDB:
         """
         ...
 
+    def get_with_metadata(
+        self,
+        key: bytes,
+        *,
+        durability_filter: Literal["remote", "memory"] | None = None,
+        dirty: bool | None = None,
+    ) -> KeyValueWithMetadata | None:
+        """
+        Get a value from the database by key, along with the sequence number and
+        timestamps of the row it was read from.
+
+        Args:
+            key: Non-empty key.
+            durability_filter: Restrict sources ("remote" or "memory").
+            dirty: Include uncommitted/dirty data if True.
+
+        Returns:
+            :class:`KeyValueWithMetadata` or ``None`` if not found.
+
+        Examples:
+            >>> row = db.get_with_metadata(b"k")
+            >>> row["value"], row["seq"]
+            (b'v', 1)
+        """
+        ...
+
+    async def get_with_metadata_async(
+        self,
+        key: bytes,
+        *,
+        durability_filter: Literal["remote", "memory"] | None = None,
+        dirty: bool | None = None,
+    ) -> KeyValueWithMetadata | None:
+        """
+        Async variant of ``get_with_metadata``.
+
+        Args:
+            key: Non-empty key.
+            durability_filter: Restrict sources ("remote" or "memory").
+            dirty: Include uncommitted/dirty data if True.
+
+        Returns:
+            :class:`KeyValueWithMetadata` or ``None`` if not found.
+
+        Examples:
+            >>> row = await db.get_with_metadata_async(b"k")
+            >>> row["create_ts"]
+            1700000000000
+        """
+        ...
+
     def delete(self, key: bytes) -> None:
         """
         Delete a key.
@@ -379,6 +495,7 @@ class SlateDB:
         read_ahead_bytes: int | None = None,
         cache_blocks: bool | None = None,
         max_fetch_tasks: int | None = None,
+        with_metadata: bool | None = None,
     ) -> DbIterator:
         """
         Iterate with advanced scan options.
@@ -391,6 +508,8 @@ class SlateDB:
             read_ahead_bytes: Read-ahead size hint.
             cache_blocks: Cache blocks during iteration if ``True``.
             max_fetch_tasks: Limit background fetch task count.
+            with_metadata: Yield a :class:`KeyValueWithMetadata` per row instead of
+                ``(key, value)`` tuples if ``True``.
 
         Returns:
             :class:`DbIterator` over the requested range.
@@ -413,6 +532,7 @@ class SlateDB:
         read_ahead_bytes: int | None = None,
         cache_blocks: bool | None = None,
         max_fetch_tasks: int | None = None,
+        with_metadata: bool | None = None,
     ) -> DbIterator:
         """
         Async variant of ``scan_with_options``.
@@ -425,6 +545,8 @@ class SlateDB:
             read_ahead_bytes: Read-ahead size hint.
             cache_blocks: Cache blocks during iteration if ``True``.
             max_fetch_tasks: Limit background fetch task count.
+            with_metadata: Yield a :class:`KeyValueWithMetadata` per row instead of
+                ``(key, value)`` tuples if ``True``.
 
         Returns:
             :class:`DbIterator` over the requested range that supports ``async for``.
@@ -801,6 +923,41 @@ class SlateDBSnapshot:
         """
         ...
 
+    def get_with_metadata(self, key: bytes) -> KeyValueWithMetadata | None:
+        """
+        Get a value from the snapshot by key, along with the sequence number and
+        timestamps of the row it was read from.
+
+        Args:
+            key: Non-empty key.
+
+        Returns:
+            :class:`KeyValueWithMetadata` or ``None`` if not found.
+
+        Examples:
+            >>> row = snap.get_with_metadata(b"k")
+            >>> row["value"], row["seq"]
+            (b'v', 1)
+        """
+        ...
+
+    async def get_with_metadata_async(self, key: bytes) -> KeyValueWithMetadata | None:
+        """
+        Async variant of ``get_with_metadata``.
+
+        Args:
+            key: Non-empty key.
+
+        Returns:
+            :class:`KeyValueWithMetadata` or ``None`` if not found.
+
+        Examples:
+            >>> row = await snap.get_with_metadata_async(b"k")
+            >>> row["create_ts"]
+            1700000000000
+        """
+        ...
+
     def scan(self, start: bytes, end: bytes | None = None) -> DbIterator:
         """
         Iterate a range within the snapshot.
@@ -851,6 +1008,7 @@ class SlateDBSnapshot:
         read_ahead_bytes: int | None = None,
         cache_blocks: bool | None = None,
         max_fetch_tasks: int | None = None,
+        with_metadata: bool | None = None,
     ) -> DbIterator:
         """
         Iterate a range with advanced options within the snapshot.
@@ -863,6 +1021,8 @@ class SlateDBSnapshot:
             read_ahead_bytes: Read-ahead size hint.
             cache_blocks: Cache blocks during iteration if ``True``.
             max_fetch_tasks: Limit background fetch task count.
+            with_metadata: Yield a :class:`KeyValueWithMetadata` per row instead of
+                ``(key, value)`` tuples if ``True``.
 
         Returns:
             :class:`DbIterator` over the requested range.
@@ -886,6 +1046,7 @@ class SlateDBSnapshot:
         read_ahead_bytes: int | None = None,
         cache_blocks: bool | None = None,
         max_fetch_tasks: int | None = None,
+        with_metadata: bool | None = None,
     ) -> DbIterator:
         """
         Async variant of ``scan_with_options``.
@@ -898,6 +1059,8 @@ class SlateDBSnapshot:
             read_ahead_bytes: Read-ahead size hint.
             cache_blocks: Cache blocks during iteration if ``True``.
             max_fetch_tasks: Limit background fetch task count.
+            with_metadata: Yield a :class:`KeyValueWithMetadata` per row instead of
+                ``(key, value)`` tuples if ``True``.
 
         Returns:
             :class:`DbIterator` over the requested range for async iteration.
@@ -1385,6 +1548,57 @@ class SlateDBReader:
         """
         ...
 
+    def get_with_metadata(
+        self,
+        key: bytes,
+        *,
+        durability_filter: Literal["remote", "memory"] | None = None,
+        dirty: bool | None = None,
+    ) -> KeyValueWithMetadata | None:
+        """
+        Get a value from the database by key, along with the sequence number and
+        timestamps of the row it was read from.
+
+        Args:
+            key: Non-empty key.
+            durability_filter: Restrict sources ("remote" or "memory").
+            dirty: Include uncommitted/dirty data if True.
+
+        Returns:
+            :class:`KeyValueWithMetadata` or ``None`` if not found.
+
+        Examples:
+            >>> row = reader.get_with_metadata(b"k")
+            >>> row["value"], row["seq"]
+            (b'v', 1)
+        """
+        ...
+
+    async def get_with_metadata_async(
+        self,
+        key: bytes,
+        *,
+        durability_filter: Literal["remote", "memory"] | None = None,
+        dirty: bool | None = None,
+    ) -> KeyValueWithMetadata | None:
+        """
+        Async variant of ``get_with_metadata``.
+
+        Args:
+            key: Non-empty key.
+            durability_filter: Restrict sources ("remote" or "memory").
+            dirty: Include uncommitted/dirty data if True.
+
+        Returns:
+            :class:`KeyValueWithMetadata` or ``None`` if not found.
+
+        Examples:
+            >>> row = await reader.get_with_metadata_async(b"k")
+            >>> row["create_ts"]
+            1700000000000
+        """
+        ...
+
     def scan(self, start: bytes, end: bytes | None = None) -> DbIterator:
         """
         Iterate a range using the reader.
@@ -1434,6 +1648,7 @@ class SlateDBReader:
         read_ahead_bytes: int | None = None,
         cache_blocks: bool | None = None,
         max_fetch_tasks: int | None = None,
+        with_metadata: bool | None = None,
     ) -> DbIterator:
         """
         Iterate a range with advanced options using the reader.
@@ -1446,6 +1661,8 @@ class SlateDBReader:
             read_ahead_bytes: Read-ahead size hint.
             cache_blocks: Cache blocks during iteration if ``True``.
             max_fetch_tasks: Limit background fetch task count.
+            with_metadata: Yield a :class:`KeyValueWithMetadata` per row instead of
+                ``(key, value)`` tuples if ``True``.
 
         Returns:
             :class:`DbIterator` over the requested range.
@@ -1469,6 +1686,7 @@ class SlateDBReader:
         read_ahead_bytes: int | None = None,
         cache_blocks: bool | None = None,
         max_fetch_tasks: int | None = None,
+        with_metadata: bool | None = None,
     ) -> DbIterator:
         """
         Async variant of ``scan_with_options``.
@@ -1481,6 +1699,8 @@ class SlateDBReader:
             read_ahead_bytes: Read-ahead size hint.
             cache_blocks: Cache blocks during iteration if ``True``.
             max_fetch_tasks: Limit background fetch task count.
+            with_metadata: Yield a :class:`KeyValueWithMetadata` per row instead of
+                ``(key, value)`` tuples if ``True``.
 
         Returns:
             :class:`DbIterator` over the requested range for async iteration.
@@ -1624,6 +1844,16 @@ class CheckpointCreateResult(TypedDict):
     manifest_id: int
 
 
+class KeyValueWithMetadata(TypedDict):
+    """A value returned by ``get_with_metadata``, or by a scan with ``with_metadata=True``."""
+
+    key: bytes
+    value: bytes
+    seq: int
+    create_ts: int | None
+    expire_ts: int | None
+
+
 class WriteBatch:
     """Accumulates atomic write operations for :class:`SlateDB`.write(...)."""
 
@@ -1706,6 +1936,23 @@ class WriteBatch:
         """
         ...
 
+    def add_precondition(self, key: bytes, expected: bytes | None = None) -> None:
+        """
+        Require a condition on the key's current value for the batch to be written.
+
+        The batch fails with ``PreconditionError`` and none of its writes are applied
+        if the condition does not hold.
+
+        Args:
+            key: Non-empty key.
+            expected: Value the key must currently have, or ``None`` to require that
+                the key does not exist.
+
+        Examples:
+            >>> wb = WriteBatch(); wb.add_precondition(b"k"); wb.put(b"k", b"v")
+        """
+        ...
+
 class SlateDBAdmin:
     """Administrative interface for managing manifests, checkpoints, and GC."""
 
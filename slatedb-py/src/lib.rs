@@ -27,8 +27,10 @@ use ::slatedb::DbReader;
 use ::slatedb::DbSnapshot;
 use ::slatedb::Error;
 use ::slatedb::IsolationLevel;
+use ::slatedb::KeyValueWithMetadata;
 use ::slatedb::MergeOperator;
 use ::slatedb::MergeOperatorError;
+use ::slatedb::Precondition;
 use chrono::{DateTime, Utc};
 use once_cell::sync::OnceCell;
 use pyo3::create_exception;
@@ -51,6 +53,7 @@ fn get_runtime() -> &'static Runtime {
 
 // Python exception types mirroring slatedb::Error kinds
 create_exception!(slatedb, TransactionError, PyException);
+create_exception!(slatedb, PreconditionError, PyException);
 create_exception!(slatedb, ClosedError, PyException);
 create_exception!(slatedb, UnavailableError, PyException);
 create_exception!(slatedb, InvalidError, PyException);
@@ -63,6 +66,7 @@ fn map_error(err: Error) -> PyErr {
     let msg = format!("{}\nBacktrace:\n{}", err, bt);
     match err.kind() {
         ::slatedb::ErrorKind::Transaction => TransactionError::new_err(msg),
+        ::slatedb::ErrorKind::Precondition => PreconditionError::new_err(msg),
         ::slatedb::ErrorKind::Closed(_) => ClosedError::new_err(msg),
         ::slatedb::ErrorKind::Unavailable => UnavailableError::new_err(msg),
         ::slatedb::ErrorKind::Invalid => InvalidError::new_err(msg),
@@ -188,6 +192,7 @@ fn slatedb(py: Python<'_>, m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<PyDbIterator>()?;
     // Export exception types
     m.add("TransactionError", py.get_type::<TransactionError>())?;
+    m.add("PreconditionError", py.get_type::<PreconditionError>())?;
     m.add("ClosedError", py.get_type::<ClosedError>())?;
     m.add("UnavailableError", py.get_type::<UnavailableError>())?;
     m.add("InvalidError", py.get_type::<InvalidError>())?;
@@ -318,6 +323,18 @@ fn build_read_options(
     Ok(opts)
 }
 
+/// Converts a row read with its metadata into a dict with `key`, `value`, `seq`,
+/// `create_ts` and `expire_ts` items.
+fn row_to_dict<'py>(py: Python<'py>, row: &KeyValueWithMetadata) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new(py);
+    dict.set_item("key", PyBytes::new(py, &row.key))?;
+    dict.set_item("value", PyBytes::new(py, &row.value))?;
+    dict.set_item("seq", row.seq)?;
+    dict.set_item("create_ts", row.create_ts)?;
+    dict.set_item("expire_ts", row.expire_ts)?;
+    Ok(dict)
+}
+
 fn build_put_options(ttl: Option<u64>) -> PutOptions {
     PutOptions {
         ttl: match ttl {
@@ -339,6 +356,7 @@ fn build_merge_options(ttl: Option<u64>) -> MergeOptions {
 fn build_write_options(await_durable: Option<bool>) -> WriteOptions {
     WriteOptions {
         await_durable: await_durable.unwrap_or(true),
+        ..WriteOptions::default()
     }
 }
 
@@ -512,6 +530,75 @@ impl PySlateDB {
         py.allow_threads(|| rt.block_on(async { db.put(&key, &value).await.map_err(map_error) }))
     }
 
+    #[pyo3(signature = (key, value))]
+    fn put_if_absent(&self, py: Python<'_>, key: Vec<u8>, value: Vec<u8>) -> PyResult<()> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let db = self.inner.clone();
+        let rt = get_runtime();
+        py.allow_threads(|| {
+            rt.block_on(async { db.put_if_absent(&key, &value).await.map_err(map_error) })
+        })
+    }
+
+    #[pyo3(signature = (key, value))]
+    fn put_if_absent_async<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+        value: Vec<u8>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let db = self.inner.clone();
+        future_into_py(py, async move {
+            db.put_if_absent(&key, &value).await.map_err(map_error)
+        })
+    }
+
+    #[pyo3(signature = (key, expected, value))]
+    fn compare_and_set(
+        &self,
+        py: Python<'_>,
+        key: Vec<u8>,
+        expected: Vec<u8>,
+        value: Vec<u8>,
+    ) -> PyResult<()> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let db = self.inner.clone();
+        let rt = get_runtime();
+        py.allow_threads(|| {
+            rt.block_on(async {
+                db.compare_and_set(&key, &expected, &value)
+                    .await
+                    .map_err(map_error)
+            })
+        })
+    }
+
+    #[pyo3(signature = (key, expected, value))]
+    fn compare_and_set_async<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+        expected: Vec<u8>,
+        value: Vec<u8>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let db = self.inner.clone();
+        future_into_py(py, async move {
+            db.compare_and_set(&key, &expected, &value)
+                .await
+                .map_err(map_error)
+        })
+    }
+
     #[pyo3(signature = (key))]
     fn get<'py>(&self, py: Python<'py>, key: Vec<u8>) -> PyResult<Option<Bound<'py, PyBytes>>> {
         if key.is_empty() {
@@ -557,6 +644,55 @@ impl PySlateDB {
         Ok(res.map(|b| PyBytes::new(py, &b)))
     }
 
+    #[pyo3(signature = (key, *, durability_filter = None, dirty = None))]
+    fn get_with_metadata<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+        durability_filter: Option<String>,
+        dirty: Option<bool>,
+    ) -> PyResult<Option<Bound<'py, PyDict>>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let db = self.inner.clone();
+        let opts = build_read_options(durability_filter, dirty)?;
+        let rt = get_runtime();
+        let row = py.allow_threads(|| {
+            rt.block_on(async {
+                db.get_with_metadata_with_options(&key, &opts)
+                    .await
+                    .map_err(map_error)
+            })
+        })?;
+        row.map(|row| row_to_dict(py, &row)).transpose()
+    }
+
+    #[pyo3(signature = (key, *, durability_filter = None, dirty = None))]
+    fn get_with_metadata_async<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+        durability_filter: Option<String>,
+        dirty: Option<bool>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let db = self.inner.clone();
+        let opts = build_read_options(durability_filter, dirty)?;
+        future_into_py(py, async move {
+            let row = db
+                .get_with_metadata_with_options(&key, &opts)
+                .await
+                .map_err(map_error)?;
+            Python::with_gil(|py| match row {
+                Some(row) => Ok(row_to_dict(py, &row)?.into_any().unbind()),
+                None => Ok(py.None()),
+            })
+        })
+    }
+
     #[pyo3(signature = (start, end = None))]
     fn scan(&self, start: Vec<u8>, end: Option<Vec<u8>>) -> PyResult<PyDbIterator> {
         if start.is_empty() {
@@ -607,7 +743,7 @@ impl PySlateDB {
         py.allow_threads(|| rt.block_on(async { db.delete(&key).await.map_err(map_error) }))
     }
 
-    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None))]
+    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None, with_metadata = None))]
     fn scan_with_options(
         &self,
         start: Vec<u8>,
@@ -617,6 +753,7 @@ impl PySlateDB {
         read_ahead_bytes: Option<usize>,
         cache_blocks: Option<bool>,
         max_fetch_tasks: Option<usize>,
+        with_metadata: Option<bool>,
     ) -> PyResult<PyDbIterator> {
         if start.is_empty() {
             return Err(InvalidError::new_err("start cannot be empty"));
@@ -640,10 +777,13 @@ impl PySlateDB {
                 .await
                 .map_err(map_error)
         })?;
-        Ok(PyDbIterator::from_iter(iter))
+        Ok(PyDbIterator::from_iter_with_metadata(
+            iter,
+            with_metadata.unwrap_or(false),
+        ))
     }
 
-    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None))]
+    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None, with_metadata = None))]
     fn scan_with_options_async<'py>(
         &self,
         py: Python<'py>,
@@ -654,6 +794,7 @@ impl PySlateDB {
         read_ahead_bytes: Option<usize>,
         cache_blocks: Option<bool>,
         max_fetch_tasks: Option<usize>,
+        with_metadata: Option<bool>,
     ) -> PyResult<Bound<'py, PyAny>> {
         if start.is_empty() {
             return Err(InvalidError::new_err("start cannot be empty"));
@@ -676,7 +817,10 @@ impl PySlateDB {
                 .scan_with_options(start..end, &opts)
                 .await
                 .map_err(map_error)?;
-            Ok(PyDbIterator::from_iter(iter))
+            Ok(PyDbIterator::from_iter_with_metadata(
+                iter,
+                with_metadata.unwrap_or(false),
+            ))
         })
     }
 
@@ -1163,6 +1307,42 @@ impl PySlateDBSnapshot {
         })
     }
 
+    #[pyo3(signature = (key))]
+    fn get_with_metadata<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+    ) -> PyResult<Option<Bound<'py, PyDict>>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let snapshot = self.inner_ref()?;
+        let rt = get_runtime();
+        let row = py.allow_threads(|| {
+            rt.block_on(async { snapshot.get_with_metadata(&key).await.map_err(map_error) })
+        })?;
+        row.map(|row| row_to_dict(py, &row)).transpose()
+    }
+
+    #[pyo3(signature = (key))]
+    fn get_with_metadata_async<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let snapshot = self.inner_ref()?;
+        future_into_py(py, async move {
+            let row = snapshot.get_with_metadata(&key).await.map_err(map_error)?;
+            Python::with_gil(|py| match row {
+                Some(row) => Ok(row_to_dict(py, &row)?.into_any().unbind()),
+                None => Ok(py.None()),
+            })
+        })
+    }
+
     #[pyo3(signature = (start, end = None))]
     fn scan(&self, start: Vec<u8>, end: Option<Vec<u8>>) -> PyResult<PyDbIterator> {
         if start.is_empty() {
@@ -1201,7 +1381,7 @@ impl PySlateDBSnapshot {
         })
     }
 
-    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None))]
+    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None, with_metadata = None))]
     fn scan_with_options(
         &self,
         start: Vec<u8>,
@@ -1211,6 +1391,7 @@ impl PySlateDBSnapshot {
         read_ahead_bytes: Option<usize>,
         cache_blocks: Option<bool>,
         max_fetch_tasks: Option<usize>,
+        with_metadata: Option<bool>,
     ) -> PyResult<PyDbIterator> {
         if start.is_empty() {
             return Err(InvalidError::new_err("start cannot be empty"));
@@ -1235,10 +1416,13 @@ impl PySlateDBSnapshot {
                 .await
                 .map_err(map_error)
         })?;
-        Ok(PyDbIterator::from_iter(iter))
+        Ok(PyDbIterator::from_iter_with_metadata(
+            iter,
+            with_metadata.unwrap_or(false),
+        ))
     }
 
-    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None))]
+    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None, with_metadata = None))]
     fn scan_with_options_async<'py>(
         &self,
         py: Python<'py>,
@@ -1249,6 +1433,7 @@ impl PySlateDBSnapshot {
         read_ahead_bytes: Option<usize>,
         cache_blocks: Option<bool>,
         max_fetch_tasks: Option<usize>,
+        with_metadata: Option<bool>,
     ) -> PyResult<Bound<'py, PyAny>> {
         if start.is_empty() {
             return Err(InvalidError::new_err("start cannot be empty"));
@@ -1271,7 +1456,10 @@ impl PySlateDBSnapshot {
                 .scan_with_options(start..end, &opts)
                 .await
                 .map_err(map_error)?;
-            Ok(PyDbIterator::from_iter(iter))
+            Ok(PyDbIterator::from_iter_with_metadata(
+                iter,
+                with_metadata.unwrap_or(false),
+            ))
         })
     }
 
@@ -1365,6 +1553,19 @@ impl PyWriteBatch {
         self.inner.merge_with_options(&key, &value, &opts);
         Ok(())
     }
+
+    #[pyo3(signature = (key, expected = None))]
+    fn add_precondition(&mut self, key: Vec<u8>, expected: Option<Vec<u8>>) -> PyResult<()> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let precondition = match expected {
+            Some(value) => Precondition::ValueEquals(value.into()),
+            None => Precondition::Absent,
+        };
+        self.inner.add_precondition(&key, precondition);
+        Ok(())
+    }
 }
 
 #[pymethods]
@@ -1807,6 +2008,56 @@ impl PySlateDBReader {
         Ok(res.map(|b| PyBytes::new(py, &b)))
     }
 
+    #[pyo3(signature = (key, *, durability_filter = None, dirty = None))]
+    fn get_with_metadata<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+        durability_filter: Option<String>,
+        dirty: Option<bool>,
+    ) -> PyResult<Option<Bound<'py, PyDict>>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let reader = self.inner.clone();
+        let opts = build_read_options(durability_filter, dirty)?;
+        let rt = get_runtime();
+        let row = py.allow_threads(|| {
+            rt.block_on(async {
+                reader
+                    .get_with_metadata_with_options(&key, &opts)
+                    .await
+                    .map_err(map_error)
+            })
+        })?;
+        row.map(|row| row_to_dict(py, &row)).transpose()
+    }
+
+    #[pyo3(signature = (key, *, durability_filter = None, dirty = None))]
+    fn get_with_metadata_async<'py>(
+        &self,
+        py: Python<'py>,
+        key: Vec<u8>,
+        durability_filter: Option<String>,
+        dirty: Option<bool>,
+    ) -> PyResult<Bound<'py, PyAny>> {
+        if key.is_empty() {
+            return Err(InvalidError::new_err("key cannot be empty"));
+        }
+        let reader = self.inner.clone();
+        let opts = build_read_options(durability_filter, dirty)?;
+        future_into_py(py, async move {
+            let row = reader
+                .get_with_metadata_with_options(&key, &opts)
+                .await
+                .map_err(map_error)?;
+            Python::with_gil(|py| match row {
+                Some(row) => Ok(row_to_dict(py, &row)?.into_any().unbind()),
+                None => Ok(py.None()),
+            })
+        })
+    }
+
     #[pyo3(signature = (key, *, durability_filter = None, dirty = None))]
     fn get_with_options_async<'py>(
         &self,
@@ -1868,7 +2119,7 @@ impl PySlateDBReader {
         })
     }
 
-    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None))]
+    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None, with_metadata = None))]
     fn scan_with_options(
         &self,
         start: Vec<u8>,
@@ -1878,6 +2129,7 @@ impl PySlateDBReader {
         read_ahead_bytes: Option<usize>,
         cache_blocks: Option<bool>,
         max_fetch_tasks: Option<usize>,
+        with_metadata: Option<bool>,
     ) -> PyResult<PyDbIterator> {
         if start.is_empty() {
             return Err(InvalidError::new_err("start cannot be empty"));
@@ -1902,10 +2154,13 @@ impl PySlateDBReader {
                 .await
                 .map_err(map_error)
         })?;
-        Ok(PyDbIterator::from_iter(iter))
+        Ok(PyDbIterator::from_iter_with_metadata(
+            iter,
+            with_metadata.unwrap_or(false),
+        ))
     }
 
-    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None))]
+    #[pyo3(signature = (start, end = None, *, durability_filter = None, dirty = None, read_ahead_bytes = None, cache_blocks = None, max_fetch_tasks = None, with_metadata = None))]
     fn scan_with_options_async<'py>(
         &self,
         py: Python<'py>,
@@ -1916,6 +2171,7 @@ impl PySlateDBReader {
         read_ahead_bytes: Option<usize>,
         cache_blocks: Option<bool>,
         max_fetch_tasks: Option<usize>,
+        with_metadata: Option<bool>,
     ) -> PyResult<Bound<'py, PyAny>> {
         if start.is_empty() {
             return Err(InvalidError::new_err("start cannot be empty"));
@@ -1938,7 +2194,10 @@ impl PySlateDBReader {
                 .scan_with_options(start..end, &opts)
                 .await
                 .map_err(map_error)?;
-            Ok(PyDbIterator::from_iter(iter))
+            Ok(PyDbIterator::from_iter_with_metadata(
+                iter,
+                with_metadata.unwrap_or(false),
+            ))
         })
     }
 
@@ -2450,16 +2709,38 @@ impl PySlateDBAdmin {
 #[pyclass(name = "DbIterator")]
 pub struct PyDbIterator {
     inner_iter: Arc<Mutex<Option<::slatedb::DbIterator>>>,
+    /// Whether to yield dicts with the metadata of each row instead of (key, value) tuples.
+    with_metadata: bool,
 }
 
 impl PyDbIterator {
     fn from_iter(iter: ::slatedb::DbIterator) -> Self {
+        Self::from_iter_with_metadata(iter, false)
+    }
+
+    fn from_iter_with_metadata(iter: ::slatedb::DbIterator, with_metadata: bool) -> Self {
         Self {
             inner_iter: Arc::new(Mutex::new(Some(iter))),
+            with_metadata,
         }
     }
 }
 
+/// Converts a row into the item yielded by a [`PyDbIterator`].
+fn row_to_item(
+    py: Python<'_>,
+    row: &KeyValueWithMetadata,
+    with_metadata: bool,
+) -> PyResult<PyObject> {
+    if with_metadata {
+        return Ok(row_to_dict(py, row)?.into_any().unbind());
+    }
+    let key = PyBytes::new(py, &row.key);
+    let value = PyBytes::new(py, &row.value);
+    let tuple = PyTuple::new(py, vec![key, value])?;
+    Ok(tuple.into())
+}
+
 #[pymethods]
 impl PyDbIterator {
     fn __iter__(slf: PyRefMut<'_, Self>) -> PyRefMut<'_, Self> {
@@ -2475,17 +2756,12 @@ impl PyDbIterator {
                 let iter = guard
                     .as_mut()
                     .ok_or_else(|| InternalError::new_err("iterator not initialized"))?;
-                let next = iter.next().await.map_err(map_error)?;
+                let next = iter.next_with_metadata().await.map_err(map_error)?;
                 Ok::<_, PyErr>(next)
             })
         })?;
         match kv_opt {
-            Some(kv) => {
-                let key = PyBytes::new(py, &kv.key);
-                let value = PyBytes::new(py, &kv.value);
-                let tuple = PyTuple::new(py, vec![key, value])?;
-                Ok(tuple.into())
-            }
+            Some(kv) => row_to_item(py, &kv, self.with_metadata),
             None => Err(pyo3::exceptions::PyStopIteration::new_err("")),
         }
     }
@@ -2497,19 +2773,15 @@ impl PyDbIterator {
 
     fn __anext__<'py>(&mut self, py: Python<'py>) -> PyResult<Bound<'py, PyAny>> {
         let inner = self.inner_iter.clone();
+        let with_metadata = self.with_metadata;
         future_into_py::<_, PyObject>(py, async move {
             let mut guard = inner.lock().await;
             let iter = guard
                 .as_mut()
                 .ok_or_else(|| InternalError::new_err("iterator not initialized"))?;
-            let kv_opt = iter.next().await.map_err(map_error)?;
+            let kv_opt = iter.next_with_metadata().await.map_err(map_error)?;
             match kv_opt {
-                Some(kv) => Python::with_gil(|py| {
-                    let key = PyBytes::new(py, &kv.key);
-                    let value = PyBytes::new(py, &kv.value);
-                    let tuple = PyTuple::new(py, vec![key, value])?;
-                    Ok::<PyObject, PyErr>(tuple.into())
-                }),
+                Some(kv) => Python::with_gil(|py| row_to_item(py, &kv, with_metadata)),
                 None => Err(pyo3::exceptions::PyStopAsyncIteration::new_err("")),
             }
         })
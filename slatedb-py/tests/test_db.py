@@ -5,6 +5,7 @@ import pytest
 from slatedb import (
     ClosedError,
     InvalidError,
+    PreconditionError,
     SlateDB,
     TransactionError,
     UnavailableError,
@@ -506,6 +507,33 @@ def test_db_get_with_options(db_path, env_file):
     finally:
         db.close()
 
+def test_db_get_and_scan_with_metadata(db_path, env_file):
+    db = SlateDB(db_path, env_file=env_file)
+    try:
+        db.put(b"m1", b"v1")
+        db.put_with_options(b"m2", b"v2", ttl=60_000)
+
+        row1 = db.get_with_metadata(b"m1")
+        assert row1["key"] == b"m1"
+        assert row1["value"] == b"v1"
+        assert row1["create_ts"] is not None
+        assert row1["expire_ts"] is None
+        row2 = db.get_with_metadata(b"m2", durability_filter="memory")
+        assert row2["seq"] > row1["seq"]
+        assert row2["expire_ts"] == row2["create_ts"] + 60_000
+        assert db.get_with_metadata(b"missing") is None
+
+        rows = list(db.scan_with_options(b"m", with_metadata=True))
+        assert rows == [row1, row2]
+        # scans still yield tuples by default
+        assert list(db.scan_with_options(b"m")) == [(b"m1", b"v1"), (b"m2", b"v2")]
+
+        snapshot = db.snapshot()
+        assert snapshot.get_with_metadata(b"m1") == row1
+        snapshot.close()
+    finally:
+        db.close()
+
 def test_txn_scan_with_options_sync(db_path, env_file):
     db = SlateDB(db_path, env_file=env_file)
     try:
@@ -668,6 +696,59 @@ def test_db_write_batch_and_write_with_options(db_path, env_file):
     finally:
         db.close()
 
+def test_db_put_if_absent_and_compare_and_set(db_path, env_file):
+    db = SlateDB(db_path, env_file=env_file)
+    try:
+        db.put_if_absent(b"c1", b"v1")
+        with pytest.raises(PreconditionError):
+            db.put_if_absent(b"c1", b"v2")
+        assert db.get(b"c1") == b"v1"
+
+        with pytest.raises(PreconditionError):
+            db.compare_and_set(b"c1", b"v0", b"v2")
+        db.compare_and_set(b"c1", b"v1", b"v2")
+        assert db.get(b"c1") == b"v2"
+    finally:
+        db.close()
+
+
+@pytest.mark.asyncio
+async def test_db_put_if_absent_and_compare_and_set_async(db_path, env_file):
+    db = SlateDB(db_path, env_file=env_file)
+    try:
+        await db.put_if_absent_async(b"ca1", b"v1")
+        with pytest.raises(PreconditionError):
+            await db.put_if_absent_async(b"ca1", b"v2")
+        await db.compare_and_set_async(b"ca1", b"v1", b"v2")
+        assert await db.get_async(b"ca1") == b"v2"
+    finally:
+        await db.close_async()
+
+
+def test_db_write_batch_with_preconditions(db_path, env_file):
+    db = SlateDB(db_path, env_file=env_file)
+    try:
+        db.put(b"p1", b"v1")
+
+        wb = WriteBatch()
+        wb.add_precondition(b"p1", b"v1")
+        wb.add_precondition(b"p2")
+        wb.put(b"p1", b"v2")
+        wb.put(b"p2", b"v2")
+        db.write(wb)
+        assert db.get(b"p1") == b"v2"
+        assert db.get(b"p2") == b"v2"
+
+        wb2 = WriteBatch()
+        wb2.add_precondition(b"p2")
+        wb2.delete(b"p1")
+        with pytest.raises(PreconditionError):
+            db.write(wb2)
+        assert db.get(b"p1") == b"v2"
+    finally:
+        db.close()
+
+
 def test_db_write_batch_put_with_options_and_merges(db_path, env_file):
     def concat(key, existing, value):
         return (existing or b"") + value
@@ -1,28 +1,47 @@
+use crate::block_iterator::BlockIterator;
+use crate::bytes_range::BytesRange;
+use crate::change_stream::ChangeValue;
 use crate::checkpoint::{Checkpoint, CheckpointCreateResult};
 use crate::clock::SystemClock;
+use crate::column_family::{self, DEFAULT_COLUMN_FAMILY_ID};
+use crate::compactions_store::{CompactionsStore, StoredCompactions};
+use crate::compactor_state::{Compaction, CompactionSpec, SourceId};
+use crate::comparator::KeyComparator;
 use crate::config::{CheckpointOptions, GarbageCollectorOptions};
 use crate::db::builder::GarbageCollectorBuilder;
+use crate::db_state::{CoreDbState, SsTableHandle, SsTableId};
 use crate::dispatcher::MessageHandlerExecutor;
+use crate::encryption::EncryptionProviderType;
 use crate::error::SlateDBError;
 use crate::garbage_collector::GC_TASK_NAME;
+use crate::iter::{IterationOrder, KeyValueIterator};
 use crate::manifest::store::{ManifestStore, StoredManifest};
 
 use crate::clone;
 use crate::object_stores::{ObjectStoreType, ObjectStores};
+use crate::partitioned_keyspace;
 use crate::rand::DbRand;
 use crate::seq_tracker::FindOption;
+use crate::sst::SsTableFormat;
+use crate::sst_iter::{SstIterator, SstIteratorOptions};
+use crate::tablestore::TableStore;
+use crate::types::ValueDeletable;
 use crate::utils::{IdGenerator, WatchableOnceCell};
+use bytes::Bytes;
 use chrono::{DateTime, Utc};
 use fail_parallel::FailPointRegistry;
+use log::info;
 use object_store::path::Path;
 use object_store::ObjectStore;
+use std::collections::HashSet;
 use std::env;
 use std::error::Error;
-use std::ops::RangeBounds;
+use std::ops::{Bound, RangeBounds};
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::runtime::Handle;
 use tokio::sync::mpsc;
+use ulid::Ulid;
 use uuid::Uuid;
 
 pub use crate::db::builder::AdminBuilder;
@@ -42,6 +61,10 @@ pub struct Admin {
     pub(crate) system_clock: Arc<dyn SystemClock>,
     /// The random number generator to use for randomness.
     pub(crate) rand: Arc<DbRand>,
+    /// The order of the keys of the database.
+    pub(crate) comparator: KeyComparator,
+    /// The provider of the keys the database is encrypted with, if it's encrypted.
+    pub(crate) encryption_provider: Option<EncryptionProviderType>,
 }
 
 impl Admin {
@@ -50,10 +73,11 @@ impl Admin {
         &self,
         maybe_id: Option<u64>,
     ) -> Result<Option<String>, Box<dyn Error>> {
-        let manifest_store = ManifestStore::new(
+        let manifest_store = ManifestStore::new_with_encryption_provider(
             &self.path,
             self.object_stores.store_of(ObjectStoreType::Main).clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
         );
         let id_manifest = if let Some(id) = maybe_id {
             manifest_store
@@ -75,10 +99,11 @@ impl Admin {
         &self,
         range: R,
     ) -> Result<String, Box<dyn Error>> {
-        let manifest_store = ManifestStore::new(
+        let manifest_store = ManifestStore::new_with_encryption_provider(
             &self.path,
             self.object_stores.store_of(ObjectStoreType::Main).clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
         );
         let manifests = manifest_store.list_manifests(range).await?;
         Ok(serde_json::to_string(&manifests)?)
@@ -94,10 +119,11 @@ impl Admin {
         &self,
         name_filter: Option<&str>,
     ) -> Result<Vec<Checkpoint>, Box<dyn Error>> {
-        let manifest_store = ManifestStore::new(
+        let manifest_store = ManifestStore::new_with_encryption_provider(
             &self.path,
             self.object_stores.store_of(ObjectStoreType::Main).clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
         );
         let (_, manifest) = manifest_store.read_latest_manifest().await?;
 
@@ -132,14 +158,7 @@ impl Admin {
         &self,
         gc_opts: GarbageCollectorOptions,
     ) -> Result<(), Box<dyn Error>> {
-        let gc = GarbageCollectorBuilder::new(
-            self.path.clone(),
-            self.object_stores.store_of(ObjectStoreType::Main).clone(),
-        )
-        .with_system_clock(self.system_clock.clone())
-        .with_wal_object_store(self.object_stores.store_of(ObjectStoreType::Wal).clone())
-        .with_options(gc_opts)
-        .build();
+        let gc = self.gc_builder(gc_opts).build();
         gc.run_gc_once().await;
         Ok(())
     }
@@ -153,14 +172,7 @@ impl Admin {
     /// * `gc_opts`: The garbage collector options.
     ///
     pub async fn run_gc(&self, gc_opts: GarbageCollectorOptions) -> Result<(), crate::Error> {
-        let gc = GarbageCollectorBuilder::new(
-            self.path.clone(),
-            self.object_stores.store_of(ObjectStoreType::Main).clone(),
-        )
-        .with_system_clock(self.system_clock.clone())
-        .with_wal_object_store(self.object_stores.store_of(ObjectStoreType::Wal).clone())
-        .with_options(gc_opts)
-        .build();
+        let gc = self.gc_builder(gc_opts).build();
 
         let (_, rx) = mpsc::unbounded_channel();
         let closed_result = WatchableOnceCell::new();
@@ -222,10 +234,11 @@ impl Admin {
         &self,
         options: &CheckpointOptions,
     ) -> Result<CheckpointCreateResult, crate::Error> {
-        let manifest_store = Arc::new(ManifestStore::new(
+        let manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
             &self.path,
             self.object_stores.store_of(ObjectStoreType::Main).clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
         ));
         manifest_store
             .validate_no_wal_object_store_configured()
@@ -250,10 +263,11 @@ impl Admin {
         id: Uuid,
         lifetime: Option<Duration>,
     ) -> Result<(), crate::Error> {
-        let manifest_store = Arc::new(ManifestStore::new(
+        let manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
             &self.path,
             self.object_stores.store_of(ObjectStoreType::Main).clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
         ));
         let mut stored_manifest = StoredManifest::load(manifest_store).await?;
         stored_manifest
@@ -277,10 +291,11 @@ impl Admin {
 
     /// Deletes the checkpoint with the specified id.
     pub async fn delete_checkpoint(&self, id: Uuid) -> Result<(), crate::Error> {
-        let manifest_store = Arc::new(ManifestStore::new(
+        let manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
             &self.path,
             self.object_stores.store_of(ObjectStoreType::Main).clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
         ));
         let mut stored_manifest = StoredManifest::load(manifest_store).await?;
         stored_manifest
@@ -344,14 +359,268 @@ impl Admin {
         Ok(manifest.core.sequence_tracker.find_seq(ts, opt))
     }
 
+    /// Compacts the L0 SSTs and sorted runs of the default column family that overlap the
+    /// given key range, e.g. to reclaim the space of deleted keys and to speed up reads after
+    /// a bulk delete.
+    ///
+    /// The L0 SSTs and sorted runs that overlap the range are merged into the oldest sorted
+    /// run that overlaps it, or into a new sorted run if none does. Tombstones are only
+    /// dropped if it is the last sorted run. The other L0 SSTs and sorted runs are left
+    /// alone, except those the merged keys would otherwise move below: the L0 SSTs older than
+    /// an overlapping one, and the sorted runs in between that hold some of the merged keys.
+    ///
+    /// The compaction is submitted to the compactor of the database, which validates it like
+    /// the compactions it schedules itself, and starts it once it does not conflict with a
+    /// running compaction. A compactor must be running, either in the process of the
+    /// [`crate::Db`] or standalone: the admin doesn't run the compaction itself. This fn polls
+    /// the compactor's state every `poll_interval` and calls `on_progress` whenever the
+    /// progress changes, until the compactor has written the output of the compaction to the
+    /// manifest, or until `timeout` has passed, if set.
+    ///
+    /// ## Errors
+    /// - [`crate::ErrorKind::Unavailable`] if the compaction did not finish within `timeout`,
+    ///   e.g. because no compactor is running. If the compactor hasn't started it yet, the
+    ///   compaction is withdrawn. A running compaction keeps running.
+    /// - [`crate::ErrorKind::Invalid`] if the compactor rejected the compaction, e.g.
+    ///   because the compaction scheduler does not allow it.
+    /// - [`crate::ErrorKind::Invalid`] if the admin was not built with the comparator of
+    ///   the database.
+    /// - [`crate::ErrorKind::Internal`] if the compaction failed.
+    pub async fn compact_range<K, T, F>(
+        &self,
+        range: T,
+        poll_interval: Duration,
+        timeout: Option<Duration>,
+        mut on_progress: F,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        T: RangeBounds<K>,
+        F: FnMut(&CompactRangeProgress),
+    {
+        let range = BytesRange::from_ref_by(range, &self.comparator);
+        let manifest_store = self.manifest_store();
+        let (_, manifest) = manifest_store.read_latest_manifest().await?;
+        self.comparator
+            .check_matches(manifest.core.comparator.as_deref())?;
+        let spans = KeySpans::load(&self.table_store(), &manifest.core).await?;
+        let Some(spec) = compact_range_spec(&manifest.core, &spans, &range, &self.comparator)
+        else {
+            on_progress(&CompactRangeProgress::NothingToCompact);
+            return Ok(());
+        };
+        // the compaction replaces all SSTs of its sources in the manifest
+        let source_ssts: HashSet<SsTableId> = spec
+            .sources()
+            .iter()
+            .flat_map(|source| match source {
+                SourceId::Sst(id) => vec![SsTableId::Compacted(*id)],
+                SourceId::SortedRun(id) => manifest
+                    .core
+                    .compacted
+                    .iter()
+                    .filter(|sr| sr.id == *id)
+                    .flat_map(|sr| sr.ssts.iter().map(|sst| sst.id))
+                    .collect(),
+            })
+            .collect();
+
+        let id = self.rand.rng().gen_ulid(self.system_clock.as_ref());
+        let compactions_store = Arc::new(CompactionsStore::new_with_encryption_provider(
+            &self.path,
+            self.object_stores.store_of(ObjectStoreType::Main).clone(),
+            self.encryption_provider.clone(),
+        ));
+        let mut stored_compactions = StoredCompactions::load_or_create(compactions_store).await?;
+        info!("submitting compaction [id={}, spec={:?}]", id, spec);
+        stored_compactions
+            .submit_compaction(Compaction::new(id, spec))
+            .await?;
+        let mut progress = CompactRangeProgress::Submitted;
+        on_progress(&progress);
+
+        let started = self.system_clock.now();
+        loop {
+            self.system_clock.sleep(poll_interval).await;
+            let compactions = stored_compactions.refresh().await?;
+            let next_progress = if compactions.submitted.iter().any(|c| c.id() == id) {
+                CompactRangeProgress::Submitted
+            } else if let Some(compaction) = compactions.compactions.iter().find(|c| c.id() == id) {
+                CompactRangeProgress::Running {
+                    output_ssts: compaction.output_ssts().len(),
+                }
+            } else {
+                break;
+            };
+            if next_progress != progress {
+                progress = next_progress;
+                on_progress(&progress);
+            }
+            if let Some(timeout) = timeout {
+                if self.system_clock.now() >= started + timeout {
+                    stored_compactions.withdraw_compaction(id).await?;
+                    return Err(SlateDBError::CompactionTimeout { id, timeout }.into());
+                }
+            }
+        }
+
+        // the compactor removes a finished compaction only after writing its output to the
+        // manifest, so the sources are gone unless the compaction was rejected or failed
+        let (manifest_id, manifest) = manifest_store.read_latest_manifest().await?;
+        let sources_remain = manifest
+            .core
+            .l0
+            .iter()
+            .chain(manifest.core.compacted.iter().flat_map(|sr| sr.ssts.iter()))
+            .any(|sst| source_ssts.contains(&sst.id));
+        if sources_remain {
+            return Err(match progress {
+                CompactRangeProgress::Running { .. } => SlateDBError::CompactionJobFailed {
+                    id,
+                    message: "compaction ended without writing its output to the manifest"
+                        .to_string(),
+                },
+                _ => SlateDBError::InvalidCompaction,
+            }
+            .into());
+        }
+        on_progress(&CompactRangeProgress::Finished { manifest_id });
+        Ok(())
+    }
+
+    /// Lists every version of `key` in the default column family that the database still
+    /// retains, including tombstones and merge operands that haven't been merged yet. This
+    /// is meant for debugging, e.g. to find out why a read returns an unexpected value.
+    ///
+    /// Each layer of the latest manifest is walked separately, and the versions are
+    /// returned in the order reads see them: WAL SSTs from newest to oldest, then L0 SSTs
+    /// from newest to oldest, then sorted runs from newest to oldest. The admin client
+    /// can't see the memtables of a running writer, so the WAL SSTs that haven't been
+    /// flushed to L0 yet stand in for them. Writes made with the WAL disabled are only
+    /// listed once their memtable is flushed.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to list the versions of
+    ///
+    /// ## Returns
+    /// - `Vec<KeyVersion>`: the versions of the key, along with the SST and block each one
+    ///   was found in
+    ///
+    /// ## Errors
+    /// - [`crate::ErrorKind::Invalid`] if the admin was not built with the comparator of
+    ///   the database.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::admin::Admin;
+    /// use slatedb::Db;
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::error::Error;
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Box<dyn Error>> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", Arc::clone(&object_store)).await?;
+    ///     db.put(b"key", b"value1").await?;
+    ///     db.put(b"key", b"value2").await?;
+    ///     db.close().await?;
+    ///
+    ///     let admin = Admin::builder("test_db", object_store).build();
+    ///     for version in admin.key_history(b"key").await? {
+    ///         println!("{:?}", version);
+    ///     }
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn key_history<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+    ) -> Result<Vec<KeyVersion>, crate::Error> {
+        let key = Bytes::copy_from_slice(key.as_ref());
+        let (_, manifest) = self.manifest_store().read_latest_manifest().await?;
+        self.comparator
+            .check_matches(manifest.core.comparator.as_deref())?;
+        let table_store = self.table_store();
+
+        let mut versions = Vec::new();
+        let wal_ssts = table_store
+            .list_wal_ssts(manifest.core.replay_after_wal_id + 1..)
+            .await?;
+        for wal_sst in wal_ssts.iter().rev() {
+            let sst = table_store.open_sst(&wal_sst.id).await?;
+            // the keys of WAL SSTs are prefixed with their column family id if the
+            // database has column families
+            let sst_key = if sst.info.column_family_keys {
+                column_family::encode_key(DEFAULT_COLUMN_FAMILY_ID, &key)
+            } else {
+                key.clone()
+            };
+            let layer = KeyVersionLayer::Wal {
+                wal_id: sst.id.unwrap_wal_id(),
+            };
+            versions.extend(key_versions_in_sst(&table_store, &sst, &sst_key, layer).await?);
+        }
+        for sst in &manifest.core.l0 {
+            let layer = KeyVersionLayer::L0 {
+                sst_id: sst.id.unwrap_compacted_id(),
+            };
+            versions.extend(key_versions_in_sst(&table_store, sst, &key, layer).await?);
+        }
+        for sr in &manifest.core.compacted {
+            if let Some(sst) = sr.find_sst_with_range_covering_key(&key, &self.comparator) {
+                let layer = KeyVersionLayer::SortedRun {
+                    sorted_run_id: sr.id,
+                    sst_id: sst.id.unwrap_compacted_id(),
+                };
+                versions.extend(key_versions_in_sst(&table_store, sst, &key, layer).await?);
+            }
+        }
+        Ok(versions)
+    }
+
+    fn gc_builder(&self, gc_opts: GarbageCollectorOptions) -> GarbageCollectorBuilder<Path> {
+        let builder = GarbageCollectorBuilder::new(
+            self.path.clone(),
+            self.object_stores.store_of(ObjectStoreType::Main).clone(),
+        )
+        .with_system_clock(self.system_clock.clone())
+        .with_wal_object_store(self.object_stores.store_of(ObjectStoreType::Wal).clone())
+        .with_options(gc_opts);
+        match &self.encryption_provider {
+            Some(encryption_provider) => {
+                builder.with_encryption_provider(encryption_provider.clone())
+            }
+            None => builder,
+        }
+    }
+
     fn manifest_store(&self) -> ManifestStore {
-        ManifestStore::new(
+        ManifestStore::new_with_encryption_provider(
             &self.path,
             self.object_stores.store_of(ObjectStoreType::Main).clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
         )
     }
 
+    fn table_store(&self) -> Arc<TableStore> {
+        Arc::new(TableStore::new(
+            ObjectStores::new(
+                self.object_stores.store_of(ObjectStoreType::Main).clone(),
+                Some(self.object_stores.store_of(ObjectStoreType::Wal).clone()),
+            ),
+            SsTableFormat {
+                comparator: self.comparator.clone(),
+                encryption_provider: self.encryption_provider.clone(),
+                ..SsTableFormat::default()
+            },
+            self.path.clone(),
+            None,
+        ))
+    }
+
     /// Clone a database. If no db already exists at the specified path, then this will create
     /// a new db under the path that is a clone of the db at parent_path.
     ///
@@ -403,6 +672,7 @@ impl Admin {
             Arc::new(FailPointRegistry::new()),
             self.system_clock.clone(),
             self.rand.clone(),
+            self.encryption_provider.clone(),
         )
         .await?;
         Ok(())
@@ -435,6 +705,243 @@ impl Admin {
     }
 }
 
+/// Progress of a compaction submitted by [`Admin::compact_range`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CompactRangeProgress {
+    /// No L0 SST or sorted run overlaps the range, so there is nothing to compact.
+    NothingToCompact,
+    /// The compaction is waiting for the compactor to start it.
+    Submitted,
+    /// The compactor is running the compaction, which has written `output_ssts` SSTs so far.
+    Running { output_ssts: usize },
+    /// The compactor has written the output of the compaction to the manifest with id
+    /// `manifest_id`.
+    Finished { manifest_id: u64 },
+}
+
+/// A version of a key, as returned by [`Admin::key_history`].
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyVersion {
+    /// The layer of the database the version was found in.
+    pub layer: KeyVersionLayer,
+    /// The index of the block of the SST the version was found in.
+    pub block: usize,
+    /// The value, merge operand or tombstone of the version.
+    pub value: ChangeValue,
+    /// The sequence number of the version.
+    pub seq: u64,
+    /// The time the version was written at, if known.
+    pub create_ts: Option<i64>,
+    /// The time the version expires at, if it has a TTL.
+    pub expire_ts: Option<i64>,
+}
+
+/// The layer of the database a [`KeyVersion`] was found in.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum KeyVersionLayer {
+    /// A WAL SST that hasn't been flushed to L0 yet.
+    Wal { wal_id: u64 },
+    /// An L0 SST.
+    L0 { sst_id: Ulid },
+    /// An SST of a sorted run.
+    SortedRun { sorted_run_id: u32, sst_id: Ulid },
+}
+
+/// Reads the versions of `key` in `sst`. Versions of a key can span several blocks, so
+/// every block whose key range includes the key is read.
+async fn key_versions_in_sst(
+    table_store: &TableStore,
+    sst: &SsTableHandle,
+    key: &Bytes,
+    layer: KeyVersionLayer,
+) -> Result<Vec<KeyVersion>, SlateDBError> {
+    let comparator = table_store.comparator();
+    if sst
+        .calculate_view_range(BytesRange::from(key.clone()..=key.clone()), comparator)
+        .is_none()
+    {
+        return Ok(Vec::new());
+    }
+    let index = table_store.read_index(sst).await?;
+    let block_ids = {
+        let index = index.borrow();
+        // SSTs without rows, such as the empty WAL SSTs written to fence writers, have
+        // no blocks
+        if index.block_meta().is_empty() {
+            return Ok(Vec::new());
+        }
+        let start =
+            partitioned_keyspace::first_partition_including_or_after_key(&index, key, comparator);
+        let end = partitioned_keyspace::last_partition_including_key(&index, key, comparator)
+            .map(|block| block + 1)
+            .unwrap_or(start);
+        start..end
+    };
+    if block_ids.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let blocks = table_store
+        .read_blocks_using_index(sst, index, block_ids.clone(), false)
+        .await?;
+    let mut versions = Vec::new();
+    for (block_id, block) in block_ids.zip(blocks) {
+        let mut iter = BlockIterator::new(block, IterationOrder::Ascending)
+            .with_comparator(comparator.clone());
+        iter.seek(key).await?;
+        while let Some(entry) = iter.next_entry().await? {
+            if entry.key != *key {
+                break;
+            }
+            let value = match entry.value {
+                ValueDeletable::BlobRef(blob_ref) => {
+                    ValueDeletable::Value(table_store.read_blob_value(&blob_ref).await?)
+                }
+                value => value,
+            };
+            versions.push(KeyVersion {
+                layer: layer.clone(),
+                block: block_id,
+                value: value.into(),
+                seq: entry.seq,
+                create_ts: entry.create_ts,
+                expire_ts: entry.expire_ts,
+            });
+        }
+    }
+    Ok(versions)
+}
+
+/// The ranges of the keys stored in the L0 SSTs and sorted runs of a database, in the order
+/// of [`CoreDbState::l0`] and [`CoreDbState::compacted`]. A range is `None` if no key is
+/// stored. Ranges include the range tombstones of the SSTs.
+struct KeySpans {
+    l0: Vec<Option<BytesRange>>,
+    compacted: Vec<Option<BytesRange>>,
+}
+
+impl KeySpans {
+    /// Reads the last key of each L0 SST and of the last SST of each sorted run. The
+    /// manifest only records the first keys.
+    async fn load(
+        table_store: &Arc<TableStore>,
+        db_state: &CoreDbState,
+    ) -> Result<Self, SlateDBError> {
+        let mut l0 = Vec::with_capacity(db_state.l0.len());
+        for sst in &db_state.l0 {
+            l0.push(key_span(table_store, std::slice::from_ref(sst)).await?);
+        }
+        let mut compacted = Vec::with_capacity(db_state.compacted.len());
+        for sr in &db_state.compacted {
+            compacted.push(key_span(table_store, &sr.ssts).await?);
+        }
+        Ok(Self { l0, compacted })
+    }
+}
+
+/// Returns the range of the keys stored in `ssts`, which are ordered by key, or `None` if
+/// they store no key.
+async fn key_span(
+    table_store: &Arc<TableStore>,
+    ssts: &[SsTableHandle],
+) -> Result<Option<BytesRange>, SlateDBError> {
+    let comparator = table_store.comparator();
+    let (Some(first_sst), Some(last_sst)) = (ssts.first(), ssts.last()) else {
+        return Ok(None);
+    };
+    let sst_iter_options = SstIteratorOptions {
+        cache_blocks: false,
+        order: IterationOrder::Descending,
+        resolve_blob_refs: false,
+        ..SstIteratorOptions::default()
+    };
+    let last_key = match SstIterator::new_owned_initialized(
+        ..,
+        last_sst.clone(),
+        table_store.clone(),
+        sst_iter_options,
+    )
+    .await?
+    {
+        Some(mut iter) => iter.next_entry().await?.map(|entry| entry.key),
+        None => None,
+    };
+    let mut span = last_key.map(|last_key| {
+        BytesRange::new(
+            first_sst.compacted_effective_start_bound(),
+            Bound::Included(last_key),
+        )
+    });
+    for tombstone in ssts.iter().flat_map(|sst| sst.range_tombstones()) {
+        span = Some(match span {
+            Some(span) => span.span_by(&tombstone.range, comparator),
+            None => tombstone.range,
+        });
+    }
+    Ok(span)
+}
+
+/// Builds the compaction of the L0 SSTs and sorted runs that overlap `range`, or returns
+/// `None` if nothing overlaps it. The sources are in the order used by the compaction
+/// schedulers: L0 SSTs from newest to oldest, then sorted runs from newest to oldest.
+///
+/// The sources are merged into the oldest sorted run that overlaps the range, or into a new
+/// sorted run if none does. Reads search the L0 SSTs and sorted runs that are left out
+/// before that sorted run, so only those that hold none of the keys of the newer sources
+/// are left out: newer L0 SSTs and sorted runs that don't overlap the range, and older
+/// sorted runs that don't overlap the range or the newer sources. L0 SSTs older than an
+/// overlapping one are always compacted.
+fn compact_range_spec(
+    db_state: &CoreDbState,
+    spans: &KeySpans,
+    range: &BytesRange,
+    comparator: &KeyComparator,
+) -> Option<CompactionSpec> {
+    let overlaps = |span: &Option<BytesRange>, other: &BytesRange| {
+        span.as_ref()
+            .is_some_and(|span| span.intersect_by(other, comparator).is_some())
+    };
+    let newest_l0 = spans.l0.iter().position(|span| overlaps(span, range));
+    let oldest_sr = spans
+        .compacted
+        .iter()
+        .rposition(|span| overlaps(span, range));
+
+    let mut sources = Vec::new();
+    // the key ranges of the sources picked so far, which are newer than the next candidate
+    let mut source_spans: Vec<&BytesRange> = Vec::new();
+    if let Some(newest_l0) = newest_l0 {
+        for (sst, span) in db_state.l0.iter().zip(&spans.l0).skip(newest_l0) {
+            sources.push(SourceId::Sst(sst.id.unwrap_compacted_id()));
+            source_spans.extend(span);
+        }
+    }
+    let destination = match oldest_sr {
+        Some(oldest_sr) => {
+            for (sr, span) in db_state.compacted[..=oldest_sr]
+                .iter()
+                .zip(&spans.compacted)
+            {
+                let is_source = overlaps(span, range)
+                    || source_spans
+                        .iter()
+                        .any(|source_span| overlaps(span, source_span));
+                if is_source {
+                    sources.push(SourceId::SortedRun(sr.id));
+                    source_spans.extend(span);
+                }
+            }
+            db_state.compacted[oldest_sr].id
+        }
+        None if newest_l0.is_some() => db_state.compacted.first().map_or(0, |sr| sr.id + 1),
+        None => return None,
+    };
+    Some(CompactionSpec::new(sources, destination))
+}
+
 /// Loads an object store from configured environment variables.
 /// The provider is specified using the CLOUD_PROVIDER variable.
 /// For specific provider configurations, see the corresponding
@@ -589,3 +1096,80 @@ pub fn load_opendal() -> Result<Arc<dyn ObjectStore>, Box<dyn Error>> {
     let op = Operator::via_iter(scheme, iter)?;
     Ok(Arc::new(object_store_opendal::OpendalStore::new(op)) as Arc<dyn ObjectStore>)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::{FlushOptions, FlushType, Settings};
+    use crate::{ColumnFamilyOptions, Db};
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn test_key_history_lists_versions_in_each_layer() {
+        let path = "/tmp/test_key_history";
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder(path, object_store.clone())
+            .with_column_family("users", ColumnFamilyOptions::default())
+            .build()
+            .await
+            .unwrap();
+        let users = db.column_family("users").unwrap();
+        db.put(b"key", b"value1").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        db.delete(b"key").await.unwrap();
+        // neither the same key in another column family nor other keys are listed
+        db.put_cf(&users, b"key", b"users_value").await.unwrap();
+        db.put(b"other", b"value").await.unwrap();
+
+        let admin = Admin::builder(path, object_store).build();
+        let versions = admin.key_history(b"key").await.unwrap();
+
+        assert_eq!(versions.len(), 2);
+        assert!(matches!(versions[0].layer, KeyVersionLayer::Wal { .. }));
+        assert_eq!(versions[0].value, ChangeValue::Tombstone);
+        assert!(matches!(versions[1].layer, KeyVersionLayer::L0 { .. }));
+        assert_eq!(versions[1].block, 0);
+        assert_eq!(
+            versions[1].value,
+            ChangeValue::Value(Bytes::from_static(b"value1"))
+        );
+        assert!(versions[0].seq > versions[1].seq);
+        assert!(versions[1].create_ts.is_some());
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_key_history_resolves_blob_values() {
+        let path = "/tmp/test_key_history";
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(Settings {
+                min_blob_value_size: Some(64),
+                ..Settings::default()
+            })
+            .build()
+            .await
+            .unwrap();
+        db.put(b"key", &[1u8; 100]).await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+
+        let admin = Admin::builder(path, object_store).build();
+        let versions = admin.key_history(b"key").await.unwrap();
+
+        assert_eq!(versions.len(), 1);
+        assert!(matches!(versions[0].layer, KeyVersionLayer::L0 { .. }));
+        assert_eq!(
+            versions[0].value,
+            ChangeValue::Value(Bytes::from(vec![1u8; 100]))
+        );
+        db.close().await.unwrap();
+    }
+}
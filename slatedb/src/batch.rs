@@ -1,14 +1,18 @@
 //! # Batch
 //!
 //! This module contains types for batch operations. A batch operation is a
-//! collection of write operations (puts and/or deletes) that are applied
+//! collection of write operations (puts, deletes and range deletes) that are applied
 //! atomically to the database.
 
+use crate::bytes_range::BytesRange;
+use crate::column_family::{self, ColumnFamily, DEFAULT_COLUMN_FAMILY_ID};
+use crate::comparator::KeyComparator;
 use crate::config::{MergeOptions, PutOptions};
 use crate::error::SlateDBError;
 use crate::iter::{IterationOrder, KeyValueIterator};
 use crate::mem_table::{KVTableInternalKeyRange, SequencedKey};
 use crate::merge_operator::{MergeOperatorIterator, MergeOperatorType};
+use crate::range_tombstone::RangeTombstone;
 use crate::types::{RowEntry, ValueDeletable};
 use async_trait::async_trait;
 use bytes::Bytes;
@@ -37,6 +41,7 @@ use uuid::Uuid;
 ///     batch.put(b"key1", b"value1");
 ///     batch.put(b"key2", b"value2");
 ///     batch.delete(b"key3");
+///     batch.delete_range(b"key4".as_slice()..b"key9".as_slice());
 ///
 ///     db.write(batch).await;
 ///     Ok(())
@@ -50,12 +55,23 @@ use uuid::Uuid;
 #[derive(Clone, Debug)]
 pub struct WriteBatch {
     pub(crate) ops: BTreeMap<SequencedKey, WriteOp>,
+    /// key ranges deleted by the batch. They are applied before `ops`, so a
+    /// write made after a range delete in the same batch is kept.
+    pub(crate) range_deletes: Vec<BytesRange>,
+    /// conditions on the current value of keys that must hold for the batch to
+    /// be applied. They are checked by the writer against the latest committed
+    /// state, right before the batch is assigned its sequence number.
+    pub(crate) preconditions: BTreeMap<Bytes, Precondition>,
     pub(crate) txn_id: Option<Uuid>,
     /// due to merges, multiple writes may happen for the same key in one batch,
     /// this write_idx tracks the order in which writes happen within a single
     /// batch (unrelated to the sequence number of the batch, which is assigned
     /// atomically by the oracle when the batch is committed).
     pub(crate) write_idx: u64,
+    /// writes to named column families, by column family id. Each column family
+    /// batch holds the ops of one column family; they are applied atomically with
+    /// the rest of this batch.
+    pub(crate) column_families: BTreeMap<u32, WriteBatch>,
 }
 
 impl Default for WriteBatch {
@@ -64,6 +80,28 @@ impl Default for WriteBatch {
     }
 }
 
+/// A condition on the current value of a key that must hold for a [`WriteBatch`]
+/// to be applied. If any precondition of a batch does not hold, none of its
+/// writes are applied and the write fails with [`crate::ErrorKind::Precondition`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Precondition {
+    /// The key must not exist (or must be deleted or expired).
+    Absent,
+    /// The key must exist and its current value must equal the given bytes.
+    ValueEquals(Bytes),
+}
+
+impl Precondition {
+    /// Returns true if the precondition holds for the key's current value.
+    pub(crate) fn matches(&self, current: Option<&Bytes>) -> bool {
+        match self {
+            Precondition::Absent => current.is_none(),
+            Precondition::ValueEquals(expected) => current == Some(expected),
+        }
+    }
+}
+
 /// A write operation in a batch.
 #[derive(PartialEq, Clone)]
 pub(crate) enum WriteOp {
@@ -144,8 +182,11 @@ impl WriteBatch {
     pub fn new() -> Self {
         WriteBatch {
             ops: BTreeMap::new(),
+            range_deletes: Vec::new(),
+            preconditions: BTreeMap::new(),
             txn_id: None,
             write_idx: 0,
+            column_families: BTreeMap::new(),
         }
     }
 
@@ -171,8 +212,11 @@ impl WriteBatch {
     pub(crate) fn with_txn_id(self, txn_id: Uuid) -> Self {
         Self {
             ops: self.ops,
+            range_deletes: self.range_deletes,
+            preconditions: self.preconditions,
             txn_id: Some(txn_id),
             write_idx: self.write_idx,
+            column_families: self.column_families,
         }
     }
 
@@ -285,12 +329,192 @@ impl WriteBatch {
         self.write_idx += 1;
     }
 
+    /// Delete every key in the given range. Keys written to the batch before the
+    /// range delete are dropped from the batch; keys written after it are kept.
+    ///
+    /// # Panics
+    /// - if a bound of the range is an empty key
+    /// - if the range is empty
+    pub fn delete_range<K, T>(&mut self, range: T)
+    where
+        K: AsRef<[u8]>,
+        T: RangeBounds<K>,
+    {
+        let start = range
+            .start_bound()
+            .map(|b| Bytes::copy_from_slice(b.as_ref()));
+        let end = range
+            .end_bound()
+            .map(|b| Bytes::copy_from_slice(b.as_ref()));
+        let range = BytesRange::new(start, end);
+
+        // the range delete overwrites every key it covers, so we can safely
+        // remove all previous entries in the range.
+        self.ops.retain(|key, _| !range.contains(&key.user_key));
+        self.range_deletes.push(range);
+    }
+
+    /// Require `precondition` to hold for `key` when the batch is written. The
+    /// check is made atomically with the write against the latest committed state,
+    /// so no other write can land in between. A later precondition for the same
+    /// key replaces an earlier one.
+    ///
+    /// Preconditions are only checked for batches that contain at least one write.
+    ///
+    /// # Panics
+    /// - if the key is empty
+    /// - if the key size is larger than u16::MAX
+    pub fn add_precondition<K: AsRef<[u8]>>(&mut self, key: K, precondition: Precondition) {
+        self.assert_kv(&key, &[]);
+
+        self.preconditions
+            .insert(Bytes::copy_from_slice(key.as_ref()), precondition);
+    }
+
+    /// Put a key-value pair into a column family. Keys must not be empty.
+    ///
+    /// # Panics
+    /// - if the key is empty
+    /// - if the key size is larger than u16::MAX
+    /// - if the value size is larger than u32::MAX
+    pub fn put_cf<K, V>(&mut self, column_family: &ColumnFamily, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.put_cf_with_options(column_family, key, value, &PutOptions::default())
+    }
+
+    /// Put a key-value pair into a column family. Keys must not be empty.
+    ///
+    /// # Panics
+    /// - if the key is empty
+    /// - if the key size is larger than u16::MAX
+    /// - if the value size is larger than u32::MAX
+    pub fn put_cf_with_options<K, V>(
+        &mut self,
+        column_family: &ColumnFamily,
+        key: K,
+        value: V,
+        options: &PutOptions,
+    ) where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.column_family_batch(column_family)
+            .put_with_options(key, value, options)
+    }
+
+    /// Merge a key-value pair into a column family. Keys must not be empty.
+    pub fn merge_cf<K, V>(&mut self, column_family: &ColumnFamily, key: K, value: V)
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.merge_cf_with_options(column_family, key, value, &MergeOptions::default())
+    }
+
+    /// Merge a key-value pair into a column family with custom options.
+    pub fn merge_cf_with_options<K, V>(
+        &mut self,
+        column_family: &ColumnFamily,
+        key: K,
+        value: V,
+        options: &MergeOptions,
+    ) where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.column_family_batch(column_family)
+            .merge_with_options(key, value, options)
+    }
+
+    /// Delete a key from a column family. Keys must not be empty.
+    pub fn delete_cf<K: AsRef<[u8]>>(&mut self, column_family: &ColumnFamily, key: K) {
+        self.column_family_batch(column_family).delete(key)
+    }
+
+    fn column_family_batch(&mut self, column_family: &ColumnFamily) -> &mut WriteBatch {
+        if column_family.id == DEFAULT_COLUMN_FAMILY_ID {
+            return self;
+        }
+        self.column_families.entry(column_family.id).or_default()
+    }
+
+    /// Returns the ops of a column family as a batch of their own, or None if the
+    /// batch has no writes to the column family.
+    pub(crate) fn into_column_family(mut self, column_family: u32) -> Option<WriteBatch> {
+        if column_family == DEFAULT_COLUMN_FAMILY_ID {
+            self.column_families.clear();
+            return Some(self);
+        }
+        self.column_families.remove(&column_family)
+    }
+
+    /// Returns the keys written by the batch. Keys of named column families are
+    /// prefixed with their column family id, so they may collide with keys of the
+    /// default column family. This is only used for conflict detection, where a
+    /// collision causes a spurious conflict at worst.
     pub(crate) fn keys(&self) -> HashSet<Bytes> {
-        self.ops.keys().map(|key| key.user_key.clone()).collect()
+        let column_family_keys = self.column_families.iter().flat_map(|(id, batch)| {
+            batch
+                .ops
+                .keys()
+                .map(move |key| column_family::encode_key(*id, &key.user_key))
+        });
+        self.ops
+            .keys()
+            .map(|key| key.user_key.clone())
+            .chain(column_family_keys)
+            .collect()
+    }
+
+    /// Returns the number of write operations in the batch, across column families.
+    pub(crate) fn num_ops(&self) -> usize {
+        self.ops.len()
+            + self.range_deletes.len()
+            + self
+                .column_families
+                .values()
+                .map(|batch| batch.num_ops())
+                .sum::<usize>()
+    }
+
+    /// Returns the size in bytes of the keys and values written by the batch, across
+    /// column families. Range deletes are not counted.
+    pub(crate) fn size_bytes(&self) -> usize {
+        let ops_size = self
+            .ops
+            .iter()
+            .map(|(key, op)| {
+                key.user_key.len()
+                    + match op {
+                        WriteOp::Put(_, value, _) | WriteOp::Merge(_, value, _) => value.len(),
+                        WriteOp::Delete(_) => 0,
+                    }
+            })
+            .sum::<usize>();
+        ops_size
+            + self
+                .column_families
+                .values()
+                .map(|batch| batch.size_bytes())
+                .sum::<usize>()
     }
 
     pub(crate) fn is_empty(&self) -> bool {
         self.ops.is_empty()
+            && self.range_deletes.is_empty()
+            && self.column_families.values().all(|batch| batch.is_empty())
+    }
+
+    /// Converts the range deletes of a WriteBatch into range tombstones with seq and
+    /// timestamp set.
+    pub(crate) fn extract_range_tombstones(&self, seq: u64, now: i64) -> Vec<RangeTombstone> {
+        self.range_deletes
+            .iter()
+            .map(|range| RangeTombstone::new(range.clone(), seq, now))
+            .collect()
     }
 
     /// Converts a WriteBatch into a vector of RowEntry objects with seq and timestamp set,
@@ -331,20 +555,46 @@ impl WriteBatch {
 pub(crate) struct WriteBatchIterator {
     iter: Peekable<Box<dyn Iterator<Item = (SequencedKey, RowEntry)> + Send + Sync>>,
     ordering: IterationOrder,
+    comparator: KeyComparator,
 }
 
 impl WriteBatchIterator {
+    #[cfg(test)]
     pub(crate) fn new(
         batch: WriteBatch,
         range: impl RangeBounds<Bytes>,
         ordering: IterationOrder,
     ) -> Self {
-        let range = KVTableInternalKeyRange::from(range);
-        let mut entries: Vec<(SequencedKey, RowEntry)> = batch
-            .ops
-            .range(range)
-            .map(|(k, v)| (k.clone(), v.to_row_entry(u64::MAX, None, None)))
-            .collect();
+        let range = BytesRange::from(range);
+        Self::new_with_comparator(batch, range, ordering, KeyComparator::default())
+    }
+
+    /// Creates an iterator over the entries of `batch` in `range`, with keys ordered by
+    /// `comparator`. The ops of a batch are kept in byte order, so they're re-sorted
+    /// when the comparator orders keys differently.
+    pub(crate) fn new_with_comparator(
+        batch: WriteBatch,
+        range: BytesRange,
+        ordering: IterationOrder,
+        comparator: KeyComparator,
+    ) -> Self {
+        let mut entries: Vec<(SequencedKey, RowEntry)> = if comparator.is_bytewise() {
+            batch
+                .ops
+                .range(KVTableInternalKeyRange::from(range))
+                .map(|(k, v)| (k.clone(), v.to_row_entry(u64::MAX, None, None)))
+                .collect()
+        } else {
+            let mut entries: Vec<(SequencedKey, RowEntry)> = batch
+                .ops
+                .iter()
+                .filter(|(k, _)| range.contains_by(&k.user_key, &comparator))
+                .map(|(k, v)| (k.clone(), v.to_row_entry(u64::MAX, None, None)))
+                .collect();
+            // the sort is stable, so the versions of a key stay newest first
+            entries.sort_by(|(a, _), (b, _)| comparator.compare(&a.user_key, &b.user_key));
+            entries
+        };
 
         if matches!(ordering, IterationOrder::Descending) {
             entries.reverse();
@@ -356,6 +606,7 @@ impl WriteBatchIterator {
         Self {
             iter: iter.peekable(),
             ordering,
+            comparator,
         }
     }
 
@@ -391,6 +642,7 @@ impl WriteBatchIterator {
         Self {
             iter: iter.peekable(),
             ordering,
+            comparator: KeyComparator::default(),
         }
     }
 }
@@ -407,9 +659,10 @@ impl KeyValueIterator for WriteBatchIterator {
 
     async fn seek(&mut self, next_key: &[u8]) -> Result<(), crate::error::SlateDBError> {
         while let Some((key, _)) = self.iter.peek() {
+            let key_ord = self.comparator.compare(&key.user_key, next_key);
             if match self.ordering {
-                IterationOrder::Ascending => key.user_key.as_ref() < next_key,
-                IterationOrder::Descending => key.user_key.as_ref() > next_key,
+                IterationOrder::Ascending => key_ord.is_lt(),
+                IterationOrder::Descending => key_ord.is_gt(),
             } {
                 self.iter.next();
             } else {
@@ -518,6 +771,88 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_writebatch_size_bytes() {
+        let mut batch = WriteBatch::new();
+        batch.put(b"key1", b"value1");
+        batch.put(b"key1", b"value22");
+        batch.merge(b"key2", b"m");
+        batch.delete(b"key3");
+        assert_eq!(batch.size_bytes(), 4 + 7 + 4 + 1 + 4);
+    }
+
+    #[test]
+    fn test_writebatch_delete_range_drops_earlier_ops_in_range() {
+        let mut batch = WriteBatch::new();
+        batch.put(b"key1", b"value1");
+        batch.put(b"key2", b"value2");
+        batch.merge(b"key3", b"merge3");
+        batch.delete_range(b"key2".as_slice()..=b"key3".as_slice());
+        batch.put(b"key3", b"value3");
+
+        let keys: Vec<_> = batch.ops.keys().map(|k| k.user_key.clone()).collect();
+        assert_eq!(
+            keys,
+            vec![Bytes::from_static(b"key1"), Bytes::from_static(b"key3")]
+        );
+        assert_eq!(
+            batch.extract_range_tombstones(7, 100),
+            vec![RangeTombstone::new(
+                BytesRange::from_ref("key2"..="key3"),
+                7,
+                100
+            )]
+        );
+        assert!(!batch.is_empty());
+    }
+
+    #[test]
+    fn test_writebatch_later_precondition_replaces_earlier_one() {
+        let mut batch = WriteBatch::new();
+        batch.add_precondition(b"key1", Precondition::Absent);
+        batch.add_precondition(b"key2", Precondition::Absent);
+        batch.add_precondition(b"key1", Precondition::ValueEquals(Bytes::from_static(b"v")));
+
+        assert_eq!(
+            batch.preconditions.into_iter().collect::<Vec<_>>(),
+            vec![
+                (
+                    Bytes::from_static(b"key1"),
+                    Precondition::ValueEquals(Bytes::from_static(b"v"))
+                ),
+                (Bytes::from_static(b"key2"), Precondition::Absent),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_precondition_matches_current_value() {
+        let value = Bytes::from_static(b"value");
+        let other = Bytes::from_static(b"other");
+
+        assert!(Precondition::Absent.matches(None));
+        assert!(!Precondition::Absent.matches(Some(&value)));
+        assert!(Precondition::ValueEquals(value.clone()).matches(Some(&value)));
+        assert!(!Precondition::ValueEquals(value.clone()).matches(Some(&other)));
+        assert!(!Precondition::ValueEquals(value).matches(None));
+    }
+
+    #[test]
+    fn test_writebatch_with_only_range_delete_is_not_empty() {
+        let mut batch = WriteBatch::new();
+        batch.delete_range::<&[u8], _>(..);
+
+        assert!(batch.ops.is_empty());
+        assert!(!batch.is_empty());
+    }
+
+    #[test]
+    #[should_panic(expected = "Range must be non-empty")]
+    fn test_writebatch_delete_range_rejects_empty_range() {
+        let mut batch = WriteBatch::new();
+        batch.delete_range(b"key2".as_slice()..b"key1".as_slice());
+    }
+
     #[tokio::test]
     async fn test_writebatch_iterator_basic() {
         let mut batch = WriteBatch::new();
@@ -26,6 +26,7 @@
 //! be contention between `get`s, which holds a lock, and the write loop._
 
 use async_trait::async_trait;
+use bytes::Bytes;
 use fail_parallel::fail_point;
 use futures::stream::BoxStream;
 use futures::StreamExt;
@@ -35,35 +36,75 @@ use std::time::Duration;
 use tracing::instrument;
 
 use crate::clock::SystemClock;
-use crate::config::WriteOptions;
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+use crate::config::{ReadOptions, WriteOptions};
 use crate::dispatcher::MessageHandler;
+use crate::ingest::IngestReservation;
+use crate::oracle::Oracle;
+use crate::range_tombstone::RangeTombstone;
+use crate::reader::DbStateReader;
 use crate::types::RowEntry;
 use crate::utils::WatchableOnceCellReader;
 use crate::{batch::WriteBatch, db::DbInner, error::SlateDBError};
 
 pub(crate) const WRITE_BATCH_TASK_NAME: &str = "writer";
 
-pub(crate) struct WriteBatchMessage {
-    pub(crate) batch: WriteBatch,
-    pub(crate) options: WriteOptions,
-    pub(crate) done: tokio::sync::oneshot::Sender<
-        Result<WatchableOnceCellReader<Result<(), SlateDBError>>, SlateDBError>,
-    >,
+pub(crate) enum WriteBatchMessage {
+    WriteBatch {
+        batch: WriteBatch,
+        options: WriteOptions,
+        /// The preconditions of the batch, checked before it was sent to the loop.
+        preconditions: Option<CheckedPreconditions>,
+        done: tokio::sync::oneshot::Sender<
+            Result<WatchableOnceCellReader<Result<(), SlateDBError>>, SlateDBError>,
+        >,
+    },
+    /// Reserves the sequence numbers of SSTs being ingested. Handling it in the write
+    /// loop guarantees that every write with a lower sequence number is in a memtable.
+    ReserveIngestSeqs {
+        count: usize,
+        done: tokio::sync::oneshot::Sender<Result<IngestReservation, SlateDBError>>,
+    },
+    /// Stops the write loop from applying writes, for a handoff of the database to the
+    /// next writer. Every write sent before it is applied when `done` is notified, and the
+    /// writes sent after it fail.
+    StopWrites {
+        done: tokio::sync::oneshot::Sender<()>,
+    },
 }
 
 impl std::fmt::Debug for WriteBatchMessage {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        let WriteBatchMessage { batch, options, .. } = self;
-        f.debug_struct("WriteBatch")
-            .field("batch", batch)
-            .field("options", options)
-            .finish()
+        match self {
+            WriteBatchMessage::WriteBatch { batch, options, .. } => f
+                .debug_struct("WriteBatch")
+                .field("batch", batch)
+                .field("options", options)
+                .finish(),
+            WriteBatchMessage::ReserveIngestSeqs { count, .. } => f
+                .debug_struct("ReserveIngestSeqs")
+                .field("count", count)
+                .finish(),
+            WriteBatchMessage::StopWrites { .. } => f.debug_struct("StopWrites").finish(),
+        }
     }
 }
 
+/// The state the preconditions of a batch were checked against before the batch was sent
+/// to the write loop.
+#[derive(Debug)]
+pub(crate) struct CheckedPreconditions {
+    /// The last committed sequence number of the state the preconditions were checked
+    /// against.
+    seq: u64,
+    /// The earliest time one of the values the preconditions were checked against expires.
+    min_expire_ts: Option<i64>,
+}
+
 pub(crate) struct WriteBatchEventHandler {
     db_inner: Arc<DbInner>,
     is_first_write: bool,
+    writes_stopped: bool,
 }
 
 impl WriteBatchEventHandler {
@@ -71,6 +112,7 @@ impl WriteBatchEventHandler {
         Self {
             db_inner,
             is_first_write: true,
+            writes_stopped: false,
         }
     }
 }
@@ -78,21 +120,44 @@ impl WriteBatchEventHandler {
 #[async_trait]
 impl MessageHandler<WriteBatchMessage> for WriteBatchEventHandler {
     async fn handle(&mut self, message: WriteBatchMessage) -> Result<(), SlateDBError> {
-        let WriteBatchMessage {
-            batch,
-            options,
-            done,
-        } = message;
-        let result = self.db_inner.write_batch(batch).await;
+        let (batch, options, preconditions, done) = match message {
+            WriteBatchMessage::WriteBatch { done, .. } if self.writes_stopped => {
+                _ = done.send(Err(SlateDBError::Closed));
+                return Ok(());
+            }
+            WriteBatchMessage::ReserveIngestSeqs { done, .. } if self.writes_stopped => {
+                _ = done.send(Err(SlateDBError::Closed));
+                return Ok(());
+            }
+            WriteBatchMessage::WriteBatch {
+                batch,
+                options,
+                preconditions,
+                done,
+            } => (batch, options, preconditions, done),
+            WriteBatchMessage::ReserveIngestSeqs { count, done } => {
+                _ = done.send(self.db_inner.reserve_ingest_seqs(count));
+                return Ok(());
+            }
+            WriteBatchMessage::StopWrites { done } => {
+                self.writes_stopped = true;
+                _ = done.send(());
+                return Ok(());
+            }
+        };
+        let result = self.db_inner.write_batch(batch, preconditions).await;
         // if this is the first write and the WAL is disabled, make sure users are flushing
-        // their memtables in a timely manner.
+        // their memtables in a timely manner. Failed writes, such as batches whose
+        // preconditions don't hold, are only reported to their caller.
         if self.is_first_write && !self.db_inner.wal_enabled && options.await_durable {
-            self.is_first_write = false;
-            let this_watcher = result.clone()?;
-            let this_clock = self.db_inner.system_clock.clone();
-            tokio::spawn(async move {
-                monitor_first_write(this_watcher, this_clock).await;
-            });
+            if let Ok(this_watcher) = &result {
+                self.is_first_write = false;
+                let this_watcher = this_watcher.clone();
+                let this_clock = self.db_inner.system_clock.clone();
+                tokio::spawn(async move {
+                    monitor_first_write(this_watcher, this_clock).await;
+                });
+            }
         }
         _ = done.send(result);
         Ok(())
@@ -105,7 +170,15 @@ impl MessageHandler<WriteBatchMessage> for WriteBatchEventHandler {
     ) -> Result<(), SlateDBError> {
         let error = result.clone().err().unwrap_or(SlateDBError::Closed);
         while let Some(msg) = messages.next().await {
-            let _ = msg.done.send(Err(error.clone()));
+            match msg {
+                WriteBatchMessage::WriteBatch { done, .. } => {
+                    let _ = done.send(Err(error.clone()));
+                }
+                WriteBatchMessage::ReserveIngestSeqs { done, .. } => {
+                    let _ = done.send(Err(error.clone()));
+                }
+                WriteBatchMessage::StopWrites { .. } => {}
+            }
         }
         Ok(())
     }
@@ -113,10 +186,11 @@ impl MessageHandler<WriteBatchMessage> for WriteBatchEventHandler {
 
 impl DbInner {
     #[allow(clippy::panic)]
-    #[instrument(level = "trace", skip_all, fields(batch_size = batch.ops.len()))]
+    #[instrument(level = "trace", skip_all, fields(batch_size = batch.num_ops()))]
     async fn write_batch(
         &self,
         batch: WriteBatch,
+        preconditions: Option<CheckedPreconditions>,
     ) -> Result<WatchableOnceCellReader<Result<(), SlateDBError>>, SlateDBError> {
         let now = self.mono_clock.now().await?;
         let commit_seq = self.oracle.last_seq.next();
@@ -129,14 +203,33 @@ impl DbInner {
             }
         }
 
+        self.recheck_preconditions(&batch, preconditions, now)
+            .await?;
+
+        let (default_ttl, merge_operator) = {
+            let settings = self.settings.read();
+            (settings.default_ttl, settings.merge_operator.clone())
+        };
         let entries = batch
-            .extract_entries(
-                commit_seq,
-                now,
-                self.settings.default_ttl,
-                self.settings.merge_operator.clone(),
-            )
+            .extract_entries(commit_seq, now, default_ttl, merge_operator)
             .await?;
+        let range_tombstones = batch.extract_range_tombstones(commit_seq, now);
+        let mut column_family_entries = Vec::with_capacity(batch.column_families.len());
+        for (id, column_family_batch) in &batch.column_families {
+            let options = self
+                .column_families
+                .options(*id)
+                .ok_or_else(|| SlateDBError::UnknownColumnFamily(id.to_string()))?;
+            let entries = column_family_batch
+                .extract_entries(
+                    commit_seq,
+                    now,
+                    options.default_ttl,
+                    options.merge_operator.clone(),
+                )
+                .await?;
+            column_family_entries.push((*id, entries));
+        }
 
         let durable_watcher = if self.wal_enabled {
             // WAL entries must be appended to the wal buffer atomically. Otherwise,
@@ -144,15 +237,18 @@ impl DbInner {
             // would violate the guarantee that batches are written atomically. We do
             // this by appending the entire entry batch in a single call to the WAL buffer,
             // which holds a write lock during the append.
-            let wal_watcher = self.wal_buffer.append(&entries)?.durable_watcher();
+            let wal_watcher = self
+                .wal_buffer
+                .append(&entries, &column_family_entries, &range_tombstones)?
+                .durable_watcher();
             self.wal_buffer.maybe_trigger_flush()?;
             // TODO: handle sync here, if sync is enabled, we can call `flush` here. let's put this
             // in another Pull Request.
-            self.write_entries_to_memtable(entries);
+            self.write_entries_to_memtable(entries, column_family_entries, range_tombstones);
             wal_watcher
         } else {
             // if WAL is disabled, we just write the entries to memtable.
-            self.write_entries_to_memtable(entries)
+            self.write_entries_to_memtable(entries, column_family_entries, range_tombstones)
         };
 
         // update the last_applied_seq to wal buffer. if a chunk of WAL entries are applied to the memtable
@@ -192,14 +288,128 @@ impl DbInner {
         Ok(durable_watcher)
     }
 
-    /// Write entries to the currently active memtable. Returns a durable watcher for the memtable.
+    /// Checks the batch's preconditions against the latest committed state before the
+    /// batch is sent to the write loop, so that the object store reads of the check don't
+    /// hold up the other writes. Fails with [`SlateDBError::PreconditionFailed`] if one
+    /// doesn't hold.
+    pub(crate) async fn check_preconditions(
+        &self,
+        batch: &WriteBatch,
+    ) -> Result<Option<CheckedPreconditions>, SlateDBError> {
+        if batch.preconditions.is_empty() {
+            return Ok(None);
+        }
+        let seq = self.oracle.last_committed_seq();
+        let db_state = self.state.read().view();
+        let mut min_expire_ts: Option<i64> = None;
+        for (key, precondition) in &batch.preconditions {
+            let current = self
+                .reader
+                .get_row_with_options(
+                    DEFAULT_COLUMN_FAMILY_ID,
+                    key,
+                    &ReadOptions::default(),
+                    &db_state,
+                    None,
+                    Some(seq),
+                )
+                .await?;
+            if let Some(expire_ts) = current.as_ref().and_then(|row| row.expire_ts) {
+                min_expire_ts = Some(min_expire_ts.map_or(expire_ts, |ts| ts.min(expire_ts)));
+            }
+            if !precondition.matches(current.as_ref().map(|row| &row.value)) {
+                return Err(SlateDBError::PreconditionFailed { key: key.clone() });
+            }
+        }
+        Ok(Some(CheckedPreconditions { seq, min_expire_ts }))
+    }
+
+    /// Checks the batch's preconditions again in the write loop, where nothing can be
+    /// committed between the check and the write. Keys are only read again if they may
+    /// have changed since `checked`, or if one of the values checked has expired by `now`.
+    /// In the common case, this only looks at the memtables.
+    async fn recheck_preconditions(
+        &self,
+        batch: &WriteBatch,
+        checked: Option<CheckedPreconditions>,
+        now: i64,
+    ) -> Result<(), SlateDBError> {
+        if batch.preconditions.is_empty() {
+            return Ok(());
+        }
+        let db_state = self.state.read().view();
+        for (key, precondition) in &batch.preconditions {
+            let unchanged = checked.as_ref().is_some_and(|checked| {
+                checked.min_expire_ts.is_none_or(|ts| ts > now)
+                    && !Self::written_since(&db_state, key, checked.seq)
+            });
+            if unchanged {
+                continue;
+            }
+            let current = self
+                .reader
+                .get_with_options(
+                    DEFAULT_COLUMN_FAMILY_ID,
+                    key,
+                    &ReadOptions::default(),
+                    &db_state,
+                    None,
+                    None,
+                )
+                .await?;
+            if !precondition.matches(current.as_ref()) {
+                return Err(SlateDBError::PreconditionFailed { key: key.clone() });
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns true if a write committed after `seq` may have changed `key`. Writes are in
+    /// the memtables until they are flushed to L0, so once a write newer than `seq` has
+    /// been flushed, the key is assumed to have changed.
+    fn written_since(db_state: &impl DbStateReader, key: &Bytes, seq: u64) -> bool {
+        if db_state.core().last_l0_seq > seq {
+            return true;
+        }
+        let memtables = std::iter::once(db_state.memtable())
+            .chain(db_state.imm_memtable().iter().map(|imm| imm.table()));
+        for memtable in memtables {
+            let mut iter = memtable.range_ascending(key.clone()..=key.clone());
+            while let Some(entry) = iter.next_entry_sync() {
+                if entry.seq > seq {
+                    return true;
+                }
+            }
+            if memtable
+                .range_tombstones()
+                .iter()
+                .any(|tombstone| tombstone.covers(key, seq))
+            {
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Write entries and range tombstones to the currently active memtable. Returns a durable
+    /// watcher for the memtable.
     fn write_entries_to_memtable(
         &self,
         entries: Vec<RowEntry>,
+        column_family_entries: Vec<(u32, Vec<RowEntry>)>,
+        range_tombstones: Vec<RangeTombstone>,
     ) -> WatchableOnceCellReader<Result<(), SlateDBError>> {
         let guard = self.state.read();
         let memtable = guard.memtable();
         entries.into_iter().for_each(|entry| memtable.put(entry));
+        for (id, entries) in column_family_entries {
+            entries
+                .into_iter()
+                .for_each(|entry| memtable.put_column_family(id, entry));
+        }
+        range_tombstones
+            .into_iter()
+            .for_each(|tombstone| memtable.put_range_tombstone(tombstone));
         memtable.table().durable_watcher()
     }
 
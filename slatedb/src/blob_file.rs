@@ -0,0 +1,389 @@
+//! Blob files store large values separately from the SSTs of their rows, so compactions
+//! rewrite the small pointers to the values rather than the values themselves.
+//!
+//! A blob file is written alongside the L0 or compacted SST that first separated its
+//! values, and has the same id. It holds a sequence of records, each of which is the kind
+//! of the record, the value, and the CRC32 checksum of the kind and the value:
+//!
+//! ```txt
+//!  |--------------------------------------------------------|
+//!  |  u8    |  var    | u32      |  u8    |  var    | ...   |
+//!  |--------|---------|----------|--------|---------|-------|
+//!  | kind_0 | value_0 | crc32_0  | kind_1 | value_1 | ...   |
+//!  |--------------------------------------------------------|
+//! ```
+//!
+//! The kind is [`PLAIN_RECORD`] for values stored as is, and [`SEALED_RECORD`] for values
+//! sealed in an encryption envelope by the [`crate::encryption::Encryptor`] of the SST.
+//!
+//! Rows of SSTs point to their values with a [`BlobRef`]. Blob files are immutable. A
+//! blob file is deleted by the garbage collector once none of the SSTs of the database
+//! point to it. Overwritten and deleted values stay in their blob file until then, so
+//! compactions rewrite the values of blob files whose live bytes have fallen below
+//! [`crate::config::CompactorOptions::min_blob_file_live_ratio`] of their size into the
+//! blob files of their output SSTs. Once every SST pointing to such a blob file has been
+//! compacted, the garbage collector deletes it.
+
+use std::collections::{BTreeMap, HashSet};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::{Buf, BufMut, Bytes};
+use ulid::Ulid;
+
+use crate::blob::ReadOnlyBlob;
+use crate::encryption::{self, EncryptionProviderType, Encryptor};
+use crate::error::SlateDBError;
+use crate::iter::KeyValueIterator;
+use crate::sst::{CHECKSUM_SIZE, SIZEOF_U32, SIZEOF_U64};
+use crate::tablestore::TableStore;
+use crate::types::{RowEntry, ValueDeletable};
+
+/// The kind of records holding a value as is.
+pub(crate) const PLAIN_RECORD: u8 = 0;
+/// The kind of records holding a value sealed in an encryption envelope.
+pub(crate) const SEALED_RECORD: u8 = 1;
+const RECORD_KIND_SIZE: usize = 1;
+
+/// A pointer to a value stored in a blob file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct BlobRef {
+    /// The id of the blob file.
+    pub(crate) blob_id: Ulid,
+    /// The offset of the record holding the value in the blob file.
+    pub(crate) offset: u64,
+    /// The length of the value as stored in the record, excluding its kind and checksum.
+    pub(crate) len: u32,
+}
+
+impl BlobRef {
+    /// The length of an encoded blob ref.
+    pub(crate) const ENCODED_LEN: usize = 16 + SIZEOF_U64 + SIZEOF_U32;
+
+    pub(crate) fn encode(&self, output: &mut impl BufMut) {
+        output.put_u128(u128::from(self.blob_id));
+        output.put_u64(self.offset);
+        output.put_u32(self.len);
+    }
+
+    pub(crate) fn decode(data: &mut Bytes) -> Result<Self, SlateDBError> {
+        if data.remaining() < Self::ENCODED_LEN {
+            return Err(SlateDBError::InvalidBlobRef);
+        }
+        Ok(Self {
+            blob_id: Ulid::from(data.get_u128()),
+            offset: data.get_u64(),
+            len: data.get_u32(),
+        })
+    }
+
+    /// Returns the length of the record holding the value.
+    fn record_len(&self) -> usize {
+        RECORD_KIND_SIZE + self.len as usize + CHECKSUM_SIZE
+    }
+
+    /// Returns the range of the blob file holding the record of the value.
+    fn record_range(&self) -> std::ops::Range<u64> {
+        self.offset..self.offset + self.record_len() as u64
+    }
+}
+
+/// Reads the value a blob ref points to from its blob file. Sealed values are decrypted
+/// with `encryption_provider`.
+pub(crate) async fn read_blob_value(
+    obj: &impl ReadOnlyBlob,
+    blob_ref: &BlobRef,
+    encryption_provider: Option<&EncryptionProviderType>,
+) -> Result<Bytes, SlateDBError> {
+    let mut record = obj.read_range(blob_ref.record_range()).await?;
+    if record.len() != blob_ref.record_len() {
+        return Err(SlateDBError::InvalidBlobRef);
+    }
+    let data = record.split_to(RECORD_KIND_SIZE + blob_ref.len as usize);
+    if crc32fast::hash(&data) != record.get_u32() {
+        return Err(SlateDBError::ChecksumMismatch);
+    }
+    let value = data.slice(RECORD_KIND_SIZE..);
+    match data[0] {
+        PLAIN_RECORD => Ok(value),
+        SEALED_RECORD => encryption::unseal(encryption_provider, value),
+        _ => Err(SlateDBError::InvalidBlobRef),
+    }
+}
+
+/// Returns the number of bytes of each blob file the rows of an SST point to.
+#[derive(Debug, Default)]
+pub(crate) struct BlobRefCounter {
+    live_bytes: BTreeMap<Ulid, u64>,
+}
+
+impl BlobRefCounter {
+    pub(crate) fn add(&mut self, blob_ref: &BlobRef) {
+        *self.live_bytes.entry(blob_ref.blob_id).or_default() += blob_ref.len as u64;
+    }
+
+    pub(crate) fn build(self) -> Vec<BlobFileRef> {
+        self.live_bytes
+            .into_iter()
+            .map(|(id, live_bytes)| BlobFileRef { id, live_bytes })
+            .collect()
+    }
+}
+
+/// The values of a blob file the rows of an SST point to.
+#[derive(Clone, Debug, PartialEq, serde::Serialize)]
+pub(crate) struct BlobFileRef {
+    /// The id of the blob file.
+    pub(crate) id: Ulid,
+    /// The total length of the values of the blob file the SST points to.
+    pub(crate) live_bytes: u64,
+}
+
+/// Builds a blob file from the values separated from the rows of an SST.
+pub(crate) struct BlobFileBuilder {
+    id: Ulid,
+    min_value_size: usize,
+    data: Vec<u8>,
+    encryptor: Option<Encryptor>,
+}
+
+impl BlobFileBuilder {
+    /// Creates a builder for the blob file `id`. Values are sealed with `encryptor`, if
+    /// set.
+    pub(crate) fn new(id: Ulid, min_value_size: usize, encryptor: Option<Encryptor>) -> Self {
+        Self {
+            id,
+            min_value_size,
+            data: Vec::new(),
+            encryptor,
+        }
+    }
+
+    /// Returns true if the value should be stored in the blob file.
+    pub(crate) fn should_separate(&self, value: &[u8]) -> bool {
+        value.len() >= self.min_value_size
+    }
+
+    /// Appends a value to the blob file and returns a pointer to it.
+    pub(crate) fn add(&mut self, value: &[u8]) -> Result<BlobRef, SlateDBError> {
+        let offset = self.data.len();
+        let len = match &self.encryptor {
+            Some(encryptor) => {
+                let sealed = encryptor.seal(value)?;
+                self.data.put_u8(SEALED_RECORD);
+                self.data.put_slice(&sealed);
+                sealed.len()
+            }
+            None => {
+                self.data.put_u8(PLAIN_RECORD);
+                self.data.put_slice(value);
+                value.len()
+            }
+        };
+        self.data.put_u32(crc32fast::hash(&self.data[offset..]));
+        Ok(BlobRef {
+            blob_id: self.id,
+            offset: offset as u64,
+            len: u32::try_from(len).expect("value len > u32"),
+        })
+    }
+
+    /// Returns the encoded blob file, or None if no values were added to it.
+    pub(crate) fn build(self) -> Option<EncodedBlobFile> {
+        if self.data.is_empty() {
+            return None;
+        }
+        Some(EncodedBlobFile {
+            id: self.id,
+            data: Bytes::from(self.data),
+        })
+    }
+}
+
+pub(crate) struct EncodedBlobFile {
+    pub(crate) id: Ulid,
+    pub(crate) data: Bytes,
+}
+
+/// Replaces the blob refs of an iterator that point to one of `blob_files` with their
+/// values, so the writer of the output SST separates the values into its own blob file.
+pub(crate) struct BlobRewriteIterator<T: KeyValueIterator> {
+    iterator: T,
+    blob_files: HashSet<Ulid>,
+    table_store: Arc<TableStore>,
+}
+
+impl<T: KeyValueIterator> BlobRewriteIterator<T> {
+    pub(crate) fn new(
+        iterator: T,
+        blob_files: HashSet<Ulid>,
+        table_store: Arc<TableStore>,
+    ) -> Self {
+        Self {
+            iterator,
+            blob_files,
+            table_store,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: KeyValueIterator> KeyValueIterator for BlobRewriteIterator<T> {
+    async fn init(&mut self) -> Result<(), SlateDBError> {
+        self.iterator.init().await
+    }
+
+    async fn next_entry(&mut self) -> Result<Option<RowEntry>, SlateDBError> {
+        let Some(mut entry) = self.iterator.next_entry().await? else {
+            return Ok(None);
+        };
+        if let ValueDeletable::BlobRef(blob_ref) = &entry.value {
+            if self.blob_files.contains(&blob_ref.blob_id) {
+                let value = self.table_store.read_blob_value(blob_ref).await?;
+                entry.value = ValueDeletable::Value(value);
+            }
+        }
+        Ok(Some(entry))
+    }
+
+    async fn seek(&mut self, next_key: &[u8]) -> Result<(), SlateDBError> {
+        self.iterator.seek(next_key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::ops::Range;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::encryption::tests::XorEncryptionProvider;
+
+    struct BytesBlob {
+        bytes: Bytes,
+    }
+
+    impl ReadOnlyBlob for BytesBlob {
+        async fn len(&self) -> Result<u64, SlateDBError> {
+            Ok(self.bytes.len() as u64)
+        }
+
+        async fn read_range(&self, range: Range<u64>) -> Result<Bytes, SlateDBError> {
+            Ok(self.bytes.slice(range.start as usize..range.end as usize))
+        }
+
+        async fn read(&self) -> Result<Bytes, SlateDBError> {
+            Ok(self.bytes.clone())
+        }
+    }
+
+    #[test]
+    fn test_should_encode_decode_blob_ref() {
+        let blob_ref = BlobRef {
+            blob_id: Ulid::from_parts(1234, 5678),
+            offset: 42,
+            len: 4096,
+        };
+        let mut buf = Vec::new();
+        blob_ref.encode(&mut buf);
+        assert_eq!(buf.len(), BlobRef::ENCODED_LEN);
+
+        let decoded = BlobRef::decode(&mut Bytes::from(buf)).unwrap();
+        assert_eq!(decoded, blob_ref);
+    }
+
+    #[tokio::test]
+    async fn test_should_read_values_of_blob_file() {
+        let mut builder = BlobFileBuilder::new(Ulid::from_parts(1, 2), 4, None);
+        assert!(!builder.should_separate(b"abc"));
+        assert!(builder.should_separate(b"abcd"));
+        let ref1 = builder.add(b"value1").unwrap();
+        let ref2 = builder.add(b"value22").unwrap();
+        let blob = builder.build().unwrap();
+        assert_eq!(blob.id, Ulid::from_parts(1, 2));
+        assert_eq!(ref2.offset, (RECORD_KIND_SIZE + 6 + CHECKSUM_SIZE) as u64);
+
+        let obj = BytesBlob { bytes: blob.data };
+        assert_eq!(
+            read_blob_value(&obj, &ref1, None).await.unwrap(),
+            Bytes::from_static(b"value1")
+        );
+        assert_eq!(
+            read_blob_value(&obj, &ref2, None).await.unwrap(),
+            Bytes::from_static(b"value22")
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_read_sealed_values_of_blob_file() {
+        let provider: EncryptionProviderType = Arc::new(XorEncryptionProvider::new("k1", 0x5a));
+        let encryptor = Encryptor::new(provider.clone());
+        let mut builder = BlobFileBuilder::new(Ulid::from_parts(1, 2), 1, Some(encryptor));
+        let blob_ref = builder.add(b"secret-value").unwrap();
+        let blob = builder.build().unwrap();
+        assert!(!blob
+            .data
+            .windows(b"secret-value".len())
+            .any(|w| w == b"secret-value"));
+
+        let obj = BytesBlob { bytes: blob.data };
+        assert_eq!(
+            read_blob_value(&obj, &blob_ref, Some(&provider))
+                .await
+                .unwrap(),
+            Bytes::from_static(b"secret-value")
+        );
+        let result = read_blob_value(&obj, &blob_ref, None).await;
+        assert!(matches!(
+            result,
+            Err(SlateDBError::EncryptionKeyUnavailable { key_id }) if key_id == "k1"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_read_of_corrupted_value() {
+        let mut builder = BlobFileBuilder::new(Ulid::from_parts(1, 2), 1, None);
+        let blob_ref = builder.add(b"value").unwrap();
+        let mut data = builder.build().unwrap().data.to_vec();
+        data[RECORD_KIND_SIZE] ^= 1;
+
+        let obj = BytesBlob {
+            bytes: Bytes::from(data),
+        };
+        let result = read_blob_value(&obj, &blob_ref, None).await;
+        assert!(matches!(result, Err(SlateDBError::ChecksumMismatch)));
+    }
+
+    #[test]
+    fn test_should_not_build_empty_blob_file() {
+        let builder = BlobFileBuilder::new(Ulid::from_parts(1, 2), 1, None);
+        assert!(builder.build().is_none());
+    }
+
+    #[test]
+    fn test_should_count_live_bytes_per_blob_file() {
+        let mut counter = BlobRefCounter::default();
+        let id1 = Ulid::from_parts(1, 1);
+        let id2 = Ulid::from_parts(2, 2);
+        for (blob_id, len) in [(id2, 10), (id1, 5), (id2, 7)] {
+            counter.add(&BlobRef {
+                blob_id,
+                offset: 0,
+                len,
+            });
+        }
+        assert_eq!(
+            counter.build(),
+            vec![
+                BlobFileRef {
+                    id: id1,
+                    live_bytes: 5
+                },
+                BlobFileRef {
+                    id: id2,
+                    live_bytes: 17
+                },
+            ]
+        );
+    }
+}
@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use crate::comparator::KeyComparator;
 use crate::iter::IterationOrder;
 use crate::iter::IterationOrder::Ascending;
 use crate::row_codec::SstRowCodecV0;
@@ -50,6 +51,7 @@ pub struct BlockIterator<B: BlockLike> {
     // so we use `Bytes` temporarily
     first_key: Bytes,
     ordering: IterationOrder,
+    comparator: KeyComparator,
 }
 
 #[async_trait]
@@ -76,7 +78,12 @@ impl<B: BlockLike> KeyValueIterator for BlockIterator<B> {
             match result {
                 Ok(None) => return Ok(()),
                 Ok(Some(kv)) => {
-                    if kv.key < next_key {
+                    let ordering = self.comparator.compare(&kv.key, next_key);
+                    let before_next_key = match self.ordering {
+                        Ascending => ordering.is_lt(),
+                        Descending => ordering.is_gt(),
+                    };
+                    if before_next_key {
                         self.advance();
                     } else {
                         return Ok(());
@@ -95,9 +102,17 @@ impl<B: BlockLike> BlockIterator<B> {
             block,
             off_off: 0,
             ordering,
+            comparator: KeyComparator::default(),
         }
     }
 
+    /// Sets the order of the keys in the block, which is used to seek.
+    pub(crate) fn with_comparator(mut self, comparator: KeyComparator) -> Self {
+        self.comparator = comparator;
+        self
+    }
+
+    #[cfg(test)]
     pub fn new_ascending(block: B) -> Self {
         Self::new(block, Ascending)
     }
@@ -149,7 +164,7 @@ mod tests {
     use crate::block::BlockBuilder;
     use crate::block_iterator::BlockIterator;
     use crate::bytes_range::BytesRange;
-    use crate::iter::KeyValueIterator;
+    use crate::iter::{IterationOrder, KeyValueIterator};
     use crate::proptest_util::{arbitrary, sample};
     use crate::test_utils::{assert_iterator, assert_next_entry, gen_attrs, gen_empty_attrs};
     use crate::types::RowEntry;
@@ -263,6 +278,37 @@ mod tests {
         assert_iterator(&mut iter, Vec::new()).await;
     }
 
+    #[tokio::test]
+    async fn test_seek_descending_to_nonexisting_key() {
+        let mut block_builder = BlockBuilder::new(1024);
+        assert!(block_builder.add_value(b"donkey", b"kong", gen_empty_attrs()));
+        assert!(block_builder.add_value(b"kratos", b"atreus", gen_empty_attrs()));
+        assert!(block_builder.add_value(b"super", b"mario", gen_empty_attrs()));
+        let block = block_builder.build().unwrap();
+        let mut iter = BlockIterator::new(block, IterationOrder::Descending);
+        assert_next_entry(&mut iter, &RowEntry::new_value(b"super", b"mario", 0)).await;
+        iter.seek(b"l").await.unwrap();
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"kratos", b"atreus", 0),
+                RowEntry::new_value(b"donkey", b"kong", 0),
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_seek_descending_to_key_before_first_key() {
+        let mut block_builder = BlockBuilder::new(1024);
+        assert!(block_builder.add_value(b"donkey", b"kong", gen_empty_attrs()));
+        assert!(block_builder.add_value(b"kratos", b"atreus", gen_empty_attrs()));
+        let block = block_builder.build().unwrap();
+        let mut iter = BlockIterator::new(block, IterationOrder::Descending);
+        iter.seek(b"a").await.unwrap();
+        assert_iterator(&mut iter, Vec::new()).await;
+    }
+
     #[test]
     fn should_iterate_arbitrary_range() {
         let mut runner = proptest_util::runner::new(file!(), None);
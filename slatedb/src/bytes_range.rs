@@ -4,6 +4,7 @@ use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::ops::{Bound, RangeBounds};
 
 use crate::comparable_range::{ComparableRange, EndBound, StartBound};
+use crate::comparator::KeyComparator;
 
 /// Concrete struct representing a range of Bytes. Gets around much of
 /// the cumbersome work associated with the generic trait RangeBounds<Bytes>
@@ -52,6 +53,15 @@ fn is_bound_non_empty(bound: &Bound<Bytes>) -> bool {
 
 impl BytesRange {
     pub(crate) fn new(start_bound: Bound<Bytes>, end_bound: Bound<Bytes>) -> Self {
+        Self::new_by(start_bound, end_bound, &KeyComparator::default())
+    }
+
+    /// Creates a range with its bounds ordered by `comparator`.
+    pub(crate) fn new_by(
+        start_bound: Bound<Bytes>,
+        end_bound: Bound<Bytes>,
+        comparator: &KeyComparator,
+    ) -> Self {
         assert!(
             is_bound_non_empty(&start_bound),
             "Start bound must be non-empty"
@@ -61,7 +71,10 @@ impl BytesRange {
             "End bound must be non-empty"
         );
         let inner = ComparableRange::new(start_bound, end_bound);
-        assert!(inner.non_empty(), "Range must be non-empty");
+        assert!(
+            inner.non_empty_by(|a, b| comparator.compare(a, b)),
+            "Range must be non-empty"
+        );
         Self { inner }
     }
 
@@ -90,6 +103,15 @@ impl BytesRange {
 
     #[cfg(test)]
     pub(crate) fn from_ref<K, T>(range: T) -> Self
+    where
+        K: AsRef<[u8]>,
+        T: RangeBounds<K>,
+    {
+        Self::from_ref_by(range, &KeyComparator::default())
+    }
+
+    /// Creates a range from borrowed bounds, with keys ordered by `comparator`.
+    pub(crate) fn from_ref_by<K, T>(range: T, comparator: &KeyComparator) -> Self
     where
         K: AsRef<[u8]>,
         T: RangeBounds<K>,
@@ -100,6 +122,25 @@ impl BytesRange {
         let end = range
             .end_bound()
             .map(|b| Bytes::copy_from_slice(b.as_ref()));
+        Self::new_by(start, end, comparator)
+    }
+
+    /// Returns the range of the keys that start with `prefix`. The range ends at the
+    /// shortest key that is greater than every key with the prefix.
+    pub(crate) fn from_prefix(prefix: &Bytes) -> Self {
+        let start = if prefix.is_empty() {
+            Unbounded
+        } else {
+            Included(prefix.clone())
+        };
+        let end = match prefix.iter().rposition(|b| *b != u8::MAX) {
+            Some(last) => {
+                let mut end = prefix[..=last].to_vec();
+                end[last] += 1;
+                Excluded(Bytes::from(end))
+            }
+            None => Unbounded,
+        };
         Self::new(start, end)
     }
 
@@ -109,6 +150,46 @@ impl BytesRange {
             .map(|inner| Self { inner })
     }
 
+    /// Intersects the ranges with their bounds ordered by `comparator`.
+    pub(crate) fn intersect_by(&self, other: &Self, comparator: &KeyComparator) -> Option<Self> {
+        self.inner
+            .intersect_by(&other.inner, |a, b| comparator.compare(a, b))
+            .map(|inner| Self { inner })
+    }
+
+    /// Returns the smallest range that contains both ranges, with keys ordered by
+    /// `comparator`.
+    pub(crate) fn span_by(&self, other: &Self, comparator: &KeyComparator) -> Self {
+        Self {
+            inner: self
+                .inner
+                .span_by(&other.inner, |a, b| comparator.compare(a, b)),
+        }
+    }
+
+    /// Checks whether `key` is in the range with keys ordered by `comparator`.
+    pub(crate) fn contains_by(&self, key: &[u8], comparator: &KeyComparator) -> bool {
+        !self.key_precedes(key, comparator) && !self.key_exceeds(key, comparator)
+    }
+
+    /// Checks whether `key` comes before the start of the range.
+    pub(crate) fn key_precedes(&self, key: &[u8], comparator: &KeyComparator) -> bool {
+        match self.start_bound() {
+            Included(start) => comparator.compare(key, start).is_lt(),
+            Excluded(start) => comparator.compare(key, start).is_le(),
+            Unbounded => false,
+        }
+    }
+
+    /// Checks whether `key` comes after the end of the range.
+    pub(crate) fn key_exceeds(&self, key: &[u8], comparator: &KeyComparator) -> bool {
+        match self.end_bound() {
+            Included(end) => comparator.compare(key, end).is_gt(),
+            Excluded(end) => comparator.compare(key, end).is_ge(),
+            Unbounded => false,
+        }
+    }
+
     pub(crate) fn is_start_bound_included_or_unbounded(&self) -> bool {
         !matches!(self.start_bound(), Excluded(_))
     }
@@ -142,6 +223,7 @@ impl BytesRange {
 #[cfg(test)]
 pub(crate) mod tests {
     use crate::bytes_range::BytesRange;
+    use crate::comparator::tests::reverse_comparator;
     use crate::proptest_util::arbitrary;
     use crate::proptest_util::sample;
 
@@ -208,6 +290,53 @@ pub(crate) mod tests {
         });
     }
 
+    #[test]
+    fn test_from_prefix() {
+        let range = BytesRange::from_prefix(&Bytes::from_static(b"user:"));
+        assert!(range.contains(&Bytes::from_static(b"user:")));
+        assert!(range.contains(&Bytes::from_static(b"user:\xff\xff")));
+        assert!(!range.contains(&Bytes::from_static(b"user;")));
+        assert!(!range.contains(&Bytes::from_static(b"user")));
+
+        let range = BytesRange::from_prefix(&Bytes::from_static(b"a\xff"));
+        assert_eq!(
+            range.end_bound(),
+            Bound::Excluded(&Bytes::from_static(b"b"))
+        );
+
+        let range = BytesRange::from_prefix(&Bytes::from_static(b"\xff"));
+        assert_eq!(range.end_bound(), Unbounded);
+    }
+
+    #[test]
+    fn test_ranges_ordered_by_comparator() {
+        let comparator = reverse_comparator();
+        let range = BytesRange::new_by(
+            Bound::Included(Bytes::from("d")),
+            Bound::Excluded(Bytes::from("b")),
+            &comparator,
+        );
+        assert!(range.contains_by(b"d", &comparator));
+        assert!(range.contains_by(b"c", &comparator));
+        assert!(!range.contains_by(b"b", &comparator));
+        assert!(range.key_precedes(b"e", &comparator));
+        assert!(range.key_exceeds(b"a", &comparator));
+
+        let other = BytesRange::new_by(Bound::Excluded(Bytes::from("c")), Unbounded, &comparator);
+        let intersection = range.intersect_by(&other, &comparator).unwrap();
+        assert_eq!(
+            intersection,
+            BytesRange::new_by(
+                Bound::Excluded(Bytes::from("c")),
+                Bound::Excluded(Bytes::from("b")),
+                &comparator,
+            )
+        );
+        let disjoint =
+            BytesRange::new_by(Unbounded, Bound::Included(Bytes::from("e")), &comparator);
+        assert!(range.intersect_by(&disjoint, &comparator).is_none());
+    }
+
     #[test]
     fn test_new_with_unbounded_range_is_valid() {
         BytesRange::new(Unbounded, Unbounded);
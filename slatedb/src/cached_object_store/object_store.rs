@@ -9,6 +9,7 @@ use std::{ops::Range, sync::Arc};
 
 use crate::cached_object_store::admission::AdmissionPicker;
 use crate::cached_object_store::storage::{LocalCacheStorage, PartID};
+use crate::clock::SystemClock;
 use crate::error::SlateDBError;
 use log::warn;
 
@@ -22,6 +23,7 @@ pub(crate) struct CachedObjectStore {
     pub(crate) admission_picker: AdmissionPicker,
     pub(crate) cache_puts: bool,
     stats: Arc<CachedObjectStoreStats>,
+    system_clock: Arc<dyn SystemClock>,
 }
 
 impl CachedObjectStore {
@@ -31,6 +33,7 @@ impl CachedObjectStore {
         part_size_bytes: usize,
         cache_puts: bool,
         stats: Arc<CachedObjectStoreStats>,
+        system_clock: Arc<dyn SystemClock>,
     ) -> Result<Arc<Self>, SlateDBError> {
         if part_size_bytes == 0 || !part_size_bytes.is_multiple_of(1024) {
             return Err(SlateDBError::InvalidCachePartSize);
@@ -41,6 +44,7 @@ impl CachedObjectStore {
             part_size_bytes,
             cache_storage,
             stats,
+            system_clock,
             admission_picker: AdmissionPicker::default(),
             cache_puts,
         }))
@@ -234,6 +238,12 @@ impl CachedObjectStore {
             opts.range = Some(self.align_get_range(range));
         }
 
+        // the payload is streamed from the object store while it's saved, so the fetch is
+        // timed until the save completes.
+        let _timer = self
+            .stats
+            .object_store_cache_fetch_latency_us
+            .start_timer(self.system_clock.as_ref());
         let get_result = self.object_store.get_opts(location, opts).await?;
         let result_meta = get_result.meta.clone();
         let result_attrs = get_result.attributes.clone();
@@ -358,6 +368,7 @@ impl CachedObjectStore {
         let location = location.clone();
         let entry = self.cache_storage.entry(&location, self.part_size_bytes);
         let db_stats = self.stats.clone();
+        let system_clock = self.system_clock.clone();
         Box::pin(async move {
             db_stats.object_store_cache_part_access.inc();
 
@@ -373,6 +384,9 @@ impl CachedObjectStore {
                 start: (part_id * part_size) as u64,
                 end: ((part_id + 1) * part_size) as u64,
             };
+            let timer = db_stats
+                .object_store_cache_fetch_latency_us
+                .start_timer(system_clock.as_ref());
             let get_result = object_store
                 .get_opts(
                     &location,
@@ -388,6 +402,7 @@ impl CachedObjectStore {
             let meta = get_result.meta.clone();
             let attrs = get_result.attributes.clone();
             let bytes = get_result.bytes().await?;
+            drop(timer);
             entry.save_head((&meta, &attrs)).await.ok();
             entry.save_part(part_id, bytes.clone()).await.ok();
             Ok(Bytes::copy_from_slice(&bytes.slice(range_in_part)))
@@ -621,9 +636,15 @@ mod tests {
         ));
 
         let part_size = 1024;
-        let cached_store =
-            CachedObjectStore::new(object_store.clone(), cache_storage, part_size, false, stats)
-                .unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store.clone(),
+            cache_storage,
+            part_size,
+            false,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
         let entry = cached_store.cache_storage.entry(&location, 1024);
 
         let object_size_hint = cached_store.save_get_result(get_result).await?;
@@ -698,8 +719,15 @@ mod tests {
             Arc::new(DbRand::default()),
         ));
 
-        let cached_store =
-            CachedObjectStore::new(object_store, cache_storage, part_size, false, stats).unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store,
+            cache_storage,
+            part_size,
+            false,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
         let entry = cached_store.cache_storage.entry(&location, part_size);
         let object_size_hint = cached_store.save_get_result(get_result).await?;
         assert_eq!(object_size_hint, 1024 * 3);
@@ -743,8 +771,15 @@ mod tests {
             Arc::new(DbRand::default()),
         ));
 
-        let cached_store =
-            CachedObjectStore::new(object_store, cache_storage, 1024, false, stats).unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store,
+            cache_storage,
+            1024,
+            false,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
 
         struct Test {
             input: (Option<GetRange>, usize),
@@ -832,8 +867,15 @@ mod tests {
             Arc::new(DefaultSystemClock::new()),
             Arc::new(DbRand::default()),
         ));
-        let cached_store =
-            CachedObjectStore::new(object_store, cache_storage, 1024, false, stats).unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store,
+            cache_storage,
+            1024,
+            false,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
 
         let aligned = cached_store.align_range(&(9..1025), 1024);
         assert_eq!(aligned, 0..2048);
@@ -855,8 +897,15 @@ mod tests {
             Arc::new(DefaultSystemClock::new()),
             Arc::new(DbRand::default()),
         ));
-        let cached_store =
-            CachedObjectStore::new(object_store, cache_storage, 1024, false, stats).unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store,
+            cache_storage,
+            1024,
+            false,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
 
         let aligned = cached_store.align_get_range(&GetRange::Bounded(9..1025));
         assert_eq!(aligned, GetRange::Bounded(0..2048));
@@ -886,9 +935,15 @@ mod tests {
             Arc::new(DefaultSystemClock::new()),
             Arc::new(DbRand::default()),
         ));
-        let cached_store =
-            CachedObjectStore::new(object_store.clone(), cache_storage, 1024, false, stats)
-                .unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store.clone(),
+            cache_storage,
+            1024,
+            false,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
 
         let test_path = Path::from("/data/testdata1");
         let test_payload = gen_rand_bytes(1024 * 3 + 2);
@@ -970,8 +1025,15 @@ mod tests {
 
         let object_store = Arc::new(object_store::memory::InMemory::new());
 
-        let cached_store =
-            CachedObjectStore::new(object_store.clone(), cache_storage, 1024, true, stats).unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store.clone(),
+            cache_storage,
+            1024,
+            true,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
 
         // Create some test files to preload
         let test_paths = vec![
@@ -1020,8 +1082,15 @@ mod tests {
 
         let object_store = Arc::new(object_store::memory::InMemory::new());
 
-        let cached_store =
-            CachedObjectStore::new(object_store.clone(), cache_storage, 1024, true, stats).unwrap();
+        let cached_store = CachedObjectStore::new(
+            object_store.clone(),
+            cache_storage,
+            1024,
+            true,
+            stats,
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
 
         // Create some test files
         let test_paths = vec![Path::from("file1.sst"), Path::from("file2.sst")];
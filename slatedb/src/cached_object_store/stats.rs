@@ -1,4 +1,4 @@
-use crate::stats::{Counter, Gauge, StatRegistry};
+use crate::stats::{Counter, Gauge, Histogram, StatRegistry};
 use std::sync::Arc;
 
 macro_rules! oscache_stat_name {
@@ -13,6 +13,7 @@ pub const OBJECT_STORE_CACHE_KEYS: &str = oscache_stat_name!("cache_keys");
 pub const OBJECT_STORE_CACHE_BYTES: &str = oscache_stat_name!("cache_bytes");
 pub const OBJECT_STORE_CACHE_EVICTED_KEYS: &str = oscache_stat_name!("evicted_keys");
 pub const OBJECT_STORE_CACHE_EVICTED_BYTES: &str = oscache_stat_name!("evicted_bytes");
+pub const OBJECT_STORE_CACHE_FETCH_LATENCY_US: &str = oscache_stat_name!("fetch_latency_us");
 
 #[derive(Debug, Clone)]
 pub(crate) struct CachedObjectStoreStats {
@@ -22,6 +23,7 @@ pub(crate) struct CachedObjectStoreStats {
     pub(super) object_store_cache_bytes: Arc<Gauge<u64>>,
     pub(super) object_store_cache_evicted_keys: Arc<Counter>,
     pub(super) object_store_cache_evicted_bytes: Arc<Counter>,
+    pub(super) object_store_cache_fetch_latency_us: Arc<Histogram>,
 }
 
 impl CachedObjectStoreStats {
@@ -33,31 +35,43 @@ impl CachedObjectStoreStats {
             object_store_cache_keys: Arc::new(Gauge::default()),
             object_store_cache_evicted_bytes: Arc::new(Counter::default()),
             object_store_cache_evicted_keys: Arc::new(Counter::default()),
+            object_store_cache_fetch_latency_us: Arc::new(Histogram::default()),
         };
         registry.register(
             OBJECT_STORE_CACHE_PART_HITS,
+            "Number of object parts read from the local cache.",
             stats.object_store_cache_part_hits.clone(),
         );
         registry.register(
             OBJECT_STORE_CACHE_PART_ACCESS,
+            "Number of object parts requested from the local cache.",
             stats.object_store_cache_part_access.clone(),
         );
         registry.register(
             OBJECT_STORE_CACHE_KEYS,
+            "Number of object parts in the local cache.",
             stats.object_store_cache_keys.clone(),
         );
         registry.register(
             OBJECT_STORE_CACHE_BYTES,
+            "Size in bytes of the object parts in the local cache.",
             stats.object_store_cache_bytes.clone(),
         );
         registry.register(
             OBJECT_STORE_CACHE_EVICTED_BYTES,
+            "Size in bytes of the object parts evicted from the local cache.",
             stats.object_store_cache_evicted_bytes.clone(),
         );
         registry.register(
             OBJECT_STORE_CACHE_EVICTED_KEYS,
+            "Number of object parts evicted from the local cache.",
             stats.object_store_cache_evicted_keys.clone(),
         );
+        registry.register(
+            OBJECT_STORE_CACHE_FETCH_LATENCY_US,
+            "Latency in microseconds of fetching objects missing from the local cache.",
+            stats.object_store_cache_fetch_latency_us.clone(),
+        );
         stats
     }
 }
@@ -0,0 +1,663 @@
+//! # Change Stream
+//!
+//! This module implements change data capture (CDC) on top of the files the writer
+//! already persists. A [`ChangeStream`] returns every committed row change with a
+//! sequence number at or above the one it was opened at, in sequence number order,
+//! and then keeps following the database as new writes become durable.
+//!
+//! Changes are read from two places:
+//!
+//! - WAL SSTs, which hold every write in commit order. The stream tails them with the
+//!   same [`WalReplayIterator`] used to recover the memtable on startup.
+//! - L0 SSTs, for changes whose WAL SSTs have already been garbage collected (or that
+//!   were written with the WAL disabled). L0 SSTs are read from the oldest active
+//!   manifest that still holds the changes, so a checkpoint keeps its changes
+//!   readable for as long as it lives.
+//!
+//! A stream follows a single column family: [`crate::Db::subscribe`] returns the changes
+//! of the default column family and [`crate::Db::subscribe_cf`] those of a named one.
+//!
+//! Once L0 SSTs are compacted into sorted runs their history is gone. Opening a stream
+//! at a sequence number that is no longer retained fails with
+//! [`SlateDBError::ChangesNotRetained`].
+
+use std::collections::{HashSet, VecDeque};
+use std::ops::{Bound, RangeBounds};
+use std::sync::Arc;
+use std::time::Duration;
+
+use bytes::Bytes;
+
+use crate::clock::SystemClock;
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+use crate::db_state::{ColumnFamilyState, CoreDbState, SsTableHandle, SsTableId};
+use crate::error::SlateDBError;
+use crate::iter::KeyValueIterator;
+use crate::manifest::store::ManifestStore;
+use crate::range_tombstone::RangeTombstone;
+use crate::sst_iter::{SstIterator, SstIteratorOptions};
+use crate::tablestore::TableStore;
+use crate::types::{RowEntry, ValueDeletable};
+use crate::utils::WatchableOnceCellReader;
+use crate::wal_replay::{WalReplayIterator, WalReplayOptions};
+
+/// The maximum number of WAL SSTs loaded into the stream's buffer at once.
+const MAX_WAL_SSTS_PER_FETCH: u64 = 16;
+
+/// The value written by a [`RowChange`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChangeValue {
+    /// The key was set to this value.
+    Value(Bytes),
+    /// A merge operand was written for the key.
+    Merge(Bytes),
+    /// The key was deleted.
+    Tombstone,
+    /// Every key in the range was deleted by a range delete.
+    RangeDelete {
+        /// The start bound of the deleted range.
+        start: Bound<Bytes>,
+        /// The end bound of the deleted range.
+        end: Bound<Bytes>,
+    },
+}
+
+/// A committed write to a single key, as returned by a [`ChangeStream`].
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RowChange {
+    /// The key that was written. For a [`ChangeValue::RangeDelete`], the start key of the
+    /// deleted range, or an empty key if the range has no start bound.
+    pub key: Bytes,
+    /// The value, merge operand or tombstone that was written.
+    pub value: ChangeValue,
+    /// The sequence number of the write batch the change was committed in.
+    pub seq: u64,
+    /// The time the change was written at, if known.
+    pub create_ts: Option<i64>,
+    /// The time the value expires at, if it has a TTL.
+    pub expire_ts: Option<i64>,
+}
+
+impl From<ValueDeletable> for ChangeValue {
+    fn from(value: ValueDeletable) -> Self {
+        match value {
+            ValueDeletable::Value(value) => ChangeValue::Value(value),
+            ValueDeletable::Merge(value) => ChangeValue::Merge(value),
+            ValueDeletable::Tombstone => ChangeValue::Tombstone,
+            ValueDeletable::BlobRef(_) => {
+                unreachable!("blob refs are resolved before changes are returned")
+            }
+        }
+    }
+}
+
+impl From<RangeTombstone> for RowChange {
+    fn from(tombstone: RangeTombstone) -> Self {
+        let start = tombstone.range.start_bound().cloned();
+        let key = match &start {
+            Bound::Included(key) | Bound::Excluded(key) => key.clone(),
+            Bound::Unbounded => Bytes::new(),
+        };
+        Self {
+            key,
+            value: ChangeValue::RangeDelete {
+                start,
+                end: tombstone.range.end_bound().cloned(),
+            },
+            seq: tombstone.seq,
+            create_ts: Some(tombstone.create_ts),
+            expire_ts: None,
+        }
+    }
+}
+
+impl From<RowEntry> for RowChange {
+    fn from(entry: RowEntry) -> Self {
+        Self {
+            key: entry.key,
+            value: entry.value.into(),
+            seq: entry.seq,
+            create_ts: entry.create_ts,
+            expire_ts: entry.expire_ts,
+        }
+    }
+}
+
+/// An async stream of the changes committed to a database, in sequence number order.
+///
+/// A stream is opened with [`crate::Db::subscribe`], [`crate::Db::subscribe_cf`] or
+/// [`crate::DbReader::subscribe`], and only returns the changes of one column family.
+/// Only durable changes are returned: with the WAL enabled a change shows up once its
+/// WAL SST has been written, and with the WAL disabled once its memtable has been
+/// flushed to L0. A range delete is returned as a single change with a
+/// [`ChangeValue::RangeDelete`] value, rather than as a change per deleted key.
+///
+/// To resume after a restart, open a new stream at the sequence number following the
+/// last change that was processed. Keep a checkpoint alive to make sure the changes
+/// are still retained when the stream is reopened.
+pub struct ChangeStream {
+    manifest_store: Arc<ManifestStore>,
+    table_store: Arc<TableStore>,
+    system_clock: Arc<dyn SystemClock>,
+    poll_interval: Duration,
+    closed_result: WatchableOnceCellReader<Result<(), SlateDBError>>,
+    /// The id of the column family whose changes are returned.
+    column_family: u32,
+    /// The lowest sequence number that has not been loaded into `buffer` yet.
+    next_seq: u64,
+    /// The next WAL SST to read, once the stream has found its place in the WAL.
+    next_wal_id: Option<u64>,
+    /// L0 SSTs that remain to be read, oldest last.
+    pending_l0: VecDeque<SsTableHandle>,
+    /// The last sequence number held by the L0 SSTs in `pending_l0`.
+    l0_last_seq: u64,
+    /// Whether the oldest SST in `pending_l0` must be checked for missing history.
+    check_l0_retention: bool,
+    /// L0 SSTs that have already been read.
+    read_l0: HashSet<SsTableId>,
+    buffer: VecDeque<RowChange>,
+}
+
+impl ChangeStream {
+    pub(crate) fn new(
+        column_family: u32,
+        from_seq: u64,
+        manifest_store: Arc<ManifestStore>,
+        table_store: Arc<TableStore>,
+        system_clock: Arc<dyn SystemClock>,
+        poll_interval: Duration,
+        closed_result: WatchableOnceCellReader<Result<(), SlateDBError>>,
+    ) -> Self {
+        Self {
+            manifest_store,
+            table_store,
+            system_clock,
+            poll_interval,
+            closed_result,
+            column_family,
+            next_seq: from_seq,
+            next_wal_id: None,
+            pending_l0: VecDeque::new(),
+            l0_last_seq: 0,
+            check_l0_retention: false,
+            read_l0: HashSet::new(),
+            buffer: VecDeque::new(),
+        }
+    }
+
+    /// Returns the next committed change, waiting for one to become durable if the
+    /// stream has caught up with the database. Returns `None` once the database (or
+    /// reader) that opened the stream is closed.
+    ///
+    /// ## Errors
+    /// - `Error` with kind [`crate::ErrorKind::Invalid`]: if the changes at the stream's
+    ///   position are no longer retained.
+    /// - `Error`: if there was an error reading the WAL, L0 SSTs or manifests.
+    pub async fn next(&mut self) -> Result<Option<RowChange>, crate::Error> {
+        self.next_change().await.map_err(Into::into)
+    }
+
+    /// Returns the sequence number the stream will return changes from next.
+    pub fn position(&self) -> u64 {
+        self.buffer
+            .front()
+            .map_or(self.next_seq, |change| change.seq)
+    }
+
+    async fn next_change(&mut self) -> Result<Option<RowChange>, SlateDBError> {
+        loop {
+            if let Some(change) = self.buffer.pop_front() {
+                return Ok(Some(change));
+            }
+            if let Some(result) = self.closed_result.read() {
+                return result.map(|_| None);
+            }
+            if !self.fetch().await? {
+                self.system_clock.sleep(self.poll_interval).await;
+            }
+        }
+    }
+
+    /// Loads the next changes into the buffer. Returns false if the stream has caught
+    /// up and should wait before fetching again.
+    async fn fetch(&mut self) -> Result<bool, SlateDBError> {
+        if let Some(sst) = self.pending_l0.pop_back() {
+            self.read_l0_sst(sst).await?;
+            return Ok(true);
+        }
+
+        if let Some(next_wal_id) = self.next_wal_id {
+            let wal_ids = self
+                .table_store
+                .list_wal_ssts(next_wal_id..next_wal_id + MAX_WAL_SSTS_PER_FETCH)
+                .await?;
+            // only read WAL SSTs that directly follow the last one read. a gap means
+            // the next one was garbage collected, so the stream has to find its place
+            // again from the manifest.
+            let contiguous = wal_ids
+                .iter()
+                .zip(next_wal_id..)
+                .take_while(|(wal_sst, id)| wal_sst.id == SsTableId::Wal(*id))
+                .count() as u64;
+            if contiguous > 0 {
+                self.read_wal_ssts(next_wal_id, next_wal_id + contiguous)
+                    .await?;
+                return Ok(true);
+            }
+        }
+
+        self.seek_from_manifests().await
+    }
+
+    /// Finds where the changes from `next_seq` on are stored. Changes that have not
+    /// been flushed to L0 by the latest manifest are read from the WAL. Older changes
+    /// are read from the L0 SSTs of the oldest active manifest that still holds them.
+    async fn seek_from_manifests(&mut self) -> Result<bool, SlateDBError> {
+        let manifests = self.manifest_store.read_active_manifests().await?;
+        let latest = &manifests
+            .last_key_value()
+            .ok_or(SlateDBError::LatestTransactionalObjectVersionMissing)?
+            .1
+            .core;
+
+        if self.next_seq > latest.last_l0_seq {
+            let wal_id = latest.replay_after_wal_id + 1;
+            let wal_id = self.next_wal_id.map_or(wal_id, |id| id.max(wal_id));
+            let moved = self.next_wal_id != Some(wal_id);
+            self.next_wal_id = Some(wal_id);
+            return Ok(moved);
+        }
+
+        let core = manifests
+            .values()
+            .map(|manifest| &manifest.core)
+            .filter(|core| core.last_l0_seq >= self.next_seq)
+            .min_by_key(|core| core.last_l0_seq)
+            .unwrap_or(latest);
+        self.seek_to_l0(core)?;
+        Ok(true)
+    }
+
+    fn seek_to_l0(&mut self, core: &CoreDbState) -> Result<(), SlateDBError> {
+        let l0 = core.column_family_l0(self.column_family);
+        let l0_ids: HashSet<SsTableId> = l0.iter().map(|sst| sst.id).collect();
+        self.read_l0.retain(|id| l0_ids.contains(id));
+        // if none of the manifest's L0 SSTs were read before, nothing is known about the
+        // changes between the stream's position and its oldest L0 SST, which might have
+        // been compacted already.
+        self.check_l0_retention = self.read_l0.is_empty()
+            && core
+                .column_family_l0_last_compacted(self.column_family)
+                .is_some();
+        self.pending_l0 = l0
+            .iter()
+            .filter(|sst| !self.read_l0.contains(&sst.id))
+            .cloned()
+            .collect();
+        self.l0_last_seq = core.last_l0_seq;
+        self.next_wal_id = Some(core.replay_after_wal_id + 1);
+
+        if self.pending_l0.is_empty() {
+            if self.check_l0_retention {
+                return Err(SlateDBError::ChangesNotRetained { seq: self.next_seq });
+            }
+            self.next_seq = self.next_seq.max(self.l0_last_seq + 1);
+        }
+        Ok(())
+    }
+
+    async fn read_l0_sst(&mut self, sst: SsTableHandle) -> Result<(), SlateDBError> {
+        let id = sst.id;
+        let range_tombstones = sst.range_tombstones();
+        let mut entries = Vec::new();
+        if let Some(mut iter) = SstIterator::new_owned_initialized(
+            ..,
+            sst,
+            Arc::clone(&self.table_store),
+            SstIteratorOptions::default(),
+        )
+        .await?
+        {
+            while let Some(entry) = iter.next_entry().await? {
+                entries.push(entry);
+            }
+        }
+
+        if self.check_l0_retention {
+            let min_seq = entries
+                .iter()
+                .map(|entry| entry.seq)
+                .chain(range_tombstones.iter().map(|tombstone| tombstone.seq))
+                .min();
+            if let Some(min_seq) = min_seq {
+                if min_seq > self.next_seq {
+                    return Err(SlateDBError::ChangesNotRetained { seq: self.next_seq });
+                }
+                self.check_l0_retention = false;
+            }
+        }
+
+        let changes = entries
+            .into_iter()
+            .filter(|entry| entry.seq <= self.l0_last_seq)
+            .map(RowChange::from)
+            .chain(
+                range_tombstones
+                    .into_iter()
+                    .filter(|tombstone| tombstone.seq <= self.l0_last_seq)
+                    .map(RowChange::from),
+            )
+            .collect();
+        self.buffer_changes(changes);
+        self.read_l0.insert(id);
+        if self.pending_l0.is_empty() {
+            self.next_seq = self.next_seq.max(self.l0_last_seq + 1);
+        }
+        Ok(())
+    }
+
+    async fn read_wal_ssts(&mut self, start_id: u64, end_id: u64) -> Result<(), SlateDBError> {
+        // replay into a table with just the stream's column family
+        let mut core = CoreDbState::new();
+        if self.column_family != DEFAULT_COLUMN_FAMILY_ID {
+            core.column_families
+                .push(ColumnFamilyState::new(self.column_family, String::new()));
+        }
+        let mut replay_iter = WalReplayIterator::range(
+            start_id..end_id,
+            &core,
+            WalReplayOptions::default(),
+            Arc::clone(&self.table_store),
+        )
+        .await?;
+        let mut changes = Vec::new();
+        while let Some(replayed) = replay_iter.next().await? {
+            let table = if self.column_family == DEFAULT_COLUMN_FAMILY_ID {
+                Arc::clone(replayed.table.table())
+            } else {
+                match replayed.table.column_families().get(&self.column_family) {
+                    Some(table) => Arc::clone(table),
+                    None => continue,
+                }
+            };
+            let mut iter = table.iter();
+            while let Some(entry) = iter.next_entry_sync() {
+                changes.push(RowChange::from(entry));
+            }
+            changes.extend(table.range_tombstones().into_iter().map(RowChange::from));
+        }
+
+        self.buffer_changes(changes);
+        self.next_wal_id = Some(end_id);
+        Ok(())
+    }
+
+    /// Adds the changes at or after `next_seq` to the buffer in sequence number order.
+    fn buffer_changes(&mut self, mut changes: Vec<RowChange>) {
+        changes.retain(|change| change.seq >= self.next_seq);
+        changes.sort_by(|a, b| a.seq.cmp(&b.seq).then_with(|| a.key.cmp(&b.key)));
+        if let Some(last) = changes.last() {
+            self.next_seq = last.seq + 1;
+        }
+        self.buffer.extend(changes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use object_store::memory::InMemory;
+    use object_store::ObjectStore;
+
+    use super::*;
+    use crate::config::{FlushOptions, FlushType, Settings};
+    use crate::ColumnFamilyOptions;
+    use crate::{Db, DbReader};
+
+    fn test_settings() -> Settings {
+        Settings {
+            flush_interval: Some(Duration::from_millis(10)),
+            manifest_poll_interval: Duration::from_millis(10),
+            compactor_options: None,
+            ..Settings::default()
+        }
+    }
+
+    async fn next_change(stream: &mut ChangeStream) -> RowChange {
+        tokio::time::timeout(Duration::from_secs(10), stream.next())
+            .await
+            .expect("timed out waiting for change")
+            .unwrap()
+            .expect("stream ended")
+    }
+
+    #[tokio::test]
+    async fn should_stream_changes_from_wal() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_change_stream", object_store)
+            .with_settings(test_settings())
+            .build()
+            .await
+            .unwrap();
+        db.put(b"a", b"1").await.unwrap();
+        db.put(b"b", b"2").await.unwrap();
+        db.delete(b"a").await.unwrap();
+
+        let mut stream = db.subscribe(0).await.unwrap();
+
+        let first = next_change(&mut stream).await;
+        assert_eq!(first.key, Bytes::from_static(b"a"));
+        assert_eq!(first.value, ChangeValue::Value(Bytes::from_static(b"1")));
+        let second = next_change(&mut stream).await;
+        assert_eq!(second.key, Bytes::from_static(b"b"));
+        assert_eq!(second.value, ChangeValue::Value(Bytes::from_static(b"2")));
+        assert!(second.seq > first.seq);
+        let third = next_change(&mut stream).await;
+        assert_eq!(third.key, Bytes::from_static(b"a"));
+        assert_eq!(third.value, ChangeValue::Tombstone);
+        assert!(third.seq > second.seq);
+        assert_eq!(stream.position(), third.seq + 1);
+    }
+
+    #[tokio::test]
+    async fn should_stream_range_deletes() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_change_stream", object_store)
+            .with_settings(test_settings())
+            .build()
+            .await
+            .unwrap();
+        db.put(b"a", b"1").await.unwrap();
+        db.delete_range(b"a".as_slice()..b"c".as_slice())
+            .await
+            .unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let table_store = Arc::clone(&db.inner.table_store);
+        for wal_sst in table_store.list_wal_ssts(..).await.unwrap() {
+            table_store.delete_sst(&wal_sst.id).await.unwrap();
+        }
+        db.delete_range(b"x".as_slice()..).await.unwrap();
+
+        let mut stream = db.subscribe(0).await.unwrap();
+
+        assert_eq!(next_change(&mut stream).await.key, Bytes::from_static(b"a"));
+        // read from the L0 SST
+        let change = next_change(&mut stream).await;
+        assert_eq!(change.key, Bytes::from_static(b"a"));
+        assert_eq!(
+            change.value,
+            ChangeValue::RangeDelete {
+                start: Bound::Included(Bytes::from_static(b"a")),
+                end: Bound::Excluded(Bytes::from_static(b"c")),
+            }
+        );
+        // read from the WAL
+        let change = next_change(&mut stream).await;
+        assert_eq!(change.key, Bytes::from_static(b"x"));
+        assert_eq!(
+            change.value,
+            ChangeValue::RangeDelete {
+                start: Bound::Included(Bytes::from_static(b"x")),
+                end: Bound::Unbounded,
+            }
+        );
+    }
+
+    #[tokio::test]
+    async fn should_stream_changes_of_column_family() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_change_stream", object_store)
+            .with_settings(test_settings())
+            .with_column_family("users", ColumnFamilyOptions::default())
+            .build()
+            .await
+            .unwrap();
+        let users = db.column_family("users").unwrap();
+        db.put(b"a", b"1").await.unwrap();
+        db.put_cf(&users, b"b", b"2").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let table_store = Arc::clone(&db.inner.table_store);
+        for wal_sst in table_store.list_wal_ssts(..).await.unwrap() {
+            table_store.delete_sst(&wal_sst.id).await.unwrap();
+        }
+        db.put(b"c", b"3").await.unwrap();
+        db.put_cf(&users, b"d", b"4").await.unwrap();
+
+        let mut default_stream = db.subscribe(0).await.unwrap();
+        let mut users_stream = db.subscribe_cf(&users, 0).await.unwrap();
+
+        // the first change of each stream is read from L0, the second from the WAL
+        assert_eq!(
+            next_change(&mut default_stream).await.key,
+            Bytes::from_static(b"a")
+        );
+        assert_eq!(
+            next_change(&mut default_stream).await.key,
+            Bytes::from_static(b"c")
+        );
+        assert_eq!(
+            next_change(&mut users_stream).await.key,
+            Bytes::from_static(b"b")
+        );
+        assert_eq!(
+            next_change(&mut users_stream).await.key,
+            Bytes::from_static(b"d")
+        );
+    }
+
+    #[tokio::test]
+    async fn should_start_from_seq() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_change_stream", object_store)
+            .with_settings(test_settings())
+            .build()
+            .await
+            .unwrap();
+        db.put(b"a", b"1").await.unwrap();
+        db.put(b"b", b"2").await.unwrap();
+        let mut stream = db.subscribe(0).await.unwrap();
+        next_change(&mut stream).await;
+        let second = next_change(&mut stream).await;
+
+        let mut stream = db.subscribe(second.seq).await.unwrap();
+
+        assert_eq!(next_change(&mut stream).await, second);
+    }
+
+    #[tokio::test]
+    async fn should_tail_new_writes() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_change_stream", object_store)
+            .with_settings(test_settings())
+            .build()
+            .await
+            .unwrap();
+        let mut stream = db.subscribe(0).await.unwrap();
+
+        db.put(b"key", b"value").await.unwrap();
+
+        let change = next_change(&mut stream).await;
+        assert_eq!(change.key, Bytes::from_static(b"key"));
+        assert_eq!(
+            change.value,
+            ChangeValue::Value(Bytes::from_static(b"value"))
+        );
+    }
+
+    #[tokio::test]
+    async fn should_read_l0_after_wal_is_deleted() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_change_stream", object_store)
+            .with_settings(test_settings())
+            .build()
+            .await
+            .unwrap();
+        db.put(b"a", b"1").await.unwrap();
+        db.put(b"b", b"2").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let table_store = Arc::clone(&db.inner.table_store);
+        for wal_sst in table_store.list_wal_ssts(..).await.unwrap() {
+            table_store.delete_sst(&wal_sst.id).await.unwrap();
+        }
+        db.put(b"c", b"3").await.unwrap();
+
+        let mut stream = db.subscribe(0).await.unwrap();
+
+        let keys = [
+            next_change(&mut stream).await.key,
+            next_change(&mut stream).await.key,
+            next_change(&mut stream).await.key,
+        ];
+        assert_eq!(
+            keys,
+            [
+                Bytes::from_static(b"a"),
+                Bytes::from_static(b"b"),
+                Bytes::from_static(b"c")
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn should_end_stream_when_reader_is_closed() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_change_stream", object_store.clone())
+            .with_settings(test_settings())
+            .build()
+            .await
+            .unwrap();
+        db.put(b"key", b"value").await.unwrap();
+        let reader = DbReader::open(
+            "/tmp/test_change_stream",
+            object_store,
+            None,
+            Default::default(),
+        )
+        .await
+        .unwrap();
+        let mut stream = reader.subscribe(0).await.unwrap();
+        assert_eq!(
+            next_change(&mut stream).await.key,
+            Bytes::from_static(b"key")
+        );
+
+        reader.close().await.unwrap();
+
+        assert_eq!(stream.next().await.unwrap(), None);
+    }
+}
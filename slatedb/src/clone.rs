@@ -2,6 +2,7 @@ use crate::checkpoint::Checkpoint;
 use crate::clock::SystemClock;
 use crate::config::CheckpointOptions;
 use crate::db_state::{CoreDbState, SsTableId};
+use crate::encryption::EncryptionProviderType;
 use crate::error::SlateDBError;
 use crate::error::SlateDBError::CheckpointMissing;
 use crate::manifest::store::{ManifestStore, StoredManifest};
@@ -23,6 +24,7 @@ pub(crate) async fn create_clone<P: Into<Path>>(
     fp_registry: Arc<FailPointRegistry>,
     system_clock: Arc<dyn SystemClock>,
     rand: Arc<DbRand>,
+    encryption_provider: Option<EncryptionProviderType>,
 ) -> Result<(), SlateDBError> {
     let clone_path = clone_path.into();
     let parent_path = parent_path.into();
@@ -31,15 +33,17 @@ pub(crate) async fn create_clone<P: Into<Path>>(
         return Err(SlateDBError::IdenticalClonePaths(parent_path));
     }
 
-    let clone_manifest_store = Arc::new(ManifestStore::new(
+    let clone_manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
         &clone_path,
         object_store.clone(),
         system_clock.clone(),
+        encryption_provider.clone(),
     ));
-    let parent_manifest_store = Arc::new(ManifestStore::new(
+    let parent_manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
         &parent_path,
         object_store.clone(),
         system_clock.clone(),
+        encryption_provider.clone(),
     ));
     parent_manifest_store
         .validate_no_wal_object_store_configured()
@@ -54,6 +58,7 @@ pub(crate) async fn create_clone<P: Into<Path>>(
         system_clock.clone(),
         rand,
         fp_registry.clone(),
+        encryption_provider,
     )
     .await?;
 
@@ -84,6 +89,7 @@ async fn create_clone_manifest(
     system_clock: Arc<dyn SystemClock>,
     rand: Arc<DbRand>,
     #[allow(unused)] fp_registry: Arc<FailPointRegistry>,
+    encryption_provider: Option<EncryptionProviderType>,
 ) -> Result<StoredManifest, SlateDBError> {
     let clone_manifest = match StoredManifest::try_load(clone_manifest_store.clone()).await? {
         Some(initialized_clone_manifest) if initialized_clone_manifest.db_state().initialized => {
@@ -98,6 +104,7 @@ async fn create_clone_manifest(
                 &initialized_clone_manifest,
                 object_store.clone(),
                 system_clock.clone(),
+                encryption_provider,
             )
             .await?;
             return Ok(initialized_clone_manifest);
@@ -147,10 +154,11 @@ async fn create_clone_manifest(
         let external_db_manifest_store = if external_db.path == parent_path {
             parent_manifest_store.clone()
         } else {
-            Arc::new(ManifestStore::new(
+            Arc::new(ManifestStore::new_with_encryption_provider(
                 &external_db.path.clone().into(),
                 object_store.clone(),
                 system_clock.clone(),
+                encryption_provider.clone(),
             ))
         };
         let mut external_db_manifest =
@@ -240,6 +248,7 @@ async fn validate_external_dbs_contain_final_checkpoint(
     clone_manifest: &StoredManifest,
     object_store: Arc<dyn ObjectStore>,
     system_clock: Arc<dyn SystemClock>,
+    encryption_provider: Option<EncryptionProviderType>,
 ) -> Result<(), SlateDBError> {
     // Validate external dbs all contain the final checkpoint
     for external_db in &clone_manifest.manifest().external_dbs {
@@ -250,10 +259,11 @@ async fn validate_external_dbs_contain_final_checkpoint(
         let external_manifest_store = if external_db.path == parent_path {
             parent_manifest_store.clone()
         } else {
-            Arc::new(ManifestStore::new(
+            Arc::new(ManifestStore::new_with_encryption_provider(
                 &external_db.path.clone().into(),
                 object_store.clone(),
                 system_clock.clone(),
+                encryption_provider.clone(),
             ))
         };
         let external_manifest = external_manifest_store.read_latest_manifest().await?.1;
@@ -322,6 +332,7 @@ mod tests {
     use crate::db::Db;
     use crate::db_state::CoreDbState;
     use crate::error::SlateDBError;
+    use crate::iter::IterationOrder;
     use crate::manifest::store::{ManifestStore, StoredManifest};
     use crate::manifest::Manifest;
     use crate::proptest_util::{rng, sample};
@@ -360,6 +371,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             Arc::new(DefaultSystemClock::new()),
             Arc::new(DbRand::default()),
+            None,
         )
         .await
         .unwrap();
@@ -368,7 +380,8 @@ mod tests {
             .await
             .unwrap();
         let mut db_iter = clone_db.scan::<Vec<u8>, RangeFull>(..).await.unwrap();
-        test_utils::assert_ranged_db_scan(&table, .., &mut db_iter).await;
+        test_utils::assert_ranged_db_scan(&table, .., IterationOrder::Ascending, &mut db_iter)
+            .await;
         clone_db.close().await.unwrap();
     }
 
@@ -425,6 +438,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             Arc::new(DefaultSystemClock::new()),
             Arc::new(DbRand::default()),
+            None,
         )
         .await
         .unwrap();
@@ -435,7 +449,13 @@ mod tests {
             .await
             .unwrap();
         let mut db_iter = clone_db.scan::<Vec<u8>, RangeFull>(..).await.unwrap();
-        test_utils::assert_ranged_db_scan(&checkpoint_table, .., &mut db_iter).await;
+        test_utils::assert_ranged_db_scan(
+            &checkpoint_table,
+            ..,
+            IterationOrder::Ascending,
+            &mut db_iter,
+        )
+        .await;
         clone_db.close().await.unwrap();
     }
 
@@ -479,6 +499,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap_err();
@@ -542,6 +563,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap_err();
@@ -593,6 +615,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap_err();
@@ -622,6 +645,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap();
@@ -641,6 +665,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await?;
 
@@ -691,6 +716,7 @@ mod tests {
             Arc::clone(&fp_registry),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap_err();
@@ -705,6 +731,7 @@ mod tests {
             Arc::clone(&fp_registry),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap();
@@ -742,6 +769,7 @@ mod tests {
             Arc::clone(&fp_registry),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap_err();
@@ -772,6 +800,7 @@ mod tests {
             Arc::clone(&fp_registry),
             system_clock.clone(),
             rand.clone(),
+            None,
         )
         .await
         .unwrap_err();
@@ -802,6 +831,7 @@ mod tests {
             Arc::new(FailPointRegistry::new()),
             Arc::new(DefaultSystemClock::new()),
             Arc::new(DbRand::default()),
+            None,
         )
         .await;
         assert!(matches!(
@@ -0,0 +1,252 @@
+//! # Column Families
+//!
+//! A column family is a named keyspace inside a single database. Each column family
+//! has its own memtable, L0 SSTs and sorted runs, and its own [`ColumnFamilyOptions`]
+//! for the compression codec, bloom filter, default TTL and merge operator. Keys in
+//! different column families never conflict, even if they are equal.
+//!
+//! All column families share the WAL, the sequence number and the manifest, so a
+//! [`crate::WriteBatch`] (or [`crate::DBTransaction`]) that writes to several column
+//! families is still applied atomically. When a database has column families, every
+//! row key in its WAL SSTs is prefixed with the id of the column family it belongs
+//! to (see [`encode_key`]).
+//!
+//! Column families are declared with [`crate::DbBuilder::with_column_family`]. The
+//! first time a database is opened with a new column family, the column family is
+//! assigned an id and recorded in the manifest. The default column family, which is
+//! read and written by the regular [`crate::Db`] methods, has id 0.
+
+use std::collections::HashMap;
+
+use bytes::{BufMut, Bytes, BytesMut};
+
+use crate::compaction_filter::CompactionFilterType;
+use crate::config::CompressionCodec;
+use crate::db_state::CoreDbState;
+use crate::error::SlateDBError;
+use crate::merge_operator::MergeOperatorType;
+use crate::sst::SsTableFormat;
+
+/// The id of the default column family.
+pub(crate) const DEFAULT_COLUMN_FAMILY_ID: u32 = 0;
+
+const COLUMN_FAMILY_ID_SIZE: usize = std::mem::size_of::<u32>();
+
+/// A handle to a column family of a database, returned by [`crate::Db::column_family`].
+///
+/// # Examples
+///
+/// ```
+/// use slatedb::{ColumnFamilyOptions, Db, Error};
+/// use slatedb::object_store::{ObjectStore, memory::InMemory};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+///     let db = Db::builder("test_db", object_store)
+///         .with_column_family("users", ColumnFamilyOptions::default())
+///         .build()
+///         .await?;
+///     let users = db.column_family("users")?;
+///     db.put_cf(&users, b"key", b"value").await?;
+///     assert_eq!(db.get_cf(&users, b"key").await?, Some("value".into()));
+///     assert_eq!(db.get(b"key").await?, None);
+///     Ok(())
+/// }
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct ColumnFamily {
+    pub(crate) id: u32,
+    name: String,
+}
+
+impl ColumnFamily {
+    pub(crate) fn new(id: u32, name: String) -> Self {
+        Self { id, name }
+    }
+
+    /// Returns the name of the column family.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+}
+
+/// Options of a column family. They take the place of the matching database
+/// [`crate::Settings`] for the data of the column family.
+#[derive(Clone)]
+pub struct ColumnFamilyOptions {
+    /// The compression algorithm used for the SSTs of the column family.
+    pub compression_codec: Option<CompressionCodec>,
+    /// The number of bits per key of the bloom filters of the column family's SSTs.
+    pub filter_bits_per_key: u32,
+    /// The default time-to-live (TTL) for puts to the column family, in milliseconds.
+    pub default_ttl: Option<u64>,
+    /// The merge operator of the column family.
+    pub merge_operator: Option<MergeOperatorType>,
+    /// The compaction filter of the column family.
+    pub compaction_filter: Option<CompactionFilterType>,
+}
+
+impl Default for ColumnFamilyOptions {
+    fn default() -> Self {
+        Self {
+            compression_codec: None,
+            filter_bits_per_key: 10,
+            default_ttl: None,
+            merge_operator: None,
+            compaction_filter: None,
+        }
+    }
+}
+
+impl std::fmt::Debug for ColumnFamilyOptions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ColumnFamilyOptions")
+            .field("compression_codec", &self.compression_codec)
+            .field("filter_bits_per_key", &self.filter_bits_per_key)
+            .field("default_ttl", &self.default_ttl)
+            .field("merge_operator", &self.merge_operator.is_some())
+            .field("compaction_filter", &self.compaction_filter.is_some())
+            .finish()
+    }
+}
+
+impl ColumnFamilyOptions {
+    /// Sets the compression algorithm used for the SSTs of the column family.
+    pub fn with_compression_codec(self, compression_codec: Option<CompressionCodec>) -> Self {
+        Self {
+            compression_codec,
+            ..self
+        }
+    }
+
+    /// Sets the number of bits per key of the bloom filters of the column family's SSTs.
+    pub fn with_filter_bits_per_key(self, filter_bits_per_key: u32) -> Self {
+        Self {
+            filter_bits_per_key,
+            ..self
+        }
+    }
+
+    /// Sets the default time-to-live (TTL) for puts to the column family, in milliseconds.
+    pub fn with_default_ttl(self, default_ttl: Option<u64>) -> Self {
+        Self {
+            default_ttl,
+            ..self
+        }
+    }
+
+    /// Sets the merge operator of the column family.
+    pub fn with_merge_operator(self, merge_operator: MergeOperatorType) -> Self {
+        Self {
+            merge_operator: Some(merge_operator),
+            ..self
+        }
+    }
+
+    /// Sets the compaction filter of the column family.
+    pub fn with_compaction_filter(self, compaction_filter: CompactionFilterType) -> Self {
+        Self {
+            compaction_filter: Some(compaction_filter),
+            ..self
+        }
+    }
+
+    /// Returns the format of the column family's SSTs, based on the database's format.
+    pub(crate) fn sst_format(&self, base: &SsTableFormat) -> SsTableFormat {
+        SsTableFormat {
+            compression_codec: self.compression_codec,
+            filter_bits_per_key: self.filter_bits_per_key,
+            ..base.clone()
+        }
+    }
+}
+
+/// The column families a database was opened with, resolved against the ids
+/// recorded in the manifest.
+#[derive(Clone, Default)]
+pub(crate) struct ColumnFamilies {
+    handles: HashMap<String, ColumnFamily>,
+    options: HashMap<u32, ColumnFamilyOptions>,
+}
+
+impl ColumnFamilies {
+    /// Resolves the ids of the named column families. Column families missing from
+    /// `core` are ignored.
+    pub(crate) fn new(core: &CoreDbState, options: &HashMap<String, ColumnFamilyOptions>) -> Self {
+        let mut column_families = Self::default();
+        for state in &core.column_families {
+            if let Some(options) = options.get(&state.name) {
+                column_families.handles.insert(
+                    state.name.clone(),
+                    ColumnFamily::new(state.id, state.name.clone()),
+                );
+                column_families.options.insert(state.id, options.clone());
+            }
+        }
+        column_families
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.handles.is_empty()
+    }
+
+    pub(crate) fn handle(&self, name: &str) -> Result<ColumnFamily, SlateDBError> {
+        self.handles
+            .get(name)
+            .cloned()
+            .ok_or_else(|| SlateDBError::UnknownColumnFamily(name.to_string()))
+    }
+
+    pub(crate) fn options(&self, id: u32) -> Option<&ColumnFamilyOptions> {
+        self.options.get(&id)
+    }
+
+    pub(crate) fn merge_operators(&self) -> HashMap<u32, MergeOperatorType> {
+        self.options
+            .iter()
+            .filter_map(|(id, options)| options.merge_operator.clone().map(|op| (*id, op)))
+            .collect()
+    }
+}
+
+/// Prefixes `key` with the big-endian id of its column family. This is how keys are
+/// stored in the WAL SSTs of databases with column families.
+pub(crate) fn encode_key(column_family: u32, key: &[u8]) -> Bytes {
+    let mut encoded = BytesMut::with_capacity(COLUMN_FAMILY_ID_SIZE + key.len());
+    encoded.put_u32(column_family);
+    encoded.put_slice(key);
+    encoded.freeze()
+}
+
+/// Splits a key encoded with [`encode_key`] into its column family id and user key.
+pub(crate) fn decode_key(key: &Bytes) -> Result<(u32, Bytes), SlateDBError> {
+    if key.len() < COLUMN_FAMILY_ID_SIZE {
+        return Err(SlateDBError::InvalidColumnFamilyKey(key.clone()));
+    }
+    let mut id = [0; COLUMN_FAMILY_ID_SIZE];
+    id.copy_from_slice(&key[..COLUMN_FAMILY_ID_SIZE]);
+    Ok((u32::from_be_bytes(id), key.slice(COLUMN_FAMILY_ID_SIZE..)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn should_encode_and_decode_key() {
+        let encoded = encode_key(7, b"key");
+        assert_eq!(encoded.as_ref(), b"\x00\x00\x00\x07key");
+        assert_eq!(decode_key(&encoded).unwrap(), (7, Bytes::from("key")));
+    }
+
+    #[test]
+    fn should_reject_key_without_column_family_id() {
+        let result = decode_key(&Bytes::from_static(b"ab"));
+        assert!(matches!(
+            result,
+            Err(SlateDBError::InvalidColumnFamilyKey(_))
+        ));
+    }
+}
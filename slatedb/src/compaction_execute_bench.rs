@@ -16,6 +16,7 @@ use ulid::Ulid;
 
 use crate::bytes_generator::OrderedBytesGenerator;
 use crate::clock::{DefaultSystemClock, SystemClock};
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
 use crate::compactor::stats::CompactionStats;
 use crate::compactor::CompactorMessage;
 use crate::compactor_executor::{
@@ -257,6 +258,8 @@ impl CompactionExecuteBench {
             compaction_logical_clock_tick: manifest.db_state().last_l0_clock_tick,
             retention_min_seq: Some(manifest.db_state().recent_snapshot_min_seq),
             is_dest_last_run,
+            column_family: DEFAULT_COLUMN_FAMILY_ID,
+            output_ssts: vec![],
         })
     }
 
@@ -294,6 +297,8 @@ impl CompactionExecuteBench {
             compaction_logical_clock_tick: state.last_l0_clock_tick,
             retention_min_seq: Some(state.recent_snapshot_min_seq),
             is_dest_last_run,
+            column_family: DEFAULT_COLUMN_FAMILY_ID,
+            output_ssts: vec![],
         }
     }
 
@@ -336,6 +341,8 @@ impl CompactionExecuteBench {
             self.system_clock.clone(),
             manifest_store.clone(),
             None,
+            None,
+            HashMap::new(),
         );
 
         let manifest = StoredManifest::load(manifest_store).await?;
@@ -0,0 +1,57 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+
+/// The decision of a [`CompactionFilter`] for a row.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum CompactionFilterDecision {
+    /// Keep the row unchanged.
+    Keep,
+    /// Drop the row. The compactor writes a tombstone in its place, so older versions of
+    /// the key in lower levels of the LSM tree are not revived.
+    Drop,
+    /// Replace the value of the row.
+    Replace(Bytes),
+}
+
+/// A trait for implementing custom rules to expire or rewrite rows during compaction.
+///
+/// The compactor calls the filter for every value that survives the retention policy
+/// (TTL, snapshots, and history retention) of a compaction. Tombstones and merge operands
+/// that could not be merged are not passed to the filter.
+///
+/// Since rows are only filtered when they are compacted, readers can still see a row the
+/// filter would drop until the compaction of the SST that contains it.
+///
+/// # Examples
+/// Here's an example of a filter that drops soft-deleted rows:
+/// ```
+/// use bytes::Bytes;
+/// use slatedb::{CompactionFilter, CompactionFilterDecision};
+///
+/// struct SoftDeleteFilter;
+///
+/// impl CompactionFilter for SoftDeleteFilter {
+///     fn filter(&self, _key: &Bytes, value: &Bytes) -> CompactionFilterDecision {
+///         if value.starts_with(b"deleted:") {
+///             CompactionFilterDecision::Drop
+///         } else {
+///             CompactionFilterDecision::Keep
+///         }
+///     }
+/// }
+/// ```
+pub trait CompactionFilter {
+    /// Decides whether a row is kept, dropped, or rewritten by the compaction.
+    ///
+    /// # Arguments
+    /// * `key` - The key of the row
+    /// * `value` - The value of the row
+    ///
+    /// # Returns
+    /// * The [`CompactionFilterDecision`] for the row
+    fn filter(&self, key: &Bytes, value: &Bytes) -> CompactionFilterDecision;
+}
+
+pub(crate) type CompactionFilterType = Arc<dyn CompactionFilter + Send + Sync>;
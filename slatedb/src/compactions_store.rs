@@ -0,0 +1,355 @@
+//! Durable storage for the compactor's in-progress compactions (RFC-0013).
+//!
+//! The compactions are stored as a transactional object in the `compactor` directory next
+//! to the manifest, using the same sequentially numbered files as the manifest
+//! (`compactor/00000000000000000001.compactor`, ...).
+//! The compactor rewrites the object when it starts a compaction, when a compaction writes an
+//! output SST, and when a compaction finishes, so a restarted compactor can pick its
+//! compactions up from the last output SST instead of starting over.
+
+use crate::clock::SystemClock;
+use crate::compactor_state::{Compaction, Compactions};
+use crate::encryption::{EncryptedObjectCodec, EncryptionProviderType};
+use crate::error::SlateDBError;
+use crate::flatbuffer_types::FlatBufferCompactionsCodec;
+use crate::transactional_object::object_store::ObjectStoreSequencedStorageProtocol;
+use crate::transactional_object::{
+    DirtyObject, FenceableTransactionalObject, GenericObjectMetadata, MonotonicId,
+    SequencedStorageProtocol, SimpleTransactionalObject, TransactionalObject,
+    TransactionalStorageProtocol,
+};
+use log::debug;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use std::ops::RangeBounds;
+use std::sync::Arc;
+use std::time::Duration;
+use ulid::Ulid;
+
+// This type wraps StoredCompactions, and fences other compactors by incrementing the
+// compactor epoch when initialized. It also detects when the current compactor has been
+// fenced and fails all operations with SlateDBError::Fenced.
+pub(crate) struct FenceableCompactions {
+    inner: FenceableTransactionalObject<Compactions>,
+}
+
+impl FenceableCompactions {
+    pub(crate) async fn init(
+        stored_compactions: StoredCompactions,
+        compactions_update_timeout: Duration,
+        system_clock: Arc<dyn SystemClock>,
+    ) -> Result<Self, SlateDBError> {
+        let inner = FenceableTransactionalObject::init(
+            stored_compactions.inner,
+            compactions_update_timeout,
+            system_clock,
+            |c: &Compactions| c.compactor_epoch,
+            |c: &mut Compactions, e: u64| c.compactor_epoch = e,
+        )
+        .await?;
+        Ok(Self { inner })
+    }
+
+    pub(crate) fn compactions(&self) -> &Compactions {
+        self.inner.object()
+    }
+
+    pub(crate) fn prepare_dirty(&self) -> Result<DirtyObject<Compactions>, SlateDBError> {
+        Ok(self.inner.prepare_dirty()?)
+    }
+
+    pub(crate) async fn update(
+        &mut self,
+        dirty: DirtyObject<Compactions>,
+    ) -> Result<(), SlateDBError> {
+        Ok(self.inner.update(dirty).await?)
+    }
+
+    pub(crate) async fn refresh(&mut self) -> Result<&Compactions, SlateDBError> {
+        Ok(self.inner.refresh().await?)
+    }
+}
+
+// Represents the compactions stored in the object store. Like StoredManifest, this type
+// tracks the current contents and id of the stored object, and updates are written with
+// the next consecutive id, conditional on no other compactor having written that id.
+pub(crate) struct StoredCompactions {
+    inner: SimpleTransactionalObject<Compactions>,
+}
+
+impl StoredCompactions {
+    /// Write the initial (empty) compactions object. Fails with
+    /// [`SlateDBError::TransactionalObjectVersionExists`] if one already exists.
+    pub(crate) async fn create(store: Arc<CompactionsStore>) -> Result<Self, SlateDBError> {
+        let inner = SimpleTransactionalObject::<Compactions>::init(
+            Arc::clone(&store.inner)
+                as Arc<dyn TransactionalStorageProtocol<Compactions, MonotonicId>>,
+            Compactions::new(0),
+        )
+        .await?;
+        Ok(Self { inner })
+    }
+
+    /// Load the current compactions from the supplied store. If no compactions object
+    /// has been written yet then this fn returns None.
+    pub(crate) async fn try_load(
+        store: Arc<CompactionsStore>,
+    ) -> Result<Option<Self>, SlateDBError> {
+        Ok(
+            SimpleTransactionalObject::<Compactions>::try_load(Arc::clone(&store.inner)
+                as Arc<dyn TransactionalStorageProtocol<Compactions, MonotonicId>>)
+            .await?
+            .map(|inner| Self { inner }),
+        )
+    }
+
+    /// Load the current compactions, or write the initial (empty) object if none exists.
+    pub(crate) async fn load_or_create(store: Arc<CompactionsStore>) -> Result<Self, SlateDBError> {
+        loop {
+            if let Some(stored) = Self::try_load(store.clone()).await? {
+                return Ok(stored);
+            }
+            match Self::create(store.clone()).await {
+                // another compactor created it concurrently, load it on the next iteration
+                Err(SlateDBError::TransactionalObjectVersionExists) => continue,
+                result => return result,
+            }
+        }
+    }
+
+    #[cfg(test)]
+    pub(crate) fn compactions(&self) -> &Compactions {
+        self.inner.object()
+    }
+
+    /// Refreshes the in-memory view with the latest compactions stored in the object store.
+    pub(crate) async fn refresh(&mut self) -> Result<&Compactions, SlateDBError> {
+        Ok(self.inner.refresh().await?)
+    }
+
+    /// Appends a compaction to the submitted compactions, for the compactor to validate and
+    /// start on its next poll. Retries on version conflicts with the compactor or other
+    /// clients.
+    pub(crate) async fn submit_compaction(
+        &mut self,
+        compaction: Compaction,
+    ) -> Result<(), SlateDBError> {
+        Ok(self
+            .inner
+            .maybe_apply_update(|stored| {
+                let mut dirty = stored.prepare_dirty()?;
+                dirty.value.submitted.push(compaction.clone());
+                Ok::<_, SlateDBError>(Some(dirty))
+            })
+            .await?)
+    }
+
+    /// Removes a compaction from the submitted compactions if the compactor hasn't started
+    /// or rejected it yet. Retries on version conflicts with the compactor or other clients.
+    pub(crate) async fn withdraw_compaction(&mut self, id: Ulid) -> Result<(), SlateDBError> {
+        Ok(self
+            .inner
+            .maybe_apply_update(|stored| {
+                if !stored.object().submitted.iter().any(|c| c.id() == id) {
+                    return Ok(None);
+                }
+                let mut dirty = stored.prepare_dirty()?;
+                dirty.value.submitted.retain(|c| c.id() != id);
+                Ok::<_, SlateDBError>(Some(dirty))
+            })
+            .await?)
+    }
+}
+
+pub(crate) struct CompactionsStore {
+    inner: Arc<dyn SequencedStorageProtocol<Compactions>>,
+}
+
+impl CompactionsStore {
+    #[cfg_attr(not(test), allow(dead_code))]
+    pub(crate) fn new(root_path: &Path, object_store: Arc<dyn ObjectStore>) -> Self {
+        Self::new_with_encryption_provider(root_path, object_store, None)
+    }
+
+    /// Creates a compactions store that encrypts compactions files with the current key
+    /// of `encryption_provider`, if set.
+    pub(crate) fn new_with_encryption_provider(
+        root_path: &Path,
+        object_store: Arc<dyn ObjectStore>,
+        encryption_provider: Option<EncryptionProviderType>,
+    ) -> Self {
+        let inner = Arc::new(ObjectStoreSequencedStorageProtocol::<Compactions>::new(
+            root_path,
+            object_store,
+            "compactor",
+            "compactor",
+            Box::new(EncryptedObjectCodec::new(
+                Box::new(FlatBufferCompactionsCodec {}),
+                encryption_provider,
+            )),
+        ));
+        Self { inner }
+    }
+
+    /// List the compactions files in the object store. The last element in an unbounded
+    /// range is the current compactions file.
+    pub(crate) async fn list_compactions<R: RangeBounds<u64>>(
+        &self,
+        id_range: R,
+    ) -> Result<Vec<GenericObjectMetadata>, SlateDBError> {
+        Ok(self
+            .inner
+            .list(
+                id_range.start_bound().map(|b| (*b).into()),
+                id_range.end_bound().map(|b| (*b).into()),
+            )
+            .await?)
+    }
+
+    /// Delete a compactions file from the object store. The current compactions file
+    /// cannot be deleted.
+    pub(crate) async fn delete_compactions(&self, id: u64) -> Result<(), SlateDBError> {
+        if let Some((latest_id, _)) = self.try_read_latest_compactions().await? {
+            if latest_id == id {
+                return Err(SlateDBError::InvalidDeletion);
+            }
+        }
+        debug!("deleting compactions [id={}]", id);
+        Ok(self.inner.delete(MonotonicId::new(id)).await?)
+    }
+
+    pub(crate) async fn try_read_latest_compactions(
+        &self,
+    ) -> Result<Option<(u64, Compactions)>, SlateDBError> {
+        Ok(self
+            .inner
+            .try_read_latest()
+            .await
+            .map(|opt| opt.map(|(id, compactions)| (id.into(), compactions)))?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::clock::DefaultSystemClock;
+    use crate::compactions_store::{CompactionsStore, FenceableCompactions, StoredCompactions};
+    use crate::compactor_state::{Compaction, CompactionSpec, SourceId};
+    use crate::error::SlateDBError;
+    use object_store::memory::InMemory;
+    use object_store::path::Path;
+    use std::sync::Arc;
+    use std::time::Duration;
+    use ulid::Ulid;
+
+    const ROOT: &str = "/root/path";
+
+    fn new_memory_compactions_store() -> Arc<CompactionsStore> {
+        let os = Arc::new(InMemory::new());
+        Arc::new(CompactionsStore::new(&Path::from(ROOT), os))
+    }
+
+    #[tokio::test]
+    async fn test_should_create_compactions_once() {
+        let store = new_memory_compactions_store();
+        assert!(StoredCompactions::try_load(store.clone())
+            .await
+            .unwrap()
+            .is_none());
+
+        let created = StoredCompactions::load_or_create(store.clone())
+            .await
+            .unwrap();
+        let loaded = StoredCompactions::load_or_create(store.clone())
+            .await
+            .unwrap();
+
+        assert_eq!(created.compactions(), loaded.compactions());
+        assert_eq!(store.list_compactions(..).await.unwrap().len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_should_persist_compactions() {
+        let store = new_memory_compactions_store();
+        let stored = StoredCompactions::load_or_create(store.clone())
+            .await
+            .unwrap();
+        let mut fc = FenceableCompactions::init(
+            stored,
+            Duration::from_secs(300),
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .await
+        .unwrap();
+        let compaction = Compaction::new(
+            Ulid::new(),
+            CompactionSpec::new(vec![SourceId::SortedRun(1), SourceId::SortedRun(0)], 0),
+        );
+        let mut dirty = fc.prepare_dirty().unwrap();
+        dirty.value.compactions.push(compaction.clone());
+        fc.update(dirty).await.unwrap();
+
+        let (_, latest) = store.try_read_latest_compactions().await.unwrap().unwrap();
+
+        assert_eq!(latest.compactor_epoch, 1);
+        assert_eq!(latest.compactions, vec![compaction]);
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_update_when_fenced() {
+        let store = new_memory_compactions_store();
+        let clock = Arc::new(DefaultSystemClock::new());
+        let timeout = Duration::from_secs(300);
+        let mut fc1 = FenceableCompactions::init(
+            StoredCompactions::load_or_create(store.clone())
+                .await
+                .unwrap(),
+            timeout,
+            clock.clone(),
+        )
+        .await
+        .unwrap();
+        let fc2 = FenceableCompactions::init(
+            StoredCompactions::load_or_create(store.clone())
+                .await
+                .unwrap(),
+            timeout,
+            clock,
+        )
+        .await
+        .unwrap();
+        assert_eq!(fc2.compactions().compactor_epoch, 2);
+
+        let result = fc1.refresh().await;
+
+        assert!(matches!(result, Err(SlateDBError::Fenced)));
+    }
+
+    #[tokio::test]
+    async fn test_should_not_delete_latest_compactions() {
+        let store = new_memory_compactions_store();
+        let stored = StoredCompactions::load_or_create(store.clone())
+            .await
+            .unwrap();
+        FenceableCompactions::init(
+            stored,
+            Duration::from_secs(300),
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .await
+        .unwrap();
+
+        assert!(matches!(
+            store.delete_compactions(2).await,
+            Err(SlateDBError::InvalidDeletion)
+        ));
+        store.delete_compactions(1).await.unwrap();
+        let ids: Vec<u64> = store
+            .list_compactions(..)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id.into())
+            .collect();
+        assert_eq!(ids, vec![2]);
+    }
+}
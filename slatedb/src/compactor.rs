@@ -40,24 +40,32 @@
 //!
 //! Progress and GC safety:
 //! - Progress is tracked per compaction as `bytes_processed` (approximate, for observability).
-//! - The lowest start time among active compaction ids (ULIDs) is exported as a
-//!   “low-watermark” hint so GC can avoid deleting inputs required by running work.
+//! - In-flight compactions, along with the output SSTs they have written so far, are persisted
+//!   in the compactions store ([`crate::compactions_store`]) next to the manifest. A restarted
+//!   compactor resumes them after the last key they wrote, and GC keeps their output SSTs and
+//!   inputs.
+//! - The lowest start time among active compaction ids (ULIDs) is also exported as a
+//!   “low-watermark” hint for a GC running in the same process.
 //!
 //! High-level flow:
 //! 1) Poll manifest and merge remote state into local [`CompactorState`].
 //! 2) Ask the [`CompactionScheduler`] for candidate [`CompactionSpec`] values.
-//! 3) For each accepted spec, create a [`Compaction`] (id + spec), derive a
+//! 3) For each accepted spec, create a [`Compaction`] (id + spec), persist it, derive a
 //!    [`StartCompactionJobArgs`] with concrete inputs, and pass it to the executor.
-//! 4) Upon completion, update [`CompactorState`], write a new manifest, and repeat.
+//! 4) Persist each output SST the executor reports.
+//! 5) Upon completion, update [`CompactorState`], write a new manifest, remove the
+//!    persisted compaction, and repeat.
 //!
 //! These names are used consistently across modules so it is clear whether a type
 //! represents a description (Spec), a durable decision (Compaction), or a running
 //! attempt (JobSpec).
 
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use std::time::Duration;
 
 use async_trait::async_trait;
+use chrono::{DateTime, Utc};
 use futures::stream::BoxStream;
 use log::{debug, error, info, warn};
 use tokio::runtime::Handle;
@@ -65,18 +73,26 @@ use tracing::instrument;
 use ulid::Ulid;
 
 use crate::clock::SystemClock;
+use crate::column_family::ColumnFamilyOptions;
+use crate::compaction_filter::CompactionFilterType;
+use crate::compactions_store::{CompactionsStore, FenceableCompactions, StoredCompactions};
 use crate::compactor::stats::CompactionStats;
 use crate::compactor_executor::{
     CompactionExecutor, StartCompactionJobArgs, TokioCompactionExecutor,
 };
 use crate::compactor_state::{Compaction, CompactionSpec, CompactorState, SourceId};
+use crate::comparator::KeyComparator;
 use crate::config::{CheckpointOptions, CompactorOptions};
-use crate::db_state::SortedRun;
+use crate::db_state::{SortedRun, SsTableHandle};
 use crate::dispatcher::{MessageFactory, MessageHandler, MessageHandlerExecutor};
 use crate::error::{Error, SlateDBError};
+use crate::event_listener::{
+    CompactionFinishedEvent, CompactionStartedEvent, EventListeners, ManifestWrittenEvent,
+};
 use crate::manifest::store::{FenceableManifest, ManifestStore, StoredManifest};
 use crate::merge_operator::MergeOperatorType;
 use crate::rand::DbRand;
+use crate::remote_compaction::{CompactionJobRunner, RemoteCompactionExecutor};
 pub use crate::size_tiered_compaction::SizeTieredCompactionSchedulerSupplier;
 use crate::stats::StatRegistry;
 use crate::tablestore::TableStore;
@@ -160,10 +176,20 @@ pub(crate) enum CompactorMessage {
         /// The total number of bytes processed so far (estimate).
         bytes_processed: u64,
     },
+    /// Sent by the [`CompactionExecutor`] each time a compaction job finishes writing an
+    /// output SST, other than the last one.
+    CompactionJobOutput {
+        /// The job id that wrote the SST.
+        id: Ulid,
+        /// The finished output SST.
+        sst: Box<SsTableHandle>,
+    },
     /// Ticker-triggered message to log DB runs and in-flight job state.
     LogStats,
     /// Ticker-triggered message to refresh the manifest and schedule compactions.
     PollManifest,
+    /// Sent by [`crate::Db::update_settings`] to replace the compactor options.
+    UpdateOptions(Arc<CompactorOptions>),
 }
 
 /// The compactor is responsible for taking groups of sorted runs (this doc uses the term
@@ -189,35 +215,47 @@ pub(crate) enum CompactorMessage {
 /// scheduler supplied by [`crate::size_tiered_compaction::SizeTieredCompactionSchedulerSupplier`]
 ///
 /// The Executor does the actual work of compacting sorted runs by sort-merging them into a new
-/// sorted run. It implements the [`CompactionExecutor`] trait. Currently, the only implementation
-/// is the [`TokioCompactionExecutor`] which runs compaction on a local tokio runtime.
+/// sorted run. It implements the [`CompactionExecutor`] trait. The default implementation is
+/// the [`TokioCompactionExecutor`] which runs compaction on a local tokio runtime. When a
+/// [`CompactionJobRunner`] is configured, the [`RemoteCompactionExecutor`] hands jobs to the
+/// runner instead, e.g. to run them on other processes.
 #[derive(Clone)]
 #[allow(dead_code)]
 pub(crate) struct Compactor {
     manifest_store: Arc<ManifestStore>,
+    compactions_store: Arc<CompactionsStore>,
     table_store: Arc<TableStore>,
     options: Arc<CompactorOptions>,
     scheduler_supplier: Arc<dyn CompactionSchedulerSupplier>,
+    job_runner: Option<Arc<dyn CompactionJobRunner>>,
     task_executor: Arc<MessageHandlerExecutor>,
     compactor_runtime: Handle,
     rand: Arc<DbRand>,
     stats: Arc<CompactionStats>,
     system_clock: Arc<dyn SystemClock>,
     merge_operator: Option<MergeOperatorType>,
+    compaction_filter: Option<CompactionFilterType>,
+    column_families: HashMap<String, ColumnFamilyOptions>,
+    event_listeners: EventListeners,
 }
 
 impl Compactor {
     pub(crate) fn new(
         manifest_store: Arc<ManifestStore>,
+        compactions_store: Arc<CompactionsStore>,
         table_store: Arc<TableStore>,
         options: CompactorOptions,
         scheduler_supplier: Arc<dyn CompactionSchedulerSupplier>,
+        job_runner: Option<Arc<dyn CompactionJobRunner>>,
         compactor_runtime: Handle,
         rand: Arc<DbRand>,
         stat_registry: Arc<StatRegistry>,
         system_clock: Arc<dyn SystemClock>,
         closed_result: WatchableOnceCell<Result<(), SlateDBError>>,
         merge_operator: Option<MergeOperatorType>,
+        compaction_filter: Option<CompactionFilterType>,
+        column_families: HashMap<String, ColumnFamilyOptions>,
+        event_listeners: EventListeners,
     ) -> Self {
         let stats = Arc::new(CompactionStats::new(stat_registry));
         let task_executor = Arc::new(MessageHandlerExecutor::new(
@@ -226,15 +264,20 @@ impl Compactor {
         ));
         Self {
             manifest_store,
+            compactions_store,
             table_store,
             options: Arc::new(options),
             scheduler_supplier,
+            job_runner,
             task_executor,
             compactor_runtime,
             rand,
             stats,
             system_clock,
             merge_operator,
+            compaction_filter,
+            column_families,
+            event_listeners,
         }
     }
 
@@ -250,25 +293,38 @@ impl Compactor {
     pub async fn run_async_task(&self) -> Result<(), SlateDBError> {
         let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
         let scheduler = Arc::from(self.scheduler_supplier.compaction_scheduler(&self.options));
-        let executor = Arc::new(TokioCompactionExecutor::new(
-            self.compactor_runtime.clone(),
-            self.options.clone(),
-            tx,
-            self.table_store.clone(),
-            self.rand.clone(),
-            self.stats.clone(),
-            self.system_clock.clone(),
-            self.manifest_store.clone(),
-            self.merge_operator.clone(),
-        ));
+        let executor: Arc<dyn CompactionExecutor + Send + Sync> = match &self.job_runner {
+            Some(runner) => Arc::new(RemoteCompactionExecutor::new(
+                self.compactor_runtime.clone(),
+                runner.clone(),
+                tx,
+                self.stats.clone(),
+            )),
+            None => Arc::new(TokioCompactionExecutor::new(
+                self.compactor_runtime.clone(),
+                self.options.clone(),
+                tx,
+                self.table_store.clone(),
+                self.rand.clone(),
+                self.stats.clone(),
+                self.system_clock.clone(),
+                self.manifest_store.clone(),
+                self.merge_operator.clone(),
+                self.compaction_filter.clone(),
+                self.column_families.clone(),
+            )),
+        };
         let handler = CompactorEventHandler::new(
             self.manifest_store.clone(),
+            self.compactions_store.clone(),
             self.options.clone(),
             scheduler,
             executor,
             self.rand.clone(),
             self.stats.clone(),
             self.system_clock.clone(),
+            self.table_store.comparator().clone(),
+            self.event_listeners.clone(),
         )
         .await?;
         self.task_executor
@@ -301,12 +357,23 @@ impl Compactor {
 pub(crate) struct CompactorEventHandler {
     state: CompactorState,
     manifest: FenceableManifest,
+    compactions: FenceableCompactions,
+    /// Persisted compactions of an earlier compactor that have yet to be resumed.
+    pending_compactions: Vec<Compaction>,
+    /// Ids of submitted compactions that were started or rejected, and are removed from the
+    /// persisted submitted compactions on the next write.
+    handled_submissions: HashSet<Ulid>,
     options: Arc<CompactorOptions>,
     scheduler: Arc<dyn CompactionScheduler + Send + Sync>,
     executor: Arc<dyn CompactionExecutor + Send + Sync>,
     rand: Arc<DbRand>,
     stats: Arc<CompactionStats>,
     system_clock: Arc<dyn SystemClock>,
+    event_listeners: EventListeners,
+    /// The times the running compactions were started by this compactor.
+    compaction_start_times: HashMap<Ulid, DateTime<Utc>>,
+    /// Whether the poll interval changed since the tickers were created.
+    tickers_changed: bool,
 }
 
 #[async_trait]
@@ -324,10 +391,15 @@ impl MessageHandler<CompactorMessage> for CompactorEventHandler {
         ]
     }
 
+    fn tickers_changed(&mut self) -> bool {
+        std::mem::take(&mut self.tickers_changed)
+    }
+
     async fn handle(&mut self, message: CompactorMessage) -> Result<(), SlateDBError> {
         match message {
             CompactorMessage::LogStats => self.handle_log_ticker(),
             CompactorMessage::PollManifest => self.handle_ticker().await,
+            CompactorMessage::UpdateOptions(options) => self.update_options(options),
             CompactorMessage::CompactionJobFinished { id, result } => match result {
                 Ok(sr) => self
                     .finish_compaction(id, sr)
@@ -335,7 +407,9 @@ impl MessageHandler<CompactorMessage> for CompactorEventHandler {
                     .expect("fatal error finishing compaction"),
                 Err(err) => {
                     error!("error executing compaction [error={:#?}]", err);
-                    self.finish_failed_compaction(id);
+                    self.finish_failed_compaction(id)
+                        .await
+                        .expect("fatal error finishing failed compaction");
                 }
             },
             CompactorMessage::CompactionJobProgress {
@@ -345,6 +419,13 @@ impl MessageHandler<CompactorMessage> for CompactorEventHandler {
                 self.state
                     .update_compaction(&id, |c| c.set_bytes_processed(bytes_processed));
             }
+            CompactorMessage::CompactionJobOutput { id, sst } => {
+                self.state
+                    .update_compaction(&id, |c| c.add_output_sst(*sst));
+                self.write_compactions()
+                    .await
+                    .expect("fatal error persisting compaction output");
+            }
         }
         Ok(())
     }
@@ -364,33 +445,63 @@ impl MessageHandler<CompactorMessage> for CompactorEventHandler {
 impl CompactorEventHandler {
     pub(crate) async fn new(
         manifest_store: Arc<ManifestStore>,
+        compactions_store: Arc<CompactionsStore>,
         options: Arc<CompactorOptions>,
         scheduler: Arc<dyn CompactionScheduler + Send + Sync>,
         executor: Arc<dyn CompactionExecutor + Send + Sync>,
         rand: Arc<DbRand>,
         stats: Arc<CompactionStats>,
         system_clock: Arc<dyn SystemClock>,
+        comparator: KeyComparator,
+        event_listeners: EventListeners,
     ) -> Result<Self, SlateDBError> {
         let stored_manifest = StoredManifest::load(manifest_store.clone()).await?;
+        comparator.check_matches(stored_manifest.db_state().comparator.as_deref())?;
         let manifest = FenceableManifest::init_compactor(
             stored_manifest,
             options.manifest_update_timeout,
             system_clock.clone(),
         )
         .await?;
-        let state = CompactorState::new(manifest.prepare_dirty()?);
+        let stored_compactions = StoredCompactions::load_or_create(compactions_store).await?;
+        let compactions = FenceableCompactions::init(
+            stored_compactions,
+            options.manifest_update_timeout,
+            system_clock.clone(),
+        )
+        .await?;
+        let pending_compactions = compactions.compactions().compactions.clone();
+        let state = CompactorState::new(manifest.prepare_dirty()?).with_comparator(comparator);
         Ok(Self {
             state,
             manifest,
+            compactions,
+            pending_compactions,
+            handled_submissions: HashSet::new(),
             options,
             scheduler,
             executor,
             rand,
             stats,
             system_clock,
+            event_listeners,
+            compaction_start_times: HashMap::new(),
+            tickers_changed: false,
         })
     }
 
+    /// Replaces the options of the handler and the executor. The scheduler and the
+    /// fenceable manifest keep the options they were created with, so
+    /// [`crate::Db::update_settings`] rejects changes to the options they use.
+    fn update_options(&mut self, options: Arc<CompactorOptions>) {
+        info!("updating compactor options [options={:?}]", options);
+        if options.poll_interval != self.options.poll_interval {
+            self.tickers_changed = true;
+        }
+        self.executor.update_options(options.clone());
+        self.options = options;
+    }
+
     /// Emits the current compaction state and per-job progress.
     fn handle_log_ticker(&self) {
         self.log_compaction_state();
@@ -469,10 +580,21 @@ impl CompactorEventHandler {
                 },
             )
             .await?;
+        self.notify_manifest_written();
         self.state
             .merge_remote_manifest(self.manifest.prepare_dirty()?);
         let dirty = self.state.manifest().clone();
-        self.manifest.update(dirty).await
+        self.manifest.update(dirty).await?;
+        self.notify_manifest_written();
+        Ok(())
+    }
+
+    fn notify_manifest_written(&self) {
+        let event = ManifestWrittenEvent {
+            id: self.manifest.id(),
+        };
+        self.event_listeners
+            .notify(|l| l.on_manifest_written(&event));
     }
 
     /// Writes the manifest, retrying on version conflicts by reloading and retrying.
@@ -495,26 +617,33 @@ impl CompactorEventHandler {
     ///
     /// - Compaction has sources
     /// - Compactions with only L0 sources must have a destination > highest existing SR ID
-    fn validate_compaction(&self, compaction: &CompactionSpec) -> Result<(), SlateDBError> {
+    ///
+    /// The invariants are checked against the column family of the compaction.
+    fn validate_compaction(&self, compaction: &Compaction) -> Result<(), SlateDBError> {
+        let state = self.state.column_family_view(compaction.column_family());
+        let compaction = compaction.spec();
         // Validate compaction sources exist
         if compaction.sources().is_empty() {
             warn!("submitted compaction is empty: {:?}", compaction.sources());
             return Err(SlateDBError::InvalidCompaction);
         }
 
+        // SSTs of sorted runs can be sources of partial compactions
+        let sorted_run_ssts: HashSet<Ulid> = state
+            .db_state()
+            .compacted
+            .iter()
+            .flat_map(|sr| sr.ssts.iter())
+            .map(|sst| sst.id.unwrap_compacted_id())
+            .collect();
         let has_only_l0 = compaction
             .sources()
             .iter()
-            .all(|s| matches!(s, SourceId::Sst(_)));
+            .all(|s| matches!(s, SourceId::Sst(id) if !sorted_run_ssts.contains(id)));
 
         if has_only_l0 {
             // L0-only: must create new SR with id > highest_existing
-            let highest_id = self
-                .state
-                .db_state()
-                .compacted
-                .first()
-                .map_or(0, |sr| sr.id + 1);
+            let highest_id = state.db_state().compacted.first().map_or(0, |sr| sr.id + 1);
             if compaction.destination() < highest_id {
                 warn!("compaction destination is lesser than the expected L0-only highest_id: {:?} {:?}",
                 compaction.destination(), highest_id);
@@ -523,15 +652,25 @@ impl CompactorEventHandler {
         }
 
         self.scheduler
-            .validate_compaction(&self.state, compaction)
+            .validate_compaction(&state, compaction)
             .map_err(|_e| SlateDBError::InvalidCompaction)
     }
 
     /// Invokes the scheduler and starts accepted compactions, provided that there are fewer than
-    /// [`CompactorOptions::max_concurrent_compactions`] currently running.
+    /// [`CompactorOptions::max_concurrent_compactions`] currently running. The scheduler is
+    /// invoked once per column family, with a view of the state restricted to it.
     async fn maybe_schedule_compactions(&mut self) -> Result<(), SlateDBError> {
-        let mut specs = self.scheduler.maybe_schedule_compaction(&self.state);
-        for spec in specs.drain(..) {
+        let mut specs = Vec::new();
+        for column_family in self.state.column_family_ids() {
+            let state = self.state.column_family_view(column_family);
+            specs.extend(
+                self.scheduler
+                    .maybe_schedule_compaction(&state)
+                    .into_iter()
+                    .map(|spec| (column_family, spec)),
+            );
+        }
+        for (column_family, spec) in specs.drain(..) {
             let active_compactions = self.state.compactions().count();
             if active_compactions >= self.options.max_concurrent_compactions {
                 info!(
@@ -543,7 +682,7 @@ impl CompactorEventHandler {
                 break;
             }
             let compaction_id = self.rand.rng().gen_ulid(self.system_clock.as_ref());
-            let compaction = Compaction::new(compaction_id, spec);
+            let compaction = Compaction::new(compaction_id, spec).with_column_family(column_family);
             self.submit_compaction(compaction).await?;
         }
         Ok(())
@@ -556,7 +695,10 @@ impl CompactorEventHandler {
         compaction: Compaction,
     ) -> Result<(), SlateDBError> {
         self.log_compaction_state();
-        let db_state = self.state.db_state();
+        let db_state = &self
+            .state
+            .db_state()
+            .column_family_view(compaction.column_family());
 
         let ssts = compaction.get_ssts(db_state);
         let sorted_runs = compaction.get_sorted_runs(db_state);
@@ -568,15 +710,27 @@ impl CompactorEventHandler {
                 .last()
                 .is_some_and(|sr| spec.destination() == sr.id);
 
+        let (source_ssts, source_sorted_runs) = split_sources(spec.sources());
+        let started_event = CompactionStartedEvent {
+            id: compaction.id(),
+            source_ssts,
+            source_sorted_runs,
+            destination: spec.destination(),
+            source_bytes: ssts.iter().map(|sst| sst.estimate_size()).sum::<u64>()
+                + sorted_runs.iter().map(|sr| sr.estimate_size()).sum::<u64>(),
+        };
+
         let job_args = StartCompactionJobArgs {
             id: job_id,
             compaction_id: compaction.id(),
             destination: spec.destination(),
+            column_family: compaction.column_family(),
             ssts,
             sorted_runs,
             compaction_logical_clock_tick: db_state.last_l0_clock_tick,
             retention_min_seq: Some(db_state.recent_snapshot_min_seq),
             is_dest_last_run,
+            output_ssts: compaction.output_ssts().clone(),
         };
 
         // TODO(sujeetsawala): Add job attempt to compaction
@@ -598,6 +752,12 @@ impl CompactorEventHandler {
         })
         .await
         .map_err(|_| SlateDBError::CompactionExecutorFailed);
+        if result.is_ok() {
+            self.compaction_start_times
+                .insert(started_event.id, self.system_clock.now());
+            self.event_listeners
+                .notify(|l| l.on_compaction_started(&started_event));
+        }
         result
     }
 
@@ -605,10 +765,54 @@ impl CompactorEventHandler {
     // state writers
     //
 
-    /// Records a failed compaction attempt.
-    fn finish_failed_compaction(&mut self, id: Ulid) {
+    /// Records a failed compaction attempt. Its output SSTs are left to the garbage collector.
+    async fn finish_failed_compaction(&mut self, id: Ulid) -> Result<(), SlateDBError> {
         self.state.remove_compaction(&id);
+        self.compaction_start_times.remove(&id);
+        self.write_compactions().await?;
         self.update_compaction_low_watermark();
+        Ok(())
+    }
+
+    /// Persists the in-flight compactions of [`CompactorState`] and removes the handled
+    /// submitted compactions, if either changed. Clients append submitted compactions
+    /// concurrently, so the write is retried on version conflicts.
+    async fn write_compactions(&mut self) -> Result<(), SlateDBError> {
+        let compactions: Vec<Compaction> = self
+            .state
+            .compactions()
+            .map(|c| {
+                // progress is not persisted
+                let mut c = c.clone();
+                c.set_bytes_processed(0);
+                c
+            })
+            .collect();
+        loop {
+            let mut dirty = self.compactions.prepare_dirty()?;
+            let num_submitted = dirty.value.submitted.len();
+            dirty
+                .value
+                .submitted
+                .retain(|c| !self.handled_submissions.contains(&c.id()));
+            if dirty.value.compactions == compactions
+                && dirty.value.submitted.len() == num_submitted
+            {
+                return Ok(());
+            }
+            dirty.value.compactions = compactions.clone();
+            match self.compactions.update(dirty).await {
+                Ok(()) => {
+                    self.handled_submissions.clear();
+                    return Ok(());
+                }
+                Err(SlateDBError::TransactionalObjectVersionExists) => {
+                    debug!("conflicting compactions version. refreshing and retrying write.");
+                    self.compactions.refresh().await?;
+                }
+                Err(err) => return Err(err),
+            }
+        }
     }
 
     /// Records a successful compaction, persists the manifest, and checks for new compactions
@@ -619,9 +823,41 @@ impl CompactorEventHandler {
         id: Ulid,
         output_sr: SortedRun,
     ) -> Result<(), SlateDBError> {
+        let finished_event = self
+            .state
+            .compactions()
+            .find(|c| c.id() == id)
+            .map(|compaction| {
+                let spec = compaction.spec();
+                let (source_ssts, source_sorted_runs) = split_sources(spec.sources());
+                let duration = self
+                    .compaction_start_times
+                    .remove(&id)
+                    .and_then(|start| (self.system_clock.now() - start).to_std().ok())
+                    .unwrap_or_default();
+                CompactionFinishedEvent {
+                    id,
+                    source_ssts,
+                    source_sorted_runs,
+                    destination: spec.destination(),
+                    output_ssts: output_sr
+                        .ssts
+                        .iter()
+                        .map(|sst| sst.id.unwrap_compacted_id())
+                        .collect(),
+                    bytes: output_sr.estimate_size(),
+                    duration,
+                }
+            });
         self.state.finish_compaction(id, output_sr);
         self.log_compaction_state();
         self.write_manifest_safely().await?;
+        if let Some(event) = finished_event {
+            self.event_listeners
+                .notify(|l| l.on_compaction_finished(&event));
+        }
+        // the compaction is removed only once its output is in the manifest
+        self.write_compactions().await?;
         self.update_compaction_low_watermark();
         self.maybe_schedule_compactions().await?;
         self.stats
@@ -632,18 +868,19 @@ impl CompactorEventHandler {
 
     /// Validates and submits a compaction for execution.
     ///
-    /// Currently, compactions are executed with a 1:1 [`Compaction`]:Compaction Job
-    /// mapping. Future work may add retries or resume logic with multiple jobs per
-    /// compaction.
+    /// The compaction is persisted before its job starts. Currently, compactions are
+    /// executed with a 1:1 [`Compaction`]:Compaction Job mapping, and a resumed compaction
+    /// reuses its id as job id.
     #[instrument(level = "debug", skip_all, fields(id = tracing::field::Empty))]
     async fn submit_compaction(&mut self, compaction: Compaction) -> Result<(), SlateDBError> {
         // Validate the candidate compaction; skip invalid ones
-        if let Err(e) = self.validate_compaction(compaction.spec()) {
+        if let Err(e) = self.validate_compaction(&compaction) {
             warn!("invalid compaction [error={:?}]", e);
             return Ok(());
         }
 
         self.state.add_compaction(compaction.clone())?;
+        self.write_compactions().await?;
         // Compactions and jobs are 1:1 right now.
         let job_id = compaction.id();
         tracing::Span::current().record("id", tracing::field::display(&job_id));
@@ -651,17 +888,154 @@ impl CompactorEventHandler {
         Ok(())
     }
 
-    /// Merges the remote manifest view into local state and checks for new compactions
-    /// to schedule.
+    /// Merges the remote manifest view into local state, resumes the compactions persisted
+    /// by an earlier compactor, starts the compactions submitted by clients, and checks for
+    /// new compactions to schedule.
     async fn refresh_db_state(&mut self) -> Result<(), SlateDBError> {
         self.state
             .merge_remote_manifest(self.manifest.prepare_dirty()?);
-        // TODO(sujeetsawala): Fetch and Run Pending Compactions from object store
-        // self.run_pending_compactions().await?;
+        self.resume_pending_compactions().await?;
+        self.start_submitted_compactions().await?;
         self.maybe_schedule_compactions().await?;
         Ok(())
     }
 
+    /// Returns `true` if all sources of the compaction are in the manifest.
+    fn compaction_sources_exist(&self, compaction: &Compaction) -> bool {
+        let db_state = self
+            .state
+            .db_state()
+            .column_family_view(compaction.column_family());
+        compaction
+            .spec()
+            .sources()
+            .iter()
+            .all(|source| match source {
+                SourceId::Sst(id) => db_state
+                    .l0
+                    .iter()
+                    .chain(db_state.compacted.iter().flat_map(|sr| sr.ssts.iter()))
+                    .any(|sst| sst.id.unwrap_compacted_id() == *id),
+                SourceId::SortedRun(id) => db_state.compacted.iter().any(|sr| sr.id == *id),
+            })
+    }
+
+    /// Starts the compactions submitted by clients, e.g. by
+    /// [`crate::admin::Admin::compact_range`], in submission order.
+    ///
+    /// A submitted compaction is rejected if its sources no longer exist or if it is not
+    /// valid, e.g. because the scheduler does not allow it. It stays submitted while
+    /// [`CompactorOptions::max_concurrent_compactions`] compactions are running or while it
+    /// conflicts with a running compaction.
+    async fn start_submitted_compactions(&mut self) -> Result<(), SlateDBError> {
+        self.compactions.refresh().await?;
+        let submitted: Vec<Compaction> = self
+            .compactions
+            .compactions()
+            .submitted
+            .iter()
+            .filter(|c| !self.handled_submissions.contains(&c.id()))
+            .cloned()
+            .collect();
+        if submitted.is_empty() {
+            return Ok(());
+        }
+        let mut started = Vec::new();
+        for compaction in submitted {
+            if !self.compaction_sources_exist(&compaction) {
+                warn!(
+                    "rejecting submitted compaction with missing sources [compaction={}]",
+                    compaction
+                );
+                self.handled_submissions.insert(compaction.id());
+                continue;
+            }
+            if let Err(e) = self.validate_compaction(&compaction) {
+                warn!(
+                    "rejecting invalid submitted compaction [compaction={}, error={:?}]",
+                    compaction, e
+                );
+                self.handled_submissions.insert(compaction.id());
+                continue;
+            }
+            let active_compactions = self.state.compactions().count();
+            if active_compactions >= self.options.max_concurrent_compactions {
+                info!(
+                    "already running {} compactions, which is at the max {}. Deferring submitted compactions",
+                    active_compactions, self.options.max_concurrent_compactions,
+                );
+                break;
+            }
+            if let Err(e) = self.state.add_compaction(compaction.clone()) {
+                info!(
+                    "deferring conflicting submitted compaction [compaction={}, error={:?}]",
+                    compaction, e
+                );
+                continue;
+            }
+            info!("starting submitted compaction [compaction={}]", compaction);
+            self.handled_submissions.insert(compaction.id());
+            started.push(compaction);
+        }
+        self.write_compactions().await?;
+        for compaction in started {
+            self.start_compaction(compaction.id(), compaction).await?;
+        }
+        self.update_compaction_low_watermark();
+        Ok(())
+    }
+
+    /// Resumes the compactions persisted by an earlier compactor from their last output SST.
+    ///
+    /// A persisted compaction is dropped if its output is already in the manifest (the
+    /// earlier compactor stopped after writing the manifest of the finished compaction), or
+    /// if it is no longer valid, e.g. because its sources no longer exist.
+    async fn resume_pending_compactions(&mut self) -> Result<(), SlateDBError> {
+        if self.pending_compactions.is_empty() {
+            return Ok(());
+        }
+        for compaction in std::mem::take(&mut self.pending_compactions) {
+            let db_state = self
+                .state
+                .db_state()
+                .column_family_view(compaction.column_family());
+            let live_ssts: HashSet<Ulid> = db_state
+                .l0
+                .iter()
+                .chain(db_state.compacted.iter().flat_map(|sr| sr.ssts.iter()))
+                .map(|sst| sst.id.unwrap_compacted_id())
+                .collect();
+            let sources_exist = self.compaction_sources_exist(&compaction);
+            let output_in_manifest = compaction
+                .output_ssts()
+                .iter()
+                .any(|sst| live_ssts.contains(&sst.id.unwrap_compacted_id()));
+            if !sources_exist || output_in_manifest {
+                info!("dropping persisted compaction [compaction={}]", compaction);
+                continue;
+            }
+            if let Err(e) = self.validate_compaction(&compaction) {
+                warn!(
+                    "dropping invalid persisted compaction [compaction={}, error={:?}]",
+                    compaction, e
+                );
+                continue;
+            }
+            if let Err(e) = self.state.add_compaction(compaction.clone()) {
+                warn!(
+                    "dropping conflicting persisted compaction [compaction={}, error={:?}]",
+                    compaction, e
+                );
+                continue;
+            }
+            info!("resuming persisted compaction [compaction={}]", compaction);
+            self.start_compaction(compaction.id(), compaction).await?;
+        }
+        self.write_compactions().await?;
+        self.update_compaction_low_watermark();
+        Ok(())
+    }
+
     /// Logs the current DB runs and in-flight compactions.
     fn log_compaction_state(&self) {
         self.state.db_state().log_db_runs();
@@ -683,9 +1057,9 @@ impl CompactorEventHandler {
     /// whose ULID timestamp is greater than or equal to this value.
     ///
     /// This is a process-local coordination mechanism that only works when the compactor
-    /// and garbage collector run in the same process and share the same StatRegistry. It's
-    /// a hack until we have proper compactor persistence (so GC can retrieve the compactor
-    /// state from the object store). See #604 for details.
+    /// and garbage collector run in the same process and share the same StatRegistry. A
+    /// garbage collector also reads the persisted compactions from the compactions store,
+    /// which covers compactors running in other processes.
     fn update_compaction_low_watermark(&self) {
         let min_ts = self
             .state
@@ -704,8 +1078,21 @@ impl CompactorEventHandler {
     }
 }
 
+/// Splits the sources of a compaction into the ids of its SSTs and of its sorted runs.
+fn split_sources(sources: &[SourceId]) -> (Vec<Ulid>, Vec<u32>) {
+    let mut ssts = Vec::new();
+    let mut sorted_runs = Vec::new();
+    for source in sources {
+        match source {
+            SourceId::Sst(id) => ssts.push(*id),
+            SourceId::SortedRun(id) => sorted_runs.push(*id),
+        }
+    }
+    (ssts, sorted_runs)
+}
+
 pub mod stats {
-    use crate::stats::{Counter, Gauge, StatRegistry};
+    use crate::stats::{Counter, Gauge, Histogram, StatRegistry};
     use std::sync::Arc;
 
     macro_rules! compactor_stat_name {
@@ -721,12 +1108,22 @@ pub mod stats {
     /// [super::CompactorEventHandler::update_longest_running_start_metric] for details.
     pub const COMPACTION_LOW_WATERMARK_TS: &str =
         compactor_stat_name!("compaction_low_watermark_ts");
+    pub const COMPACTION_FILTER_CALLS: &str = compactor_stat_name!("compaction_filter_calls");
+    pub const COMPACTION_FILTER_DROPPED: &str = compactor_stat_name!("compaction_filter_dropped");
+    pub const COMPACTION_FILTER_REPLACED: &str = compactor_stat_name!("compaction_filter_replaced");
+    pub const COMPACTION_LATENCY_US: &str = compactor_stat_name!("compaction_latency_us");
+    pub const RATE_LIMIT_WAIT_US: &str = compactor_stat_name!("rate_limit_wait_us");
 
     pub(crate) struct CompactionStats {
         pub(crate) last_compaction_ts: Arc<Gauge<u64>>,
         pub(crate) running_compactions: Arc<Gauge<i64>>,
         pub(crate) bytes_compacted: Arc<Counter>,
         pub(crate) compaction_low_watermark_ts: Arc<Gauge<u64>>,
+        pub(crate) compaction_filter_calls: Arc<Counter>,
+        pub(crate) compaction_filter_dropped: Arc<Counter>,
+        pub(crate) compaction_filter_replaced: Arc<Counter>,
+        pub(crate) compaction_latency_us: Arc<Histogram>,
+        pub(crate) rate_limit_wait_us: Arc<Histogram>,
     }
 
     impl CompactionStats {
@@ -737,20 +1134,68 @@ pub mod stats {
         /// - `running_compactions`: Gauge tracking active compaction attempts.
         /// - `bytes_compacted`: Counter of bytes written by the executor.
         /// - `compaction_low_watermark_ts`: Earliest ULID timestamp among active compactions (GC hint).
+        /// - `compaction_filter_calls`: Counter of rows passed to the compaction filter.
+        /// - `compaction_filter_dropped`: Counter of rows the compaction filter dropped.
+        /// - `compaction_filter_replaced`: Counter of rows whose value the compaction filter replaced.
+        /// - `compaction_latency_us`: Histogram of the execution time of compaction jobs.
+        /// - `rate_limit_wait_us`: Histogram of the time compactions waited for the rate limiter.
         pub(crate) fn new(stat_registry: Arc<StatRegistry>) -> Self {
             let stats = Self {
                 last_compaction_ts: Arc::new(Gauge::default()),
                 running_compactions: Arc::new(Gauge::default()),
                 bytes_compacted: Arc::new(Counter::default()),
                 compaction_low_watermark_ts: Arc::new(Gauge::default()),
+                compaction_filter_calls: Arc::new(Counter::default()),
+                compaction_filter_dropped: Arc::new(Counter::default()),
+                compaction_filter_replaced: Arc::new(Counter::default()),
+                compaction_latency_us: Arc::new(Histogram::default()),
+                rate_limit_wait_us: Arc::new(Histogram::default()),
             };
-            stat_registry.register(LAST_COMPACTION_TS_SEC, stats.last_compaction_ts.clone());
-            stat_registry.register(RUNNING_COMPACTIONS, stats.running_compactions.clone());
-            stat_registry.register(BYTES_COMPACTED, stats.bytes_compacted.clone());
+            stat_registry.register(
+                LAST_COMPACTION_TS_SEC,
+                "Unix timestamp in seconds of the last finished compaction.",
+                stats.last_compaction_ts.clone(),
+            );
+            stat_registry.register(
+                RUNNING_COMPACTIONS,
+                "Number of running compactions.",
+                stats.running_compactions.clone(),
+            );
+            stat_registry.register(
+                BYTES_COMPACTED,
+                "Number of bytes written by compactions.",
+                stats.bytes_compacted.clone(),
+            );
             stat_registry.register(
                 COMPACTION_LOW_WATERMARK_TS,
+                "Oldest timestamp still read by running compactions.",
                 stats.compaction_low_watermark_ts.clone(),
             );
+            stat_registry.register(
+                COMPACTION_FILTER_CALLS,
+                "Number of rows passed to the compaction filter.",
+                stats.compaction_filter_calls.clone(),
+            );
+            stat_registry.register(
+                COMPACTION_FILTER_DROPPED,
+                "Number of rows dropped by the compaction filter.",
+                stats.compaction_filter_dropped.clone(),
+            );
+            stat_registry.register(
+                COMPACTION_FILTER_REPLACED,
+                "Number of rows replaced by the compaction filter.",
+                stats.compaction_filter_replaced.clone(),
+            );
+            stat_registry.register(
+                COMPACTION_LATENCY_US,
+                "Latency in microseconds of compaction jobs.",
+                stats.compaction_latency_us.clone(),
+            );
+            stat_registry.register(
+                RATE_LIMIT_WAIT_US,
+                "Time in microseconds that compactions waited for the rate limiter.",
+                stats.rate_limit_wait_us.clone(),
+            );
             stats
         }
     }
@@ -772,22 +1217,28 @@ mod tests {
     use ulid::Ulid;
 
     use super::*;
+    use crate::admin::{AdminBuilder, CompactRangeProgress};
     use crate::clock::DefaultSystemClock;
+    use crate::compaction_filter::{CompactionFilter, CompactionFilterDecision};
     use crate::compactor::stats::CompactionStats;
     use crate::compactor_executor::{CompactionExecutor, TokioCompactionExecutor};
     use crate::compactor_state::{CompactorState, SourceId};
     use crate::compactor_stats::LAST_COMPACTION_TS_SEC;
     use crate::config::{
+        CompactionJobQueueOptions, FlushOptions, FlushType, LeveledCompactionSchedulerOptions,
         PutOptions, Settings, SizeTieredCompactionSchedulerOptions, Ttl, WriteOptions,
     };
     use crate::db::Db;
-    use crate::db_state::{CoreDbState, SortedRun};
+    use crate::db_state::{CoreDbState, SortedRun, SsTableHandle, SsTableId};
     use crate::error::SlateDBError;
+    use crate::event_listener::tests::RecordingEventListener;
     use crate::iter::KeyValueIterator;
+    use crate::leveled_compaction::LeveledCompactionSchedulerSupplier;
     use crate::manifest::store::{ManifestStore, StoredManifest};
     use crate::merge_operator::{MergeOperator, MergeOperatorError};
     use crate::object_stores::ObjectStores;
     use crate::proptest_util::rng;
+    use crate::remote_compaction::{CompactionWorker, ObjectStoreCompactionJobRunner};
     use crate::size_tiered_compaction::SizeTieredCompactionSchedulerSupplier;
     use crate::sst::SsTableFormat;
     use crate::sst_iter::{SstIterator, SstIteratorOptions};
@@ -799,87 +1250,465 @@ mod tests {
 
     const PATH: &str = "/test/db";
 
-    struct StringConcatMergeOperator;
+    struct StringConcatMergeOperator;
+
+    impl MergeOperator for StringConcatMergeOperator {
+        fn merge(
+            &self,
+            _key: &Bytes,
+            existing_value: Option<Bytes>,
+            value: Bytes,
+        ) -> Result<Bytes, MergeOperatorError> {
+            let mut result = existing_value.unwrap_or_default().as_ref().to_vec();
+            result.extend_from_slice(&value);
+            Ok(Bytes::from(result))
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_compactor_compacts_l0() {
+        // given:
+        let os = Arc::new(InMemory::new());
+        let logical_clock = Arc::new(TestClock::new());
+        let compaction_scheduler = Arc::new(SizeTieredCompactionSchedulerSupplier::new(
+            SizeTieredCompactionSchedulerOptions {
+                min_compaction_sources: 1,
+                max_compaction_sources: 999,
+                include_size_threshold: 4.0,
+            },
+        ));
+        let mut options = db_options(Some(compactor_options()));
+        options.l0_sst_size_bytes = 128;
+
+        let db = Db::builder(PATH, os.clone())
+            .with_settings(options)
+            .with_logical_clock(logical_clock)
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .build()
+            .await
+            .unwrap();
+
+        let (_, table_store) = build_test_stores(os.clone());
+        let mut expected = HashMap::<Vec<u8>, Vec<u8>>::new();
+        for i in 0..4 {
+            let k = vec![b'a' + i as u8; 16];
+            let v = vec![b'b' + i as u8; 48];
+            expected.insert(k.clone(), v.clone());
+            db.put(&k, &v).await.unwrap();
+            let k = vec![b'j' + i as u8; 16];
+            let v = vec![b'k' + i as u8; 48];
+            db.put(&k, &v).await.unwrap();
+            expected.insert(k.clone(), v.clone());
+        }
+
+        db.flush().await.unwrap();
+
+        // when:
+        let db_state = await_compaction(&db).await;
+
+        // then:
+        let db_state = db_state.expect("db was not compacted");
+        for run in db_state.compacted {
+            for sst in run.ssts {
+                let mut iter = SstIterator::new_borrowed_initialized(
+                    ..,
+                    &sst,
+                    table_store.clone(),
+                    SstIteratorOptions::default(),
+                )
+                .await
+                .unwrap()
+                .expect("Expected Some(iter) but got None");
+
+                // remove the key from the expected map and verify that the db matches
+                while let Some(kv) = iter.next().await.unwrap() {
+                    let expected_v = expected
+                        .remove(kv.key.as_ref())
+                        .expect("removing unexpected key");
+                    let db_v = db.get(kv.key.as_ref()).await.unwrap().unwrap();
+                    assert_eq!(expected_v, db_v.as_ref());
+                }
+            }
+        }
+        assert!(expected.is_empty());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_compactor_compacts_l0_on_remote_worker() {
+        // given:
+        let os = Arc::new(InMemory::new());
+        let queue_options = CompactionJobQueueOptions {
+            poll_interval: Duration::from_millis(10),
+            ..CompactionJobQueueOptions::default()
+        };
+        let compaction_scheduler = Arc::new(SizeTieredCompactionSchedulerSupplier::new(
+            SizeTieredCompactionSchedulerOptions {
+                min_compaction_sources: 1,
+                max_compaction_sources: 999,
+                include_size_threshold: 4.0,
+            },
+        ));
+        let mut options = db_options(Some(compactor_options()));
+        options.l0_sst_size_bytes = 128;
+        let db = Db::builder(PATH, os.clone())
+            .with_settings(options)
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .with_compaction_job_runner(Arc::new(ObjectStoreCompactionJobRunner::new(
+                PATH,
+                os.clone(),
+                queue_options,
+            )))
+            .build()
+            .await
+            .unwrap();
+        let worker = CompactionWorker::builder(PATH, os.clone())
+            .with_options(compactor_options())
+            .with_queue_options(queue_options)
+            .build();
+        let worker_task = tokio::spawn(async move { worker.run().await });
+
+        let mut expected = HashMap::<Vec<u8>, Vec<u8>>::new();
+        for i in 0..4 {
+            let k = vec![b'a' + i as u8; 16];
+            let v = vec![b'b' + i as u8; 48];
+            expected.insert(k.clone(), v.clone());
+            db.put(&k, &v).await.unwrap();
+        }
+        db.flush().await.unwrap();
+
+        // when:
+        let db_state = await_compaction(&db).await;
+
+        // then:
+        worker_task.abort();
+        assert!(db_state.is_some(), "db was not compacted");
+        for (k, v) in expected {
+            assert_eq!(db.get(&k).await.unwrap(), Some(Bytes::from(v)));
+        }
+    }
+
+    struct PrefixCompactionFilter;
+
+    impl CompactionFilter for PrefixCompactionFilter {
+        fn filter(&self, key: &Bytes, _value: &Bytes) -> CompactionFilterDecision {
+            match key.first() {
+                Some(b'a') => CompactionFilterDecision::Drop,
+                Some(b'b') => CompactionFilterDecision::Replace(Bytes::from_static(b"replaced")),
+                _ => CompactionFilterDecision::Keep,
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_compactor_applies_compaction_filter() {
+        use crate::compactor_stats::{
+            COMPACTION_FILTER_CALLS, COMPACTION_FILTER_DROPPED, COMPACTION_FILTER_REPLACED,
+        };
+
+        // given:
+        let os = Arc::new(InMemory::new());
+        let compaction_scheduler = Arc::new(SizeTieredCompactionSchedulerSupplier::new(
+            SizeTieredCompactionSchedulerOptions {
+                min_compaction_sources: 1,
+                max_compaction_sources: 999,
+                include_size_threshold: 4.0,
+            },
+        ));
+        let mut options = db_options(Some(compactor_options()));
+        options.l0_sst_size_bytes = 128;
+        let db = Db::builder(PATH, os.clone())
+            .with_settings(options)
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .with_compaction_filter(Arc::new(PrefixCompactionFilter))
+            .build()
+            .await
+            .unwrap();
+        for key in [b"a1", b"a2", b"b1", b"c1"] {
+            db.put(key, b"value").await.unwrap();
+        }
+        db.flush().await.unwrap();
+
+        // when:
+        let db_state = await_compaction(&db).await;
+
+        // then:
+        assert!(db_state.is_some(), "db was not compacted");
+        assert_eq!(db.get(b"a1").await.unwrap(), None);
+        assert_eq!(db.get(b"a2").await.unwrap(), None);
+        assert_eq!(
+            db.get(b"b1").await.unwrap(),
+            Some(Bytes::from_static(b"replaced"))
+        );
+        assert_eq!(
+            db.get(b"c1").await.unwrap(),
+            Some(Bytes::from_static(b"value"))
+        );
+        // the scheduler may compact the sorted run again, which filters the surviving rows
+        // again, but the dropped rows are only filtered by the first compaction
+        let metrics = db.metrics();
+        assert!(metrics.lookup(COMPACTION_FILTER_CALLS).unwrap().get() >= 4);
+        assert_eq!(metrics.lookup(COMPACTION_FILTER_DROPPED).unwrap().get(), 2);
+        assert!(metrics.lookup(COMPACTION_FILTER_REPLACED).unwrap().get() >= 1);
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_admin_compact_range_compacts_overlapping_l0() {
+        // given:
+        let os = Arc::new(InMemory::new());
+        // the scheduler never schedules compactions on its own
+        let compaction_scheduler = Arc::new(SizeTieredCompactionSchedulerSupplier::new(
+            SizeTieredCompactionSchedulerOptions {
+                min_compaction_sources: 100,
+                max_compaction_sources: 999,
+                include_size_threshold: 4.0,
+            },
+        ));
+        let db = Db::builder(PATH, os.clone())
+            .with_settings(db_options(Some(compactor_options())))
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .build()
+            .await
+            .unwrap();
+        for key in [b"a1", b"a2", b"b1"] {
+            db.put(key, b"value").await.unwrap();
+        }
+        db.delete(b"a1").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let admin = AdminBuilder::new(PATH, os.clone()).build();
+
+        // when:
+        let mut progress = Vec::new();
+        admin
+            .compact_range(
+                b"a".as_slice()..,
+                Duration::from_millis(10),
+                Some(Duration::from_secs(10)),
+                |p| progress.push(p.clone()),
+            )
+            .await
+            .unwrap();
+
+        // then:
+        assert_eq!(progress.first(), Some(&CompactRangeProgress::Submitted));
+        assert!(matches!(
+            progress.last(),
+            Some(CompactRangeProgress::Finished { .. })
+        ));
+        let (manifest_store, _) = build_test_stores(os.clone());
+        let db_state = get_db_state(manifest_store).await;
+        assert!(db_state.l0.is_empty());
+        assert_eq!(db_state.compacted.len(), 1);
+        assert_eq!(db.get(b"a1").await.unwrap(), None);
+        assert_eq!(
+            db.get(b"a2").await.unwrap(),
+            Some(Bytes::from_static(b"value"))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_admin_compact_range_leaves_non_overlapping_sorted_run_alone() {
+        // given:
+        let os = Arc::new(InMemory::new());
+        // the scheduler never schedules compactions on its own
+        let compaction_scheduler = Arc::new(SizeTieredCompactionSchedulerSupplier::new(
+            SizeTieredCompactionSchedulerOptions {
+                min_compaction_sources: 100,
+                max_compaction_sources: 999,
+                include_size_threshold: 4.0,
+            },
+        ));
+        let db = Db::builder(PATH, os.clone())
+            .with_settings(db_options(Some(compactor_options())))
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .build()
+            .await
+            .unwrap();
+        let admin = AdminBuilder::new(PATH, os.clone()).build();
+        // sorted run 0 holds a1, sorted run 1 holds z1
+        for (key, range) in [(b"a1", b"a".as_slice()..), (b"z1", b"z".as_slice()..)] {
+            db.put(key, b"value").await.unwrap();
+            db.flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
+            .await
+            .unwrap();
+            admin
+                .compact_range(
+                    range,
+                    Duration::from_millis(10),
+                    Some(Duration::from_secs(10)),
+                    |_| {},
+                )
+                .await
+                .unwrap();
+        }
+        db.put(b"a2", b"value").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let (manifest_store, _) = build_test_stores(os.clone());
+        let db_state = get_db_state(manifest_store.clone()).await;
+        assert_eq!(
+            db_state
+                .compacted
+                .iter()
+                .map(|sr| sr.id)
+                .collect::<Vec<_>>(),
+            vec![1, 0]
+        );
+        let sr1 = db_state.compacted[0].clone();
+
+        // when:
+        admin
+            .compact_range(
+                ..=b"b".as_slice(),
+                Duration::from_millis(10),
+                Some(Duration::from_secs(10)),
+                |_| {},
+            )
+            .await
+            .unwrap();
+
+        // then:
+        let db_state = get_db_state(manifest_store).await;
+        assert!(db_state.l0.is_empty());
+        assert_eq!(db_state.compacted.len(), 2);
+        assert_eq!(db_state.compacted[0], sr1);
+        assert_eq!(db_state.compacted[1].id, 0);
+        for key in [b"a1", b"a2", b"z1"] {
+            assert_eq!(
+                db.get(key).await.unwrap(),
+                Some(Bytes::from_static(b"value"))
+            );
+        }
+    }
+
+    #[tokio::test]
+    async fn test_admin_compact_range_times_out_without_compactor() {
+        // given:
+        let os = Arc::new(InMemory::new());
+        let db = Db::builder(PATH, os.clone())
+            .with_settings(db_options(None))
+            .build()
+            .await
+            .unwrap();
+        db.put(b"a1", b"value").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let admin = AdminBuilder::new(PATH, os.clone()).build();
+
+        // when:
+        let mut progress = Vec::new();
+        let err = admin
+            .compact_range(
+                b"a".as_slice()..,
+                Duration::from_millis(10),
+                Some(Duration::from_millis(100)),
+                |p| progress.push(p.clone()),
+            )
+            .await
+            .unwrap_err();
+
+        // then:
+        assert_eq!(err.kind(), crate::ErrorKind::Unavailable);
+        assert_eq!(progress, vec![CompactRangeProgress::Submitted]);
+        // the compaction is withdrawn, so a compactor started later doesn't run it
+        let compactions_store = Arc::new(CompactionsStore::new(&Path::from(PATH), os.clone()));
+        let stored_compactions = StoredCompactions::load_or_create(compactions_store)
+            .await
+            .unwrap();
+        assert!(stored_compactions.compactions().submitted.is_empty());
+        let (manifest_store, _) = build_test_stores(os.clone());
+        assert_eq!(get_db_state(manifest_store).await.l0.len(), 1);
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_admin_compact_range_without_overlap_does_nothing() {
+        // given:
+        let os = Arc::new(InMemory::new());
+        let db = Db::builder(PATH, os.clone())
+            .with_settings(db_options(Some(compactor_options())))
+            .build()
+            .await
+            .unwrap();
+        let admin = AdminBuilder::new(PATH, os.clone()).build();
+
+        // when:
+        let mut progress = Vec::new();
+        admin
+            .compact_range(
+                b"a".as_slice()..,
+                Duration::from_millis(10),
+                Some(Duration::from_secs(10)),
+                |p| progress.push(p.clone()),
+            )
+            .await
+            .unwrap();
 
-    impl MergeOperator for StringConcatMergeOperator {
-        fn merge(
-            &self,
-            _key: &Bytes,
-            existing_value: Option<Bytes>,
-            value: Bytes,
-        ) -> Result<Bytes, MergeOperatorError> {
-            let mut result = existing_value.unwrap_or_default().as_ref().to_vec();
-            result.extend_from_slice(&value);
-            Ok(Bytes::from(result))
-        }
+        // then:
+        assert_eq!(progress, vec![CompactRangeProgress::NothingToCompact]);
+        db.close().await.unwrap();
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_compactor_compacts_l0() {
+    async fn test_compactor_compacts_levels_with_leveled_scheduler() {
         // given:
         let os = Arc::new(InMemory::new());
-        let logical_clock = Arc::new(TestClock::new());
-        let compaction_scheduler = Arc::new(SizeTieredCompactionSchedulerSupplier::new(
-            SizeTieredCompactionSchedulerOptions {
-                min_compaction_sources: 1,
-                max_compaction_sources: 999,
-                include_size_threshold: 4.0,
+        let compaction_scheduler = Arc::new(LeveledCompactionSchedulerSupplier::new(
+            LeveledCompactionSchedulerOptions {
+                num_levels: 3,
+                l0_compaction_trigger: 1,
+                level_base_size_bytes: 512,
+                level_size_multiplier: 2,
             },
         ));
-        let mut options = db_options(Some(compactor_options()));
+        let mut options = db_options(Some(CompactorOptions {
+            max_sst_size: 256,
+            ..compactor_options()
+        }));
         options.l0_sst_size_bytes = 128;
-
         let db = Db::builder(PATH, os.clone())
             .with_settings(options)
-            .with_logical_clock(logical_clock)
             .with_compaction_scheduler_supplier(compaction_scheduler)
             .build()
             .await
             .unwrap();
 
-        let (_, table_store) = build_test_stores(os.clone());
         let mut expected = HashMap::<Vec<u8>, Vec<u8>>::new();
-        for i in 0..4 {
-            let k = vec![b'a' + i as u8; 16];
-            let v = vec![b'b' + i as u8; 48];
-            expected.insert(k.clone(), v.clone());
-            db.put(&k, &v).await.unwrap();
-            let k = vec![b'j' + i as u8; 16];
-            let v = vec![b'k' + i as u8; 48];
-            db.put(&k, &v).await.unwrap();
-            expected.insert(k.clone(), v.clone());
+        for round in 0..4u8 {
+            for i in 0..8u8 {
+                let k = vec![b'a' + i * 2 + round % 2; 16];
+                let v = vec![b'a' + round; 48];
+                expected.insert(k.clone(), v.clone());
+                db.put(&k, &v).await.unwrap();
+            }
+            db.flush().await.unwrap();
         }
 
-        db.flush().await.unwrap();
-
         // when:
-        let db_state = await_compaction(&db).await;
+        let db_state = run_for(Duration::from_secs(10), || async {
+            let core = db.inner.state.read().state().core().clone();
+            // the data reaches the last level
+            (core.l0.is_empty() && core.compacted.iter().any(|sr| sr.id == 0)).then_some(core)
+        })
+        .await;
 
         // then:
-        let db_state = db_state.expect("db was not compacted");
-        for run in db_state.compacted {
-            for sst in run.ssts {
-                let mut iter = SstIterator::new_borrowed_initialized(
-                    ..,
-                    &sst,
-                    table_store.clone(),
-                    SstIteratorOptions::default(),
-                )
-                .await
-                .unwrap()
-                .expect("Expected Some(iter) but got None");
-
-                // remove the key from the expected map and verify that the db matches
-                while let Some(kv) = iter.next().await.unwrap() {
-                    let expected_v = expected
-                        .remove(kv.key.as_ref())
-                        .expect("removing unexpected key");
-                    let db_v = db.get(kv.key.as_ref()).await.unwrap().unwrap();
-                    assert_eq!(expected_v, db_v.as_ref());
-                }
-            }
+        let db_state = db_state.expect("db was not compacted into the last level");
+        assert!(db_state.compacted.iter().all(|sr| sr.id < 3));
+        for (k, v) in expected {
+            assert_eq!(db.get(&k).await.unwrap(), Some(Bytes::from(v)));
         }
-        assert!(expected.is_empty());
     }
 
     #[cfg(feature = "wal_disable")]
@@ -927,6 +1756,7 @@ mod tests {
             &[b'a'; 16],
             &WriteOptions {
                 await_durable: false,
+                ..WriteOptions::default()
             },
         )
         .await
@@ -1029,6 +1859,7 @@ mod tests {
             &[b'a'; 16],
             &WriteOptions {
                 await_durable: false,
+                ..WriteOptions::default()
             },
         )
         .await
@@ -1405,6 +2236,7 @@ mod tests {
             },
             &WriteOptions {
                 await_durable: true,
+                ..WriteOptions::default()
             },
         )
         .await
@@ -1418,6 +2250,7 @@ mod tests {
             &crate::config::MergeOptions { ttl: Ttl::NoExpiry },
             &WriteOptions {
                 await_durable: true,
+                ..WriteOptions::default()
             },
         )
         .await
@@ -1758,6 +2591,8 @@ mod tests {
     struct CompactorEventHandlerTestFixture {
         manifest: StoredManifest,
         manifest_store: Arc<ManifestStore>,
+        compactions_store: Arc<CompactionsStore>,
+        table_store: Arc<TableStore>,
         options: Settings,
         db: Db,
         scheduler: Arc<MockScheduler>,
@@ -1765,6 +2600,7 @@ mod tests {
         real_executor: Arc<dyn CompactionExecutor>,
         real_executor_rx: tokio::sync::mpsc::UnboundedReceiver<CompactorMessage>,
         stats_registry: Arc<StatRegistry>,
+        event_listener: Arc<RecordingEventListener>,
         handler: CompactorEventHandler,
     }
 
@@ -1781,31 +2617,40 @@ mod tests {
                 .await
                 .unwrap();
 
+            let compactions_store = Arc::new(CompactionsStore::new(&Path::from(PATH), os.clone()));
             let scheduler = Arc::new(MockScheduler::new());
             let executor = Arc::new(MockExecutor::new());
             let (real_executor_tx, real_executor_rx) = tokio::sync::mpsc::unbounded_channel();
             let rand = Arc::new(DbRand::default());
             let stats_registry = Arc::new(StatRegistry::new());
             let compactor_stats = Arc::new(CompactionStats::new(stats_registry.clone()));
+            let event_listener = Arc::new(RecordingEventListener::default());
+            let mut event_listeners = EventListeners::default();
+            event_listeners.add(event_listener.clone());
             let real_executor = Arc::new(TokioCompactionExecutor::new(
                 Handle::current(),
                 compactor_options.clone(),
                 real_executor_tx,
-                table_store,
+                table_store.clone(),
                 rand.clone(),
                 compactor_stats.clone(),
                 Arc::new(DefaultSystemClock::new()),
                 manifest_store.clone(),
                 options.merge_operator.clone(),
+                options.compaction_filter.clone(),
+                HashMap::new(),
             ));
             let handler = CompactorEventHandler::new(
                 manifest_store.clone(),
+                compactions_store.clone(),
                 compactor_options.clone(),
                 scheduler.clone(),
                 executor.clone(),
                 rand.clone(),
                 compactor_stats.clone(),
                 Arc::new(DefaultSystemClock::new()),
+                KeyComparator::default(),
+                event_listeners,
             )
             .await
             .unwrap();
@@ -1813,6 +2658,8 @@ mod tests {
             Self {
                 manifest,
                 manifest_store,
+                compactions_store,
+                table_store,
                 options,
                 db,
                 scheduler,
@@ -1820,6 +2667,7 @@ mod tests {
                 real_executor_rx,
                 real_executor,
                 stats_registry,
+                event_listener,
                 handler,
             }
         }
@@ -1866,6 +2714,299 @@ mod tests {
                 self.real_executor.start_compaction_job(c)
             }
         }
+
+        async fn persisted_compactions(&self) -> Vec<Compaction> {
+            self.compactions_store
+                .try_read_latest_compactions()
+                .await
+                .unwrap()
+                .map(|(_, compactions)| compactions.compactions)
+                .unwrap_or_default()
+        }
+
+        /// Creates a new handler on the same stores, as a restarted compactor would.
+        async fn restart_handler(&self) -> CompactorEventHandler {
+            CompactorEventHandler::new(
+                self.manifest_store.clone(),
+                self.compactions_store.clone(),
+                Arc::new(compactor_options()),
+                self.scheduler.clone(),
+                self.executor.clone(),
+                Arc::new(DbRand::default()),
+                Arc::new(CompactionStats::new(Arc::new(StatRegistry::new()))),
+                Arc::new(DefaultSystemClock::new()),
+                KeyComparator::default(),
+                EventListeners::default(),
+            )
+            .await
+            .unwrap()
+        }
+    }
+
+    /// Runs a compaction job to completion on an executor that cuts an output SST after
+    /// every block, returning the reported output SSTs and the output sorted run.
+    async fn run_compaction_job(
+        table_store: Arc<TableStore>,
+        manifest_store: Arc<ManifestStore>,
+        job: StartCompactionJobArgs,
+    ) -> (Vec<SsTableHandle>, SortedRun) {
+        let (tx, mut rx) = tokio::sync::mpsc::unbounded_channel();
+        let executor = TokioCompactionExecutor::new(
+            Handle::current(),
+            Arc::new(CompactorOptions {
+                max_sst_size: 1,
+                ..compactor_options()
+            }),
+            tx,
+            table_store,
+            Arc::new(DbRand::default()),
+            Arc::new(CompactionStats::new(Arc::new(StatRegistry::new()))),
+            Arc::new(DefaultSystemClock::new()),
+            manifest_store,
+            None,
+            None,
+            HashMap::new(),
+        );
+        executor.start_compaction_job(job);
+        let mut outputs = Vec::new();
+        loop {
+            match rx.recv().await.expect("channel closed") {
+                CompactorMessage::CompactionJobOutput { sst, .. } => outputs.push(*sst),
+                CompactorMessage::CompactionJobFinished { result, .. } => {
+                    return (outputs, result.unwrap());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    async fn read_keys(table_store: Arc<TableStore>, sr: &SortedRun) -> Vec<Bytes> {
+        let mut keys = Vec::new();
+        for sst in &sr.ssts {
+            let mut iter = SstIterator::new_borrowed_initialized(
+                ..,
+                sst,
+                table_store.clone(),
+                SstIteratorOptions::default(),
+            )
+            .await
+            .unwrap()
+            .expect("Expected Some(iter) but got None");
+            while let Some(kv) = iter.next().await.unwrap() {
+                keys.push(kv.key);
+            }
+        }
+        keys
+    }
+
+    #[tokio::test]
+    async fn test_should_persist_compaction_until_finished() {
+        // given:
+        let mut fixture = CompactorEventHandlerTestFixture::new().await;
+        fixture.write_l0().await;
+        let compaction = fixture.build_l0_compaction().await;
+        fixture.scheduler.inject_compaction(compaction.clone());
+        fixture.handler.handle_ticker().await;
+        let job = fixture.assert_started_compaction(1).pop().unwrap();
+        let persisted = fixture.persisted_compactions().await;
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].id(), job.compaction_id);
+        assert_eq!(persisted[0].spec(), &compaction);
+        fixture.real_executor.start_compaction_job(job);
+        let msg = loop {
+            match fixture.real_executor_rx.recv().await {
+                Some(m @ CompactorMessage::CompactionJobFinished { .. }) => break m,
+                Some(_) => continue,
+                None => panic!("channel closed before CompactionJobFinished"),
+            }
+        };
+
+        // when:
+        fixture
+            .handler
+            .handle(msg)
+            .await
+            .expect("fatal error handling compaction message");
+
+        // then:
+        assert!(fixture.persisted_compactions().await.is_empty());
+        assert_eq!(fixture.latest_db_state().await.compacted.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_should_resume_persisted_compaction_after_restart() {
+        // given:
+        let mut fixture = CompactorEventHandlerTestFixture::new().await;
+        fixture.write_l0().await;
+        let compaction = fixture.build_l0_compaction().await;
+        fixture.scheduler.inject_compaction(compaction.clone());
+        fixture.handler.handle_ticker().await;
+        let job = fixture.assert_started_compaction(1).pop().unwrap();
+        let output_sst = SsTableHandle::new_compacted(
+            SsTableId::Compacted(Ulid::new()),
+            fixture.latest_db_state().await.l0[0].info.clone(),
+            None,
+        );
+        fixture
+            .handler
+            .handle(CompactorMessage::CompactionJobOutput {
+                id: job.id,
+                sst: Box::new(output_sst.clone()),
+            })
+            .await
+            .expect("fatal error handling compaction message");
+
+        // when:
+        let mut handler = fixture.restart_handler().await;
+        handler.handle_ticker().await;
+
+        // then:
+        let resumed = fixture.assert_started_compaction(1).pop().unwrap();
+        assert_eq!(resumed.compaction_id, job.compaction_id);
+        assert_eq!(resumed.output_ssts, vec![output_sst]);
+        let persisted = fixture.persisted_compactions().await;
+        assert_eq!(persisted.len(), 1);
+        assert_eq!(persisted[0].id(), job.compaction_id);
+    }
+
+    #[tokio::test]
+    async fn test_should_drop_persisted_compaction_when_sources_are_gone() {
+        // given:
+        let mut fixture = CompactorEventHandlerTestFixture::new().await;
+        fixture.write_l0().await;
+        let compaction = fixture.build_l0_compaction().await;
+        fixture.scheduler.inject_compaction(compaction.clone());
+        fixture.handler.handle_ticker().await;
+        fixture.assert_started_compaction(1);
+        // the compaction finished, but the compactor stopped before removing it
+        let mut dirty = fixture.manifest.prepare_dirty().unwrap();
+        let l0 = dirty.value.core.l0.pop_back().unwrap();
+        dirty.value.core.compacted.push(SortedRun {
+            id: 0,
+            ssts: vec![SsTableHandle::new_compacted(
+                SsTableId::Compacted(Ulid::new()),
+                l0.info.clone(),
+                None,
+            )],
+        });
+        fixture.manifest.update(dirty).await.unwrap();
+
+        // when:
+        let mut handler = fixture.restart_handler().await;
+        handler.handle_ticker().await;
+
+        // then:
+        fixture.assert_started_compaction(0);
+        assert!(fixture.persisted_compactions().await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_resume_compaction_job_after_last_output_key() {
+        // given:
+        let mut fixture = CompactorEventHandlerTestFixture::new().await;
+        for _ in 0..6 {
+            fixture.write_l0().await;
+        }
+        let compaction = fixture.build_l0_compaction().await;
+        fixture.scheduler.inject_compaction(compaction.clone());
+        fixture.handler.handle_ticker().await;
+        let job = fixture.assert_started_compaction(1).pop().unwrap();
+        let (outputs, sr) = run_compaction_job(
+            fixture.table_store.clone(),
+            fixture.manifest_store.clone(),
+            job.clone(),
+        )
+        .await;
+        assert!(sr.ssts.len() >= 3);
+        assert_eq!(outputs, sr.ssts[..sr.ssts.len() - 1].to_vec());
+
+        // when:
+        let resumed_job = StartCompactionJobArgs {
+            output_ssts: outputs[..1].to_vec(),
+            ..job
+        };
+        let (_, resumed_sr) = run_compaction_job(
+            fixture.table_store.clone(),
+            fixture.manifest_store.clone(),
+            resumed_job,
+        )
+        .await;
+
+        // then:
+        assert_eq!(resumed_sr.ssts[0], outputs[0]);
+        assert!(resumed_sr.ssts.len() > 1);
+        assert_eq!(
+            read_keys(fixture.table_store.clone(), &resumed_sr).await,
+            read_keys(fixture.table_store.clone(), &sr).await
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_notify_event_listeners_of_compaction() {
+        // given:
+        let mut fixture = CompactorEventHandlerTestFixture::new().await;
+        fixture.write_l0().await;
+        let compaction = fixture.build_l0_compaction().await;
+        let source_ssts: Vec<Ulid> = compaction
+            .sources()
+            .iter()
+            .map(|source| match source {
+                SourceId::Sst(id) => *id,
+                SourceId::SortedRun(_) => unreachable!(),
+            })
+            .collect();
+        fixture.scheduler.inject_compaction(compaction.clone());
+        fixture.handler.handle_ticker().await;
+        let job = fixture.assert_started_compaction(1).pop().unwrap();
+        fixture.real_executor.start_compaction_job(job.clone());
+        let msg = loop {
+            match fixture.real_executor_rx.recv().await {
+                Some(m @ CompactorMessage::CompactionJobFinished { .. }) => break m,
+                Some(_) => continue,
+                None => panic!("channel closed before CompactionJobFinished"),
+            }
+        };
+
+        // when:
+        fixture
+            .handler
+            .handle(msg)
+            .await
+            .expect("fatal error handling compaction message");
+
+        // then:
+        let started = fixture.event_listener.compaction_started.lock().clone();
+        assert_eq!(started.len(), 1);
+        assert_eq!(started[0].id, job.compaction_id);
+        assert_eq!(started[0].source_ssts, source_ssts);
+        assert!(started[0].source_sorted_runs.is_empty());
+        assert_eq!(started[0].destination, 0);
+        assert_eq!(started[0].source_bytes, job.estimated_source_bytes());
+        let finished = fixture.event_listener.compaction_finished.lock().clone();
+        assert_eq!(finished.len(), 1);
+        assert_eq!(finished[0].id, job.compaction_id);
+        assert_eq!(finished[0].source_ssts, source_ssts);
+        let db_state = fixture.latest_db_state().await;
+        let output_sr = db_state.compacted.first().unwrap();
+        assert_eq!(
+            finished[0].output_ssts,
+            output_sr
+                .ssts
+                .iter()
+                .map(|sst| sst.id.unwrap_compacted_id())
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(finished[0].bytes, output_sr.estimate_size());
+        let manifest_id = fixture
+            .manifest_store
+            .read_latest_manifest()
+            .await
+            .unwrap()
+            .0;
+        assert_eq!(
+            fixture.event_listener.manifest_written.lock().last(),
+            Some(&ManifestWrittenEvent { id: manifest_id })
+        );
     }
 
     #[tokio::test]
@@ -2117,7 +3258,10 @@ mod tests {
     async fn test_validate_compaction_empty_sources_rejected() {
         let fixture = CompactorEventHandlerTestFixture::new().await;
         let c = CompactionSpec::new(Vec::new(), 0);
-        let err = fixture.handler.validate_compaction(&c).unwrap_err();
+        let err = fixture
+            .handler
+            .validate_compaction(&Compaction::new(Ulid::new(), c))
+            .unwrap_err();
         assert!(matches!(err, SlateDBError::InvalidCompaction));
     }
 
@@ -2127,7 +3271,10 @@ mod tests {
         // ensure at least one L0 exists
         fixture.write_l0().await;
         let c = fixture.build_l0_compaction().await;
-        fixture.handler.validate_compaction(&c).unwrap();
+        fixture
+            .handler
+            .validate_compaction(&Compaction::new(Ulid::new(), c))
+            .unwrap();
     }
 
     #[tokio::test]
@@ -2157,7 +3304,10 @@ mod tests {
         // now highest_id should be 1; build L0-only compaction with dest 0 (below highest)
         fixture.write_l0().await;
         let c2 = fixture.build_l0_compaction().await; // destination 0
-        let err = fixture.handler.validate_compaction(&c2).unwrap_err();
+        let err = fixture
+            .handler
+            .validate_compaction(&Compaction::new(Ulid::new(), c2))
+            .unwrap_err();
         assert!(matches!(err, SlateDBError::InvalidCompaction));
     }
 
@@ -2195,7 +3345,10 @@ mod tests {
             sr_id,
         );
         // Compactor-level validation should not reject (scheduler default validate returns Ok(()))
-        fixture.handler.validate_compaction(&mixed).unwrap();
+        fixture
+            .handler
+            .validate_compaction(&Compaction::new(Ulid::new(), mixed))
+            .unwrap();
     }
 
     async fn run_for<T, F>(duration: Duration, f: impl Fn() -> F) -> Option<T>
@@ -2258,6 +3411,52 @@ mod tests {
         .await
     }
 
+    #[tokio::test]
+    async fn test_should_start_submitted_compaction() {
+        // given:
+        let mut fixture = CompactorEventHandlerTestFixture::new().await;
+        fixture.write_l0().await;
+        let compaction = Compaction::new(Ulid::new(), fixture.build_l0_compaction().await);
+        let mut stored = StoredCompactions::load_or_create(fixture.compactions_store.clone())
+            .await
+            .unwrap();
+        stored.submit_compaction(compaction.clone()).await.unwrap();
+
+        // when:
+        fixture.handler.handle_ticker().await;
+
+        // then:
+        let job = fixture.assert_started_compaction(1).pop().unwrap();
+        assert_eq!(job.compaction_id, compaction.id());
+        let persisted = stored.refresh().await.unwrap();
+        assert_eq!(persisted.compactions, vec![compaction]);
+        assert!(persisted.submitted.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_should_reject_submitted_compaction_with_missing_sources() {
+        // given:
+        let mut fixture = CompactorEventHandlerTestFixture::new().await;
+        fixture.write_l0().await;
+        let compaction = Compaction::new(
+            Ulid::new(),
+            CompactionSpec::new(vec![SourceId::SortedRun(7)], 7),
+        );
+        let mut stored = StoredCompactions::load_or_create(fixture.compactions_store.clone())
+            .await
+            .unwrap();
+        stored.submit_compaction(compaction).await.unwrap();
+
+        // when:
+        fixture.handler.handle_ticker().await;
+
+        // then:
+        fixture.assert_started_compaction(0);
+        let persisted = stored.refresh().await.unwrap();
+        assert!(persisted.compactions.is_empty());
+        assert!(persisted.submitted.is_empty());
+    }
+
     #[allow(unused)] // only used with feature(wal_disable)
     async fn await_compacted_compaction(
         manifest_store: Arc<ManifestStore>,
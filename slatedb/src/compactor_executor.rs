@@ -1,27 +1,35 @@
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::mem;
+use std::ops::Bound;
 use std::sync::atomic::{self, AtomicBool};
 use std::sync::Arc;
 
+use bytes::Bytes;
 use chrono::TimeDelta;
 use futures::future::{join, join_all};
 use parking_lot::Mutex;
 use tokio::task::JoinHandle;
 
+use crate::blob_file::BlobRewriteIterator;
 use crate::clock::SystemClock;
+use crate::column_family::{ColumnFamilyOptions, DEFAULT_COLUMN_FAMILY_ID};
+use crate::compaction_filter::CompactionFilterType;
 use crate::compactor::CompactorMessage;
 use crate::compactor::CompactorMessage::CompactionJobFinished;
 use crate::config::CompactorOptions;
-use crate::db_state::{SortedRun, SsTableHandle, SsTableId};
+use crate::db_state::{CoreDbState, SortedRun, SsTableHandle, SsTableId};
 use crate::error::SlateDBError;
-use crate::iter::KeyValueIterator;
+use crate::iter::{IterationOrder, KeyValueIterator};
 use crate::manifest::store::{ManifestStore, StoredManifest};
 use crate::merge_iterator::MergeIterator;
 use crate::merge_operator::{
     MergeOperatorIterator, MergeOperatorRequiredIterator, MergeOperatorType,
 };
 use crate::rand::DbRand;
+use crate::range_tombstone::{RangeTombstone, RangeTombstoneIterator, RangeTombstones};
+use crate::rate_limiter::RateLimiter;
 use crate::retention_iterator::RetentionIterator;
+use crate::seq_tracker::{FindOption, SequenceTracker};
 use crate::sorted_run_iterator::SortedRunIterator;
 use crate::sst_iter::{SstIterator, SstIteratorOptions};
 use crate::tablestore::TableStore;
@@ -49,6 +57,8 @@ pub(crate) struct StartCompactionJobArgs {
     pub(crate) compaction_id: Ulid,
     /// Destination sorted run id to be produced by this job.
     pub(crate) destination: u32,
+    /// The column family whose SSTs and sorted runs are compacted.
+    pub(crate) column_family: u32,
     /// Input L0 SSTs for this job.
     pub(crate) ssts: Vec<SsTableHandle>,
     /// Input existing sorted runs for this job.
@@ -60,6 +70,9 @@ pub(crate) struct StartCompactionJobArgs {
     pub(crate) is_dest_last_run: bool,
     /// Optional minimum sequence to retain; lower sequences may be dropped by retention.
     pub(crate) retention_min_seq: Option<u64>,
+    /// Output SSTs already written by an earlier run of this compaction. The job resumes
+    /// after the last key of the last of them.
+    pub(crate) output_ssts: Vec<SsTableHandle>,
 }
 
 impl std::fmt::Debug for StartCompactionJobArgs {
@@ -68,6 +81,7 @@ impl std::fmt::Debug for StartCompactionJobArgs {
             .field("id", &self.id)
             .field("job_id", &self.compaction_id)
             .field("destination", &self.destination)
+            .field("column_family", &self.column_family)
             .field("ssts", &self.ssts)
             .field("sorted_runs", &self.sorted_runs)
             .field(
@@ -77,6 +91,7 @@ impl std::fmt::Debug for StartCompactionJobArgs {
             .field("is_dest_last_run", &self.is_dest_last_run)
             .field("estimated_source_bytes", &self.estimated_source_bytes())
             .field("retention_min_seq", &self.retention_min_seq)
+            .field("output_ssts", &self.output_ssts)
             .finish()
     }
 }
@@ -106,6 +121,10 @@ pub(crate) trait CompactionExecutor {
 
     /// Returns true if the executor has been stopped (but not necessarily finished).
     fn is_stopped(&self) -> bool;
+
+    /// Replaces the compactor options used by the jobs started after this call. Executors
+    /// that don't run jobs with the compactor options ignore them.
+    fn update_options(&self, _options: Arc<CompactorOptions>) {}
 }
 
 pub(crate) struct TokioCompactionExecutor {
@@ -123,10 +142,13 @@ impl TokioCompactionExecutor {
         clock: Arc<dyn SystemClock>,
         manifest_store: Arc<ManifestStore>,
         merge_operator: Option<MergeOperatorType>,
+        compaction_filter: Option<CompactionFilterType>,
+        column_families: HashMap<String, ColumnFamilyOptions>,
     ) -> Self {
+        let rate_limiter = RateLimiter::new(options.max_write_bytes_per_second, clock.clone());
         Self {
             inner: Arc::new(TokioCompactionExecutorInner {
-                options,
+                options: Mutex::new(options),
                 handle,
                 worker_tx,
                 table_store,
@@ -137,9 +159,22 @@ impl TokioCompactionExecutor {
                 is_stopped: AtomicBool::new(false),
                 manifest_store,
                 merge_operator,
+                compaction_filter,
+                column_families,
+                rate_limiter,
             }),
         }
     }
+
+    /// Runs a compaction job on the current task and returns the resulting [`SortedRun`].
+    /// Unlike [`CompactionExecutor::start_compaction_job`], the job's completion is not
+    /// reported on the worker channel, only its progress and output SSTs.
+    pub(crate) async fn execute_compaction_job(
+        &self,
+        args: StartCompactionJobArgs,
+    ) -> Result<SortedRun, SlateDBError> {
+        self.inner.execute_compaction_job(args).await
+    }
 }
 
 impl CompactionExecutor for TokioCompactionExecutor {
@@ -154,6 +189,13 @@ impl CompactionExecutor for TokioCompactionExecutor {
     fn is_stopped(&self) -> bool {
         self.inner.is_stopped()
     }
+
+    fn update_options(&self, options: Arc<CompactorOptions>) {
+        self.inner
+            .rate_limiter
+            .set_bytes_per_second(options.max_write_bytes_per_second);
+        *self.inner.options.lock() = options;
+    }
 }
 
 struct TokioCompactionTask {
@@ -161,87 +203,258 @@ struct TokioCompactionTask {
 }
 
 pub(crate) struct TokioCompactionExecutorInner {
-    options: Arc<CompactorOptions>,
+    options: Mutex<Arc<CompactorOptions>>,
     handle: tokio::runtime::Handle,
     worker_tx: tokio::sync::mpsc::UnboundedSender<CompactorMessage>,
     table_store: Arc<TableStore>,
-    tasks: Arc<Mutex<HashMap<u32, TokioCompactionTask>>>,
+    /// Running tasks by column family and destination sorted run id.
+    tasks: Arc<Mutex<HashMap<(u32, u32), TokioCompactionTask>>>,
     rand: Arc<DbRand>,
     stats: Arc<CompactionStats>,
     clock: Arc<dyn SystemClock>,
     is_stopped: AtomicBool,
     manifest_store: Arc<ManifestStore>,
     merge_operator: Option<MergeOperatorType>,
+    compaction_filter: Option<CompactionFilterType>,
+    /// Options of the column families, by name.
+    column_families: HashMap<String, ColumnFamilyOptions>,
+    /// Limits the bytes per second written by all the running compactions.
+    rate_limiter: RateLimiter,
 }
 
 impl TokioCompactionExecutorInner {
+    fn options(&self) -> Arc<CompactorOptions> {
+        self.options.lock().clone()
+    }
+
     /// Builds input iterators for all sources (L0 and SR) and wraps them with optional
     /// merge and retention logic.
+    /// If `resume_key` is set, the iterators start after it. The values of
+    /// `rewritten_blob_files` are returned in place of the blob refs pointing to them.
     async fn load_iterators<'a>(
         &self,
         job_args: &'a StartCompactionJobArgs,
+        resume_key: Option<&'a Bytes>,
+        sequence_tracker: Arc<SequenceTracker>,
+        merge_operator: Option<MergeOperatorType>,
+        compaction_filter: Option<CompactionFilterType>,
+        rewritten_blob_files: HashSet<Ulid>,
     ) -> Result<RetentionIterator<Box<dyn KeyValueIterator + 'a>>, SlateDBError> {
         let sst_iter_options = SstIteratorOptions {
             max_fetch_tasks: 4,
             blocks_to_fetch: 256,
             cache_blocks: false, // don't clobber the cache
             eager_spawn: true,
+            // values in blob files are left in place. The merge operator and the compaction
+            // filter read the ones they need.
+            resolve_blob_refs: false,
+            ..SstIteratorOptions::default()
         };
 
+        let start_bound = resume_key.map_or(Bound::Unbounded, Bound::Excluded);
         let max_parallel = compute_max_parallel(job_args.ssts.len(), &job_args.sorted_runs, 4);
         // L0 (borrowed)
         let l0_iters_futures = build_concurrent(job_args.ssts.iter(), max_parallel, |h| {
-            SstIterator::new_borrowed_initialized(.., h, self.table_store.clone(), sst_iter_options)
+            SstIterator::new_borrowed_initialized(
+                (start_bound.cloned(), Bound::Unbounded),
+                h,
+                self.table_store.clone(),
+                sst_iter_options,
+            )
         });
 
         // SR (borrowed)
         let sr_iters_futures =
             build_concurrent(job_args.sorted_runs.iter(), max_parallel, |sr| async {
-                SortedRunIterator::new_borrowed(.., sr, self.table_store.clone(), sst_iter_options)
-                    .await
-                    .map(Some)
+                SortedRunIterator::new_borrowed(
+                    (start_bound.map(|key| key.as_ref()), Bound::Unbounded),
+                    sr,
+                    self.table_store.clone(),
+                    sst_iter_options,
+                )
+                .await
+                .map(Some)
             });
 
         let (l0_iters_res, sr_iters_res) = join(l0_iters_futures, sr_iters_futures).await;
         let l0_iters = l0_iters_res?;
         let sr_iters = sr_iters_res?;
 
-        let l0_merge_iter = MergeIterator::new(l0_iters)?.with_dedup(false);
-        let sr_merge_iter = MergeIterator::new(sr_iters)?.with_dedup(false);
-
-        let merge_iter = MergeIterator::new([l0_merge_iter, sr_merge_iter])?.with_dedup(false);
-        let merge_iter = if let Some(merge_operator) = self.merge_operator.clone() {
-            Box::new(MergeOperatorIterator::new(
-                merge_operator,
-                merge_iter,
-                false,
-                job_args.compaction_logical_clock_tick,
-            ))
+        // Entries covered by a droppable range tombstone are turned into point tombstones
+        // before merging, so merge operands are never applied on top of deleted values.
+        let mut droppable_tombstones = RangeTombstones::new();
+        for tombstone in Self::input_range_tombstones(job_args) {
+            if self.is_range_tombstone_droppable(job_args, &tombstone, &sequence_tracker) {
+                droppable_tombstones.push(tombstone);
+            }
+        }
+        let droppable_tombstones = Arc::new(droppable_tombstones);
+        let l0_iters = l0_iters
+            .into_iter()
+            .map(|iter| RangeTombstoneIterator::new(iter, droppable_tombstones.clone()));
+        let sr_iters = sr_iters
+            .into_iter()
+            .map(|iter| RangeTombstoneIterator::new(iter, droppable_tombstones.clone()));
+
+        let comparator = self.table_store.comparator();
+        let l0_merge_iter = MergeIterator::new(l0_iters)?
+            .with_dedup(false)
+            .with_comparator(comparator.clone());
+        let sr_merge_iter = MergeIterator::new(sr_iters)?
+            .with_dedup(false)
+            .with_comparator(comparator.clone());
+
+        let merge_iter = MergeIterator::new([l0_merge_iter, sr_merge_iter])?
+            .with_dedup(false)
+            .with_comparator(comparator.clone());
+        let merge_iter =
+            BlobRewriteIterator::new(merge_iter, rewritten_blob_files, self.table_store.clone());
+        let merge_iter = if let Some(merge_operator) = merge_operator {
+            Box::new(
+                MergeOperatorIterator::new(
+                    merge_operator,
+                    merge_iter,
+                    false,
+                    job_args.compaction_logical_clock_tick,
+                )
+                .with_table_store(self.table_store.clone()),
+            )
         } else {
             Box::new(MergeOperatorRequiredIterator::new(merge_iter)) as Box<dyn KeyValueIterator>
         };
 
-        let stored_manifest = StoredManifest::load(self.manifest_store.clone()).await?;
         let mut retention_iter = RetentionIterator::new(
             merge_iter,
-            None,
+            self.options().history_retention,
             job_args.retention_min_seq,
             job_args.is_dest_last_run,
             job_args.compaction_logical_clock_tick,
             self.clock.clone(),
-            Arc::new(stored_manifest.db_state().sequence_tracker.clone()),
+            sequence_tracker,
         )
-        .await?;
+        .await?
+        .with_range_tombstones(droppable_tombstones);
+        if let Some(compaction_filter) = compaction_filter {
+            retention_iter = retention_iter
+                .with_compaction_filter(compaction_filter, self.stats.clone())
+                .with_table_store(self.table_store.clone());
+        }
         retention_iter.init().await?;
         Ok(retention_iter)
     }
 
+    /// Returns the blob files that the input SSTs of a compaction job point to and whose
+    /// live bytes, summed over the SSTs of `db_state`, are below `min_live_ratio` of their
+    /// size.
+    async fn sparse_blob_files(
+        &self,
+        job_args: &StartCompactionJobArgs,
+        db_state: &CoreDbState,
+        min_live_ratio: f64,
+    ) -> Result<HashSet<Ulid>, SlateDBError> {
+        let input_blob_files: HashSet<Ulid> = job_args
+            .ssts
+            .iter()
+            .chain(job_args.sorted_runs.iter().flat_map(|sr| sr.ssts.iter()))
+            .flat_map(|sst| sst.info.blob_refs.iter().map(|blob_ref| blob_ref.id))
+            .collect();
+        if input_blob_files.is_empty() {
+            return Ok(HashSet::new());
+        }
+        let mut live_bytes: HashMap<Ulid, u64> = HashMap::new();
+        let db_ssts = db_state
+            .all_l0()
+            .chain(db_state.all_compacted().flat_map(|sr| sr.ssts.iter()));
+        for sst in db_ssts {
+            for blob_ref in &sst.info.blob_refs {
+                *live_bytes.entry(blob_ref.id).or_default() += blob_ref.live_bytes;
+            }
+        }
+        let mut sparse_blob_files = HashSet::new();
+        for blob in self.table_store.list_blob_files().await? {
+            if !input_blob_files.contains(&blob.id) {
+                continue;
+            }
+            let live = live_bytes.get(&blob.id).copied().unwrap_or_default();
+            if (live as f64) < blob.size as f64 * min_live_ratio {
+                debug!(
+                    "rewriting values of sparse blob file [id={}, size={}, live_bytes={}]",
+                    blob.id, blob.size, live,
+                );
+                sparse_blob_files.insert(blob.id);
+            }
+        }
+        Ok(sparse_blob_files)
+    }
+
+    /// Returns the range tombstones stored in the input SSTs of a compaction job.
+    fn input_range_tombstones(job_args: &StartCompactionJobArgs) -> Vec<RangeTombstone> {
+        job_args
+            .ssts
+            .iter()
+            .chain(job_args.sorted_runs.iter().flat_map(|sr| sr.ssts.iter()))
+            .flat_map(|sst| sst.range_tombstones())
+            .collect()
+    }
+
+    /// A range tombstone is droppable once every active snapshot can see it and it is older
+    /// than the history retention window: the entries it covers can then be removed physically.
+    fn is_range_tombstone_droppable(
+        &self,
+        job_args: &StartCompactionJobArgs,
+        tombstone: &RangeTombstone,
+        sequence_tracker: &SequenceTracker,
+    ) -> bool {
+        let visible_to_snapshots = job_args
+            .retention_min_seq
+            .is_none_or(|min_seq| tombstone.seq <= min_seq);
+        let outside_history = self.options().history_retention.is_none_or(|retention| {
+            let now = self.clock.now().timestamp_millis();
+            // same conservative estimate of the write time as the RetentionIterator
+            let created = sequence_tracker
+                .find_ts(tombstone.seq, FindOption::RoundUp)
+                .map_or(now, |ts| ts.timestamp_millis());
+            created + (retention.as_millis() as i64) <= now
+        });
+        visible_to_snapshots && outside_history
+    }
+
+    /// Returns the last key written to the output SSTs of a resumed compaction job.
+    async fn last_output_key(
+        &self,
+        output_ssts: &[SsTableHandle],
+    ) -> Result<Option<Bytes>, SlateDBError> {
+        let Some(last_sst) = output_ssts.last() else {
+            return Ok(None);
+        };
+        let sst_iter_options = SstIteratorOptions {
+            cache_blocks: false,
+            order: IterationOrder::Descending,
+            resolve_blob_refs: false,
+            ..SstIteratorOptions::default()
+        };
+        let Some(mut iter) = SstIterator::new_owned_initialized(
+            ..,
+            last_sst.clone(),
+            self.table_store.clone(),
+            sst_iter_options,
+        )
+        .await?
+        else {
+            return Ok(None);
+        };
+        Ok(iter.next_entry().await?.map(|entry| entry.key))
+    }
+
     /// Executes a single compaction job and returns the resulting [`SortedRun`].
     ///
     /// ## Steps
-    /// - Streams and merges input keys across all sources
+    /// - Streams and merges input keys across all sources, starting after the last key of
+    ///   the output SSTs of an earlier run of the job, if any
     /// - Applies merge and retention policies
-    /// - Writes output SSTs up to `max_sst_size`, reporting periodic progress
+    /// - Writes output SSTs up to `max_sst_size`, reporting periodic progress and each
+    ///   finished output SST. Output SSTs are only cut between keys, so that a resumed
+    ///   job never splits the versions of a key across runs.
     ///
     /// ## Returns
     /// - The destination [`SortedRun`] with all output SST handles.
@@ -251,14 +464,71 @@ impl TokioCompactionExecutorInner {
         args: StartCompactionJobArgs,
     ) -> Result<SortedRun, SlateDBError> {
         debug!("executing compaction [job_args={:?}]", args);
-        let mut all_iter = self.load_iterators(&args).await?;
-        let mut output_ssts = Vec::new();
-        let mut current_writer = self.table_store.table_writer(SsTableId::Compacted(
-            self.rand.rng().gen_ulid(self.clock.as_ref()),
-        ));
+        let max_sst_size = self.options().max_sst_size;
+        let stored_manifest = StoredManifest::load(self.manifest_store.clone()).await?;
+        let sequence_tracker = Arc::new(stored_manifest.db_state().sequence_tracker.clone());
+        let (merge_operator, compaction_filter, sst_format) =
+            if args.column_family == DEFAULT_COLUMN_FAMILY_ID {
+                (
+                    self.merge_operator.clone(),
+                    self.compaction_filter.clone(),
+                    self.table_store.sst_format().clone(),
+                )
+            } else {
+                // column families the compactor was not configured with use the default options
+                let options = stored_manifest
+                    .db_state()
+                    .column_family(args.column_family)
+                    .and_then(|cf| self.column_families.get(&cf.name))
+                    .cloned()
+                    .unwrap_or_default();
+                (
+                    options.merge_operator.clone(),
+                    options.compaction_filter.clone(),
+                    options.sst_format(self.table_store.sst_format()),
+                )
+            };
+        let rewritten_blob_files = match self.options().min_blob_file_live_ratio {
+            Some(min_live_ratio) => {
+                self.sparse_blob_files(&args, stored_manifest.db_state(), min_live_ratio)
+                    .await?
+            }
+            None => HashSet::new(),
+        };
+        let resume_key = self.last_output_key(&args.output_ssts).await?;
+        let mut all_iter = self
+            .load_iterators(
+                &args,
+                resume_key.as_ref(),
+                Arc::clone(&sequence_tracker),
+                merge_operator,
+                compaction_filter,
+                rewritten_blob_files,
+            )
+            .await?;
+        let mut output_ssts = args.output_ssts.clone();
+        let mut last_key = resume_key.clone();
+        let mut current_writer = self.table_store.table_writer_with_format(
+            SsTableId::Compacted(self.rand.rng().gen_ulid(self.clock.as_ref())),
+            &sst_format,
+        );
         let mut bytes_written = 0usize;
         let mut last_progress_report = self.clock.now();
 
+        // Range tombstones are carried over to the first output SST, unless the output is the
+        // last sorted run and the covered entries have all been dropped by this compaction.
+        // A resumed job has already written them.
+        let mut has_range_tombstones = false;
+        for tombstone in Self::input_range_tombstones(&args) {
+            if output_ssts.is_empty()
+                && !(args.is_dest_last_run
+                    && self.is_range_tombstone_droppable(&args, &tombstone, &sequence_tracker))
+            {
+                current_writer.add_range_tombstone(tombstone);
+                has_range_tombstones = true;
+            }
+        }
+
         while let Some(kv) = all_iter.next_entry().await? {
             let duration_since_last_report =
                 self.clock.now().signed_duration_since(last_progress_report);
@@ -277,26 +547,40 @@ impl TokioCompactionExecutorInner {
                 last_progress_report = self.clock.now();
             }
 
-            if let Some(block_size) = current_writer.add(kv).await? {
-                bytes_written += block_size;
-            }
-
-            if bytes_written > self.options.max_sst_size {
+            if bytes_written > max_sst_size && last_key.as_ref() != Some(&kv.key) {
                 let finished_writer = mem::replace(
                     &mut current_writer,
-                    self.table_store.table_writer(SsTableId::Compacted(
-                        self.rand.rng().gen_ulid(self.clock.as_ref()),
-                    )),
+                    self.table_store.table_writer_with_format(
+                        SsTableId::Compacted(self.rand.rng().gen_ulid(self.clock.as_ref())),
+                        &sst_format,
+                    ),
                 );
                 let sst = finished_writer.close().await?;
 
                 self.stats.bytes_compacted.add(sst.info.filter_offset);
+                // See the progress report above for why send() is allowed.
+                #[allow(clippy::disallowed_methods)]
+                self.worker_tx
+                    .send(CompactorMessage::CompactionJobOutput {
+                        id: args.id,
+                        sst: Box::new(sst.clone()),
+                    })
+                    .expect("failed to send compaction output");
                 output_ssts.push(sst);
                 bytes_written = 0;
             }
+
+            last_key = Some(kv.key.clone());
+            if let Some(block_size) = current_writer.add(kv).await? {
+                bytes_written += block_size;
+                let waited = self.rate_limiter.acquire(block_size as u64).await;
+                if !waited.is_zero() {
+                    self.stats.rate_limit_wait_us.record_duration(waited);
+                }
+            }
         }
 
-        if !current_writer.is_drained() {
+        if !current_writer.is_drained() || (output_ssts.is_empty() && has_range_tombstones) {
             let sst = current_writer.close().await?;
 
             self.stats.bytes_compacted.add(sst.info.filter_offset);
@@ -315,14 +599,12 @@ impl TokioCompactionExecutorInner {
         if self.is_stopped.load(atomic::Ordering::SeqCst) {
             return;
         }
-        let dst = args.destination;
+        let dst = (args.column_family, args.destination);
         self.stats.running_compactions.inc();
         assert!(!tasks.contains_key(&dst));
 
         let id = args.id;
 
-        // TODO(sujeetsawala): Add compaction plan to object store with InProgress status
-
         let this = self.clone();
         let this_cleanup = self.clone();
         let task = spawn_bg_task(
@@ -345,7 +627,13 @@ impl TokioCompactionExecutorInner {
                     .expect("failed to send compaction finished msg");
                 this_cleanup.stats.running_compactions.dec();
             },
-            async move { this.execute_compaction_job(args).await },
+            async move {
+                let _timer = this
+                    .stats
+                    .compaction_latency_us
+                    .start_timer(this.clock.as_ref());
+                this.execute_compaction_job(args).await
+            },
         );
         tasks.insert(dst, TokioCompactionTask { task });
     }
@@ -4,7 +4,9 @@ use std::fmt::{Display, Formatter};
 use log::{error, info};
 use ulid::Ulid;
 
-use crate::db_state::{CoreDbState, SortedRun, SsTableHandle};
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+use crate::comparator::KeyComparator;
+use crate::db_state::{ColumnFamilyState, CoreDbState, SortedRun, SsTableHandle};
 use crate::error::SlateDBError;
 use crate::manifest::Manifest;
 use crate::transactional_object::DirtyObject;
@@ -12,8 +14,9 @@ use crate::transactional_object::DirtyObject;
 /// Identifier for a compaction input source.
 ///
 /// A `SourceId` distinguishes between two kinds of inputs a compaction can read:
-/// an existing compacted sorted run (identified by its run id), or an L0 SSTable
-/// (identified by its ULID).
+/// an existing compacted sorted run (identified by its run id), or a single SSTable
+/// (identified by its ULID). A single SSTable is usually an L0 SST, but it can also be
+/// an SST of a sorted run, for compactions that rewrite only part of a run.
 #[derive(Clone, Debug, Hash, PartialEq, Eq)]
 pub enum SourceId {
     SortedRun(u32),
@@ -67,6 +70,10 @@ impl SourceId {
 
 /// Immutable spec that describes a compaction. Currently, this only holds the
 /// input sources and destination SR id for a compaction.
+///
+/// If the sources include SSTs of sorted runs, the compaction is partial: its output
+/// replaces only those SSTs. When the destination sorted run already exists and is not
+/// one of the sources, the output SSTs are merged into it.
 #[derive(Clone, Debug, PartialEq)]
 pub struct CompactionSpec {
     /// Input sources for the compaction.
@@ -119,6 +126,11 @@ pub(crate) struct Compaction {
     spec: CompactionSpec,
     /// Total number of bytes processed so far for this compaction.
     bytes_processed: u64,
+    /// The column family whose SSTs and sorted runs are compacted.
+    column_family: u32,
+    /// Output SSTs written so far, in key order. A compaction that is resumed (e.g. after
+    /// a compactor restart) continues after the last key of the last output SST.
+    output_ssts: Vec<SsTableHandle>,
 }
 
 impl Compaction {
@@ -127,6 +139,24 @@ impl Compaction {
             id,
             spec,
             bytes_processed: 0,
+            column_family: DEFAULT_COLUMN_FAMILY_ID,
+            output_ssts: Vec::new(),
+        }
+    }
+
+    /// Sets the column family whose SSTs and sorted runs are compacted.
+    pub(crate) fn with_column_family(self, column_family: u32) -> Self {
+        Self {
+            column_family,
+            ..self
+        }
+    }
+
+    /// Sets the output SSTs already written by an earlier attempt of the compaction.
+    pub(crate) fn with_output_ssts(self, output_ssts: Vec<SsTableHandle>) -> Self {
+        Self {
+            output_ssts,
+            ..self
         }
     }
 
@@ -146,7 +176,8 @@ impl Compaction {
             .collect()
     }
 
-    /// Returns all L0 SSTable sources for this compaction.
+    /// Returns all SSTable sources for this compaction: L0 SSTs and single SSTs of
+    /// sorted runs.
     ///
     /// ## Arguments
     /// - `db_state`: The current core DB state from the manifest.
@@ -154,6 +185,7 @@ impl Compaction {
         let ssts_by_id: HashMap<Ulid, &SsTableHandle> = db_state
             .l0
             .iter()
+            .chain(db_state.compacted.iter().flat_map(|sr| sr.ssts.iter()))
             .map(|sst| (sst.id.unwrap_compacted_id(), sst))
             .collect();
 
@@ -165,6 +197,25 @@ impl Compaction {
             .collect()
     }
 
+    /// Returns true if the compaction reads single SSTs of sorted runs. Its output then
+    /// replaces only those SSTs instead of whole sorted runs.
+    ///
+    /// ## Arguments
+    /// - `db_state`: The current core DB state from the manifest.
+    pub(crate) fn is_partial(&self, db_state: &CoreDbState) -> bool {
+        let sorted_run_ssts: HashSet<Ulid> = db_state
+            .compacted
+            .iter()
+            .flat_map(|sr| sr.ssts.iter())
+            .map(|sst| sst.id.unwrap_compacted_id())
+            .collect();
+        self.spec
+            .sources()
+            .iter()
+            .filter_map(|s| s.maybe_unwrap_sst())
+            .any(|id| sorted_run_ssts.contains(&id))
+    }
+
     /// The stable id (ULID) used to track this compaction across messages and attempts.
     pub(crate) fn id(&self) -> Ulid {
         self.id
@@ -175,10 +226,25 @@ impl Compaction {
         &self.spec
     }
 
+    /// The column family whose SSTs and sorted runs are compacted.
+    pub(crate) fn column_family(&self) -> u32 {
+        self.column_family
+    }
+
+    /// The output SSTs written so far, in key order.
+    pub(crate) fn output_ssts(&self) -> &Vec<SsTableHandle> {
+        &self.output_ssts
+    }
+
     /// Sets bytes processed so far for this compaction.
     pub(crate) fn set_bytes_processed(&mut self, bytes: u64) {
         self.bytes_processed = bytes;
     }
+
+    /// Records an output SST that the compaction has finished writing.
+    pub(crate) fn add_output_sst(&mut self, sst: SsTableHandle) {
+        self.output_ssts.push(sst);
+    }
 }
 
 impl Display for Compaction {
@@ -190,13 +256,48 @@ impl Display for Compaction {
             .map(|s| format!("{}", s))
             .collect();
         write!(f, "{:?} -> {}", displayed_sources, self.spec.destination(),)?;
+        if self.column_family != DEFAULT_COLUMN_FAMILY_ID {
+            write!(f, " (column family {})", self.column_family)?;
+        }
         if self.bytes_processed > 0 {
             write!(f, " ({} bytes processed)", self.bytes_processed)?;
         }
+        if !self.output_ssts.is_empty() {
+            write!(f, " ({} output SSTs)", self.output_ssts.len())?;
+        }
         Ok(())
     }
 }
 
+/// The compactor's durable state, stored next to the manifest (see
+/// [`crate::compactions_store`]).
+///
+/// It holds the epoch of the current compactor and the compactions it has started but not
+/// finished yet, along with the output SSTs each has written so far. A restarted compactor
+/// uses it to resume the compactions, and the garbage collector uses it to keep their output
+/// SSTs.
+#[derive(Clone, Debug, PartialEq)]
+pub(crate) struct Compactions {
+    /// The epoch of the compactor that owns the compactions.
+    pub(crate) compactor_epoch: u64,
+    /// The in-progress compactions, in id order.
+    pub(crate) compactions: Vec<Compaction>,
+    /// Compactions submitted by clients that the compactor has yet to start or reject,
+    /// in submission order.
+    pub(crate) submitted: Vec<Compaction>,
+}
+
+impl Compactions {
+    /// Creates the state of a compactor that has not started any compaction.
+    pub(crate) fn new(compactor_epoch: u64) -> Self {
+        Self {
+            compactor_epoch,
+            compactions: Vec::new(),
+            submitted: Vec::new(),
+        }
+    }
+}
+
 /// Process-local runtime state owned by the compactor.
 ///
 /// This is the in-memory view that a single compactor task uses to:
@@ -205,6 +306,7 @@ impl Display for Compaction {
 pub struct CompactorState {
     manifest: DirtyObject<Manifest>,
     compactions: BTreeMap<Ulid, Compaction>,
+    comparator: KeyComparator,
 }
 
 impl CompactorState {
@@ -214,9 +316,20 @@ impl CompactorState {
         Self {
             manifest,
             compactions: BTreeMap::new(),
+            comparator: KeyComparator::default(),
         }
     }
 
+    /// Sets the order of the keys of the database, which orders the SSTs of sorted runs.
+    pub(crate) fn with_comparator(self, comparator: KeyComparator) -> Self {
+        Self { comparator, ..self }
+    }
+
+    /// Returns the order of the keys of the database.
+    pub(crate) fn comparator(&self) -> &KeyComparator {
+        &self.comparator
+    }
+
     /// Returns the current in-memory core DB state derived from the manifest.
     pub(crate) fn db_state(&self) -> &CoreDbState {
         self.manifest.core()
@@ -232,6 +345,34 @@ impl CompactorState {
         self.compactions.values()
     }
 
+    /// Returns the ids of the column families of the database, starting with the
+    /// default column family.
+    pub(crate) fn column_family_ids(&self) -> Vec<u32> {
+        std::iter::once(DEFAULT_COLUMN_FAMILY_ID)
+            .chain(self.db_state().column_families.iter().map(|cf| cf.id))
+            .collect()
+    }
+
+    /// Returns a view of this state restricted to a single column family: its
+    /// `db_state()` holds the column family's L0 SSTs and sorted runs (see
+    /// [`CoreDbState::column_family_view`]), and its compactions are those of the
+    /// column family. Schedulers are invoked once per column family with such a view.
+    pub(crate) fn column_family_view(&self, column_family: u32) -> CompactorState {
+        let mut manifest = self.manifest.clone();
+        manifest.value.core = self.db_state().column_family_view(column_family);
+        let compactions = self
+            .compactions
+            .iter()
+            .filter(|(_, c)| c.column_family() == column_family)
+            .map(|(id, c)| (*id, c.clone()))
+            .collect();
+        CompactorState {
+            manifest,
+            compactions,
+            comparator: self.comparator.clone(),
+        }
+    }
+
     /// Merges the remote (writer) manifest view into the compactor's local state.
     ///
     /// This preserves local knowledge about compactions already applied (e.g., L0 last
@@ -258,6 +399,30 @@ impl CompactorState {
             }
         }
 
+        // the writer owns the list of column families and their L0s, the compactor
+        // owns their sorted runs.
+        let column_families = remote_manifest
+            .core()
+            .column_families
+            .iter()
+            .map(|writer_cf| match my_db_state.column_family(writer_cf.id) {
+                Some(my_cf) => ColumnFamilyState {
+                    l0_last_compacted: my_cf.l0_last_compacted,
+                    l0: writer_cf
+                        .l0
+                        .iter()
+                        .take_while(|sst| {
+                            Some(sst.id.unwrap_compacted_id()) != my_cf.l0_last_compacted
+                        })
+                        .cloned()
+                        .collect(),
+                    compacted: my_cf.compacted.clone(),
+                    ..writer_cf.clone()
+                },
+                None => writer_cf.clone(),
+            })
+            .collect();
+
         // write out the merged core db state and manifest
         let merged = CoreDbState {
             initialized: remote_manifest.value.core.initialized,
@@ -272,6 +437,8 @@ impl CompactorState {
             wal_object_store_uri: my_db_state.wal_object_store_uri.clone(),
             recent_snapshot_min_seq: remote_manifest.value.core.recent_snapshot_min_seq,
             sequence_tracker: remote_manifest.value.core.sequence_tracker,
+            column_families,
+            comparator: remote_manifest.value.core.comparator.clone(),
         };
         remote_manifest.value.core = merged;
         self.manifest = remote_manifest;
@@ -287,14 +454,17 @@ impl CompactorState {
         if self
             .compactions
             .values()
+            .filter(|c| c.column_family() == compaction.column_family())
             .map(|c| c.spec())
             .any(|c| c.destination() == spec.destination())
         {
             // we already have an ongoing compaction for this destination
             return Err(SlateDBError::InvalidCompaction);
         }
-        if self
+        let db_state = self
             .db_state()
+            .column_family_view(compaction.column_family());
+        if db_state
             .compacted
             .iter()
             .any(|sr| sr.id == spec.destination())
@@ -302,6 +472,7 @@ impl CompactorState {
                 SourceId::SortedRun(sr) => *sr == spec.destination(),
                 SourceId::Sst(_) => false,
             })
+            && !compaction.is_partial(&db_state)
         {
             // the compaction overwrites an existing sr but doesn't include the sr
             return Err(SlateDBError::InvalidCompaction);
@@ -331,12 +502,14 @@ impl CompactorState {
     ///
     /// This removes compacted L0 SSTs and source SRs, inserts the output SR in id-descending
     /// order, updates `l0_last_compacted`, and removes the compaction from the in-flight map.
+    /// For partial compactions, the source SSTs are removed from their sorted runs (dropping
+    /// runs left empty), and the output SSTs are merged into the destination SR if it exists.
     pub(crate) fn finish_compaction(&mut self, compaction_id: Ulid, output_sr: SortedRun) {
         if let Some(compaction) = self.compactions.get(&compaction_id) {
             let spec = compaction.spec();
             info!("finished compaction [spec={}]", spec);
             // reconstruct l0
-            let compaction_l0s: HashSet<Ulid> = spec
+            let compaction_ssts: HashSet<Ulid> = spec
                 .sources()
                 .iter()
                 .filter_map(|id| id.maybe_unwrap_sst())
@@ -347,45 +520,84 @@ impl CompactorState {
                 .chain(std::iter::once(&SourceId::SortedRun(spec.destination())))
                 .filter_map(|id| id.maybe_unwrap_sorted_run())
                 .collect();
-            let mut db_state = self.db_state().clone();
+            let column_family = compaction.column_family();
+            let mut db_state = self.db_state().column_family_view(column_family);
             let new_l0: VecDeque<SsTableHandle> = db_state
                 .l0
                 .iter()
                 .filter_map(|l0| {
                     let l0_id = l0.id.unwrap_compacted_id();
-                    if compaction_l0s.contains(&l0_id) {
+                    if compaction_ssts.contains(&l0_id) {
                         return None;
                     }
                     Some(l0.clone())
                 })
                 .collect();
+            // a partial compaction merges its output into the existing destination sr
+            let merge_into_destination = compaction.is_partial(&db_state)
+                && !spec
+                    .sources()
+                    .contains(&SourceId::SortedRun(spec.destination()));
             let mut new_compacted = Vec::new();
             let mut inserted = false;
             for compacted in db_state.compacted.iter() {
+                if merge_into_destination && compacted.id == output_sr.id {
+                    new_compacted.push(Self::merge_sorted_run(
+                        &self.comparator,
+                        compacted,
+                        &compaction_ssts,
+                        &output_sr,
+                    ));
+                    inserted = true;
+                    continue;
+                }
                 if !inserted && output_sr.id >= compacted.id {
                     new_compacted.push(output_sr.clone());
                     inserted = true;
                 }
-                if !compaction_srs.contains(&compacted.id) {
+                if compaction_srs.contains(&compacted.id) {
+                    continue;
+                }
+                let ssts: Vec<SsTableHandle> = compacted
+                    .ssts
+                    .iter()
+                    .filter(|sst| !compaction_ssts.contains(&sst.id.unwrap_compacted_id()))
+                    .cloned()
+                    .collect();
+                if ssts.len() == compacted.ssts.len() {
                     new_compacted.push(compacted.clone());
+                } else if !ssts.is_empty() {
+                    new_compacted.push(SortedRun {
+                        id: compacted.id,
+                        ssts,
+                    });
                 }
             }
             if !inserted {
                 new_compacted.push(output_sr.clone());
             }
             Self::assert_compacted_srs_in_id_order(&new_compacted);
-            let first_source = spec
+            let l0_ids: HashSet<Ulid> = db_state
+                .l0
+                .iter()
+                .map(|sst| sst.id.unwrap_compacted_id())
+                .collect();
+            if let Some(compacted_l0) = spec
                 .sources()
-                .first()
-                .expect("illegal: empty compaction spec");
-            if let Some(compacted_l0) = first_source.maybe_unwrap_sst() {
-                // if there are l0s, the newest must be the first entry in sources
+                .iter()
+                .filter_map(|id| id.maybe_unwrap_sst())
+                .find(|id| l0_ids.contains(id))
+            {
+                // if there are l0s, the newest must be the first l0 entry in sources
                 // TODO: validate that this is the case
                 db_state.l0_last_compacted = Some(compacted_l0)
             }
             db_state.l0 = new_l0;
             db_state.compacted = new_compacted;
-            self.manifest.value.core = db_state;
+            self.manifest
+                .value
+                .core
+                .apply_column_family_view(column_family, db_state);
             if self.compactions.remove(&compaction_id).is_none() {
                 error!(
                     "scheduled compaction not found [compaction_id={}]",
@@ -397,6 +609,33 @@ impl CompactorState {
         }
     }
 
+    /// Replaces the compacted SSTs of a sorted run with the output SSTs of a partial
+    /// compaction, keeping the SSTs ordered by key.
+    fn merge_sorted_run(
+        comparator: &KeyComparator,
+        sorted_run: &SortedRun,
+        compacted_ssts: &HashSet<Ulid>,
+        output_sr: &SortedRun,
+    ) -> SortedRun {
+        let mut ssts: Vec<SsTableHandle> = sorted_run
+            .ssts
+            .iter()
+            .filter(|sst| !compacted_ssts.contains(&sst.id.unwrap_compacted_id()))
+            .chain(output_sr.ssts.iter())
+            .cloned()
+            .collect();
+        ssts.sort_by(|a, b| {
+            comparator.compare(
+                a.compacted_effective_start_key(),
+                b.compacted_effective_start_key(),
+            )
+        });
+        SortedRun {
+            id: sorted_run.id,
+            ssts,
+        }
+    }
+
     /// Debug assertion that compacted sorted runs are kept in strictly descending id order.
     fn assert_compacted_srs_in_id_order(compacted: &[SortedRun]) {
         let mut last_sr_id = u32::MAX;
@@ -526,6 +765,56 @@ mod tests {
         assert_eq!(state.compactions().count(), 0)
     }
 
+    #[test]
+    fn test_should_merge_partial_compaction_into_destination() {
+        // given:
+        let rt = build_runtime();
+        let (_, _, mut state, system_clock, rand) = build_test_state(rt.handle());
+        let mut ssts: Vec<SsTableHandle> = state.db_state().l0.iter().cloned().collect();
+        ssts.sort_by(|a, b| {
+            a.compacted_effective_start_key()
+                .cmp(b.compacted_effective_start_key())
+        });
+        let compaction_id = rand.rng().gen_ulid(system_clock.as_ref());
+        let spec = build_l0_compaction(&state.db_state().l0, 1);
+        state
+            .add_compaction(Compaction::new(compaction_id, spec))
+            .expect("failed to add compaction");
+        state.finish_compaction(
+            compaction_id,
+            SortedRun {
+                id: 1,
+                ssts: ssts[..2].to_vec(),
+            },
+        );
+
+        // when:
+        for (source, output) in [(&ssts[0], &ssts[3]), (&ssts[1], &ssts[2])] {
+            let compaction_id = rand.rng().gen_ulid(system_clock.as_ref());
+            let spec = CompactionSpec::new(vec![Sst(source.id.unwrap_compacted_id())], 0);
+            state
+                .add_compaction(Compaction::new(compaction_id, spec))
+                .expect("failed to add compaction");
+            state.finish_compaction(
+                compaction_id,
+                SortedRun {
+                    id: 0,
+                    ssts: vec![output.clone()],
+                },
+            );
+        }
+
+        // then: the emptied sr is dropped and the outputs are ordered by key
+        assert_eq!(
+            compacted_to_description(&state.db_state().compacted),
+            vec![SortedRunDescription {
+                id: 0,
+                ssts: vec![ssts[2].id, ssts[3].id],
+            }]
+        );
+        assert_eq!(state.compactions().count(), 0);
+    }
+
     #[test]
     fn test_should_merge_db_state_correctly_when_never_compacted() {
         // given:
@@ -139,11 +139,22 @@ impl<T: Ord> Ord for EndBound<T> {
 }
 
 fn cmp_bound<T: Ord>(a: &Bound<T>, b: &Bound<T>, start: bool) -> Ordering {
+    cmp_bound_by(a, b, start, &T::cmp)
+}
+
+/// Compares two bounds with the order of `cmp`. Start bounds order `Unbounded` first,
+/// end bounds order it last.
+fn cmp_bound_by<T, F: Fn(&T, &T) -> Ordering>(
+    a: &Bound<T>,
+    b: &Bound<T>,
+    start: bool,
+    cmp: &F,
+) -> Ordering {
     match (a, b) {
         (Bound::Included(a), Bound::Included(b)) | (Bound::Excluded(a), Bound::Excluded(b)) => {
-            a.cmp(b)
+            cmp(a, b)
         }
-        (Bound::Included(a), Bound::Excluded(b)) => match a.cmp(b) {
+        (Bound::Included(a), Bound::Excluded(b)) => match cmp(a, b) {
             Ordering::Equal => {
                 if start {
                     Ordering::Less
@@ -153,7 +164,7 @@ fn cmp_bound<T: Ord>(a: &Bound<T>, b: &Bound<T>, start: bool) -> Ordering {
             }
             other => other,
         },
-        (Bound::Excluded(a), Bound::Included(b)) => match a.cmp(b) {
+        (Bound::Excluded(a), Bound::Included(b)) => match cmp(a, b) {
             Ordering::Equal => {
                 if start {
                     Ordering::Greater
@@ -235,6 +246,48 @@ impl<T: Ord + Clone> ComparableRange<T> {
         }
     }
 
+    /// Intersects the ranges with their bounds ordered by `cmp` rather than by [`Ord`].
+    pub(crate) fn intersect_by<F: Fn(&T, &T) -> Ordering>(
+        &self,
+        other: &Self,
+        cmp: F,
+    ) -> Option<Self> {
+        let max_start = match cmp_bound_by(&self.start.inner, &other.start.inner, true, &cmp) {
+            Ordering::Greater => &self.start,
+            _ => &other.start,
+        };
+        let min_end = match cmp_bound_by(&self.end.inner, &other.end.inner, false, &cmp) {
+            Ordering::Greater => &other.end,
+            _ => &self.end,
+        };
+        let intersection = Self {
+            start: max_start.clone(),
+            end: min_end.clone(),
+        };
+        if intersection.non_empty_by(cmp) {
+            Some(intersection)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the smallest range that contains both ranges, with their bounds ordered
+    /// by `cmp`.
+    pub(crate) fn span_by<F: Fn(&T, &T) -> Ordering>(&self, other: &Self, cmp: F) -> Self {
+        let min_start = match cmp_bound_by(&self.start.inner, &other.start.inner, true, &cmp) {
+            Ordering::Greater => &other.start,
+            _ => &self.start,
+        };
+        let max_end = match cmp_bound_by(&self.end.inner, &other.end.inner, false, &cmp) {
+            Ordering::Greater => &self.end,
+            _ => &other.end,
+        };
+        Self {
+            start: min_start.clone(),
+            end: max_end.clone(),
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn union(&self, other: &Self) -> Option<Self> {
         // Sort the ranges to make the function commutative
@@ -263,11 +316,16 @@ impl<T: Ord + Clone> ComparableRange<T> {
     }
 
     pub(crate) fn non_empty(&self) -> bool {
+        self.non_empty_by(T::cmp)
+    }
+
+    /// Checks whether the range is non-empty with its bounds ordered by `cmp`.
+    pub(crate) fn non_empty_by<F: Fn(&T, &T) -> Ordering>(&self, cmp: F) -> bool {
         match (&self.start.inner, &self.end.inner) {
-            (Bound::Included(a), Bound::Included(b)) => a <= b,
-            (Bound::Included(a), Bound::Excluded(b)) => a < b,
-            (Bound::Excluded(a), Bound::Excluded(b)) => a < b,
-            (Bound::Excluded(a), Bound::Included(b)) => a < b,
+            (Bound::Included(a), Bound::Included(b)) => cmp(a, b).is_le(),
+            (Bound::Included(a), Bound::Excluded(b)) => cmp(a, b).is_lt(),
+            (Bound::Excluded(a), Bound::Excluded(b)) => cmp(a, b).is_lt(),
+            (Bound::Excluded(a), Bound::Included(b)) => cmp(a, b).is_lt(),
             (Bound::Unbounded, _) => true,
             (_, Bound::Unbounded) => true,
         }
@@ -0,0 +1,166 @@
+use std::cmp::Ordering;
+use std::fmt;
+use std::panic::{RefUnwindSafe, UnwindSafe};
+use std::sync::Arc;
+
+use crate::error::SlateDBError;
+
+/// A trait for defining the order of the keys of a database.
+///
+/// By default, keys are ordered lexicographically by their bytes. A comparator replaces
+/// that order everywhere keys are ordered: in the memtable, in the blocks and indexes of
+/// SSTs, in sorted runs, and when merging sources in reads and compactions. Scan ranges
+/// and seeks are interpreted in the order of the comparator too.
+///
+/// The order must be a total order that is consistent with byte equality: `compare`
+/// must only return [`Ordering::Equal`] for keys with the same bytes. A comparator that
+/// folds case, for example, must break ties between `"Key"` and `"key"` by comparing
+/// their bytes.
+///
+/// The name of the comparator is persisted in the manifest when the database is created.
+/// Opening the database with a comparator of a different name fails, so the name must
+/// change whenever the order changes.
+///
+/// Range deletes and prefix scans rely on the lexicographic order of keys, so they're
+/// not supported by databases with a comparator.
+///
+/// # Examples
+/// Here's an example of a comparator that orders `<name>:<timestamp>` keys by name and
+/// then by descending timestamp, so the latest entry of a name comes first:
+/// ```
+/// use std::cmp::Ordering;
+/// use slatedb::Comparator;
+///
+/// struct LatestFirstComparator;
+///
+/// impl Comparator for LatestFirstComparator {
+///     fn name(&self) -> &str {
+///         "latest_first"
+///     }
+///
+///     fn compare(&self, a: &[u8], b: &[u8]) -> Ordering {
+///         fn split(key: &[u8]) -> (&[u8], &[u8]) {
+///             match key.iter().rposition(|b| *b == b':') {
+///                 Some(i) => (&key[..i], &key[i + 1..]),
+///                 None => (key, &[]),
+///             }
+///         }
+///         let (a_name, a_ts) = split(a);
+///         let (b_name, b_ts) = split(b);
+///         a_name.cmp(b_name).then_with(|| b_ts.cmp(a_ts))
+///     }
+/// }
+/// ```
+pub trait Comparator {
+    /// Returns the name of the comparator, which is persisted in the manifest.
+    fn name(&self) -> &str;
+
+    /// Compares two keys.
+    ///
+    /// # Arguments
+    /// * `a` - The first key
+    /// * `b` - The second key
+    ///
+    /// # Returns
+    /// * The order of `a` relative to `b`
+    fn compare(&self, a: &[u8], b: &[u8]) -> Ordering;
+}
+
+pub(crate) type ComparatorType = Arc<dyn Comparator + Send + Sync>;
+
+/// The order of the keys of a database: the comparator of the database if it has one, or
+/// the lexicographic order of their bytes otherwise.
+#[derive(Clone, Default)]
+pub(crate) struct KeyComparator {
+    comparator: Option<ComparatorType>,
+}
+
+impl KeyComparator {
+    pub(crate) fn new(comparator: Option<ComparatorType>) -> Self {
+        Self { comparator }
+    }
+
+    /// Returns the comparator, or None if keys are ordered by their bytes.
+    pub(crate) fn comparator(&self) -> Option<&ComparatorType> {
+        self.comparator.as_ref()
+    }
+
+    /// Returns the name of the comparator, or None if keys are ordered by their bytes.
+    pub(crate) fn name(&self) -> Option<&str> {
+        self.comparator.as_ref().map(|comparator| comparator.name())
+    }
+
+    /// Returns an error if this comparator is not the one named by a manifest, since
+    /// reading or writing the SSTs of the database with it would use the wrong key order.
+    pub(crate) fn check_matches(&self, expected: Option<&str>) -> Result<(), SlateDBError> {
+        if expected != self.name() {
+            return Err(SlateDBError::ComparatorMismatch {
+                expected: expected.map(String::from),
+                actual: self.name().map(String::from),
+            });
+        }
+        Ok(())
+    }
+
+    /// Returns true if keys are ordered by their bytes.
+    pub(crate) fn is_bytewise(&self) -> bool {
+        self.comparator.is_none()
+    }
+
+    #[inline]
+    pub(crate) fn compare(&self, a: &[u8], b: &[u8]) -> Ordering {
+        match &self.comparator {
+            Some(comparator) => comparator.compare(a, b),
+            None => a.cmp(b),
+        }
+    }
+}
+
+// Comparators only compare keys, so a panic in one can't leave the types that hold a
+// KeyComparator, such as write batches, in a broken state.
+impl UnwindSafe for KeyComparator {}
+impl RefUnwindSafe for KeyComparator {}
+
+impl fmt::Debug for KeyComparator {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("KeyComparator")
+            .field(&self.name().unwrap_or("bytewise"))
+            .finish()
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Orders keys by their bytes in reverse.
+    pub(crate) struct ReverseComparator;
+
+    impl Comparator for ReverseComparator {
+        fn name(&self) -> &str {
+            "reverse"
+        }
+
+        fn compare(&self, a: &[u8], b: &[u8]) -> Ordering {
+            b.cmp(a)
+        }
+    }
+
+    pub(crate) fn reverse_comparator() -> KeyComparator {
+        KeyComparator::new(Some(Arc::new(ReverseComparator)))
+    }
+
+    #[test]
+    fn test_key_comparator() {
+        let bytewise = KeyComparator::default();
+        assert!(bytewise.is_bytewise());
+        assert_eq!(bytewise.name(), None);
+        assert_eq!(bytewise.compare(b"a", b"b"), Ordering::Less);
+
+        let reverse = reverse_comparator();
+        assert!(!reverse.is_bytewise());
+        assert_eq!(reverse.name(), Some("reverse"));
+        assert_eq!(reverse.compare(b"a", b"b"), Ordering::Greater);
+        assert_eq!(reverse.compare(b"a", b"a"), Ordering::Equal);
+    }
+}
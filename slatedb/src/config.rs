@@ -153,6 +153,8 @@
 //!     min_age: '86400s'
 //! ```
 //!
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
 use duration_str::{deserialize_duration, deserialize_option_duration};
 use figment::providers::{Env, Format, Json, Toml, Yaml};
 use figment::{Figment, Metadata, Provider};
@@ -164,9 +166,14 @@ use uuid::Uuid;
 
 use crate::error::SlateDBError;
 
+use crate::compaction_filter::CompactionFilterType;
+use crate::comparator::ComparatorType;
 use crate::db_cache::DbCache;
+use crate::encryption::EncryptionProviderType;
 use crate::garbage_collector::{DEFAULT_INTERVAL, DEFAULT_MIN_AGE};
+pub use crate::iter::IterationOrder;
 use crate::merge_operator::MergeOperatorType;
+use crate::prefix_extractor::PrefixExtractorType;
 
 /// Enum representing different levels of cache preloading on startup
 #[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq)]
@@ -243,6 +250,15 @@ pub struct ReadOptions {
     /// Whether to include dirty data in the scan. "dirty" means that the data is not considered
     /// as "committed" yet, whose seq number is greater than the last committed seq number.
     pub dirty: bool,
+    /// Read the database as it was at this sequence number: writes with a higher
+    /// sequence number are ignored. Old versions are only available while a snapshot,
+    /// checkpoint or [`CompactorOptions::history_retention`] keeps them.
+    pub as_of_seq: Option<u64>,
+    /// Read the database as it was at this time. The timestamp is mapped to the most
+    /// recent sequence number the database recorded at or before it, so the read can
+    /// lag the timestamp by up to the sequence tracker's recording interval. If both
+    /// this and `as_of_seq` are set, the older of the two is used.
+    pub as_of_timestamp: Option<DateTime<Utc>>,
 }
 
 impl ReadOptions {
@@ -260,6 +276,20 @@ impl ReadOptions {
             ..self
         }
     }
+
+    pub fn with_as_of_seq(self, as_of_seq: u64) -> Self {
+        Self {
+            as_of_seq: Some(as_of_seq),
+            ..self
+        }
+    }
+
+    pub fn with_as_of_timestamp(self, as_of_timestamp: DateTime<Utc>) -> Self {
+        Self {
+            as_of_timestamp: Some(as_of_timestamp),
+            ..self
+        }
+    }
 }
 #[derive(Clone, Debug)]
 pub struct ScanOptions {
@@ -279,6 +309,24 @@ pub struct ScanOptions {
     /// The maximum number of concurrent tasks for fetching blocks during scans.
     /// Higher values can improve throughput but use more resources. The default is 1.
     pub max_fetch_tasks: usize,
+    /// The order in which the scan returns keys. The default is
+    /// [`IterationOrder::Ascending`]. With [`IterationOrder::Descending`], the scan
+    /// starts at the end of the range and returns keys in decreasing order.
+    pub order: IterationOrder,
+    /// Read the database as it was at this sequence number: writes with a higher
+    /// sequence number are ignored. Old versions are only available while a snapshot,
+    /// checkpoint or [`CompactorOptions::history_retention`] keeps them.
+    pub as_of_seq: Option<u64>,
+    /// Read the database as it was at this time. The timestamp is mapped to the most
+    /// recent sequence number the database recorded at or before it, so the read can
+    /// lag the timestamp by up to the sequence tracker's recording interval. If both
+    /// this and `as_of_seq` are set, the older of the two is used.
+    pub as_of_timestamp: Option<DateTime<Utc>>,
+    /// Only return keys that start with this prefix. The scan range is narrowed to
+    /// the keys with the prefix, and if the database has a [`Settings::prefix_extractor`]
+    /// that extracts a prefix from it, the L0 SSTs and sorted runs whose bloom filters
+    /// don't contain that prefix are skipped.
+    pub prefix: Option<Bytes>,
 }
 
 impl Default for ScanOptions {
@@ -290,6 +338,10 @@ impl Default for ScanOptions {
             read_ahead_bytes: 1,
             cache_blocks: false,
             max_fetch_tasks: 1,
+            order: IterationOrder::Ascending,
+            as_of_seq: None,
+            as_of_timestamp: None,
+            prefix: None,
         }
     }
 }
@@ -330,6 +382,31 @@ impl ScanOptions {
             ..self
         }
     }
+
+    pub fn with_order(self, order: IterationOrder) -> Self {
+        Self { order, ..self }
+    }
+
+    pub fn with_as_of_seq(self, as_of_seq: u64) -> Self {
+        Self {
+            as_of_seq: Some(as_of_seq),
+            ..self
+        }
+    }
+
+    pub fn with_as_of_timestamp(self, as_of_timestamp: DateTime<Utc>) -> Self {
+        Self {
+            as_of_timestamp: Some(as_of_timestamp),
+            ..self
+        }
+    }
+
+    pub fn with_prefix(self, prefix: impl Into<Bytes>) -> Self {
+        Self {
+            prefix: Some(prefix.into()),
+            ..self
+        }
+    }
 }
 
 /// Enum representing the type of flush to perform.
@@ -366,13 +443,40 @@ pub struct WriteOptions {
     /// Whether `put` calls should block until the write has been durably committed
     /// to the DB.
     pub await_durable: bool,
+
+    /// Whether the write should fail with [`crate::ErrorKind::Unavailable`] instead of
+    /// waiting when it is slowed down or stopped by the [`WriteStallOptions`], or when
+    /// it exceeds [`RateLimitOptions::write_bytes_per_second`].
+    pub fail_on_stall: bool,
 }
 
 impl Default for WriteOptions {
-    /// Create a new `WriteOptions`` with `await_durable` set to `true`.
+    /// Create a new `WriteOptions`` with `await_durable` set to `true` and
+    /// `fail_on_stall` set to `false`.
     fn default() -> Self {
         Self {
             await_durable: true,
+            fail_on_stall: false,
+        }
+    }
+}
+
+impl WriteOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn with_await_durable(self, await_durable: bool) -> Self {
+        Self {
+            await_durable,
+            ..self
+        }
+    }
+
+    pub fn with_fail_on_stall(self, fail_on_stall: bool) -> Self {
+        Self {
+            fail_on_stall,
+            ..self
         }
     }
 }
@@ -597,12 +701,27 @@ pub struct Settings {
     /// The compression algorithm to use for SSTables.
     pub compression_codec: Option<CompressionCodec>,
 
+    /// The minimum size of the values that are stored in blob files instead of in the
+    /// L0 SSTs and sorted runs. Large values in blob files are not rewritten by
+    /// compactions, which reduces write amplification at the cost of an extra read
+    /// for each of them. Blob files are deleted by the garbage collector once no SST
+    /// points to them.
+    ///
+    /// Default: None (all values are stored in SSTs)
+    pub min_blob_value_size: Option<usize>,
+
     /// The object store cache options.
     pub object_store_cache_options: ObjectStoreCacheOptions,
 
     /// Configuration options for the garbage collector.
     pub garbage_collector_options: Option<GarbageCollectorOptions>,
 
+    /// When to slow down and stop writes while flushes and compactions catch up.
+    pub write_stall_options: WriteStallOptions,
+
+    /// Limits on the bytes per second written by clients and by memtable flushes.
+    pub rate_limit_options: RateLimitOptions,
+
     /// The default time-to-live (TTL) for insertions (note that re-inserting a key
     /// with any value will update the TTL to use the default_ttl)
     ///
@@ -616,6 +735,28 @@ pub struct Settings {
     /// during reads and compactions to produce the final result.
     #[serde(skip)]
     pub merge_operator: Option<MergeOperatorType>,
+
+    /// The compaction filter to use for the database. If not set, compactions only drop
+    /// rows by TTL, deletes, and the history retention policy.
+    ///
+    /// The compaction filter is called for each value that survives a compaction, and can
+    /// keep, drop, or replace it. See [`crate::CompactionFilter`].
+    #[serde(skip)]
+    pub compaction_filter: Option<CompactionFilterType>,
+
+    /// The prefix extractor to use for the database. If set, the prefixes of keys are
+    /// added to the bloom filters of L0 SSTs and sorted runs, so scans with
+    /// [`ScanOptions::prefix`] can skip the SSTs that don't contain the prefix.
+    /// See [`crate::PrefixExtractor`].
+    #[serde(skip)]
+    pub prefix_extractor: Option<PrefixExtractorType>,
+
+    /// The comparator that defines the order of the keys of the database. If not set,
+    /// keys are ordered lexicographically by their bytes. The comparator must be set
+    /// when the database is created and can't change afterwards. See
+    /// [`crate::Comparator`].
+    #[serde(skip)]
+    pub comparator: Option<ComparatorType>,
 }
 
 // Implement Debug manually for DbOptions.
@@ -637,11 +778,14 @@ impl std::fmt::Debug for Settings {
             .field("l0_max_ssts", &self.l0_max_ssts)
             .field("compactor_options", &self.compactor_options)
             .field("compression_codec", &self.compression_codec)
+            .field("min_blob_value_size", &self.min_blob_value_size)
             .field(
                 "object_store_cache_options",
                 &self.object_store_cache_options,
             )
             .field("garbage_collector_options", &self.garbage_collector_options)
+            .field("write_stall_options", &self.write_stall_options)
+            .field("rate_limit_options", &self.rate_limit_options)
             .field("filter_bits_per_key", &self.filter_bits_per_key)
             .field("default_ttl", &self.default_ttl)
             .field(
@@ -652,11 +796,61 @@ impl std::fmt::Debug for Settings {
                     .map(|_| "Some(merge_operator)")
                     .unwrap_or("None"),
             )
+            .field(
+                "compaction_filter",
+                &self
+                    .compaction_filter
+                    .as_ref()
+                    .map(|_| "Some(compaction_filter)")
+                    .unwrap_or("None"),
+            )
+            .field(
+                "prefix_extractor",
+                &self
+                    .prefix_extractor
+                    .as_ref()
+                    .map(|extractor| extractor.name())
+                    .unwrap_or("None"),
+            )
+            .field(
+                "comparator",
+                &self
+                    .comparator
+                    .as_ref()
+                    .map(|comparator| comparator.name())
+                    .unwrap_or("None"),
+            )
             .finish()
     }
 }
 
 impl Settings {
+    /// Returns an error if a setting is set to a value the database can't run with.
+    /// The same checks apply to the settings a database is opened with and to the
+    /// settings changed with [`crate::Db::update_settings`].
+    pub(crate) fn validate(&self) -> Result<(), SlateDBError> {
+        self.write_stall_options.validate()?;
+        self.rate_limit_options.validate()?;
+        if let Some(options) = &self.compactor_options {
+            options.validate()?;
+        }
+        Ok(())
+    }
+
+    pub fn with_write_stall_options(self, write_stall_options: WriteStallOptions) -> Self {
+        Self {
+            write_stall_options,
+            ..self
+        }
+    }
+
+    pub fn with_rate_limit_options(self, rate_limit_options: RateLimitOptions) -> Self {
+        Self {
+            rate_limit_options,
+            ..self
+        }
+    }
+
     /// Converts the Settings to a JSON string representation
     pub fn to_json_string(&self) -> Result<String, serde_json::Error> {
         serde_json::to_string(self)
@@ -807,15 +1001,53 @@ impl Default for Settings {
             l0_max_ssts: 8,
             compactor_options: Some(CompactorOptions::default()),
             compression_codec: None,
+            min_blob_value_size: None,
             object_store_cache_options: ObjectStoreCacheOptions::default(),
             garbage_collector_options: None,
+            write_stall_options: WriteStallOptions::default(),
+            rate_limit_options: RateLimitOptions::default(),
             filter_bits_per_key: 10,
             default_ttl: None,
             merge_operator: None,
+            compaction_filter: None,
+            prefix_extractor: None,
+            comparator: None,
         }
     }
 }
 
+/// A change to the settings of an open database, applied with
+/// [`crate::Db::update_settings`]. Fields left as `None` keep their current value.
+///
+/// Only the settings that the background tasks of the database can pick up while they
+/// run can be changed. The other settings require reopening the database.
+#[derive(Clone, Debug, Default)]
+pub struct SettingsPatch {
+    /// The new [`Settings::flush_interval`]. `Some(None)` disables automatic WAL
+    /// flushes.
+    pub flush_interval: Option<Option<Duration>>,
+
+    /// The new [`Settings::l0_sst_size_bytes`].
+    pub l0_sst_size_bytes: Option<usize>,
+
+    /// The new [`Settings::max_unflushed_bytes`].
+    pub max_unflushed_bytes: Option<usize>,
+
+    /// The new options of the compactor that runs with the database. The
+    /// `manifest_update_timeout` and `max_concurrent_compactions` options can't be
+    /// changed.
+    pub compactor_options: Option<CompactorOptions>,
+
+    /// The new options of the garbage collector that runs with the database.
+    pub garbage_collector_options: Option<GarbageCollectorOptions>,
+
+    /// The new [`Settings::write_stall_options`].
+    pub write_stall_options: Option<WriteStallOptions>,
+
+    /// The new [`Settings::rate_limit_options`].
+    pub rate_limit_options: Option<RateLimitOptions>,
+}
+
 #[derive(Clone, Deserialize, Serialize)]
 pub struct DbReaderOptions {
     /// How frequently to poll for new manifest files and WAL data. Refreshing the manifest
@@ -839,6 +1071,21 @@ pub struct DbReaderOptions {
 
     #[serde(skip)]
     pub merge_operator: Option<MergeOperatorType>,
+
+    /// The prefix extractor the database was written with, used to skip SSTs in scans
+    /// with [`ScanOptions::prefix`]. See [`Settings::prefix_extractor`].
+    #[serde(skip)]
+    pub prefix_extractor: Option<PrefixExtractorType>,
+
+    /// The comparator the database was created with. Opening a database with a
+    /// comparator of a different name fails. See [`Settings::comparator`].
+    #[serde(skip)]
+    pub comparator: Option<ComparatorType>,
+
+    /// The provider of the keys the database is encrypted with, if it's encrypted. See
+    /// [`crate::DbBuilder::with_encryption_provider`].
+    #[serde(skip)]
+    pub encryption_provider: Option<EncryptionProviderType>,
 }
 
 impl Default for DbReaderOptions {
@@ -849,6 +1096,9 @@ impl Default for DbReaderOptions {
             max_memtable_bytes: 64 * 1024 * 1024,
             block_cache: default_block_cache(),
             merge_operator: None,
+            prefix_extractor: None,
+            comparator: None,
+            encryption_provider: None,
         }
     }
 }
@@ -956,10 +1206,37 @@ pub struct CompactorOptions {
 
     /// The maximum number of concurrent compactions to execute at once
     pub max_concurrent_compactions: usize,
+
+    /// How long old versions of a key are kept after they are overwritten or deleted.
+    /// Compactions keep every version written within this window, so reads with
+    /// [`ReadOptions::as_of_seq`] or [`ReadOptions::as_of_timestamp`] can see them.
+    ///
+    /// If this value is None, old versions are only kept for active snapshots and
+    /// checkpoints.
+    #[serde(deserialize_with = "deserialize_option_duration")]
+    #[serde(serialize_with = "serialize_option_duration")]
+    pub history_retention: Option<Duration>,
+
+    /// The maximum number of bytes per second that compactions write to object
+    /// storage, shared by the running compactions.
+    ///
+    /// Default: None (compactions are not rate limited)
+    pub max_write_bytes_per_second: Option<u64>,
+
+    /// The fraction of a blob file's bytes that must still be pointed to by the SSTs of
+    /// the database for compactions to leave its values in place. Compactions copy the
+    /// values of sparser blob files into the blob files of their output SSTs, so the
+    /// garbage collector can delete the sparse files once no SST points to them.
+    ///
+    /// If this value is None, values are never copied and a blob file is kept as long as
+    /// one of its values is live.
+    ///
+    /// Default: Some(0.5)
+    pub min_blob_file_live_ratio: Option<f64>,
 }
 
-/// Default options for the compactor. Currently, only a
-/// `SizeTieredCompactionScheduler` compaction strategy is implemented.
+/// Default options for the compactor. The compaction strategy is chosen separately, with
+/// a `CompactionSchedulerSupplier` (size-tiered by default).
 impl Default for CompactorOptions {
     /// Returns a `CompactorOptions` with a 5 second poll interval and a 256MiB max
     /// SSTable size.
@@ -969,8 +1246,46 @@ impl Default for CompactorOptions {
             manifest_update_timeout: Duration::from_secs(300),
             max_sst_size: 256 * 1024 * 1024,
             max_concurrent_compactions: 4,
+            history_retention: None,
+            max_write_bytes_per_second: None,
+            min_blob_file_live_ratio: Some(0.5),
+        }
+    }
+}
+
+impl CompactorOptions {
+    pub fn with_max_write_bytes_per_second(self, max_write_bytes_per_second: u64) -> Self {
+        Self {
+            max_write_bytes_per_second: Some(max_write_bytes_per_second),
+            ..self
+        }
+    }
+
+    pub fn with_min_blob_file_live_ratio(self, min_blob_file_live_ratio: f64) -> Self {
+        Self {
+            min_blob_file_live_ratio: Some(min_blob_file_live_ratio),
+            ..self
         }
     }
+
+    pub(crate) fn validate(&self) -> Result<(), SlateDBError> {
+        if self.max_write_bytes_per_second == Some(0) {
+            return Err(SlateDBError::InvalidSetting {
+                setting: "compactor_options.max_write_bytes_per_second",
+                reason: "the rate must be positive",
+            });
+        }
+        if self
+            .min_blob_file_live_ratio
+            .is_some_and(|ratio| !(0.0..=1.0).contains(&ratio))
+        {
+            return Err(SlateDBError::InvalidSetting {
+                setting: "compactor_options.min_blob_file_live_ratio",
+                reason: "the ratio must be between 0 and 1",
+            });
+        }
+        Ok(())
+    }
 }
 
 // Implement Debug manually for CompactorOptions.
@@ -985,6 +1300,12 @@ impl std::fmt::Debug for CompactorOptions {
                 "max_concurrent_compactions",
                 &self.max_concurrent_compactions,
             )
+            .field("history_retention", &self.history_retention)
+            .field(
+                "max_write_bytes_per_second",
+                &self.max_write_bytes_per_second,
+            )
+            .field("min_blob_file_live_ratio", &self.min_blob_file_live_ratio)
             .finish()
     }
 }
@@ -1014,6 +1335,157 @@ impl Default for SizeTieredCompactionSchedulerOptions {
     }
 }
 
+/// Options for the Leveled Compaction Scheduler
+#[derive(Clone, Debug)]
+pub struct LeveledCompactionSchedulerOptions {
+    /// The number of levels below L0. Level 1 is stored in the sorted run with id
+    /// `num_levels - 1` and the last level in the sorted run with id 0.
+    pub num_levels: usize,
+
+    /// The number of L0 SSTs that triggers a compaction of L0 into level 1.
+    pub l0_compaction_trigger: usize,
+
+    /// The target size of level 1, in bytes.
+    pub level_base_size_bytes: u64,
+
+    /// The target size of each level beyond level 1 is this value times the target
+    /// size of the level above it. The last level has no target size.
+    pub level_size_multiplier: u32,
+}
+
+impl Default for LeveledCompactionSchedulerOptions {
+    fn default() -> Self {
+        Self {
+            num_levels: 6,
+            l0_compaction_trigger: 4,
+            level_base_size_bytes: 256 * 1024 * 1024,
+            level_size_multiplier: 10,
+        }
+    }
+}
+
+/// Options for the object store compaction job queue, which hands compaction jobs from
+/// the compactor to compaction workers running in other processes.
+#[derive(Clone, Copy, Debug)]
+pub struct CompactionJobQueueOptions {
+    /// The interval at which the compactor checks for the results of its jobs, and idle
+    /// workers check for new jobs.
+    pub poll_interval: Duration,
+
+    /// How long a worker may go without renewing the claim on a job before the job is
+    /// handed to another worker. Workers renew their claims four times per timeout.
+    pub claim_timeout: Duration,
+}
+
+impl Default for CompactionJobQueueOptions {
+    fn default() -> Self {
+        Self {
+            poll_interval: Duration::from_secs(1),
+            claim_timeout: Duration::from_secs(60),
+        }
+    }
+}
+
+/// Options for slowing down and stopping writes when flushes and compactions fall
+/// behind.
+///
+/// Writes are delayed when the number of L0 SSTs or the number of unflushed bytes
+/// crosses a slowdown limit. The delay grows linearly from zero at the slowdown limit to
+/// `max_write_delay` at the stop limit. Writes stop when the number of L0 SSTs reaches
+/// `l0_stop_ssts`, until compactions bring it back down, and when the number of
+/// unflushed bytes reaches [`Settings::max_unflushed_bytes`], until flushes catch up.
+///
+/// Writes with [`WriteOptions::fail_on_stall`] set fail instead of being delayed or
+/// stopped.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct WriteStallOptions {
+    /// The number of L0 SSTs at which writes are slowed down. The SSTs are counted in
+    /// the column family with the most L0 SSTs.
+    ///
+    /// Default: None (writes are not slowed down by L0 SSTs)
+    pub l0_slowdown_ssts: Option<usize>,
+
+    /// The number of L0 SSTs at which writes are stopped. If not set, the delay of
+    /// writes reaches `max_write_delay` at [`Settings::l0_max_ssts`].
+    ///
+    /// Default: None (writes are not stopped by L0 SSTs)
+    pub l0_stop_ssts: Option<usize>,
+
+    /// The number of unflushed bytes at which writes are slowed down. See
+    /// [`Settings::max_unflushed_bytes`] for how the bytes are counted.
+    ///
+    /// Default: None (writes are not slowed down by unflushed bytes)
+    pub unflushed_bytes_slowdown: Option<usize>,
+
+    /// The delay of a write when the stop limits are reached. Stopped writes check the
+    /// limits again at this interval.
+    #[serde(deserialize_with = "deserialize_duration")]
+    #[serde(serialize_with = "serialize_duration")]
+    pub max_write_delay: Duration,
+}
+
+impl Default for WriteStallOptions {
+    fn default() -> Self {
+        Self {
+            l0_slowdown_ssts: None,
+            l0_stop_ssts: None,
+            unflushed_bytes_slowdown: None,
+            max_write_delay: Duration::from_millis(100),
+        }
+    }
+}
+
+impl WriteStallOptions {
+    pub(crate) fn validate(&self) -> Result<(), SlateDBError> {
+        if let (Some(slowdown), Some(stop)) = (self.l0_slowdown_ssts, self.l0_stop_ssts) {
+            if slowdown > stop {
+                return Err(SlateDBError::InvalidSetting {
+                    setting: "write_stall_options.l0_slowdown_ssts",
+                    reason: "the slowdown limit must not exceed the stop limit",
+                });
+            }
+        }
+        // stopped writes poll the limits at this interval
+        if self.max_write_delay.is_zero() {
+            return Err(SlateDBError::InvalidSetting {
+                setting: "write_stall_options.max_write_delay",
+                reason: "the delay must be positive",
+            });
+        }
+        Ok(())
+    }
+}
+
+/// Limits on the bytes per second written by a database. The limits are token buckets
+/// that allow bursts of up to one second of writes. See
+/// [`CompactorOptions::max_write_bytes_per_second`] for the limit of compactions.
+#[derive(Clone, Debug, Default, Deserialize, Serialize)]
+pub struct RateLimitOptions {
+    /// The maximum number of bytes per second of keys and values written by clients.
+    /// Writes with [`WriteOptions::fail_on_stall`] set fail instead of waiting when the
+    /// limit is reached.
+    ///
+    /// Default: None (writes are not rate limited)
+    pub write_bytes_per_second: Option<u64>,
+
+    /// The maximum number of bytes per second that memtable flushes write to L0.
+    ///
+    /// Default: None (flushes are not rate limited)
+    pub flush_bytes_per_second: Option<u64>,
+}
+
+impl RateLimitOptions {
+    pub(crate) fn validate(&self) -> Result<(), SlateDBError> {
+        if self.write_bytes_per_second == Some(0) || self.flush_bytes_per_second == Some(0) {
+            return Err(SlateDBError::InvalidSetting {
+                setting: "rate_limit_options",
+                reason: "the rates must be positive",
+            });
+        }
+        Ok(())
+    }
+}
+
 /// Garbage collector options.
 #[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct GarbageCollectorOptions {
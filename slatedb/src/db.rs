@@ -29,24 +29,26 @@ use object_store::path::Path;
 use object_store::registry::{DefaultObjectStoreRegistry, ObjectStoreRegistry};
 use object_store::ObjectStore;
 
-use crate::compactor::COMPACTOR_TASK_NAME;
+use crate::compactor::{CompactorMessage, COMPACTOR_TASK_NAME};
 use crate::db_transaction::DBTransaction;
 use crate::dispatcher::MessageHandlerExecutor;
-use crate::garbage_collector::GC_TASK_NAME;
+use crate::garbage_collector::{GcMessage, GC_TASK_NAME};
 use crate::transaction_manager::IsolationLevel;
 use parking_lot::RwLock;
 use std::time::Duration;
 use tokio::sync::mpsc::UnboundedSender;
 
-use crate::batch::WriteBatch;
+use crate::batch::{Precondition, WriteBatch};
 use crate::batch_write::{WriteBatchMessage, WRITE_BATCH_TASK_NAME};
 use crate::bytes_range::BytesRange;
 use crate::cached_object_store::CachedObjectStore;
+use crate::change_stream::ChangeStream;
 use crate::clock::MonotonicClock;
 use crate::clock::{LogicalClock, SystemClock};
+use crate::column_family::{ColumnFamilies, ColumnFamily, DEFAULT_COLUMN_FAMILY_ID};
 use crate::config::{
     FlushOptions, FlushType, MergeOptions, PreloadLevel, PutOptions, ReadOptions, ScanOptions,
-    Settings, WriteOptions,
+    Settings, SettingsPatch, WriteOptions,
 };
 use crate::db_iter::DbIterator;
 use crate::db_read::DbRead;
@@ -54,20 +56,24 @@ use crate::db_snapshot::DbSnapshot;
 use crate::db_state::{DbState, SsTableId};
 use crate::db_stats::DbStats;
 use crate::error::SlateDBError;
-use crate::manifest::store::FenceableManifest;
+use crate::event_listener::EventListeners;
+use crate::manifest::store::{FenceableManifest, ManifestStore};
 use crate::mem_table::WritableKVTable;
 use crate::mem_table_flush::{MemtableFlushMsg, MEMTABLE_FLUSHER_TASK_NAME};
 use crate::oracle::{DbOracle, Oracle};
 use crate::paths::PathResolver;
 use crate::rand::DbRand;
+use crate::rate_limiter::RateLimiter;
 use crate::reader::Reader;
 use crate::sst_iter::SstIteratorOptions;
 use crate::stats::StatRegistry;
 use crate::tablestore::TableStore;
 use crate::transaction_manager::TransactionManager;
+use crate::types::KeyValueWithMetadata;
 use crate::utils::{MonotonicSeq, SendSafely};
 use crate::wal_buffer::{WalBufferManager, WAL_BUFFER_TASK_NAME};
 use crate::wal_replay::{WalReplayIterator, WalReplayOptions};
+use crate::write_stall::{write_stall, WriteStall};
 use log::{info, trace, warn};
 
 pub mod builder;
@@ -77,8 +83,11 @@ pub use builder::DbBuilder;
 
 pub(crate) struct DbInner {
     pub(crate) state: Arc<RwLock<DbState>>,
-    pub(crate) settings: Settings,
+    /// The settings of the database. The mutable subset can be changed with
+    /// [`Db::update_settings`].
+    pub(crate) settings: RwLock<Settings>,
     pub(crate) table_store: Arc<TableStore>,
+    pub(crate) manifest_store: Arc<ManifestStore>,
     pub(crate) memtable_flush_notifier: UnboundedSender<MemtableFlushMsg>,
     pub(crate) write_notifier: UnboundedSender<WriteBatchMessage>,
     pub(crate) db_stats: DbStats,
@@ -98,6 +107,14 @@ pub(crate) struct DbInner {
     pub(crate) wal_enabled: bool,
     /// [`txn_manager`] tracks all the live transactions and related metadata.
     pub(crate) txn_manager: Arc<TransactionManager>,
+    /// The column families the database was opened with.
+    pub(crate) column_families: ColumnFamilies,
+    /// The listeners notified of the flushes and the close of the database.
+    pub(crate) event_listeners: EventListeners,
+    /// Limits the bytes per second of keys and values written by clients.
+    pub(crate) write_rate_limiter: RateLimiter,
+    /// Limits the bytes per second written to L0 by memtable flushes.
+    pub(crate) flush_rate_limiter: RateLimiter,
 }
 
 impl DbInner {
@@ -107,12 +124,15 @@ impl DbInner {
         system_clock: Arc<dyn SystemClock>,
         rand: Arc<DbRand>,
         table_store: Arc<TableStore>,
+        manifest_store: Arc<ManifestStore>,
         manifest: DirtyObject<Manifest>,
         memtable_flush_notifier: UnboundedSender<MemtableFlushMsg>,
         write_notifier: UnboundedSender<WriteBatchMessage>,
         stat_registry: Arc<StatRegistry>,
         fp_registry: Arc<FailPointRegistry>,
         merge_operator: Option<crate::merge_operator::MergeOperatorType>,
+        column_families: ColumnFamilies,
+        event_listeners: EventListeners,
     ) -> Result<Self, SlateDBError> {
         // both last_seq and last_committed_seq will be updated after WAL replay.
         let last_l0_seq = manifest.core().last_l0_seq;
@@ -131,7 +151,10 @@ impl DbInner {
         ));
 
         // state are mostly manifest, including IMM, L0, etc.
-        let state = Arc::new(RwLock::new(DbState::new(manifest)));
+        let state = Arc::new(RwLock::new(DbState::new(
+            manifest,
+            table_store.comparator().clone(),
+        )));
 
         let db_stats = DbStats::new(stat_registry.as_ref());
         let wal_enabled = DbInner::wal_enabled_in_options(&settings);
@@ -142,6 +165,15 @@ impl DbInner {
             mono_clock: mono_clock.clone(),
             oracle: oracle.clone(),
             merge_operator,
+            column_family_merge_operators: column_families.merge_operators(),
+            prefix_extractor: settings.prefix_extractor.clone(),
+            history_retention: RwLock::new(
+                settings
+                    .compactor_options
+                    .as_ref()
+                    .and_then(|options| options.history_retention),
+            ),
+            system_clock: system_clock.clone(),
         };
 
         let recent_flushed_wal_id = state.read().state().core().replay_after_wal_id;
@@ -153,18 +185,34 @@ impl DbInner {
             oracle.clone(),
             table_store.clone(),
             mono_clock.clone(),
+            system_clock.clone(),
+            event_listeners.clone(),
             settings.l0_sst_size_bytes,
             settings.flush_interval,
+            !column_families.is_empty(),
+        ));
+
+        let txn_manager = Arc::new(TransactionManager::new(
+            rand.clone(),
+            table_store.comparator().clone(),
         ));
 
-        let txn_manager = Arc::new(TransactionManager::new(rand.clone()));
+        let write_rate_limiter = RateLimiter::new(
+            settings.rate_limit_options.write_bytes_per_second,
+            system_clock.clone(),
+        );
+        let flush_rate_limiter = RateLimiter::new(
+            settings.rate_limit_options.flush_bytes_per_second,
+            system_clock.clone(),
+        );
 
         let db_inner = Self {
             state,
-            settings,
+            settings: RwLock::new(settings),
             oracle,
             wal_enabled,
             table_store,
+            manifest_store,
             memtable_flush_notifier,
             wal_buffer,
             write_notifier,
@@ -176,6 +224,10 @@ impl DbInner {
             fp_registry,
             reader,
             txn_manager,
+            column_families,
+            event_listeners,
+            write_rate_limiter,
+            flush_rate_limiter,
         };
         Ok(db_inner)
     }
@@ -186,11 +238,76 @@ impl DbInner {
         key: K,
         options: &ReadOptions,
     ) -> Result<Option<Bytes>, SlateDBError> {
+        self.get_cf_with_options(DEFAULT_COLUMN_FAMILY_ID, key, options)
+            .await
+    }
+
+    /// Get the value for a given key of a column family.
+    pub async fn get_cf_with_options<K: AsRef<[u8]>>(
+        &self,
+        column_family: u32,
+        key: K,
+        options: &ReadOptions,
+    ) -> Result<Option<Bytes>, SlateDBError> {
+        self.db_stats.get_requests.inc();
+        let _timer = self
+            .db_stats
+            .get_latency_us
+            .start_timer(self.system_clock.as_ref());
+        self.check_closed()?;
+        let db_state = self.state.read().view();
+        self.reader
+            .get_with_options(column_family, key, options, &db_state, None, None)
+            .await
+    }
+
+    /// Get the value for a given key, along with the metadata of its row.
+    pub async fn get_with_metadata<K: AsRef<[u8]>>(
+        &self,
+        key: K,
+        options: &ReadOptions,
+    ) -> Result<Option<KeyValueWithMetadata>, SlateDBError> {
         self.db_stats.get_requests.inc();
+        let _timer = self
+            .db_stats
+            .get_latency_us
+            .start_timer(self.system_clock.as_ref());
+        self.check_closed()?;
+        let db_state = self.state.read().view();
+        self.reader
+            .get_row_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                key,
+                options,
+                &db_state,
+                None,
+                None,
+            )
+            .await
+    }
+
+    /// Get the values of several keys from a single view of the db.
+    pub async fn get_many_with_options(
+        &self,
+        keys: &[Bytes],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, SlateDBError> {
+        self.db_stats.get_requests.add(keys.len() as u64);
+        let _timer = self
+            .db_stats
+            .get_many_latency_us
+            .start_timer(self.system_clock.as_ref());
         self.check_closed()?;
         let db_state = self.state.read().view();
         self.reader
-            .get_with_options(key, options, &db_state, None, None)
+            .get_many_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                keys,
+                options,
+                &db_state,
+                None,
+                None,
+            )
             .await
     }
 
@@ -198,12 +315,26 @@ impl DbInner {
         &self,
         range: BytesRange,
         options: &ScanOptions,
+    ) -> Result<DbIterator, SlateDBError> {
+        self.scan_cf_with_options(DEFAULT_COLUMN_FAMILY_ID, range, options)
+            .await
+    }
+
+    pub async fn scan_cf_with_options(
+        &self,
+        column_family: u32,
+        range: BytesRange,
+        options: &ScanOptions,
     ) -> Result<DbIterator, SlateDBError> {
         self.db_stats.scan_requests.inc();
+        let _timer = self
+            .db_stats
+            .scan_latency_us
+            .start_timer(self.system_clock.as_ref());
         self.check_closed()?;
         let db_state = self.state.read().view();
         self.reader
-            .scan_with_options(range, options, &db_state, None, None, None)
+            .scan_with_options(column_family, range, options, &db_state, None, None, None)
             .await
     }
 
@@ -257,22 +388,35 @@ impl DbInner {
         batch: WriteBatch,
         options: &WriteOptions,
     ) -> Result<(), SlateDBError> {
+        let _timer = self
+            .db_stats
+            .write_latency_us
+            .start_timer(self.system_clock.as_ref());
         self.check_closed()?;
-        if batch.ops.is_empty() {
+        if batch.is_empty() {
             return Ok(());
         }
+        if !batch.range_deletes.is_empty() && !self.table_store.comparator().is_bytewise() {
+            return Err(SlateDBError::UnsupportedWithComparator("range delete"));
+        }
         // record write batch and number of operations
         self.db_stats.write_batch_count.inc();
-        self.db_stats.write_ops.add(batch.ops.len() as u64);
+        self.db_stats.write_ops.add(batch.num_ops() as u64);
+        let batch_size_bytes = batch.size_bytes() as u64;
 
+        let preconditions = self.check_preconditions(&batch).await?;
         let (tx, rx) = tokio::sync::oneshot::channel();
-        let batch_msg = WriteBatchMessage {
+        let batch_msg = WriteBatchMessage::WriteBatch {
             batch,
             options: options.clone(),
+            preconditions,
             done: tx,
         };
 
-        self.maybe_apply_backpressure().await?;
+        self.maybe_stall_write(options.fail_on_stall).await?;
+        self.maybe_apply_backpressure(options.fail_on_stall).await?;
+        self.maybe_rate_limit_write(batch_size_bytes, options.fail_on_stall)
+            .await?;
         self.write_notifier
             .send_safely(self.state.read().closed_result_reader(), batch_msg)?;
 
@@ -287,29 +431,126 @@ impl DbInner {
         Ok(())
     }
 
-    #[inline]
-    pub(crate) async fn maybe_apply_backpressure(&self) -> Result<(), SlateDBError> {
+    /// Returns the estimated sizes in bytes of the unflushed WAL buffer and of the
+    /// immutable memtables.
+    fn unflushed_bytes(&self) -> Result<(usize, usize), SlateDBError> {
+        let wal_size_bytes = self.wal_buffer.estimated_bytes()?;
+        let imm_memtable_size_bytes = {
+            let guard = self.state.read();
+            // Exclude active memtable to avoid a write lock.
+            guard
+                .state()
+                .imm_memtable
+                .iter()
+                .map(|imm| {
+                    let metadata = imm.table().metadata();
+                    self.table_store
+                        .estimate_encoded_size(metadata.entry_num, metadata.entries_size_in_bytes)
+                })
+                .sum::<usize>()
+        };
+        Ok((wal_size_bytes, imm_memtable_size_bytes))
+    }
+
+    /// Delays or stops the write while L0 or the unflushed bytes are over the limits of
+    /// the write stall options. Fails instead if `fail_on_stall` is set.
+    async fn maybe_stall_write(&self, fail_on_stall: bool) -> Result<(), SlateDBError> {
+        let start = self.system_clock.now();
+        let mut stalled = false;
         loop {
-            let (wal_size_bytes, imm_memtable_size_bytes) = {
-                let wal_size_bytes = self.wal_buffer.estimated_bytes()?;
-                let imm_memtable_size_bytes = {
-                    let guard = self.state.read();
-                    // Exclude active memtable to avoid a write lock.
-                    guard
-                        .state()
-                        .imm_memtable
-                        .iter()
-                        .map(|imm| {
-                            let metadata = imm.table().metadata();
-                            self.table_store.estimate_encoded_size(
-                                metadata.entry_num,
-                                metadata.entries_size_in_bytes,
-                            )
-                        })
-                        .sum::<usize>()
-                };
-                (wal_size_bytes, imm_memtable_size_bytes)
+            let (options, l0_max_ssts, max_unflushed_bytes) = {
+                let settings = self.settings.read();
+                (
+                    settings.write_stall_options.clone(),
+                    settings.l0_max_ssts,
+                    settings.max_unflushed_bytes,
+                )
             };
+            let l0_ssts = self.state.read().state().core().max_l0_len();
+            let unflushed_bytes = if options.unflushed_bytes_slowdown.is_some() {
+                let (wal_size_bytes, imm_memtable_size_bytes) = self.unflushed_bytes()?;
+                wal_size_bytes + imm_memtable_size_bytes
+            } else {
+                0
+            };
+            let stall = write_stall(
+                &options,
+                l0_ssts,
+                l0_max_ssts,
+                unflushed_bytes,
+                max_unflushed_bytes,
+            );
+            if stall != WriteStall::None && fail_on_stall {
+                self.db_stats.write_stall_failures.inc();
+                return Err(SlateDBError::WriteStalled(match stall {
+                    WriteStall::Stop => "too many L0 SSTs",
+                    _ => "writes are slowed down",
+                }));
+            }
+            match stall {
+                WriteStall::None => break,
+                WriteStall::Delay(delay) => {
+                    self.db_stats.write_slowdowns.inc();
+                    self.system_clock.sleep(delay).await;
+                    stalled = true;
+                    break;
+                }
+                WriteStall::Stop => {
+                    if !stalled {
+                        self.db_stats.write_stops.inc();
+                        warn!(
+                            "too many L0 SSTs. stopping writes until compactions catch up [l0_ssts={}, l0_stop_ssts={:?}]",
+                            l0_ssts, options.l0_stop_ssts,
+                        );
+                    }
+                    stalled = true;
+                    self.system_clock.sleep(options.max_write_delay).await;
+                    self.check_closed()?;
+                }
+            }
+        }
+        if stalled {
+            let elapsed = (self.system_clock.now() - start)
+                .to_std()
+                .unwrap_or_default();
+            self.db_stats.write_stall_us.record_duration(elapsed);
+        }
+        Ok(())
+    }
+
+    /// Waits until the write rate limiter admits `size_bytes`. Fails instead if
+    /// `fail_on_stall` is set.
+    async fn maybe_rate_limit_write(
+        &self,
+        size_bytes: u64,
+        fail_on_stall: bool,
+    ) -> Result<(), SlateDBError> {
+        if fail_on_stall {
+            if !self.write_rate_limiter.try_acquire(size_bytes) {
+                self.db_stats.write_stall_failures.inc();
+                return Err(SlateDBError::WriteStalled("write rate limit exceeded"));
+            }
+            return Ok(());
+        }
+        let waited = self.write_rate_limiter.acquire(size_bytes).await;
+        if !waited.is_zero() {
+            self.db_stats
+                .write_rate_limit_wait_us
+                .record_duration(waited);
+        }
+        Ok(())
+    }
+
+    /// Waits for flushes when the unflushed bytes reach `max_unflushed_bytes`. Fails
+    /// instead if `fail_on_stall` is set.
+    #[inline]
+    pub(crate) async fn maybe_apply_backpressure(
+        &self,
+        fail_on_stall: bool,
+    ) -> Result<(), SlateDBError> {
+        loop {
+            let max_unflushed_bytes = self.settings.read().max_unflushed_bytes;
+            let (wal_size_bytes, imm_memtable_size_bytes) = self.unflushed_bytes()?;
             let total_mem_size_bytes = wal_size_bytes + imm_memtable_size_bytes;
 
             trace!(
@@ -317,17 +558,21 @@ impl DbInner {
                 total_mem_size_bytes,
                 wal_size_bytes,
                 imm_memtable_size_bytes,
-                self.settings.max_unflushed_bytes,
+                max_unflushed_bytes,
             );
 
-            if total_mem_size_bytes >= self.settings.max_unflushed_bytes {
+            if total_mem_size_bytes >= max_unflushed_bytes {
+                if fail_on_stall {
+                    self.db_stats.write_stall_failures.inc();
+                    return Err(SlateDBError::WriteStalled("too many unflushed bytes"));
+                }
                 self.db_stats.backpressure_count.inc();
                 warn!(
                     "unflushed memtable size exceeds max_unflushed_bytes. applying backpressure. [total_mem_size_bytes={}, wal_size_bytes={}, imm_memtable_size_bytes={}, max_unflushed_bytes={}]",
                     total_mem_size_bytes,
                     wal_size_bytes,
                     imm_memtable_size_bytes,
-                    self.settings.max_unflushed_bytes,
+                    max_unflushed_bytes,
                 );
 
                 let maybe_oldest_unflushed_memtable = {
@@ -394,6 +639,27 @@ impl DbInner {
         rx.await?
     }
 
+    /// Stops writes, flushes the WAL and the memtables to L0, and writes the handoff marker
+    /// of this writer to the manifest.
+    async fn write_handoff(&self) -> Result<(), SlateDBError> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.write_notifier.send_safely(
+            self.state.read().closed_result_reader(),
+            WriteBatchMessage::StopWrites { done: tx },
+        )?;
+        rx.await?;
+        if self.wal_enabled {
+            self.flush_wals().await?;
+        }
+        self.flush_memtables().await?;
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.memtable_flush_notifier.send_safely(
+            self.state.read().closed_result_reader(),
+            MemtableFlushMsg::WriteHandoff { sender: tx },
+        )?;
+        rx.await?
+    }
+
     pub(crate) async fn flush_memtables(&self) -> Result<(), SlateDBError> {
         {
             let last_flushed_wal_id = self.wal_buffer.recent_flushed_wal_id();
@@ -415,11 +681,12 @@ impl DbInner {
             blocks_to_fetch: 256,
             cache_blocks: false,
             eager_spawn: true,
+            ..SstIteratorOptions::default()
         };
 
         let replay_options = WalReplayOptions {
             sst_batch_size: 4,
-            min_memtable_bytes: self.settings.l0_sst_size_bytes,
+            min_memtable_bytes: self.settings.read().l0_sst_size_bytes,
             max_memtable_bytes: usize::MAX,
             sst_iter_options,
         };
@@ -430,7 +697,7 @@ impl DbInner {
                 .await?;
 
         while let Some(replayed_table) = replay_iter.next().await? {
-            self.maybe_apply_backpressure().await?;
+            self.maybe_apply_backpressure(false).await?;
             self.replay_memtable(replayed_table)?;
         }
 
@@ -451,25 +718,17 @@ impl DbInner {
         path_resolver: &PathResolver,
     ) -> Result<(), SlateDBError> {
         let current_state = self.state.read().state();
-        let max_cache_size = self
-            .settings
-            .object_store_cache_options
-            .max_cache_size_bytes
-            .unwrap_or(usize::MAX);
-
-        match self
-            .settings
-            .object_store_cache_options
-            .preload_disk_cache_on_startup
-        {
+        let cache_options = self.settings.read().object_store_cache_options.clone();
+        let max_cache_size = cache_options.max_cache_size_bytes.unwrap_or(usize::MAX);
+
+        match cache_options.preload_disk_cache_on_startup {
             Some(PreloadLevel::AllSst) => {
                 // Preload both L0 and compacted SSTs
-                let l0_count = current_state.manifest.core().l0.len();
+                let l0_count = current_state.manifest.core().all_l0().count();
                 let compacted_count: usize = current_state
                     .manifest
                     .core()
-                    .compacted
-                    .iter()
+                    .all_compacted()
                     .map(|level| level.ssts.len())
                     .sum();
                 let total_capacity = l0_count + compacted_count;
@@ -482,8 +741,7 @@ impl DbInner {
                     current_state
                         .manifest
                         .core()
-                        .l0
-                        .iter()
+                        .all_l0()
                         .map(|sst_handle| path_resolver.table_path(&sst_handle.id)),
                 );
 
@@ -492,8 +750,7 @@ impl DbInner {
                     current_state
                         .manifest
                         .core()
-                        .compacted
-                        .iter()
+                        .all_compacted()
                         .flat_map(|level| &level.ssts)
                         .map(|sst_handle| path_resolver.table_path(&sst_handle.id)),
                 );
@@ -512,8 +769,7 @@ impl DbInner {
                 let l0_sst_paths: Vec<object_store::path::Path> = current_state
                     .manifest
                     .core()
-                    .l0
-                    .iter()
+                    .all_l0()
                     .map(|sst_handle| path_resolver.table_path(&sst_handle.id))
                     .collect();
 
@@ -559,6 +815,10 @@ impl DbInner {
 pub struct Db {
     pub(crate) inner: Arc<DbInner>,
     task_executor: Arc<MessageHandlerExecutor>,
+    /// The channel of the compactor that runs with the database, if any.
+    compactor_tx: Option<UnboundedSender<CompactorMessage>>,
+    /// The channel of the garbage collector that runs with the database, if any.
+    gc_tx: Option<UnboundedSender<GcMessage>>,
 }
 
 impl Db {
@@ -674,11 +934,64 @@ impl Db {
             warn!("failed to shutdown memtable writer task [error={:?}]", e);
         }
 
-        self.inner.state.write().closed_result().write(Ok(()));
+        let closed_result = self.inner.state.write().closed_result();
+        closed_result.write(Ok(()));
+        if let Some(result) = closed_result.reader().read() {
+            self.inner.event_listeners.notify_closed(&result);
+        }
         info!("db closed");
         Ok(())
     }
 
+    /// Hand off the database to the next writer and close it.
+    ///
+    /// Opening the database in another process fences this writer, and the writes that are
+    /// in flight when it's fenced fail with a fenced error. This method hands off the
+    /// database cooperatively instead: it stops accepting writes, waits for the writes in
+    /// flight to be applied, flushes the WAL and the memtables to L0, writes a handoff
+    /// marker to the manifest, and closes the database. The writes in flight succeed, the
+    /// writes that come after fail with a closed error, and the next writer opens the
+    /// database without any WAL to replay.
+    ///
+    /// A [`DbReader`](crate::DbReader) can wait for the handoff as a warm standby with
+    /// [`DbReader::promote`](crate::DbReader::promote).
+    ///
+    /// The database is closed even if the handoff fails, in which case the next writer
+    /// replays the WAL as it does after [`Db::close`].
+    ///
+    /// ## Returns
+    /// - `Result<(), Error>`: if there was an error handing off the database
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store.clone()).await?;
+    ///     db.put(b"key", b"value").await?;
+    ///     db.transfer_leadership().await?;
+    ///
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     assert_eq!(db.get(b"key").await?, Some("value".into()));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn transfer_leadership(&self) -> Result<(), crate::Error> {
+        self.inner.check_closed()?;
+        let result = self.inner.write_handoff().await;
+        match &result {
+            Ok(()) => info!("db handed off to the next writer"),
+            Err(e) => warn!("failed to hand off db [error={:?}]", e),
+        }
+        self.close().await?;
+        result.map_err(Into::into)
+    }
+
     /// Create a snapshot of the database.
     ///
     /// ## Returns
@@ -723,6 +1036,53 @@ impl Db {
         Ok(snapshot)
     }
 
+    /// Subscribe to the changes committed to the default column family of the database,
+    /// starting at `from_seq`. Use [`Db::subscribe_cf`] to follow a named column family.
+    ///
+    /// The returned [`ChangeStream`] yields every durable change with a sequence number
+    /// of at least `from_seq` in sequence number order, and then waits for new changes.
+    /// The stream polls object storage every `manifest_poll_interval` once it has caught
+    /// up, and ends when the database is closed.
+    ///
+    /// ## Arguments
+    /// - `from_seq`: the sequence number of the first change to return
+    ///
+    /// ## Errors
+    /// - `Error`: if the database is closed.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{ChangeValue, Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.put(b"key", b"value").await?;
+    ///
+    ///     let mut changes = db.subscribe(0).await?;
+    ///     let change = changes.next().await?.expect("db is open");
+    ///     assert_eq!(change.key.as_ref(), b"key");
+    ///     assert_eq!(change.value, ChangeValue::Value(b"value".as_slice().into()));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn subscribe(&self, from_seq: u64) -> Result<ChangeStream, crate::Error> {
+        self.inner.check_closed()?;
+        Ok(ChangeStream::new(
+            DEFAULT_COLUMN_FAMILY_ID,
+            from_seq,
+            Arc::clone(&self.inner.manifest_store),
+            Arc::clone(&self.inner.table_store),
+            Arc::clone(&self.inner.system_clock),
+            self.inner.settings.read().manifest_poll_interval,
+            self.inner.state.read().closed_result_reader(),
+        ))
+    }
+
     /// Get a value from the database with default read options.
     ///
     /// The `Bytes` object returned contains a slice of an entire
@@ -808,6 +1168,153 @@ impl Db {
             .map_err(Into::into)
     }
 
+    /// Get a value from the database with default read options, along with the sequence
+    /// number and timestamps of the row it was read from.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to get
+    ///
+    /// ## Returns
+    /// - `Result<Option<KeyValueWithMetadata>, Error>`:
+    ///   - `Some(KeyValueWithMetadata)`: the value and its metadata if it exists
+    ///   - `None`: if the value does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error};
+    /// use slatedb::config::{PutOptions, Ttl, WriteOptions};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.put_with_options(
+    ///         b"key",
+    ///         b"value",
+    ///         &PutOptions { ttl: Ttl::ExpireAfter(60_000) },
+    ///         &WriteOptions::default(),
+    ///     )
+    ///     .await?;
+    ///     let row = db.get_with_metadata(b"key").await?.expect("key exists");
+    ///     assert_eq!(row.value, "value");
+    ///     assert_eq!(row.expire_ts, row.create_ts.map(|ts| ts + 60_000));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_with_metadata<K: AsRef<[u8]> + Send>(
+        &self,
+        key: K,
+    ) -> Result<Option<KeyValueWithMetadata>, crate::Error> {
+        self.get_with_metadata_with_options(key, &ReadOptions::default())
+            .await
+    }
+
+    /// Get a value from the database with custom read options, along with the sequence
+    /// number and timestamps of the row it was read from.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to get
+    /// - `options`: the read options to use
+    ///
+    /// ## Returns
+    /// - `Result<Option<KeyValueWithMetadata>, Error>`:
+    ///   - `Some(KeyValueWithMetadata)`: the value and its metadata if it exists
+    ///   - `None`: if the value does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    pub async fn get_with_metadata_with_options<K: AsRef<[u8]> + Send>(
+        &self,
+        key: K,
+        options: &ReadOptions,
+    ) -> Result<Option<KeyValueWithMetadata>, crate::Error> {
+        self.inner
+            .get_with_metadata(key, options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Get the values of several keys from the database with default read options.
+    ///
+    /// All the keys are read from the same view of the database, so the result is
+    /// consistent even if other writes happen in the meantime. The filter and index of
+    /// each SST are read once for all the keys it may hold, and the blocks of different
+    /// SSTs are fetched concurrently, which makes this cheaper than calling [`Db::get`]
+    /// for each key.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get. They may be in any order and contain duplicates.
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, Error>`: the value of each key, in the order of
+    ///   `keys`, or `None` if the key does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the values
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.put(b"key1", b"value1").await?;
+    ///     db.put(b"key2", b"value2").await?;
+    ///     assert_eq!(
+    ///         db.get_many(&[b"key2", b"key3", b"key1"]).await?,
+    ///         vec![Some("value2".into()), None, Some("value1".into())]
+    ///     );
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn get_many<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, &ReadOptions::default())
+            .await
+    }
+
+    /// Get the values of several keys from the database with custom read options.
+    ///
+    /// See [`Db::get_many`].
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get. They may be in any order and contain duplicates.
+    /// - `options`: the read options to use
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, Error>`: the value of each key, in the order of
+    ///   `keys`, or `None` if the key does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the values
+    pub async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        let keys: Vec<Bytes> = keys
+            .iter()
+            .map(|key| Bytes::copy_from_slice(key.as_ref()))
+            .collect();
+        self.inner
+            .get_many_with_options(&keys, options)
+            .await
+            .map_err(Into::into)
+    }
+
     /// Scan a range of keys using the default scan options.
     ///
     /// returns a `DbIterator`
@@ -891,9 +1398,9 @@ impl Db {
         let end = range
             .end_bound()
             .map(|b| Bytes::copy_from_slice(b.as_ref()));
-        let range = (start, end);
+        let range = BytesRange::new_by(start, end, self.inner.table_store.comparator());
         self.inner
-            .scan_with_options(BytesRange::from(range), options)
+            .scan_with_options(range, options)
             .await
             .map_err(Into::into)
     }
@@ -974,18 +1481,25 @@ impl Db {
         self.write_with_options(batch, write_opts).await
     }
 
-    /// Delete a key from the database with default `WriteOptions`.
+    /// Write a value into the database only if the key does not currently exist.
+    ///
+    /// The check and the write are applied atomically by the writer, so this is
+    /// cheaper than opening a transaction for the same purpose. Use a [`WriteBatch`]
+    /// with [`WriteBatch::add_precondition`] to combine preconditions with custom
+    /// options or other writes.
     ///
     /// ## Arguments
-    /// - `key`: the key to delete
+    /// - `key`: the key to write
+    /// - `value`: the value to write
     ///
     /// ## Errors
-    /// - `Error`: if there was an error deleting the key.
+    /// - `Error` with kind [`crate::ErrorKind::Precondition`]: if the key already exists.
+    /// - `Error`: if there was an error writing the value.
     ///
     /// ## Examples
     ///
     /// ```
-    /// use slatedb::{Db, Error};
+    /// use slatedb::{Db, Error, ErrorKind};
     /// use slatedb::object_store::{ObjectStore, memory::InMemory};
     /// use std::sync::Arc;
     ///
@@ -993,17 +1507,105 @@ impl Db {
     /// async fn main() -> Result<(), Error> {
     ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
     ///     let db = Db::open("test_db", object_store).await?;
-    ///     db.delete(b"key").await?;
+    ///     db.put_if_absent(b"key", b"value").await?;
+    ///     let err = db.put_if_absent(b"key", b"other").await.unwrap_err();
+    ///     assert_eq!(err.kind(), ErrorKind::Precondition);
     ///     Ok(())
     /// }
     /// ```
-    pub async fn delete<K: AsRef<[u8]>>(&self, key: K) -> Result<(), crate::Error> {
+    pub async fn put_if_absent<K, V>(&self, key: K, value: V) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
         let mut batch = WriteBatch::new();
-        batch.delete(key.as_ref());
+        batch.add_precondition(key.as_ref(), Precondition::Absent);
+        batch.put(key, value);
         self.write(batch).await
     }
 
-    /// Delete a key from the database with custom `WriteOptions`.
+    /// Write a value into the database only if the key's current value equals
+    /// `expected`.
+    ///
+    /// The check and the write are applied atomically by the writer, so this is
+    /// cheaper than opening a transaction for the same purpose.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to write
+    /// - `expected`: the value the key must currently have
+    /// - `new`: the value to write
+    ///
+    /// ## Errors
+    /// - `Error` with kind [`crate::ErrorKind::Precondition`]: if the key does not exist
+    ///   or its value differs from `expected`.
+    /// - `Error`: if there was an error writing the value.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.put(b"key", b"v1").await?;
+    ///     db.compare_and_set(b"key", b"v1", b"v2").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn compare_and_set<K, E, V>(
+        &self,
+        key: K,
+        expected: E,
+        new: V,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        E: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut batch = WriteBatch::new();
+        batch.add_precondition(
+            key.as_ref(),
+            Precondition::ValueEquals(Bytes::copy_from_slice(expected.as_ref())),
+        );
+        batch.put(key, new);
+        self.write(batch).await
+    }
+
+    /// Delete a key from the database with default `WriteOptions`.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to delete
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error deleting the key.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.delete(b"key").await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete<K: AsRef<[u8]>>(&self, key: K) -> Result<(), crate::Error> {
+        let mut batch = WriteBatch::new();
+        batch.delete(key.as_ref());
+        self.write(batch).await
+    }
+
+    /// Delete a key from the database with custom `WriteOptions`.
     ///
     /// ## Arguments
     /// - `key`: the key to delete
@@ -1037,6 +1639,86 @@ impl Db {
         self.write_with_options(batch, options).await
     }
 
+    /// Delete every key in a range from the database with default `WriteOptions`.
+    ///
+    /// The deletion is stored as a single range tombstone, so its cost does not depend
+    /// on the number of keys in the range.
+    ///
+    /// ## Arguments
+    /// - `range`: the range of keys to delete
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error deleting the range.
+    ///
+    /// ## Panics
+    /// - if a bound of the range is an empty key or the range is empty
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.delete_range(b"a".as_slice()..b"c".as_slice()).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_range<K, T>(&self, range: T) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        T: RangeBounds<K>,
+    {
+        let mut batch = WriteBatch::new();
+        batch.delete_range(range);
+        self.write(batch).await
+    }
+
+    /// Delete every key in a range from the database with custom `WriteOptions`.
+    ///
+    /// ## Arguments
+    /// - `range`: the range of keys to delete
+    /// - `options`: the write options to use
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error deleting the range.
+    ///
+    /// ## Panics
+    /// - if a bound of the range is an empty key or the range is empty
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, config::WriteOptions, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.delete_range_with_options(b"a".as_slice().., &WriteOptions::default()).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn delete_range_with_options<K, T>(
+        &self,
+        range: T,
+        options: &WriteOptions,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        T: RangeBounds<K>,
+    {
+        let mut batch = WriteBatch::new();
+        batch.delete_range(range);
+        self.write_with_options(batch, options).await
+    }
+
     /// Merge a value into the database with default `MergeOptions` and `WriteOptions`.
     ///
     /// Merge operations allow applications to bypass the traditional read/modify/write cycle
@@ -1154,138 +1836,399 @@ impl Db {
         self.write_with_options(batch, write_opts).await
     }
 
-    /// Write a batch of put/delete operations atomically to the database. Batch writes
-    /// block other gets and writes until the batch is written to the WAL (or memtable if
-    /// WAL is disabled).
+    /// Returns the handle of a column family the database was opened with.
     ///
     /// ## Arguments
-    /// - `batch`: the batch of put/delete operations to write
+    /// - `name`: the name of the column family
     ///
     /// ## Errors
-    /// - `Error`: if there was an error writing the batch.
+    /// - `Error`: if the database was not opened with the column family (see
+    ///   [`DbBuilder::with_column_family`])
     ///
     /// ## Examples
     ///
     /// ```
-    /// use slatedb::{WriteBatch, Db, Error};
+    /// use slatedb::{ColumnFamilyOptions, Db, Error};
     /// use slatedb::object_store::{ObjectStore, memory::InMemory};
     /// use std::sync::Arc;
     ///
     /// #[tokio::main]
     /// async fn main() -> Result<(), Error> {
     ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-    ///     let db = Db::open("test_db", object_store).await?;
-    ///
-    ///     let mut batch = WriteBatch::new();
-    ///     batch.put(b"key1", b"value1");
-    ///     batch.put(b"key2", b"value2");
-    ///     batch.delete(b"key1");
-    ///     db.write(batch).await?;
-    ///
+    ///     let db = Db::builder("test_db", object_store)
+    ///         .with_column_family("users", ColumnFamilyOptions::default())
+    ///         .build()
+    ///         .await?;
+    ///     assert_eq!(db.column_family("users")?.name(), "users");
+    ///     assert!(db.column_family("orders").is_err());
     ///     Ok(())
     /// }
     /// ```
-    pub async fn write(&self, batch: WriteBatch) -> Result<(), crate::Error> {
-        self.write_with_options(batch, &WriteOptions::default())
-            .await
+    pub fn column_family(&self, name: &str) -> Result<ColumnFamily, crate::Error> {
+        self.inner.column_families.handle(name).map_err(Into::into)
     }
 
-    /// Write a batch of put/delete operations atomically to the database. Batch writes
-    /// block other gets and writes until the batch is written to the WAL (or memtable if
-    /// WAL is disabled).
+    /// Subscribe to the changes committed to a column family, starting at `from_seq`.
+    /// See [`Db::subscribe`] for how the returned stream behaves.
     ///
     /// ## Arguments
-    /// - `batch`: the batch of put/delete operations to write
-    /// - `options`: the write options to use
+    /// - `column_family`: the column family to follow
+    /// - `from_seq`: the sequence number of the first change to return
     ///
     /// ## Errors
-    /// - `Error`: if there was an error writing the batch.
+    /// - `Error`: if the database is closed.
+    pub async fn subscribe_cf(
+        &self,
+        column_family: &ColumnFamily,
+        from_seq: u64,
+    ) -> Result<ChangeStream, crate::Error> {
+        self.inner.check_closed()?;
+        Ok(ChangeStream::new(
+            column_family.id,
+            from_seq,
+            Arc::clone(&self.inner.manifest_store),
+            Arc::clone(&self.inner.table_store),
+            Arc::clone(&self.inner.system_clock),
+            self.inner.settings.read().manifest_poll_interval,
+            self.inner.state.read().closed_result_reader(),
+        ))
+    }
+
+    /// Get a value from a column family with the default read options.
     ///
-    /// ## Examples
+    /// ## Arguments
+    /// - `column_family`: the column family to read from
+    /// - `key`: the key to get
     ///
-    /// ```
-    /// use slatedb::{WriteBatch, Db, config::WriteOptions, Error};
-    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
-    /// use std::sync::Arc;
+    /// ## Returns
+    /// - `Result<Option<Bytes>, Error>`:
+    ///   - `Some(Bytes)`: the value if it exists
+    ///   - `None`: if the value does not exist
     ///
-    /// #[tokio::main]
-    /// async fn main() -> Result<(), Error> {
-    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-    ///     let db = Db::open("test_db", object_store).await?;
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    pub async fn get_cf<K: AsRef<[u8]> + Send>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+    ) -> Result<Option<Bytes>, crate::Error> {
+        self.get_cf_with_options(column_family, key, &ReadOptions::default())
+            .await
+    }
+
+    /// Get a value from a column family with custom read options.
     ///
-    ///     let mut batch = WriteBatch::new();
-    ///     batch.put(b"key1", b"value1");
-    ///     batch.put(b"key2", b"value2");
-    ///     batch.delete(b"key1");
-    ///     db.write_with_options(batch, &WriteOptions::default()).await?;
+    /// ## Arguments
+    /// - `column_family`: the column family to read from
+    /// - `key`: the key to get
+    /// - `options`: the read options to use
     ///
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn write_with_options(
+    /// ## Returns
+    /// - `Result<Option<Bytes>, Error>`:
+    ///   - `Some(Bytes)`: the value if it exists
+    ///   - `None`: if the value does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    pub async fn get_cf_with_options<K: AsRef<[u8]> + Send>(
         &self,
-        batch: WriteBatch,
-        options: &WriteOptions,
-    ) -> Result<(), crate::Error> {
+        column_family: &ColumnFamily,
+        key: K,
+        options: &ReadOptions,
+    ) -> Result<Option<Bytes>, crate::Error> {
         self.inner
-            .write_with_options(batch, options)
+            .get_cf_with_options(column_family.id, key, options)
             .await
             .map_err(Into::into)
     }
 
-    /// Flush in-memory writes to disk. This function blocks until the in-memory
-    /// data has been durably written to object storage.
-    ///
-    /// If WAL is enabled, this method is equivalent to:
-    /// `flush_with_options(FlushOptions { flush_type: FlushType::Wal })`
+    /// Scan a range of keys of a column family with the default scan options.
     ///
-    /// If WAL is disabled, this method is equivalent to:
-    /// `flush_with_options(FlushOptions { flush_type: FlushType::Memtable })`.
+    /// ## Arguments
+    /// - `column_family`: the column family to scan
+    /// - `range`: the range of keys to scan
     ///
     /// ## Errors
-    /// - `Error`: if there was an error flushing the database
-    ///
-    /// ## Examples
-    ///
-    /// ```
-    /// use slatedb::{Db, Error};
-    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
-    /// use std::sync::Arc;
-    ///
-    /// #[tokio::main]
-    /// async fn main() -> Result<(), Error> {
-    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-    ///     let db = Db::open("test_db", object_store).await?;
-    ///     db.flush().await?;
-    ///     Ok(())
-    /// }
-    /// ```
-    pub async fn flush(&self) -> Result<(), crate::Error> {
-        if self.inner.wal_enabled {
-            self.flush_with_options(FlushOptions {
-                flush_type: FlushType::Wal,
-            })
-            .await
-        } else {
-            self.flush_with_options(FlushOptions {
-                flush_type: FlushType::MemTable,
-            })
+    /// - `Error`: if there was an error scanning the range of keys
+    pub async fn scan_cf<K, T>(
+        &self,
+        column_family: &ColumnFamily,
+        range: T,
+    ) -> Result<DbIterator, crate::Error>
+    where
+        K: AsRef<[u8]> + Send,
+        T: RangeBounds<K> + Send,
+    {
+        self.scan_cf_with_options(column_family, range, &ScanOptions::default())
             .await
-        }
     }
 
-    /// Flush in-memory writes to disk with custom options.
-    ///
-    /// An error will be returned if `options.flush_type` is `FlushType::Wal` and the WAL
-    /// is disabled.
-    ///
-    /// `FlushType::Memtable` is allowed even if WAL is enabled.
+    /// Scan a range of keys of a column family with the provided options.
     ///
     /// ## Arguments
-    /// - `options`: the flush options
+    /// - `column_family`: the column family to scan
+    /// - `range`: the range of keys to scan
+    /// - `options`: the scan options to use
     ///
-    /// ## Returns
-    /// - `Result<(), crate::Error>`: the result of the flush operation.
+    /// ## Errors
+    /// - `Error`: if there was an error scanning the range of keys
+    pub async fn scan_cf_with_options<K, T>(
+        &self,
+        column_family: &ColumnFamily,
+        range: T,
+        options: &ScanOptions,
+    ) -> Result<DbIterator, crate::Error>
+    where
+        K: AsRef<[u8]> + Send,
+        T: RangeBounds<K> + Send,
+    {
+        let start = range
+            .start_bound()
+            .map(|b| Bytes::copy_from_slice(b.as_ref()));
+        let end = range
+            .end_bound()
+            .map(|b| Bytes::copy_from_slice(b.as_ref()));
+        let range = BytesRange::new_by(start, end, self.inner.table_store.comparator());
+        self.inner
+            .scan_cf_with_options(column_family.id, range, options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Write a value into a column family with default `WriteOptions`. The
+    /// column family's default TTL applies.
+    ///
+    /// ## Arguments
+    /// - `column_family`: the column family to write to
+    /// - `key`: the key to write
+    /// - `value`: the value to write
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error writing the value.
+    pub async fn put_cf<K, V>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+        value: V,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.put_cf_with_options(
+            column_family,
+            key,
+            value,
+            &PutOptions::default(),
+            &WriteOptions::default(),
+        )
+        .await
+    }
+
+    /// Write a value into a column family with custom `PutOptions` and `WriteOptions`.
+    ///
+    /// ## Arguments
+    /// - `column_family`: the column family to write to
+    /// - `key`: the key to write
+    /// - `value`: the value to write
+    /// - `put_opts`: the put options to use
+    /// - `write_opts`: the write options to use
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error writing the value.
+    pub async fn put_cf_with_options<K, V>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+        value: V,
+        put_opts: &PutOptions,
+        write_opts: &WriteOptions,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut batch = WriteBatch::new();
+        batch.put_cf_with_options(column_family, key, value, put_opts);
+        self.write_with_options(batch, write_opts).await
+    }
+
+    /// Delete a key from a column family with default `WriteOptions`.
+    ///
+    /// ## Arguments
+    /// - `column_family`: the column family to delete from
+    /// - `key`: the key to delete
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error deleting the key.
+    pub async fn delete_cf<K: AsRef<[u8]>>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+    ) -> Result<(), crate::Error> {
+        let mut batch = WriteBatch::new();
+        batch.delete_cf(column_family, key);
+        self.write(batch).await
+    }
+
+    /// Merge a value into a column family with default `MergeOptions` and
+    /// `WriteOptions`. The column family's merge operator is used to combine the
+    /// operands.
+    ///
+    /// ## Arguments
+    /// - `column_family`: the column family to merge into
+    /// - `key`: the key to merge into
+    /// - `value`: the merge operand
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error merging the value.
+    pub async fn merge_cf<K, V>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+        value: V,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let mut batch = WriteBatch::new();
+        batch.merge_cf(column_family, key, value);
+        self.write(batch).await
+    }
+
+    /// Write a batch of put/delete operations atomically to the database. Batch writes
+    /// block other gets and writes until the batch is written to the WAL (or memtable if
+    /// WAL is disabled).
+    ///
+    /// ## Arguments
+    /// - `batch`: the batch of put/delete operations to write
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error writing the batch.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{WriteBatch, Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///
+    ///     let mut batch = WriteBatch::new();
+    ///     batch.put(b"key1", b"value1");
+    ///     batch.put(b"key2", b"value2");
+    ///     batch.delete(b"key1");
+    ///     db.write(batch).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn write(&self, batch: WriteBatch) -> Result<(), crate::Error> {
+        self.write_with_options(batch, &WriteOptions::default())
+            .await
+    }
+
+    /// Write a batch of put/delete operations atomically to the database. Batch writes
+    /// block other gets and writes until the batch is written to the WAL (or memtable if
+    /// WAL is disabled).
+    ///
+    /// ## Arguments
+    /// - `batch`: the batch of put/delete operations to write
+    /// - `options`: the write options to use
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error writing the batch.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{WriteBatch, Db, config::WriteOptions, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///
+    ///     let mut batch = WriteBatch::new();
+    ///     batch.put(b"key1", b"value1");
+    ///     batch.put(b"key2", b"value2");
+    ///     batch.delete(b"key1");
+    ///     db.write_with_options(batch, &WriteOptions::default()).await?;
+    ///
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn write_with_options(
+        &self,
+        batch: WriteBatch,
+        options: &WriteOptions,
+    ) -> Result<(), crate::Error> {
+        self.inner
+            .write_with_options(batch, options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Flush in-memory writes to disk. This function blocks until the in-memory
+    /// data has been durably written to object storage.
+    ///
+    /// If WAL is enabled, this method is equivalent to:
+    /// `flush_with_options(FlushOptions { flush_type: FlushType::Wal })`
+    ///
+    /// If WAL is disabled, this method is equivalent to:
+    /// `flush_with_options(FlushOptions { flush_type: FlushType::Memtable })`.
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error flushing the database
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.flush().await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn flush(&self) -> Result<(), crate::Error> {
+        if self.inner.wal_enabled {
+            self.flush_with_options(FlushOptions {
+                flush_type: FlushType::Wal,
+            })
+            .await
+        } else {
+            self.flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
+            .await
+        }
+    }
+
+    /// Flush in-memory writes to disk with custom options.
+    ///
+    /// An error will be returned if `options.flush_type` is `FlushType::Wal` and the WAL
+    /// is disabled.
+    ///
+    /// `FlushType::Memtable` is allowed even if WAL is enabled.
+    ///
+    /// ## Arguments
+    /// - `options`: the flush options
+    ///
+    /// ## Returns
+    /// - `Result<(), crate::Error>`: the result of the flush operation.
     ///
     /// ## Errors
     /// - `Error`: if there was an error flushing the database
@@ -1323,28 +2266,240 @@ impl Db {
         .map_err(Into::into)
     }
 
+    /// Ingest SSTs built with [`crate::SstWriter`] into the database.
+    ///
+    /// The SSTs are read from the database's main object store, rewritten into the
+    /// database, and added to L0 in a single manifest update, without going through the
+    /// WAL or the memtable. Each SST gets a sequence number newer than all writes made
+    /// before the call, so its rows take precedence over them; when several SSTs contain
+    /// the same key, the SST that comes last in `paths` wins. The rewritten SSTs are
+    /// compacted like any other L0 SST, so ingesting many of them at once can temporarily
+    /// hold back memtable flushes until the compactor catches up.
+    ///
+    /// ## Arguments
+    /// - `paths`: the paths of the SSTs in the main object store
+    ///
+    /// ## Errors
+    /// - `Error`: with [`crate::ErrorKind::Invalid`] if an SST does not exist or its keys
+    ///   are not sorted.
+    /// - `Error`: with [`crate::ErrorKind::Precondition`] if an SST overlaps a write made
+    ///   while the SSTs were being ingested. None of the SSTs are added to the database
+    ///   and the ingestion can be retried.
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, Error, SstWriter};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory, path::Path};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let path = Path::from("ingest/000001.sst");
+    ///     let mut writer = SstWriter::new(path.clone(), object_store.clone());
+    ///     writer.put(b"key", b"value").await?;
+    ///     writer.close().await?;
+    ///
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.ingest_ssts(&[path]).await?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn ingest_ssts(&self, paths: &[Path]) -> Result<(), crate::Error> {
+        self.inner.ingest_ssts(paths).await.map_err(Into::into)
+    }
+
     /// Get the metrics registry for the database.
     pub fn metrics(&self) -> Arc<StatRegistry> {
         self.inner.stat_registry.clone()
     }
 
-    /// Begin a new transaction with the specified isolation level.
+    /// Changes settings of the open database, without reopening it. Only the settings of
+    /// a [`SettingsPatch`] can be changed.
+    ///
+    /// The new size limits apply to the next writes. The background tasks pick up the new
+    /// intervals and compactor options after the messages they are processing, and
+    /// restart their intervals with the new values.
     ///
     /// ## Arguments
-    /// - `isolation_level`: the isolation level for the transaction
+    /// - `patch`: the settings to change
     ///
     /// ## Returns
-    /// - `Result<DBTransaction, crate::Error>`: the transaction handle
+    /// - `Ok(())`: if the settings were changed
+    /// - `Err(Error)`: if the patch changes a setting that can't be changed while the
+    ///   database is open, like the compactor's `manifest_update_timeout`, if it sets an
+    ///   interval to zero, or if it changes the options of a compactor or a garbage
+    ///   collector that doesn't run with the database. No setting is changed then.
     ///
     /// ## Examples
     ///
-    /// ```rust
-    /// use slatedb::{Db, IsolationLevel};
+    /// ```
+    /// use slatedb::{Db, Error, SettingsPatch};
     /// use slatedb::object_store::memory::InMemory;
     /// use std::sync::Arc;
+    /// use std::time::Duration;
     ///
     /// #[tokio::main]
-    /// async fn main() -> Result<(), slatedb::Error> {
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store).await?;
+    ///     db.update_settings(SettingsPatch {
+    ///         flush_interval: Some(Some(Duration::from_millis(50))),
+    ///         max_unflushed_bytes: Some(256 * 1024 * 1024),
+    ///         ..Default::default()
+    ///     })?;
+    ///     Ok(())
+    /// }
+    /// ```
+    pub fn update_settings(&self, patch: SettingsPatch) -> Result<(), crate::Error> {
+        self.inner.check_closed()?;
+        // read before locking the settings, which are read while the state is locked
+        let closed_result_reader = self.inner.state.read().closed_result_reader();
+        let mut settings = self.inner.settings.write();
+        self.validate_settings_patch(&settings, &patch)?;
+        if let Some(flush_interval) = patch.flush_interval {
+            self.inner
+                .wal_buffer
+                .set_max_flush_interval(flush_interval)?;
+            settings.flush_interval = flush_interval;
+        }
+        if let Some(l0_sst_size_bytes) = patch.l0_sst_size_bytes {
+            self.inner
+                .wal_buffer
+                .set_max_wal_bytes_size(l0_sst_size_bytes);
+            settings.l0_sst_size_bytes = l0_sst_size_bytes;
+        }
+        if let Some(max_unflushed_bytes) = patch.max_unflushed_bytes {
+            settings.max_unflushed_bytes = max_unflushed_bytes;
+        }
+        if let Some(options) = patch.write_stall_options {
+            settings.write_stall_options = options;
+        }
+        if let Some(options) = patch.rate_limit_options {
+            self.inner
+                .write_rate_limiter
+                .set_bytes_per_second(options.write_bytes_per_second);
+            self.inner
+                .flush_rate_limiter
+                .set_bytes_per_second(options.flush_bytes_per_second);
+            settings.rate_limit_options = options;
+        }
+        if let (Some(options), Some(tx)) = (patch.compactor_options, &self.compactor_tx) {
+            tx.send_safely(
+                closed_result_reader.clone(),
+                CompactorMessage::UpdateOptions(Arc::new(options.clone())),
+            )?;
+            *self.inner.reader.history_retention.write() = options.history_retention;
+            settings.compactor_options = Some(options);
+        }
+        if let (Some(options), Some(tx)) = (patch.garbage_collector_options, &self.gc_tx) {
+            tx.send_safely(
+                closed_result_reader,
+                GcMessage::UpdateOptions(options.clone()),
+            )?;
+            settings.garbage_collector_options = Some(options);
+        }
+        info!("updated settings [settings={:?}]", *settings);
+        Ok(())
+    }
+
+    /// Returns an error if `patch` changes a setting that can't be changed while the
+    /// database is open, or sets one to an invalid value.
+    fn validate_settings_patch(
+        &self,
+        settings: &Settings,
+        patch: &SettingsPatch,
+    ) -> Result<(), SlateDBError> {
+        if let Some(flush_interval) = patch.flush_interval {
+            if !self.inner.wal_enabled {
+                return Err(SlateDBError::InvalidSetting {
+                    setting: "flush_interval",
+                    reason: "the WAL is disabled",
+                });
+            }
+            if flush_interval.is_some_and(|interval| interval.is_zero()) {
+                return Err(SlateDBError::InvalidSetting {
+                    setting: "flush_interval",
+                    reason: "the interval must be positive",
+                });
+            }
+        }
+        if let Some(options) = &patch.write_stall_options {
+            options.validate()?;
+        }
+        if let Some(options) = &patch.rate_limit_options {
+            options.validate()?;
+        }
+        if let Some(options) = &patch.compactor_options {
+            if self.compactor_tx.is_none() {
+                return Err(SlateDBError::InvalidSetting {
+                    setting: "compactor_options",
+                    reason: "no compactor runs with the database",
+                });
+            }
+            let current = settings.compactor_options.clone().unwrap_or_default();
+            // the fenceable manifest and the scheduler are created with these options
+            if options.manifest_update_timeout != current.manifest_update_timeout {
+                return Err(SlateDBError::ImmutableSetting(
+                    "compactor_options.manifest_update_timeout",
+                ));
+            }
+            if options.max_concurrent_compactions != current.max_concurrent_compactions {
+                return Err(SlateDBError::ImmutableSetting(
+                    "compactor_options.max_concurrent_compactions",
+                ));
+            }
+            if options.poll_interval.is_zero() {
+                return Err(SlateDBError::InvalidSetting {
+                    setting: "compactor_options.poll_interval",
+                    reason: "the interval must be positive",
+                });
+            }
+            options.validate()?;
+        }
+        if let Some(options) = &patch.garbage_collector_options {
+            if self.gc_tx.is_none() {
+                return Err(SlateDBError::InvalidSetting {
+                    setting: "garbage_collector_options",
+                    reason: "no garbage collector runs with the database",
+                });
+            }
+            let has_zero_interval = [
+                options.manifest_options,
+                options.wal_options,
+                options.compacted_options,
+            ]
+            .iter()
+            .flatten()
+            .any(|o| o.interval.is_some_and(|interval| interval.is_zero()));
+            if has_zero_interval {
+                return Err(SlateDBError::InvalidSetting {
+                    setting: "garbage_collector_options",
+                    reason: "the intervals must be positive",
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// Begin a new transaction with the specified isolation level.
+    ///
+    /// ## Arguments
+    /// - `isolation_level`: the isolation level for the transaction
+    ///
+    /// ## Returns
+    /// - `Result<DBTransaction, crate::Error>`: the transaction handle
+    ///
+    /// ## Examples
+    ///
+    /// ```rust
+    /// use slatedb::{Db, IsolationLevel};
+    /// use slatedb::object_store::memory::InMemory;
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), slatedb::Error> {
     ///     let object_store = Arc::new(InMemory::new());
     ///     let db = Db::open("test_db", object_store).await?;
     ///     let txn = db.begin(IsolationLevel::SerializableSnapshot).await?;
@@ -1393,6 +2548,14 @@ impl DbRead for Db {
         self.get_with_options(key, options).await
     }
 
+    async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, options).await
+    }
+
     async fn scan_with_options<K, T>(
         &self,
         range: T,
@@ -1419,6 +2582,7 @@ mod tests {
     use std::time::Duration;
 
     use super::*;
+    use crate::blob_file::BlobFileRef;
     use crate::cached_object_store::stats::{
         OBJECT_STORE_CACHE_PART_ACCESS, OBJECT_STORE_CACHE_PART_HITS,
     };
@@ -1427,17 +2591,27 @@ mod tests {
     use crate::clock::DefaultSystemClock;
     #[cfg(feature = "test-util")]
     use crate::clock::MockSystemClock;
+    use crate::column_family::ColumnFamilyOptions;
+    use crate::comparator::tests::ReverseComparator;
     use crate::config::DurabilityLevel::{Memory, Remote};
     use crate::config::{
-        CompactorOptions, DurabilityLevel, GarbageCollectorDirectoryOptions,
-        GarbageCollectorOptions, ObjectStoreCacheOptions, Settings,
-        SizeTieredCompactionSchedulerOptions, Ttl,
+        CompactorOptions, DbReaderOptions, DurabilityLevel, GarbageCollectorDirectoryOptions,
+        GarbageCollectorOptions, ObjectStoreCacheOptions, RateLimitOptions, Settings,
+        SettingsPatch, SizeTieredCompactionSchedulerOptions, Ttl, WriteStallOptions,
     };
     use crate::db::builder::GarbageCollectorBuilder;
     use crate::db_state::CoreDbState;
-    use crate::db_stats::IMMUTABLE_MEMTABLE_FLUSHES;
-    use crate::iter::KeyValueIterator;
+    use crate::db_stats::{
+        GET_LATENCY_US, GET_MANY_LATENCY_US, IMMUTABLE_MEMTABLE_FLUSHES, MEMTABLE_FLUSH_LATENCY_US,
+        SCAN_LATENCY_US, SST_PREFIX_FILTER_NEGATIVES, SST_PREFIX_FILTER_POSITIVES,
+        WAL_FLUSH_LATENCY_US, WRITE_LATENCY_US, WRITE_STALL_FAILURES,
+    };
+    use crate::encryption::tests::XorEncryptionProvider;
+    use crate::event_listener::tests::RecordingEventListener;
+    use crate::event_listener::{DbClosedEvent, ManifestWrittenEvent};
+    use crate::iter::{IterationOrder, KeyValueIterator};
     use crate::manifest::store::{ManifestStore, StoredManifest};
+    use crate::manifest::WriterHandoff;
     use crate::object_stores::ObjectStores;
     use crate::proptest_util::arbitrary;
     use crate::proptest_util::sample;
@@ -1449,7 +2623,10 @@ mod tests {
     use crate::sst_iter::{SstIterator, SstIteratorOptions};
     use crate::test_utils::{assert_iterator, OnDemandCompactionSchedulerSupplier, TestClock};
     use crate::types::RowEntry;
-    use crate::{proptest_util, test_utils, CloseReason, KeyValue};
+    use crate::{
+        proptest_util, test_utils, CloseReason, DbReader, FixedLengthPrefixExtractor, KeyValue,
+        SstWriter,
+    };
     use futures::{future, future::join_all, StreamExt};
     use object_store::memory::InMemory;
     use object_store::ObjectStore;
@@ -1480,406 +2657,557 @@ mod tests {
         kv_store.close().await.unwrap();
     }
 
-    #[test]
-    fn test_get_after_put() {
-        let mut runner = new_proptest_runner(None);
-        let runtime = Runtime::new().unwrap();
-
-        let table = sample::table(runner.rng(), 1000, 10);
-        let db_options = test_db_options(0, 1024, None);
-        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
-
-        runner
-            .run(
-                &(arbitrary::bytes(100), arbitrary::bytes(100)),
-                |(key, value)| {
-                    runtime.block_on(async {
-                        if !key.is_empty() {
-                            db.put_with_options(
-                                &key,
-                                &value,
-                                &PutOptions::default(),
-                                &WriteOptions {
-                                    await_durable: false,
-                                },
-                            )
-                            .await
-                            .unwrap();
-                            assert_eq!(
-                                Some(value),
-                                db.get_with_options(
-                                    &key,
-                                    &ReadOptions {
-                                        durability_filter: Memory,
-                                        dirty: false,
-                                    }
-                                )
-                                .await
-                                .unwrap()
-                            );
-                        }
-                    });
-                    Ok(())
-                },
-            )
-            .unwrap();
-    }
-
     #[tokio::test]
-    async fn test_no_flush_interval() {
+    async fn test_should_record_latency_histograms() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let db_options_no_flush_interval = {
-            let mut db_options = test_db_options(0, 1024, None);
-            db_options.flush_interval = None;
-            db_options
-        };
         let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(db_options_no_flush_interval)
+            .with_settings(test_db_options(0, 1024, None))
             .build()
             .await
             .unwrap();
-        let key = b"test_key";
-        let value = b"test_value";
 
+        kv_store.put(b"key", b"value").await.unwrap();
+        kv_store.flush().await.unwrap();
         kv_store
-            .put_with_options(
-                key,
-                value,
-                &PutOptions::default(),
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
             .await
             .unwrap();
+        kv_store.get(b"key").await.unwrap();
+        kv_store
+            .get_many(&[b"key".as_slice(), b"other"])
+            .await
+            .unwrap();
+        kv_store.scan::<Vec<u8>, _>(..).await.unwrap();
 
-        // a sanity check: the wal contains the most recent write
-        assert_ne!(kv_store.inner.wal_buffer.estimated_bytes().unwrap(), 0);
-
-        // and a flush() should clear it
-        kv_store.flush().await.unwrap();
-        assert_eq!(kv_store.inner.wal_buffer.estimated_bytes().unwrap(), 0);
+        let metrics = kv_store.metrics();
+        for name in [
+            GET_LATENCY_US,
+            SCAN_LATENCY_US,
+            WRITE_LATENCY_US,
+            WAL_FLUSH_LATENCY_US,
+            MEMTABLE_FLUSH_LATENCY_US,
+        ] {
+            let histogram = metrics.lookup(name).unwrap().histogram().unwrap();
+            assert!(histogram.count() > 0, "no latency recorded for {name}");
+        }
+        // a multi-key get is recorded once, and only in its own histogram
+        for name in [GET_LATENCY_US, GET_MANY_LATENCY_US] {
+            let histogram = metrics.lookup(name).unwrap().histogram().unwrap();
+            assert_eq!(histogram.count(), 1, "unexpected samples in {name}");
+        }
+        kv_store.close().await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_get_with_default_ttl_and_read_uncommitted() {
-        let clock = Arc::new(TestClock::new());
-        let ttl = 100;
-
+    async fn test_should_notify_event_listeners_of_flushes_and_close() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let listener = Arc::new(RecordingEventListener::default());
         let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
-            .with_logical_clock(clock.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .with_event_listener(listener.clone())
             .build()
             .await
             .unwrap();
 
-        let key = b"test_key";
-        let value = b"test_value";
-
-        // insert at t=0
-        kv_store.put(key, value).await.unwrap();
+        kv_store.put(b"key", b"value").await.unwrap();
+        kv_store.flush().await.unwrap();
+        let wal_flushed = listener.wal_flushed.lock().clone();
+        assert!(!wal_flushed.is_empty());
+        assert!(wal_flushed.iter().all(|event| event.bytes > 0));
 
-        // advance clock to t=99 --> still returned
-        clock.ticker.store(99, Ordering::SeqCst);
+        kv_store
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
+            .await
+            .unwrap();
+        let memtable_flushed = listener.memtable_flushed.lock().clone();
+        assert_eq!(memtable_flushed.len(), 1);
+        let l0 = kv_store.inner.state.read().state().core().l0.clone();
         assert_eq!(
-            Some(Bytes::from_static(value)),
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
-                .await
-                .unwrap(),
+            memtable_flushed[0].ssts,
+            vec![l0.front().unwrap().id.unwrap_compacted_id()]
         );
-
-        // advance clock to t=100 --> no longer returned
-        clock.ticker.store(100, Ordering::SeqCst);
+        assert!(memtable_flushed[0].bytes > 0);
+        assert_eq!(memtable_flushed[0].last_seq, 1);
+        let manifest_id = kv_store
+            .inner
+            .manifest_store
+            .read_latest_manifest()
+            .await
+            .unwrap()
+            .0;
         assert_eq!(
-            None,
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
-                .await
-                .unwrap(),
+            listener.manifest_written.lock().last(),
+            Some(&ManifestWrittenEvent { id: manifest_id })
         );
 
         kv_store.close().await.unwrap();
+        kv_store.close().await.unwrap();
+        assert_eq!(
+            *listener.db_closed.lock(),
+            vec![DbClosedEvent {
+                reason: CloseReason::Clean
+            }]
+        );
     }
 
     #[tokio::test]
-    async fn test_get_with_row_override_ttl_and_read_uncommitted() {
-        let clock = Arc::new(TestClock::new());
-        let default_ttl = 100;
-
+    async fn test_should_notify_event_listeners_of_background_task_failure() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(default_ttl)))
-            .with_logical_clock(clock.clone())
+        let listener = Arc::new(RecordingEventListener::default());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 128, None))
+            .with_fp_registry(fp_registry.clone())
+            .with_event_listener(listener.clone())
             .build()
             .await
             .unwrap();
 
-        let key = b"test_key";
-        let value = b"test_value";
-
-        // insert at t=0 with row-level override of 50 for ttl
-        kv_store
-            .put_with_options(
-                key,
-                value,
-                &PutOptions {
-                    ttl: Ttl::ExpireAfter(50),
-                },
-                &WriteOptions::default(),
-            )
-            .await
-            .unwrap();
-
-        // advance clock to t=49 --> still returned
-        clock.ticker.store(49, Ordering::SeqCst);
-        assert_eq!(
-            Some(Bytes::from_static(value)),
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
-                .await
-                .unwrap(),
-        );
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "panic").unwrap();
+        db.put(b"foo", b"bar").await.unwrap_err();
+        // the close is notified by a task that watches the background tasks
+        while listener.db_closed.lock().is_empty() {
+            tokio::task::yield_now().await;
+        }
+        db.close().await.unwrap();
 
-        // advance clock to t=50 --> no longer returned
-        clock.ticker.store(50, Ordering::SeqCst);
         assert_eq!(
-            None,
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
-                .await
-                .unwrap(),
+            *listener.db_closed.lock(),
+            vec![DbClosedEvent {
+                reason: CloseReason::Panic
+            }]
         );
-
-        kv_store.close().await.unwrap();
+        assert!(listener.wal_flushed.lock().is_empty());
     }
 
     #[tokio::test]
-    async fn test_get_with_default_ttl_and_read_committed() {
-        let clock = Arc::new(TestClock::new());
-        let ttl = 100;
-
+    async fn test_should_update_settings() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut settings = test_db_options(0, 1024 * 1024, Some(CompactorOptions::default()));
+        settings.flush_interval = None;
+        settings.garbage_collector_options = Some(GarbageCollectorOptions::default());
         let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
-            .with_logical_clock(clock.clone())
+            .with_settings(settings)
             .build()
             .await
             .unwrap();
 
-        let key = b"test_key";
-        let key_other = b"time_advancing_key";
-        let value = b"test_value";
+        let compactor_options = CompactorOptions {
+            poll_interval: Duration::from_millis(50),
+            max_sst_size: 4096,
+            history_retention: Some(Duration::from_secs(60)),
+            ..CompactorOptions::default()
+        };
+        let gc_directory_options = GarbageCollectorDirectoryOptions {
+            interval: Some(Duration::from_secs(1)),
+            min_age: Duration::from_secs(10),
+        };
+        kv_store
+            .update_settings(SettingsPatch {
+                flush_interval: Some(Some(Duration::from_millis(10))),
+                l0_sst_size_bytes: Some(2048),
+                max_unflushed_bytes: Some(1024 * 1024),
+                compactor_options: Some(compactor_options.clone()),
+                garbage_collector_options: Some(GarbageCollectorOptions {
+                    manifest_options: Some(gc_directory_options),
+                    wal_options: Some(gc_directory_options),
+                    compacted_options: Some(gc_directory_options),
+                }),
+                write_stall_options: Some(WriteStallOptions {
+                    l0_slowdown_ssts: Some(4),
+                    ..WriteStallOptions::default()
+                }),
+                rate_limit_options: Some(RateLimitOptions {
+                    flush_bytes_per_second: Some(1024 * 1024),
+                    ..RateLimitOptions::default()
+                }),
+            })
+            .unwrap();
 
-        // insert at t=0
-        kv_store.put(key, value).await.unwrap();
+        {
+            let settings = kv_store.inner.settings.read();
+            assert_eq!(settings.flush_interval, Some(Duration::from_millis(10)));
+            assert_eq!(settings.l0_sst_size_bytes, 2048);
+            assert_eq!(settings.max_unflushed_bytes, 1024 * 1024);
+            assert_eq!(settings.write_stall_options.l0_slowdown_ssts, Some(4));
+            assert_eq!(
+                settings.rate_limit_options.flush_bytes_per_second,
+                Some(1024 * 1024)
+            );
+            assert_eq!(
+                settings.compactor_options.as_ref().unwrap().max_sst_size,
+                4096
+            );
+            assert_eq!(
+                settings
+                    .garbage_collector_options
+                    .as_ref()
+                    .unwrap()
+                    .wal_options
+                    .unwrap()
+                    .min_age,
+                Duration::from_secs(10)
+            );
+        }
 
-        // advance clock to t=99 --> still returned
-        clock.ticker.store(99, Ordering::SeqCst);
-        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
-        kv_store.flush().await.unwrap();
-        assert_eq!(
-            Some(Bytes::from_static(value)),
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
-                .await
-                .unwrap(),
-        );
+        // the WAL is only flushed by the new flush interval
+        tokio::time::timeout(
+            Duration::from_secs(10),
+            kv_store.put_with_options(
+                b"key",
+                b"value",
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: true,
+                    ..WriteOptions::default()
+                },
+            ),
+        )
+        .await
+        .expect("write was not flushed by the updated flush interval")
+        .unwrap();
 
-        // advance clock to t=100 without flushing --> still returned
-        clock.ticker.store(100, Ordering::SeqCst);
-        assert_eq!(
-            Some(Bytes::from_static(value)),
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
-                .await
-                .unwrap(),
-        );
+        kv_store.close().await.unwrap();
+    }
 
-        // advance durable clock time to t=100 by flushing -- no longer returned
-        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
-        kv_store.flush().await.unwrap();
-        assert_eq!(
-            None,
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
-                .await
-                .unwrap(),
-        );
+    #[tokio::test]
+    async fn test_should_reject_invalid_settings_updates() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, Some(CompactorOptions::default())))
+            .build()
+            .await
+            .unwrap();
+
+        let invalid_patches = [
+            SettingsPatch {
+                flush_interval: Some(Some(Duration::ZERO)),
+                ..SettingsPatch::default()
+            },
+            SettingsPatch {
+                compactor_options: Some(CompactorOptions {
+                    manifest_update_timeout: Duration::from_secs(1),
+                    ..CompactorOptions::default()
+                }),
+                ..SettingsPatch::default()
+            },
+            SettingsPatch {
+                compactor_options: Some(CompactorOptions {
+                    max_concurrent_compactions: 1,
+                    ..CompactorOptions::default()
+                }),
+                ..SettingsPatch::default()
+            },
+            SettingsPatch {
+                compactor_options: Some(CompactorOptions {
+                    poll_interval: Duration::ZERO,
+                    ..CompactorOptions::default()
+                }),
+                ..SettingsPatch::default()
+            },
+            // no garbage collector runs with the database
+            SettingsPatch {
+                garbage_collector_options: Some(GarbageCollectorOptions::default()),
+                ..SettingsPatch::default()
+            },
+            SettingsPatch {
+                write_stall_options: Some(WriteStallOptions {
+                    l0_slowdown_ssts: Some(8),
+                    l0_stop_ssts: Some(4),
+                    ..WriteStallOptions::default()
+                }),
+                ..SettingsPatch::default()
+            },
+            SettingsPatch {
+                rate_limit_options: Some(RateLimitOptions {
+                    write_bytes_per_second: Some(0),
+                    ..RateLimitOptions::default()
+                }),
+                ..SettingsPatch::default()
+            },
+        ];
+        for patch in invalid_patches {
+            let err = kv_store
+                .update_settings(SettingsPatch {
+                    l0_sst_size_bytes: Some(4096),
+                    ..patch
+                })
+                .unwrap_err();
+            assert_eq!(err.kind(), crate::ErrorKind::Invalid);
+        }
 
+        // rejected patches don't change any setting
+        assert_eq!(kv_store.inner.settings.read().l0_sst_size_bytes, 1024);
         kv_store.close().await.unwrap();
+        let err = kv_store
+            .update_settings(SettingsPatch {
+                l0_sst_size_bytes: Some(4096),
+                ..SettingsPatch::default()
+            })
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Closed(CloseReason::Clean));
     }
 
     #[tokio::test]
-    #[cfg(feature = "wal_disable")]
-    async fn test_get_with_durability_level_when_wal_disabled() {
+    async fn test_should_reject_invalid_settings_at_open() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let mut options = test_db_options(0, 1024 * 1024, None);
-        options.wal_enabled = false;
-        let db = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(options)
+        let mut zero_delay = test_db_options(0, 1024, None);
+        zero_delay.write_stall_options = WriteStallOptions {
+            l0_stop_ssts: Some(4),
+            max_write_delay: Duration::ZERO,
+            ..WriteStallOptions::default()
+        };
+        let mut slowdown_after_stop = test_db_options(0, 1024, None);
+        slowdown_after_stop.write_stall_options = WriteStallOptions {
+            l0_slowdown_ssts: Some(8),
+            l0_stop_ssts: Some(4),
+            ..WriteStallOptions::default()
+        };
+        let mut zero_rate = test_db_options(0, 1024, None);
+        zero_rate.rate_limit_options = RateLimitOptions {
+            flush_bytes_per_second: Some(0),
+            ..RateLimitOptions::default()
+        };
+        let zero_compaction_rate = test_db_options(
+            0,
+            1024,
+            Some(CompactorOptions {
+                max_write_bytes_per_second: Some(0),
+                ..CompactorOptions::default()
+            }),
+        );
+
+        for settings in [
+            zero_delay,
+            slowdown_after_stop,
+            zero_rate,
+            zero_compaction_rate,
+        ] {
+            let result = Db::builder("/tmp/test_kv_store", object_store.clone())
+                .with_settings(settings)
+                .build()
+                .await;
+            let Err(err) = result else {
+                panic!("expected invalid settings to be rejected");
+            };
+            assert_eq!(err.kind(), crate::ErrorKind::Invalid);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_writes_stopped_by_l0_when_fail_on_stall_is_set() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut settings = test_db_options(0, 1024, None);
+        settings.write_stall_options = WriteStallOptions {
+            l0_stop_ssts: Some(1),
+            ..WriteStallOptions::default()
+        };
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(settings)
             .build()
             .await
             .unwrap();
-        let put_options = PutOptions::default();
-        let write_options = WriteOptions {
-            await_durable: false,
+        let fail_on_stall = WriteOptions {
+            fail_on_stall: true,
+            ..WriteOptions::default()
         };
-        let get_memory_options = ReadOptions::new().with_durability_filter(Memory);
-        let get_remote_options = ReadOptions::new().with_durability_filter(Remote);
 
-        db.put_with_options(b"foo", b"bar", &put_options, &write_options)
+        kv_store.put(b"key1", b"value1").await.unwrap();
+        kv_store
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
             .await
             .unwrap();
-        let val_bytes = Bytes::copy_from_slice(b"bar");
-        assert_eq!(
-            None,
-            db.get_with_options(b"foo", &get_remote_options)
-                .await
-                .unwrap()
-        );
-        assert_eq!(
-            Some(val_bytes.clone()),
-            db.get_with_options(b"foo", &get_memory_options)
-                .await
-                .unwrap()
-        );
-        db.flush().await.unwrap();
+        assert_eq!(kv_store.inner.state.read().state().core().l0.len(), 1);
+
+        // no compactor runs, so L0 stays at the stop limit
+        let err = kv_store
+            .put_with_options(b"key2", b"value2", &PutOptions::default(), &fail_on_stall)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Unavailable);
         assert_eq!(
-            Some(val_bytes.clone()),
-            db.get_with_options(b"foo", &get_remote_options)
-                .await
+            kv_store
+                .metrics()
+                .lookup(WRITE_STALL_FAILURES)
                 .unwrap()
+                .get(),
+            1
         );
+
+        kv_store
+            .update_settings(SettingsPatch {
+                write_stall_options: Some(WriteStallOptions::default()),
+                ..SettingsPatch::default()
+            })
+            .unwrap();
+        kv_store
+            .put_with_options(b"key2", b"value2", &PutOptions::default(), &fail_on_stall)
+            .await
+            .unwrap();
         assert_eq!(
-            Some(val_bytes.clone()),
-            db.get_with_options(b"foo", &get_memory_options)
-                .await
-                .unwrap()
+            kv_store.get(b"key2").await.unwrap(),
+            Some(Bytes::from_static(b"value2"))
         );
+        kv_store.close().await.unwrap();
     }
 
     #[tokio::test]
-    #[cfg(feature = "wal_disable")]
-    async fn test_find_with_multiple_repeated_keys() {
+    async fn test_should_fail_writes_over_rate_limit_when_fail_on_stall_is_set() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let mut options = test_db_options(0, 1024 * 1024, None);
-        options.wal_enabled = false;
-        let db = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(options)
+        let mut settings = test_db_options(0, 1024, None);
+        settings.rate_limit_options = RateLimitOptions {
+            write_bytes_per_second: Some(1),
+            ..RateLimitOptions::default()
+        };
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(settings)
             .build()
             .await
             .unwrap();
+        let fail_on_stall = WriteOptions {
+            fail_on_stall: true,
+            ..WriteOptions::default()
+        };
 
-        // write enough rows with the same key that we yield an L0 SST with multiple blocks
-        let mut last_val: String = "foo".to_string();
-        for x in 0..4096 {
-            let val = format!("val{}", x);
-            db.put_with_options(
-                b"key",
-                val.as_bytes(),
-                &PutOptions {
-                    ttl: Default::default(),
-                },
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
+        // the first write empties the bucket, and the next one would have to wait
+        kv_store
+            .put_with_options(b"key1", b"value1", &PutOptions::default(), &fail_on_stall)
             .await
             .unwrap();
-            last_val = val;
-            if db
-                .inner
-                .state
-                .write()
-                .memtable()
-                .metadata()
-                .entries_size_in_bytes
-                > (SsTableFormat::default().block_size * 3)
-            {
-                break;
-            }
-        }
-        assert_eq!(
-            Some(Bytes::copy_from_slice(last_val.as_bytes())),
-            db.get_with_options(b"key", &ReadOptions::new().with_durability_filter(Memory))
-                .await
-                .unwrap()
-        );
-        db.flush().await.unwrap();
+        let err = kv_store
+            .put_with_options(b"key2", b"value2", &PutOptions::default(), &fail_on_stall)
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Unavailable);
+        assert_eq!(kv_store.get(b"key2").await.unwrap(), None);
+        kv_store.close().await.unwrap();
+    }
 
-        let state = db.inner.state.read().view();
-        assert_eq!(1, state.state.manifest.core().l0.len());
-        let sst = state.state.manifest.core().l0.front().unwrap();
-        let index = db.inner.table_store.read_index(sst).await.unwrap();
-        assert!(index.borrow().block_meta().len() >= 3);
-        assert_eq!(
-            Some(Bytes::copy_from_slice(last_val.as_bytes())),
-            db.get(b"key").await.unwrap()
-        );
-        db.close().await.unwrap();
+    #[test]
+    fn test_get_after_put() {
+        let mut runner = new_proptest_runner(None);
+        let runtime = Runtime::new().unwrap();
+
+        let table = sample::table(runner.rng(), 1000, 10);
+        let db_options = test_db_options(0, 1024, None);
+        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+
+        runner
+            .run(
+                &(arbitrary::bytes(100), arbitrary::bytes(100)),
+                |(key, value)| {
+                    runtime.block_on(async {
+                        if !key.is_empty() {
+                            db.put_with_options(
+                                &key,
+                                &value,
+                                &PutOptions::default(),
+                                &WriteOptions {
+                                    await_durable: false,
+                                    ..WriteOptions::default()
+                                },
+                            )
+                            .await
+                            .unwrap();
+                            assert_eq!(
+                                Some(value),
+                                db.get_with_options(
+                                    &key,
+                                    &ReadOptions {
+                                        durability_filter: Memory,
+                                        dirty: false,
+                                        ..ReadOptions::default()
+                                    }
+                                )
+                                .await
+                                .unwrap()
+                            );
+                        }
+                    });
+                    Ok(())
+                },
+            )
+            .unwrap();
     }
 
     #[tokio::test]
-    async fn test_get_with_row_override_ttl_and_read_committed() {
-        let clock = Arc::new(TestClock::new());
-        let ttl = 100;
-
+    async fn test_no_flush_interval() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db_options_no_flush_interval = {
+            let mut db_options = test_db_options(0, 1024, None);
+            db_options.flush_interval = None;
+            db_options
+        };
         let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
-            .with_logical_clock(clock.clone())
+            .with_settings(db_options_no_flush_interval)
             .build()
             .await
             .unwrap();
-
         let key = b"test_key";
-        let key_other = b"time_advancing_key";
         let value = b"test_value";
 
-        // insert at t=0 with row-level override of 50 for ttl
         kv_store
             .put_with_options(
                 key,
                 value,
-                &PutOptions {
-                    ttl: Ttl::ExpireAfter(50),
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
                 },
-                &WriteOptions::default(),
             )
             .await
             .unwrap();
 
-        // advance clock to t=49 --> still returned
-        clock.ticker.store(49, Ordering::SeqCst);
-        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
-        kv_store.flush().await.unwrap();
-        assert_eq!(
-            Some(Bytes::from_static(value)),
-            kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
-                .await
-                .unwrap(),
-        );
+        // a sanity check: the wal contains the most recent write
+        assert_ne!(kv_store.inner.wal_buffer.estimated_bytes().unwrap(), 0);
 
-        // advance clock to t=50 without flushing --> still returned
-        clock.ticker.store(50, Ordering::SeqCst);
+        // and a flush() should clear it
+        kv_store.flush().await.unwrap();
+        assert_eq!(kv_store.inner.wal_buffer.estimated_bytes().unwrap(), 0);
+    }
+
+    #[tokio::test]
+    async fn test_get_with_default_ttl_and_read_uncommitted() {
+        let clock = Arc::new(TestClock::new());
+        let ttl = 100;
+
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
+            .with_logical_clock(clock.clone())
+            .build()
+            .await
+            .unwrap();
+
+        let key = b"test_key";
+        let value = b"test_value";
+
+        // insert at t=0
+        kv_store.put(key, value).await.unwrap();
+
+        // advance clock to t=99 --> still returned
+        clock.ticker.store(99, Ordering::SeqCst);
         assert_eq!(
             Some(Bytes::from_static(value)),
             kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
                 .await
                 .unwrap(),
         );
 
-        // advance durable clock time to t=100 by flushing -- no longer returned
-        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
-        kv_store.flush().await.unwrap();
+        // advance clock to t=100 --> no longer returned
+        clock.ticker.store(100, Ordering::SeqCst);
         assert_eq!(
             None,
             kv_store
-                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
                 .await
                 .unwrap(),
         );
@@ -1888,64 +3216,55 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_scan_with_default_ttl_and_read_uncommitted() {
+    async fn test_get_and_scan_with_metadata() {
         let clock = Arc::new(TestClock::new());
-        let ttl = 100;
-
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
+            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(100)))
             .with_logical_clock(clock.clone())
             .build()
             .await
             .unwrap();
 
-        // insert keys at t=0
+        clock.ticker.store(10, Ordering::SeqCst);
         kv_store.put(b"key1", b"value1").await.unwrap();
-        kv_store.put(b"key2", b"value2").await.unwrap();
-        kv_store.put(b"key3", b"value3").await.unwrap();
-
-        // advance clock to t=99 --> still returned
-        clock.ticker.store(99, Ordering::SeqCst);
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Memory),
+        clock.ticker.store(20, Ordering::SeqCst);
+        kv_store
+            .put_with_options(
+                b"key2",
+                b"value2",
+                &PutOptions { ttl: Ttl::NoExpiry },
+                &WriteOptions::default(),
             )
             .await
             .unwrap();
 
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key1");
-        assert_eq!(kv.value.as_ref(), b"value1");
-
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key2");
-        assert_eq!(kv.value.as_ref(), b"value2");
-
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key3");
-        assert_eq!(kv.value.as_ref(), b"value3");
-
-        assert_eq!(iter.next().await.unwrap(), None);
-
-        // advance clock to t=100 --> no longer returned
-        clock.ticker.store(100, Ordering::SeqCst);
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Memory),
-            )
-            .await
-            .unwrap();
+        let row1 = kv_store.get_with_metadata(b"key1").await.unwrap().unwrap();
+        assert_eq!(row1.value, Bytes::from_static(b"value1"));
+        assert_eq!(row1.create_ts, Some(10));
+        assert_eq!(row1.expire_ts, Some(110));
+        let row2 = kv_store.get_with_metadata(b"key2").await.unwrap().unwrap();
+        assert_eq!(row2.create_ts, Some(20));
+        assert_eq!(row2.expire_ts, None);
+        assert!(row2.seq > row1.seq);
+        assert_eq!(kv_store.get_with_metadata(b"key3").await.unwrap(), None);
 
-        assert_eq!(iter.next().await.unwrap(), None);
+        // snapshots and scans see the same metadata
+        let snapshot = kv_store.snapshot().await.unwrap();
+        assert_eq!(
+            snapshot.get_with_metadata(b"key1").await.unwrap(),
+            Some(row1.clone())
+        );
+        let mut iter = kv_store.scan::<&[u8], _>(..).await.unwrap();
+        assert_eq!(iter.next_with_metadata().await.unwrap(), Some(row1));
+        assert_eq!(iter.next_with_metadata().await.unwrap(), Some(row2));
+        assert_eq!(iter.next_with_metadata().await.unwrap(), None);
 
         kv_store.close().await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_scan_with_row_override_ttl_and_read_uncommitted() {
+    async fn test_get_with_row_override_ttl_and_read_uncommitted() {
         let clock = Arc::new(TestClock::new());
         let default_ttl = 100;
 
@@ -1957,11 +3276,14 @@ mod tests {
             .await
             .unwrap();
 
-        // insert keys at t=0 with different TTL overrides
+        let key = b"test_key";
+        let value = b"test_value";
+
+        // insert at t=0 with row-level override of 50 for ttl
         kv_store
             .put_with_options(
-                b"key1",
-                b"value1",
+                key,
+                value,
                 &PutOptions {
                     ttl: Ttl::ExpireAfter(50),
                 },
@@ -1969,88 +3291,32 @@ mod tests {
             )
             .await
             .unwrap();
-        kv_store
-            .put_with_options(
-                b"key2",
-                b"value2",
-                &PutOptions {
-                    ttl: Ttl::ExpireAfter(75),
-                },
-                &WriteOptions::default(),
-            )
-            .await
-            .unwrap();
-        kv_store
-            .put_with_options(
-                b"key3",
-                b"value3",
-                &PutOptions {
-                    ttl: Ttl::ExpireAfter(100),
-                },
-                &WriteOptions::default(),
-            )
-            .await
-            .unwrap();
 
-        // advance clock to t=49 --> all still returned
+        // advance clock to t=49 --> still returned
         clock.ticker.store(49, Ordering::SeqCst);
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Memory),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
-        assert_eq!(iter.next().await.unwrap(), None);
+        assert_eq!(
+            Some(Bytes::from_static(value)),
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
+                .await
+                .unwrap(),
+        );
 
-        // advance clock to t=50 --> key1 expired, key2 and key3 still returned
+        // advance clock to t=50 --> no longer returned
         clock.ticker.store(50, Ordering::SeqCst);
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Memory),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
-        assert_eq!(iter.next().await.unwrap(), None);
-
-        // advance clock to t=75 --> key1 and key2 expired, only key3 returned
-        clock.ticker.store(75, Ordering::SeqCst);
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Memory),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
-        assert_eq!(iter.next().await.unwrap(), None);
-
-        // advance clock to t=100 --> all expired
-        clock.ticker.store(100, Ordering::SeqCst);
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Memory),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(iter.next().await.unwrap(), None);
+        assert_eq!(
+            None,
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Memory))
+                .await
+                .unwrap(),
+        );
 
         kv_store.close().await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_scan_with_default_ttl_and_read_committed() {
+    async fn test_get_with_default_ttl_and_read_committed() {
         let clock = Arc::new(TestClock::new());
         let ttl = 100;
 
@@ -2062,83 +3328,183 @@ mod tests {
             .await
             .unwrap();
 
+        let key = b"test_key";
         let key_other = b"time_advancing_key";
+        let value = b"test_value";
 
-        // insert keys at t=0
-        kv_store.put(b"key1", b"value1").await.unwrap();
-        kv_store.put(b"key2", b"value2").await.unwrap();
-        kv_store.put(b"key3", b"value3").await.unwrap();
+        // insert at t=0
+        kv_store.put(key, value).await.unwrap();
 
         // advance clock to t=99 --> still returned
         clock.ticker.store(99, Ordering::SeqCst);
-        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
         kv_store.flush().await.unwrap();
-
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Remote),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
-        assert_eq!(iter.next().await.unwrap(), None);
+        assert_eq!(
+            Some(Bytes::from_static(value)),
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .await
+                .unwrap(),
+        );
 
         // advance clock to t=100 without flushing --> still returned
         clock.ticker.store(100, Ordering::SeqCst);
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Remote),
-            )
-            .await
-            .unwrap();
-
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
-        assert_eq!(iter.next().await.unwrap(), None);
+        assert_eq!(
+            Some(Bytes::from_static(value)),
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .await
+                .unwrap(),
+        );
 
         // advance durable clock time to t=100 by flushing -- no longer returned
-        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
         kv_store.flush().await.unwrap();
+        assert_eq!(
+            None,
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .await
+                .unwrap(),
+        );
 
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key4"[..],
-                &ScanOptions::new().with_durability_filter(Remote),
-            )
-            .await
+        kv_store.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "wal_disable")]
+    async fn test_get_with_durability_level_when_wal_disabled() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut options = test_db_options(0, 1024 * 1024, None);
+        options.wal_enabled = false;
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(options)
+            .build()
+            .await
             .unwrap();
+        let put_options = PutOptions::default();
+        let write_options = WriteOptions {
+            await_durable: false,
+            ..WriteOptions::default()
+        };
+        let get_memory_options = ReadOptions::new().with_durability_filter(Memory);
+        let get_remote_options = ReadOptions::new().with_durability_filter(Remote);
 
-        assert_eq!(iter.next().await.unwrap(), None);
+        db.put_with_options(b"foo", b"bar", &put_options, &write_options)
+            .await
+            .unwrap();
+        let val_bytes = Bytes::copy_from_slice(b"bar");
+        assert_eq!(
+            None,
+            db.get_with_options(b"foo", &get_remote_options)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Some(val_bytes.clone()),
+            db.get_with_options(b"foo", &get_memory_options)
+                .await
+                .unwrap()
+        );
+        db.flush().await.unwrap();
+        assert_eq!(
+            Some(val_bytes.clone()),
+            db.get_with_options(b"foo", &get_remote_options)
+                .await
+                .unwrap()
+        );
+        assert_eq!(
+            Some(val_bytes.clone()),
+            db.get_with_options(b"foo", &get_memory_options)
+                .await
+                .unwrap()
+        );
+    }
 
-        kv_store.close().await.unwrap();
+    #[tokio::test]
+    #[cfg(feature = "wal_disable")]
+    async fn test_find_with_multiple_repeated_keys() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut options = test_db_options(0, 1024 * 1024, None);
+        options.wal_enabled = false;
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(options)
+            .build()
+            .await
+            .unwrap();
+
+        // write enough rows with the same key that we yield an L0 SST with multiple blocks
+        let mut last_val: String = "foo".to_string();
+        for x in 0..4096 {
+            let val = format!("val{}", x);
+            db.put_with_options(
+                b"key",
+                val.as_bytes(),
+                &PutOptions {
+                    ttl: Default::default(),
+                },
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+            last_val = val;
+            if db
+                .inner
+                .state
+                .write()
+                .memtable()
+                .metadata()
+                .entries_size_in_bytes
+                > (SsTableFormat::default().block_size * 3)
+            {
+                break;
+            }
+        }
+        assert_eq!(
+            Some(Bytes::copy_from_slice(last_val.as_bytes())),
+            db.get_with_options(b"key", &ReadOptions::new().with_durability_filter(Memory))
+                .await
+                .unwrap()
+        );
+        db.flush().await.unwrap();
+
+        let state = db.inner.state.read().view();
+        assert_eq!(1, state.state.manifest.core().l0.len());
+        let sst = state.state.manifest.core().l0.front().unwrap();
+        let index = db.inner.table_store.read_index(sst).await.unwrap();
+        assert!(index.borrow().block_meta().len() >= 3);
+        assert_eq!(
+            Some(Bytes::copy_from_slice(last_val.as_bytes())),
+            db.get(b"key").await.unwrap()
+        );
+        db.close().await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_scan_with_row_override_ttl_and_read_committed() {
+    async fn test_get_with_row_override_ttl_and_read_committed() {
         let clock = Arc::new(TestClock::new());
-        let default_ttl = 100;
+        let ttl = 100;
 
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(default_ttl)))
+            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
             .with_logical_clock(clock.clone())
             .build()
             .await
             .unwrap();
 
+        let key = b"test_key";
         let key_other = b"time_advancing_key";
+        let value = b"test_value";
 
-        // insert keys at t=0 with row-level TTL overrides
+        // insert at t=0 with row-level override of 50 for ttl
         kv_store
             .put_with_options(
-                b"key1",
-                b"value1",
+                key,
+                value,
                 &PutOptions {
                     ttl: Ttl::ExpireAfter(50),
                 },
@@ -2146,73 +3512,91 @@ mod tests {
             )
             .await
             .unwrap();
-        kv_store
-            .put_with_options(
-                b"key2",
-                b"value2",
-                &PutOptions {
-                    ttl: Ttl::ExpireAfter(75),
-                },
-                &WriteOptions::default(),
-            )
-            .await
-            .unwrap();
 
-        // advance clock to t=49 --> all still returned
+        // advance clock to t=49 --> still returned
         clock.ticker.store(49, Ordering::SeqCst);
-        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
         kv_store.flush().await.unwrap();
+        assert_eq!(
+            Some(Bytes::from_static(value)),
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .await
+                .unwrap(),
+        );
 
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key3"[..],
-                &ScanOptions::new().with_durability_filter(Remote),
-            )
+        // advance clock to t=50 without flushing --> still returned
+        clock.ticker.store(50, Ordering::SeqCst);
+        assert_eq!(
+            Some(Bytes::from_static(value)),
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .await
+                .unwrap(),
+        );
+
+        // advance durable clock time to t=100 by flushing -- no longer returned
+        kv_store.put(key_other, value).await.unwrap(); // fake data to advance clock
+        kv_store.flush().await.unwrap();
+        assert_eq!(
+            None,
+            kv_store
+                .get_with_options(key, &ReadOptions::new().with_durability_filter(Remote))
+                .await
+                .unwrap(),
+        );
+
+        kv_store.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_scan_with_default_ttl_and_read_uncommitted() {
+        let clock = Arc::new(TestClock::new());
+        let ttl = 100;
+
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
+            .with_logical_clock(clock.clone())
+            .build()
             .await
             .unwrap();
 
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
-        assert_eq!(iter.next().await.unwrap(), None);
+        // insert keys at t=0
+        kv_store.put(b"key1", b"value1").await.unwrap();
+        kv_store.put(b"key2", b"value2").await.unwrap();
+        kv_store.put(b"key3", b"value3").await.unwrap();
 
-        // advance clock to t=50 without flushing --> still returned (durable clock hasn't advanced)
-        clock.ticker.store(50, Ordering::SeqCst);
+        // advance clock to t=99 --> still returned
+        clock.ticker.store(99, Ordering::SeqCst);
         let mut iter = kv_store
             .scan_with_options(
-                &b"key1"[..]..&b"key3"[..],
-                &ScanOptions::new().with_durability_filter(Remote),
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Memory),
             )
             .await
             .unwrap();
 
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
-        assert_eq!(iter.next().await.unwrap(), None);
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key.as_ref(), b"key1");
+        assert_eq!(kv.value.as_ref(), b"value1");
 
-        // advance durable clock time to t=50 by flushing -- key1 expired
-        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
-        kv_store.flush().await.unwrap();
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key.as_ref(), b"key2");
+        assert_eq!(kv.value.as_ref(), b"value2");
 
-        let mut iter = kv_store
-            .scan_with_options(
-                &b"key1"[..]..&b"key3"[..],
-                &ScanOptions::new().with_durability_filter(Remote),
-            )
-            .await
-            .unwrap();
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key.as_ref(), b"key3");
+        assert_eq!(kv.value.as_ref(), b"value3");
 
-        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
         assert_eq!(iter.next().await.unwrap(), None);
 
-        // advance durable clock time to t=75 by flushing -- both keys expired
-        clock.ticker.store(75, Ordering::SeqCst);
-        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
-        kv_store.flush().await.unwrap();
-
+        // advance clock to t=100 --> no longer returned
+        clock.ticker.store(100, Ordering::SeqCst);
         let mut iter = kv_store
             .scan_with_options(
-                &b"key1"[..]..&b"key3"[..],
-                &ScanOptions::new().with_durability_filter(Remote),
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Memory),
             )
             .await
             .unwrap();
@@ -2223,2045 +3607,3682 @@ mod tests {
     }
 
     #[tokio::test]
-    async fn test_get_with_object_store_cache_metrics() {
+    async fn test_scan_with_row_override_ttl_and_read_uncommitted() {
+        let clock = Arc::new(TestClock::new());
+        let default_ttl = 100;
+
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let mut opts = test_db_options(0, 1024, None);
-        let temp_dir = tempfile::Builder::new()
-            .prefix("objstore_cache_test_")
-            .tempdir()
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(default_ttl)))
+            .with_logical_clock(clock.clone())
+            .build()
+            .await
             .unwrap();
 
-        opts.object_store_cache_options.root_folder = Some(temp_dir.keep());
-        opts.object_store_cache_options.part_size_bytes = 1024;
-        let kv_store = Db::builder(
-            "/tmp/test_kv_store_with_cache_metrics",
-            object_store.clone(),
-        )
-        .with_settings(opts)
-        .build()
-        .await
-        .unwrap();
-
-        let access_count0 = kv_store
-            .metrics()
-            .lookup(OBJECT_STORE_CACHE_PART_ACCESS)
-            .unwrap()
-            .get();
-        let key = b"test_key";
-        let value = b"test_value";
-        kv_store.put(key, value).await.unwrap();
-        kv_store.flush().await.unwrap();
-
-        let got = kv_store.get(key).await.unwrap();
-        let access_count1 = kv_store
-            .metrics()
-            .lookup(OBJECT_STORE_CACHE_PART_ACCESS)
-            .unwrap()
-            .get();
-        assert_eq!(got, Some(Bytes::from_static(value)));
-        assert!(access_count1 > 0);
-        assert!(access_count1 >= access_count0);
-        assert!(
-            kv_store
-                .metrics()
-                .lookup(OBJECT_STORE_CACHE_PART_HITS)
-                .unwrap()
-                .get()
-                >= 1
-        );
-    }
+        // insert keys at t=0 with different TTL overrides
+        kv_store
+            .put_with_options(
+                b"key1",
+                b"value1",
+                &PutOptions {
+                    ttl: Ttl::ExpireAfter(50),
+                },
+                &WriteOptions::default(),
+            )
+            .await
+            .unwrap();
+        kv_store
+            .put_with_options(
+                b"key2",
+                b"value2",
+                &PutOptions {
+                    ttl: Ttl::ExpireAfter(75),
+                },
+                &WriteOptions::default(),
+            )
+            .await
+            .unwrap();
+        kv_store
+            .put_with_options(
+                b"key3",
+                b"value3",
+                &PutOptions {
+                    ttl: Ttl::ExpireAfter(100),
+                },
+                &WriteOptions::default(),
+            )
+            .await
+            .unwrap();
 
-    async fn test_object_store_cache_helper(
-        cache_puts_enabled: bool,
-        db_path: &str,
-        expected_cache_parts: Vec<(&str, usize)>,
-    ) -> (Arc<CachedObjectStore>, Db) {
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let mut opts = test_db_options(0, 1024, None);
-        // disable manifest polling to avoid caching manifests non-deterministically
-        opts.manifest_poll_interval = Duration::from_millis(u64::MAX);
-        let temp_dir = tempfile::Builder::new()
-            .prefix("objstore_cache_test_")
-            .tempdir()
+        // advance clock to t=49 --> all still returned
+        clock.ticker.store(49, Ordering::SeqCst);
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Memory),
+            )
+            .await
             .unwrap();
 
-        let stats_registry = StatRegistry::new();
-        let cache_stats = Arc::new(CachedObjectStoreStats::new(&stats_registry));
-        let part_size = 1024;
-        info!("temp_dir: {:?}", temp_dir.path());
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-        let cache_storage = Arc::new(FsCacheStorage::new(
-            temp_dir.keep(),
-            None,
-            None,
-            cache_stats.clone(),
-            Arc::new(DefaultSystemClock::new()),
-            Arc::new(DbRand::default()),
-        ));
+        // advance clock to t=50 --> key1 expired, key2 and key3 still returned
+        clock.ticker.store(50, Ordering::SeqCst);
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Memory),
+            )
+            .await
+            .unwrap();
 
-        let cached_object_store = CachedObjectStore::new(
-            object_store.clone(),
-            cache_storage,
-            part_size,
-            cache_puts_enabled,
-            cache_stats.clone(),
-        )
-        .unwrap();
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-        let kv_store = Db::builder(db_path, cached_object_store.clone())
-            .with_settings(opts)
-            .build()
+        // advance clock to t=75 --> key1 and key2 expired, only key3 returned
+        clock.ticker.store(75, Ordering::SeqCst);
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Memory),
+            )
             .await
             .unwrap();
-        let key = b"test_key";
-        let value = b"test_value";
-        kv_store.put(key, value).await.unwrap();
-        kv_store.flush().await.unwrap();
-
-        // Verify cache behavior
-        for (path, expected_parts) in expected_cache_parts {
-            let entry = cached_object_store
-                .cache_storage
-                .entry(&object_store::path::Path::from(path), part_size);
-            assert_eq!(
-                entry.cached_parts().await.unwrap().len(),
-                expected_parts,
-                "Path: {}",
-                path
-            );
-        }
 
-        (cached_object_store, kv_store)
-    }
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-    #[tokio::test]
-    async fn test_get_with_object_store_cache_stored_files() {
-        let expected_cache_parts =
-            vec![
-            ("tmp/test_kv_store_with_cache_stored_files/manifest/00000000000000000001.manifest", 0),
-            ("tmp/test_kv_store_with_cache_stored_files/manifest/00000000000000000002.manifest", 0),
-            // 1 part is cached because of wal_replay after fencing (which reads the SST, thereby caching it)
-            ("tmp/test_kv_store_with_cache_stored_files/wal/00000000000000000001.sst", 1),
-            ("tmp/test_kv_store_with_cache_stored_files/wal/00000000000000000002.sst", 0),
-        ];
+        // advance clock to t=100 --> all expired
+        clock.ticker.store(100, Ordering::SeqCst);
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Memory),
+            )
+            .await
+            .unwrap();
 
-        let (_cached_object_store, kv_store) = test_object_store_cache_helper(
-            false, // cache_puts disabled
-            "/tmp/test_kv_store_with_cache_stored_files",
-            expected_cache_parts,
-        )
-        .await;
+        assert_eq!(iter.next().await.unwrap(), None);
 
         kv_store.close().await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_get_with_object_store_cache_put_caching_enabled() {
-        let expected_cache_parts =
-            vec![
-            ("tmp/test_kv_store_with_put_cache_enabled/manifest/00000000000000000001.manifest", 1),
-            ("tmp/test_kv_store_with_put_cache_enabled/manifest/00000000000000000002.manifest", 1),
-            ("tmp/test_kv_store_with_put_cache_enabled/wal/00000000000000000001.sst", 1),
-            ("tmp/test_kv_store_with_put_cache_enabled/wal/00000000000000000002.sst", 1),
-        ];
-
-        let (_cached_object_store, kv_store) = test_object_store_cache_helper(
-            true, // cache_puts enabled
-            "/tmp/test_kv_store_with_put_cache_enabled",
-            expected_cache_parts,
-        )
-        .await;
-
-        kv_store.close().await.unwrap();
-    }
+    async fn test_scan_with_default_ttl_and_read_committed() {
+        let clock = Arc::new(TestClock::new());
+        let ttl = 100;
 
-    async fn build_database_from_table(
-        table: &BTreeMap<Bytes, Bytes>,
-        db_options: Settings,
-        await_durable: bool,
-    ) -> Db {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let db = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(db_options)
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(ttl)))
+            .with_logical_clock(clock.clone())
             .build()
             .await
             .unwrap();
 
-        test_utils::seed_database(&db, table, false).await.unwrap();
+        let key_other = b"time_advancing_key";
 
-        if await_durable {
-            db.flush().await.unwrap();
-        }
+        // insert keys at t=0
+        kv_store.put(b"key1", b"value1").await.unwrap();
+        kv_store.put(b"key2", b"value2").await.unwrap();
+        kv_store.put(b"key3", b"value3").await.unwrap();
 
-        db
-    }
+        // advance clock to t=99 --> still returned
+        clock.ticker.store(99, Ordering::SeqCst);
+        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.flush().await.unwrap();
 
-    #[tokio::test]
-    async fn test_should_allow_iterating_behind_box_dyn() {
-        #[async_trait]
-        trait IteratorSupplier {
-            async fn iterator(&self) -> Box<dyn IteratorTrait>;
-        }
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Remote),
+            )
+            .await
+            .unwrap();
 
-        struct DbHolder {
-            db: Db,
-        }
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-        #[async_trait]
-        impl IteratorSupplier for DbHolder {
-            async fn iterator(&self) -> Box<dyn IteratorTrait> {
-                let range = BytesRange::new_empty();
-                let iter = self
-                    .db
-                    .inner
-                    .scan_with_options(range, &ScanOptions::default())
-                    .await
-                    .unwrap();
-                Box::new(iter)
-            }
-        }
+        // advance clock to t=100 without flushing --> still returned
+        clock.ticker.store(100, Ordering::SeqCst);
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Remote),
+            )
+            .await
+            .unwrap();
 
-        #[async_trait]
-        trait IteratorTrait {
-            async fn next(&mut self) -> Result<Option<KeyValue>, crate::Error>;
-        }
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key3");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-        #[async_trait]
-        impl IteratorTrait for DbIterator {
-            async fn next(&mut self) -> Result<Option<KeyValue>, crate::Error> {
-                DbIterator::next(self).await
-            }
-        }
+        // advance durable clock time to t=100 by flushing -- no longer returned
+        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.flush().await.unwrap();
 
-        let db_options = test_db_options(0, 1024, None);
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let db = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(db_options)
-            .build()
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key4"[..],
+                &ScanOptions::new().with_durability_filter(Remote),
+            )
             .await
             .unwrap();
-        let db_holder = DbHolder { db };
-        let mut boxed = db_holder.iterator().await;
-        let next = boxed.next().await;
-        assert_eq!(next.unwrap(), None);
+
+        assert_eq!(iter.next().await.unwrap(), None);
+
+        kv_store.close().await.unwrap();
     }
 
-    async fn assert_records_in_range(
-        table: &BTreeMap<Bytes, Bytes>,
-        db: &Db,
-        scan_options: &ScanOptions,
-        range: BytesRange,
-    ) {
-        let mut iter = db
-            .inner
-            .scan_with_options(range.clone(), scan_options)
+    #[tokio::test]
+    async fn test_scan_with_row_override_ttl_and_read_committed() {
+        let clock = Arc::new(TestClock::new());
+        let default_ttl = 100;
+
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options_with_ttl(0, 1024, None, Some(default_ttl)))
+            .with_logical_clock(clock.clone())
+            .build()
             .await
             .unwrap();
-        test_utils::assert_ranged_db_scan(table, range, &mut iter).await;
-    }
-
-    #[test]
-    fn test_scan_returns_records_in_range() {
-        let mut runner = new_proptest_runner(None);
-        let table = sample::table(runner.rng(), 1000, 5);
 
-        let runtime = Runtime::new().unwrap();
-        let db_options = test_db_options(0, 1024, None);
-        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+        let key_other = b"time_advancing_key";
 
-        runner
-            .run(&arbitrary::nonempty_range(10), |range| {
-                runtime.block_on(assert_records_in_range(
-                    &table,
-                    &db,
-                    &ScanOptions::default(),
-                    range,
-                ));
-                Ok(())
-            })
+        // insert keys at t=0 with row-level TTL overrides
+        kv_store
+            .put_with_options(
+                b"key1",
+                b"value1",
+                &PutOptions {
+                    ttl: Ttl::ExpireAfter(50),
+                },
+                &WriteOptions::default(),
+            )
+            .await
+            .unwrap();
+        kv_store
+            .put_with_options(
+                b"key2",
+                b"value2",
+                &PutOptions {
+                    ttl: Ttl::ExpireAfter(75),
+                },
+                &WriteOptions::default(),
+            )
+            .await
             .unwrap();
-    }
 
-    fn new_proptest_runner(rng_seed: Option<[u8; 32]>) -> TestRunner {
-        proptest_util::runner::new(file!(), rng_seed)
-    }
+        // advance clock to t=49 --> all still returned
+        clock.ticker.store(49, Ordering::SeqCst);
+        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.flush().await.unwrap();
 
-    #[test]
-    fn test_scan_returns_uncommitted_records_if_read_level_uncommitted() {
-        let mut runner = new_proptest_runner(None);
-        let table = sample::table(runner.rng(), 1000, 5);
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key3"[..],
+                &ScanOptions::new().with_durability_filter(Remote),
+            )
+            .await
+            .unwrap();
 
-        let runtime = Runtime::new().unwrap();
-        let mut db_options = test_db_options(0, 1024, None);
-        db_options.flush_interval = Some(Duration::from_secs(5));
-        let db = runtime.block_on(build_database_from_table(&table, db_options, false));
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-        runner
-            .run(&arbitrary::nonempty_range(10), |range| {
-                let scan_options = ScanOptions {
-                    durability_filter: Memory,
-                    ..ScanOptions::default()
-                };
-                runtime.block_on(assert_records_in_range(&table, &db, &scan_options, range));
-                Ok(())
-            })
+        // advance clock to t=50 without flushing --> still returned (durable clock hasn't advanced)
+        clock.ticker.store(50, Ordering::SeqCst);
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key3"[..],
+                &ScanOptions::new().with_durability_filter(Remote),
+            )
+            .await
             .unwrap();
-    }
 
-    #[test]
-    fn test_seek_outside_of_range_returns_invalid_argument() {
-        let mut runner = new_proptest_runner(None);
-        let table = sample::table(runner.rng(), 1000, 10);
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key1");
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-        let runtime = Runtime::new().unwrap();
-        let db_options = test_db_options(0, 1024, None);
-        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+        // advance durable clock time to t=50 by flushing -- key1 expired
+        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.flush().await.unwrap();
 
-        runner
-            .run(
-                &(arbitrary::nonempty_bytes(10), arbitrary::rng()),
-                |(arbitrary_key, mut rng)| {
-                    runtime.block_on(assert_out_of_bound_seek_returns_invalid_argument(
-                        &db,
-                        &mut rng,
-                        arbitrary_key,
-                    ));
-                    Ok(())
-                },
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key3"[..],
+                &ScanOptions::new().with_durability_filter(Remote),
             )
+            .await
             .unwrap();
 
-        async fn assert_out_of_bound_seek_returns_invalid_argument(
-            db: &Db,
-            rng: &mut TestRng,
-            arbitrary_key: Bytes,
-        ) {
-            let mut iter = db
-                .scan_with_options(..arbitrary_key.clone(), &ScanOptions::default())
-                .await
-                .unwrap();
-
-            let lower_bounded_range = BytesRange::from(arbitrary_key.clone()..);
-            let value = sample::bytes_in_range(rng, &lower_bounded_range);
-            let err = iter.seek(value.clone()).await.unwrap_err();
-            assert!(
-                err.to_string()
-                    .contains("cannot seek to a key outside the iterator range"),
-                "{}",
-                err
-            );
+        assert_eq!(iter.next().await.unwrap().unwrap().key.as_ref(), b"key2");
+        assert_eq!(iter.next().await.unwrap(), None);
 
-            let mut iter = db
-                .scan_with_options(arbitrary_key.clone().., &ScanOptions::default())
-                .await
-                .unwrap();
+        // advance durable clock time to t=75 by flushing -- both keys expired
+        clock.ticker.store(75, Ordering::SeqCst);
+        kv_store.put(key_other, b"value").await.unwrap(); // fake data to advance clock
+        kv_store.flush().await.unwrap();
 
-            let upper_bounded_range = BytesRange::from(..arbitrary_key.clone());
-            let value = sample::bytes_in_range(rng, &upper_bounded_range);
-            let err = iter.seek(value.clone()).await.unwrap_err();
-            assert!(
-                err.to_string()
-                    .contains("cannot seek to a key outside the iterator range"),
-                "{}",
-                err
-            );
-        }
-    }
+        let mut iter = kv_store
+            .scan_with_options(
+                &b"key1"[..]..&b"key3"[..],
+                &ScanOptions::new().with_durability_filter(Remote),
+            )
+            .await
+            .unwrap();
 
-    #[test]
-    fn test_seek_fast_forwards_iterator() {
-        let mut runner = new_proptest_runner(None);
-        let table = sample::table(runner.rng(), 1000, 10);
+        assert_eq!(iter.next().await.unwrap(), None);
 
-        let runtime = Runtime::new().unwrap();
-        let db_options = test_db_options(0, 1024, None);
-        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+        kv_store.close().await.unwrap();
+    }
 
-        runner
-            .run(
-                &(arbitrary::nonempty_range(5), arbitrary::rng()),
-                |(range, mut rng)| {
-                    runtime.block_on(assert_seek_fast_forwards_iterator(
-                        &table, &db, &range, &mut rng,
-                    ));
-                    Ok(())
-                },
-            )
+    #[tokio::test]
+    async fn test_get_with_object_store_cache_metrics() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut opts = test_db_options(0, 1024, None);
+        let temp_dir = tempfile::Builder::new()
+            .prefix("objstore_cache_test_")
+            .tempdir()
             .unwrap();
 
-        async fn assert_seek_fast_forwards_iterator(
-            table: &BTreeMap<Bytes, Bytes>,
-            db: &Db,
-            scan_range: &BytesRange,
-            rng: &mut TestRng,
-        ) {
-            let mut iter = db
-                .inner
-                .scan_with_options(scan_range.clone(), &ScanOptions::default())
-                .await
-                .unwrap();
+        opts.object_store_cache_options.root_folder = Some(temp_dir.keep());
+        opts.object_store_cache_options.part_size_bytes = 1024;
+        let kv_store = Db::builder(
+            "/tmp/test_kv_store_with_cache_metrics",
+            object_store.clone(),
+        )
+        .with_settings(opts)
+        .build()
+        .await
+        .unwrap();
 
-            let seek_key = sample::bytes_in_range(rng, scan_range);
-            iter.seek(seek_key.clone()).await.unwrap();
+        let access_count0 = kv_store
+            .metrics()
+            .lookup(OBJECT_STORE_CACHE_PART_ACCESS)
+            .unwrap()
+            .get();
+        let key = b"test_key";
+        let value = b"test_value";
+        kv_store.put(key, value).await.unwrap();
+        kv_store.flush().await.unwrap();
 
-            let seek_range = BytesRange::new(Included(seek_key), scan_range.end_bound().cloned());
-            test_utils::assert_ranged_db_scan(table, seek_range, &mut iter).await;
-        }
+        let got = kv_store.get(key).await.unwrap();
+        let access_count1 = kv_store
+            .metrics()
+            .lookup(OBJECT_STORE_CACHE_PART_ACCESS)
+            .unwrap()
+            .get();
+        assert_eq!(got, Some(Bytes::from_static(value)));
+        assert!(access_count1 > 0);
+        assert!(access_count1 >= access_count0);
+        assert!(
+            kv_store
+                .metrics()
+                .lookup(OBJECT_STORE_CACHE_PART_HITS)
+                .unwrap()
+                .get()
+                >= 1
+        );
     }
 
-    #[tokio::test]
-    async fn test_write_batch() {
+    async fn test_object_store_cache_helper(
+        cache_puts_enabled: bool,
+        db_path: &str,
+        expected_cache_parts: Vec<(&str, usize)>,
+    ) -> (Arc<CachedObjectStore>, Db) {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
-            .with_settings(test_db_options(0, 1024, None))
+        let mut opts = test_db_options(0, 1024, None);
+        // disable manifest polling to avoid caching manifests non-deterministically
+        opts.manifest_poll_interval = Duration::from_millis(u64::MAX);
+        let temp_dir = tempfile::Builder::new()
+            .prefix("objstore_cache_test_")
+            .tempdir()
+            .unwrap();
+
+        let stats_registry = StatRegistry::new();
+        let cache_stats = Arc::new(CachedObjectStoreStats::new(&stats_registry));
+        let part_size = 1024;
+        info!("temp_dir: {:?}", temp_dir.path());
+
+        let cache_storage = Arc::new(FsCacheStorage::new(
+            temp_dir.keep(),
+            None,
+            None,
+            cache_stats.clone(),
+            Arc::new(DefaultSystemClock::new()),
+            Arc::new(DbRand::default()),
+        ));
+
+        let cached_object_store = CachedObjectStore::new(
+            object_store.clone(),
+            cache_storage,
+            part_size,
+            cache_puts_enabled,
+            cache_stats.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .unwrap();
+
+        let kv_store = Db::builder(db_path, cached_object_store.clone())
+            .with_settings(opts)
             .build()
             .await
             .unwrap();
+        let key = b"test_key";
+        let value = b"test_value";
+        kv_store.put(key, value).await.unwrap();
+        kv_store.flush().await.unwrap();
 
-        // Create a new WriteBatch
-        let mut batch = WriteBatch::new();
-        batch.put(b"key1", b"value1");
-        batch.put(b"key2", b"value2");
-        batch.delete(b"key1");
+        // Verify cache behavior
+        for (path, expected_parts) in expected_cache_parts {
+            let entry = cached_object_store
+                .cache_storage
+                .entry(&object_store::path::Path::from(path), part_size);
+            assert_eq!(
+                entry.cached_parts().await.unwrap().len(),
+                expected_parts,
+                "Path: {}",
+                path
+            );
+        }
 
-        // Write the batch
-        kv_store.write(batch).await.expect("write batch failed");
+        (cached_object_store, kv_store)
+    }
 
-        // Read back keys
-        assert_eq!(kv_store.get(b"key1").await.unwrap(), None);
-        assert_eq!(
-            kv_store.get(b"key2").await.unwrap(),
-            Some(Bytes::from_static(b"value2"))
-        );
+    #[tokio::test]
+    async fn test_get_with_object_store_cache_stored_files() {
+        let expected_cache_parts =
+            vec![
+            ("tmp/test_kv_store_with_cache_stored_files/manifest/00000000000000000001.manifest", 0),
+            ("tmp/test_kv_store_with_cache_stored_files/manifest/00000000000000000002.manifest", 0),
+            // 1 part is cached because of wal_replay after fencing (which reads the SST, thereby caching it)
+            ("tmp/test_kv_store_with_cache_stored_files/wal/00000000000000000001.sst", 1),
+            ("tmp/test_kv_store_with_cache_stored_files/wal/00000000000000000002.sst", 0),
+        ];
+
+        let (_cached_object_store, kv_store) = test_object_store_cache_helper(
+            false, // cache_puts disabled
+            "/tmp/test_kv_store_with_cache_stored_files",
+            expected_cache_parts,
+        )
+        .await;
 
         kv_store.close().await.unwrap();
     }
 
-    #[cfg(feature = "wal_disable")]
     #[tokio::test]
-    async fn test_write_batch_without_wal() {
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        // Use a very small l0 size to force flushes so await is notified
-        let mut options = test_db_options(0, 8, None);
+    async fn test_get_with_object_store_cache_put_caching_enabled() {
+        let expected_cache_parts =
+            vec![
+            ("tmp/test_kv_store_with_put_cache_enabled/manifest/00000000000000000001.manifest", 1),
+            ("tmp/test_kv_store_with_put_cache_enabled/manifest/00000000000000000002.manifest", 1),
+            ("tmp/test_kv_store_with_put_cache_enabled/wal/00000000000000000001.sst", 1),
+            ("tmp/test_kv_store_with_put_cache_enabled/wal/00000000000000000002.sst", 1),
+        ];
 
-        // Disable WAL
-        options.wal_enabled = false;
+        let (_cached_object_store, kv_store) = test_object_store_cache_helper(
+            true, // cache_puts enabled
+            "/tmp/test_kv_store_with_put_cache_enabled",
+            expected_cache_parts,
+        )
+        .await;
 
-        let kv_store = Db::builder("/tmp/test_kv_store_without_wal", object_store.clone())
-            .with_settings(options)
+        kv_store.close().await.unwrap();
+    }
+
+    async fn build_database_from_table(
+        table: &BTreeMap<Bytes, Bytes>,
+        db_options: Settings,
+        await_durable: bool,
+    ) -> Db {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(db_options)
             .build()
             .await
             .unwrap();
 
-        // Create a new WriteBatch
-        let mut batch = WriteBatch::new();
-        batch.put(b"key1", b"value1");
-        batch.put(b"key2", b"value2");
-        batch.delete(b"key1");
-
-        // Write the batch
-        kv_store.write(batch).await.expect("write batch failed");
+        test_utils::seed_database(&db, table, false).await.unwrap();
 
-        // Read back keys
-        assert_eq!(kv_store.get(b"key1").await.unwrap(), None);
-        assert_eq!(kv_store.get(b"key2").await.unwrap(), Some("value2".into()));
+        if await_durable {
+            db.flush().await.unwrap();
+        }
 
-        kv_store.close().await.unwrap();
+        db
     }
 
     #[tokio::test]
-    async fn test_write_batch_with_empty_key() {
-        let mut batch = WriteBatch::new();
-        let result = std::panic::catch_unwind(move || {
-            batch.put(b"", b"value");
-        });
-        assert!(
-            result.is_err(),
-            "Expected panic when using empty key in put operation"
-        );
+    async fn test_should_allow_iterating_behind_box_dyn() {
+        #[async_trait]
+        trait IteratorSupplier {
+            async fn iterator(&self) -> Box<dyn IteratorTrait>;
+        }
 
-        let mut batch = WriteBatch::new();
-        let result = std::panic::catch_unwind(move || {
-            batch.delete(b"");
-        });
-        assert!(
-            result.is_err(),
-            "Expected panic when using empty key in delete operation"
-        );
-    }
+        struct DbHolder {
+            db: Db,
+        }
 
-    /// Test that batch writes are atomic. Test does the following:
-    ///
-    /// - A set of, say 100 keys, 1-100
-    /// - Two tasks writing, one writing value to be same key, and other
-    ///   writing it to be key*2.
-    /// - We wait for both to complete.
-    /// - Assert that either all values are same as key, or all values key*2.
-    /// - Repeat above loop few times.
-    ///
-    /// _Note: This test is non-deterministic because it depends on the async
-    /// runtime to schedule the tasks in a way that the writes are concurrent._
-    #[tokio::test]
-    async fn test_concurrent_batch_writes_consistency() {
+        #[async_trait]
+        impl IteratorSupplier for DbHolder {
+            async fn iterator(&self) -> Box<dyn IteratorTrait> {
+                let range = BytesRange::new_empty();
+                let iter = self
+                    .db
+                    .inner
+                    .scan_with_options(range, &ScanOptions::default())
+                    .await
+                    .unwrap();
+                Box::new(iter)
+            }
+        }
+
+        #[async_trait]
+        trait IteratorTrait {
+            async fn next(&mut self) -> Result<Option<KeyValue>, crate::Error>;
+        }
+
+        #[async_trait]
+        impl IteratorTrait for DbIterator {
+            async fn next(&mut self) -> Result<Option<KeyValue>, crate::Error> {
+                DbIterator::next(self).await
+            }
+        }
+
+        let db_options = test_db_options(0, 1024, None);
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(db_options)
+            .build()
+            .await
+            .unwrap();
+        let db_holder = DbHolder { db };
+        let mut boxed = db_holder.iterator().await;
+        let next = boxed.next().await;
+        assert_eq!(next.unwrap(), None);
+    }
+
+    async fn assert_records_in_range(
+        table: &BTreeMap<Bytes, Bytes>,
+        db: &Db,
+        scan_options: &ScanOptions,
+        range: BytesRange,
+    ) {
+        let mut iter = db
+            .inner
+            .scan_with_options(range.clone(), scan_options)
+            .await
+            .unwrap();
+        test_utils::assert_ranged_db_scan(table, range, scan_options.order, &mut iter).await;
+    }
+
+    #[test]
+    fn test_scan_returns_records_in_range() {
+        let mut runner = new_proptest_runner(None);
+        let table = sample::table(runner.rng(), 1000, 5);
+
+        let runtime = Runtime::new().unwrap();
+        let db_options = test_db_options(0, 1024, None);
+        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+
+        runner
+            .run(&arbitrary::nonempty_range(10), |range| {
+                runtime.block_on(assert_records_in_range(
+                    &table,
+                    &db,
+                    &ScanOptions::default(),
+                    range,
+                ));
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_scan_returns_records_in_range_descending() {
+        let mut runner = new_proptest_runner(None);
+        let table = sample::table(runner.rng(), 1000, 5);
+
+        let runtime = Runtime::new().unwrap();
+        let db_options = test_db_options(0, 1024, None);
+        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+
+        runner
+            .run(&arbitrary::nonempty_range(10), |range| {
+                runtime.block_on(assert_records_in_range(
+                    &table,
+                    &db,
+                    &ScanOptions::default().with_order(IterationOrder::Descending),
+                    range,
+                ));
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    fn new_proptest_runner(rng_seed: Option<[u8; 32]>) -> TestRunner {
+        proptest_util::runner::new(file!(), rng_seed)
+    }
+
+    #[test]
+    fn test_scan_returns_uncommitted_records_if_read_level_uncommitted() {
+        let mut runner = new_proptest_runner(None);
+        let table = sample::table(runner.rng(), 1000, 5);
+
+        let runtime = Runtime::new().unwrap();
+        let mut db_options = test_db_options(0, 1024, None);
+        db_options.flush_interval = Some(Duration::from_secs(5));
+        let db = runtime.block_on(build_database_from_table(&table, db_options, false));
+
+        runner
+            .run(&arbitrary::nonempty_range(10), |range| {
+                let scan_options = ScanOptions {
+                    durability_filter: Memory,
+                    ..ScanOptions::default()
+                };
+                runtime.block_on(assert_records_in_range(&table, &db, &scan_options, range));
+                Ok(())
+            })
+            .unwrap();
+    }
+
+    #[test]
+    fn test_seek_outside_of_range_returns_invalid_argument() {
+        let mut runner = new_proptest_runner(None);
+        let table = sample::table(runner.rng(), 1000, 10);
+
+        let runtime = Runtime::new().unwrap();
+        let db_options = test_db_options(0, 1024, None);
+        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+
+        runner
+            .run(
+                &(arbitrary::nonempty_bytes(10), arbitrary::rng()),
+                |(arbitrary_key, mut rng)| {
+                    runtime.block_on(assert_out_of_bound_seek_returns_invalid_argument(
+                        &db,
+                        &mut rng,
+                        arbitrary_key,
+                    ));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        async fn assert_out_of_bound_seek_returns_invalid_argument(
+            db: &Db,
+            rng: &mut TestRng,
+            arbitrary_key: Bytes,
+        ) {
+            let mut iter = db
+                .scan_with_options(..arbitrary_key.clone(), &ScanOptions::default())
+                .await
+                .unwrap();
+
+            let lower_bounded_range = BytesRange::from(arbitrary_key.clone()..);
+            let value = sample::bytes_in_range(rng, &lower_bounded_range);
+            let err = iter.seek(value.clone()).await.unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("cannot seek to a key outside the iterator range"),
+                "{}",
+                err
+            );
+
+            let mut iter = db
+                .scan_with_options(arbitrary_key.clone().., &ScanOptions::default())
+                .await
+                .unwrap();
+
+            let upper_bounded_range = BytesRange::from(..arbitrary_key.clone());
+            let value = sample::bytes_in_range(rng, &upper_bounded_range);
+            let err = iter.seek(value.clone()).await.unwrap_err();
+            assert!(
+                err.to_string()
+                    .contains("cannot seek to a key outside the iterator range"),
+                "{}",
+                err
+            );
+        }
+    }
+
+    #[test]
+    fn test_seek_fast_forwards_iterator() {
+        let mut runner = new_proptest_runner(None);
+        let table = sample::table(runner.rng(), 1000, 10);
+
+        let runtime = Runtime::new().unwrap();
+        let db_options = test_db_options(0, 1024, None);
+        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+
+        runner
+            .run(
+                &(arbitrary::nonempty_range(5), arbitrary::rng()),
+                |(range, mut rng)| {
+                    runtime.block_on(assert_seek_fast_forwards_iterator(
+                        &table, &db, &range, &mut rng,
+                    ));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        async fn assert_seek_fast_forwards_iterator(
+            table: &BTreeMap<Bytes, Bytes>,
+            db: &Db,
+            scan_range: &BytesRange,
+            rng: &mut TestRng,
+        ) {
+            let mut iter = db
+                .inner
+                .scan_with_options(scan_range.clone(), &ScanOptions::default())
+                .await
+                .unwrap();
+
+            let seek_key = sample::bytes_in_range(rng, scan_range);
+            iter.seek(seek_key.clone()).await.unwrap();
+
+            let seek_range = BytesRange::new(Included(seek_key), scan_range.end_bound().cloned());
+            test_utils::assert_ranged_db_scan(
+                table,
+                seek_range,
+                IterationOrder::Ascending,
+                &mut iter,
+            )
+            .await;
+        }
+    }
+
+    #[test]
+    fn test_seek_fast_forwards_descending_iterator() {
+        let mut runner = new_proptest_runner(None);
+        let table = sample::table(runner.rng(), 1000, 10);
+
+        let runtime = Runtime::new().unwrap();
+        let db_options = test_db_options(0, 1024, None);
+        let db = runtime.block_on(build_database_from_table(&table, db_options, true));
+
+        runner
+            .run(
+                &(arbitrary::nonempty_range(5), arbitrary::rng()),
+                |(range, mut rng)| {
+                    runtime.block_on(assert_seek_fast_forwards_descending_iterator(
+                        &table, &db, &range, &mut rng,
+                    ));
+                    Ok(())
+                },
+            )
+            .unwrap();
+
+        async fn assert_seek_fast_forwards_descending_iterator(
+            table: &BTreeMap<Bytes, Bytes>,
+            db: &Db,
+            scan_range: &BytesRange,
+            rng: &mut TestRng,
+        ) {
+            let mut iter = db
+                .inner
+                .scan_with_options(
+                    scan_range.clone(),
+                    &ScanOptions::default().with_order(IterationOrder::Descending),
+                )
+                .await
+                .unwrap();
+
+            let seek_key = sample::bytes_in_range(rng, scan_range);
+            iter.seek(seek_key.clone()).await.unwrap();
+
+            let seek_range = BytesRange::new(scan_range.start_bound().cloned(), Included(seek_key));
+            test_utils::assert_ranged_db_scan(
+                table,
+                seek_range,
+                IterationOrder::Descending,
+                &mut iter,
+            )
+            .await;
+        }
+    }
+
+    #[tokio::test]
+    async fn test_descending_scan_merges_memtable_and_l0() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+        db.put(b"key1", b"value1").await.unwrap();
+        db.put(b"key2", b"value2").await.unwrap();
+        db.put(b"key3", b"value3").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        db.put(b"key2", b"value2-new").await.unwrap();
+        db.delete(b"key3").await.unwrap();
+        db.put(b"key4", b"value4").await.unwrap();
+
+        let mut iter = db
+            .scan_with_options::<Vec<u8>, _>(
+                ..,
+                &ScanOptions::default().with_order(IterationOrder::Descending),
+            )
+            .await
+            .unwrap();
+
+        let mut actual = Vec::new();
+        while let Some(kv) = iter.next().await.unwrap() {
+            actual.push((kv.key, kv.value));
+        }
+        assert_eq!(
+            actual,
+            vec![
+                (Bytes::from_static(b"key4"), Bytes::from_static(b"value4")),
+                (
+                    Bytes::from_static(b"key2"),
+                    Bytes::from_static(b"value2-new")
+                ),
+                (Bytes::from_static(b"key1"), Bytes::from_static(b"value1")),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_prefix_scan_skips_ssts_without_prefix() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_prefix_extractor(Arc::new(FixedLengthPrefixExtractor::new(7)))
+            .build()
+            .await
+            .unwrap();
+        let flush_options = FlushOptions {
+            flush_type: FlushType::MemTable,
+        };
+        db.put(b"user:1:a", b"value1").await.unwrap();
+        db.put(b"user:1:b", b"value2").await.unwrap();
+        db.flush_with_options(flush_options.clone()).await.unwrap();
+        // the key range of this SST covers the prefix, but none of its keys has it
+        db.put(b"user:0:a", b"value3").await.unwrap();
+        db.put(b"user:2:a", b"value4").await.unwrap();
+        db.flush_with_options(flush_options).await.unwrap();
+        db.put(b"user:1:c", b"value5").await.unwrap();
+
+        let mut iter = db
+            .scan_with_options::<Vec<u8>, _>(.., &ScanOptions::default().with_prefix("user:1:"))
+            .await
+            .unwrap();
+
+        let mut actual = Vec::new();
+        while let Some(kv) = iter.next().await.unwrap() {
+            actual.push(kv.key);
+        }
+        assert_eq!(
+            actual,
+            vec![
+                Bytes::from_static(b"user:1:a"),
+                Bytes::from_static(b"user:1:b"),
+                Bytes::from_static(b"user:1:c"),
+            ]
+        );
+        let metrics = db.metrics();
+        assert_eq!(
+            metrics.lookup(SST_PREFIX_FILTER_POSITIVES).unwrap().get(),
+            1
+        );
+        assert_eq!(
+            metrics.lookup(SST_PREFIX_FILTER_NEGATIVES).unwrap().get(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_batch() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+
+        // Create a new WriteBatch
+        let mut batch = WriteBatch::new();
+        batch.put(b"key1", b"value1");
+        batch.put(b"key2", b"value2");
+        batch.delete(b"key1");
+
+        // Write the batch
+        kv_store.write(batch).await.expect("write batch failed");
+
+        // Read back keys
+        assert_eq!(kv_store.get(b"key1").await.unwrap(), None);
+        assert_eq!(
+            kv_store.get(b"key2").await.unwrap(),
+            Some(Bytes::from_static(b"value2"))
+        );
+
+        kv_store.close().await.unwrap();
+    }
+
+    #[cfg(feature = "wal_disable")]
+    #[tokio::test]
+    async fn test_write_batch_without_wal() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        // Use a very small l0 size to force flushes so await is notified
+        let mut options = test_db_options(0, 8, None);
+
+        // Disable WAL
+        options.wal_enabled = false;
+
+        let kv_store = Db::builder("/tmp/test_kv_store_without_wal", object_store.clone())
+            .with_settings(options)
+            .build()
+            .await
+            .unwrap();
+
+        // Create a new WriteBatch
+        let mut batch = WriteBatch::new();
+        batch.put(b"key1", b"value1");
+        batch.put(b"key2", b"value2");
+        batch.delete(b"key1");
+
+        // Write the batch
+        kv_store.write(batch).await.expect("write batch failed");
+
+        // Read back keys
+        assert_eq!(kv_store.get(b"key1").await.unwrap(), None);
+        assert_eq!(kv_store.get(b"key2").await.unwrap(), Some("value2".into()));
+
+        kv_store.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_write_batch_with_empty_key() {
+        let mut batch = WriteBatch::new();
+        let result = std::panic::catch_unwind(move || {
+            batch.put(b"", b"value");
+        });
+        assert!(
+            result.is_err(),
+            "Expected panic when using empty key in put operation"
+        );
+
+        let mut batch = WriteBatch::new();
+        let result = std::panic::catch_unwind(move || {
+            batch.delete(b"");
+        });
+        assert!(
+            result.is_err(),
+            "Expected panic when using empty key in delete operation"
+        );
+    }
+
+    /// Test that batch writes are atomic. Test does the following:
+    ///
+    /// - A set of, say 100 keys, 1-100
+    /// - Two tasks writing, one writing value to be same key, and other
+    ///   writing it to be key*2.
+    /// - We wait for both to complete.
+    /// - Assert that either all values are same as key, or all values key*2.
+    /// - Repeat above loop few times.
+    ///
+    /// _Note: This test is non-deterministic because it depends on the async
+    /// runtime to schedule the tasks in a way that the writes are concurrent._
+    #[tokio::test]
+    async fn test_concurrent_batch_writes_consistency() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let compaction_scheduler = Arc::new(SizeTieredCompactionSchedulerSupplier::new(
             SizeTieredCompactionSchedulerOptions::default(),
         ));
-        let kv_store = Arc::new(
-            Db::builder("/tmp/test_concurrent_kv_store", object_store)
-                .with_settings(test_db_options(
-                    0,
-                    1024,
-                    // Enable compactor to prevent l0 from filling up and
-                    // applying backpressure indefinitely.
-                    Some(CompactorOptions {
-                        poll_interval: Duration::from_millis(100),
-                        max_sst_size: 256,
-                        max_concurrent_compactions: 1,
-                        manifest_update_timeout: Duration::from_secs(300),
-                    }),
-                ))
-                .with_compaction_scheduler_supplier(compaction_scheduler)
-                .build()
+        let kv_store = Arc::new(
+            Db::builder("/tmp/test_concurrent_kv_store", object_store)
+                .with_settings(test_db_options(
+                    0,
+                    1024,
+                    // Enable compactor to prevent l0 from filling up and
+                    // applying backpressure indefinitely.
+                    Some(CompactorOptions {
+                        poll_interval: Duration::from_millis(100),
+                        max_sst_size: 256,
+                        max_concurrent_compactions: 1,
+                        manifest_update_timeout: Duration::from_secs(300),
+                        history_retention: None,
+                        max_write_bytes_per_second: None,
+                        min_blob_file_live_ratio: Some(0.5),
+                    }),
+                ))
+                .with_compaction_scheduler_supplier(compaction_scheduler)
+                .build()
+                .await
+                .unwrap(),
+        );
+
+        const NUM_KEYS: usize = 100;
+        const NUM_ROUNDS: usize = 20;
+
+        for _ in 0..NUM_ROUNDS {
+            // Write two tasks that write to the same keys
+            let task1 = {
+                let store = kv_store.clone();
+                tokio::spawn(async move {
+                    let mut batch = WriteBatch::new();
+                    for key in 1..=NUM_KEYS {
+                        batch.put(key.to_be_bytes(), key.to_be_bytes());
+                    }
+                    store.write(batch).await.expect("write batch failed");
+                })
+            };
+
+            let task2 = {
+                let store = kv_store.clone();
+                tokio::spawn(async move {
+                    let mut batch = WriteBatch::new();
+                    for key in 1..=NUM_KEYS {
+                        let value = (key * 2).to_be_bytes();
+                        batch.put(key.to_be_bytes(), value);
+                    }
+                    store.write(batch).await.expect("write batch failed");
+                })
+            };
+
+            // Wait for both tasks to complete
+            join_all(vec![task1, task2]).await;
+
+            // Ensure consistency: all values must be either key or key * 2
+            let mut all_key = true;
+            let mut all_key2 = true;
+
+            for key in 1..=NUM_KEYS {
+                let value = kv_store.get(key.to_be_bytes()).await.unwrap();
+                let value = value.expect("Value should exist");
+
+                if value.as_ref() != key.to_be_bytes() {
+                    all_key = false;
+                }
+                if value.as_ref() != (key * 2).to_be_bytes() {
+                    all_key2 = false;
+                }
+            }
+
+            // Assert that the result is consistent: either all key or all key * 2
+            assert!(
+                all_key || all_key2,
+                "Inconsistent state: not all values match either key or key * 2"
+            );
+        }
+
+        kv_store.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "wal_disable")]
+    async fn test_disable_wal_after_wal_enabled() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        // open a db and write a wal entry
+        let options = test_db_options(0, 32, None);
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(options)
+            .build()
+            .await
+            .unwrap();
+        db.put(&[b'a'; 4], &[b'j'; 4]).await.unwrap();
+        db.put(&[b'b'; 4], &[b'k'; 4]).await.unwrap();
+        db.close().await.unwrap();
+
+        // open a db with wal disabled and write a memtable
+        let mut options = test_db_options(0, 32, None);
+        options.wal_enabled = false;
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(options.clone())
+            .build()
+            .await
+            .unwrap();
+        db.delete_with_options(
+            &[b'b'; 4],
+            &WriteOptions {
+                await_durable: false,
+                ..WriteOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        db.put(&[b'a'; 4], &[b'z'; 64]).await.unwrap();
+        db.close().await.unwrap();
+
+        // ensure we don't overwrite the values we just put on a reload
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(options.clone())
+            .build()
+            .await
+            .unwrap();
+        let val = db.get(&[b'a'; 4]).await.unwrap();
+        assert_eq!(val.unwrap(), Bytes::copy_from_slice(&[b'z'; 64]));
+        let val = db.get(&[b'b'; 4]).await.unwrap();
+        assert!(val.is_none());
+    }
+
+    #[cfg(feature = "wal_disable")]
+    #[tokio::test]
+    async fn test_wal_disabled() {
+        use crate::{test_utils::assert_iterator, types::RowEntry};
+
+        let clock = Arc::new(TestClock::new());
+        let mut options = test_db_options(0, 256, None);
+        options.wal_enabled = false;
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = Path::from("/tmp/test_kv_store");
+        let sst_format = SsTableFormat::default();
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(object_store.clone(), None),
+            sst_format,
+            path.clone(),
+            None,
+        ));
+        let db = Db::builder(path.clone(), object_store.clone())
+            .with_settings(options)
+            .with_logical_clock(clock.clone())
+            .build()
+            .await
+            .unwrap();
+        let manifest_store = Arc::new(ManifestStore::new(
+            &path,
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        ));
+        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
+        let write_options = WriteOptions {
+            await_durable: false,
+            ..WriteOptions::default()
+        };
+
+        db.put_with_options(
+            &[b'a'; 32],
+            &[b'j'; 32],
+            &PutOptions::default(),
+            &write_options,
+        )
+        .await
+        .unwrap();
+        db.delete_with_options(&[b'b'; 31], &write_options)
+            .await
+            .unwrap();
+
+        // ensure the memtable's size is greater than l0_sst_size_bytes, or
+        // the memtable will not be flushed to l0, and the test will hang
+        // at this put_with_options call.
+        let write_options = WriteOptions {
+            await_durable: true,
+            ..WriteOptions::default()
+        };
+        clock.ticker.store(10, Ordering::SeqCst);
+        db.put_with_options(
+            &[b'c'; 32],
+            &[b'l'; 32],
+            &PutOptions::default(),
+            &write_options,
+        )
+        .await
+        .unwrap();
+
+        let state = wait_for_manifest_condition(
+            &mut stored_manifest,
+            |s| !s.l0.is_empty(),
+            Duration::from_secs(30),
+        )
+        .await;
+        assert_eq!(state.l0.len(), 1);
+
+        let l0 = state.l0.front().unwrap();
+        let mut iter = SstIterator::new_borrowed_initialized(
+            ..,
+            l0,
+            table_store.clone(),
+            SstIteratorOptions::default(),
+        )
+        .await
+        .unwrap()
+        .expect("Expected Some(iter) but got None");
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(&[b'a'; 32], &[b'j'; 32], 1).with_create_ts(0),
+                RowEntry::new_tombstone(&[b'b'; 31], 2).with_create_ts(0),
+                RowEntry::new_value(&[b'c'; 32], &[b'l'; 32], 3).with_create_ts(10),
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_put_flushes_memtable() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let kv_store = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 256, None))
+            .build()
+            .await
+            .unwrap();
+        let clock = Arc::new(DefaultSystemClock::new());
+        let manifest_store = Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            clock.clone(),
+        ));
+        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
+        let sst_format = SsTableFormat {
+            min_filter_keys: 10,
+            ..SsTableFormat::default()
+        };
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(object_store.clone(), None),
+            sst_format,
+            path,
+            None,
+        ));
+
+        // Write data a few times such that each loop results in a memtable flush
+        let mut last_wal_id = 0;
+        for i in 0..3 {
+            let key = [b'a' + i; 16];
+            let value = [b'b' + i; 50];
+            kv_store.put(&key, &value).await.unwrap();
+            let key = [b'j' + i; 16];
+            let value = [b'k' + i; 50];
+            kv_store.put(&key, &value).await.unwrap();
+            let db_state = wait_for_manifest_condition(
+                &mut stored_manifest,
+                |s| s.replay_after_wal_id > last_wal_id,
+                Duration::from_secs(30),
+            )
+            .await;
+
+            // 2 wal per iteration.
+            assert_eq!(db_state.replay_after_wal_id, (i as u64) * 2 + 2);
+            last_wal_id = db_state.replay_after_wal_id
+        }
+
+        let manifest = stored_manifest.refresh().await.unwrap();
+        let l0 = &manifest.core.l0;
+        assert_eq!(l0.len(), 3);
+        let sst_iter_options = SstIteratorOptions::default();
+
+        for i in 0u8..3u8 {
+            let sst1 = l0.get(2 - i as usize).unwrap();
+            let mut iter = SstIterator::new_borrowed_initialized(
+                ..,
+                sst1,
+                table_store.clone(),
+                sst_iter_options,
+            )
+            .await
+            .unwrap()
+            .expect("Expected Some(iter) but got None");
+            let kv = iter.next().await.unwrap().unwrap();
+            assert_eq!(kv.key.as_ref(), [b'a' + i; 16]);
+            assert_eq!(kv.value.as_ref(), [b'b' + i; 50]);
+            let kv = iter.next().await.unwrap().unwrap();
+            assert_eq!(kv.key.as_ref(), [b'j' + i; 16]);
+            assert_eq!(kv.value.as_ref(), [b'k' + i; 50]);
+            let kv = iter.next().await.unwrap();
+            assert!(kv.is_none());
+        }
+        assert!(
+            kv_store
+                .metrics()
+                .lookup(IMMUTABLE_MEMTABLE_FLUSHES)
+                .unwrap()
+                .get()
+                > 0
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_memtable_with_wal_enabled() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_flush_with_options";
+        let mut options = test_db_options(0, 256, None);
+        options.flush_interval = Some(Duration::from_secs(u64::MAX));
+        let kv_store = Db::builder(path, object_store.clone())
+            .with_settings(options)
+            .build()
+            .await
+            .unwrap();
+
+        let manifest_store = Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        ));
+        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
+        let sst_format = SsTableFormat {
+            min_filter_keys: 10,
+            ..SsTableFormat::default()
+        };
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(object_store.clone(), None),
+            sst_format,
+            path,
+            None,
+        ));
+
+        // Write some data to populate the memtable
+        let key1 = b"test_key_1";
+        let value1 = b"test_value_1";
+        kv_store
+            .put_with_options(
+                key1,
+                value1,
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let key2 = b"test_key_2";
+        let value2 = b"test_value_2";
+        kv_store
+            .put_with_options(
+                key2,
+                value2,
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // Get initial state
+        let initial_manifest = stored_manifest.refresh().await.unwrap();
+        let initial_l0_count = initial_manifest.core.l0.len();
+
+        let initial_flush_count = kv_store
+            .metrics()
+            .lookup(IMMUTABLE_MEMTABLE_FLUSHES)
+            .unwrap()
+            .get();
+
+        // Flush memtable using flush_with_options
+        kv_store
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
+            .await
+            .unwrap();
+
+        // Wait for the flush to complete and manifest to be updated
+        let db_state = wait_for_manifest_condition(
+            &mut stored_manifest,
+            |s| s.l0.len() > initial_l0_count,
+            Duration::from_secs(30),
+        )
+        .await;
+
+        // Verify that a new SST was created in L0
+        assert_eq!(db_state.l0.len(), initial_l0_count + 1);
+
+        // Verify that the flush metrics were updated
+        let final_flush_count = kv_store
+            .metrics()
+            .lookup(IMMUTABLE_MEMTABLE_FLUSHES)
+            .unwrap()
+            .get();
+        assert!(final_flush_count > initial_flush_count);
+
+        // Verify that the WAL has not been flushed
+        let recent_flushed_wal_id = kv_store.inner.wal_buffer.recent_flushed_wal_id();
+        assert_eq!(recent_flushed_wal_id, 0);
+
+        // Verify that the data is still accessible after flush
+        let retrieved_value1 = kv_store.get(key1).await.unwrap().unwrap();
+        assert_eq!(retrieved_value1.as_ref(), value1);
+
+        let retrieved_value2 = kv_store.get(key2).await.unwrap().unwrap();
+        assert_eq!(retrieved_value2.as_ref(), value2);
+
+        // Verify the data exists in the newly created SST
+        let latest_sst = db_state.l0.back().unwrap();
+        let sst_iter_options = SstIteratorOptions::default();
+        let mut iter = SstIterator::new_borrowed_initialized(
+            ..,
+            latest_sst,
+            table_store.clone(),
+            sst_iter_options,
+        )
+        .await
+        .unwrap()
+        .expect("Expected Some(iter) but got None");
+
+        // Collect all key-value pairs from the SST
+        let mut found_keys = std::collections::HashSet::new();
+        while let Some(kv) = iter.next().await.unwrap() {
+            found_keys.insert(kv.key.to_vec());
+        }
+
+        // Verify our keys are in the SST
+        assert!(found_keys.contains(key1.as_slice()));
+        assert!(found_keys.contains(key2.as_slice()));
+    }
+
+    #[cfg(feature = "test-util")]
+    async fn test_sequence_tracker_persisted_across_flush_and_reload_impl(wal_enabled: bool) {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_sequence_tracker_flush";
+        let mut settings = test_db_options(0, 256, None);
+        settings.flush_interval = None;
+        #[cfg(feature = "wal_disable")]
+        {
+            settings.wal_enabled = wal_enabled;
+        }
+        #[cfg(not(feature = "wal_disable"))]
+        let _ = wal_enabled;
+        let logical_clock = Arc::new(TestClock::new());
+        let system_clock = Arc::new(MockSystemClock::new());
+
+        let kv_store = Db::builder(path, object_store.clone())
+            .with_settings(settings.clone())
+            .with_system_clock(system_clock.clone())
+            .with_logical_clock(logical_clock.clone())
+            .build()
+            .await
+            .unwrap();
+
+        let manifest_store = Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        ));
+        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
+        let write_options = WriteOptions {
+            await_durable: false,
+            ..WriteOptions::default()
+        };
+        let put_options = PutOptions::default();
+
+        let timestamps_ms = [0_i64, 60_000, 120_000];
+        for (idx, ts) in timestamps_ms.iter().enumerate() {
+            logical_clock.ticker.store(*ts, Ordering::SeqCst);
+            system_clock.set(*ts);
+            let key = format!("key-{idx}").into_bytes();
+            let value = format!("value-{idx}").into_bytes();
+            kv_store
+                .put_with_options(&key, &value, &put_options, &write_options)
                 .await
-                .unwrap(),
+                .unwrap();
+        }
+
+        kv_store
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
+            .await
+            .unwrap();
+
+        let target_ts = Utc.timestamp_opt(120, 0).single().unwrap();
+        let persisted_state = wait_for_manifest_condition(
+            &mut stored_manifest,
+            move |core| {
+                core.sequence_tracker
+                    .find_seq(target_ts, FindOption::RoundDown)
+                    == Some(3)
+            },
+            Duration::from_secs(5),
+        )
+        .await;
+
+        let tracker = persisted_state.sequence_tracker.clone();
+        let live_tracker = kv_store
+            .inner
+            .state
+            .read()
+            .state()
+            .core()
+            .sequence_tracker
+            .clone();
+        assert_eq!(tracker, live_tracker);
+
+        let seq1_ts = tracker.find_ts(1, FindOption::RoundDown).unwrap();
+        assert_eq!(seq1_ts.timestamp(), 0);
+
+        let seq2_ts = tracker.find_ts(2, FindOption::RoundDown).unwrap();
+        assert_eq!(seq2_ts.timestamp(), 60);
+
+        let seq3_ts = tracker.find_ts(3, FindOption::RoundDown).unwrap();
+        assert_eq!(seq3_ts.timestamp(), 120);
+
+        let ts_lookup = Utc.timestamp_opt(60, 0).single().unwrap();
+        assert_eq!(tracker.find_seq(ts_lookup, FindOption::RoundDown), Some(2));
+
+        kv_store.close().await.unwrap();
+
+        let reopened = Db::builder(path, object_store.clone())
+            .with_settings(settings)
+            .with_system_clock(system_clock.clone())
+            .with_logical_clock(logical_clock.clone())
+            .build()
+            .await
+            .unwrap();
+
+        let reopened_tracker = reopened
+            .inner
+            .state
+            .read()
+            .state()
+            .core()
+            .sequence_tracker
+            .clone();
+        assert_eq!(tracker, reopened_tracker);
+
+        reopened.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    #[cfg(feature = "test-util")]
+    async fn test_sequence_tracker_persisted_across_flush_and_reload_wal_enabled() {
+        test_sequence_tracker_persisted_across_flush_and_reload_impl(true).await;
+    }
+
+    #[tokio::test]
+    #[cfg(all(feature = "test-util", feature = "wal_disable"))]
+    async fn test_sequence_tracker_persisted_across_flush_and_reload_wal_disabled() {
+        test_sequence_tracker_persisted_across_flush_and_reload_impl(false).await;
+    }
+
+    #[cfg(feature = "test-util")]
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_sequence_tracker_not_ahead_of_last_l0_seq_when_flush_races_with_writes() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut settings = test_db_options(0, 512, None);
+        settings.flush_interval = None;
+
+        let system_clock = Arc::new(MockSystemClock::new());
+        let db = Db::builder(
+            "/tmp/test_sequence_tracker_flush_race",
+            object_store.clone(),
+        )
+        .with_settings(settings)
+        .with_system_clock(system_clock.clone())
+        .with_fp_registry(fp_registry.clone())
+        .build()
+        .await
+        .unwrap();
+
+        let write_options = WriteOptions {
+            await_durable: false,
+            ..WriteOptions::default()
+        };
+
+        async fn put_with_timestamp(
+            db: &Db,
+            system_clock: &Arc<MockSystemClock>,
+            idx: usize,
+            write_options: &WriteOptions,
+        ) {
+            system_clock.set((idx as i64) * 60_000);
+            let key = format!("race-key-{idx}").into_bytes();
+            let value = format!("race-value-{idx}").into_bytes();
+            db.put_with_options(&key, &value, &PutOptions::default(), write_options)
+                .await
+                .unwrap();
+        }
+
+        put_with_timestamp(&db, &system_clock, 0, &write_options).await;
+        put_with_timestamp(&db, &system_clock, 1, &write_options).await;
+
+        fail_parallel::cfg(fp_registry.clone(), "flush-memtable-to-l0", "pause").unwrap();
+
+        let flush_handle = {
+            let inner = Arc::clone(&db.inner);
+            tokio::spawn(async move { inner.flush_memtables().await })
+        };
+
+        let mut froze_memtable = false;
+        for _ in 0..200 {
+            {
+                let guard = db.inner.state.read();
+                if !guard.state().imm_memtable.is_empty() {
+                    froze_memtable = true;
+                    break;
+                }
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+        assert!(froze_memtable, "memtable was not frozen before flush");
+
+        put_with_timestamp(&db, &system_clock, 2, &write_options).await;
+        put_with_timestamp(&db, &system_clock, 3, &write_options).await;
+
+        fail_parallel::cfg(fp_registry.clone(), "flush-memtable-to-l0", "off").unwrap();
+
+        flush_handle.await.unwrap().unwrap();
+
+        {
+            let guard = db.inner.state.read();
+            assert!(guard.state().imm_memtable.is_empty());
+        }
+
+        let manifest_state = {
+            let guard = db.inner.state.read();
+            guard.state().manifest.core().clone()
+        };
+        let last_l0_seq = manifest_state.last_l0_seq;
+        assert!(
+            last_l0_seq >= 2,
+            "expected flushed memtable to advance last_l0_seq"
+        );
+
+        assert!(
+            manifest_state
+                .sequence_tracker
+                .find_ts(last_l0_seq + 1, FindOption::RoundUp)
+                .is_none(),
+            "sequence tracker should not advance beyond last_l0_seq (last_l0_seq={})",
+            last_l0_seq
+        );
+
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_flush_with_options_wal() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_flush_with_options_wal";
+        let mut options = test_db_options(0, 1024, None);
+        // Larger memtable to avoid memtable flushes
+        options.flush_interval = Some(Duration::from_secs(u64::MAX));
+        // Fail all memtable writes before the DB starts, so we can be sure that
+        // only the WAL is flushed.
+        fail_parallel::cfg(
+            fp_registry.clone(),
+            "write-compacted-sst-io-error",
+            "return",
+        )
+        .unwrap();
+        let kv_store = Db::builder(path, object_store.clone())
+            .with_settings(options)
+            .with_fp_registry(fp_registry.clone())
+            .build()
+            .await
+            .unwrap();
+
+        // Write some data to populate the WAL buffer
+        let key1 = b"wal_test_key_1";
+        let value1 = b"wal_test_value_1";
+        kv_store
+            .put_with_options(
+                key1,
+                value1,
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        let key2 = b"wal_test_key_2";
+        let value2 = b"wal_test_value_2";
+        kv_store
+            .put_with_options(
+                key2,
+                value2,
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+
+        // Get initial WAL ID to verify flush occurred
+        let initial_wal_id = kv_store.inner.wal_buffer.recent_flushed_wal_id();
+
+        // Flush WAL using flush_with_options - this should succeed without error
+        let flush_result = kv_store
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::Wal,
+            })
+            .await;
+
+        // Verify the flush operation completed successfully
+        assert!(flush_result.is_ok(), "WAL flush should succeed");
+
+        // Verify that the data is still accessible after WAL flush
+        let retrieved_value1 = kv_store.get(key1).await.unwrap().unwrap();
+        assert_eq!(retrieved_value1.as_ref(), value1);
+
+        let retrieved_value2 = kv_store.get(key2).await.unwrap().unwrap();
+        assert_eq!(retrieved_value2.as_ref(), value2);
+
+        // Verify that the WAL buffer is in a consistent state after flush
+        // The recent_flushed_wal_id should be at least as high as before
+        let final_wal_id = kv_store.inner.wal_buffer.recent_flushed_wal_id();
+        assert!(
+            final_wal_id >= initial_wal_id,
+            "WAL ID should not decrease after flush"
         );
 
-        const NUM_KEYS: usize = 100;
-        const NUM_ROUNDS: usize = 20;
+        // Verify that the memtable has not been flushed by checking the db for error state
+        assert!(
+            kv_store.inner.check_closed().is_ok(),
+            "DB should not have an error state"
+        );
+    }
 
-        for _ in 0..NUM_ROUNDS {
-            // Write two tasks that write to the same keys
-            let task1 = {
-                let store = kv_store.clone();
-                tokio::spawn(async move {
-                    let mut batch = WriteBatch::new();
-                    for key in 1..=NUM_KEYS {
-                        batch.put(key.to_be_bytes(), key.to_be_bytes());
-                    }
-                    store.write(batch).await.expect("write batch failed");
-                })
-            };
+    #[tokio::test]
+    #[cfg(feature = "wal_disable")]
+    async fn test_flush_with_options_wal_disabled_error() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_flush_with_options_wal_disabled";
+        let mut options = test_db_options(0, 1024, None);
+        options.wal_enabled = false; // Disable WAL
+        let kv_store = Db::builder(path, object_store.clone())
+            .with_settings(options)
+            .build()
+            .await
+            .unwrap();
 
-            let task2 = {
-                let store = kv_store.clone();
-                tokio::spawn(async move {
-                    let mut batch = WriteBatch::new();
-                    for key in 1..=NUM_KEYS {
-                        let value = (key * 2).to_be_bytes();
-                        batch.put(key.to_be_bytes(), value);
-                    }
-                    store.write(batch).await.expect("write batch failed");
-                })
-            };
+        // Write some data to the database
+        let key1 = b"test_key_1";
+        let value1 = b"test_value_1";
+        kv_store
+            .put_with_options(
+                key1,
+                value1,
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
+            .await
+            .unwrap();
 
-            // Wait for both tasks to complete
-            join_all(vec![task1, task2]).await;
+        // Attempt to flush WAL on a WAL-disabled database
+        let flush_result = kv_store
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::Wal,
+            })
+            .await;
 
-            // Ensure consistency: all values must be either key or key * 2
-            let mut all_key = true;
-            let mut all_key2 = true;
+        // Verify that we get the WalDisabled error
+        assert!(flush_result.is_err(), "Expected WalDisabled error");
+        let error = flush_result.unwrap_err();
+
+        assert!(
+            error
+                .to_string()
+                .contains("attempted a WAL operation when the WAL is disabled"),
+            "Expected WalDisabled error message, got: {}",
+            error
+        );
+
+        // Verify that memtable flush still works when WAL is disabled
+        let memtable_flush_result = kv_store
+            .flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
+            .await;
+        assert!(
+            memtable_flush_result.is_ok(),
+            "Memtable flush should work even when WAL is disabled"
+        );
+
+        // Verify that the data is still accessible
+        let retrieved_value1 = kv_store.get(key1).await.unwrap().unwrap();
+        assert_eq!(retrieved_value1.as_ref(), value1);
+    }
+
+    // 2 threads so we can can wait on the write_with_options (main) thread
+    // while the write_batch (background) thread is blocked on writing the
+    // WAL SST.
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_apply_wal_memory_backpressure() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = Path::from("/tmp/test_kv_store");
+        let mut options = test_db_options(0, 1, None);
+        options.max_unflushed_bytes = 1;
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(options)
+            .with_fp_registry(fp_registry.clone())
+            .build()
+            .await
+            .unwrap();
+        let db_stats = db.inner.db_stats.clone();
+        let write_opts = WriteOptions {
+            await_durable: false,
+            ..WriteOptions::default()
+        };
+
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
+
+        // Helper function to wait for a condition to be true.
+        let wait_for = async move |condition: Box<dyn Fn() -> bool>| {
+            for _ in 0..3000 {
+                if condition() {
+                    return;
+                }
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+
+        // 1 wal entry in memory
+        db.put_with_options(b"key1", b"val1", &PutOptions::default(), &write_opts)
+            .await
+            .unwrap();
+
+        // Wait for put to end up in the WAL buffer
+        let this_wal_buffer = db.inner.wal_buffer.clone();
+        wait_for(Box::new(move || {
+            this_wal_buffer.buffered_wal_entries_count() > 0
+        }))
+        .await;
+
+        // Verify that there is now 1 WAL entry in memory.
+        assert_eq!(db.inner.wal_buffer.buffered_wal_entries_count(), 1);
+
+        // Put another WAL entry, which should trigger backpressure. Do this in a separate
+        // task since the put() is blocked until the WAL is flushed, which isn't happening
+        // due to the fail point.
+        let join_handle = tokio::spawn(async move {
+            db.put_with_options(b"key2", b"val2", &PutOptions::default(), &write_opts)
+                .await
+                .unwrap();
+        });
+
+        let this_stats = db_stats.clone();
+        // Wait up to 30s for backpressure to be applied to the second write.
+        wait_for(Box::new(move || {
+            this_stats.backpressure_count.value.load(Ordering::SeqCst) > 0
+        }))
+        .await;
+
+        // Verify that backpressure is applied.
+        assert!(db_stats.backpressure_count.value.load(Ordering::SeqCst) >= 1);
+
+        // Unblock so put_with_options in join_handle can complete and join_handle.await returns
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
+
+        // Shutdown the background task
+        join_handle.abort();
+        let _ = join_handle.await;
+    }
+
+    #[tokio::test]
+    async fn test_apply_backpressure_to_memtable_flush() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut options = test_db_options(0, 1, None);
+        options.l0_max_ssts = 4;
+        let db = Db::builder("/tmp/test_kv_store", object_store.clone())
+            .with_settings(options)
+            .build()
+            .await
+            .unwrap();
+        db.put(b"key1", b"val1").await.unwrap();
+        db.put(b"key2", b"val2").await.unwrap();
+        db.put(b"key3", b"val3").await.unwrap();
+        db.put(b"key4", b"val4").await.unwrap();
+        db.put(b"key5", b"val5").await.unwrap();
+
+        db.flush().await.unwrap();
+
+        let db_state = db.inner.state.read().view();
+        assert_eq!(db_state.state.imm_memtable.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_put_empty_value() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+        let key = b"test_key";
+        let value = b"";
+        kv_store.put(key, value).await.unwrap();
+        kv_store.flush().await.unwrap();
+
+        assert_eq!(
+            kv_store.get(key).await.unwrap(),
+            Some(Bytes::from_static(value))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_flush_while_iterating() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let kv_store = Db::builder("/tmp/test_kv_store", object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .with_logical_clock(Arc::new(TestClock::new()))
+            .build()
+            .await
+            .unwrap();
+
+        let memtable = {
+            let lock = kv_store.inner.state.read();
+            lock.memtable()
+                .put(RowEntry::new_value(b"abc1111", b"value1111", 1));
+            lock.memtable()
+                .put(RowEntry::new_value(b"abc2222", b"value2222", 2));
+            lock.memtable()
+                .put(RowEntry::new_value(b"abc3333", b"value3333", 3));
+            lock.memtable().table().clone()
+        };
 
-            for key in 1..=NUM_KEYS {
-                let value = kv_store.get(key.to_be_bytes()).await.unwrap();
-                let value = value.expect("Value should exist");
+        let mut iter = memtable.iter();
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key, b"abc1111".as_slice());
 
-                if value.as_ref() != key.to_be_bytes() {
-                    all_key = false;
-                }
-                if value.as_ref() != (key * 2).to_be_bytes() {
-                    all_key2 = false;
-                }
-            }
+        kv_store.flush().await.unwrap();
 
-            // Assert that the result is consistent: either all key or all key * 2
-            assert!(
-                all_key || all_key2,
-                "Inconsistent state: not all values match either key or key * 2"
-            );
-        }
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key, b"abc2222".as_slice());
 
-        kv_store.close().await.unwrap();
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key, b"abc3333".as_slice());
     }
 
     #[tokio::test]
-    #[cfg(feature = "wal_disable")]
-    async fn test_disable_wal_after_wal_enabled() {
+    async fn test_basic_restore() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
-        // open a db and write a wal entry
-        let options = test_db_options(0, 32, None);
-        let db = Db::builder(path, object_store.clone())
-            .with_settings(options)
+        let mut next_wal_id = 1;
+        let kv_store = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 128, None))
+            .with_logical_clock(Arc::new(TestClock::new()))
             .build()
             .await
             .unwrap();
-        db.put(&[b'a'; 4], &[b'j'; 4]).await.unwrap();
-        db.put(&[b'b'; 4], &[b'k'; 4]).await.unwrap();
-        db.close().await.unwrap();
+        // increment wal id for the empty wal
+        next_wal_id += 1;
 
-        // open a db with wal disabled and write a memtable
-        let mut options = test_db_options(0, 32, None);
-        options.wal_enabled = false;
-        let db = Db::builder(path, object_store.clone())
-            .with_settings(options.clone())
-            .build()
-            .await
-            .unwrap();
-        db.delete_with_options(
-            &[b'b'; 4],
-            &WriteOptions {
-                await_durable: false,
-            },
-        )
-        .await
-        .unwrap();
-        db.put(&[b'a'; 4], &[b'z'; 64]).await.unwrap();
-        db.close().await.unwrap();
+        // do a few writes that will result in l0 flushes
+        let l0_count: u64 = 3;
+        for i in 0..l0_count {
+            kv_store
+                .put(&[b'a' + i as u8; 16], &[b'b' + i as u8; 48])
+                .await
+                .unwrap();
+            kv_store
+                .put(&[b'j' + i as u8; 16], &[b'k' + i as u8; 48])
+                .await
+                .unwrap();
+            next_wal_id += 2;
+        }
 
-        // ensure we don't overwrite the values we just put on a reload
-        let db = Db::builder(path, object_store.clone())
-            .with_settings(options.clone())
-            .build()
-            .await
-            .unwrap();
-        let val = db.get(&[b'a'; 4]).await.unwrap();
-        assert_eq!(val.unwrap(), Bytes::copy_from_slice(&[b'z'; 64]));
-        let val = db.get(&[b'b'; 4]).await.unwrap();
-        assert!(val.is_none());
-    }
+        // write some smaller keys so that we populate wal without flushing to l0
+        let sst_count: u64 = 5;
+        for i in 0..sst_count {
+            kv_store
+                .put(&i.to_be_bytes(), &i.to_be_bytes())
+                .await
+                .unwrap();
+            kv_store.flush().await.unwrap();
+            next_wal_id += 1;
+        }
 
-    #[cfg(feature = "wal_disable")]
-    #[tokio::test]
-    async fn test_wal_disabled() {
-        use crate::{test_utils::assert_iterator, types::RowEntry};
+        kv_store.close().await.unwrap();
 
-        let clock = Arc::new(TestClock::new());
-        let mut options = test_db_options(0, 256, None);
-        options.wal_enabled = false;
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = Path::from("/tmp/test_kv_store");
-        let sst_format = SsTableFormat::default();
-        let table_store = Arc::new(TableStore::new(
-            ObjectStores::new(object_store.clone(), None),
-            sst_format,
-            path.clone(),
-            None,
-        ));
-        let db = Db::builder(path.clone(), object_store.clone())
-            .with_settings(options)
-            .with_logical_clock(clock.clone())
+        // recover and validate that sst files are loaded on recovery.
+        let kv_store_restored = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 128, None))
+            .with_logical_clock(Arc::new(TestClock::new()))
             .build()
             .await
             .unwrap();
+        // increment wal id for the empty wal
+        next_wal_id += 1;
+
+        for i in 0..l0_count {
+            let val = kv_store_restored.get([b'a' + i as u8; 16]).await.unwrap();
+            assert_eq!(val, Some(Bytes::copy_from_slice(&[b'b' + i as u8; 48])));
+            let val = kv_store_restored.get([b'j' + i as u8; 16]).await.unwrap();
+            assert_eq!(val, Some(Bytes::copy_from_slice(&[b'k' + i as u8; 48])));
+        }
+        for i in 0..sst_count {
+            let val = kv_store_restored.get(i.to_be_bytes()).await.unwrap();
+            assert_eq!(val, Some(Bytes::copy_from_slice(&i.to_be_bytes())));
+        }
+        kv_store_restored.close().await.unwrap();
+
+        // validate that the manifest file exists.
         let manifest_store = Arc::new(ManifestStore::new(
-            &path,
+            &Path::from(path),
             object_store.clone(),
             Arc::new(DefaultSystemClock::new()),
         ));
-        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
-        let write_options = WriteOptions {
-            await_durable: false,
-        };
+        let stored_manifest = StoredManifest::load(manifest_store).await.unwrap();
+        let db_state = stored_manifest.db_state();
+        assert_eq!(db_state.next_wal_sst_id, next_wal_id);
+    }
 
-        db.put_with_options(
-            &[b'a'; 32],
-            &[b'j'; 32],
-            &PutOptions::default(),
-            &write_options,
-        )
-        .await
-        .unwrap();
-        db.delete_with_options(&[b'b'; 31], &write_options)
+    #[tokio::test]
+    #[allow(clippy::await_holding_lock)]
+    async fn test_restore_seq_number() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 256, None))
+            .with_logical_clock(Arc::new(TestClock::new()))
+            .build()
             .await
             .unwrap();
 
-        // ensure the memtable's size is greater than l0_sst_size_bytes, or
-        // the memtable will not be flushed to l0, and the test will hang
-        // at this put_with_options call.
-        let write_options = WriteOptions {
-            await_durable: true,
-        };
-        clock.ticker.store(10, Ordering::SeqCst);
-        db.put_with_options(
-            &[b'c'; 32],
-            &[b'l'; 32],
-            &PutOptions::default(),
-            &write_options,
-        )
-        .await
-        .unwrap();
+        db.put(b"key1", b"val1").await.unwrap();
+        db.put(b"key2", b"val2").await.unwrap();
+        db.put(b"key3", b"val3").await.unwrap();
+        db.flush().await.unwrap();
+        db.close().await.unwrap();
 
-        let state = wait_for_manifest_condition(
-            &mut stored_manifest,
-            |s| !s.l0.is_empty(),
-            Duration::from_secs(30),
-        )
-        .await;
-        assert_eq!(state.l0.len(), 1);
+        let db_restored = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 256, None))
+            .with_logical_clock(Arc::new(TestClock::new()))
+            .build()
+            .await
+            .unwrap();
 
-        let l0 = state.l0.front().unwrap();
-        let mut iter = SstIterator::new_borrowed_initialized(
-            ..,
-            l0,
-            table_store.clone(),
-            SstIteratorOptions::default(),
-        )
-        .await
-        .unwrap()
-        .expect("Expected Some(iter) but got None");
+        let state = db_restored.inner.state.read();
+        let memtable = state.memtable();
+        let mut iter = memtable.table().iter();
         assert_iterator(
             &mut iter,
             vec![
-                RowEntry::new_value(&[b'a'; 32], &[b'j'; 32], 1).with_create_ts(0),
-                RowEntry::new_tombstone(&[b'b'; 31], 2).with_create_ts(0),
-                RowEntry::new_value(&[b'c'; 32], &[b'l'; 32], 3).with_create_ts(10),
+                RowEntry::new_value(b"key1", b"val1", 1).with_create_ts(0),
+                RowEntry::new_value(b"key2", b"val2", 2).with_create_ts(0),
+                RowEntry::new_value(b"key3", b"val3", 3).with_create_ts(0),
             ],
         )
         .await;
     }
 
     #[tokio::test]
-    async fn test_put_flushes_memtable() {
+    async fn test_all_kv_seq_num_are_greater_than_0() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_kv_store";
-        let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 256, None))
+        let path = "/tmp/test_kv_store_seq_num";
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024 * 1024, None))
             .build()
             .await
-            .unwrap();
-        let clock = Arc::new(DefaultSystemClock::new());
-        let manifest_store = Arc::new(ManifestStore::new(
-            &Path::from(path),
-            object_store.clone(),
-            clock.clone(),
-        ));
-        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
-        let sst_format = SsTableFormat {
-            min_filter_keys: 10,
-            ..SsTableFormat::default()
-        };
-        let table_store = Arc::new(TableStore::new(
-            ObjectStores::new(object_store.clone(), None),
-            sst_format,
-            path,
-            None,
-        ));
-
-        // Write data a few times such that each loop results in a memtable flush
-        let mut last_wal_id = 0;
-        for i in 0..3 {
-            let key = [b'a' + i; 16];
-            let value = [b'b' + i; 50];
-            kv_store.put(&key, &value).await.unwrap();
-            let key = [b'j' + i; 16];
-            let value = [b'k' + i; 50];
-            kv_store.put(&key, &value).await.unwrap();
-            let db_state = wait_for_manifest_condition(
-                &mut stored_manifest,
-                |s| s.replay_after_wal_id > last_wal_id,
-                Duration::from_secs(30),
-            )
-            .await;
+            .unwrap();
 
-            // 2 wal per iteration.
-            assert_eq!(db_state.replay_after_wal_id, (i as u64) * 2 + 2);
-            last_wal_id = db_state.replay_after_wal_id
-        }
+        // Write some data to memtable
+        db.put(b"key1", b"value1").await.unwrap();
 
-        let manifest = stored_manifest.refresh().await.unwrap();
-        let l0 = &manifest.core.l0;
-        assert_eq!(l0.len(), 3);
-        let sst_iter_options = SstIteratorOptions::default();
+        let val = db.get(b"key1").await.unwrap();
+        assert_eq!(val, Some(Bytes::from_static(b"value1")));
 
-        for i in 0u8..3u8 {
-            let sst1 = l0.get(2 - i as usize).unwrap();
-            let mut iter = SstIterator::new_borrowed_initialized(
-                ..,
-                sst1,
-                table_store.clone(),
-                sst_iter_options,
-            )
-            .await
-            .unwrap()
-            .expect("Expected Some(iter) but got None");
-            let kv = iter.next().await.unwrap().unwrap();
-            assert_eq!(kv.key.as_ref(), [b'a' + i; 16]);
-            assert_eq!(kv.value.as_ref(), [b'b' + i; 50]);
-            let kv = iter.next().await.unwrap().unwrap();
-            assert_eq!(kv.key.as_ref(), [b'j' + i; 16]);
-            assert_eq!(kv.value.as_ref(), [b'k' + i; 50]);
-            let kv = iter.next().await.unwrap();
-            assert!(kv.is_none());
-        }
-        assert!(
-            kv_store
-                .metrics()
-                .lookup(IMMUTABLE_MEMTABLE_FLUSHES)
-                .unwrap()
-                .get()
-                > 0
-        );
+        let state = db.inner.state.read();
+        let memtable = state.memtable();
+        assert_eq!(memtable.table().last_seq(), Some(1));
     }
 
-    #[tokio::test]
-    async fn test_flush_memtable_with_wal_enabled() {
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_should_read_uncommitted_data_if_read_level_uncommitted() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_flush_with_options";
-        let mut options = test_db_options(0, 256, None);
-        options.flush_interval = Some(Duration::from_secs(u64::MAX));
+        let path = "/tmp/test_kv_store";
         let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(options)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
 
-        let manifest_store = Arc::new(ManifestStore::new(
-            &Path::from(path),
-            object_store.clone(),
-            Arc::new(DefaultSystemClock::new()),
-        ));
-        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
-        let sst_format = SsTableFormat {
-            min_filter_keys: 10,
-            ..SsTableFormat::default()
-        };
-        let table_store = Arc::new(TableStore::new(
-            ObjectStores::new(object_store.clone(), None),
-            sst_format,
-            path,
-            None,
-        ));
-
-        // Write some data to populate the memtable
-        let key1 = b"test_key_1";
-        let value1 = b"test_value_1";
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
         kv_store
             .put_with_options(
-                key1,
-                value1,
+                "foo".as_bytes(),
+                "bar".as_bytes(),
                 &PutOptions::default(),
                 &WriteOptions {
                     await_durable: false,
+                    ..WriteOptions::default()
                 },
             )
             .await
             .unwrap();
 
-        let key2 = b"test_key_2";
-        let value2 = b"test_value_2";
-        kv_store
-            .put_with_options(
-                key2,
-                value2,
-                &PutOptions::default(),
-                &WriteOptions {
-                    await_durable: false,
-                },
+        // Validate uncommitted read
+        let val = kv_store
+            .get_with_options(
+                "foo".as_bytes(),
+                &ReadOptions::new().with_durability_filter(Memory),
             )
             .await
             .unwrap();
+        assert_eq!(val, Some("bar".into()));
 
-        // Get initial state
-        let initial_manifest = stored_manifest.refresh().await.unwrap();
-        let initial_l0_count = initial_manifest.core.l0.len();
-
-        let initial_flush_count = kv_store
-            .metrics()
-            .lookup(IMMUTABLE_MEMTABLE_FLUSHES)
-            .unwrap()
-            .get();
-
-        // Flush memtable using flush_with_options
-        kv_store
-            .flush_with_options(FlushOptions {
-                flush_type: FlushType::MemTable,
-            })
+        // Validate committed read should still return None
+        let val = kv_store
+            .get_with_options(
+                "foo".as_bytes(),
+                &ReadOptions::new().with_durability_filter(Remote),
+            )
             .await
             .unwrap();
+        assert_eq!(val, None);
 
-        // Wait for the flush to complete and manifest to be updated
-        let db_state = wait_for_manifest_condition(
-            &mut stored_manifest,
-            |s| s.l0.len() > initial_l0_count,
-            Duration::from_secs(30),
-        )
-        .await;
-
-        // Verify that a new SST was created in L0
-        assert_eq!(db_state.l0.len(), initial_l0_count + 1);
-
-        // Verify that the flush metrics were updated
-        let final_flush_count = kv_store
-            .metrics()
-            .lookup(IMMUTABLE_MEMTABLE_FLUSHES)
-            .unwrap()
-            .get();
-        assert!(final_flush_count > initial_flush_count);
-
-        // Verify that the WAL has not been flushed
-        let recent_flushed_wal_id = kv_store.inner.wal_buffer.recent_flushed_wal_id();
-        assert_eq!(recent_flushed_wal_id, 0);
-
-        // Verify that the data is still accessible after flush
-        let retrieved_value1 = kv_store.get(key1).await.unwrap().unwrap();
-        assert_eq!(retrieved_value1.as_ref(), value1);
-
-        let retrieved_value2 = kv_store.get(key2).await.unwrap().unwrap();
-        assert_eq!(retrieved_value2.as_ref(), value2);
-
-        // Verify the data exists in the newly created SST
-        let latest_sst = db_state.l0.back().unwrap();
-        let sst_iter_options = SstIteratorOptions::default();
-        let mut iter = SstIterator::new_borrowed_initialized(
-            ..,
-            latest_sst,
-            table_store.clone(),
-            sst_iter_options,
-        )
-        .await
-        .unwrap()
-        .expect("Expected Some(iter) but got None");
-
-        // Collect all key-value pairs from the SST
-        let mut found_keys = std::collections::HashSet::new();
-        while let Some(kv) = iter.next().await.unwrap() {
-            found_keys.insert(kv.key.to_vec());
-        }
-
-        // Verify our keys are in the SST
-        assert!(found_keys.contains(key1.as_slice()));
-        assert!(found_keys.contains(key2.as_slice()));
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
+        kv_store.close().await.unwrap();
     }
 
-    #[cfg(feature = "test-util")]
-    async fn test_sequence_tracker_persisted_across_flush_and_reload_impl(wal_enabled: bool) {
+    #[tokio::test]
+    async fn test_get_many_across_memtable_and_l0() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_sequence_tracker_flush";
-        let mut settings = test_db_options(0, 256, None);
-        settings.flush_interval = None;
-        #[cfg(feature = "wal_disable")]
-        {
-            settings.wal_enabled = wal_enabled;
-        }
-        #[cfg(not(feature = "wal_disable"))]
-        let _ = wal_enabled;
-        let logical_clock = Arc::new(TestClock::new());
-        let system_clock = Arc::new(MockSystemClock::new());
-
-        let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(settings.clone())
-            .with_system_clock(system_clock.clone())
-            .with_logical_clock(logical_clock.clone())
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 256, None))
             .build()
             .await
             .unwrap();
+        let keys: Vec<Vec<u8>> = (0..32).map(|i| format!("key{i:02}").into_bytes()).collect();
+        for key in &keys {
+            db.put(key, b"flushed").await.unwrap();
+        }
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        assert!(db.inner.state.read().state().core().l0.len() > 1);
+        let snapshot = db.snapshot().await.unwrap();
+        db.put(&keys[3], b"memtable").await.unwrap();
+        db.delete(&keys[7]).await.unwrap();
+
+        let mut lookup: Vec<&[u8]> = keys.iter().rev().map(|key| key.as_slice()).collect();
+        lookup.push(b"missing");
+        lookup.push(&keys[3]);
+        let values = db.get_many(&lookup).await.unwrap();
+        for (key, value) in lookup.iter().zip(&values) {
+            assert_eq!(*value, db.get(key).await.unwrap());
+        }
+        assert_eq!(values[lookup.len() - 2], None);
+        assert_eq!(
+            values[lookup.len() - 1],
+            Some(Bytes::from_static(b"memtable"))
+        );
 
-        let manifest_store = Arc::new(ManifestStore::new(
-            &Path::from(path),
-            object_store.clone(),
-            Arc::new(DefaultSystemClock::new()),
-        ));
-        let mut stored_manifest = StoredManifest::load(manifest_store.clone()).await.unwrap();
-        let write_options = WriteOptions {
-            await_durable: false,
-        };
-        let put_options = PutOptions::default();
-
-        let timestamps_ms = [0_i64, 60_000, 120_000];
-        for (idx, ts) in timestamps_ms.iter().enumerate() {
-            logical_clock.ticker.store(*ts, Ordering::SeqCst);
-            system_clock.set(*ts);
-            let key = format!("key-{idx}").into_bytes();
-            let value = format!("value-{idx}").into_bytes();
-            kv_store
-                .put_with_options(&key, &value, &put_options, &write_options)
-                .await
-                .unwrap();
-        }
-
-        kv_store
-            .flush_with_options(FlushOptions {
-                flush_type: FlushType::MemTable,
-            })
-            .await
-            .unwrap();
-
-        let target_ts = Utc.timestamp_opt(120, 0).single().unwrap();
-        let persisted_state = wait_for_manifest_condition(
-            &mut stored_manifest,
-            move |core| {
-                core.sequence_tracker
-                    .find_seq(target_ts, FindOption::RoundDown)
-                    == Some(3)
-            },
-            Duration::from_secs(5),
-        )
-        .await;
-
-        let tracker = persisted_state.sequence_tracker.clone();
-        let live_tracker = kv_store
-            .inner
-            .state
-            .read()
-            .state()
-            .core()
-            .sequence_tracker
-            .clone();
-        assert_eq!(tracker, live_tracker);
-
-        let seq1_ts = tracker.find_ts(1, FindOption::RoundDown).unwrap();
-        assert_eq!(seq1_ts.timestamp(), 0);
-
-        let seq2_ts = tracker.find_ts(2, FindOption::RoundDown).unwrap();
-        assert_eq!(seq2_ts.timestamp(), 60);
-
-        let seq3_ts = tracker.find_ts(3, FindOption::RoundDown).unwrap();
-        assert_eq!(seq3_ts.timestamp(), 120);
-
-        let ts_lookup = Utc.timestamp_opt(60, 0).single().unwrap();
-        assert_eq!(tracker.find_seq(ts_lookup, FindOption::RoundDown), Some(2));
+        // snapshots and transactions read from their own view
+        let values = snapshot.get_many(&[&keys[3], &keys[7]]).await.unwrap();
+        assert_eq!(values, vec![Some("flushed".into()), Some("flushed".into())]);
+        let txn = db
+            .begin(IsolationLevel::SerializableSnapshot)
+            .await
+            .unwrap();
+        txn.put(&keys[7], b"txn").unwrap();
+        let values = txn.get_many(&[&keys[3], &keys[7]]).await.unwrap();
+        assert_eq!(values, vec![Some("memtable".into()), Some("txn".into())]);
+    }
 
-        kv_store.close().await.unwrap();
+    async fn write_ingest_sst(
+        object_store: &Arc<dyn ObjectStore>,
+        path: &str,
+        rows: &[(&[u8], Option<&[u8]>)],
+    ) -> Path {
+        let path = Path::from(path);
+        let mut writer = SstWriter::new(path.clone(), object_store.clone());
+        for (key, value) in rows {
+            match value {
+                Some(value) => writer.put(key, value).await.unwrap(),
+                None => writer.delete(key).await.unwrap(),
+            }
+        }
+        writer.close().await.unwrap();
+        path
+    }
 
-        let reopened = Db::builder(path, object_store.clone())
-            .with_settings(settings)
-            .with_system_clock(system_clock.clone())
-            .with_logical_clock(logical_clock.clone())
+    #[tokio::test]
+    async fn test_should_ingest_ssts() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
             .build()
             .await
             .unwrap();
+        db.put(b"key1", b"old").await.unwrap();
+        db.put(b"key2", b"old").await.unwrap();
+        let sst1 = write_ingest_sst(
+            &object_store,
+            "ingest/1.sst",
+            &[
+                (b"key1", Some(b"ingested")),
+                (b"key2", None),
+                (b"key3", Some(b"first")),
+            ],
+        )
+        .await;
+        let sst2 =
+            write_ingest_sst(&object_store, "ingest/2.sst", &[(b"key3", Some(b"second"))]).await;
 
-        let reopened_tracker = reopened
-            .inner
-            .state
-            .read()
-            .state()
-            .core()
-            .sequence_tracker
-            .clone();
-        assert_eq!(tracker, reopened_tracker);
+        db.ingest_ssts(&[sst1, sst2]).await.unwrap();
 
-        reopened.close().await.unwrap();
-    }
+        // the memtable with the older writes is flushed before the ingested SSTs
+        assert_eq!(db.inner.state.read().state().core().l0.len(), 3);
+        assert_eq!(
+            db.get(b"key1").await.unwrap(),
+            Some(Bytes::from_static(b"ingested"))
+        );
+        assert_eq!(db.get(b"key2").await.unwrap(), None);
+        assert_eq!(
+            db.get(b"key3").await.unwrap(),
+            Some(Bytes::from_static(b"second"))
+        );
 
-    #[tokio::test]
-    #[cfg(feature = "test-util")]
-    async fn test_sequence_tracker_persisted_across_flush_and_reload_wal_enabled() {
-        test_sequence_tracker_persisted_across_flush_and_reload_impl(true).await;
+        // writes after a restart must still be newer than the ingested rows
+        db.close().await.unwrap();
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get(b"key3").await.unwrap(),
+            Some(Bytes::from_static(b"second"))
+        );
+        db.put(b"key3", b"after").await.unwrap();
+        assert_eq!(
+            db.get(b"key3").await.unwrap(),
+            Some(Bytes::from_static(b"after"))
+        );
     }
 
     #[tokio::test]
-    #[cfg(all(feature = "test-util", feature = "wal_disable"))]
-    async fn test_sequence_tracker_persisted_across_flush_and_reload_wal_disabled() {
-        test_sequence_tracker_persisted_across_flush_and_reload_impl(false).await;
-    }
-
-    #[cfg(feature = "test-util")]
-    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_sequence_tracker_not_ahead_of_last_l0_seq_when_flush_races_with_writes() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
+    async fn test_should_fail_to_ingest_missing_sst() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let mut settings = test_db_options(0, 512, None);
-        settings.flush_interval = None;
+        let db = Db::builder("/tmp/test_kv_store", object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
 
-        let system_clock = Arc::new(MockSystemClock::new());
-        let db = Db::builder(
-            "/tmp/test_sequence_tracker_flush_race",
-            object_store.clone(),
-        )
-        .with_settings(settings)
-        .with_system_clock(system_clock.clone())
-        .with_fp_registry(fp_registry.clone())
-        .build()
-        .await
-        .unwrap();
+        let err = db
+            .ingest_ssts(&[Path::from("ingest/missing.sst")])
+            .await
+            .unwrap_err();
 
-        let write_options = WriteOptions {
-            await_durable: false,
-        };
+        assert_eq!(err.kind(), crate::ErrorKind::Invalid);
+    }
 
-        async fn put_with_timestamp(
-            db: &Db,
-            system_clock: &Arc<MockSystemClock>,
-            idx: usize,
-            write_options: &WriteOptions,
-        ) {
-            system_clock.set((idx as i64) * 60_000);
-            let key = format!("race-key-{idx}").into_bytes();
-            let value = format!("race-value-{idx}").into_bytes();
-            db.put_with_options(&key, &value, &PutOptions::default(), write_options)
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_should_reject_ingest_overlapping_newer_write() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        let db = Arc::new(
+            Db::builder("/tmp/test_kv_store", object_store.clone())
+                .with_settings(test_db_options(0, 1024, None))
+                .with_fp_registry(fp_registry.clone())
+                .build()
                 .await
-                .unwrap();
-        }
-
-        put_with_timestamp(&db, &system_clock, 0, &write_options).await;
-        put_with_timestamp(&db, &system_clock, 1, &write_options).await;
-
-        fail_parallel::cfg(fp_registry.clone(), "flush-memtable-to-l0", "pause").unwrap();
+                .unwrap(),
+        );
+        let sst = write_ingest_sst(
+            &object_store,
+            "ingest/1.sst",
+            &[(b"key1", Some(b"ingested")), (b"key3", Some(b"ingested"))],
+        )
+        .await;
 
-        let flush_handle = {
-            let inner = Arc::clone(&db.inner);
-            tokio::spawn(async move { inner.flush_memtables().await })
+        // write to the ingested range once the ingestion has reserved its seq
+        fail_parallel::cfg(fp_registry.clone(), "ingest-ssts-before-publish", "pause").unwrap();
+        let last_seq = db.inner.oracle.last_seq.load();
+        let ingest = {
+            let db = db.clone();
+            let sst = sst.clone();
+            tokio::spawn(async move { db.ingest_ssts(&[sst]).await })
         };
-
-        let mut froze_memtable = false;
-        for _ in 0..200 {
-            {
-                let guard = db.inner.state.read();
-                if !guard.state().imm_memtable.is_empty() {
-                    froze_memtable = true;
-                    break;
-                }
-            }
+        while db.inner.oracle.last_seq.load() == last_seq {
             tokio::time::sleep(Duration::from_millis(10)).await;
         }
-        assert!(froze_memtable, "memtable was not frozen before flush");
+        db.put(b"key2", b"newer").await.unwrap();
+        fail_parallel::cfg(fp_registry.clone(), "ingest-ssts-before-publish", "off").unwrap();
 
-        put_with_timestamp(&db, &system_clock, 2, &write_options).await;
-        put_with_timestamp(&db, &system_clock, 3, &write_options).await;
-
-        fail_parallel::cfg(fp_registry.clone(), "flush-memtable-to-l0", "off").unwrap();
-
-        flush_handle.await.unwrap().unwrap();
-
-        {
-            let guard = db.inner.state.read();
-            assert!(guard.state().imm_memtable.is_empty());
-        }
+        let err = ingest.await.unwrap().unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Precondition);
+        assert_eq!(db.get(b"key1").await.unwrap(), None);
 
-        let manifest_state = {
-            let guard = db.inner.state.read();
-            guard.state().manifest.core().clone()
-        };
-        let last_l0_seq = manifest_state.last_l0_seq;
-        assert!(
-            last_l0_seq >= 2,
-            "expected flushed memtable to advance last_l0_seq"
+        // the write is older than a new attempt, which succeeds
+        db.ingest_ssts(&[sst]).await.unwrap();
+        assert_eq!(
+            db.get(b"key1").await.unwrap(),
+            Some(Bytes::from_static(b"ingested"))
         );
-
-        assert!(
-            manifest_state
-                .sequence_tracker
-                .find_ts(last_l0_seq + 1, FindOption::RoundUp)
-                .is_none(),
-            "sequence tracker should not advance beyond last_l0_seq (last_l0_seq={})",
-            last_l0_seq
+        assert_eq!(
+            db.get(b"key2").await.unwrap(),
+            Some(Bytes::from_static(b"newer"))
         );
-
-        db.close().await.unwrap();
     }
 
-    #[tokio::test]
-    async fn test_flush_with_options_wal() {
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn test_should_read_only_committed_data() {
         let fp_registry = Arc::new(FailPointRegistry::new());
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_flush_with_options_wal";
-        let mut options = test_db_options(0, 1024, None);
-        // Larger memtable to avoid memtable flushes
-        options.flush_interval = Some(Duration::from_secs(u64::MAX));
-        // Fail all memtable writes before the DB starts, so we can be sure that
-        // only the WAL is flushed.
-        fail_parallel::cfg(
-            fp_registry.clone(),
-            "write-compacted-sst-io-error",
-            "return",
-        )
-        .unwrap();
+        let path = "/tmp/test_kv_store";
         let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(options)
+            .with_settings(test_db_options(0, 1024, None))
             .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
 
-        // Write some data to populate the WAL buffer
-        let key1 = b"wal_test_key_1";
-        let value1 = b"wal_test_value_1";
         kv_store
-            .put_with_options(
-                key1,
-                value1,
-                &PutOptions::default(),
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
+            .put("foo".as_bytes(), "bar".as_bytes())
             .await
             .unwrap();
-
-        let key2 = b"wal_test_key_2";
-        let value2 = b"wal_test_value_2";
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
         kv_store
             .put_with_options(
-                key2,
-                value2,
+                "foo".as_bytes(),
+                "bla".as_bytes(),
                 &PutOptions::default(),
                 &WriteOptions {
                     await_durable: false,
+                    ..WriteOptions::default()
                 },
             )
             .await
             .unwrap();
 
-        // Get initial WAL ID to verify flush occurred
-        let initial_wal_id = kv_store.inner.wal_buffer.recent_flushed_wal_id();
-
-        // Flush WAL using flush_with_options - this should succeed without error
-        let flush_result = kv_store
-            .flush_with_options(FlushOptions {
-                flush_type: FlushType::Wal,
-            })
-            .await;
-
-        // Verify the flush operation completed successfully
-        assert!(flush_result.is_ok(), "WAL flush should succeed");
-
-        // Verify that the data is still accessible after WAL flush
-        let retrieved_value1 = kv_store.get(key1).await.unwrap().unwrap();
-        assert_eq!(retrieved_value1.as_ref(), value1);
-
-        let retrieved_value2 = kv_store.get(key2).await.unwrap().unwrap();
-        assert_eq!(retrieved_value2.as_ref(), value2);
-
-        // Verify that the WAL buffer is in a consistent state after flush
-        // The recent_flushed_wal_id should be at least as high as before
-        let final_wal_id = kv_store.inner.wal_buffer.recent_flushed_wal_id();
-        assert!(
-            final_wal_id >= initial_wal_id,
-            "WAL ID should not decrease after flush"
-        );
+        let val = kv_store
+            .get_with_options(
+                "foo".as_bytes(),
+                &ReadOptions::new().with_durability_filter(Remote),
+            )
+            .await
+            .unwrap();
+        assert_eq!(val, Some("bar".into()));
+        let val = kv_store
+            .get_with_options(
+                "foo".as_bytes(),
+                &ReadOptions::new().with_durability_filter(Memory),
+            )
+            .await
+            .unwrap();
+        assert_eq!(val, Some("bla".into()));
 
-        // Verify that the memtable has not been flushed by checking the db for error state
-        assert!(
-            kv_store.inner.check_closed().is_ok(),
-            "DB should not have an error state"
-        );
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
+        kv_store.close().await.unwrap();
     }
 
     #[tokio::test]
-    #[cfg(feature = "wal_disable")]
-    async fn test_flush_with_options_wal_disabled_error() {
+    async fn test_should_delete_without_awaiting_flush() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_flush_with_options_wal_disabled";
-        let mut options = test_db_options(0, 1024, None);
-        options.wal_enabled = false; // Disable WAL
+        let path = "/tmp/test_kv_store";
         let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(options)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
 
-        // Write some data to the database
-        let key1 = b"test_key_1";
-        let value1 = b"test_value_1";
         kv_store
-            .put_with_options(
-                key1,
-                value1,
-                &PutOptions::default(),
+            .put("foo".as_bytes(), "bar".as_bytes())
+            .await
+            .unwrap();
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
+        kv_store
+            .delete_with_options(
+                "foo".as_bytes(),
                 &WriteOptions {
                     await_durable: false,
+                    ..WriteOptions::default()
                 },
             )
             .await
             .unwrap();
 
-        // Attempt to flush WAL on a WAL-disabled database
-        let flush_result = kv_store
-            .flush_with_options(FlushOptions {
-                flush_type: FlushType::Wal,
-            })
-            .await;
-
-        // Verify that we get the WalDisabled error
-        assert!(flush_result.is_err(), "Expected WalDisabled error");
-        let error = flush_result.unwrap_err();
-
-        assert!(
-            error
-                .to_string()
-                .contains("attempted a WAL operation when the WAL is disabled"),
-            "Expected WalDisabled error message, got: {}",
-            error
-        );
-
-        // Verify that memtable flush still works when WAL is disabled
-        let memtable_flush_result = kv_store
-            .flush_with_options(FlushOptions {
-                flush_type: FlushType::MemTable,
-            })
-            .await;
-        assert!(
-            memtable_flush_result.is_ok(),
-            "Memtable flush should work even when WAL is disabled"
-        );
-
-        // Verify that the data is still accessible
-        let retrieved_value1 = kv_store.get(key1).await.unwrap().unwrap();
-        assert_eq!(retrieved_value1.as_ref(), value1);
-    }
-
-    // 2 threads so we can can wait on the write_with_options (main) thread
-    // while the write_batch (background) thread is blocked on writing the
-    // WAL SST.
-    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_apply_wal_memory_backpressure() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = Path::from("/tmp/test_kv_store");
-        let mut options = test_db_options(0, 1, None);
-        options.max_unflushed_bytes = 1;
-        let db = Db::builder(path, object_store.clone())
-            .with_settings(options)
-            .with_fp_registry(fp_registry.clone())
-            .build()
+        let val = kv_store
+            .get_with_options(
+                "foo".as_bytes(),
+                &ReadOptions::new().with_durability_filter(Remote),
+            )
             .await
             .unwrap();
-        let db_stats = db.inner.db_stats.clone();
-        let write_opts = WriteOptions {
-            await_durable: false,
-        };
-
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
-
-        // Helper function to wait for a condition to be true.
-        let wait_for = async move |condition: Box<dyn Fn() -> bool>| {
-            for _ in 0..3000 {
-                if condition() {
-                    return;
-                }
-                tokio::time::sleep(Duration::from_millis(10)).await;
-            }
-        };
-
-        // 1 wal entry in memory
-        db.put_with_options(b"key1", b"val1", &PutOptions::default(), &write_opts)
+        assert_eq!(val, Some("bar".into()));
+        let val = kv_store
+            .get_with_options(
+                "foo".as_bytes(),
+                &ReadOptions::new().with_durability_filter(Memory),
+            )
             .await
             .unwrap();
+        assert_eq!(val, None);
 
-        // Wait for put to end up in the WAL buffer
-        let this_wal_buffer = db.inner.wal_buffer.clone();
-        wait_for(Box::new(move || {
-            this_wal_buffer.buffered_wal_entries_count() > 0
-        }))
-        .await;
-
-        // Verify that there is now 1 WAL entry in memory.
-        assert_eq!(db.inner.wal_buffer.buffered_wal_entries_count(), 1);
-
-        // Put another WAL entry, which should trigger backpressure. Do this in a separate
-        // task since the put() is blocked until the WAL is flushed, which isn't happening
-        // due to the fail point.
-        let join_handle = tokio::spawn(async move {
-            db.put_with_options(b"key2", b"val2", &PutOptions::default(), &write_opts)
-                .await
-                .unwrap();
-        });
-
-        let this_stats = db_stats.clone();
-        // Wait up to 30s for backpressure to be applied to the second write.
-        wait_for(Box::new(move || {
-            this_stats.backpressure_count.value.load(Ordering::SeqCst) > 0
-        }))
-        .await;
-
-        // Verify that backpressure is applied.
-        assert!(db_stats.backpressure_count.value.load(Ordering::SeqCst) >= 1);
-
-        // Unblock so put_with_options in join_handle can complete and join_handle.await returns
         fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
-
-        // Shutdown the background task
-        join_handle.abort();
-        let _ = join_handle.await;
+        kv_store.close().await.unwrap();
     }
 
     #[tokio::test]
-    async fn test_apply_backpressure_to_memtable_flush() {
+    async fn test_scan_should_read_only_committed_data() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let mut options = test_db_options(0, 1, None);
-        options.l0_max_ssts = 4;
-        let db = Db::builder("/tmp/test_kv_store", object_store.clone())
-            .with_settings(options)
+        let path = "/tmp/test_kv_store";
+        let kv_store = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
-        db.put(b"key1", b"val1").await.unwrap();
-        db.put(b"key2", b"val2").await.unwrap();
-        db.put(b"key3", b"val3").await.unwrap();
-        db.put(b"key4", b"val4").await.unwrap();
-        db.put(b"key5", b"val5").await.unwrap();
 
-        db.flush().await.unwrap();
+        // Write and commit some initial data
+        kv_store
+            .put("key1".as_bytes(), "committed1".as_bytes())
+            .await
+            .unwrap();
+        kv_store
+            .put("key2".as_bytes(), "committed2".as_bytes())
+            .await
+            .unwrap();
+        kv_store
+            .put("key3".as_bytes(), "committed3".as_bytes())
+            .await
+            .unwrap();
 
-        let db_state = db.inner.state.read().view();
-        assert_eq!(db_state.state.imm_memtable.len(), 1);
-    }
+        // Pause WAL writes to prevent new writes from being committed
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
 
-    #[tokio::test]
-    async fn test_put_empty_value() {
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let kv_store = Db::builder("/tmp/test_kv_store", object_store.clone())
-            .with_settings(test_db_options(0, 1024, None))
-            .build()
+        // Write uncommitted data
+        kv_store
+            .put_with_options(
+                "key2".as_bytes(),
+                "uncommitted2".as_bytes(),
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
+            .await
+            .unwrap();
+        kv_store
+            .put_with_options(
+                "key4".as_bytes(),
+                "uncommitted4".as_bytes(),
+                &PutOptions::default(),
+                &WriteOptions {
+                    await_durable: false,
+                    ..WriteOptions::default()
+                },
+            )
             .await
             .unwrap();
-        let key = b"test_key";
-        let value = b"";
-        kv_store.put(key, value).await.unwrap();
-        kv_store.flush().await.unwrap();
-
-        assert_eq!(
-            kv_store.get(key).await.unwrap(),
-            Some(Bytes::from_static(value))
-        );
-    }
 
-    #[tokio::test]
-    async fn test_flush_while_iterating() {
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let kv_store = Db::builder("/tmp/test_kv_store", object_store.clone())
-            .with_settings(test_db_options(0, 1024, None))
-            .with_logical_clock(Arc::new(TestClock::new()))
-            .build()
+        // Scan with Remote filter should only see committed data
+        let mut iter = kv_store
+            .scan_with_options(
+                "key1".as_bytes().."key5".as_bytes(),
+                &ScanOptions::new().with_durability_filter(Remote),
+            )
             .await
             .unwrap();
 
-        let memtable = {
-            let lock = kv_store.inner.state.read();
-            lock.memtable()
-                .put(RowEntry::new_value(b"abc1111", b"value1111", 1));
-            lock.memtable()
-                .put(RowEntry::new_value(b"abc2222", b"value2222", 2));
-            lock.memtable()
-                .put(RowEntry::new_value(b"abc3333", b"value3333", 3));
-            lock.memtable().table().clone()
-        };
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key.as_ref(), b"key1");
+        assert_eq!(kv.value.as_ref(), b"committed1");
+
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key.as_ref(), b"key2");
+        assert_eq!(kv.value.as_ref(), b"committed2"); // Old committed value
+
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key.as_ref(), b"key3");
+        assert_eq!(kv.value.as_ref(), b"committed3");
+
+        // key4 should not be visible with Remote filter
+        assert_eq!(iter.next().await.unwrap(), None);
+
+        // Scan with Memory filter should see uncommitted data
+        let mut iter = kv_store
+            .scan_with_options(
+                "key1".as_bytes().."key5".as_bytes(),
+                &ScanOptions::new().with_durability_filter(Memory),
+            )
+            .await
+            .unwrap();
 
-        let mut iter = memtable.iter();
         let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key, b"abc1111".as_slice());
+        assert_eq!(kv.key.as_ref(), b"key1");
+        assert_eq!(kv.value.as_ref(), b"committed1");
 
-        kv_store.flush().await.unwrap();
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key.as_ref(), b"key2");
+        assert_eq!(kv.value.as_ref(), b"uncommitted2"); // New uncommitted value
 
         let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key, b"abc2222".as_slice());
+        assert_eq!(kv.key.as_ref(), b"key3");
+        assert_eq!(kv.value.as_ref(), b"committed3");
 
         let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key, b"abc3333".as_slice());
+        assert_eq!(kv.key.as_ref(), b"key4");
+        assert_eq!(kv.value.as_ref(), b"uncommitted4"); // Uncommitted key visible
+
+        assert_eq!(iter.next().await.unwrap(), None);
+
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
+        kv_store.close().await.unwrap();
     }
 
-    #[tokio::test]
-    async fn test_basic_restore() {
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_should_recover_imm_from_wal() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "pause").unwrap();
+
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
         let mut next_wal_id = 1;
-        let kv_store = Db::builder(path, object_store.clone())
+        let db = Db::builder(path, object_store.clone())
             .with_settings(test_db_options(0, 128, None))
-            .with_logical_clock(Arc::new(TestClock::new()))
+            .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
-        // increment wal id for the empty wal
         next_wal_id += 1;
 
-        // do a few writes that will result in l0 flushes
-        let l0_count: u64 = 3;
-        for i in 0..l0_count {
-            kv_store
-                .put(&[b'a' + i as u8; 16], &[b'b' + i as u8; 48])
-                .await
-                .unwrap();
-            kv_store
-                .put(&[b'j' + i as u8; 16], &[b'k' + i as u8; 48])
-                .await
-                .unwrap();
-            next_wal_id += 2;
-        }
-
-        // write some smaller keys so that we populate wal without flushing to l0
-        let sst_count: u64 = 5;
-        for i in 0..sst_count {
-            kv_store
-                .put(&i.to_be_bytes(), &i.to_be_bytes())
-                .await
-                .unwrap();
-            kv_store.flush().await.unwrap();
-            next_wal_id += 1;
-        }
-
-        kv_store.close().await.unwrap();
+        // write a few keys that will result in memtable flushes
+        let key1 = [b'a'; 32];
+        let value1 = [b'b'; 96];
+        db.put(key1, value1).await.unwrap();
+        next_wal_id += 1;
+        let key2 = [b'c'; 32];
+        let value2 = [b'd'; 96];
+        db.put(key2, value2).await.unwrap();
+        next_wal_id += 1;
 
-        // recover and validate that sst files are loaded on recovery.
-        let kv_store_restored = Db::builder(path, object_store.clone())
+        let reader = Db::builder(path, object_store.clone())
             .with_settings(test_db_options(0, 128, None))
-            .with_logical_clock(Arc::new(TestClock::new()))
+            .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
+
         // increment wal id for the empty wal
         next_wal_id += 1;
 
-        for i in 0..l0_count {
-            let val = kv_store_restored.get([b'a' + i as u8; 16]).await.unwrap();
-            assert_eq!(val, Some(Bytes::copy_from_slice(&[b'b' + i as u8; 48])));
-            let val = kv_store_restored.get([b'j' + i as u8; 16]).await.unwrap();
-            assert_eq!(val, Some(Bytes::copy_from_slice(&[b'k' + i as u8; 48])));
-        }
-        for i in 0..sst_count {
-            let val = kv_store_restored.get(i.to_be_bytes()).await.unwrap();
-            assert_eq!(val, Some(Bytes::copy_from_slice(&i.to_be_bytes())));
-        }
-        kv_store_restored.close().await.unwrap();
+        // verify that we reload imm
+        let db_state = reader.inner.state.read().view();
+        assert_eq!(db_state.state.imm_memtable.len(), 2);
 
-        // validate that the manifest file exists.
-        let manifest_store = Arc::new(ManifestStore::new(
-            &Path::from(path),
-            object_store.clone(),
-            Arc::new(DefaultSystemClock::new()),
-        ));
-        let stored_manifest = StoredManifest::load(manifest_store).await.unwrap();
-        let db_state = stored_manifest.db_state();
-        assert_eq!(db_state.next_wal_sst_id, next_wal_id);
+        // one empty wal and two wals for the puts
+        assert_eq!(
+            db_state
+                .state
+                .imm_memtable
+                .front()
+                .unwrap()
+                .recent_flushed_wal_id(),
+            1 + 2
+        );
+        assert_eq!(
+            db_state
+                .state
+                .imm_memtable
+                .get(1)
+                .unwrap()
+                .recent_flushed_wal_id(),
+            2
+        );
+        assert_eq!(db_state.state.core().next_wal_sst_id, next_wal_id);
+        assert_eq!(
+            reader.get(key1).await.unwrap(),
+            Some(Bytes::copy_from_slice(&value1))
+        );
+        assert_eq!(
+            reader.get(key2).await.unwrap(),
+            Some(Bytes::copy_from_slice(&value2))
+        );
+
+        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "off").unwrap();
+        db.close().await.unwrap();
+        reader.close().await.unwrap();
     }
 
-    #[tokio::test]
-    #[allow(clippy::await_holding_lock)]
-    async fn test_restore_seq_number() {
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_should_recover_imm_from_wal_after_flush_error() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        fail_parallel::cfg(
+            fp_registry.clone(),
+            "write-compacted-sst-io-error",
+            "return",
+        )
+        .unwrap();
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
         let db = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 256, None))
-            .with_logical_clock(Arc::new(TestClock::new()))
+            .with_settings(test_db_options(0, 4096, None))
+            .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
 
-        db.put(b"key1", b"val1").await.unwrap();
-        db.put(b"key2", b"val2").await.unwrap();
-        db.put(b"key3", b"val3").await.unwrap();
-        db.flush().await.unwrap();
+        // write data to the WAL, but not enough to trigger a memtable flush
+        let key1 = [b'a'; 32];
+        let value1 = [b'b'; 96];
+        let result = db.put(&key1, &value1).await;
+        assert!(result.is_ok(), "Failed to write key1");
+        assert_eq!(db.inner.wal_buffer.recent_flushed_wal_id(), 2);
+
+        // force a flush (even if the memtable is not full)
+        let flush_result = db.inner.flush_memtables().await;
+        assert!(flush_result.is_err());
         db.close().await.unwrap();
 
-        let db_restored = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 256, None))
-            .with_logical_clock(Arc::new(TestClock::new()))
+        // pause write-compacted-sst-io-error to prevent immutable tables
+        // from being flushed, so we can snapshot the state when there is
+        // an immutable table to verify its contents.
+        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "pause").unwrap();
+
+        // reload the db
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 128, None))
+            .with_fp_registry(fp_registry.clone())
             .build()
             .await
             .unwrap();
 
-        let state = db_restored.inner.state.read();
-        let memtable = state.memtable();
-        let mut iter = memtable.table().iter();
-        assert_iterator(
-            &mut iter,
-            vec![
-                RowEntry::new_value(b"key1", b"val1", 1).with_create_ts(0),
-                RowEntry::new_value(b"key2", b"val2", 2).with_create_ts(0),
-                RowEntry::new_value(b"key3", b"val3", 3).with_create_ts(0),
-            ],
-        )
-        .await;
+        let db_state = db.inner.state.read().view();
+
+        // resume write-compacted-sst-io-error since we got a snapshot and
+        // want to let the test finish.
+        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "off").unwrap();
+
+        // verify that we reload imm
+        assert_eq!(db_state.state.imm_memtable.len(), 1);
+
+        // verify that we have no L0 SSTs because memtables should have failed to flush
+        assert_eq!(db_state.state.core().l0.len(), 0);
+        assert_eq!(db_state.state.core().compacted.len(), 0);
+
+        // one empty wal and one wal for the first put
+        assert_eq!(
+            db_state
+                .state
+                .imm_memtable
+                .front()
+                .unwrap()
+                .recent_flushed_wal_id(),
+            1 + 1
+        );
+        assert!(db_state.state.imm_memtable.get(1).is_none());
+
+        assert_eq!(db_state.state.core().next_wal_sst_id, 4);
+        assert_eq!(
+            db.get(key1).await.unwrap(),
+            Some(Bytes::copy_from_slice(&value1))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_should_fail_write_if_wal_flush_task_panics() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let db = Arc::new(
+            Db::builder(path, object_store.clone())
+                .with_settings(test_db_options(0, 128, None))
+                .with_fp_registry(fp_registry.clone())
+                .build()
+                .await
+                .unwrap(),
+        );
+
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "panic").unwrap();
+        let result = db.put(b"foo", b"bar").await.unwrap_err();
+        assert!(result.to_string().contains("background task panicked"));
     }
 
-    #[tokio::test]
-    async fn test_all_kv_seq_num_are_greater_than_0() {
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_kv_store_seq_num";
-        let db = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 1024 * 1024, None))
-            .build()
-            .await
-            .unwrap();
+    #[tokio::test]
+    async fn test_wal_id_last_seen_should_exist_even_if_wal_write_fails() {
+        let fp_registry = Arc::new(FailPointRegistry::new());
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let db = Arc::new(
+            Db::builder(path, object_store.clone())
+                .with_settings(test_db_options(0, 128, None))
+                .with_fp_registry(fp_registry.clone())
+                .build()
+                .await
+                .unwrap(),
+        );
+
+        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "panic").unwrap();
+
+        // Trigger a WAL write, which should not advance the manifest WAL ID
+        let result = db.put(b"foo", b"bar").await.unwrap_err();
+        assert_eq!(result.kind(), crate::ErrorKind::Closed(CloseReason::Panic));
+        assert!(result
+            .to_string()
+            .contains("background task panicked. name=`wal_writer`"));
+
+        // Close, which flushes the latest manifest to the object store
+        // TODO: it might make sense to return an error if there're unflushed wals in memory
+        // on close().
+        db.close().await.unwrap();
+
+        let manifest_store = ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        );
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(object_store.clone(), None),
+            SsTableFormat::default(),
+            path,
+            None,
+        ));
 
-        // Write some data to memtable
-        db.put(b"key1", b"value1").await.unwrap();
+        // Get the next WAL SST ID based on what's currently in the object store
+        let next_wal_sst_id = table_store.next_wal_sst_id(0).await.unwrap();
 
-        let val = db.get(b"key1").await.unwrap();
-        assert_eq!(val, Some(Bytes::from_static(b"value1")));
+        // Get the latest manifest
+        let (_, manifest) = manifest_store.read_latest_manifest().await.unwrap();
 
-        let state = db.inner.state.read();
-        let memtable = state.memtable();
-        assert_eq!(memtable.table().last_seq(), Some(1));
+        // It's possible that there exists buffered multiple wals in memory, so the next_wal_sst_id
+        // in manifest is greater than the next_wal_sst_id based on what's currently in the object
+        // store unless ALL the wals are flushed.
+        assert!(manifest.core.next_wal_sst_id > next_wal_sst_id);
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
-    async fn test_should_read_uncommitted_data_if_read_level_uncommitted() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
+    async fn do_test_should_read_compacted_db(options: Settings) {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
-        let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 1024, None))
-            .with_fp_registry(fp_registry.clone())
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |_state| this_should_compact_l0.swap(false, Ordering::SeqCst),
+        )));
+
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(options)
+            .with_compaction_scheduler_supplier(compaction_scheduler.clone())
             .build()
             .await
             .unwrap();
+        let ms = ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        );
+        let mut sm = StoredManifest::load(Arc::new(ms)).await.unwrap();
 
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
-        kv_store
-            .put_with_options(
-                "foo".as_bytes(),
-                "bar".as_bytes(),
-                &PutOptions::default(),
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
-            .await
-            .unwrap();
+        // write enough to fill up a few l0 SSTs
+        for i in 0..4 {
+            db.put(&[b'a' + i; 32], &[1u8 + i; 32]).await.unwrap();
+            db.put(&[b'm' + i; 32], &[13u8 + i; 32]).await.unwrap();
+        }
+        // wait for compactor to compact them
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| {
+                // compact after writing values. include in loop since the on demand scheduler
+                // only runs once per `should_compact`, and memtables might still be getting
+                // flushed (await_durable in the put()'s above only wait for the writes to hit
+                // the WAL before returning).
+                should_compact_l0.store(true, Ordering::SeqCst);
+                s.l0_last_compacted.is_some() && s.l0.is_empty()
+            },
+            Duration::from_secs(10),
+        )
+        .await;
+        info!(
+            "1 l0: {} {}",
+            db.inner.state.read().state().core().l0.len(),
+            db.inner.state.read().state().core().compacted.len()
+        );
 
-        // Validate uncommitted read
-        let val = kv_store
-            .get_with_options(
-                "foo".as_bytes(),
-                &ReadOptions::new().with_durability_filter(Memory),
-            )
-            .await
-            .unwrap();
-        assert_eq!(val, Some("bar".into()));
+        // write more l0s and wait for compaction
+        for i in 0..4 {
+            db.put(&[b'f' + i; 32], &[6u8 + i; 32]).await.unwrap();
+            db.put(&[b's' + i; 32], &[19u8 + i; 32]).await.unwrap();
+        }
+        // wait for compactor to compact them
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| {
+                // compact after writing values. include in loop since the on demand scheduler
+                // only runs once per `should_compact`, and memtables might still be getting
+                // flushed (await_durable in the put()'s above only wait for the writes to hit
+                // the WAL before returning).
+                should_compact_l0.store(true, Ordering::SeqCst);
+                s.l0_last_compacted.is_some() && s.l0.is_empty()
+            },
+            Duration::from_secs(10),
+        )
+        .await;
+        info!(
+            "2 l0: {} {}",
+            db.inner.state.read().state().core().l0.len(),
+            db.inner.state.read().state().core().compacted.len()
+        );
+        // write another l0
+        db.put(&[b'a'; 32], &[128u8; 32]).await.unwrap();
+        db.put(&[b'm'; 32], &[129u8; 32]).await.unwrap();
 
-        // Validate committed read should still return None
-        let val = kv_store
-            .get_with_options(
-                "foo".as_bytes(),
-                &ReadOptions::new().with_durability_filter(Remote),
-            )
-            .await
-            .unwrap();
-        assert_eq!(val, None);
+        let val = db.get([b'a'; 32]).await.unwrap();
+        assert_eq!(val, Some(Bytes::copy_from_slice(&[128u8; 32])));
+        let val = db.get([b'm'; 32]).await.unwrap();
+        assert_eq!(val, Some(Bytes::copy_from_slice(&[129u8; 32])));
+        for i in 1..4 {
+            info!(
+                "3 l0: {} {}",
+                db.inner.state.read().state().core().l0.len(),
+                db.inner.state.read().state().core().compacted.len()
+            );
+            let val = db.get([b'a' + i; 32]).await.unwrap();
+            assert_eq!(val, Some(Bytes::copy_from_slice(&[1u8 + i; 32])));
+            let val = db.get([b'm' + i; 32]).await.unwrap();
+            assert_eq!(val, Some(Bytes::copy_from_slice(&[13u8 + i; 32])));
+        }
+        for i in 0..4 {
+            let val = db.get([b'f' + i; 32]).await.unwrap();
+            assert_eq!(val, Some(Bytes::copy_from_slice(&[6u8 + i; 32])));
+            let val = db.get([b's' + i; 32]).await.unwrap();
+            assert_eq!(val, Some(Bytes::copy_from_slice(&[19u8 + i; 32])));
+        }
+        let neg_lookup = db.get(b"abc").await;
+        assert!(neg_lookup.unwrap().is_none());
+    }
 
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
-        kv_store.close().await.unwrap();
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_should_read_from_compacted_db() {
+        do_test_should_read_compacted_db(test_db_options(
+            0,
+            127,
+            Some(CompactorOptions {
+                poll_interval: Duration::from_millis(100),
+                max_sst_size: 256,
+                max_concurrent_compactions: 1,
+                manifest_update_timeout: Duration::from_secs(300),
+                history_retention: None,
+                max_write_bytes_per_second: None,
+                min_blob_file_live_ratio: Some(0.5),
+            }),
+        ))
+        .await;
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
-    async fn test_should_read_only_committed_data() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_should_read_from_compacted_db_no_filters() {
+        do_test_should_read_compacted_db(test_db_options(
+            u32::MAX,
+            127,
+            Some(CompactorOptions {
+                poll_interval: Duration::from_millis(100),
+                manifest_update_timeout: Duration::from_secs(300),
+                max_sst_size: 256,
+                max_concurrent_compactions: 1,
+                history_retention: None,
+                max_write_bytes_per_second: None,
+                min_blob_file_live_ratio: Some(0.5),
+            }),
+        ))
+        .await
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_comparator_orders_keys_in_memtable_l0_and_compacted_runs() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
-        let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 1024, None))
-            .with_fp_registry(fp_registry.clone())
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |_state| this_should_compact_l0.swap(false, Ordering::SeqCst),
+        )));
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(
+                0,
+                127,
+                Some(CompactorOptions {
+                    poll_interval: Duration::from_millis(100),
+                    max_sst_size: 256,
+                    max_concurrent_compactions: 1,
+                    manifest_update_timeout: Duration::from_secs(300),
+                    history_retention: None,
+                    max_write_bytes_per_second: None,
+                    min_blob_file_live_ratio: Some(0.5),
+                }),
+            ))
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .with_comparator(Arc::new(ReverseComparator))
             .build()
             .await
             .unwrap();
+        let mut sm = StoredManifest::load(Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        )))
+        .await
+        .unwrap();
 
-        kv_store
-            .put("foo".as_bytes(), "bar".as_bytes())
-            .await
-            .unwrap();
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
-        kv_store
-            .put_with_options(
-                "foo".as_bytes(),
-                "bla".as_bytes(),
-                &PutOptions::default(),
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
-            .await
-            .unwrap();
-
-        let val = kv_store
-            .get_with_options(
-                "foo".as_bytes(),
-                &ReadOptions::new().with_durability_filter(Remote),
-            )
-            .await
-            .unwrap();
-        assert_eq!(val, Some("bar".into()));
-        let val = kv_store
-            .get_with_options(
-                "foo".as_bytes(),
-                &ReadOptions::new().with_durability_filter(Memory),
+        // fill up a few l0 SSTs and compact them into a sorted run of several SSTs
+        for i in 0..8 {
+            db.put(&[b'a' + i; 32], &[i; 32]).await.unwrap();
+            db.put(&[b'm' + i; 32], &[i; 32]).await.unwrap();
+        }
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| {
+                should_compact_l0.store(true, Ordering::SeqCst);
+                s.l0_last_compacted.is_some() && s.l0.is_empty()
+            },
+            Duration::from_secs(10),
+        )
+        .await;
+        // overwrite some keys in l0 and the memtable
+        db.put(&[b'c'; 32], &[100u8; 32]).await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        db.put(&[b'p'; 32], &[101u8; 32]).await.unwrap();
+
+        let mut expected: Vec<(Bytes, Bytes)> = (0..8)
+            .flat_map(|i| [(b'a' + i, i), (b'm' + i, i)])
+            .map(|(k, v)| {
+                let v = match k {
+                    b'c' => 100u8,
+                    b'p' => 101u8,
+                    _ => v,
+                };
+                (Bytes::from(vec![k; 32]), Bytes::from(vec![v; 32]))
+            })
+            .collect();
+        expected.sort_by(|a, b| b.0.cmp(&a.0));
+        for (key, value) in &expected {
+            assert_eq!(db.get(key).await.unwrap(), Some(value.clone()));
+        }
+
+        let mut iter = db.scan::<Vec<u8>, _>(..).await.unwrap();
+        let mut actual = Vec::new();
+        while let Some(kv) = iter.next().await.unwrap() {
+            actual.push((kv.key, kv.value));
+        }
+        assert_eq!(actual, expected);
+
+        // ranges are ordered by the comparator too
+        let mut iter = db
+            .scan_with_options(
+                [b'o'; 32].as_slice()..=[b'c'; 32].as_slice(),
+                &ScanOptions::default().with_order(IterationOrder::Descending),
             )
             .await
             .unwrap();
-        assert_eq!(val, Some("bla".into()));
-
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
-        kv_store.close().await.unwrap();
+        let mut actual = Vec::new();
+        while let Some(kv) = iter.next().await.unwrap() {
+            actual.push(kv.key[0]);
+        }
+        assert_eq!(actual, b"cdefghmno".to_vec());
     }
 
     #[tokio::test]
-    async fn test_should_delete_without_awaiting_flush() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
+    async fn test_comparator_mismatch_fails_open() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
-        let kv_store = Db::builder(path, object_store.clone())
+        let db = Db::builder(path, object_store.clone())
             .with_settings(test_db_options(0, 1024, None))
-            .with_fp_registry(fp_registry.clone())
+            .with_comparator(Arc::new(ReverseComparator))
             .build()
             .await
             .unwrap();
+        db.put(b"key1", b"value1").await.unwrap();
+        db.close().await.unwrap();
 
-        kv_store
-            .put("foo".as_bytes(), "bar".as_bytes())
-            .await
-            .unwrap();
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
-        kv_store
-            .delete_with_options(
-                "foo".as_bytes(),
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
+        let result = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await;
+        let Err(err) = result else {
+            panic!("expected comparator mismatch");
+        };
+        assert_eq!(err.kind(), crate::ErrorKind::Invalid);
+        assert!(err.to_string().contains("comparator mismatch"));
+
+        let result =
+            DbReader::open(path, object_store.clone(), None, DbReaderOptions::default()).await;
+        let Err(err) = result else {
+            panic!("expected comparator mismatch");
+        };
+        assert!(err.to_string().contains("comparator mismatch"));
+
+        // a standalone compactor, a compaction worker and the admin would all order
+        // the keys of SSTs in the wrong way
+        let compactor =
+            crate::db::builder::CompactorBuilder::new(path, object_store.clone()).build();
+        let Err(err) = compactor.run_async_task().await else {
+            panic!("expected comparator mismatch");
+        };
+        assert!(matches!(err, SlateDBError::ComparatorMismatch { .. }));
+
+        let worker =
+            crate::remote_compaction::CompactionWorker::builder(path, object_store.clone()).build();
+        let id = ulid::Ulid::new();
+        let job = crate::remote_compaction::CompactionJob {
+            args: crate::compactor_executor::StartCompactionJobArgs {
+                id,
+                compaction_id: id,
+                destination: 0,
+                column_family: DEFAULT_COLUMN_FAMILY_ID,
+                ssts: vec![],
+                sorted_runs: vec![],
+                compaction_logical_clock_tick: 0,
+                is_dest_last_run: true,
+                retention_min_seq: None,
+                output_ssts: vec![],
+            },
+        };
+        let result = worker.run_job(job).await;
+        assert!(result.error().unwrap().contains("comparator mismatch"));
+
+        let admin = crate::admin::Admin::builder(path, object_store.clone()).build();
+        let Err(err) = admin.key_history(b"key1").await else {
+            panic!("expected comparator mismatch");
+        };
+        assert!(err.to_string().contains("comparator mismatch"));
+
+        let db = Db::builder(path, object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_comparator(Arc::new(ReverseComparator))
+            .build()
             .await
             .unwrap();
+        assert_eq!(
+            db.get(b"key1").await.unwrap(),
+            Some(Bytes::from_static(b"value1"))
+        );
+    }
 
-        let val = kv_store
-            .get_with_options(
-                "foo".as_bytes(),
-                &ReadOptions::new().with_durability_filter(Remote),
-            )
+    #[tokio::test]
+    async fn test_comparator_rejects_range_deletes_and_prefix_scans() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_comparator(Arc::new(ReverseComparator))
+            .build()
             .await
             .unwrap();
-        assert_eq!(val, Some("bar".into()));
-        let val = kv_store
-            .get_with_options(
-                "foo".as_bytes(),
-                &ReadOptions::new().with_durability_filter(Memory),
-            )
+
+        let err = db
+            .delete_range(b"a".as_slice()..b"c".as_slice())
             .await
-            .unwrap();
-        assert_eq!(val, None);
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Invalid);
 
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
-        kv_store.close().await.unwrap();
+        let result = db
+            .scan_with_options::<Vec<u8>, _>(.., &ScanOptions::default().with_prefix("a"))
+            .await;
+        let Err(err) = result else {
+            panic!("expected prefix scan to be rejected");
+        };
+        assert_eq!(err.kind(), crate::ErrorKind::Invalid);
     }
 
     #[tokio::test]
-    async fn test_scan_should_read_only_committed_data() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
+    async fn test_large_values_are_stored_in_blob_files() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
-        let kv_store = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 1024, None))
-            .with_fp_registry(fp_registry.clone())
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |state| {
+                !state.db_state().l0.is_empty() && this_should_compact_l0.load(Ordering::SeqCst)
+            },
+        )));
+        let mut settings = test_db_options(
+            0,
+            1024,
+            Some(CompactorOptions {
+                poll_interval: Duration::from_millis(100),
+                ..CompactorOptions::default()
+            }),
+        );
+        settings.min_blob_value_size = Some(64);
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(settings)
+            .with_compaction_scheduler_supplier(compaction_scheduler)
             .build()
             .await
             .unwrap();
+        let mut sm = StoredManifest::load(Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        )))
+        .await
+        .unwrap();
 
-        // Write and commit some initial data
-        kv_store
-            .put("key1".as_bytes(), "committed1".as_bytes())
-            .await
-            .unwrap();
-        kv_store
-            .put("key2".as_bytes(), "committed2".as_bytes())
-            .await
-            .unwrap();
-        kv_store
-            .put("key3".as_bytes(), "committed3".as_bytes())
-            .await
-            .unwrap();
+        db.put(b"large", &[1u8; 100]).await.unwrap();
+        db.put(b"small", b"value").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
 
-        // Pause WAL writes to prevent new writes from being committed
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "pause").unwrap();
+        // only the large value is stored in the blob file of the L0 SST
+        let l0 = db.inner.state.read().state().core().l0.clone();
+        assert_eq!(l0.len(), 1);
+        let blob_id = l0[0].id.unwrap_compacted_id();
+        assert_eq!(
+            l0[0].info.blob_refs,
+            vec![BlobFileRef {
+                id: blob_id,
+                live_bytes: 100
+            }]
+        );
+        let blobs = db.inner.table_store.list_blob_files().await.unwrap();
+        assert_eq!(
+            blobs.iter().map(|b| b.id).collect::<Vec<_>>(),
+            vec![blob_id]
+        );
+        assert_eq!(
+            db.get(b"large").await.unwrap(),
+            Some(Bytes::from(vec![1u8; 100]))
+        );
+        assert_eq!(
+            db.get_many(&[b"large".as_slice(), b"small", b"missing"])
+                .await
+                .unwrap(),
+            vec![
+                Some(Bytes::from(vec![1u8; 100])),
+                Some(Bytes::from_static(b"value")),
+                None
+            ]
+        );
 
-        // Write uncommitted data
-        kv_store
-            .put_with_options(
-                "key2".as_bytes(),
-                "uncommitted2".as_bytes(),
-                &PutOptions::default(),
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
-            .await
-            .unwrap();
-        kv_store
-            .put_with_options(
-                "key4".as_bytes(),
-                "uncommitted4".as_bytes(),
-                &PutOptions::default(),
-                &WriteOptions {
-                    await_durable: false,
-                },
-            )
-            .await
-            .unwrap();
+        // compaction passes the blob refs through without rewriting the values
+        should_compact_l0.store(true, Ordering::SeqCst);
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| s.l0.is_empty() && s.compacted.len() == 1,
+            Duration::from_secs(10),
+        )
+        .await;
+        let sr_ssts = sm.db_state().compacted[0].ssts.clone();
+        assert_eq!(sr_ssts.len(), 1);
+        assert_eq!(sr_ssts[0].info.blob_refs, l0[0].info.blob_refs);
+        let blobs = db.inner.table_store.list_blob_files().await.unwrap();
+        assert_eq!(blobs.len(), 1);
 
-        // Scan with Remote filter should only see committed data
-        let mut iter = kv_store
-            .scan_with_options(
-                "key1".as_bytes().."key5".as_bytes(),
-                &ScanOptions::new().with_durability_filter(Remote),
-            )
+        let reader = DbReader::open(path, object_store.clone(), None, DbReaderOptions::default())
             .await
             .unwrap();
+        let mut iter = reader.scan::<Vec<u8>, _>(..).await.unwrap();
+        let mut actual = Vec::new();
+        while let Some(kv) = iter.next().await.unwrap() {
+            actual.push((kv.key, kv.value));
+        }
+        assert_eq!(
+            actual,
+            vec![
+                (Bytes::from_static(b"large"), Bytes::from(vec![1u8; 100])),
+                (Bytes::from_static(b"small"), Bytes::from_static(b"value")),
+            ]
+        );
+        let values = reader.get_many(&[b"large", b"small"]).await.unwrap();
+        assert_eq!(values[0], Some(Bytes::from(vec![1u8; 100])));
+    }
 
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key1");
-        assert_eq!(kv.value.as_ref(), b"committed1");
-
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key2");
-        assert_eq!(kv.value.as_ref(), b"committed2"); // Old committed value
-
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key3");
-        assert_eq!(kv.value.as_ref(), b"committed3");
+    #[tokio::test]
+    async fn test_compaction_filter_keeps_values_in_their_blob_files() {
+        struct KeepFilter {
+            values: parking_lot::Mutex<Vec<Bytes>>,
+        }
 
-        // key4 should not be visible with Remote filter
-        assert_eq!(iter.next().await.unwrap(), None);
+        impl crate::CompactionFilter for KeepFilter {
+            fn filter(&self, _key: &Bytes, value: &Bytes) -> crate::CompactionFilterDecision {
+                self.values.lock().push(value.clone());
+                crate::CompactionFilterDecision::Keep
+            }
+        }
 
-        // Scan with Memory filter should see uncommitted data
-        let mut iter = kv_store
-            .scan_with_options(
-                "key1".as_bytes().."key5".as_bytes(),
-                &ScanOptions::new().with_durability_filter(Memory),
-            )
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |state| {
+                !state.db_state().l0.is_empty() && this_should_compact_l0.load(Ordering::SeqCst)
+            },
+        )));
+        let mut settings = test_db_options(
+            0,
+            1024,
+            Some(CompactorOptions {
+                poll_interval: Duration::from_millis(100),
+                ..CompactorOptions::default()
+            }),
+        );
+        settings.min_blob_value_size = Some(64);
+        let compaction_filter = Arc::new(KeepFilter {
+            values: parking_lot::Mutex::new(Vec::new()),
+        });
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(settings)
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .with_compaction_filter(compaction_filter.clone())
+            .build()
             .await
             .unwrap();
+        let mut sm = StoredManifest::load(Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        )))
+        .await
+        .unwrap();
 
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key1");
-        assert_eq!(kv.value.as_ref(), b"committed1");
+        db.put(b"large", &[1u8; 100]).await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let l0 = db.inner.state.read().state().core().l0.clone();
+        assert_eq!(l0.len(), 1);
 
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key2");
-        assert_eq!(kv.value.as_ref(), b"uncommitted2"); // New uncommitted value
+        should_compact_l0.store(true, Ordering::SeqCst);
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| s.l0.is_empty() && s.compacted.len() == 1,
+            Duration::from_secs(10),
+        )
+        .await;
 
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key3");
-        assert_eq!(kv.value.as_ref(), b"committed3");
+        // the filter reads the value, but the kept row still points to the old blob file
+        assert_eq!(
+            *compaction_filter.values.lock(),
+            vec![Bytes::from(vec![1u8; 100])]
+        );
+        let sr_ssts = sm.db_state().compacted[0].ssts.clone();
+        assert_eq!(sr_ssts.len(), 1);
+        assert_eq!(sr_ssts[0].info.blob_refs, l0[0].info.blob_refs);
+        let blobs = db.inner.table_store.list_blob_files().await.unwrap();
+        assert_eq!(
+            blobs.iter().map(|b| b.id).collect::<Vec<_>>(),
+            vec![l0[0].id.unwrap_compacted_id()]
+        );
+        assert_eq!(
+            db.get(b"large").await.unwrap(),
+            Some(Bytes::from(vec![1u8; 100]))
+        );
+        db.close().await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_compaction_rewrites_values_of_sparse_blob_files() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |state| {
+                !state.db_state().l0.is_empty() && this_should_compact_l0.load(Ordering::SeqCst)
+            },
+        )));
+        let mut settings = test_db_options(
+            0,
+            1024,
+            Some(
+                CompactorOptions {
+                    poll_interval: Duration::from_millis(100),
+                    ..CompactorOptions::default()
+                }
+                .with_min_blob_file_live_ratio(0.5),
+            ),
+        );
+        settings.min_blob_value_size = Some(64);
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(settings)
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .build()
+            .await
+            .unwrap();
+        let mut sm = StoredManifest::load(Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        )))
+        .await
+        .unwrap();
+        let flush_options = FlushOptions {
+            flush_type: FlushType::MemTable,
+        };
 
-        let kv = iter.next().await.unwrap().unwrap();
-        assert_eq!(kv.key.as_ref(), b"key4");
-        assert_eq!(kv.value.as_ref(), b"uncommitted4"); // Uncommitted key visible
+        for key in [b"k1", b"k2", b"k3", b"k4"] {
+            db.put(key, &[1u8; 100]).await.unwrap();
+        }
+        db.flush_with_options(flush_options.clone()).await.unwrap();
+        let blob_id = db.inner.state.read().state().core().l0[0]
+            .id
+            .unwrap_compacted_id();
+        for key in [b"k1", b"k2", b"k3"] {
+            db.put(key, b"small").await.unwrap();
+        }
+        db.flush_with_options(flush_options.clone()).await.unwrap();
 
-        assert_eq!(iter.next().await.unwrap(), None);
+        // the overwritten values are dropped, but the blob file is left in place
+        should_compact_l0.store(true, Ordering::SeqCst);
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| s.l0.is_empty() && s.compacted.len() == 1,
+            Duration::from_secs(10),
+        )
+        .await;
+        let sr_ssts = sm.db_state().compacted[0].ssts.clone();
+        assert_eq!(
+            sr_ssts[0].info.blob_refs,
+            vec![BlobFileRef {
+                id: blob_id,
+                live_bytes: 100
+            }]
+        );
 
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "off").unwrap();
-        kv_store.close().await.unwrap();
+        // the blob file is now 3/4 dead, so the next compaction copies its live value
+        db.put(b"k5", b"small").await.unwrap();
+        db.flush_with_options(flush_options).await.unwrap();
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| {
+                s.l0.is_empty()
+                    && s.compacted[0]
+                        .ssts
+                        .iter()
+                        .all(|sst| sst.info.blob_refs.iter().all(|r| r.id != blob_id))
+            },
+            Duration::from_secs(10),
+        )
+        .await;
+        let sr_ssts = sm.db_state().compacted[0].ssts.clone();
+        assert_eq!(sr_ssts.len(), 1);
+        assert_eq!(
+            sr_ssts[0].info.blob_refs,
+            vec![BlobFileRef {
+                id: sr_ssts[0].id.unwrap_compacted_id(),
+                live_bytes: 100
+            }]
+        );
+        assert_eq!(
+            db.get(b"k4").await.unwrap(),
+            Some(Bytes::from(vec![1u8; 100]))
+        );
+        db.close().await.unwrap();
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_should_recover_imm_from_wal() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
-        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "pause").unwrap();
-
+    #[tokio::test]
+    async fn test_encrypted_db() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
-        let mut next_wal_id = 1;
+        let provider = Arc::new(XorEncryptionProvider::new("k1", 0x5a));
+        let mut settings = test_db_options(
+            0,
+            1024,
+            Some(CompactorOptions {
+                poll_interval: Duration::from_millis(100),
+                ..CompactorOptions::default()
+            }),
+        );
+        settings.min_blob_value_size = Some(64);
         let db = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 128, None))
-            .with_fp_registry(fp_registry.clone())
+            .with_settings(settings.clone())
+            .with_encryption_provider(provider.clone())
             .build()
             .await
             .unwrap();
-        next_wal_id += 1;
+        let large_value = b"secret-large-value".repeat(8);
 
-        // write a few keys that will result in memtable flushes
-        let key1 = [b'a'; 32];
-        let value1 = [b'b'; 96];
-        db.put(key1, value1).await.unwrap();
-        next_wal_id += 1;
-        let key2 = [b'c'; 32];
-        let value2 = [b'd'; 96];
-        db.put(key2, value2).await.unwrap();
-        next_wal_id += 1;
+        db.put(b"secret-key1", b"secret-value1").await.unwrap();
+        db.put(b"secret-large", &large_value).await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        // data written after the key is rotated is encrypted with the new key
+        provider.rotate("k2", 0x3c);
+        db.put(b"secret-key2", b"secret-value2").await.unwrap();
+        db.flush().await.unwrap();
+        db.close().await.unwrap();
 
-        let reader = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 128, None))
-            .with_fp_registry(fp_registry.clone())
+        // no plaintext reaches object storage
+        let objects: Vec<_> = object_store
+            .list(None)
+            .map(|r| r.unwrap().location)
+            .collect()
+            .await;
+        assert!(objects.iter().any(|p| p.extension() == Some("blob")));
+        for location in objects {
+            let bytes = object_store
+                .get(&location)
+                .await
+                .unwrap()
+                .bytes()
+                .await
+                .unwrap();
+            assert!(
+                !bytes.windows(b"secret".len()).any(|w| w == b"secret"),
+                "found plaintext in {}",
+                location
+            );
+        }
+
+        // the data is read back with the provider
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(settings.clone())
+            .with_encryption_provider(provider.clone())
             .build()
             .await
             .unwrap();
-
-        // increment wal id for the empty wal
-        next_wal_id += 1;
-
-        // verify that we reload imm
-        let db_state = reader.inner.state.read().view();
-        assert_eq!(db_state.state.imm_memtable.len(), 2);
-
-        // one empty wal and two wals for the puts
         assert_eq!(
-            db_state
-                .state
-                .imm_memtable
-                .front()
-                .unwrap()
-                .recent_flushed_wal_id(),
-            1 + 2
+            db.get(b"secret-key1").await.unwrap(),
+            Some(Bytes::from_static(b"secret-value1"))
         );
         assert_eq!(
-            db_state
-                .state
-                .imm_memtable
-                .get(1)
-                .unwrap()
-                .recent_flushed_wal_id(),
-            2
+            db.get(b"secret-large").await.unwrap(),
+            Some(Bytes::from(large_value.clone()))
         );
-        assert_eq!(db_state.state.core().next_wal_sst_id, next_wal_id);
         assert_eq!(
-            reader.get(key1).await.unwrap(),
-            Some(Bytes::copy_from_slice(&value1))
+            db.get(b"secret-key2").await.unwrap(),
+            Some(Bytes::from_static(b"secret-value2"))
         );
+        db.close().await.unwrap();
+
+        let reader = DbReader::open(
+            path,
+            object_store.clone(),
+            None,
+            DbReaderOptions {
+                encryption_provider: Some(provider.clone()),
+                ..DbReaderOptions::default()
+            },
+        )
+        .await
+        .unwrap();
         assert_eq!(
-            reader.get(key2).await.unwrap(),
-            Some(Bytes::copy_from_slice(&value2))
+            reader.get(b"secret-key1").await.unwrap(),
+            Some(Bytes::from_static(b"secret-value1"))
         );
-
-        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "off").unwrap();
-        db.close().await.unwrap();
         reader.close().await.unwrap();
+
+        // opening the database without the provider fails clearly
+        let result = Db::builder(path, object_store.clone())
+            .with_settings(settings)
+            .build()
+            .await;
+        let Err(err) = result else {
+            panic!("expected opening the database without the provider to fail");
+        };
+        assert_eq!(err.kind(), crate::ErrorKind::Invalid);
+        assert!(err.to_string().contains("encryption key unavailable"));
     }
 
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_should_recover_imm_from_wal_after_flush_error() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
-        fail_parallel::cfg(
-            fp_registry.clone(),
-            "write-compacted-sst-io-error",
-            "return",
-        )
-        .unwrap();
+    async fn test_delete_range_hides_keys_from_memtable_l0_and_compacted_runs() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |_state| this_should_compact_l0.swap(false, Ordering::SeqCst),
+        )));
         let db = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 4096, None))
-            .with_fp_registry(fp_registry.clone())
+            .with_settings(test_db_options(
+                0,
+                1024,
+                Some(CompactorOptions {
+                    poll_interval: Duration::from_millis(100),
+                    max_sst_size: 256,
+                    max_concurrent_compactions: 1,
+                    manifest_update_timeout: Duration::from_secs(300),
+                    history_retention: None,
+                    max_write_bytes_per_second: None,
+                    min_blob_file_live_ratio: Some(0.5),
+                }),
+            ))
+            .with_compaction_scheduler_supplier(compaction_scheduler)
             .build()
             .await
             .unwrap();
+        let ms = ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        );
+        let mut sm = StoredManifest::load(Arc::new(ms)).await.unwrap();
 
-        // write data to the WAL, but not enough to trigger a memtable flush
-        let key1 = [b'a'; 32];
-        let value1 = [b'b'; 96];
-        let result = db.put(&key1, &value1).await;
-        assert!(result.is_ok(), "Failed to write key1");
-        assert_eq!(db.inner.wal_buffer.recent_flushed_wal_id(), 2);
+        for key in [b"key1", b"key2", b"key3", b"key4", b"key5"] {
+            db.put(key, b"old").await.unwrap();
+        }
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
 
-        // force a flush (even if the memtable is not full)
-        let flush_result = db.inner.flush_memtables().await;
-        assert!(flush_result.is_err());
-        db.close().await.unwrap();
+        // the range tombstone lives in the memtable, the covered keys in l0
+        db.delete_range(b"key2".as_slice()..b"key5".as_slice())
+            .await
+            .unwrap();
+        db.put(b"key3", b"new").await.unwrap();
+        async fn assert_visible(db: &Db) {
+            assert_eq!(db.get(b"key2").await.unwrap(), None);
+            assert_eq!(
+                db.get(b"key3").await.unwrap(),
+                Some(Bytes::from_static(b"new"))
+            );
+            let mut iter = db.scan::<&[u8], _>(..).await.unwrap();
+            let mut keys = Vec::new();
+            while let Some(kv) = iter.next().await.unwrap() {
+                keys.push(kv.key);
+            }
+            assert_eq!(
+                keys,
+                vec![
+                    Bytes::from_static(b"key1"),
+                    Bytes::from_static(b"key3"),
+                    Bytes::from_static(b"key5"),
+                ]
+            );
+        }
+        assert_visible(&db).await;
 
-        // pause write-compacted-sst-io-error to prevent immutable tables
-        // from being flushed, so we can snapshot the state when there is
-        // an immutable table to verify its contents.
-        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "pause").unwrap();
+        // both the tombstone and the covered keys are in l0
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        assert_visible(&db).await;
 
-        // reload the db
+        // compacting into the last sorted run drops the covered keys and the tombstone
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| {
+                should_compact_l0.store(true, Ordering::SeqCst);
+                s.l0_last_compacted.is_some() && s.l0.is_empty()
+            },
+            Duration::from_secs(10),
+        )
+        .await;
+        assert_visible(&db).await;
+        let manifest = sm.refresh().await.unwrap();
+        assert!(manifest
+            .core
+            .compacted
+            .iter()
+            .flat_map(|sr| sr.ssts.iter())
+            .all(|sst| sst.info.range_tombstones.is_empty()));
+        let mut iter = crate::sorted_run_iterator::SortedRunIterator::new_owned_initialized(
+            BytesRange::from(..),
+            manifest.core.compacted[0].clone(),
+            db.inner.table_store.clone(),
+            SstIteratorOptions::default(),
+        )
+        .await
+        .unwrap();
+        let mut keys = Vec::new();
+        while let Some(entry) = iter.next_entry().await.unwrap() {
+            keys.push(entry.key);
+        }
+        assert_eq!(
+            keys,
+            vec![
+                Bytes::from_static(b"key1"),
+                Bytes::from_static(b"key3"),
+                Bytes::from_static(b"key5"),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_delete_range_should_survive_wal_replay() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
         let db = Db::builder(path, object_store.clone())
-            .with_settings(test_db_options(0, 128, None))
-            .with_fp_registry(fp_registry.clone())
+            .with_settings(test_db_options(0, 1024, None))
             .build()
             .await
             .unwrap();
+        db.put(b"key1", b"value1").await.unwrap();
+        db.put(b"key2", b"value2").await.unwrap();
+        db.delete_range(b"key1".as_slice()..=b"key1".as_slice())
+            .await
+            .unwrap();
+        db.close().await.unwrap();
 
-        let db_state = db.inner.state.read().view();
-
-        // resume write-compacted-sst-io-error since we got a snapshot and
-        // want to let the test finish.
-        fail_parallel::cfg(fp_registry.clone(), "write-compacted-sst-io-error", "off").unwrap();
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
 
-        // verify that we reload imm
-        assert_eq!(db_state.state.imm_memtable.len(), 1);
+        assert_eq!(db.get(b"key1").await.unwrap(), None);
+        assert_eq!(
+            db.get(b"key2").await.unwrap(),
+            Some(Bytes::from_static(b"value2"))
+        );
+    }
 
-        // verify that we have no L0 SSTs because memtables should have failed to flush
-        assert_eq!(db_state.state.core().l0.len(), 0);
-        assert_eq!(db_state.state.core().compacted.len(), 0);
+    #[tokio::test]
+    async fn test_read_as_of_seq() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+        db.put(b"key1", b"v1").await.unwrap();
+        let seq = db.inner.oracle.last_committed_seq();
+        db.put(b"key1", b"v2").await.unwrap();
+        db.put(b"key2", b"v1").await.unwrap();
 
-        // one empty wal and one wal for the first put
+        let read_options = ReadOptions::new().with_as_of_seq(seq);
         assert_eq!(
-            db_state
-                .state
-                .imm_memtable
-                .front()
-                .unwrap()
-                .recent_flushed_wal_id(),
-            1 + 1
+            db.get_with_options(b"key1", &read_options).await.unwrap(),
+            Some(Bytes::from_static(b"v1"))
         );
-        assert!(db_state.state.imm_memtable.get(1).is_none());
-
-        assert_eq!(db_state.state.core().next_wal_sst_id, 4);
         assert_eq!(
-            db.get(key1).await.unwrap(),
-            Some(Bytes::copy_from_slice(&value1))
+            db.get_with_options(b"key2", &read_options).await.unwrap(),
+            None
+        );
+        let scan_options = ScanOptions::new().with_as_of_seq(seq);
+        let mut iter = db
+            .scan_with_options::<&[u8], _>(.., &scan_options)
+            .await
+            .unwrap();
+        assert_eq!(
+            iter.next().await.unwrap(),
+            Some(KeyValue {
+                key: Bytes::from_static(b"key1"),
+                value: Bytes::from_static(b"v1"),
+            })
         );
+        assert_eq!(iter.next().await.unwrap(), None);
     }
 
     #[tokio::test]
-    async fn test_should_fail_write_if_wal_flush_task_panics() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
+    #[cfg(feature = "test-util")]
+    async fn test_read_as_of_timestamp() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_kv_store";
-        let db = Arc::new(
-            Db::builder(path, object_store.clone())
-                .with_settings(test_db_options(0, 128, None))
-                .with_fp_registry(fp_registry.clone())
-                .build()
-                .await
-                .unwrap(),
+        let system_clock = Arc::new(MockSystemClock::new());
+        // keep the overwritten versions readable once the memtable is flushed
+        let mut settings = test_db_options(
+            0,
+            1024,
+            Some(CompactorOptions {
+                history_retention: Some(Duration::from_secs(3600)),
+                ..CompactorOptions::default()
+            }),
         );
+        settings.flush_interval = None;
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(settings)
+            .with_system_clock(system_clock.clone())
+            .build()
+            .await
+            .unwrap();
+        let write_options = WriteOptions {
+            await_durable: false,
+            ..WriteOptions::default()
+        };
+        system_clock.set(60_000);
+        db.put_with_options(b"key", b"v1", &PutOptions::default(), &write_options)
+            .await
+            .unwrap();
+        system_clock.set(180_000);
+        db.put_with_options(b"key", b"v2", &PutOptions::default(), &write_options)
+            .await
+            .unwrap();
 
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "panic").unwrap();
-        let result = db.put(b"foo", b"bar").await.unwrap_err();
-        assert!(result.to_string().contains("background task panicked"));
-    }
-
-    #[tokio::test]
-    async fn test_wal_id_last_seen_should_exist_even_if_wal_write_fails() {
-        let fp_registry = Arc::new(FailPointRegistry::new());
-        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
-        let path = "/tmp/test_kv_store";
-        let db = Arc::new(
-            Db::builder(path, object_store.clone())
-                .with_settings(test_db_options(0, 128, None))
-                .with_fp_registry(fp_registry.clone())
-                .build()
-                .await
-                .unwrap(),
+        let read_at = |secs| {
+            ReadOptions::new().with_as_of_timestamp(Utc.timestamp_opt(secs, 0).single().unwrap())
+        };
+        assert_eq!(
+            db.get_with_options(b"key", &read_at(0)).await.unwrap(),
+            None
         );
-
-        fail_parallel::cfg(fp_registry.clone(), "write-wal-sst-io-error", "panic").unwrap();
-
-        // Trigger a WAL write, which should not advance the manifest WAL ID
-        let result = db.put(b"foo", b"bar").await.unwrap_err();
-        assert_eq!(result.kind(), crate::ErrorKind::Closed(CloseReason::Panic));
-        assert!(result
-            .to_string()
-            .contains("background task panicked. name=`wal_writer`"));
-
-        // Close, which flushes the latest manifest to the object store
-        // TODO: it might make sense to return an error if there're unflushed wals in memory
-        // on close().
-        db.close().await.unwrap();
-
-        let manifest_store = ManifestStore::new(
-            &Path::from(path),
-            object_store.clone(),
-            Arc::new(DefaultSystemClock::new()),
+        assert_eq!(
+            db.get_with_options(b"key", &read_at(120)).await.unwrap(),
+            Some(Bytes::from_static(b"v1"))
+        );
+        assert_eq!(
+            db.get_with_options(b"key", &read_at(180)).await.unwrap(),
+            Some(Bytes::from_static(b"v2"))
         );
-        let table_store = Arc::new(TableStore::new(
-            ObjectStores::new(object_store.clone(), None),
-            SsTableFormat::default(),
-            path,
-            None,
-        ));
-
-        // Get the next WAL SST ID based on what's currently in the object store
-        let next_wal_sst_id = table_store.next_wal_sst_id(0).await.unwrap();
-
-        // Get the latest manifest
-        let (_, manifest) = manifest_store.read_latest_manifest().await.unwrap();
 
-        // It's possible that there exists buffered multiple wals in memory, so the next_wal_sst_id
-        // in manifest is greater than the next_wal_sst_id based on what's currently in the object
-        // store unless ALL the wals are flushed.
-        assert!(manifest.core.next_wal_sst_id > next_wal_sst_id);
+        // the trackers of flushed memtables are read from the manifest
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        assert_eq!(
+            db.get_with_options(b"key", &read_at(120)).await.unwrap(),
+            Some(Bytes::from_static(b"v1"))
+        );
     }
 
-    async fn do_test_should_read_compacted_db(options: Settings) {
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_compaction_keeps_versions_within_history_retention() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
         let path = "/tmp/test_kv_store";
         let should_compact_l0 = Arc::new(AtomicBool::new(false));
@@ -4269,10 +7290,17 @@ mod tests {
         let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
             move |_state| this_should_compact_l0.swap(false, Ordering::SeqCst),
         )));
-
         let db = Db::builder(path, object_store.clone())
-            .with_settings(options)
-            .with_compaction_scheduler_supplier(compaction_scheduler.clone())
+            .with_settings(test_db_options(
+                0,
+                1024,
+                Some(CompactorOptions {
+                    poll_interval: Duration::from_millis(100),
+                    history_retention: Some(Duration::from_secs(3600)),
+                    ..CompactorOptions::default()
+                }),
+            ))
+            .with_compaction_scheduler_supplier(compaction_scheduler)
             .build()
             .await
             .unwrap();
@@ -4283,112 +7311,318 @@ mod tests {
         );
         let mut sm = StoredManifest::load(Arc::new(ms)).await.unwrap();
 
-        // write enough to fill up a few l0 SSTs
-        for i in 0..4 {
-            db.put(&[b'a' + i; 32], &[1u8 + i; 32]).await.unwrap();
-            db.put(&[b'm' + i; 32], &[13u8 + i; 32]).await.unwrap();
-        }
-        // wait for compactor to compact them
+        db.put(b"key", b"v1").await.unwrap();
+        let seq = db.inner.oracle.last_committed_seq();
+        db.put(b"key", b"v2").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
         wait_for_manifest_condition(
             &mut sm,
             |s| {
-                // compact after writing values. include in loop since the on demand scheduler
-                // only runs once per `should_compact`, and memtables might still be getting
-                // flushed (await_durable in the put()'s above only wait for the writes to hit
-                // the WAL before returning).
                 should_compact_l0.store(true, Ordering::SeqCst);
                 s.l0_last_compacted.is_some() && s.l0.is_empty()
             },
             Duration::from_secs(10),
         )
         .await;
-        info!(
-            "1 l0: {} {}",
-            db.inner.state.read().state().core().l0.len(),
-            db.inner.state.read().state().core().compacted.len()
+        // wait for the writer to stop reading the compacted l0
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !db.inner.state.read().state().core().l0.is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert_eq!(
+            db.get_with_options(b"key", &ReadOptions::new().with_as_of_seq(seq))
+                .await
+                .unwrap(),
+            Some(Bytes::from_static(b"v1"))
+        );
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"v2"))
         );
+    }
 
-        // write more l0s and wait for compaction
-        for i in 0..4 {
-            db.put(&[b'f' + i; 32], &[6u8 + i; 32]).await.unwrap();
-            db.put(&[b's' + i; 32], &[19u8 + i; 32]).await.unwrap();
-        }
-        // wait for compactor to compact them
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_read_as_of_seq_fails_once_versions_are_no_longer_retained() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |_state| this_should_compact_l0.swap(false, Ordering::SeqCst),
+        )));
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(
+                0,
+                1024,
+                Some(CompactorOptions {
+                    poll_interval: Duration::from_millis(100),
+                    history_retention: None,
+                    ..CompactorOptions::default()
+                }),
+            ))
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .build()
+            .await
+            .unwrap();
+        let ms = ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        );
+        let mut sm = StoredManifest::load(Arc::new(ms)).await.unwrap();
+
+        db.put(b"key", b"v1").await.unwrap();
+        let seq = db.inner.oracle.last_committed_seq();
+        db.put(b"key", b"v2").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
         wait_for_manifest_condition(
             &mut sm,
             |s| {
-                // compact after writing values. include in loop since the on demand scheduler
-                // only runs once per `should_compact`, and memtables might still be getting
-                // flushed (await_durable in the put()'s above only wait for the writes to hit
-                // the WAL before returning).
                 should_compact_l0.store(true, Ordering::SeqCst);
                 s.l0_last_compacted.is_some() && s.l0.is_empty()
             },
             Duration::from_secs(10),
         )
         .await;
-        info!(
-            "2 l0: {} {}",
-            db.inner.state.read().state().core().l0.len(),
-            db.inner.state.read().state().core().compacted.len()
+
+        // without a snapshot or history retention the compaction drops v1
+        let err = db
+            .get_with_options(b"key", &ReadOptions::new().with_as_of_seq(seq))
+            .await
+            .unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Invalid);
+        assert!(err.to_string().contains("no longer retained"), "{err}");
+        assert_eq!(
+            db.get_with_options(b"key", &ReadOptions::new().with_as_of_seq(seq + 1))
+                .await
+                .unwrap(),
+            Some(Bytes::from_static(b"v2"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_put_if_absent() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+
+        db.put_if_absent(b"key", b"value1").await.unwrap();
+        let err = db.put_if_absent(b"key", b"value2").await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Precondition);
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"value1"))
+        );
+
+        // a deleted key is absent again, including once the delete is flushed to L0
+        db.delete(b"key").await.unwrap();
+        db.flush().await.unwrap();
+        db.put_if_absent(b"key", b"value3").await.unwrap();
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"value3"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compare_and_set() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+
+        let err = db.compare_and_set(b"key", b"v1", b"v2").await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Precondition);
+        assert_eq!(db.get(b"key").await.unwrap(), None);
+
+        db.put(b"key", b"v1").await.unwrap();
+        db.flush().await.unwrap();
+        let err = db.compare_and_set(b"key", b"v0", b"v2").await.unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Precondition);
+        db.compare_and_set(b"key", b"v1", b"v2").await.unwrap();
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"v2"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_batch_with_failed_precondition_applies_nothing() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+        db.put(b"key1", b"value1").await.unwrap();
+
+        let mut batch = WriteBatch::new();
+        batch.add_precondition(b"key2", Precondition::Absent);
+        batch.add_precondition(b"key1", Precondition::Absent);
+        batch.put(b"key2", b"value2");
+        batch.delete(b"key1");
+        let err = db.write(batch).await.unwrap_err();
+
+        assert_eq!(err.kind(), crate::ErrorKind::Precondition);
+        assert_eq!(
+            db.get(b"key1").await.unwrap(),
+            Some(Bytes::from_static(b"value1"))
+        );
+        assert_eq!(db.get(b"key2").await.unwrap(), None);
+    }
+
+    /// Sends a batch to the write loop with preconditions checked beforehand, if any.
+    async fn send_write_batch(
+        db: &Db,
+        batch: WriteBatch,
+        options: WriteOptions,
+        preconditions: Option<crate::batch_write::CheckedPreconditions>,
+    ) -> Result<(), SlateDBError> {
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        let msg = WriteBatchMessage::WriteBatch {
+            batch,
+            options,
+            preconditions,
+            done: tx,
+        };
+        db.inner
+            .write_notifier
+            .send_safely(db.inner.state.read().closed_result_reader(), msg)?;
+        rx.await?.map(|_| ())
+    }
+
+    #[tokio::test]
+    async fn test_write_loop_rechecks_preconditions_of_keys_written_since_check() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+        let mut batch = WriteBatch::new();
+        batch.add_precondition(b"key", Precondition::Absent);
+        batch.put(b"key", b"value2");
+
+        // the key is written between the check and the write
+        let checked = db.inner.check_preconditions(&batch).await.unwrap();
+        db.put(b"key", b"value1").await.unwrap();
+        let result = send_write_batch(&db, batch.clone(), WriteOptions::default(), checked).await;
+        assert!(matches!(
+            result,
+            Err(SlateDBError::PreconditionFailed { .. })
+        ));
+
+        // the key is written and flushed to L0 between the check and the write
+        db.delete(b"key").await.unwrap();
+        let checked = db.inner.check_preconditions(&batch).await.unwrap();
+        db.put(b"key", b"value1").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        let result = send_write_batch(&db, batch.clone(), WriteOptions::default(), checked).await;
+        assert!(matches!(
+            result,
+            Err(SlateDBError::PreconditionFailed { .. })
+        ));
+
+        // other keys are written between the check and the write
+        db.delete(b"key").await.unwrap();
+        let checked = db.inner.check_preconditions(&batch).await.unwrap();
+        db.put(b"other", b"value").await.unwrap();
+        send_write_batch(&db, batch, WriteOptions::default(), checked)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"value2"))
         );
-        // write another l0
-        db.put(&[b'a'; 32], &[128u8; 32]).await.unwrap();
-        db.put(&[b'm'; 32], &[129u8; 32]).await.unwrap();
-
-        let val = db.get([b'a'; 32]).await.unwrap();
-        assert_eq!(val, Some(Bytes::copy_from_slice(&[128u8; 32])));
-        let val = db.get([b'm'; 32]).await.unwrap();
-        assert_eq!(val, Some(Bytes::copy_from_slice(&[129u8; 32])));
-        for i in 1..4 {
-            info!(
-                "3 l0: {} {}",
-                db.inner.state.read().state().core().l0.len(),
-                db.inner.state.read().state().core().compacted.len()
-            );
-            let val = db.get([b'a' + i; 32]).await.unwrap();
-            assert_eq!(val, Some(Bytes::copy_from_slice(&[1u8 + i; 32])));
-            let val = db.get([b'm' + i; 32]).await.unwrap();
-            assert_eq!(val, Some(Bytes::copy_from_slice(&[13u8 + i; 32])));
-        }
-        for i in 0..4 {
-            let val = db.get([b'f' + i; 32]).await.unwrap();
-            assert_eq!(val, Some(Bytes::copy_from_slice(&[6u8 + i; 32])));
-            let val = db.get([b's' + i; 32]).await.unwrap();
-            assert_eq!(val, Some(Bytes::copy_from_slice(&[19u8 + i; 32])));
-        }
-        let neg_lookup = db.get(b"abc").await;
-        assert!(neg_lookup.unwrap().is_none());
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_should_read_from_compacted_db() {
-        do_test_should_read_compacted_db(test_db_options(
-            0,
-            127,
-            Some(CompactorOptions {
-                poll_interval: Duration::from_millis(100),
-                max_sst_size: 256,
-                max_concurrent_compactions: 1,
-                manifest_update_timeout: Duration::from_secs(300),
-            }),
-        ))
-        .await;
+    #[tokio::test]
+    #[cfg(feature = "wal_disable")]
+    async fn test_failed_precondition_should_not_stop_writes_when_wal_disabled() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(Settings {
+                wal_enabled: false,
+                ..test_db_options(0, 1024, None)
+            })
+            .build()
+            .await
+            .unwrap();
+        let no_await_durable = WriteOptions::new().with_await_durable(false);
+        db.put_with_options(b"key", b"value1", &PutOptions::default(), &no_await_durable)
+            .await
+            .unwrap();
+
+        // the first durable write fails its precondition in the write loop
+        let mut batch = WriteBatch::new();
+        batch.add_precondition(b"key", Precondition::Absent);
+        batch.put(b"key", b"value2");
+        let result = send_write_batch(&db, batch, WriteOptions::default(), None).await;
+        assert!(matches!(
+            result,
+            Err(SlateDBError::PreconditionFailed { .. })
+        ));
+
+        db.put_with_options(b"key", b"value3", &PutOptions::default(), &no_await_durable)
+            .await
+            .unwrap();
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"value3"))
+        );
     }
 
-    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
-    async fn test_should_read_from_compacted_db_no_filters() {
-        do_test_should_read_compacted_db(test_db_options(
-            u32::MAX,
-            127,
-            Some(CompactorOptions {
-                poll_interval: Duration::from_millis(100),
-                manifest_update_timeout: Duration::from_secs(300),
-                max_sst_size: 256,
-                max_concurrent_compactions: 1,
-            }),
-        ))
-        .await
+    #[tokio::test]
+    async fn test_concurrent_put_if_absent_should_have_single_winner() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Arc::new(
+            Db::builder("/tmp/test_kv_store", object_store)
+                .with_settings(test_db_options(0, 1024, None))
+                .build()
+                .await
+                .unwrap(),
+        );
+
+        let handles: Vec<_> = (0..10u8)
+            .map(|i| {
+                let db = db.clone();
+                tokio::spawn(async move { db.put_if_absent(b"key", [i]).await })
+            })
+            .collect();
+        let mut winners = Vec::new();
+        for (i, handle) in handles.into_iter().enumerate() {
+            match handle.await.unwrap() {
+                Ok(()) => winners.push(i as u8),
+                Err(err) => assert_eq!(err.kind(), crate::ErrorKind::Precondition),
+            }
+        }
+
+        assert_eq!(winners.len(), 1);
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::copy_from_slice(&winners))
+        );
     }
 
     #[tokio::test]
@@ -4424,6 +7658,7 @@ mod tests {
                 &PutOptions::default(),
                 &WriteOptions {
                     await_durable: true,
+                    ..WriteOptions::default()
                 },
             )
             .await
@@ -4452,6 +7687,67 @@ mod tests {
         assert_eq!(db2.inner.state.read().state().core().next_wal_sst_id, 5);
     }
 
+    #[tokio::test]
+    async fn test_should_transfer_leadership_without_wal_to_replay() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let db1 = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024 * 1024, None))
+            .build()
+            .await
+            .unwrap();
+        db1.put(b"1", b"1").await.unwrap();
+        db1.put(b"2", b"2").await.unwrap();
+        let last_seq = db1.inner.oracle.last_committed_seq();
+
+        db1.transfer_leadership().await.unwrap();
+        assert!(db1.put(b"3", b"3").await.is_err());
+        assert!(db1.transfer_leadership().await.is_err());
+
+        // the writes are in L0, and the handoff is in the manifest
+        let manifest_store = Arc::new(ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        ));
+        let manifest = StoredManifest::load(manifest_store.clone())
+            .await
+            .unwrap()
+            .manifest()
+            .clone();
+        assert_eq!(manifest.core.last_l0_seq, last_seq);
+        assert_eq!(
+            manifest.pending_handoff(),
+            Some(&WriterHandoff {
+                writer_epoch: manifest.writer_epoch,
+                last_seq,
+            })
+        );
+        let wals_to_replay = db1
+            .inner
+            .table_store
+            .list_wal_ssts(manifest.core.replay_after_wal_id + 1..)
+            .await
+            .unwrap();
+        assert!(wals_to_replay.is_empty());
+
+        // the next writer consumes the handoff
+        let db2 = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024 * 1024, None))
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(db2.get(b"2").await.unwrap(), Some(Bytes::from_static(b"2")));
+        let next_manifest = StoredManifest::load(manifest_store)
+            .await
+            .unwrap()
+            .manifest()
+            .clone();
+        assert_eq!(next_manifest.writer_epoch, manifest.writer_epoch + 1);
+        assert_eq!(next_manifest.writer_handoff, None);
+        db2.close().await.unwrap();
+    }
+
     #[tokio::test]
     async fn test_invalid_clock_progression() {
         // Given:
@@ -4883,6 +8179,7 @@ mod tests {
             let put_option = PutOptions::default();
             let write_option = WriteOptions {
                 await_durable: false,
+                ..WriteOptions::default()
             };
             db.put_with_options(key.as_bytes(), value.clone(), &put_option, &write_option)
                 .await
@@ -4946,9 +8243,15 @@ mod tests {
             l0_sst_size_bytes,
             compactor_options,
             compression_codec: None,
+            min_blob_value_size: None,
             merge_operator: None,
+            compaction_filter: None,
+            prefix_extractor: None,
+            comparator: None,
             object_store_cache_options: ObjectStoreCacheOptions::default(),
             garbage_collector_options: None,
+            write_stall_options: WriteStallOptions::default(),
+            rate_limit_options: RateLimitOptions::default(),
             default_ttl: ttl,
         }
     }
@@ -5087,6 +8390,7 @@ mod tests {
         // do a write and flush memtable only (not wal)
         let write_opts = WriteOptions {
             await_durable: false,
+            ..WriteOptions::default()
         };
         db.put_with_options(&b"foo", &b"bar", &PutOptions::default(), &write_opts)
             .await
@@ -5104,6 +8408,7 @@ mod tests {
                 &ReadOptions {
                     durability_filter: DurabilityLevel::Memory,
                     dirty: false,
+                    ..ReadOptions::default()
                 },
             )
             .await
@@ -5115,6 +8420,7 @@ mod tests {
                 &ReadOptions {
                     durability_filter: DurabilityLevel::Remote,
                     dirty: false,
+                    ..ReadOptions::default()
                 },
             )
             .await
@@ -5283,6 +8589,244 @@ mod tests {
         assert_eq!(result, Some(Bytes::from("abc")));
     }
 
+    #[tokio::test]
+    async fn test_column_family_is_isolated_from_default() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_column_family("users", ColumnFamilyOptions::default())
+            .build()
+            .await
+            .unwrap();
+        let users = db.column_family("users").unwrap();
+
+        db.put(b"key1", b"default").await.unwrap();
+        db.put_cf(&users, b"key1", b"users").await.unwrap();
+        db.put_cf(&users, b"key2", b"users").await.unwrap();
+        db.delete_cf(&users, b"key2").await.unwrap();
+        db.put_cf(&users, b"key3", b"users").await.unwrap();
+
+        assert_eq!(
+            db.get(b"key1").await.unwrap(),
+            Some(Bytes::from_static(b"default"))
+        );
+        assert_eq!(
+            db.get_cf(&users, b"key1").await.unwrap(),
+            Some(Bytes::from_static(b"users"))
+        );
+        assert_eq!(db.get(b"key3").await.unwrap(), None);
+        let mut iter = db.scan_cf::<&[u8], _>(&users, ..).await.unwrap();
+        assert_eq!(
+            iter.next().await.unwrap().map(|kv| kv.key),
+            Some(Bytes::from_static(b"key1"))
+        );
+        assert_eq!(
+            iter.next().await.unwrap().map(|kv| kv.key),
+            Some(Bytes::from_static(b"key3"))
+        );
+        assert_eq!(iter.next().await.unwrap(), None);
+    }
+
+    #[tokio::test]
+    async fn test_unknown_column_family() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .build()
+            .await
+            .unwrap();
+
+        let err = db.column_family("users").unwrap_err();
+        assert_eq!(err.kind(), crate::ErrorKind::Invalid);
+    }
+
+    #[tokio::test]
+    async fn test_write_batch_across_column_families() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_column_family("users", ColumnFamilyOptions::default())
+            .with_column_family("orders", ColumnFamilyOptions::default())
+            .build()
+            .await
+            .unwrap();
+        let users = db.column_family("users").unwrap();
+        let orders = db.column_family("orders").unwrap();
+
+        let mut batch = WriteBatch::new();
+        batch.put(b"key", b"default");
+        batch.put_cf(&users, b"key", b"users");
+        batch.put_cf(&orders, b"key", b"orders");
+        db.write(batch).await.unwrap();
+
+        let txn = db.begin(IsolationLevel::Snapshot).await.unwrap();
+        txn.put_cf(&users, b"txn", b"users").unwrap();
+        txn.put_cf(&orders, b"txn", b"orders").unwrap();
+        txn.commit().await.unwrap();
+
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"default"))
+        );
+        for (cf, value) in [(&users, "users"), (&orders, "orders")] {
+            assert_eq!(db.get_cf(cf, b"key").await.unwrap(), Some(value.into()));
+            assert_eq!(db.get_cf(cf, b"txn").await.unwrap(), Some(value.into()));
+        }
+    }
+
+    #[tokio::test]
+    async fn test_column_family_should_recover_from_wal() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .with_column_family("users", ColumnFamilyOptions::default())
+            .build()
+            .await
+            .unwrap();
+        let users = db.column_family("users").unwrap();
+        db.put(b"key", b"default").await.unwrap();
+        db.put_cf(&users, b"key", b"users").await.unwrap();
+        db.close().await.unwrap();
+
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(0, 1024, None))
+            .with_column_family("users", ColumnFamilyOptions::default())
+            .build()
+            .await
+            .unwrap();
+        let recovered = db.column_family("users").unwrap();
+
+        assert_eq!(recovered, users);
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"default"))
+        );
+        assert_eq!(
+            db.get_cf(&users, b"key").await.unwrap(),
+            Some(Bytes::from_static(b"users"))
+        );
+    }
+
+    #[tokio::test]
+    async fn test_column_family_options() {
+        let clock = Arc::new(TestClock::new());
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let db = Db::builder("/tmp/test_kv_store", object_store)
+            .with_settings(test_db_options(0, 1024, None))
+            .with_logical_clock(clock.clone())
+            .with_column_family(
+                "sessions",
+                ColumnFamilyOptions::default().with_default_ttl(Some(100)),
+            )
+            .with_column_family(
+                "counters",
+                ColumnFamilyOptions::default()
+                    .with_merge_operator(Arc::new(StringConcatMergeOperator)),
+            )
+            .build()
+            .await
+            .unwrap();
+        let sessions = db.column_family("sessions").unwrap();
+        let counters = db.column_family("counters").unwrap();
+
+        db.put(b"key", b"default").await.unwrap();
+        db.put_cf(&sessions, b"key", b"session").await.unwrap();
+        db.merge_cf(&counters, b"key", b"a").await.unwrap();
+        db.merge_cf(&counters, b"key", b"b").await.unwrap();
+
+        assert_eq!(
+            db.get_cf(&counters, b"key").await.unwrap(),
+            Some(Bytes::from_static(b"ab"))
+        );
+        clock.ticker.store(100, Ordering::SeqCst);
+        assert_eq!(db.get_cf(&sessions, b"key").await.unwrap(), None);
+        assert_eq!(
+            db.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"default"))
+        );
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_column_family_should_flush_and_compact_separately() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = "/tmp/test_kv_store";
+        let should_compact_l0 = Arc::new(AtomicBool::new(false));
+        let this_should_compact_l0 = should_compact_l0.clone();
+        let compaction_scheduler = Arc::new(OnDemandCompactionSchedulerSupplier::new(Arc::new(
+            move |state| {
+                !state.db_state().l0.is_empty() && this_should_compact_l0.load(Ordering::SeqCst)
+            },
+        )));
+        let db = Db::builder(path, object_store.clone())
+            .with_settings(test_db_options(
+                0,
+                1024,
+                Some(CompactorOptions {
+                    poll_interval: Duration::from_millis(100),
+                    ..CompactorOptions::default()
+                }),
+            ))
+            .with_column_family("users", ColumnFamilyOptions::default())
+            .with_compaction_scheduler_supplier(compaction_scheduler)
+            .build()
+            .await
+            .unwrap();
+        let users = db.column_family("users").unwrap();
+        let ms = ManifestStore::new(
+            &Path::from(path),
+            object_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        );
+        let mut sm = StoredManifest::load(Arc::new(ms)).await.unwrap();
+
+        db.put_cf(&users, b"key", b"users").await.unwrap();
+        db.flush_with_options(FlushOptions {
+            flush_type: FlushType::MemTable,
+        })
+        .await
+        .unwrap();
+        {
+            let state = db.inner.state.read().state();
+            let core = state.core();
+            assert!(core.l0.is_empty());
+            assert_eq!(core.column_family_l0(users.id).len(), 1);
+        }
+
+        should_compact_l0.store(true, Ordering::SeqCst);
+        wait_for_manifest_condition(
+            &mut sm,
+            |s| {
+                s.column_family_l0(users.id).is_empty()
+                    && s.column_family_compacted(users.id).len() == 1
+            },
+            Duration::from_secs(10),
+        )
+        .await;
+        tokio::time::timeout(Duration::from_secs(10), async {
+            while !db
+                .inner
+                .state
+                .read()
+                .state()
+                .core()
+                .column_family_l0(users.id)
+                .is_empty()
+            {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        assert!(sm.db_state().compacted.is_empty());
+        assert_eq!(
+            db.get_cf(&users, b"key").await.unwrap(),
+            Some(Bytes::from_static(b"users"))
+        );
+        assert_eq!(db.get(b"key").await.unwrap(), None);
+    }
+
     /// Reproduces a race where GC can delete an L0 SST before the manifest
     /// is updated to reference it at the DB level:
     /// 1. New L0 is written
@@ -5324,6 +8868,7 @@ mod tests {
             &PutOptions::default(),
             &WriteOptions {
                 await_durable: false,
+                ..WriteOptions::default()
             },
         )
         .await
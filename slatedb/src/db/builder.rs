@@ -121,14 +121,19 @@ use crate::clock::DefaultLogicalClock;
 use crate::clock::DefaultSystemClock;
 use crate::clock::LogicalClock;
 use crate::clock::SystemClock;
+use crate::column_family::{ColumnFamilies, ColumnFamilyOptions};
+use crate::compaction_filter::CompactionFilterType;
+use crate::compactions_store::CompactionsStore;
 use crate::compactor::CompactorEventHandler;
 use crate::compactor::SizeTieredCompactionSchedulerSupplier;
 use crate::compactor::COMPACTOR_TASK_NAME;
 use crate::compactor::{CompactionSchedulerSupplier, Compactor};
-use crate::compactor_executor::TokioCompactionExecutor;
+use crate::compactor_executor::{CompactionExecutor, TokioCompactionExecutor};
 use crate::compactor_stats::CompactionStats;
+use crate::comparator::{ComparatorType, KeyComparator};
 use crate::config::default_block_cache;
 use crate::config::default_meta_cache;
+use crate::config::CompactionJobQueueOptions;
 use crate::config::CompactorOptions;
 use crate::config::GarbageCollectorOptions;
 use crate::config::SizeTieredCompactionSchedulerOptions;
@@ -137,9 +142,11 @@ use crate::db::Db;
 use crate::db::DbInner;
 use crate::db_cache::SplitCache;
 use crate::db_cache::{DbCache, DbCacheWrapper};
-use crate::db_state::CoreDbState;
+use crate::db_state::{CoreDbState, SsTableId};
 use crate::dispatcher::MessageHandlerExecutor;
+use crate::encryption::EncryptionProviderType;
 use crate::error::SlateDBError;
+use crate::event_listener::{EventListener, EventListeners};
 use crate::garbage_collector::GarbageCollector;
 use crate::garbage_collector::GC_TASK_NAME;
 use crate::manifest::store::{FenceableManifest, ManifestStore, StoredManifest};
@@ -148,7 +155,11 @@ use crate::mem_table_flush::MEMTABLE_FLUSHER_TASK_NAME;
 use crate::merge_operator::MergeOperatorType;
 use crate::object_stores::ObjectStores;
 use crate::paths::PathResolver;
+use crate::prefix_extractor::PrefixExtractorType;
 use crate::rand::DbRand;
+use crate::remote_compaction::{
+    CompactionJobQueue, CompactionJobRunner, CompactionWorker, RemoteCompactionExecutor,
+};
 use crate::retrying_object_store::RetryingObjectStore;
 use crate::sst::SsTableFormat;
 use crate::stats::StatRegistry;
@@ -170,10 +181,17 @@ pub struct DbBuilder<P: Into<Path>> {
     gc_runtime: Option<Handle>,
     compaction_runtime: Option<Handle>,
     compaction_scheduler_supplier: Option<Arc<dyn CompactionSchedulerSupplier>>,
+    compaction_job_runner: Option<Arc<dyn CompactionJobRunner>>,
     fp_registry: Arc<FailPointRegistry>,
     seed: Option<u64>,
     sst_block_size: Option<SstBlockSize>,
     merge_operator: Option<MergeOperatorType>,
+    compaction_filter: Option<CompactionFilterType>,
+    prefix_extractor: Option<PrefixExtractorType>,
+    comparator: Option<ComparatorType>,
+    column_families: HashMap<String, ColumnFamilyOptions>,
+    encryption_provider: Option<EncryptionProviderType>,
+    event_listeners: EventListeners,
 }
 
 impl<P: Into<Path>> DbBuilder<P> {
@@ -190,10 +208,17 @@ impl<P: Into<Path>> DbBuilder<P> {
             gc_runtime: None,
             compaction_runtime: None,
             compaction_scheduler_supplier: None,
+            compaction_job_runner: None,
             fp_registry: Arc::new(FailPointRegistry::new()),
             seed: None,
             sst_block_size: None,
             merge_operator: None,
+            compaction_filter: None,
+            prefix_extractor: None,
+            comparator: None,
+            column_families: HashMap::new(),
+            encryption_provider: None,
+            event_listeners: EventListeners::default(),
         }
     }
 
@@ -256,6 +281,16 @@ impl<P: Into<Path>> DbBuilder<P> {
         self
     }
 
+    /// Sets the runner the compactor hands its compaction jobs to, instead of running them
+    /// on the compaction runtime. See [`crate::remote_compaction`].
+    pub fn with_compaction_job_runner(
+        mut self,
+        compaction_job_runner: Arc<dyn CompactionJobRunner>,
+    ) -> Self {
+        self.compaction_job_runner = Some(compaction_job_runner);
+        self
+    }
+
     /// Sets the fail point registry to use for the database.
     pub fn with_fp_registry(mut self, fp_registry: Arc<FailPointRegistry>) -> Self {
         self.fp_registry = fp_registry;
@@ -308,8 +343,113 @@ impl<P: Into<Path>> DbBuilder<P> {
         self
     }
 
+    /// Sets the compaction filter to use for the database. The compaction filter is
+    /// called for each value that survives a compaction, and can keep, drop, or
+    /// replace it.
+    ///
+    /// # Arguments
+    ///
+    /// * `compaction_filter` - An Arc-wrapped compaction filter implementation.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for chaining.
+    pub fn with_compaction_filter(mut self, compaction_filter: CompactionFilterType) -> Self {
+        self.compaction_filter = Some(compaction_filter);
+        self
+    }
+
+    /// Sets the prefix extractor to use for the database. The prefixes it extracts are
+    /// added to the bloom filters of L0 SSTs and sorted runs, so scans with
+    /// [`crate::config::ScanOptions::prefix`] can skip the SSTs that don't contain the
+    /// prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix_extractor` - An Arc-wrapped prefix extractor implementation.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for chaining.
+    pub fn with_prefix_extractor(mut self, prefix_extractor: PrefixExtractorType) -> Self {
+        self.prefix_extractor = Some(prefix_extractor);
+        self
+    }
+
+    /// Sets the comparator that defines the order of the keys of the database. The name
+    /// of the comparator is recorded in the manifest when the database is created, and
+    /// opening the database with a comparator of a different name fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `comparator` - An Arc-wrapped comparator implementation.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for chaining.
+    pub fn with_comparator(mut self, comparator: ComparatorType) -> Self {
+        self.comparator = Some(comparator);
+        self
+    }
+
+    /// Sets the provider of the keys the database encrypts the data it writes to object
+    /// storage with. SSTs, blob files, and the manifest and compactions files are
+    /// encrypted with the provider's current key, and data written before encryption
+    /// was enabled is still read. See [`crate::EncryptionProvider`].
+    ///
+    /// # Arguments
+    ///
+    /// * `encryption_provider` - An Arc-wrapped encryption provider implementation.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for chaining.
+    pub fn with_encryption_provider(mut self, encryption_provider: EncryptionProviderType) -> Self {
+        self.encryption_provider = Some(encryption_provider);
+        self
+    }
+
+    /// Adds a column family to the database. Column families are named keyspaces with
+    /// their own memtable, L0 SSTs and sorted runs, read and written with methods such
+    /// as [`Db::put_cf`] and [`Db::get_cf`]. The first time the database is opened with
+    /// a column family, the column family is recorded in the manifest.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the column family.
+    /// * `options` - The options of the column family.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for chaining.
+    pub fn with_column_family(
+        mut self,
+        name: impl Into<String>,
+        options: ColumnFamilyOptions,
+    ) -> Self {
+        self.column_families.insert(name.into(), options);
+        self
+    }
+
+    /// Adds a listener for the lifecycle events of the database. The listener is also
+    /// notified of the events of the compactor and the garbage collector that run with
+    /// the database. Listeners are called in the order they were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `listener` - An Arc-wrapped event listener implementation.
+    ///
+    /// # Returns
+    ///
+    /// The builder instance for chaining.
+    pub fn with_event_listener(mut self, listener: Arc<dyn EventListener>) -> Self {
+        self.event_listeners.add(listener);
+        self
+    }
+
     /// Builds and opens the database.
     pub async fn build(self) -> Result<Db, crate::Error> {
+        self.settings.validate()?;
         let path = self.path.into();
         // TODO: proper URI generation, for now it works just as a flag
         let wal_object_store_uri = self.wal_object_store.as_ref().map(|_| String::new());
@@ -353,6 +493,13 @@ impl<P: Into<Path>> DbBuilder<P> {
             .unwrap_or_else(|| Arc::new(DefaultSystemClock::new()));
 
         let merge_operator = self.merge_operator.or(self.settings.merge_operator.clone());
+        let compaction_filter = self
+            .compaction_filter
+            .or(self.settings.compaction_filter.clone());
+        let prefix_extractor = self
+            .prefix_extractor
+            .or(self.settings.prefix_extractor.clone());
+        let comparator = KeyComparator::new(self.comparator.or(self.settings.comparator.clone()));
 
         // Setup the components
         let stat_registry = Arc::new(StatRegistry::new());
@@ -360,7 +507,11 @@ impl<P: Into<Path>> DbBuilder<P> {
             min_filter_keys: self.settings.min_filter_keys,
             filter_bits_per_key: self.settings.filter_bits_per_key,
             compression_codec: self.settings.compression_codec,
+            min_blob_value_size: self.settings.min_blob_value_size,
             block_size: self.sst_block_size.unwrap_or_default().as_bytes(),
+            prefix_extractor: prefix_extractor.clone(),
+            comparator: comparator.clone(),
+            encryption_provider: self.encryption_provider.clone(),
             ..SsTableFormat::default()
         };
 
@@ -386,6 +537,7 @@ impl<P: Into<Path>> DbBuilder<P> {
                     self.settings.object_store_cache_options.part_size_bytes,
                     self.settings.object_store_cache_options.cache_puts,
                     stats.clone(),
+                    system_clock.clone(),
                 )?;
                 cached_object_store.start_evictor().await;
                 Some(cached_object_store)
@@ -398,10 +550,11 @@ impl<P: Into<Path>> DbBuilder<P> {
         };
 
         // Setup the manifest store and load latest manifest
-        let manifest_store = Arc::new(ManifestStore::new(
+        let manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
             &path,
             maybe_cached_main_object_store.clone(),
             system_clock.clone(),
+            self.encryption_provider.clone(),
         ));
         let latest_manifest = StoredManifest::try_load(manifest_store.clone()).await?;
 
@@ -410,24 +563,37 @@ impl<P: Into<Path>> DbBuilder<P> {
             if latest_manifest.db_state().wal_object_store_uri != wal_object_store_uri {
                 return Err(SlateDBError::WalStoreReconfigurationError.into());
             }
+            comparator.check_matches(latest_manifest.db_state().comparator.as_deref())?;
         }
 
-        // Extract external SSTs from manifest if available
-        let external_ssts = match &latest_manifest {
-            Some(latest_stored_manifest) => {
-                let mut external_ssts = HashMap::new();
-                for external_db in &latest_stored_manifest.manifest().external_dbs {
-                    for id in &external_db.sst_ids {
-                        external_ssts.insert(*id, external_db.path.clone().into());
+        if let Some(handoff) = latest_manifest
+            .as_ref()
+            .and_then(|manifest| manifest.manifest().pending_handoff())
+        {
+            info!(
+                "opening db handed off by the previous writer [writer_epoch={}, last_seq={}]",
+                handoff.writer_epoch, handoff.last_seq
+            );
+        }
+
+        // Extract external SSTs and blob files from manifest if available
+        let mut external_ssts = HashMap::new();
+        let mut external_blobs = HashMap::new();
+        if let Some(latest_stored_manifest) = &latest_manifest {
+            for external_db in &latest_stored_manifest.manifest().external_dbs {
+                for id in &external_db.sst_ids {
+                    let external_path: Path = external_db.path.clone().into();
+                    if let SsTableId::Compacted(ulid) = id {
+                        external_blobs.insert(*ulid, external_path.clone());
                     }
+                    external_ssts.insert(*id, external_path);
                 }
-                external_ssts
             }
-            None => HashMap::new(),
-        };
+        }
 
         // Create path resolver and table store
-        let path_resolver = PathResolver::new_with_external_ssts(path.clone(), external_ssts);
+        let path_resolver = PathResolver::new_with_external_ssts(path.clone(), external_ssts)
+            .with_external_blobs(external_blobs);
         let table_store = Arc::new(TableStore::new_with_fp_registry(
             ObjectStores::new(
                 maybe_cached_main_object_store.clone(),
@@ -456,7 +622,8 @@ impl<P: Into<Path>> DbBuilder<P> {
         let stored_manifest = match latest_manifest {
             Some(manifest) => manifest,
             None => {
-                let state = CoreDbState::new_with_wal_object_store(wal_object_store_uri);
+                let mut state = CoreDbState::new_with_wal_object_store(wal_object_store_uri);
+                state.comparator = comparator.name().map(String::from);
                 StoredManifest::create_new_db(manifest_store.clone(), state).await?
             }
         };
@@ -467,6 +634,18 @@ impl<P: Into<Path>> DbBuilder<P> {
         )
         .await?;
 
+        // Record new column families in the manifest
+        let mut dirty = manifest.prepare_dirty()?;
+        if dirty
+            .value
+            .core
+            .add_column_families(self.column_families.keys())
+        {
+            manifest.update(dirty).await?;
+        }
+        let column_families =
+            ColumnFamilies::new(manifest.prepare_dirty()?.core(), &self.column_families);
+
         // Setup communication channels
         let (memtable_flush_tx, memtable_flush_rx) = tokio::sync::mpsc::unbounded_channel();
         let (write_tx, write_rx) = tokio::sync::mpsc::unbounded_channel();
@@ -474,6 +653,9 @@ impl<P: Into<Path>> DbBuilder<P> {
         // Create the database inner state
         let mut settings = self.settings.clone();
         settings.merge_operator = merge_operator.clone();
+        settings.compaction_filter = compaction_filter.clone();
+        settings.prefix_extractor = prefix_extractor;
+        settings.comparator = comparator.comparator().cloned();
         let inner = Arc::new(
             DbInner::new(
                 settings,
@@ -481,12 +663,15 @@ impl<P: Into<Path>> DbBuilder<P> {
                 system_clock.clone(),
                 rand.clone(),
                 table_store.clone(),
+                manifest_store.clone(),
                 manifest.prepare_dirty()?,
                 memtable_flush_tx,
                 write_tx,
                 stat_registry,
                 self.fp_registry.clone(),
                 merge_operator.clone(),
+                column_families,
+                self.event_listeners.clone(),
             )
             .await?,
         );
@@ -502,6 +687,16 @@ impl<P: Into<Path>> DbBuilder<P> {
             inner.clone().state.read().closed_result(),
             system_clock.clone(),
         ));
+        // Notify the listeners if a background task closes the database
+        if !self.event_listeners.is_empty() {
+            let mut closed_result = inner.state.read().closed_result_reader();
+            let event_listeners = self.event_listeners.clone();
+            tokio_handle.spawn(async move {
+                if let Some(result) = closed_result.try_await_value().await {
+                    event_listeners.notify_closed(&result);
+                }
+            });
+        }
         if inner.wal_enabled {
             inner.wal_buffer.init(task_executor.clone()).await?;
         };
@@ -530,9 +725,18 @@ impl<P: Into<Path>> DbBuilder<P> {
             None,
         ));
 
+        let compactions_store = Arc::new(CompactionsStore::new_with_encryption_provider(
+            &path,
+            retrying_main_object_store.clone(),
+            self.encryption_provider.clone(),
+        ));
+
         // To keep backwards compatibility, check if the compaction_scheduler_supplier or compactor_options are set.
         // If either are set, we need to initialize the compactor.
-        if self.compaction_scheduler_supplier.is_some() || self.settings.compactor_options.is_some()
+        let mut compactor_tx = None;
+        if self.compaction_scheduler_supplier.is_some()
+            || self.compaction_job_runner.is_some()
+            || self.settings.compactor_options.is_some()
         {
             let compactor_options = Arc::new(self.settings.compactor_options.unwrap_or_default());
             let compaction_handle = self
@@ -542,27 +746,42 @@ impl<P: Into<Path>> DbBuilder<P> {
                 .compaction_scheduler_supplier
                 .unwrap_or_else(default_compaction_scheduler_supplier);
             let (tx, rx) = tokio::sync::mpsc::unbounded_channel();
+            compactor_tx = Some(tx.clone());
             let scheduler = Arc::from(scheduler_supplier.compaction_scheduler(&compactor_options));
             let stats = Arc::new(CompactionStats::new(inner.stat_registry.clone()));
-            let executor = Arc::new(TokioCompactionExecutor::new(
-                compaction_handle,
-                compactor_options.clone(),
-                tx,
-                uncached_table_store.clone(),
-                rand.clone(),
-                stats.clone(),
-                system_clock.clone(),
-                manifest_store.clone(),
-                merge_operator.clone(),
-            ));
+            let executor: Arc<dyn CompactionExecutor + Send + Sync> =
+                match self.compaction_job_runner {
+                    Some(runner) => Arc::new(RemoteCompactionExecutor::new(
+                        compaction_handle,
+                        runner,
+                        tx,
+                        stats.clone(),
+                    )),
+                    None => Arc::new(TokioCompactionExecutor::new(
+                        compaction_handle,
+                        compactor_options.clone(),
+                        tx,
+                        uncached_table_store.clone(),
+                        rand.clone(),
+                        stats.clone(),
+                        system_clock.clone(),
+                        manifest_store.clone(),
+                        merge_operator.clone(),
+                        compaction_filter.clone(),
+                        self.column_families.clone(),
+                    )),
+                };
             let handler = CompactorEventHandler::new(
                 manifest_store.clone(),
+                compactions_store.clone(),
                 compactor_options.clone(),
                 scheduler,
                 executor,
                 rand.clone(),
                 stats.clone(),
                 system_clock.clone(),
+                uncached_table_store.comparator().clone(),
+                self.event_listeners.clone(),
             )
             .await?;
             task_executor.add_handler(
@@ -575,17 +794,21 @@ impl<P: Into<Path>> DbBuilder<P> {
 
         // To keep backwards compatibility, check if the gc_runtime or garbage_collector_options are set.
         // If either are set, we need to initialize the garbage collector.
+        let mut gc_tx = None;
         if self.settings.garbage_collector_options.is_some() || self.gc_runtime.is_some() {
             let gc_options = self.settings.garbage_collector_options.unwrap_or_default();
             let gc = GarbageCollector::new(
                 manifest_store.clone(),
+                compactions_store.clone(),
                 uncached_table_store.clone(),
                 gc_options,
                 inner.stat_registry.clone(),
                 system_clock.clone(),
+                self.event_listeners.clone(),
             );
-            // Garbage collector only uses tickers, so pass in a dummy rx channel
-            let (_, rx) = mpsc::unbounded_channel();
+            // Garbage collector runs on tickers, its channel only receives option updates
+            let (tx, rx) = mpsc::unbounded_channel();
+            gc_tx = Some(tx);
             task_executor.add_handler(GC_TASK_NAME.to_string(), Box::new(gc), rx, &tokio_handle)?;
         }
 
@@ -606,6 +829,8 @@ impl<P: Into<Path>> DbBuilder<P> {
         Ok(Db {
             inner,
             task_executor,
+            compactor_tx,
+            gc_tx,
         })
     }
 }
@@ -619,6 +844,8 @@ pub struct AdminBuilder<P: Into<Path>> {
     wal_object_store: Option<Arc<dyn ObjectStore>>,
     system_clock: Arc<dyn SystemClock>,
     rand: Arc<DbRand>,
+    comparator: Option<ComparatorType>,
+    encryption_provider: Option<EncryptionProviderType>,
 }
 
 impl<P: Into<Path>> AdminBuilder<P> {
@@ -630,6 +857,8 @@ impl<P: Into<Path>> AdminBuilder<P> {
             wal_object_store: None,
             system_clock: Arc::new(DefaultSystemClock::new()),
             rand: Arc::new(DbRand::default()),
+            comparator: None,
+            encryption_provider: None,
         }
     }
 
@@ -655,6 +884,20 @@ impl<P: Into<Path>> AdminBuilder<P> {
         self
     }
 
+    /// Sets the comparator of the database, which administrative functions that look up
+    /// keys, such as [`Admin::key_history`], use to search SSTs.
+    pub fn with_comparator(mut self, comparator: ComparatorType) -> Self {
+        self.comparator = Some(comparator);
+        self
+    }
+
+    /// Sets the provider of the keys the database is encrypted with, which administrative
+    /// functions use to read its manifests and SSTs.
+    pub fn with_encryption_provider(mut self, encryption_provider: EncryptionProviderType) -> Self {
+        self.encryption_provider = Some(encryption_provider);
+        self
+    }
+
     /// Builds and returns an Admin instance.
     pub fn build(self) -> Admin {
         // No retrying object stores here, since we don't want to retry admin operations
@@ -663,6 +906,8 @@ impl<P: Into<Path>> AdminBuilder<P> {
             object_stores: ObjectStores::new(self.main_object_store, self.wal_object_store),
             system_clock: self.system_clock,
             rand: self.rand,
+            comparator: KeyComparator::new(self.comparator),
+            encryption_provider: self.encryption_provider,
         }
     }
 }
@@ -677,6 +922,8 @@ pub struct GarbageCollectorBuilder<P: Into<Path>> {
     options: GarbageCollectorOptions,
     stat_registry: Arc<StatRegistry>,
     system_clock: Arc<dyn SystemClock>,
+    encryption_provider: Option<EncryptionProviderType>,
+    event_listeners: EventListeners,
 }
 
 impl<P: Into<Path>> GarbageCollectorBuilder<P> {
@@ -688,6 +935,8 @@ impl<P: Into<Path>> GarbageCollectorBuilder<P> {
             options: GarbageCollectorOptions::default(),
             stat_registry: Arc::new(StatRegistry::new()),
             system_clock: Arc::new(DefaultSystemClock::default()),
+            encryption_provider: None,
+            event_listeners: EventListeners::default(),
         }
     }
 
@@ -717,6 +966,20 @@ impl<P: Into<Path>> GarbageCollectorBuilder<P> {
         self
     }
 
+    /// Sets the provider of the keys the database is encrypted with, which the garbage
+    /// collector uses to read its manifests and SSTs.
+    pub fn with_encryption_provider(mut self, encryption_provider: EncryptionProviderType) -> Self {
+        self.encryption_provider = Some(encryption_provider);
+        self
+    }
+
+    /// Adds a listener for the SSTs the garbage collector deletes.
+    #[allow(unused)]
+    pub fn with_event_listener(mut self, listener: Arc<dyn EventListener>) -> Self {
+        self.event_listeners.add(listener);
+        self
+    }
+
     /// Builds and returns a GarbageCollector instance.
     pub fn build(self) -> GarbageCollector {
         let path: Path = self.path.into();
@@ -724,26 +987,38 @@ impl<P: Into<Path>> GarbageCollectorBuilder<P> {
         let retrying_wal_object_store = self
             .wal_object_store
             .map(|s| Arc::new(RetryingObjectStore::new(s)) as Arc<dyn ObjectStore>);
-        let manifest_store = Arc::new(ManifestStore::new(
+        let manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
             &path,
             retrying_main_object_store.clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
+        ));
+        let compactions_store = Arc::new(CompactionsStore::new_with_encryption_provider(
+            &path,
+            retrying_main_object_store.clone(),
+            self.encryption_provider.clone(),
         ));
         let table_store = Arc::new(TableStore::new(
             ObjectStores::new(
                 retrying_main_object_store.clone(),
                 retrying_wal_object_store.clone(),
             ),
-            SsTableFormat::default(), // read only SSTs can use default
+            // read only SSTs can use default
+            SsTableFormat {
+                encryption_provider: self.encryption_provider,
+                ..SsTableFormat::default()
+            },
             path,
             None, // no need for cache in GC
         ));
         GarbageCollector::new(
             manifest_store,
+            compactions_store,
             table_store,
             self.options,
             self.stat_registry,
             self.system_clock,
+            self.event_listeners,
         )
     }
 }
@@ -757,11 +1032,19 @@ pub struct CompactorBuilder<P: Into<Path>> {
     tokio_handle: Handle,
     options: CompactorOptions,
     scheduler_supplier: Option<Arc<dyn CompactionSchedulerSupplier>>,
+    job_runner: Option<Arc<dyn CompactionJobRunner>>,
     rand: Arc<DbRand>,
     stat_registry: Arc<StatRegistry>,
     system_clock: Arc<dyn SystemClock>,
     closed_result: WatchableOnceCell<Result<(), SlateDBError>>,
     merge_operator: Option<MergeOperatorType>,
+    compaction_filter: Option<CompactionFilterType>,
+    prefix_extractor: Option<PrefixExtractorType>,
+    comparator: Option<ComparatorType>,
+    min_blob_value_size: Option<usize>,
+    column_families: HashMap<String, ColumnFamilyOptions>,
+    encryption_provider: Option<EncryptionProviderType>,
+    event_listeners: EventListeners,
 }
 
 #[allow(unused)]
@@ -773,11 +1056,19 @@ impl<P: Into<Path>> CompactorBuilder<P> {
             tokio_handle: Handle::current(),
             options: CompactorOptions::default(),
             scheduler_supplier: None,
+            job_runner: None,
             rand: Arc::new(DbRand::default()),
             stat_registry: Arc::new(StatRegistry::new()),
             system_clock: Arc::new(DefaultSystemClock::default()),
             closed_result: WatchableOnceCell::new(),
             merge_operator: None,
+            compaction_filter: None,
+            prefix_extractor: None,
+            comparator: None,
+            min_blob_value_size: None,
+            column_families: HashMap::new(),
+            encryption_provider: None,
+            event_listeners: EventListeners::default(),
         }
     }
 
@@ -822,24 +1113,95 @@ impl<P: Into<Path>> CompactorBuilder<P> {
         self
     }
 
+    /// Sets the runner the compactor hands its compaction jobs to, instead of running them
+    /// on the tokio handle. See [`crate::remote_compaction`].
+    pub fn with_job_runner(mut self, job_runner: Arc<dyn CompactionJobRunner>) -> Self {
+        self.job_runner = Some(job_runner);
+        self
+    }
+
     /// Sets the merge operator to use for the compactor.
     pub fn with_merge_operator(mut self, merge_operator: MergeOperatorType) -> Self {
         self.merge_operator = Some(merge_operator);
         self
     }
 
+    /// Sets the compaction filter to use for the compactor.
+    pub fn with_compaction_filter(mut self, compaction_filter: CompactionFilterType) -> Self {
+        self.compaction_filter = Some(compaction_filter);
+        self
+    }
+
+    /// Sets the prefix extractor whose prefixes the compactor adds to the bloom filters of
+    /// the SSTs it writes. This should match the prefix extractor of the database.
+    pub fn with_prefix_extractor(mut self, prefix_extractor: PrefixExtractorType) -> Self {
+        self.prefix_extractor = Some(prefix_extractor);
+        self
+    }
+
+    /// Sets the minimum size of the values the compactor stores in blob files instead of in
+    /// the SSTs it writes. This should match [`Settings::min_blob_value_size`] of the
+    /// database.
+    pub fn with_min_blob_value_size(mut self, min_blob_value_size: usize) -> Self {
+        self.min_blob_value_size = Some(min_blob_value_size);
+        self
+    }
+
+    /// Sets the comparator the compactor orders keys with. This must match the comparator
+    /// of the database.
+    pub fn with_comparator(mut self, comparator: ComparatorType) -> Self {
+        self.comparator = Some(comparator);
+        self
+    }
+
+    /// Sets the options of a column family, which are used to compact its data.
+    /// Column families without options are compacted with the default options.
+    pub fn with_column_family(
+        mut self,
+        name: impl Into<String>,
+        options: ColumnFamilyOptions,
+    ) -> Self {
+        self.column_families.insert(name.into(), options);
+        self
+    }
+
+    /// Sets the provider of the keys the compactor encrypts the SSTs it writes with. This
+    /// must be able to decrypt the data of the database.
+    pub fn with_encryption_provider(mut self, encryption_provider: EncryptionProviderType) -> Self {
+        self.encryption_provider = Some(encryption_provider);
+        self
+    }
+
+    /// Adds a listener for the compactions of the compactor and the manifests it writes.
+    pub fn with_event_listener(mut self, listener: Arc<dyn EventListener>) -> Self {
+        self.event_listeners.add(listener);
+        self
+    }
+
     /// Builds and returns a Compactor instance.
     pub fn build(self) -> Compactor {
         let path: Path = self.path.into();
         let retrying_main_object_store = Arc::new(RetryingObjectStore::new(self.main_object_store));
-        let manifest_store = Arc::new(ManifestStore::new(
+        let manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
             &path,
             retrying_main_object_store.clone(),
             self.system_clock.clone(),
+            self.encryption_provider.clone(),
+        ));
+        let compactions_store = Arc::new(CompactionsStore::new_with_encryption_provider(
+            &path,
+            retrying_main_object_store.clone(),
+            self.encryption_provider.clone(),
         ));
         let table_store = Arc::new(TableStore::new(
             ObjectStores::new(retrying_main_object_store.clone(), None),
-            SsTableFormat::default(), // read only SSTs can use default
+            SsTableFormat {
+                prefix_extractor: self.prefix_extractor,
+                comparator: KeyComparator::new(self.comparator),
+                min_blob_value_size: self.min_blob_value_size,
+                encryption_provider: self.encryption_provider,
+                ..SsTableFormat::default()
+            },
             path,
             None, // no need for cache in GC
         ));
@@ -850,15 +1212,184 @@ impl<P: Into<Path>> CompactorBuilder<P> {
 
         Compactor::new(
             manifest_store,
+            compactions_store,
             table_store,
             self.options,
             scheduler_supplier,
+            self.job_runner,
             self.tokio_handle,
             self.rand,
             self.stat_registry,
             self.system_clock,
             self.closed_result,
             self.merge_operator,
+            self.compaction_filter,
+            self.column_families,
+            self.event_listeners,
+        )
+    }
+}
+
+/// Builder for creating new CompactionWorker instances.
+///
+/// This provides a fluent API for configuring a CompactionWorker object.
+pub struct CompactionWorkerBuilder<P: Into<Path>> {
+    path: P,
+    main_object_store: Arc<dyn ObjectStore>,
+    options: CompactorOptions,
+    queue_options: CompactionJobQueueOptions,
+    rand: Arc<DbRand>,
+    stat_registry: Arc<StatRegistry>,
+    system_clock: Arc<dyn SystemClock>,
+    merge_operator: Option<MergeOperatorType>,
+    compaction_filter: Option<CompactionFilterType>,
+    prefix_extractor: Option<PrefixExtractorType>,
+    comparator: Option<ComparatorType>,
+    min_blob_value_size: Option<usize>,
+    column_families: HashMap<String, ColumnFamilyOptions>,
+    encryption_provider: Option<EncryptionProviderType>,
+}
+
+impl<P: Into<Path>> CompactionWorkerBuilder<P> {
+    pub fn new(path: P, main_object_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            path,
+            main_object_store,
+            options: CompactorOptions::default(),
+            queue_options: CompactionJobQueueOptions::default(),
+            rand: Arc::new(DbRand::default()),
+            stat_registry: Arc::new(StatRegistry::new()),
+            system_clock: Arc::new(DefaultSystemClock::default()),
+            merge_operator: None,
+            compaction_filter: None,
+            prefix_extractor: None,
+            comparator: None,
+            min_blob_value_size: None,
+            column_families: HashMap::new(),
+            encryption_provider: None,
+        }
+    }
+
+    /// Sets the compactor options the worker runs jobs with. These should match the
+    /// options of the compactor that submits the jobs.
+    pub fn with_options(mut self, options: CompactorOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the options of the compaction job queue.
+    pub fn with_queue_options(mut self, queue_options: CompactionJobQueueOptions) -> Self {
+        self.queue_options = queue_options;
+        self
+    }
+
+    /// Sets the stats registry to use for the worker.
+    pub fn with_stat_registry(mut self, stat_registry: Arc<StatRegistry>) -> Self {
+        self.stat_registry = stat_registry;
+        self
+    }
+
+    /// Sets the system clock to use for the worker.
+    pub fn with_system_clock(mut self, system_clock: Arc<dyn SystemClock>) -> Self {
+        self.system_clock = system_clock;
+        self
+    }
+
+    /// Sets the random number generator to use for the worker.
+    pub fn with_rand(mut self, rand: Arc<DbRand>) -> Self {
+        self.rand = rand;
+        self
+    }
+
+    /// Sets the merge operator to use for the worker.
+    pub fn with_merge_operator(mut self, merge_operator: MergeOperatorType) -> Self {
+        self.merge_operator = Some(merge_operator);
+        self
+    }
+
+    /// Sets the compaction filter to use for the worker.
+    pub fn with_compaction_filter(mut self, compaction_filter: CompactionFilterType) -> Self {
+        self.compaction_filter = Some(compaction_filter);
+        self
+    }
+
+    /// Sets the prefix extractor whose prefixes the worker adds to the bloom filters of
+    /// the SSTs it writes. This should match the prefix extractor of the database.
+    pub fn with_prefix_extractor(mut self, prefix_extractor: PrefixExtractorType) -> Self {
+        self.prefix_extractor = Some(prefix_extractor);
+        self
+    }
+
+    /// Sets the minimum size of the values the worker stores in blob files instead of in
+    /// the SSTs it writes. This should match [`Settings::min_blob_value_size`] of the
+    /// database.
+    pub fn with_min_blob_value_size(mut self, min_blob_value_size: usize) -> Self {
+        self.min_blob_value_size = Some(min_blob_value_size);
+        self
+    }
+
+    /// Sets the comparator the worker orders keys with. This must match the comparator of
+    /// the database.
+    pub fn with_comparator(mut self, comparator: ComparatorType) -> Self {
+        self.comparator = Some(comparator);
+        self
+    }
+
+    /// Sets the options of a column family, which are used to compact its data.
+    /// Column families without options are compacted with the default options.
+    pub fn with_column_family(
+        mut self,
+        name: impl Into<String>,
+        options: ColumnFamilyOptions,
+    ) -> Self {
+        self.column_families.insert(name.into(), options);
+        self
+    }
+
+    /// Sets the provider of the keys the worker encrypts the SSTs it writes with. This
+    /// must be able to decrypt the data of the database.
+    pub fn with_encryption_provider(mut self, encryption_provider: EncryptionProviderType) -> Self {
+        self.encryption_provider = Some(encryption_provider);
+        self
+    }
+
+    /// Builds and returns a CompactionWorker instance.
+    pub fn build(self) -> CompactionWorker {
+        let path: Path = self.path.into();
+        // the queue is polled, so missing jobs and results must not be retried
+        let queue = CompactionJobQueue::new(&path, self.main_object_store.clone());
+        let retrying_main_object_store = Arc::new(RetryingObjectStore::new(self.main_object_store));
+        let manifest_store = Arc::new(ManifestStore::new_with_encryption_provider(
+            &path,
+            retrying_main_object_store.clone(),
+            self.system_clock.clone(),
+            self.encryption_provider.clone(),
+        ));
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(retrying_main_object_store.clone(), None),
+            SsTableFormat {
+                prefix_extractor: self.prefix_extractor,
+                comparator: KeyComparator::new(self.comparator),
+                min_blob_value_size: self.min_blob_value_size,
+                encryption_provider: self.encryption_provider,
+                ..SsTableFormat::default()
+            },
+            path,
+            None, // no need for cache in compaction
+        ));
+
+        CompactionWorker::new(
+            queue,
+            self.queue_options,
+            self.options,
+            manifest_store,
+            table_store,
+            self.rand,
+            Arc::new(CompactionStats::new(self.stat_registry)),
+            self.system_clock,
+            self.merge_operator,
+            self.compaction_filter,
+            self.column_families,
         )
     }
 }
@@ -501,13 +501,41 @@ pub mod stats {
                 data_block_miss: Arc::new(Counter::default()),
                 get_error: Arc::new(Counter::default()),
             };
-            registry.register(DB_CACHE_FILTER_HIT, stats.filter_hit.clone());
-            registry.register(DB_CACHE_FILTER_MISS, stats.filter_miss.clone());
-            registry.register(DB_CACHE_INDEX_HIT, stats.index_hit.clone());
-            registry.register(DB_CACHE_INDEX_MISS, stats.index_miss.clone());
-            registry.register(DB_CACHE_DATA_BLOCK_HIT, stats.data_block_hit.clone());
-            registry.register(DB_CACHE_DATA_BLOCK_MISS, stats.data_block_miss.clone());
-            registry.register(DB_CACHE_GET_ERROR, stats.get_error.clone());
+            registry.register(
+                DB_CACHE_FILTER_HIT,
+                "Number of bloom filters read from the block cache.",
+                stats.filter_hit.clone(),
+            );
+            registry.register(
+                DB_CACHE_FILTER_MISS,
+                "Number of bloom filters missing from the block cache.",
+                stats.filter_miss.clone(),
+            );
+            registry.register(
+                DB_CACHE_INDEX_HIT,
+                "Number of SST indexes read from the block cache.",
+                stats.index_hit.clone(),
+            );
+            registry.register(
+                DB_CACHE_INDEX_MISS,
+                "Number of SST indexes missing from the block cache.",
+                stats.index_miss.clone(),
+            );
+            registry.register(
+                DB_CACHE_DATA_BLOCK_HIT,
+                "Number of data blocks read from the block cache.",
+                stats.data_block_hit.clone(),
+            );
+            registry.register(
+                DB_CACHE_DATA_BLOCK_MISS,
+                "Number of data blocks missing from the block cache.",
+                stats.data_block_miss.clone(),
+            );
+            registry.register(
+                DB_CACHE_GET_ERROR,
+                "Number of block cache reads that failed.",
+                stats.get_error.clone(),
+            );
             stats
         }
     }
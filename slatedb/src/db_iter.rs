@@ -1,19 +1,20 @@
 use crate::batch::WriteBatchIterator;
 use crate::bytes_range::BytesRange;
+use crate::comparator::KeyComparator;
+use crate::descending_iter::DescendingIterator;
 use crate::error::SlateDBError;
 use crate::filter_iterator::FilterIterator;
-use crate::iter::{EmptyIterator, KeyValueIterator};
+use crate::iter::{EmptyIterator, IterationOrder, KeyValueIterator};
 use crate::map_iter::MapIterator;
 use crate::merge_iterator::MergeIterator;
 use crate::merge_operator::{
     MergeOperatorIterator, MergeOperatorRequiredIterator, MergeOperatorType,
 };
-use crate::types::{KeyValue, RowEntry, ValueDeletable};
+use crate::types::{KeyValue, KeyValueWithMetadata, RowEntry, ValueDeletable};
 
 use async_trait::async_trait;
 use bytes::Bytes;
 use parking_lot::Mutex;
-use std::ops::RangeBounds;
 use std::sync::Arc;
 
 /// [`DbIteratorRangeTracker'] is used to track the range of keys accessed by a [`DbIterator`].  For
@@ -40,30 +41,34 @@ struct DbIteratorRangeTrackerInner {
     first_key: Option<Bytes>,
     last_key: Option<Bytes>,
     has_data: bool,
+    comparator: KeyComparator,
 }
 
 impl DbIteratorRangeTracker {
-    pub fn new() -> Self {
+    /// Creates a tracker of keys ordered by `comparator`.
+    pub(crate) fn new(comparator: KeyComparator) -> Self {
         Self {
             inner: Mutex::new(DbIteratorRangeTrackerInner {
                 first_key: None,
                 last_key: None,
                 has_data: false,
+                comparator,
             }),
         }
     }
 
     pub fn track_key(&self, key: &Bytes) {
         let mut inner = self.inner.lock();
+        let comparator = inner.comparator.clone();
 
         inner.first_key = Some(match &inner.first_key {
-            Some(first) if key < first => key.clone(),
+            Some(first) if comparator.compare(key, first).is_lt() => key.clone(),
             Some(first) => first.clone(),
             None => key.clone(),
         });
 
         inner.last_key = Some(match &inner.last_key {
-            Some(last) if key > last => key.clone(),
+            Some(last) if comparator.compare(key, last).is_gt() => key.clone(),
             Some(last) => last.clone(),
             None => key.clone(),
         });
@@ -76,10 +81,11 @@ impl DbIteratorRangeTracker {
         match (&inner.first_key, &inner.last_key) {
             (Some(first), Some(last)) => {
                 use std::ops::Bound;
-                Some(BytesRange::from((
+                Some(BytesRange::new_by(
                     Bound::Included(first.clone()),
                     Bound::Included(last.clone()),
-                )))
+                    &inner.comparator,
+                ))
             }
             _ => None,
         }
@@ -173,17 +179,35 @@ impl ScanIterator {
         mem_iters: impl IntoIterator<Item = Box<dyn KeyValueIterator + 'static>>,
         l0_iters: impl IntoIterator<Item = Box<dyn KeyValueIterator + 'static>>,
         sr_iters: impl IntoIterator<Item = Box<dyn KeyValueIterator + 'static>>,
+        order: IterationOrder,
+        comparator: &KeyComparator,
     ) -> Result<Self, SlateDBError> {
         // wrap each in a merge iterator
         let iters = vec![
             write_batch_iter,
-            Box::new(MergeIterator::new(mem_iters)?),
-            Box::new(MergeIterator::new(l0_iters)?),
-            Box::new(MergeIterator::new(sr_iters)?),
+            Box::new(
+                MergeIterator::new(mem_iters)?
+                    .with_order(order)
+                    .with_comparator(comparator.clone()),
+            ),
+            Box::new(
+                MergeIterator::new(l0_iters)?
+                    .with_order(order)
+                    .with_comparator(comparator.clone()),
+            ),
+            Box::new(
+                MergeIterator::new(sr_iters)?
+                    .with_order(order)
+                    .with_comparator(comparator.clone()),
+            ),
         ];
 
         Ok(Self {
-            delegate: Box::new(MergeIterator::new(iters)?),
+            delegate: Box::new(
+                MergeIterator::new(iters)?
+                    .with_order(order)
+                    .with_comparator(comparator.clone()),
+            ),
         })
     }
 }
@@ -205,6 +229,8 @@ impl KeyValueIterator for ScanIterator {
 
 pub struct DbIterator {
     range: BytesRange,
+    order: IterationOrder,
+    comparator: KeyComparator,
     iter: Box<dyn KeyValueIterator + 'static>,
     invalidated_error: Option<SlateDBError>,
     last_key: Option<Bytes>,
@@ -222,13 +248,21 @@ impl DbIterator {
         range_tracker: Option<Arc<DbIteratorRangeTracker>>,
         now: i64,
         merge_operator: Option<MergeOperatorType>,
+        order: IterationOrder,
+        comparator: KeyComparator,
     ) -> Result<Self, SlateDBError> {
         // The write_batch iterator is provided only when operating within a Transaction. It represents the uncommitted
         // writes made during the transaction. We do not need to apply the max_seq filter to them, because they do
         // not have an real committed sequence number yet.
-        let write_batch_iter = write_batch_iter
-            .map(|iter| Box::new(iter) as Box<dyn KeyValueIterator + 'static>)
-            .unwrap_or_else(|| Box::new(EmptyIterator::new()));
+        let write_batch_iter = match (write_batch_iter, order) {
+            (Some(iter), IterationOrder::Ascending) => {
+                Box::new(iter) as Box<dyn KeyValueIterator + 'static>
+            }
+            (Some(iter), IterationOrder::Descending) => {
+                Box::new(DescendingIterator::new(iter).with_comparator(comparator.clone()))
+            }
+            (None, _) => Box::new(EmptyIterator::new()),
+        };
 
         // Apply the max_seq filter to all the iterators. Please note that we should apply this filter BEFORE
         // merging the iterators.
@@ -239,9 +273,9 @@ impl DbIterator {
         //
         // If we filter the iterator after merging with max_seq=100, we'll lost the entry with seq=96 from the
         // iterator A. But the element with seq=96 is actually the correct answer for this scan.
-        let mem_iters = apply_filters(mem_iters, max_seq, now);
-        let l0_iters = apply_filters(l0_iters, max_seq, now);
-        let sr_iters = apply_filters(sr_iters, max_seq, now);
+        let mem_iters = apply_filters(mem_iters, max_seq, now, order, &comparator);
+        let l0_iters = apply_filters(l0_iters, max_seq, now, order, &comparator);
+        let sr_iters = apply_filters(sr_iters, max_seq, now, order, &comparator);
 
         let mut iter = match range.as_point() {
             Some(key) => Box::new(GetIterator::new(
@@ -256,6 +290,8 @@ impl DbIterator {
                 mem_iters,
                 l0_iters,
                 sr_iters,
+                order,
+                &comparator,
             )?) as Box<dyn KeyValueIterator + 'static>,
         };
 
@@ -270,6 +306,8 @@ impl DbIterator {
 
         Ok(DbIterator {
             range,
+            order,
+            comparator,
             iter,
             invalidated_error: None,
             last_key: None,
@@ -286,11 +324,27 @@ impl DbIterator {
         self.next_key_value().await.map_err(Into::into)
     }
 
+    /// Get the next record in the scan, along with the sequence number and timestamps
+    /// of the row it was read from.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`Error`] if the iterator has been invalidated due to an underlying error.
+    pub async fn next_with_metadata(
+        &mut self,
+    ) -> Result<Option<KeyValueWithMetadata>, crate::Error> {
+        self.next_row().await.map_err(Into::into)
+    }
+
     pub(crate) async fn next_key_value(&mut self) -> Result<Option<KeyValue>, SlateDBError> {
+        Ok(self.next_row().await?.map(KeyValue::from))
+    }
+
+    pub(crate) async fn next_row(&mut self) -> Result<Option<KeyValueWithMetadata>, SlateDBError> {
         if let Some(error) = self.invalidated_error.clone() {
             Err(error)
         } else {
-            let result = self.iter.next().await;
+            let result = self.next_visible_row().await;
             let result = self.maybe_invalidate(result);
             if let Ok(Some(ref kv)) = result {
                 self.last_key = Some(kv.key.clone());
@@ -303,6 +357,15 @@ impl DbIterator {
         }
     }
 
+    async fn next_visible_row(&mut self) -> Result<Option<KeyValueWithMetadata>, SlateDBError> {
+        while let Some(entry) = self.iter.next_entry().await? {
+            if let Some(row) = KeyValueWithMetadata::from_row(entry) {
+                return Ok(Some(row));
+            }
+        }
+        Ok(None)
+    }
+
     fn maybe_invalidate<T: Clone>(
         &mut self,
         result: Result<T, SlateDBError>,
@@ -313,19 +376,22 @@ impl DbIterator {
         result
     }
 
-    /// Seek ahead to the next key. The next key must be larger than the
-    /// last key returned by the iterator and less than the end bound specified
-    /// in the `scan` arguments.
+    /// Seek ahead to the next key. The next key must come after the last key
+    /// returned by the iterator and be within the range specified in the `scan`
+    /// arguments.
     ///
     /// After a successful seek, the iterator will return the next record
-    /// with a key greater than or equal to `next_key`.
+    /// with a key greater than or equal to `next_key`. For a descending scan
+    /// (see [`crate::config::ScanOptions::order`]), the next key must be smaller
+    /// than the last key returned, and the iterator will return the next record
+    /// with a key less than or equal to `next_key`.
     ///
     /// # Errors
     ///
     /// Returns an invalid argument error in the following cases:
     ///
     /// - if `next_key` comes before the current iterator position
-    /// - if `next_key` is beyond the upper bound specified in the original
+    /// - if `next_key` is outside the range specified in the original
     ///   [`crate::db::Db::scan`] parameters
     ///
     /// Returns [`Error`] if the iterator has been invalidated in order to reclaim resources.
@@ -333,18 +399,22 @@ impl DbIterator {
         let next_key = next_key.as_ref();
         if let Some(error) = self.invalidated_error.clone() {
             Err(error.into())
-        } else if !self.range.contains(&next_key) {
+        } else if !self.range.contains_by(next_key, &self.comparator) {
             Err(SlateDBError::SeekKeyOutOfRange {
                 key: next_key.to_vec(),
                 range: self.range.clone(),
             }
             .into())
-        } else if self
-            .last_key
-            .clone()
-            .is_some_and(|last_key| next_key <= last_key)
-        {
+        } else if self.last_key.as_ref().is_some_and(|last_key| {
+            self.order == IterationOrder::Ascending
+                && self.comparator.compare(next_key, last_key).is_le()
+        }) {
             Err(SlateDBError::SeekKeyLessThanLastReturnedKey.into())
+        } else if self.last_key.as_ref().is_some_and(|last_key| {
+            self.order == IterationOrder::Descending
+                && self.comparator.compare(next_key, last_key).is_ge()
+        }) {
+            Err(SlateDBError::SeekKeyGreaterThanLastReturnedKey.into())
         } else {
             let result = self.iter.seek(next_key).await;
             self.maybe_invalidate(result).map_err(Into::into)
@@ -356,12 +426,20 @@ pub(crate) fn apply_filters<T>(
     iters: impl IntoIterator<Item = T>,
     max_seq: Option<u64>,
     now: i64,
+    order: IterationOrder,
+    comparator: &KeyComparator,
 ) -> Vec<Box<dyn KeyValueIterator>>
 where
     T: KeyValueIterator + 'static,
 {
     iters
         .into_iter()
+        .map(|iter| match order {
+            IterationOrder::Ascending => Box::new(iter) as Box<dyn KeyValueIterator + 'static>,
+            IterationOrder::Descending => {
+                Box::new(DescendingIterator::new(iter).with_comparator(comparator.clone()))
+            }
+        })
         .map(|iter| FilterIterator::new_with_max_seq(iter, max_seq))
         .map(|iter| MapIterator::new_with_ttl_now(iter, now))
         .map(|iter| Box::new(iter) as Box<dyn KeyValueIterator + 'static>)
@@ -372,9 +450,11 @@ where
 mod tests {
     use crate::batch::{WriteBatch, WriteBatchIterator};
     use crate::bytes_range::BytesRange;
+    use crate::comparator::KeyComparator;
     use crate::db_iter::DbIterator;
     use crate::error::SlateDBError;
     use crate::iter::{IterationOrder, KeyValueIterator};
+    use crate::mem_table::WritableKVTable;
     use crate::test_utils::TestIterator;
     use crate::types::RowEntry;
     use bytes::Bytes;
@@ -393,6 +473,8 @@ mod tests {
             None,
             0,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -435,6 +517,8 @@ mod tests {
             None,
             0,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -467,6 +551,8 @@ mod tests {
             None,
             0,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -495,6 +581,92 @@ mod tests {
         assert!(iter.next().await.unwrap().is_none());
     }
 
+    #[tokio::test]
+    async fn test_descending_scan_returns_latest_versions() {
+        let older = WritableKVTable::new();
+        older.put(RowEntry::new_value(b"key1", b"value1", 1));
+        older.put(RowEntry::new_value(b"key2", b"value2", 2));
+        older.put(RowEntry::new_value(b"key3", b"value3", 3));
+        let newer = WritableKVTable::new();
+        newer.put(RowEntry::new_value(b"key1", b"value1-new", 4));
+        newer.put(RowEntry::new_tombstone(b"key2", 5));
+        newer.put(RowEntry::new_value(b"key3", b"value3-new", 6));
+        newer.put(RowEntry::new_value(b"key3", b"value3-newest", 7));
+
+        let mem_iters = [newer, older].map(|table| {
+            Box::new(table.table().range(.., IterationOrder::Descending))
+                as Box<dyn KeyValueIterator + 'static>
+        });
+        let mut iter = DbIterator::new(
+            BytesRange::from(..),
+            None,
+            mem_iters,
+            VecDeque::new(),
+            VecDeque::new(),
+            None,
+            None,
+            0,
+            None,
+            IterationOrder::Descending,
+            KeyComparator::default(),
+        )
+        .await
+        .unwrap();
+
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key, Bytes::from_static(b"key3"));
+        assert_eq!(kv.value, Bytes::from_static(b"value3-newest"));
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key, Bytes::from_static(b"key1"));
+        assert_eq!(kv.value, Bytes::from_static(b"value1-new"));
+        assert!(iter.next().await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_descending_seek_cannot_rewind() {
+        let table = WritableKVTable::new();
+        table.put(RowEntry::new_value(b"key1", b"value1", 1));
+        table.put(RowEntry::new_value(b"key2", b"value2", 2));
+        table.put(RowEntry::new_value(b"key3", b"value3", 3));
+
+        let mut iter = DbIterator::new(
+            BytesRange::from(..),
+            None,
+            vec![
+                Box::new(table.table().range(.., IterationOrder::Descending))
+                    as Box<dyn KeyValueIterator + 'static>,
+            ],
+            VecDeque::new(),
+            VecDeque::new(),
+            None,
+            None,
+            0,
+            None,
+            IterationOrder::Descending,
+            KeyComparator::default(),
+        )
+        .await
+        .unwrap();
+
+        let first = iter.next().await.unwrap().unwrap();
+        assert_eq!(first.key, Bytes::from_static(b"key3"));
+
+        // Seeking to the current key or a later key should fail
+        for key in [b"key3", b"key4"] {
+            let err = iter.seek(key).await.unwrap_err();
+            assert_eq!(
+                err.to_string(),
+                "Invalid error: cannot seek to a key greater than the last returned key of a descending iterator"
+            );
+        }
+
+        // Seeking backward skips over key2
+        iter.seek(b"key1").await.unwrap();
+        let kv = iter.next().await.unwrap().unwrap();
+        assert_eq!(kv.key, Bytes::from_static(b"key1"));
+        assert!(iter.next().await.unwrap().is_none());
+    }
+
     #[tokio::test]
     async fn test_dbiterator_with_writebatch() {
         // Create a WriteBatch with some data
@@ -517,6 +689,8 @@ mod tests {
             None,
             0,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -566,6 +740,8 @@ mod tests {
             None,
             49,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -612,6 +788,8 @@ mod tests {
             None,
             50,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -654,6 +832,8 @@ mod tests {
             None,
             100,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -692,6 +872,8 @@ mod tests {
             None,
             200,
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -734,6 +916,8 @@ mod tests {
             None,
             100, // now = 100, so newer_entry with expire_ts=50 is expired
             None,
+            IterationOrder::Ascending,
+            KeyComparator::default(),
         )
         .await
         .unwrap();
@@ -60,6 +60,47 @@ pub trait DbRead {
         options: &ReadOptions,
     ) -> Result<Option<Bytes>, crate::Error>;
 
+    /// Get the values of several keys from the database with default read options.
+    ///
+    /// All the keys are read from the same view of the database.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, Error>`: the value of each key, in the order of
+    ///   `keys`, or `None` if the key does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the values
+    async fn get_many<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, &ReadOptions::default())
+            .await
+    }
+
+    /// Get the values of several keys from the database with custom read options.
+    ///
+    /// All the keys are read from the same view of the database.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get
+    /// - `options`: the read options to use
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, Error>`: the value of each key, in the order of
+    ///   `keys`, or `None` if the key does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the values
+    async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error>;
+
     /// Scan a range of keys using the default scan options.
     ///
     /// returns a `DbIterator`
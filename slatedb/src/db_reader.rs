@@ -1,7 +1,10 @@
 use crate::bytes_range::BytesRange;
+use crate::change_stream::ChangeStream;
 use crate::clock::{
     DefaultLogicalClock, DefaultSystemClock, LogicalClock, MonotonicClock, SystemClock,
 };
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+use crate::comparator::KeyComparator;
 use crate::config::{CheckpointOptions, DbReaderOptions, ReadOptions, ScanOptions};
 use crate::db_read::DbRead;
 use crate::db_state::CoreDbState;
@@ -13,14 +16,16 @@ use crate::manifest::Manifest;
 use crate::mem_table::{ImmutableMemtable, KVTable};
 use crate::oracle::DbReaderOracle;
 use crate::rand::DbRand;
+use crate::range_tombstone::{RangeTombstone, SstRangeTombstoneIndex};
 use crate::reader::{DbStateReader, Reader};
 use crate::sst_iter::SstIteratorOptions;
 use crate::stats::StatRegistry;
 use crate::store_provider::{DefaultStoreProvider, StoreProvider};
 use crate::tablestore::TableStore;
+use crate::types::KeyValueWithMetadata;
 use crate::utils::{IdGenerator, MonotonicSeq, WatchableOnceCell};
 use crate::wal_replay::{WalReplayIterator, WalReplayOptions};
-use crate::{Checkpoint, DbIterator};
+use crate::{Checkpoint, Db, DbBuilder, DbIterator};
 use async_trait::async_trait;
 use bytes::Bytes;
 use futures::stream::BoxStream;
@@ -29,7 +34,8 @@ use object_store::path::Path;
 use object_store::ObjectStore;
 use once_cell::sync::Lazy;
 use parking_lot::RwLock;
-use std::collections::VecDeque;
+use std::borrow::Cow;
+use std::collections::{HashMap, VecDeque};
 use std::ops::{RangeBounds, Sub};
 use std::sync::Arc;
 use std::time::Duration;
@@ -71,6 +77,7 @@ struct CheckpointState {
     imm_memtable: VecDeque<Arc<ImmutableMemtable>>,
     last_wal_id: u64,
     last_remote_persisted_seq: u64,
+    sst_range_tombstones: SstRangeTombstoneIndex,
 }
 
 static EMPTY_TABLE: Lazy<Arc<KVTable>> = Lazy::new(|| Arc::new(KVTable::new()));
@@ -87,6 +94,13 @@ impl DbStateReader for CheckpointState {
     fn core(&self) -> &CoreDbState {
         &self.manifest.core
     }
+
+    fn sst_range_tombstones(&self, column_family: u32) -> Cow<'_, [RangeTombstone]> {
+        Cow::Borrowed(
+            self.sst_range_tombstones
+                .column_family(&self.manifest.core, column_family),
+        )
+    }
 }
 
 impl DbReaderInner {
@@ -104,6 +118,9 @@ impl DbReaderInner {
         if !manifest.db_state().initialized {
             return Err(SlateDBError::InvalidDBState);
         }
+        table_store
+            .comparator()
+            .check_matches(manifest.db_state().comparator.as_deref())?;
 
         let checkpoint =
             Self::get_or_create_checkpoint(&mut manifest, checkpoint_id, &options, rand.clone())
@@ -142,6 +159,10 @@ impl DbReaderInner {
             mono_clock: Arc::clone(&mono_clock),
             oracle: oracle.clone(),
             merge_operator: options.merge_operator.clone(),
+            column_family_merge_operators: HashMap::new(),
+            prefix_extractor: options.prefix_extractor.clone(),
+            history_retention: RwLock::new(None),
+            system_clock: system_clock.clone(),
         };
 
         Ok(Self {
@@ -189,7 +210,52 @@ impl DbReaderInner {
         self.check_closed()?;
         let db_state = Arc::clone(&self.state.read());
         self.reader
-            .get_with_options(key, options, db_state.as_ref(), None, None)
+            .get_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                key,
+                options,
+                db_state.as_ref(),
+                None,
+                None,
+            )
+            .await
+    }
+
+    async fn get_with_metadata<K: AsRef<[u8]> + Send>(
+        &self,
+        key: K,
+        options: &ReadOptions,
+    ) -> Result<Option<KeyValueWithMetadata>, SlateDBError> {
+        self.check_closed()?;
+        let db_state = Arc::clone(&self.state.read());
+        self.reader
+            .get_row_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                key,
+                options,
+                db_state.as_ref(),
+                None,
+                None,
+            )
+            .await
+    }
+
+    async fn get_many_with_options(
+        &self,
+        keys: &[Bytes],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, SlateDBError> {
+        self.check_closed()?;
+        let db_state = Arc::clone(&self.state.read());
+        self.reader
+            .get_many_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                keys,
+                options,
+                db_state.as_ref(),
+                None,
+                None,
+            )
             .await
     }
 
@@ -201,7 +267,15 @@ impl DbReaderInner {
         self.check_closed()?;
         let db_state = Arc::clone(&self.state.read());
         self.reader
-            .scan_with_options(range, options, db_state.as_ref(), None, None, None)
+            .scan_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                range,
+                options,
+                db_state.as_ref(),
+                None,
+                None,
+                None,
+            )
             .await
     }
 
@@ -264,6 +338,7 @@ impl DbReaderInner {
                 imm_memtable,
                 last_wal_id,
                 last_remote_persisted_seq: last_committed_seq,
+                sst_range_tombstones: SstRangeTombstoneIndex::default(),
             });
         }
         Ok(())
@@ -340,6 +415,7 @@ impl DbReaderInner {
             imm_memtable,
             last_wal_id,
             last_remote_persisted_seq: last_committed_seq,
+            sst_range_tombstones: SstRangeTombstoneIndex::default(),
         })
     }
 
@@ -399,6 +475,7 @@ impl DbReaderInner {
             blocks_to_fetch: 256,
             cache_blocks: true,
             eager_spawn: true,
+            ..SstIteratorOptions::default()
         };
 
         let replay_options = WalReplayOptions {
@@ -548,6 +625,8 @@ impl DbReader {
             object_store,
             block_cache: options.block_cache.clone(),
             system_clock: clock.clone(),
+            comparator: KeyComparator::new(options.comparator.clone()),
+            encryption_provider: options.encryption_provider.clone(),
         };
 
         Self::open_internal(
@@ -705,6 +784,102 @@ impl DbReader {
             .map_err(Into::into)
     }
 
+    /// Get a value from the database with default read options, along with the sequence
+    /// number and timestamps of the row it was read from.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to get
+    ///
+    /// ## Returns
+    /// - `Result<Option<KeyValueWithMetadata>, Error>`:
+    ///   - `Some(KeyValueWithMetadata)`: the value and its metadata if it exists
+    ///   - `None`: if the value does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    pub async fn get_with_metadata<K: AsRef<[u8]> + Send>(
+        &self,
+        key: K,
+    ) -> Result<Option<KeyValueWithMetadata>, crate::Error> {
+        self.get_with_metadata_with_options(key, &ReadOptions::default())
+            .await
+    }
+
+    /// Get a value from the database with custom read options, along with the sequence
+    /// number and timestamps of the row it was read from.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to get
+    /// - `options`: the read options to use (Note that [`ReadOptions::read_level`] has no effect
+    ///   for readers, which can only observe committed state).
+    ///
+    /// ## Returns
+    /// - `Result<Option<KeyValueWithMetadata>, Error>`:
+    ///   - `Some(KeyValueWithMetadata)`: the value and its metadata if it exists
+    ///   - `None`: if the value does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    pub async fn get_with_metadata_with_options<K: AsRef<[u8]> + Send>(
+        &self,
+        key: K,
+        options: &ReadOptions,
+    ) -> Result<Option<KeyValueWithMetadata>, crate::Error> {
+        self.inner
+            .get_with_metadata(key, options)
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Get the values of several keys from the database with default read options.
+    ///
+    /// All the keys are read from the same view of the database. See [`crate::Db::get_many`].
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get. They may be in any order and contain duplicates.
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, Error>`: the value of each key, in the order of
+    ///   `keys`, or `None` if the key does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the values
+    pub async fn get_many<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, &ReadOptions::default())
+            .await
+    }
+
+    /// Get the values of several keys from the database with custom read options.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get. They may be in any order and contain duplicates.
+    /// - `options`: the read options to use (Note that [`ReadOptions::read_level`] has no effect
+    ///   for readers, which can only observe committed state).
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, Error>`: the value of each key, in the order of
+    ///   `keys`, or `None` if the key does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the values
+    pub async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        let keys: Vec<Bytes> = keys
+            .iter()
+            .map(|key| Bytes::copy_from_slice(key.as_ref()))
+            .collect();
+        self.inner
+            .get_many_with_options(&keys, options)
+            .await
+            .map_err(Into::into)
+    }
+
     /// Scan a range of keys using the default scan options.
     ///
     /// returns a `DbIterator`
@@ -812,13 +987,113 @@ impl DbReader {
         let end = range
             .end_bound()
             .map(|b| Bytes::copy_from_slice(b.as_ref()));
-        let range = BytesRange::from((start, end));
+        let range = BytesRange::new_by(start, end, self.inner.table_store.comparator());
         self.inner
             .scan_with_options(range, options)
             .await
             .map_err(Into::into)
     }
 
+    /// Subscribe to the changes committed to the default column family of the database,
+    /// starting at `from_seq`.
+    ///
+    /// See [`Db::subscribe`](crate::Db::subscribe). The stream polls object storage every
+    /// `manifest_poll_interval` once it has caught up, and ends when the reader is closed.
+    ///
+    /// ## Arguments
+    /// - `from_seq`: the sequence number of the first change to return
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, DbReader, config::DbReaderOptions, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store.clone()).await?;
+    ///     db.put(b"key", b"value").await?;
+    ///     let options = DbReaderOptions::default();
+    ///     let reader = DbReader::open("test_db", object_store.clone(), None, options).await?;
+    ///     let mut changes = reader.subscribe(0).await?;
+    ///     assert_eq!(changes.next().await?.expect("reader is open").key.as_ref(), b"key");
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn subscribe(&self, from_seq: u64) -> Result<ChangeStream, crate::Error> {
+        Ok(ChangeStream::new(
+            DEFAULT_COLUMN_FAMILY_ID,
+            from_seq,
+            Arc::clone(&self.inner.manifest_store),
+            Arc::clone(&self.inner.table_store),
+            Arc::clone(&self.inner.system_clock),
+            self.inner.options.manifest_poll_interval,
+            self.inner.closed_result_watcher.reader(),
+        ))
+    }
+
+    /// Wait for the writer of the database to hand it off with
+    /// [`Db::transfer_leadership`], then close the reader and open the database with
+    /// `builder` as the next writer.
+    ///
+    /// This lets a new process wait as a warm standby during a deployment: it serves reads
+    /// while the old writer runs, and takes over the writes as soon as the old writer has
+    /// flushed everything, with no WAL to replay. The reader checks for the handoff every
+    /// `manifest_poll_interval`. To take over even if the writer never hands off, for
+    /// example because it crashed, bound the wait with a timeout and open the database
+    /// with `builder` directly.
+    ///
+    /// `builder` must open the database that the reader reads. If several readers promote
+    /// themselves after the same handoff, the last one to open the database fences the
+    /// others.
+    ///
+    /// ## Returns
+    /// - `Result<Db, Error>`: the database opened by `builder`
+    ///
+    /// ## Examples
+    ///
+    /// ```
+    /// use slatedb::{Db, DbReader, config::DbReaderOptions, Error};
+    /// use slatedb::object_store::{ObjectStore, memory::InMemory};
+    /// use std::sync::Arc;
+    ///
+    /// #[tokio::main]
+    /// async fn main() -> Result<(), Error> {
+    ///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+    ///     let db = Db::open("test_db", object_store.clone()).await?;
+    ///     let options = DbReaderOptions::default();
+    ///     let standby = DbReader::open("test_db", object_store.clone(), None, options).await?;
+    ///
+    ///     db.put(b"key", b"value").await?;
+    ///     db.transfer_leadership().await?;
+    ///
+    ///     let db = standby.promote(Db::builder("test_db", object_store)).await?;
+    ///     assert_eq!(db.get(b"key").await?, Some("value".into()));
+    ///     Ok(())
+    /// }
+    /// ```
+    pub async fn promote<P: Into<Path>>(self, builder: DbBuilder<P>) -> Result<Db, crate::Error> {
+        loop {
+            self.inner.check_closed()?;
+            let manifest = StoredManifest::load(Arc::clone(&self.inner.manifest_store)).await?;
+            if let Some(handoff) = manifest.manifest().pending_handoff() {
+                info!(
+                    "promoting reader after writer handoff [writer_epoch={}, last_seq={}]",
+                    handoff.writer_epoch, handoff.last_seq
+                );
+                break;
+            }
+            self.inner
+                .system_clock
+                .sleep(self.inner.options.manifest_poll_interval)
+                .await;
+        }
+        self.close().await?;
+        builder.build().await
+    }
+
     /// Close the database reader.
     ///
     /// ## Returns
@@ -860,6 +1135,14 @@ impl DbRead for DbReader {
         self.get_with_options(key, options).await
     }
 
+    async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, options).await
+    }
+
     async fn scan_with_options<K, T>(
         &self,
         range: T,
@@ -879,6 +1162,7 @@ mod tests {
     use crate::config::{CheckpointOptions, CheckpointScope, Settings};
     use crate::db_reader::{DbReader, DbReaderOptions};
     use crate::db_state::CoreDbState;
+    use crate::iter::IterationOrder;
     use crate::manifest::store::{ManifestStore, StoredManifest};
     use crate::manifest::Manifest;
     use crate::object_stores::ObjectStores;
@@ -1056,7 +1340,8 @@ mod tests {
             Bytes::copy_from_slice(value),
         );
 
-        test_utils::assert_ranged_db_scan(&table, .., &mut db_iter).await;
+        test_utils::assert_ranged_db_scan(&table, .., IterationOrder::Ascending, &mut db_iter)
+            .await;
     }
 
     #[tokio::test(start_paused = true)]
@@ -1091,7 +1376,8 @@ mod tests {
 
         tokio::time::sleep(Duration::from_millis(20)).await;
         let mut db_iter = reader.scan::<Vec<u8>, _>(..).await.unwrap();
-        test_utils::assert_ranged_db_scan(&table, .., &mut db_iter).await;
+        test_utils::assert_ranged_db_scan(&table, .., IterationOrder::Ascending, &mut db_iter)
+            .await;
 
         let manifest = manifest_store.read_latest_manifest().await.unwrap().1;
         assert!(!manifest.core.checkpoints.is_empty());
@@ -1167,6 +1453,48 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn should_promote_after_writer_handoff() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let path = Path::from("/tmp/test_kv_store");
+        let test_provider = TestProvider::new(path.clone(), Arc::clone(&object_store));
+        let db = test_provider.new_db(Settings::default()).await.unwrap();
+
+        let reader_options = DbReaderOptions {
+            manifest_poll_interval: Duration::from_millis(10),
+            ..DbReaderOptions::default()
+        };
+        let reader = test_provider
+            .new_db_reader(reader_options, None)
+            .await
+            .unwrap();
+        let builder = Db::builder(path.clone(), Arc::clone(&object_store));
+        let promotion = tokio::spawn(async move { reader.promote(builder).await });
+
+        db.put(b"key", b"value").await.unwrap();
+        // the reader keeps waiting while the writer is open
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(!promotion.is_finished());
+
+        db.transfer_leadership().await.unwrap();
+        let promoted = promotion.await.unwrap().unwrap();
+        assert_eq!(
+            promoted.get(b"key").await.unwrap(),
+            Some(Bytes::from_static(b"value"))
+        );
+        promoted.put(b"key2", b"value2").await.unwrap();
+
+        // the handoff is consumed and the reader's checkpoint is deleted
+        let manifest = test_provider
+            .manifest_store()
+            .read_latest_manifest()
+            .await
+            .unwrap()
+            .1;
+        assert_eq!(manifest.writer_handoff, None);
+        assert!(manifest.core.checkpoints.is_empty());
+    }
+
     #[tokio::test(start_paused = true)]
     async fn should_fail_new_reads_if_manifest_poller_crashes() {
         let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
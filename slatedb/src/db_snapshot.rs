@@ -4,11 +4,13 @@ use std::sync::Arc;
 use uuid::Uuid;
 
 use crate::bytes_range::BytesRange;
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
 use crate::config::{ReadOptions, ScanOptions};
 use crate::db_iter::DbIterator;
 
 use crate::db::DbInner;
 use crate::transaction_manager::TransactionManager;
+use crate::types::KeyValueWithMetadata;
 use crate::DbRead;
 
 pub struct DbSnapshot {
@@ -66,7 +68,120 @@ impl DbSnapshot {
         let db_state = self.db_inner.state.read().view();
         self.db_inner
             .reader
-            .get_with_options(key, options, &db_state, None, Some(self.started_seq))
+            .get_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                key,
+                options,
+                &db_state,
+                None,
+                Some(self.started_seq),
+            )
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Get a value from the snapshot with default read options, along with the sequence
+    /// number and timestamps of the row it was read from.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to get
+    ///
+    /// ## Returns
+    /// - `Result<Option<KeyValueWithMetadata>, Error>`:
+    ///   - `Some(KeyValueWithMetadata)`: the value and its metadata if it exists
+    ///   - `None`: if the value does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    pub async fn get_with_metadata<K: AsRef<[u8]> + Send>(
+        &self,
+        key: K,
+    ) -> Result<Option<KeyValueWithMetadata>, crate::Error> {
+        self.get_with_metadata_with_options(key, &ReadOptions::default())
+            .await
+    }
+
+    /// Get a value from the snapshot with custom read options, along with the sequence
+    /// number and timestamps of the row it was read from.
+    ///
+    /// ## Arguments
+    /// - `key`: the key to get
+    /// - `options`: the read options to use
+    ///
+    /// ## Returns
+    /// - `Result<Option<KeyValueWithMetadata>, Error>`:
+    ///   - `Some(KeyValueWithMetadata)`: the value and its metadata if it exists
+    ///   - `None`: if the value does not exist
+    ///
+    /// ## Errors
+    /// - `Error`: if there was an error getting the value
+    pub async fn get_with_metadata_with_options<K: AsRef<[u8]> + Send>(
+        &self,
+        key: K,
+        options: &ReadOptions,
+    ) -> Result<Option<KeyValueWithMetadata>, crate::Error> {
+        self.db_inner.check_closed()?;
+        let db_state = self.db_inner.state.read().view();
+        self.db_inner
+            .reader
+            .get_row_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                key,
+                options,
+                &db_state,
+                None,
+                Some(self.started_seq),
+            )
+            .await
+            .map_err(Into::into)
+    }
+
+    /// Get the values of several keys from the snapshot with default read options.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, SlateDBError>`: the value of each key, in the order of
+    ///   `keys`, or None if the key does not exist
+    pub async fn get_many<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, &ReadOptions::default())
+            .await
+    }
+
+    /// Get the values of several keys from the snapshot with custom read options.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get
+    /// - `options`: the read options to use
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, SlateDBError>`: the value of each key, in the order of
+    ///   `keys`, or None if the key does not exist
+    pub async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.db_inner.check_closed()?;
+        let keys: Vec<Bytes> = keys
+            .iter()
+            .map(|key| Bytes::copy_from_slice(key.as_ref()))
+            .collect();
+        let db_state = self.db_inner.state.read().view();
+        self.db_inner
+            .reader
+            .get_many_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                &keys,
+                options,
+                &db_state,
+                None,
+                Some(self.started_seq),
+            )
             .await
             .map_err(Into::into)
     }
@@ -110,13 +225,14 @@ impl DbSnapshot {
         let end = range
             .end_bound()
             .map(|b| Bytes::copy_from_slice(b.as_ref()));
-        let range = (start, end);
+        let range = BytesRange::new_by(start, end, self.db_inner.table_store.comparator());
         self.db_inner.check_closed()?;
         let db_state = self.db_inner.state.read().view();
         self.db_inner
             .reader
             .scan_with_options(
-                BytesRange::from(range),
+                DEFAULT_COLUMN_FAMILY_ID,
+                range,
                 options,
                 &db_state,
                 None,
@@ -138,6 +254,14 @@ impl DbRead for DbSnapshot {
         self.get_with_options(key, options).await
     }
 
+    async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, options).await
+    }
+
     async fn scan_with_options<K, T>(
         &self,
         range: T,
@@ -670,6 +794,7 @@ mod tests {
                     &PutOptions::default(),
                     &WriteOptions {
                         await_durable: false,
+                        ..WriteOptions::default()
                     },
                 )
                 .await
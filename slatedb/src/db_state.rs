@@ -1,9 +1,13 @@
+use crate::blob_file::BlobFileRef;
 use crate::bytes_range::BytesRange;
 use crate::checkpoint::Checkpoint;
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+use crate::comparator::KeyComparator;
 use crate::config::CompressionCodec;
 use crate::error::SlateDBError;
 use crate::manifest::Manifest;
 use crate::mem_table::{ImmutableMemtable, KVTable, WritableKVTable};
+use crate::range_tombstone::{RangeTombstone, SstRangeTombstoneIndex};
 use crate::reader::DbStateReader;
 use crate::seq_tracker::SequenceTracker;
 use crate::transactional_object::DirtyObject;
@@ -12,7 +16,8 @@ use crate::wal_id::WalIdStore;
 use bytes::Bytes;
 use log::debug;
 use serde::Serialize;
-use std::collections::VecDeque;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, VecDeque};
 use std::fmt::{Debug, Formatter};
 use std::ops::Bound::{Excluded, Included, Unbounded};
 use std::ops::{Bound, Range, RangeBounds};
@@ -121,26 +126,35 @@ impl SsTableHandle {
         &self,
         next_handle: Option<&SsTableHandle>,
         range: &BytesRange,
+        comparator: &KeyComparator,
     ) -> Option<BytesRange> {
         assert!(matches!(self.id, Compacted(_)));
         if let Some(next_handle) = next_handle {
-            BytesRange::new(
+            BytesRange::new_by(
                 self.compacted_effective_start_bound(),
                 Excluded(next_handle.compacted_effective_start_key().clone()),
+                comparator,
             )
-            .intersect(range)
+            .intersect_by(range, comparator)
         } else {
-            self.effective_range.intersect(range)
+            self.effective_range.intersect_by(range, comparator)
         }
     }
 
-    pub(crate) fn intersects_range(&self, end_bound: Bound<Bytes>, range: &BytesRange) -> bool {
-        let sst_range =
-            BytesRange::new(Unbounded, end_bound.clone()).intersect(&self.effective_range);
+    pub(crate) fn intersects_range(
+        &self,
+        end_bound: Bound<Bytes>,
+        range: &BytesRange,
+        comparator: &KeyComparator,
+    ) -> bool {
+        let sst_range = BytesRange::new_by(Unbounded, end_bound.clone(), comparator)
+            .intersect_by(&self.effective_range, comparator);
         match sst_range {
-            Some(sst_range) => BytesRange::new(sst_range.start_bound().cloned(), end_bound)
-                .intersect(range)
-                .is_some(),
+            Some(sst_range) => {
+                BytesRange::new_by(sst_range.start_bound().cloned(), end_bound, comparator)
+                    .intersect_by(range, comparator)
+                    .is_some()
+            }
             None => false,
         }
     }
@@ -150,13 +164,33 @@ impl SsTableHandle {
     /// This method determines the effective range that can be accessed within an SST by:
     /// 1. Intersecting the requested range with the effective range
     /// 2. Returning None if the requested range does not overlap with the effective range
-    pub(crate) fn calculate_view_range(&self, range: BytesRange) -> Option<BytesRange> {
+    pub(crate) fn calculate_view_range(
+        &self,
+        range: BytesRange,
+        comparator: &KeyComparator,
+    ) -> Option<BytesRange> {
         if let Some(visible_range) = &self.visible_range {
-            return range.intersect(visible_range);
+            return range.intersect_by(visible_range, comparator);
         }
         Some(range)
     }
 
+    /// Returns the range tombstones stored in the SST, clipped to the visible range
+    /// of this handle.
+    pub(crate) fn range_tombstones(&self) -> Vec<RangeTombstone> {
+        self.info
+            .range_tombstones
+            .iter()
+            .filter_map(|tombstone| match &self.visible_range {
+                Some(visible_range) => tombstone
+                    .range
+                    .intersect(visible_range)
+                    .map(|range| RangeTombstone::new(range, tombstone.seq, tombstone.create_ts)),
+                None => Some(tombstone.clone()),
+            })
+            .collect()
+    }
+
     pub(crate) fn estimate_size(&self) -> u64 {
         // this is a hacky estimate of the sst size since we don't have it stored anywhere
         // right now. Just use the index's offset and add the index length. Since the index
@@ -213,6 +247,18 @@ pub(crate) struct SsTableInfo {
     pub(crate) filter_offset: u64,
     pub(crate) filter_len: u64,
     pub(crate) compression_codec: Option<CompressionCodec>,
+    pub(crate) range_tombstones: Vec<RangeTombstone>,
+    /// True if the row keys are prefixed with the id of their column family. Only
+    /// set on the WAL SSTs of databases with column families.
+    pub(crate) column_family_keys: bool,
+    /// The name of the [`crate::PrefixExtractor`] whose prefixes were added to the
+    /// bloom filter, if any.
+    pub(crate) prefix_extractor: Option<String>,
+    /// The blob files holding values that the rows of the SST point to.
+    pub(crate) blob_refs: Vec<BlobFileRef>,
+    /// The id of the key the blocks, index and filter of the SST were encrypted with,
+    /// if the SST is encrypted.
+    pub(crate) encryption_key_id: Option<String>,
 }
 
 pub(crate) trait SsTableInfoCodec: Send + Sync {
@@ -243,11 +289,17 @@ impl SortedRun {
         self.ssts.iter().map(|sst| sst.estimate_size()).sum()
     }
 
-    pub(crate) fn find_sst_with_range_covering_key_idx(&self, key: &[u8]) -> Option<usize> {
+    pub(crate) fn find_sst_with_range_covering_key_idx(
+        &self,
+        key: &[u8],
+        comparator: &KeyComparator,
+    ) -> Option<usize> {
         // returns the sst after the one whose range includes the key
-        let first_sst = self
-            .ssts
-            .partition_point(|sst| sst.compacted_effective_start_key() <= key);
+        let first_sst = self.ssts.partition_point(|sst| {
+            comparator
+                .compare(sst.compacted_effective_start_key(), key)
+                .is_le()
+        });
         if first_sst > 0 {
             return Some(first_sst - 1);
         }
@@ -255,12 +307,20 @@ impl SortedRun {
         None
     }
 
-    pub(crate) fn find_sst_with_range_covering_key(&self, key: &[u8]) -> Option<&SsTableHandle> {
-        self.find_sst_with_range_covering_key_idx(key)
+    pub(crate) fn find_sst_with_range_covering_key(
+        &self,
+        key: &[u8],
+        comparator: &KeyComparator,
+    ) -> Option<&SsTableHandle> {
+        self.find_sst_with_range_covering_key_idx(key, comparator)
             .map(|idx| &self.ssts[idx])
     }
 
-    fn table_idx_covering_range(&self, range: &BytesRange) -> Range<usize> {
+    fn table_idx_covering_range(
+        &self,
+        range: &BytesRange,
+        comparator: &KeyComparator,
+    ) -> Range<usize> {
         let mut min_idx = None;
         let mut max_idx = 0;
 
@@ -274,7 +334,7 @@ impl SortedRun {
                 Unbounded
             };
 
-            if current_sst.intersects_range(upper_bound, range) {
+            if current_sst.intersects_range(upper_bound, range, comparator) {
                 if min_idx.is_none() {
                     min_idx = Some(idx);
                 }
@@ -289,16 +349,21 @@ impl SortedRun {
         }
     }
 
-    pub(crate) fn tables_covering_range(&self, range: &BytesRange) -> VecDeque<&SsTableHandle> {
-        let matching_range = self.table_idx_covering_range(range);
+    pub(crate) fn tables_covering_range(
+        &self,
+        range: &BytesRange,
+        comparator: &KeyComparator,
+    ) -> VecDeque<&SsTableHandle> {
+        let matching_range = self.table_idx_covering_range(range, comparator);
         self.ssts[matching_range].iter().collect()
     }
 
     pub(crate) fn into_tables_covering_range(
         mut self,
         range: &BytesRange,
+        comparator: &KeyComparator,
     ) -> VecDeque<SsTableHandle> {
-        let matching_range = self.table_idx_covering_range(range);
+        let matching_range = self.table_idx_covering_range(range, comparator);
         self.ssts.drain(matching_range).collect()
     }
 }
@@ -306,6 +371,8 @@ impl SortedRun {
 pub(crate) struct DbState {
     memtable: WritableKVTable,
     state: Arc<COWDbState>,
+    /// The order of the keys of the memtables.
+    comparator: KeyComparator,
 
     /// If the database is closed, this will contain the result of the close operation.
     /// Otherwise, it will be None.
@@ -320,6 +387,7 @@ pub(crate) struct DbState {
 pub(crate) struct COWDbState {
     pub(crate) imm_memtable: VecDeque<Arc<ImmutableMemtable>>,
     pub(crate) manifest: DirtyObject<Manifest>,
+    pub(crate) sst_range_tombstones: SstRangeTombstoneIndex,
 }
 
 impl COWDbState {
@@ -356,6 +424,34 @@ pub(crate) struct CoreDbState {
     pub(crate) sequence_tracker: SequenceTracker,
     pub(crate) checkpoints: Vec<Checkpoint>,
     pub(crate) wal_object_store_uri: Option<String>,
+    /// The named column families. The L0 SSTs and sorted runs of the default column
+    /// family are `l0` and `compacted`.
+    pub(crate) column_families: Vec<ColumnFamilyState>,
+    /// The name of the comparator that orders the keys of the database, or None if keys
+    /// are ordered by their bytes.
+    pub(crate) comparator: Option<String>,
+}
+
+/// The persisted state of a named column family.
+#[derive(Clone, PartialEq, Serialize, Debug)]
+pub(crate) struct ColumnFamilyState {
+    pub(crate) id: u32,
+    pub(crate) name: String,
+    pub(crate) l0_last_compacted: Option<Ulid>,
+    pub(crate) l0: VecDeque<SsTableHandle>,
+    pub(crate) compacted: Vec<SortedRun>,
+}
+
+impl ColumnFamilyState {
+    pub(crate) fn new(id: u32, name: String) -> Self {
+        Self {
+            id,
+            name,
+            l0_last_compacted: None,
+            l0: VecDeque::new(),
+            compacted: vec![],
+        }
+    }
 }
 
 impl CoreDbState {
@@ -373,6 +469,8 @@ impl CoreDbState {
             wal_object_store_uri: None,
             recent_snapshot_min_seq: 0,
             sequence_tracker: SequenceTracker::new(),
+            column_families: vec![],
+            comparator: None,
         }
     }
 
@@ -406,12 +504,124 @@ impl CoreDbState {
     pub(crate) fn find_checkpoint(&self, checkpoint_id: Uuid) -> Option<&Checkpoint> {
         self.checkpoints.iter().find(|c| c.id == checkpoint_id)
     }
+
+    /// Adds a column family for each name that is not yet in the state. Returns true
+    /// if a column family was added.
+    pub(crate) fn add_column_families<'a>(
+        &mut self,
+        names: impl IntoIterator<Item = &'a String>,
+    ) -> bool {
+        let mut added = false;
+        for name in names {
+            if self.column_families.iter().any(|cf| &cf.name == name) {
+                continue;
+            }
+            let id = self
+                .column_families
+                .iter()
+                .map(|cf| cf.id)
+                .max()
+                .unwrap_or(DEFAULT_COLUMN_FAMILY_ID)
+                + 1;
+            self.column_families
+                .push(ColumnFamilyState::new(id, name.clone()));
+            added = true;
+        }
+        added
+    }
+
+    pub(crate) fn column_family(&self, id: u32) -> Option<&ColumnFamilyState> {
+        self.column_families.iter().find(|cf| cf.id == id)
+    }
+
+    pub(crate) fn column_family_mut(&mut self, id: u32) -> Option<&mut ColumnFamilyState> {
+        self.column_families.iter_mut().find(|cf| cf.id == id)
+    }
+
+    /// Returns the last L0 SST of a column family that was compacted, if any.
+    pub(crate) fn column_family_l0_last_compacted(&self, id: u32) -> Option<Ulid> {
+        if id == DEFAULT_COLUMN_FAMILY_ID {
+            return self.l0_last_compacted;
+        }
+        self.column_family(id).and_then(|cf| cf.l0_last_compacted)
+    }
+
+    /// Returns the L0 SSTs of a column family.
+    pub(crate) fn column_family_l0(&self, id: u32) -> &VecDeque<SsTableHandle> {
+        static EMPTY: VecDeque<SsTableHandle> = VecDeque::new();
+        if id == DEFAULT_COLUMN_FAMILY_ID {
+            return &self.l0;
+        }
+        self.column_family(id).map_or(&EMPTY, |cf| &cf.l0)
+    }
+
+    /// Returns the sorted runs of a column family.
+    pub(crate) fn column_family_compacted(&self, id: u32) -> &[SortedRun] {
+        if id == DEFAULT_COLUMN_FAMILY_ID {
+            return &self.compacted;
+        }
+        self.column_family(id).map_or(&[], |cf| &cf.compacted)
+    }
+
+    /// Returns the L0 SSTs of the default and the named column families.
+    pub(crate) fn all_l0(&self) -> impl Iterator<Item = &SsTableHandle> {
+        self.l0
+            .iter()
+            .chain(self.column_families.iter().flat_map(|cf| cf.l0.iter()))
+    }
+
+    /// Returns the number of L0 SSTs of the column family with the most L0 SSTs.
+    pub(crate) fn max_l0_len(&self) -> usize {
+        self.column_families
+            .iter()
+            .map(|cf| cf.l0.len())
+            .fold(self.l0.len(), std::cmp::max)
+    }
+
+    /// Returns the sorted runs of the default and the named column families.
+    pub(crate) fn all_compacted(&self) -> impl Iterator<Item = &SortedRun> {
+        self.compacted.iter().chain(
+            self.column_families
+                .iter()
+                .flat_map(|cf| cf.compacted.iter()),
+        )
+    }
+
+    /// Returns a copy of the state whose `l0_last_compacted`, `l0` and `compacted` are
+    /// those of the given column family. This lets the compactor, which works on the
+    /// default column family's fields, schedule and run compactions of any column family.
+    pub(crate) fn column_family_view(&self, id: u32) -> CoreDbState {
+        let mut view = self.clone();
+        if id != DEFAULT_COLUMN_FAMILY_ID {
+            let cf = self.column_family(id);
+            view.l0_last_compacted = cf.and_then(|cf| cf.l0_last_compacted);
+            view.l0 = cf.map(|cf| cf.l0.clone()).unwrap_or_default();
+            view.compacted = cf.map(|cf| cf.compacted.clone()).unwrap_or_default();
+        }
+        view.column_families.clear();
+        view
+    }
+
+    /// Stores the `l0_last_compacted`, `l0` and `compacted` of a view created with
+    /// [`Self::column_family_view`] back into the given column family.
+    pub(crate) fn apply_column_family_view(&mut self, id: u32, view: CoreDbState) {
+        if id == DEFAULT_COLUMN_FAMILY_ID {
+            self.l0_last_compacted = view.l0_last_compacted;
+            self.l0 = view.l0;
+            self.compacted = view.compacted;
+        } else if let Some(cf) = self.column_family_mut(id) {
+            cf.l0_last_compacted = view.l0_last_compacted;
+            cf.l0 = view.l0;
+            cf.compacted = view.compacted;
+        }
+    }
 }
 
 // represents a consistent view of the current db state
 #[derive(Clone)]
 pub(crate) struct DbStateView {
     pub(crate) memtable: Arc<KVTable>,
+    pub(crate) column_family_memtables: BTreeMap<u32, Arc<KVTable>>,
     pub(crate) state: Arc<COWDbState>,
 }
 
@@ -420,6 +630,10 @@ impl DbStateReader for DbStateView {
         Arc::clone(&self.memtable)
     }
 
+    fn column_family_memtable(&self, column_family: u32) -> Option<Arc<KVTable>> {
+        self.column_family_memtables.get(&column_family).cloned()
+    }
+
     fn imm_memtable(&self) -> &VecDeque<Arc<ImmutableMemtable>> {
         &self.state.imm_memtable
     }
@@ -427,16 +641,29 @@ impl DbStateReader for DbStateView {
     fn core(&self) -> &CoreDbState {
         self.state.core()
     }
+
+    fn sst_range_tombstones(&self, column_family: u32) -> Cow<'_, [RangeTombstone]> {
+        Cow::Borrowed(
+            self.state
+                .sst_range_tombstones
+                .column_family(self.state.core(), column_family),
+        )
+    }
 }
 
 impl DbState {
-    pub fn new(manifest: DirtyObject<Manifest>) -> Self {
+    pub fn new(manifest: DirtyObject<Manifest>, comparator: KeyComparator) -> Self {
         Self {
-            memtable: WritableKVTable::new(),
+            memtable: WritableKVTable::with_column_families(
+                manifest.core().column_families.iter().map(|cf| cf.id),
+                &comparator,
+            ),
             state: Arc::new(COWDbState {
                 imm_memtable: VecDeque::new(),
                 manifest,
+                sst_range_tombstones: SstRangeTombstoneIndex::default(),
             }),
+            comparator,
             closed_result: WatchableOnceCell::new(),
         }
     }
@@ -448,6 +675,7 @@ impl DbState {
     pub fn view(&self) -> DbStateView {
         DbStateView {
             memtable: self.memtable.table().clone(),
+            column_family_memtables: self.memtable.column_families().clone(),
             state: self.state.clone(),
         }
     }
@@ -471,7 +699,11 @@ impl DbState {
                 Err(e) => Err(e.clone()),
             };
         }
-        let old_memtable = std::mem::replace(&mut self.memtable, WritableKVTable::new());
+        let new_memtable = WritableKVTable::with_column_families(
+            self.state.core().column_families.iter().map(|cf| cf.id),
+            &self.comparator,
+        );
+        let old_memtable = std::mem::replace(&mut self.memtable, new_memtable);
         self.modify(|modifier| {
             modifier
                 .state
@@ -544,6 +776,29 @@ impl<'a> StateModifier<'a> {
         };
 
         let my_db_state = self.state.core();
+        // the writer owns the list of column families and their L0s, the compactor
+        // owns their sorted runs.
+        let column_families = my_db_state
+            .column_families
+            .iter()
+            .map(|cf| match remote_manifest.core().column_family(cf.id) {
+                Some(remote_cf) => ColumnFamilyState {
+                    l0_last_compacted: remote_cf.l0_last_compacted,
+                    l0: match &remote_cf.l0_last_compacted {
+                        Some(l0_last_compacted) => cf
+                            .l0
+                            .iter()
+                            .cloned()
+                            .take_while(|sst| sst.id.unwrap_compacted_id() != *l0_last_compacted)
+                            .collect(),
+                        None => cf.l0.clone(),
+                    },
+                    compacted: remote_cf.compacted.clone(),
+                    ..cf.clone()
+                },
+                None => cf.clone(),
+            })
+            .collect();
         remote_manifest.value.core = CoreDbState {
             initialized: my_db_state.initialized,
             l0_last_compacted: remote_manifest.value.core.l0_last_compacted,
@@ -557,7 +812,11 @@ impl<'a> StateModifier<'a> {
             sequence_tracker: remote_manifest.value.core.sequence_tracker,
             checkpoints: remote_manifest.value.core.checkpoints,
             wal_object_store_uri: my_db_state.wal_object_store_uri.clone(),
+            column_families,
+            comparator: my_db_state.comparator.clone(),
         };
+        // the writer owns the handoff marker
+        remote_manifest.value.writer_handoff = self.state.manifest.value.writer_handoff;
         self.state.manifest = remote_manifest;
     }
 
@@ -586,6 +845,7 @@ impl WalIdStore for parking_lot::RwLock<DbState> {
 mod tests {
     use crate::checkpoint::Checkpoint;
     use crate::clock::{DefaultSystemClock, SystemClock};
+    use crate::comparator::KeyComparator;
     use crate::db_state::{DbState, SortedRun, SsTableHandle, SsTableId, SsTableInfo};
     use crate::manifest::store::test_utils::new_dirty_manifest;
     use crate::proptest_util::arbitrary;
@@ -600,7 +860,7 @@ mod tests {
     #[test]
     fn test_should_merge_db_state_with_new_checkpoints() {
         // given:
-        let mut db_state = DbState::new(new_dirty_manifest());
+        let mut db_state = DbState::new(new_dirty_manifest(), KeyComparator::default());
         // mimic an externally added checkpoint
         let mut updated_state = new_dirty_manifest();
         updated_state.value.core = db_state.state.core().clone();
@@ -627,7 +887,7 @@ mod tests {
     #[test]
     fn test_should_merge_db_state_with_l0s_up_to_last_compacted() {
         // given:
-        let mut db_state = DbState::new(new_dirty_manifest());
+        let mut db_state = DbState::new(new_dirty_manifest(), KeyComparator::default());
         add_l0s_to_dbstate(&mut db_state, 4);
         // mimic the compactor popping off l0s
         let mut compactor_state = new_dirty_manifest();
@@ -654,7 +914,7 @@ mod tests {
     #[test]
     fn test_should_merge_db_state_with_all_l0s_if_none_compacted() {
         // given:
-        let mut db_state = DbState::new(new_dirty_manifest());
+        let mut db_state = DbState::new(new_dirty_manifest(), KeyComparator::default());
         add_l0s_to_dbstate(&mut db_state, 4);
         let l0s = db_state.state.core().l0.clone();
 
@@ -693,7 +953,7 @@ mod tests {
         )| {
             let sorted_first_keys: BTreeSet<Bytes> = table_first_keys.into_iter().collect();
             let sorted_run = create_sorted_run(0, &sorted_first_keys);
-            let covering_tables = sorted_run.tables_covering_range(&range);
+            let covering_tables = sorted_run.tables_covering_range(&range, &KeyComparator::default());
             let first_key = sorted_first_keys.first().unwrap().clone();
 
             let range_start_key = test_utils::bound_as_option(range.start_bound())
@@ -748,6 +1008,11 @@ mod tests {
             filter_offset: 0,
             filter_len: 0,
             compression_codec: None,
+            range_tombstones: vec![],
+            column_family_keys: false,
+            prefix_extractor: None,
+            blob_refs: vec![],
+            encryption_key_id: None,
         }
     }
 }
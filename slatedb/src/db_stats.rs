@@ -1,4 +1,4 @@
-use crate::stats::{Counter, Gauge, StatRegistry};
+use crate::stats::{Counter, Gauge, Histogram, StatRegistry};
 use std::sync::Arc;
 
 macro_rules! db_stat_name {
@@ -11,6 +11,8 @@ pub const IMMUTABLE_MEMTABLE_FLUSHES: &str = db_stat_name!("immutable_memtable_f
 pub const SST_FILTER_FALSE_POSITIVES: &str = db_stat_name!("sst_filter_false_positives");
 pub const SST_FILTER_POSITIVES: &str = db_stat_name!("sst_filter_positives");
 pub const SST_FILTER_NEGATIVES: &str = db_stat_name!("sst_filter_negatives");
+pub const SST_PREFIX_FILTER_POSITIVES: &str = db_stat_name!("sst_prefix_filter_positives");
+pub const SST_PREFIX_FILTER_NEGATIVES: &str = db_stat_name!("sst_prefix_filter_negatives");
 pub const BACKPRESSURE_COUNT: &str = db_stat_name!("backpressure_count");
 pub const WAL_BUFFER_ESTIMATED_BYTES: &str = db_stat_name!("wal_buffer_estimated_bytes");
 pub const WAL_BUFFER_FLUSHES: &str = db_stat_name!("wal_buffer_flushes");
@@ -18,6 +20,18 @@ pub const GET_REQUESTS: &str = db_stat_name!("get_requests");
 pub const SCAN_REQUESTS: &str = db_stat_name!("scan_requests");
 pub const WRITE_BATCH_COUNT: &str = db_stat_name!("write_batch_count");
 pub const WRITE_OPS: &str = db_stat_name!("write_ops");
+pub const GET_LATENCY_US: &str = db_stat_name!("get_latency_us");
+pub const GET_MANY_LATENCY_US: &str = db_stat_name!("get_many_latency_us");
+pub const SCAN_LATENCY_US: &str = db_stat_name!("scan_latency_us");
+pub const WRITE_LATENCY_US: &str = db_stat_name!("write_latency_us");
+pub const WAL_FLUSH_LATENCY_US: &str = db_stat_name!("wal_flush_latency_us");
+pub const MEMTABLE_FLUSH_LATENCY_US: &str = db_stat_name!("memtable_flush_latency_us");
+pub const WRITE_SLOWDOWNS: &str = db_stat_name!("write_slowdowns");
+pub const WRITE_STOPS: &str = db_stat_name!("write_stops");
+pub const WRITE_STALL_FAILURES: &str = db_stat_name!("write_stall_failures");
+pub const WRITE_STALL_US: &str = db_stat_name!("write_stall_us");
+pub const WRITE_RATE_LIMIT_WAIT_US: &str = db_stat_name!("write_rate_limit_wait_us");
+pub const FLUSH_RATE_LIMIT_WAIT_US: &str = db_stat_name!("flush_rate_limit_wait_us");
 
 #[non_exhaustive]
 #[derive(Clone, Debug)]
@@ -28,11 +42,25 @@ pub(crate) struct DbStats {
     pub(crate) sst_filter_false_positives: Arc<Counter>,
     pub(crate) sst_filter_positives: Arc<Counter>,
     pub(crate) sst_filter_negatives: Arc<Counter>,
+    pub(crate) sst_prefix_filter_positives: Arc<Counter>,
+    pub(crate) sst_prefix_filter_negatives: Arc<Counter>,
     pub(crate) backpressure_count: Arc<Counter>,
     pub(crate) get_requests: Arc<Counter>,
     pub(crate) scan_requests: Arc<Counter>,
     pub(crate) write_batch_count: Arc<Counter>,
     pub(crate) write_ops: Arc<Counter>,
+    pub(crate) get_latency_us: Arc<Histogram>,
+    pub(crate) get_many_latency_us: Arc<Histogram>,
+    pub(crate) scan_latency_us: Arc<Histogram>,
+    pub(crate) write_latency_us: Arc<Histogram>,
+    pub(crate) wal_flush_latency_us: Arc<Histogram>,
+    pub(crate) memtable_flush_latency_us: Arc<Histogram>,
+    pub(crate) write_slowdowns: Arc<Counter>,
+    pub(crate) write_stops: Arc<Counter>,
+    pub(crate) write_stall_failures: Arc<Counter>,
+    pub(crate) write_stall_us: Arc<Histogram>,
+    pub(crate) write_rate_limit_wait_us: Arc<Histogram>,
+    pub(crate) flush_rate_limit_wait_us: Arc<Histogram>,
 }
 
 impl DbStats {
@@ -44,32 +72,151 @@ impl DbStats {
             sst_filter_false_positives: Arc::new(Counter::default()),
             sst_filter_positives: Arc::new(Counter::default()),
             sst_filter_negatives: Arc::new(Counter::default()),
+            sst_prefix_filter_positives: Arc::new(Counter::default()),
+            sst_prefix_filter_negatives: Arc::new(Counter::default()),
             backpressure_count: Arc::new(Counter::default()),
             get_requests: Arc::new(Counter::default()),
             scan_requests: Arc::new(Counter::default()),
             write_batch_count: Arc::new(Counter::default()),
             write_ops: Arc::new(Counter::default()),
+            get_latency_us: Arc::new(Histogram::default()),
+            get_many_latency_us: Arc::new(Histogram::default()),
+            scan_latency_us: Arc::new(Histogram::default()),
+            write_latency_us: Arc::new(Histogram::default()),
+            wal_flush_latency_us: Arc::new(Histogram::default()),
+            memtable_flush_latency_us: Arc::new(Histogram::default()),
+            write_slowdowns: Arc::new(Counter::default()),
+            write_stops: Arc::new(Counter::default()),
+            write_stall_failures: Arc::new(Counter::default()),
+            write_stall_us: Arc::new(Histogram::default()),
+            write_rate_limit_wait_us: Arc::new(Histogram::default()),
+            flush_rate_limit_wait_us: Arc::new(Histogram::default()),
         };
         registry.register(
             IMMUTABLE_MEMTABLE_FLUSHES,
+            "Number of immutable memtables flushed to L0.",
             stats.immutable_memtable_flushes.clone(),
         );
         registry.register(
             WAL_BUFFER_ESTIMATED_BYTES,
+            "Estimated size in bytes of the WAL buffer.",
             stats.wal_buffer_estimated_bytes.clone(),
         );
-        registry.register(WAL_BUFFER_FLUSHES, stats.wal_buffer_flushes.clone());
+        registry.register(
+            WAL_BUFFER_FLUSHES,
+            "Number of WAL buffer flushes.",
+            stats.wal_buffer_flushes.clone(),
+        );
         registry.register(
             SST_FILTER_FALSE_POSITIVES,
+            "Number of SST bloom filter matches for keys not in the SST.",
             stats.sst_filter_false_positives.clone(),
         );
-        registry.register(SST_FILTER_POSITIVES, stats.sst_filter_positives.clone());
-        registry.register(SST_FILTER_NEGATIVES, stats.sst_filter_negatives.clone());
-        registry.register(BACKPRESSURE_COUNT, stats.backpressure_count.clone());
-        registry.register(GET_REQUESTS, stats.get_requests.clone());
-        registry.register(SCAN_REQUESTS, stats.scan_requests.clone());
-        registry.register(WRITE_BATCH_COUNT, stats.write_batch_count.clone());
-        registry.register(WRITE_OPS, stats.write_ops.clone());
+        registry.register(
+            SST_FILTER_POSITIVES,
+            "Number of SST bloom filter matches.",
+            stats.sst_filter_positives.clone(),
+        );
+        registry.register(
+            SST_FILTER_NEGATIVES,
+            "Number of SST bloom filter misses.",
+            stats.sst_filter_negatives.clone(),
+        );
+        registry.register(
+            SST_PREFIX_FILTER_POSITIVES,
+            "Number of SST prefix bloom filter matches.",
+            stats.sst_prefix_filter_positives.clone(),
+        );
+        registry.register(
+            SST_PREFIX_FILTER_NEGATIVES,
+            "Number of SST prefix bloom filter misses.",
+            stats.sst_prefix_filter_negatives.clone(),
+        );
+        registry.register(
+            BACKPRESSURE_COUNT,
+            "Number of times writes waited for flushes to catch up.",
+            stats.backpressure_count.clone(),
+        );
+        registry.register(
+            GET_REQUESTS,
+            "Number of get requests.",
+            stats.get_requests.clone(),
+        );
+        registry.register(
+            SCAN_REQUESTS,
+            "Number of scan requests.",
+            stats.scan_requests.clone(),
+        );
+        registry.register(
+            WRITE_BATCH_COUNT,
+            "Number of write batches.",
+            stats.write_batch_count.clone(),
+        );
+        registry.register(
+            WRITE_OPS,
+            "Number of operations in write batches.",
+            stats.write_ops.clone(),
+        );
+        registry.register(
+            GET_LATENCY_US,
+            "Latency in microseconds of get requests.",
+            stats.get_latency_us.clone(),
+        );
+        registry.register(
+            GET_MANY_LATENCY_US,
+            "Latency in microseconds of multi-key get requests.",
+            stats.get_many_latency_us.clone(),
+        );
+        registry.register(
+            SCAN_LATENCY_US,
+            "Latency in microseconds of opening scan iterators.",
+            stats.scan_latency_us.clone(),
+        );
+        registry.register(
+            WRITE_LATENCY_US,
+            "Latency in microseconds of write batches.",
+            stats.write_latency_us.clone(),
+        );
+        registry.register(
+            WAL_FLUSH_LATENCY_US,
+            "Latency in microseconds of writing WAL SSTs.",
+            stats.wal_flush_latency_us.clone(),
+        );
+        registry.register(
+            MEMTABLE_FLUSH_LATENCY_US,
+            "Latency in microseconds of writing immutable memtables to L0.",
+            stats.memtable_flush_latency_us.clone(),
+        );
+        registry.register(
+            WRITE_SLOWDOWNS,
+            "Number of writes delayed because L0 or the unflushed bytes crossed a slowdown limit.",
+            stats.write_slowdowns.clone(),
+        );
+        registry.register(
+            WRITE_STOPS,
+            "Number of writes stopped until compactions reduced the number of L0 SSTs.",
+            stats.write_stops.clone(),
+        );
+        registry.register(
+            WRITE_STALL_FAILURES,
+            "Number of writes that failed instead of being stalled or rate limited.",
+            stats.write_stall_failures.clone(),
+        );
+        registry.register(
+            WRITE_STALL_US,
+            "Time in microseconds that writes were delayed or stopped.",
+            stats.write_stall_us.clone(),
+        );
+        registry.register(
+            WRITE_RATE_LIMIT_WAIT_US,
+            "Time in microseconds that writes waited for the write rate limiter.",
+            stats.write_rate_limit_wait_us.clone(),
+        );
+        registry.register(
+            FLUSH_RATE_LIMIT_WAIT_US,
+            "Time in microseconds that memtable flushes waited for the flush rate limiter.",
+            stats.flush_rate_limit_wait_us.clone(),
+        );
         stats
     }
 }
@@ -7,6 +7,7 @@ use uuid::Uuid;
 
 use crate::batch::WriteBatch;
 use crate::bytes_range::BytesRange;
+use crate::column_family::{ColumnFamily, DEFAULT_COLUMN_FAMILY_ID};
 use crate::config::{MergeOptions, PutOptions, ReadOptions, ScanOptions, WriteOptions};
 use crate::db::DbInner;
 use crate::db_iter::{DbIterator, DbIteratorRangeTracker};
@@ -132,6 +133,7 @@ impl DBTransaction {
         self.db_inner
             .reader
             .get_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
                 key,
                 options,
                 &db_state,
@@ -142,6 +144,66 @@ impl DBTransaction {
             .map_err(Into::into)
     }
 
+    /// Get the values of several keys from the transaction with default read options.
+    /// This operation will track the reads for conflict detection in SSI mode.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, SlateDBError>`: the value of each key, in the order of
+    ///   `keys`, or None if the key does not exist
+    pub async fn get_many<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, &ReadOptions::default())
+            .await
+    }
+
+    /// Get the values of several keys from the transaction with custom read options.
+    /// This operation will track the reads for conflict detection in SSI mode.
+    ///
+    /// ## Arguments
+    /// - `keys`: the keys to get
+    /// - `options`: the read options to use
+    ///
+    /// ## Returns
+    /// - `Result<Vec<Option<Bytes>>, SlateDBError>`: the value of each key, in the order of
+    ///   `keys`, or None if the key does not exist
+    pub async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.db_inner.check_closed()?;
+        let keys: Vec<Bytes> = keys
+            .iter()
+            .map(|key| Bytes::copy_from_slice(key.as_ref()))
+            .collect();
+
+        // Track read keys for SSI conflict detection if needed
+        if self.isolation_level == IsolationLevel::SerializableSnapshot {
+            let read_keys: HashSet<Bytes> = keys.iter().cloned().collect();
+            self.txn_manager.track_read_keys(&self.txn_id, &read_keys);
+        }
+
+        let db_state = self.db_inner.state.read().view();
+        let write_batch_cloned = self.write_batch.read().clone();
+        self.db_inner
+            .reader
+            .get_many_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                &keys,
+                options,
+                &db_state,
+                Some(write_batch_cloned),
+                Some(self.started_seq),
+            )
+            .await
+            .map_err(Into::into)
+    }
+
     /// Scan a range of keys using the default scan options.
     /// This operation will track the read range for conflict detection in SSI mode.
     ///
@@ -183,11 +245,13 @@ impl DBTransaction {
         let end = range
             .end_bound()
             .map(|b| Bytes::copy_from_slice(b.as_ref()));
-        let range = (start, end);
+        let range = BytesRange::new_by(start, end, self.db_inner.table_store.comparator());
 
         // Track read range for SSI conflict detection if needed
         let range_tracker = if self.isolation_level == IsolationLevel::SerializableSnapshot {
-            let tracker = Arc::new(DbIteratorRangeTracker::new());
+            let tracker = Arc::new(DbIteratorRangeTracker::new(
+                self.db_inner.table_store.comparator().clone(),
+            ));
             self.range_trackers.lock().push(tracker.clone());
             Some(tracker)
         } else {
@@ -205,7 +269,8 @@ impl DBTransaction {
         self.db_inner
             .reader
             .scan_with_options(
-                BytesRange::from(range),
+                DEFAULT_COLUMN_FAMILY_ID,
+                range,
                 options,
                 &db_state,
                 Some(write_batch_cloned),
@@ -301,6 +366,57 @@ impl DBTransaction {
         Ok(())
     }
 
+    /// Put a key-value pair into a column family in the transaction.
+    /// The write will be buffered in the transaction's write batch until commit,
+    /// and is committed atomically with the transaction's other writes.
+    ///
+    /// ## Arguments
+    /// - `column_family`: the column family to write to
+    /// - `key`: the key to write
+    /// - `value`: the value to write
+    pub fn put_cf<K, V>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+        value: V,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.write_batch.write().put_cf(column_family, key, value);
+        Ok(())
+    }
+
+    /// Merge a key-value pair into a column family in the transaction.
+    pub fn merge_cf<K, V>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+        value: V,
+    ) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        self.write_batch.write().merge_cf(column_family, key, value);
+        Ok(())
+    }
+
+    /// Delete a key from a column family in the transaction.
+    ///
+    /// ## Arguments
+    /// - `column_family`: the column family to delete from
+    /// - `key`: the key to delete
+    pub fn delete_cf<K: AsRef<[u8]>>(
+        &self,
+        column_family: &ColumnFamily,
+        key: K,
+    ) -> Result<(), crate::Error> {
+        self.write_batch.write().delete_cf(column_family, key);
+        Ok(())
+    }
+
     /// Commit the transaction by applying all buffered operations to the database.
     ///
     /// This method finalizes the transaction by writing all pending puts, deletes, and other
@@ -384,6 +500,14 @@ impl DbRead for DBTransaction {
         self.get_with_options(key, options).await
     }
 
+    async fn get_many_with_options<K: AsRef<[u8]> + Send + Sync>(
+        &self,
+        keys: &[K],
+        options: &ReadOptions,
+    ) -> Result<Vec<Option<Bytes>>, crate::Error> {
+        self.get_many_with_options(keys, options).await
+    }
+
     async fn scan_with_options<K, T>(
         &self,
         range: T,
@@ -610,6 +734,7 @@ mod tests {
         // Commit without waiting for durability
         txn.commit_with_options(&WriteOptions {
             await_durable: false,
+            ..WriteOptions::default()
         })
         .await
         .unwrap();
@@ -0,0 +1,181 @@
+use async_trait::async_trait;
+use std::collections::VecDeque;
+
+use crate::comparator::KeyComparator;
+use crate::error::SlateDBError;
+use crate::iter::KeyValueIterator;
+use crate::types::RowEntry;
+
+/// An iterator adapter that turns the raw output of a descending iterator into the
+/// order expected by the rest of the read path.
+///
+/// Blocks, SSTs, sorted runs, memtables and write batches iterate in descending order
+/// by walking their entries back to front. Since versions of a key are stored newest
+/// first, this returns the versions of each key oldest first. [`MergeIterator`] and
+/// [`MergeOperatorIterator`] rely on seeing the newest version of a key first, so this
+/// adapter buffers all versions of the current key and returns them in reverse.
+///
+/// [`MergeIterator`]: crate::merge_iterator::MergeIterator
+/// [`MergeOperatorIterator`]: crate::merge_operator::MergeOperatorIterator
+pub(crate) struct DescendingIterator<T: KeyValueIterator> {
+    iterator: T,
+    /// The versions of the current key that have not been returned yet, newest first.
+    versions: VecDeque<RowEntry>,
+    /// The first entry of the next key, read from the underlying iterator while
+    /// collecting the versions of the current key.
+    peeked: Option<RowEntry>,
+    /// The order of the keys of the underlying iterator.
+    comparator: KeyComparator,
+}
+
+impl<T: KeyValueIterator> DescendingIterator<T> {
+    pub(crate) fn new(iterator: T) -> Self {
+        Self {
+            iterator,
+            versions: VecDeque::new(),
+            peeked: None,
+            comparator: KeyComparator::default(),
+        }
+    }
+
+    pub(crate) fn with_comparator(mut self, comparator: KeyComparator) -> Self {
+        self.comparator = comparator;
+        self
+    }
+
+    async fn load_next_key(&mut self) -> Result<(), SlateDBError> {
+        let first = match self.peeked.take() {
+            Some(entry) => entry,
+            None => match self.iterator.next_entry().await? {
+                Some(entry) => entry,
+                None => return Ok(()),
+            },
+        };
+        self.versions.push_front(first);
+        while let Some(entry) = self.iterator.next_entry().await? {
+            if entry.key != self.versions[0].key {
+                self.peeked = Some(entry);
+                break;
+            }
+            self.versions.push_front(entry);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl<T: KeyValueIterator> KeyValueIterator for DescendingIterator<T> {
+    async fn init(&mut self) -> Result<(), SlateDBError> {
+        self.iterator.init().await
+    }
+
+    async fn next_entry(&mut self) -> Result<Option<RowEntry>, SlateDBError> {
+        if self.versions.is_empty() {
+            self.load_next_key().await?;
+        }
+        Ok(self.versions.pop_front())
+    }
+
+    async fn seek(&mut self, next_key: &[u8]) -> Result<(), SlateDBError> {
+        if self
+            .versions
+            .front()
+            .is_some_and(|entry| self.comparator.compare(&entry.key, next_key).is_le())
+        {
+            return Ok(());
+        }
+        self.versions.clear();
+        if self
+            .peeked
+            .as_ref()
+            .is_some_and(|entry| self.comparator.compare(&entry.key, next_key).is_le())
+        {
+            return Ok(());
+        }
+        self.peeked = None;
+        self.iterator.seek(next_key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test_utils::{assert_iterator, TestIterator};
+
+    #[tokio::test]
+    async fn should_return_versions_of_each_key_newest_first() {
+        let iter = TestIterator::new()
+            .with_entry(b"c", b"c1", 1)
+            .with_entry(b"b", b"b1", 2)
+            .with_entry(b"b", b"b2", 5)
+            .with_entry(b"b", b"b3", 7)
+            .with_entry(b"a", b"a1", 3);
+
+        let mut iter = DescendingIterator::new(iter);
+
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"c", b"c1", 1),
+                RowEntry::new_value(b"b", b"b3", 7),
+                RowEntry::new_value(b"b", b"b2", 5),
+                RowEntry::new_value(b"b", b"b1", 2),
+                RowEntry::new_value(b"a", b"a1", 3),
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn should_keep_buffered_versions_when_seeking_to_current_key() {
+        let iter = TestIterator::new()
+            .with_entry(b"b", b"b1", 2)
+            .with_entry(b"b", b"b2", 5)
+            .with_entry(b"a", b"a1", 3);
+
+        let mut iter = DescendingIterator::new(iter);
+        iter.init().await.unwrap();
+        assert_eq!(
+            iter.next_entry().await.unwrap(),
+            Some(RowEntry::new_value(b"b", b"b2", 5))
+        );
+
+        iter.seek(b"b").await.unwrap();
+
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"b", b"b1", 2),
+                RowEntry::new_value(b"a", b"a1", 3),
+            ],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn should_skip_buffered_versions_when_seeking_past_current_key() {
+        let iter = TestIterator::new()
+            .with_entry(b"c", b"c1", 1)
+            .with_entry(b"c", b"c2", 2)
+            .with_entry(b"b", b"b1", 2)
+            .with_entry(b"a", b"a1", 3);
+
+        let mut iter = DescendingIterator::new(iter);
+        iter.init().await.unwrap();
+        assert_eq!(
+            iter.next_entry().await.unwrap(),
+            Some(RowEntry::new_value(b"c", b"c2", 2))
+        );
+
+        iter.seek(b"bb").await.unwrap();
+
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"b", b"b1", 2),
+                RowEntry::new_value(b"a", b"a1", 3),
+            ],
+        )
+        .await;
+    }
+}
@@ -198,41 +198,49 @@ impl<T: Send + std::fmt::Debug> MessageDispatcher<T> {
     /// 2. Else, if there is a message, read it and invoke [MessageHandler::handle].
     /// 3. Else, if there is a ticker event, read it and invoke [MessageHandler::handle].
     ///
+    /// The tickers are recreated from [MessageHandler::tickers] whenever
+    /// [MessageHandler::tickers_changed] returns true after a message is handled.
+    ///
     /// ## Returns
     ///
     /// A [Result] containing `Ok(())` on clean shutdown, or an error if the handler
     /// fails for any reason.
     async fn run(&mut self) -> Result<(), SlateDBError> {
-        let mut tickers = self
-            .handler
-            .tickers()
-            .into_iter()
-            .map(|(dur, factory)| MessageDispatcherTicker::new(self.clock.ticker(dur), factory))
-            .collect::<Vec<_>>();
-        let mut ticker_futures: FuturesUnordered<_> =
-            tickers.iter_mut().map(|t| t.tick()).collect();
         loop {
-            fail_point!(Arc::clone(&self.fp_registry), "dispatcher-run-loop", |_| {
-                Err(SlateDBError::Fenced)
-            });
-            tokio::select! {
-                biased;
-                // stop the loop if we're in an error state or cancelled
-                _ = self.cancellation_token.cancelled() => {
+            let mut tickers = self
+                .handler
+                .tickers()
+                .into_iter()
+                .map(|(dur, factory)| MessageDispatcherTicker::new(self.clock.ticker(dur), factory))
+                .collect::<Vec<_>>();
+            let mut ticker_futures: FuturesUnordered<_> =
+                tickers.iter_mut().map(|t| t.tick()).collect();
+            loop {
+                fail_point!(Arc::clone(&self.fp_registry), "dispatcher-run-loop", |_| {
+                    Err(SlateDBError::Fenced)
+                });
+                tokio::select! {
+                    biased;
+                    // stop the loop if we're in an error state or cancelled
+                    _ = self.cancellation_token.cancelled() => {
+                        return Ok(());
+                    }
+                    // if no errors, prioritize messages
+                    Some(message) = self.rx.recv() => {
+                        self.handler.handle(message).await?;
+                    },
+                    // if no messages, check tickers
+                    Some((message, ticker)) = ticker_futures.next() => {
+                        self.handler.handle(message).await?;
+                        ticker_futures.push(ticker.tick());
+                    },
+                }
+                // recreate the tickers if the handler changed their schedules
+                if self.handler.tickers_changed() {
                     break;
                 }
-                // if no errors, prioritize messages
-                Some(message) = self.rx.recv() => {
-                    self.handler.handle(message).await?;
-                },
-                // if no messages, check tickers
-                Some((message, ticker)) = ticker_futures.next() => {
-                    self.handler.handle(message).await?;
-                    ticker_futures.push(ticker.tick());
-                },
             }
         }
-        Ok(())
     }
 
     /// Tells the handler to clean up any resources.
@@ -325,6 +333,15 @@ pub(crate) trait MessageHandler<T: Send>: Send {
         vec![]
     }
 
+    /// Returns true if the schedules returned by [MessageHandler::tickers] changed since
+    /// the tickers were created, for example because a message updated an interval.
+    /// [MessageDispatcher::run] calls this after each handled message, and recreates the
+    /// tickers when it returns true. Implementations should only return true once per
+    /// change.
+    fn tickers_changed(&mut self) -> bool {
+        false
+    }
+
     /// Handles a message. Messages can come from either a channel or a ticker. See
     /// [crate::dispatcher] for details.
     ///
@@ -686,6 +703,7 @@ mod test {
     enum TestMessage {
         Channel(i32),
         Tick(i32),
+        SetTickers(Vec<(Duration, u8)>),
     }
 
     #[derive(Debug, Clone, Copy, PartialEq, Eq)]
@@ -701,6 +719,7 @@ mod test {
         tickers: Vec<(Duration, u8)>,
         clock: Arc<dyn SystemClock>,
         clock_schedule: VecDeque<Duration>,
+        tickers_changed: bool,
     }
 
     impl TestHandler {
@@ -715,6 +734,7 @@ mod test {
                 tickers: vec![],
                 clock,
                 clock_schedule: VecDeque::new(),
+                tickers_changed: false,
             }
         }
 
@@ -742,7 +762,15 @@ mod test {
             tickers
         }
 
+        fn tickers_changed(&mut self) -> bool {
+            std::mem::take(&mut self.tickers_changed)
+        }
+
         async fn handle(&mut self, message: TestMessage) -> Result<(), SlateDBError> {
+            if let TestMessage::SetTickers(tickers) = &message {
+                self.tickers = tickers.clone();
+                self.tickers_changed = true;
+            }
             self.log.lock().unwrap().push((Phase::Pre, message));
             if let Some(advance_duration) = self.clock_schedule.pop_front() {
                 self.clock.advance(advance_duration).await;
@@ -885,6 +913,51 @@ mod test {
         );
     }
 
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_dispatcher_recreates_changed_tickers() {
+        let log = Arc::new(Mutex::new(Vec::<(Phase, TestMessage)>::new()));
+        let (tx, rx) = mpsc::unbounded_channel();
+        let clock = Arc::new(MockSystemClock::new());
+        let handler = TestHandler::new(log.clone(), WatchableOnceCell::new(), clock.clone())
+            .add_ticker(Duration::from_secs(100), 1);
+        let cancellation_token = CancellationToken::new();
+        let mut dispatcher = MessageDispatcher::new(
+            Box::new(handler),
+            rx,
+            clock.clone(),
+            cancellation_token.clone(),
+        );
+        let join = tokio::spawn(async move { dispatcher.run().await });
+
+        // The first tick of the initial ticker is immediate
+        wait_for_message_count(log.clone(), 1).await;
+        let new_tickers = vec![(Duration::from_millis(5), 2)];
+        tx.send(TestMessage::SetTickers(new_tickers.clone()))
+            .unwrap();
+        // The new ticker replaces the initial one and ticks immediately
+        wait_for_message_count(log.clone(), 3).await;
+        clock.advance(Duration::from_millis(5)).await;
+        wait_for_message_count(log.clone(), 4).await;
+
+        cancellation_token.cancel();
+        let result = timeout(Duration::from_secs(30), join)
+            .await
+            .expect("dispatcher did not stop in time")
+            .expect("join failed");
+
+        assert!(matches!(result, Ok(())));
+        let messages = log.lock().unwrap().clone();
+        assert_eq!(
+            messages,
+            vec![
+                (Phase::Pre, TestMessage::Tick(1)),
+                (Phase::Pre, TestMessage::SetTickers(new_tickers)),
+                (Phase::Pre, TestMessage::Tick(2)),
+                (Phase::Pre, TestMessage::Tick(2)),
+            ]
+        );
+    }
+
     #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
     async fn test_dispatcher_prioritizes_messages_over_tickers() {
         let log = Arc::new(Mutex::new(Vec::<(Phase, TestMessage)>::new()));
@@ -0,0 +1,358 @@
+//! Client-side encryption of the data SlateDB writes to object storage.
+//!
+//! When a database is opened with an [`EncryptionProvider`], the data blocks, indexes,
+//! filters and metadata of SSTs (including the WAL), the values in blob files, and the
+//! manifest and compactions files are encrypted before they're written, and decrypted
+//! after they're read.
+//!
+//! Every payload records the id of the key it was encrypted with. SSTs record it in
+//! their [`crate::db_state::SsTableInfo`], and the other payloads are sealed in an
+//! envelope that starts with it:
+//!
+//! ```txt
+//!  |--------------------------------------------|
+//!  |  u16          |  var    |  var             |
+//!  |---------------|---------|------------------|
+//!  | key_id_len    | key_id  | ciphertext       |
+//!  |--------------------------------------------|
+//! ```
+//!
+//! New data is always encrypted with the provider's current key, so keys are rotated
+//! by changing the current key. The provider must still be able to decrypt with the
+//! old keys until compactions have rewritten the SSTs encrypted with them.
+
+use std::sync::Arc;
+
+use bytes::{Buf, BufMut, Bytes};
+use thiserror::Error;
+
+use crate::error::SlateDBError;
+use crate::transactional_object::ObjectCodec;
+
+#[non_exhaustive]
+#[derive(Clone, Debug, Error)]
+pub enum EncryptionError {
+    #[error("encryption key unavailable [key_id={key_id}]")]
+    KeyUnavailable { key_id: String },
+
+    #[error("encryption failed: {msg}")]
+    EncryptionFailed { msg: String },
+
+    #[error("decryption failed: {msg}")]
+    DecryptionFailed { msg: String },
+}
+
+/// A trait for encrypting the data SlateDB writes to object storage with keys managed
+/// by the application, e.g. in a key management service.
+///
+/// Implementations are responsible for the cipher, and should use an authenticated
+/// cipher (such as AES-GCM) with a random nonce that's included in the ciphertext.
+///
+/// # Examples
+/// Here's the outline of a provider that encrypts with keys held in memory:
+/// ```
+/// use std::collections::HashMap;
+///
+/// use bytes::Bytes;
+/// use slatedb::{EncryptionError, EncryptionProvider};
+///
+/// struct InMemoryKeys {
+///     current_key_id: String,
+///     keys: HashMap<String, [u8; 32]>,
+/// }
+///
+/// impl EncryptionProvider for InMemoryKeys {
+///     fn current_key_id(&self) -> String {
+///         self.current_key_id.clone()
+///     }
+///
+///     fn encrypt(&self, key_id: &str, plaintext: &[u8]) -> Result<Bytes, EncryptionError> {
+///         let key = self.keys.get(key_id).ok_or_else(|| EncryptionError::KeyUnavailable {
+///             key_id: key_id.to_string(),
+///         })?;
+///         // encrypt `plaintext` with `key` using an authenticated cipher
+///         # let _ = key;
+///         # Ok(Bytes::copy_from_slice(plaintext))
+///     }
+///
+///     fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Result<Bytes, EncryptionError> {
+///         let key = self.keys.get(key_id).ok_or_else(|| EncryptionError::KeyUnavailable {
+///             key_id: key_id.to_string(),
+///         })?;
+///         // decrypt `ciphertext` with `key`
+///         # let _ = key;
+///         # Ok(Bytes::copy_from_slice(ciphertext))
+///     }
+/// }
+/// ```
+pub trait EncryptionProvider {
+    /// Returns the id of the key new data is encrypted with. The id is stored alongside
+    /// the encrypted data, and passed to [`EncryptionProvider::decrypt`] to decrypt it.
+    fn current_key_id(&self) -> String;
+
+    /// Encrypts `plaintext` with the key `key_id`.
+    ///
+    /// # Arguments
+    /// * `key_id` - The id of the key to encrypt with
+    /// * `plaintext` - The data to encrypt
+    ///
+    /// # Returns
+    /// * The ciphertext, or [`EncryptionError::KeyUnavailable`] if the key can't be used
+    fn encrypt(&self, key_id: &str, plaintext: &[u8]) -> Result<Bytes, EncryptionError>;
+
+    /// Decrypts `ciphertext` that was encrypted with the key `key_id`.
+    ///
+    /// # Arguments
+    /// * `key_id` - The id of the key the data was encrypted with
+    /// * `ciphertext` - The data to decrypt
+    ///
+    /// # Returns
+    /// * The plaintext, or [`EncryptionError::KeyUnavailable`] if the key can't be used
+    fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Result<Bytes, EncryptionError>;
+}
+
+pub(crate) type EncryptionProviderType = Arc<dyn EncryptionProvider + Send + Sync>;
+
+/// The format version that marks manifest and compactions files sealed in an envelope.
+/// It's distinct from the format versions of the files themselves, which are sealed
+/// along with the rest of the files.
+pub(crate) const ENCRYPTED_OBJECT_FORMAT_VERSION: u16 = 0xEC01;
+
+/// Encrypts data with the current key of a provider, which is fixed when the encryptor
+/// is created so all parts of an SST are encrypted with the same key.
+#[derive(Clone)]
+pub(crate) struct Encryptor {
+    provider: EncryptionProviderType,
+    key_id: String,
+}
+
+impl Encryptor {
+    pub(crate) fn new(provider: EncryptionProviderType) -> Self {
+        let key_id = provider.current_key_id();
+        Self { provider, key_id }
+    }
+
+    pub(crate) fn key_id(&self) -> &str {
+        &self.key_id
+    }
+
+    pub(crate) fn encrypt(&self, plaintext: &[u8]) -> Result<Bytes, SlateDBError> {
+        Ok(self.provider.encrypt(&self.key_id, plaintext)?)
+    }
+
+    /// Encrypts `plaintext` and seals it in an envelope with the id of the key.
+    pub(crate) fn seal(&self, plaintext: &[u8]) -> Result<Bytes, SlateDBError> {
+        let ciphertext = self.encrypt(plaintext)?;
+        let key_id_len = u16::try_from(self.key_id.len()).expect("key id len > u16");
+        let mut sealed =
+            Vec::with_capacity(std::mem::size_of::<u16>() + self.key_id.len() + ciphertext.len());
+        sealed.put_u16(key_id_len);
+        sealed.put_slice(self.key_id.as_bytes());
+        sealed.put_slice(&ciphertext);
+        Ok(Bytes::from(sealed))
+    }
+}
+
+/// Decrypts data that was encrypted with the key `key_id`. Fails with
+/// [`SlateDBError::EncryptionKeyUnavailable`] if there's no provider to decrypt it.
+pub(crate) fn decrypt(
+    provider: Option<&EncryptionProviderType>,
+    key_id: &str,
+    ciphertext: &[u8],
+) -> Result<Bytes, SlateDBError> {
+    let Some(provider) = provider else {
+        return Err(SlateDBError::EncryptionKeyUnavailable {
+            key_id: key_id.to_string(),
+        });
+    };
+    Ok(provider.decrypt(key_id, ciphertext)?)
+}
+
+/// Opens an envelope sealed by [`Encryptor::seal`] and decrypts its contents.
+pub(crate) fn unseal(
+    provider: Option<&EncryptionProviderType>,
+    mut sealed: Bytes,
+) -> Result<Bytes, SlateDBError> {
+    if sealed.remaining() < std::mem::size_of::<u16>() {
+        return Err(SlateDBError::InvalidEncryptionEnvelope);
+    }
+    let key_id_len = sealed.get_u16() as usize;
+    if sealed.remaining() < key_id_len {
+        return Err(SlateDBError::InvalidEncryptionEnvelope);
+    }
+    let key_id = sealed.split_to(key_id_len);
+    let key_id =
+        std::str::from_utf8(&key_id).map_err(|_| SlateDBError::InvalidEncryptionEnvelope)?;
+    decrypt(provider, key_id, &sealed)
+}
+
+/// Wraps the codec of a transactional object, such as the manifest, to seal the encoded
+/// objects in an envelope. Objects that aren't sealed are decoded as is, so databases
+/// that didn't use encryption before can start using it.
+pub(crate) struct EncryptedObjectCodec<T> {
+    inner: Box<dyn ObjectCodec<T>>,
+    provider: Option<EncryptionProviderType>,
+}
+
+impl<T> EncryptedObjectCodec<T> {
+    /// Creates a codec that seals objects if `provider` is set. Without a provider, no
+    /// objects are sealed, and sealed objects fail to decode with the id of their key.
+    pub(crate) fn new(
+        inner: Box<dyn ObjectCodec<T>>,
+        provider: Option<EncryptionProviderType>,
+    ) -> Self {
+        Self { inner, provider }
+    }
+}
+
+impl<T> ObjectCodec<T> for EncryptedObjectCodec<T> {
+    fn encode(&self, value: &T) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
+        let encoded = self.inner.encode(value)?;
+        let Some(provider) = &self.provider else {
+            return Ok(encoded);
+        };
+        let sealed = Encryptor::new(provider.clone()).seal(&encoded)?;
+        let mut bytes = Vec::with_capacity(std::mem::size_of::<u16>() + sealed.len());
+        bytes.put_u16(ENCRYPTED_OBJECT_FORMAT_VERSION);
+        bytes.put_slice(&sealed);
+        Ok(Bytes::from(bytes))
+    }
+
+    fn decode(&self, bytes: &Bytes) -> Result<T, Box<dyn std::error::Error + Send + Sync>> {
+        if bytes.len() < 2
+            || u16::from_be_bytes([bytes[0], bytes[1]]) != ENCRYPTED_OBJECT_FORMAT_VERSION
+        {
+            return self.inner.decode(bytes);
+        }
+        let decrypted = unseal(self.provider.as_ref(), bytes.slice(2..))?;
+        self.inner.decode(&decrypted)
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use std::collections::HashMap;
+    use std::sync::Mutex;
+
+    use super::*;
+
+    /// A provider that "encrypts" by xor-ing data with a byte per key, which is enough
+    /// to check that no plaintext reaches object storage.
+    pub(crate) struct XorEncryptionProvider {
+        current_key_id: Mutex<String>,
+        keys: Mutex<HashMap<String, u8>>,
+    }
+
+    impl XorEncryptionProvider {
+        pub(crate) fn new(key_id: &str, key: u8) -> Self {
+            Self {
+                current_key_id: Mutex::new(key_id.to_string()),
+                keys: Mutex::new(HashMap::from([(key_id.to_string(), key)])),
+            }
+        }
+
+        /// Adds a key and makes it the current key.
+        pub(crate) fn rotate(&self, key_id: &str, key: u8) {
+            self.keys.lock().unwrap().insert(key_id.to_string(), key);
+            *self.current_key_id.lock().unwrap() = key_id.to_string();
+        }
+
+        pub(crate) fn remove_key(&self, key_id: &str) {
+            self.keys.lock().unwrap().remove(key_id);
+        }
+
+        fn xor(&self, key_id: &str, data: &[u8]) -> Result<Bytes, EncryptionError> {
+            let key = *self.keys.lock().unwrap().get(key_id).ok_or_else(|| {
+                EncryptionError::KeyUnavailable {
+                    key_id: key_id.to_string(),
+                }
+            })?;
+            Ok(data.iter().map(|b| b ^ key).collect())
+        }
+    }
+
+    impl EncryptionProvider for XorEncryptionProvider {
+        fn current_key_id(&self) -> String {
+            self.current_key_id.lock().unwrap().clone()
+        }
+
+        fn encrypt(&self, key_id: &str, plaintext: &[u8]) -> Result<Bytes, EncryptionError> {
+            self.xor(key_id, plaintext)
+        }
+
+        fn decrypt(&self, key_id: &str, ciphertext: &[u8]) -> Result<Bytes, EncryptionError> {
+            self.xor(key_id, ciphertext)
+        }
+    }
+
+    struct BytesCodec;
+
+    impl ObjectCodec<Bytes> for BytesCodec {
+        fn encode(&self, value: &Bytes) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(value.clone())
+        }
+
+        fn decode(&self, bytes: &Bytes) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
+            Ok(bytes.clone())
+        }
+    }
+
+    #[test]
+    fn test_should_seal_and_unseal() {
+        let provider: EncryptionProviderType = Arc::new(XorEncryptionProvider::new("k1", 0x5a));
+        let sealed = Encryptor::new(provider.clone()).seal(b"plaintext").unwrap();
+        assert_eq!(&sealed[..4], b"\x00\x02k1");
+        assert!(!sealed
+            .windows(b"plaintext".len())
+            .any(|w| w == b"plaintext"));
+        assert_eq!(
+            unseal(Some(&provider), sealed).unwrap(),
+            Bytes::from_static(b"plaintext")
+        );
+    }
+
+    #[test]
+    fn test_should_fail_unseal_without_key() {
+        let provider = Arc::new(XorEncryptionProvider::new("k1", 0x5a));
+        let sealed = Encryptor::new(provider.clone()).seal(b"plaintext").unwrap();
+
+        let result = unseal(None, sealed.clone());
+        assert!(
+            matches!(result, Err(SlateDBError::EncryptionKeyUnavailable { key_id }) if key_id == "k1")
+        );
+
+        provider.remove_key("k1");
+        let provider: EncryptionProviderType = provider;
+        let result = unseal(Some(&provider), sealed);
+        assert!(matches!(
+            result,
+            Err(SlateDBError::EncryptionKeyUnavailable { key_id }) if key_id == "k1"
+        ));
+    }
+
+    #[test]
+    fn test_should_decode_unsealed_objects() {
+        let provider = Arc::new(XorEncryptionProvider::new("k1", 0x5a));
+        let codec = EncryptedObjectCodec::new(Box::new(BytesCodec), Some(provider.clone()));
+        let plain = Bytes::from_static(b"\x00\x01object");
+        assert_eq!(codec.decode(&plain).unwrap(), plain);
+
+        let encoded = codec.encode(&plain).unwrap();
+        assert_eq!(
+            u16::from_be_bytes([encoded[0], encoded[1]]),
+            ENCRYPTED_OBJECT_FORMAT_VERSION
+        );
+        assert_eq!(codec.decode(&encoded).unwrap(), plain);
+
+        // objects encrypted with an old key are decoded after the key is rotated
+        provider.rotate("k2", 0x3c);
+        assert_eq!(codec.decode(&encoded).unwrap(), plain);
+
+        let codec = EncryptedObjectCodec::new(Box::new(BytesCodec), None);
+        let err = codec.decode(&encoded).unwrap_err();
+        assert!(matches!(
+            err.downcast_ref::<SlateDBError>(),
+            Some(SlateDBError::EncryptionKeyUnavailable { key_id }) if key_id == "k1"
+        ));
+    }
+}
@@ -1,11 +1,14 @@
+use bytes::Bytes;
 use object_store::path::Path;
 use std::ops::Bound;
 use std::time::Duration;
 use std::{path::PathBuf, sync::Arc};
 use thiserror::Error as ThisError;
+use ulid::Ulid;
 use uuid::Uuid;
 
 use crate::bytes_range::BytesRange;
+use crate::encryption::EncryptionError;
 use crate::error::SlateDBError::{
     LatestTransactionalObjectVersionMissing, TransactionalObjectVersionExists,
 };
@@ -72,6 +75,12 @@ pub(crate) enum SlateDBError {
     #[error("compaction executor failed")]
     CompactionExecutorFailed,
 
+    #[error("compaction job failed. id=`{id}`, message=`{message}`")]
+    CompactionJobFailed { id: Ulid, message: String },
+
+    #[error("compaction did not finish in time. id=`{id}`, timeout=`{timeout:?}`")]
+    CompactionTimeout { id: Ulid, timeout: Duration },
+
     #[error(
         "invalid clock tick, must be monotonic. last_tick=`{last_tick}`, next_tick=`{next_tick}`"
     )]
@@ -130,6 +139,15 @@ pub(crate) enum SlateDBError {
     #[error("merge operator missing. A merge operator is required to read merge operands")]
     MergeOperatorMissing,
 
+    #[error("encryption key unavailable. The encryption provider can't decrypt data encrypted with key `{key_id}`")]
+    EncryptionKeyUnavailable { key_id: String },
+
+    #[error("encryption error")]
+    EncryptionError(EncryptionError),
+
+    #[error("invalid encryption envelope")]
+    InvalidEncryptionEnvelope,
+
     #[error("checkpoint missing. checkpoint_id=`{0}`")]
     CheckpointMissing(Uuid),
 
@@ -151,6 +169,9 @@ pub(crate) enum SlateDBError {
     #[error("cannot seek to a key less than the last returned key")]
     SeekKeyLessThanLastReturnedKey,
 
+    #[error("cannot seek to a key greater than the last returned key of a descending iterator")]
+    SeekKeyGreaterThanLastReturnedKey,
+
     #[error(
         "parent path must be different from the clone's path. parent_path=`{0}`, clone_path=`{0}`"
     )]
@@ -171,6 +192,18 @@ pub(crate) enum SlateDBError {
     #[error("invalid sst batch size. size=`{0}`")]
     InvalidSSTBatchSize(usize),
 
+    #[error("setting can't be changed while the database is open. setting=`{0}`")]
+    ImmutableSetting(&'static str),
+
+    #[error("invalid setting. setting=`{setting}`, reason=`{reason}`")]
+    InvalidSetting {
+        setting: &'static str,
+        reason: &'static str,
+    },
+
+    #[error("write stalled. reason=`{0}`")]
+    WriteStalled(&'static str),
+
     #[error("cannot seek to a key outside the iterator range. key=`{key:?}`, start_key=`{start_key:?}`, end_key=`{end_key:?}`")]
     SeekKeyOutOfKeyRange {
         key: Vec<u8>,
@@ -205,11 +238,49 @@ pub(crate) enum SlateDBError {
     #[error("transaction conflict")]
     TransactionConflict,
 
+    #[error("write precondition failed. key=`{key:?}`")]
+    PreconditionFailed { key: Bytes },
+
+    #[error("changes are no longer retained. seq=`{seq}`")]
+    ChangesNotRetained { seq: u64 },
+
+    #[error("versions at the read point are no longer retained. seq=`{seq}`, min_retained_seq=`{min_retained_seq}`")]
+    VersionsNotRetained { seq: u64, min_retained_seq: u64 },
+
     #[error("iterator not initialized")]
     IteratorNotInitialized,
 
+    #[error("unknown column family. name=`{0}`")]
+    UnknownColumnFamily(String),
+
+    #[error("invalid column family key. key=`{0:?}`")]
+    InvalidColumnFamilyKey(Bytes),
+
     #[error("invalid sequence number ordering during merge. expected sequence numbers in descending order, but found {current_seq} followed by {next_seq}")]
     InvalidSequenceOrder { current_seq: u64, next_seq: u64 },
+
+    #[error(
+        "keys must be added in strictly increasing order. last_key=`{last_key:?}`, key=`{key:?}`"
+    )]
+    InvalidKeyOrder { last_key: Bytes, key: Bytes },
+
+    #[error("SST to ingest not found. path=`{0}`")]
+    IngestSstMissing(Path),
+
+    #[error("ingested SST overlaps data written after the ingestion started. path=`{0}`")]
+    IngestConflict(Path),
+
+    #[error("comparator mismatch. expected=`{expected:?}`, actual=`{actual:?}`")]
+    ComparatorMismatch {
+        expected: Option<String>,
+        actual: Option<String>,
+    },
+
+    #[error("operation is not supported by databases with a comparator. operation=`{0}`")]
+    UnsupportedWithComparator(&'static str),
+
+    #[error("invalid blob ref")]
+    InvalidBlobRef,
 }
 
 impl From<TransactionalObjectError> for SlateDBError {
@@ -239,6 +310,17 @@ impl From<TransactionalObjectError> for SlateDBError {
     }
 }
 
+impl From<EncryptionError> for SlateDBError {
+    fn from(error: EncryptionError) -> Self {
+        match error {
+            EncryptionError::KeyUnavailable { key_id } => {
+                SlateDBError::EncryptionKeyUnavailable { key_id }
+            }
+            error => SlateDBError::EncryptionError(error),
+        }
+    }
+}
+
 impl From<std::io::Error> for SlateDBError {
     fn from(value: std::io::Error) -> Self {
         Self::IoError(Arc::new(value))
@@ -272,6 +354,10 @@ pub enum CloseReason {
 
     /// One or more background tasks panicked.
     Panic,
+
+    /// A background task failed with an error, such as an object store error, that
+    /// it could not recover from.
+    Failed,
 }
 
 /// Represents the kind of public errors that can be returned to the user.
@@ -285,6 +371,10 @@ pub enum ErrorKind {
     /// A transaction conflict occurred. The transaction must be retried or dropped.
     Transaction,
 
+    /// A write precondition (e.g. put-if-absent or compare-and-set) did not hold. None of
+    /// the writes in the batch were applied. The user may re-read the key and retry.
+    Precondition,
+
     /// The database has been shutdown. The instance is no longer usable. The user must
     /// create a new instance to continue using the database.
     Closed(CloseReason),
@@ -324,6 +414,7 @@ impl std::fmt::Display for ErrorKind {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             ErrorKind::Transaction => write!(f, "Transaction error"),
+            ErrorKind::Precondition => write!(f, "Precondition error"),
             ErrorKind::Closed(_) => write!(f, "Closed error"),
             ErrorKind::Unavailable => write!(f, "Unavailable error"),
             ErrorKind::Invalid => write!(f, "Invalid error"),
@@ -370,6 +461,15 @@ impl Error {
         }
     }
 
+    /// Creates a new precondition error.
+    pub fn precondition(msg: String) -> Self {
+        Self {
+            msg,
+            kind: ErrorKind::Precondition,
+            source: None,
+        }
+    }
+
     /// Creates a new fencing error.
     pub fn closed(msg: String, reason: CloseReason) -> Self {
         Self {
@@ -434,6 +534,10 @@ impl From<SlateDBError> for Error {
             // Transaction errors
             SlateDBError::TransactionConflict => Error::transaction(msg),
 
+            // Precondition errors
+            SlateDBError::PreconditionFailed { .. } => Error::precondition(msg),
+            SlateDBError::IngestConflict(_) => Error::precondition(msg),
+
             // Closed
             SlateDBError::Closed => Error::closed(msg, CloseReason::Clean),
             SlateDBError::Fenced => Error::closed(msg, CloseReason::Fenced),
@@ -447,6 +551,8 @@ impl From<SlateDBError> for Error {
             #[cfg(feature = "foyer")]
             SlateDBError::FoyerError(err) => Error::unavailable(msg).with_source(Box::new(err)),
             SlateDBError::TransactionalObjectTimeout { .. } => Error::unavailable(msg),
+            SlateDBError::CompactionTimeout { .. } => Error::unavailable(msg),
+            SlateDBError::WriteStalled(_) => Error::unavailable(msg),
 
             // Invalid errors
             SlateDBError::InvalidCachePartSize => Error::invalid(msg),
@@ -463,8 +569,11 @@ impl From<SlateDBError> for Error {
             SlateDBError::InvalidCheckpointLifetime(_) => Error::invalid(msg),
             SlateDBError::InvalidManifestPollInterval(_) => Error::invalid(msg),
             SlateDBError::CheckpointLifetimeTooShort { .. } => Error::invalid(msg),
+            SlateDBError::ImmutableSetting(_) => Error::invalid(msg),
+            SlateDBError::InvalidSetting { .. } => Error::invalid(msg),
             SlateDBError::SeekKeyOutOfRange { .. } => Error::invalid(msg),
             SlateDBError::SeekKeyLessThanLastReturnedKey => Error::invalid(msg),
+            SlateDBError::SeekKeyGreaterThanLastReturnedKey => Error::invalid(msg),
             SlateDBError::IdenticalClonePaths { .. } => Error::invalid(msg),
             SlateDBError::WalDisabled => Error::invalid(msg),
             SlateDBError::InvalidCompaction => Error::invalid(msg),
@@ -472,7 +581,15 @@ impl From<SlateDBError> for Error {
             SlateDBError::InvalidDeletion => Error::invalid(msg),
             SlateDBError::MergeOperatorError(err) => Error::invalid(msg).with_source(Box::new(err)),
             SlateDBError::MergeOperatorMissing => Error::invalid(msg),
+            SlateDBError::EncryptionKeyUnavailable { .. } => Error::invalid(msg),
             SlateDBError::IteratorNotInitialized => Error::invalid(msg),
+            SlateDBError::ChangesNotRetained { .. } => Error::invalid(msg),
+            SlateDBError::VersionsNotRetained { .. } => Error::invalid(msg),
+            SlateDBError::UnknownColumnFamily(_) => Error::invalid(msg),
+            SlateDBError::InvalidKeyOrder { .. } => Error::invalid(msg),
+            SlateDBError::IngestSstMissing(_) => Error::invalid(msg),
+            SlateDBError::ComparatorMismatch { .. } => Error::invalid(msg),
+            SlateDBError::UnsupportedWithComparator(_) => Error::invalid(msg),
             SlateDBError::InvalidSequenceOrder { .. } => Error::data(msg),
 
             // Data errors
@@ -488,6 +605,10 @@ impl From<SlateDBError> for Error {
             #[cfg(any(feature = "snappy", feature = "zlib", feature = "zstd"))]
             SlateDBError::BlockCompressionError => Error::data(msg),
             SlateDBError::InvalidRowFlags { .. } => Error::data(msg),
+            SlateDBError::InvalidBlobRef => Error::data(msg),
+            SlateDBError::EncryptionError(err) => Error::data(msg).with_source(Box::new(err)),
+            SlateDBError::InvalidEncryptionEnvelope => Error::data(msg),
+            SlateDBError::InvalidColumnFamilyKey(_) => Error::data(msg),
             SlateDBError::CheckpointMissing(_) => Error::data(msg),
             SlateDBError::InvalidVersion { .. } => Error::data(msg),
             SlateDBError::ManifestMissing(_) => Error::data(msg),
@@ -505,6 +626,7 @@ impl From<SlateDBError> for Error {
 
             // Internal errors
             SlateDBError::CompactionExecutorFailed => Error::internal(msg),
+            SlateDBError::CompactionJobFailed { .. } => Error::internal(msg),
             SlateDBError::SeekKeyOutOfKeyRange { .. } => Error::internal(msg),
             SlateDBError::ReadChannelError(err) => Error::internal(msg).with_source(Box::new(err)),
             SlateDBError::BackgroundTaskExists(_) => Error::internal(msg),
@@ -0,0 +1,298 @@
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use ulid::Ulid;
+
+use crate::error::{CloseReason, SlateDBError};
+
+/// A WAL SST was written to object storage.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WalFlushedEvent {
+    /// The id of the WAL SST.
+    pub wal_id: u64,
+    /// The size in bytes of the WAL SST.
+    pub bytes: u64,
+}
+
+/// An immutable memtable was flushed to L0, and the manifest that references its SSTs was
+/// written.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemtableFlushedEvent {
+    /// The ids of the L0 SSTs written for the memtable, one per column family with data.
+    pub ssts: Vec<Ulid>,
+    /// The total size in bytes of the L0 SSTs.
+    pub bytes: u64,
+    /// The sequence number of the last row of the memtable.
+    pub last_seq: u64,
+}
+
+/// The compactor started a compaction.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompactionStartedEvent {
+    /// The id of the compaction.
+    pub id: Ulid,
+    /// The ids of the L0 SSTs compacted.
+    pub source_ssts: Vec<Ulid>,
+    /// The ids of the sorted runs compacted.
+    pub source_sorted_runs: Vec<u32>,
+    /// The id of the sorted run the compaction writes.
+    pub destination: u32,
+    /// The estimated size in bytes of the sources.
+    pub source_bytes: u64,
+}
+
+/// A compaction finished, and the manifest that references its output was written.
+/// Compactions that fail are retried by later compactions and don't have a finished event.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CompactionFinishedEvent {
+    /// The id of the compaction.
+    pub id: Ulid,
+    /// The ids of the L0 SSTs compacted.
+    pub source_ssts: Vec<Ulid>,
+    /// The ids of the sorted runs compacted.
+    pub source_sorted_runs: Vec<u32>,
+    /// The id of the sorted run the compaction wrote.
+    pub destination: u32,
+    /// The ids of the SSTs of the output sorted run.
+    pub output_ssts: Vec<Ulid>,
+    /// The estimated size in bytes of the output sorted run.
+    pub bytes: u64,
+    /// The time between the start of the compaction by this compactor and its finish.
+    pub duration: Duration,
+}
+
+/// The garbage collector deleted SSTs that are no longer referenced by the database.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SstsDeletedEvent {
+    /// The ids of the deleted WAL SSTs.
+    pub wal_ssts: Vec<u64>,
+    /// The ids of the deleted compacted SSTs.
+    pub compacted_ssts: Vec<Ulid>,
+}
+
+/// A new version of the manifest was written.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ManifestWrittenEvent {
+    /// The id of the manifest.
+    pub id: u64,
+}
+
+/// The database was closed, either by [`crate::Db::close`] or by a background task that
+/// failed.
+#[non_exhaustive]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DbClosedEvent {
+    /// Why the database was closed.
+    pub reason: CloseReason,
+}
+
+/// A trait for reacting to the lifecycle events of a database, such as flushes,
+/// compactions, and garbage collection.
+///
+/// Listeners are registered with the `with_event_listener` method of [`crate::DbBuilder`],
+/// `CompactorBuilder` and `GarbageCollectorBuilder`. Every method has a default
+/// implementation that ignores the event, so listeners only implement the events they are
+/// interested in.
+///
+/// Listeners are called from the background tasks of the database, after the change that
+/// caused the event is durable. They should return quickly and must not block, since the
+/// task that calls them waits for them to return.
+///
+/// # Examples
+/// Here's an example of a listener that logs fencing:
+/// ```
+/// use slatedb::{CloseReason, DbClosedEvent, EventListener};
+///
+/// struct FencingAlert;
+///
+/// impl EventListener for FencingAlert {
+///     fn on_db_closed(&self, event: &DbClosedEvent) {
+///         if event.reason == CloseReason::Fenced {
+///             log::error!("db was fenced by another writer");
+///         }
+///     }
+/// }
+/// ```
+pub trait EventListener: Send + Sync {
+    /// Called when a WAL SST is written.
+    fn on_wal_flushed(&self, _event: &WalFlushedEvent) {}
+
+    /// Called when an immutable memtable is flushed to L0.
+    fn on_memtable_flushed(&self, _event: &MemtableFlushedEvent) {}
+
+    /// Called when the compactor starts a compaction.
+    fn on_compaction_started(&self, _event: &CompactionStartedEvent) {}
+
+    /// Called when a compaction finishes.
+    fn on_compaction_finished(&self, _event: &CompactionFinishedEvent) {}
+
+    /// Called when the garbage collector deletes SSTs.
+    fn on_ssts_deleted(&self, _event: &SstsDeletedEvent) {}
+
+    /// Called when the writer or the compactor writes a new version of the manifest.
+    fn on_manifest_written(&self, _event: &ManifestWrittenEvent) {}
+
+    /// Called once when the database is closed.
+    fn on_db_closed(&self, _event: &DbClosedEvent) {}
+}
+
+/// The event listeners registered with a database component.
+#[derive(Clone, Default)]
+pub(crate) struct EventListeners {
+    listeners: Vec<Arc<dyn EventListener>>,
+    /// Whether the close of the database was notified. Shared by the clones of the
+    /// listeners, since the close is notified both by `Db::close` and by a task that
+    /// watches for background task failures.
+    closed: Arc<AtomicBool>,
+}
+
+impl std::fmt::Debug for EventListeners {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("EventListeners")
+            .field("len", &self.listeners.len())
+            .finish()
+    }
+}
+
+impl EventListeners {
+    pub(crate) fn add(&mut self, listener: Arc<dyn EventListener>) {
+        self.listeners.push(listener);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.listeners.is_empty()
+    }
+
+    /// Calls `f` with every listener, in the order they were added.
+    pub(crate) fn notify(&self, f: impl Fn(&dyn EventListener)) {
+        for listener in &self.listeners {
+            f(listener.as_ref());
+        }
+    }
+
+    /// Notifies the listeners that the database was closed with `result`, unless the close
+    /// was already notified.
+    pub(crate) fn notify_closed(&self, result: &Result<(), SlateDBError>) {
+        if self.closed.swap(true, Ordering::SeqCst) {
+            return;
+        }
+        let event = DbClosedEvent {
+            reason: close_reason(result),
+        };
+        self.notify(|l| l.on_db_closed(&event));
+    }
+}
+
+/// Returns the reason a database was closed from the result it was closed with.
+pub(crate) fn close_reason(result: &Result<(), SlateDBError>) -> CloseReason {
+    match result {
+        Ok(()) | Err(SlateDBError::Closed) => CloseReason::Clean,
+        Err(SlateDBError::Fenced) => CloseReason::Fenced,
+        Err(SlateDBError::BackgroundTaskPanic(_)) => CloseReason::Panic,
+        Err(_) => CloseReason::Failed,
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use parking_lot::Mutex;
+
+    use super::*;
+
+    /// A listener that records the events it receives, for tests.
+    #[derive(Default)]
+    pub(crate) struct RecordingEventListener {
+        pub(crate) wal_flushed: Mutex<Vec<WalFlushedEvent>>,
+        pub(crate) memtable_flushed: Mutex<Vec<MemtableFlushedEvent>>,
+        pub(crate) compaction_started: Mutex<Vec<CompactionStartedEvent>>,
+        pub(crate) compaction_finished: Mutex<Vec<CompactionFinishedEvent>>,
+        pub(crate) ssts_deleted: Mutex<Vec<SstsDeletedEvent>>,
+        pub(crate) manifest_written: Mutex<Vec<ManifestWrittenEvent>>,
+        pub(crate) db_closed: Mutex<Vec<DbClosedEvent>>,
+    }
+
+    impl EventListener for RecordingEventListener {
+        fn on_wal_flushed(&self, event: &WalFlushedEvent) {
+            self.wal_flushed.lock().push(event.clone());
+        }
+
+        fn on_memtable_flushed(&self, event: &MemtableFlushedEvent) {
+            self.memtable_flushed.lock().push(event.clone());
+        }
+
+        fn on_compaction_started(&self, event: &CompactionStartedEvent) {
+            self.compaction_started.lock().push(event.clone());
+        }
+
+        fn on_compaction_finished(&self, event: &CompactionFinishedEvent) {
+            self.compaction_finished.lock().push(event.clone());
+        }
+
+        fn on_ssts_deleted(&self, event: &SstsDeletedEvent) {
+            self.ssts_deleted.lock().push(event.clone());
+        }
+
+        fn on_manifest_written(&self, event: &ManifestWrittenEvent) {
+            self.manifest_written.lock().push(event.clone());
+        }
+
+        fn on_db_closed(&self, event: &DbClosedEvent) {
+            self.db_closed.lock().push(event.clone());
+        }
+    }
+
+    #[test]
+    fn test_should_notify_listeners_in_order() {
+        let first = Arc::new(RecordingEventListener::default());
+        let second = Arc::new(RecordingEventListener::default());
+        let mut listeners = EventListeners::default();
+        assert!(listeners.is_empty());
+        listeners.add(first.clone());
+        listeners.add(second.clone());
+
+        let event = ManifestWrittenEvent { id: 3 };
+        listeners.notify(|l| l.on_manifest_written(&event));
+
+        assert_eq!(*first.manifest_written.lock(), vec![event.clone()]);
+        assert_eq!(*second.manifest_written.lock(), vec![event]);
+    }
+
+    #[test]
+    fn test_should_notify_close_once() {
+        let listener = Arc::new(RecordingEventListener::default());
+        let mut listeners = EventListeners::default();
+        listeners.add(listener.clone());
+
+        listeners
+            .clone()
+            .notify_closed(&Err(SlateDBError::BackgroundTaskPanic("panic".to_string())));
+        listeners.notify_closed(&Ok(()));
+
+        assert_eq!(
+            *listener.db_closed.lock(),
+            vec![DbClosedEvent {
+                reason: CloseReason::Panic
+            }]
+        );
+    }
+
+    #[test]
+    fn test_should_map_close_results_to_reasons() {
+        assert_eq!(close_reason(&Ok(())), CloseReason::Clean);
+        assert_eq!(
+            close_reason(&Err(SlateDBError::Fenced)),
+            CloseReason::Fenced
+        );
+        assert_eq!(
+            close_reason(&Err(SlateDBError::InvalidBlobRef)),
+            CloseReason::Failed
+        );
+    }
+}
@@ -8,6 +8,8 @@ use ulid::Ulid;
 
 use crate::bytes_range::BytesRange;
 use crate::checkpoint;
+use crate::compactor_executor::StartCompactionJobArgs;
+use crate::compactor_state::{Compaction, CompactionSpec, Compactions, SourceId};
 use crate::db_state::{self, SsTableInfo, SsTableInfoCodec};
 use crate::db_state::{CoreDbState, SsTableHandle};
 
@@ -20,22 +22,30 @@ pub use root_generated::{
     SsTableInfo as FbSsTableInfo, SsTableInfoArgs,
 };
 
+use crate::blob_file::BlobFileRef;
 use crate::config::CompressionCodec;
 use crate::db_state::SsTableId;
 use crate::db_state::SsTableId::Compacted;
 use crate::error::SlateDBError;
 use crate::flatbuffer_types::root_generated::{
-    BoundType, Checkpoint, CheckpointArgs, CheckpointMetadata, CompactedSsTable,
-    CompactedSsTableArgs, CompactedSstId, CompactedSstIdArgs, CompressionFormat, SortedRun,
-    SortedRunArgs, Uuid, UuidArgs,
+    BlobFileRef as FbBlobFileRef, BlobFileRefArgs, BoundType, Checkpoint, CheckpointArgs,
+    CheckpointMetadata, CompactedSsTable, CompactedSsTableArgs, CompactedSstId, CompactedSstIdArgs,
+    CompactionJob, CompactionJobArgs, CompactionJobResult as FbCompactionJobResult,
+    CompactionJobResultArgs, CompactionSource, CompactionSourceArgs, CompactionsV1,
+    CompactionsV1Args, CompressionFormat, RangeTombstone as FbRangeTombstone, RangeTombstoneArgs,
+    SortedRun, SortedRunArgs, UlidArgs, Uuid, UuidArgs,
 };
-use crate::manifest::{ExternalDb, Manifest};
+use crate::manifest::{ExternalDb, Manifest, WriterHandoff};
 use crate::partitioned_keyspace::RangePartitionedKeySpace;
+use crate::range_tombstone::RangeTombstone;
+use crate::remote_compaction::CompactionJobResult;
 use crate::seq_tracker::SequenceTracker;
 use crate::transactional_object::ObjectCodec;
 use crate::utils::clamp_allocated_size_bytes;
 
 pub(crate) const MANIFEST_FORMAT_VERSION: u16 = 1;
+pub(crate) const COMPACTIONS_FORMAT_VERSION: u16 = 1;
+pub(crate) const COMPACTION_JOB_FORMAT_VERSION: u16 = 1;
 
 /// A wrapper around a `Bytes` buffer containing a FlatBuffer-encoded `SsTableIndex`.
 #[derive(PartialEq, Eq, Clone)]
@@ -102,6 +112,33 @@ impl FlatBufferSsTableInfoCodec {
         let first_key: Option<Bytes> = info
             .first_key()
             .map(|key| Bytes::copy_from_slice(key.bytes()));
+        let range_tombstones = info
+            .range_tombstones()
+            .map(|tombstones| {
+                tombstones
+                    .iter()
+                    .map(|tombstone| {
+                        RangeTombstone::new(
+                            FlatBufferManifestCodec::decode_bytes_range(tombstone.range()),
+                            tombstone.seq(),
+                            tombstone.create_ts(),
+                        )
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
+        let blob_refs = info
+            .blob_refs()
+            .map(|blob_refs| {
+                blob_refs
+                    .iter()
+                    .map(|blob_ref| BlobFileRef {
+                        id: FlatBufferCompactionJobCodec::decode_ulid(blob_ref.id()),
+                        live_bytes: blob_ref.live_bytes(),
+                    })
+                    .collect()
+            })
+            .unwrap_or_default();
 
         SsTableInfo {
             first_key,
@@ -110,6 +147,11 @@ impl FlatBufferSsTableInfoCodec {
             filter_offset: info.filter_offset(),
             filter_len: info.filter_len(),
             compression_codec: info.compression_format().into(),
+            range_tombstones,
+            column_family_keys: info.column_family_keys(),
+            prefix_extractor: info.prefix_extractor().map(str::to_string),
+            blob_refs,
+            encryption_key_id: info.encryption_key_id().map(str::to_string),
         }
     }
 
@@ -123,8 +165,11 @@ impl FlatBufferSsTableInfoCodec {
 pub(crate) struct FlatBufferManifestCodec {}
 
 impl ObjectCodec<Manifest> for FlatBufferManifestCodec {
-    fn encode(&self, manifest: &Manifest) -> Bytes {
-        Self::create_from_manifest(manifest)
+    fn encode(
+        &self,
+        manifest: &Manifest,
+    ) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
+        Ok(Self::create_from_manifest(manifest))
     }
 
     fn decode(&self, bytes: &Bytes) -> Result<Manifest, Box<dyn std::error::Error + Send + Sync>> {
@@ -174,37 +219,23 @@ impl FlatBufferManifestCodec {
         let l0_last_compacted = manifest
             .l0_last_compacted()
             .map(|id| Ulid::from((id.high(), id.low())));
-        let mut l0 = VecDeque::new();
-
-        for man_sst in manifest.l0().iter() {
-            let man_sst_id = man_sst.id();
-            let sst_id = Compacted(Ulid::from((man_sst_id.high(), man_sst_id.low())));
-
-            let sst_info = FlatBufferSsTableInfoCodec::sst_info(&man_sst.info());
-            let l0_sst = SsTableHandle::new_compacted(
-                sst_id,
-                sst_info,
-                man_sst.visible_range().map(Self::decode_bytes_range),
-            );
-            l0.push_back(l0_sst);
-        }
-        let mut compacted = Vec::new();
-        for manifest_sr in manifest.compacted().iter() {
-            let mut ssts = Vec::new();
-            for manifest_sst in manifest_sr.ssts().iter() {
-                let id = Compacted(manifest_sst.id().ulid());
-                let info = FlatBufferSsTableInfoCodec::sst_info(&manifest_sst.info());
-                ssts.push(SsTableHandle::new_compacted(
-                    id,
-                    info,
-                    manifest_sst.visible_range().map(Self::decode_bytes_range),
-                ));
-            }
-            compacted.push(db_state::SortedRun {
-                id: manifest_sr.id(),
-                ssts,
+        let l0 = Self::decode_l0(manifest.l0());
+        let compacted = Self::decode_sorted_runs(manifest.compacted());
+        let column_families = manifest
+            .column_families()
+            .map(|column_families| {
+                column_families
+                    .iter()
+                    .map(|cf| db_state::ColumnFamilyState {
+                        id: cf.id(),
+                        name: cf.name().to_string(),
+                        l0_last_compacted: cf.l0_last_compacted().map(|id| id.ulid()),
+                        l0: Self::decode_l0(cf.l0()),
+                        compacted: Self::decode_sorted_runs(cf.compacted()),
+                    })
+                    .collect()
             })
-        }
+            .unwrap_or_default();
         let checkpoints: Vec<checkpoint::Checkpoint> = manifest
             .checkpoints()
             .iter()
@@ -244,6 +275,8 @@ impl FlatBufferManifestCodec {
             wal_object_store_uri: manifest.wal_object_store_uri().map(|uri| uri.to_string()),
             recent_snapshot_min_seq: manifest.recent_snapshot_min_seq(),
             sequence_tracker,
+            column_families,
+            comparator: manifest.comparator().map(|name| name.to_string()),
         };
         let external_dbs = manifest.external_dbs().map(|external_dbs| {
             external_dbs
@@ -262,7 +295,59 @@ impl FlatBufferManifestCodec {
             core,
             writer_epoch: manifest.writer_epoch(),
             compactor_epoch: manifest.compactor_epoch(),
+            writer_handoff: manifest.writer_handoff().map(|handoff| WriterHandoff {
+                writer_epoch: handoff.writer_epoch(),
+                last_seq: handoff.last_seq(),
+            }),
+        }
+    }
+
+    fn decode_l0(
+        l0_ssts: Vector<'_, ForwardsUOffset<CompactedSsTable<'_>>>,
+    ) -> VecDeque<SsTableHandle> {
+        let mut l0 = VecDeque::new();
+        for man_sst in l0_ssts.iter() {
+            let man_sst_id = man_sst.id();
+            let sst_id = Compacted(Ulid::from((man_sst_id.high(), man_sst_id.low())));
+
+            let sst_info = FlatBufferSsTableInfoCodec::sst_info(&man_sst.info());
+            let l0_sst = SsTableHandle::new_compacted(
+                sst_id,
+                sst_info,
+                man_sst.visible_range().map(Self::decode_bytes_range),
+            );
+            l0.push_back(l0_sst);
         }
+        l0
+    }
+
+    fn decode_sorted_runs(
+        sorted_runs: Vector<'_, ForwardsUOffset<SortedRun<'_>>>,
+    ) -> Vec<db_state::SortedRun> {
+        let mut compacted = Vec::new();
+        for manifest_sr in sorted_runs.iter() {
+            compacted.push(db_state::SortedRun {
+                id: manifest_sr.id(),
+                ssts: Self::decode_compacted_ssts(manifest_sr.ssts()),
+            })
+        }
+        compacted
+    }
+
+    fn decode_compacted_ssts(
+        compacted_ssts: Vector<'_, ForwardsUOffset<CompactedSsTable<'_>>>,
+    ) -> Vec<SsTableHandle> {
+        let mut ssts = Vec::new();
+        for manifest_sst in compacted_ssts.iter() {
+            let id = Compacted(manifest_sst.id().ulid());
+            let info = FlatBufferSsTableInfoCodec::sst_info(&manifest_sst.info());
+            ssts.push(SsTableHandle::new_compacted(
+                id,
+                info,
+                manifest_sst.visible_range().map(Self::decode_bytes_range),
+            ));
+        }
+        ssts
     }
 
     pub fn create_from_manifest(manifest: &Manifest) -> Bytes {
@@ -272,6 +357,150 @@ impl FlatBufferManifestCodec {
     }
 }
 
+pub(crate) struct FlatBufferCompactionsCodec {}
+
+impl ObjectCodec<Compactions> for FlatBufferCompactionsCodec {
+    fn encode(
+        &self,
+        compactions: &Compactions,
+    ) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
+        let builder = FlatBufferBuilder::new();
+        let mut db_fb_builder = DbFlatBufferBuilder::new(builder);
+        Ok(db_fb_builder.create_compactions(compactions))
+    }
+
+    fn decode(
+        &self,
+        bytes: &Bytes,
+    ) -> Result<Compactions, Box<dyn std::error::Error + Send + Sync>> {
+        if bytes.len() < 2 {
+            return Err(Box::new(SlateDBError::InvalidTransactionalObjectState));
+        }
+        let version = u16::from_be_bytes([bytes[0], bytes[1]]);
+        if version != COMPACTIONS_FORMAT_VERSION {
+            return Err(Box::new(SlateDBError::InvalidVersion {
+                expected_version: COMPACTIONS_FORMAT_VERSION,
+                actual_version: version,
+            }));
+        }
+        let unversioned_bytes = bytes.slice(2..);
+        let compactions = flatbuffers::root::<CompactionsV1>(unversioned_bytes.as_ref())?;
+        Ok(Self::compactions(&compactions))
+    }
+}
+
+impl FlatBufferCompactionsCodec {
+    fn compactions(compactions: &CompactionsV1) -> Compactions {
+        let compactions_list = compactions
+            .compactions()
+            .iter()
+            .map(|compaction| Self::compaction(&compaction))
+            .collect();
+        let submitted = compactions
+            .submitted()
+            .map(|submitted| {
+                submitted
+                    .iter()
+                    .map(|compaction| Self::compaction(&compaction))
+                    .collect()
+            })
+            .unwrap_or_default();
+        Compactions {
+            compactor_epoch: compactions.compactor_epoch(),
+            compactions: compactions_list,
+            submitted,
+        }
+    }
+
+    fn compaction(compaction: &root_generated::Compaction) -> Compaction {
+        let id = compaction.id();
+        let sources = compaction
+            .sources()
+            .iter()
+            .map(|source| match source.sst_id() {
+                Some(sst_id) => SourceId::Sst(sst_id.ulid()),
+                None => SourceId::SortedRun(source.sorted_run_id()),
+            })
+            .collect();
+        Compaction::new(
+            Ulid::from((id.high(), id.low())),
+            CompactionSpec::new(sources, compaction.destination()),
+        )
+        .with_column_family(compaction.column_family())
+        .with_output_ssts(FlatBufferManifestCodec::decode_compacted_ssts(
+            compaction.output_ssts(),
+        ))
+    }
+}
+
+/// Encodes the compaction jobs and job results exchanged with remote compaction workers.
+pub(crate) struct FlatBufferCompactionJobCodec {}
+
+impl FlatBufferCompactionJobCodec {
+    pub(crate) fn encode_job(job: &StartCompactionJobArgs) -> Bytes {
+        let builder = FlatBufferBuilder::new();
+        let mut db_fb_builder = DbFlatBufferBuilder::new(builder);
+        db_fb_builder.create_compaction_job(job)
+    }
+
+    pub(crate) fn decode_job(bytes: &Bytes) -> Result<StartCompactionJobArgs, SlateDBError> {
+        let unversioned_bytes = Self::unversioned(bytes)?;
+        let job = flatbuffers::root::<CompactionJob>(unversioned_bytes.as_ref())?;
+        Ok(StartCompactionJobArgs {
+            id: Self::decode_ulid(job.id()),
+            compaction_id: Self::decode_ulid(job.compaction_id()),
+            destination: job.destination(),
+            column_family: job.column_family(),
+            ssts: FlatBufferManifestCodec::decode_compacted_ssts(job.ssts()),
+            sorted_runs: FlatBufferManifestCodec::decode_sorted_runs(job.sorted_runs()),
+            compaction_logical_clock_tick: job.compaction_logical_clock_tick(),
+            is_dest_last_run: job.is_dest_last_run(),
+            retention_min_seq: job.retention_min_seq(),
+            output_ssts: FlatBufferManifestCodec::decode_compacted_ssts(job.output_ssts()),
+        })
+    }
+
+    pub(crate) fn encode_result(result: &CompactionJobResult) -> Bytes {
+        let builder = FlatBufferBuilder::new();
+        let mut db_fb_builder = DbFlatBufferBuilder::new(builder);
+        db_fb_builder.create_compaction_job_result(result)
+    }
+
+    pub(crate) fn decode_result(bytes: &Bytes) -> Result<CompactionJobResult, SlateDBError> {
+        let unversioned_bytes = Self::unversioned(bytes)?;
+        let result = flatbuffers::root::<FbCompactionJobResult>(unversioned_bytes.as_ref())?;
+        let output = match (result.output_ssts(), result.error()) {
+            (_, Some(error)) => Err(error.to_string()),
+            (Some(output_ssts), None) => {
+                Ok(FlatBufferManifestCodec::decode_compacted_ssts(output_ssts))
+            }
+            (None, None) => return Err(SlateDBError::InvalidDBState),
+        };
+        Ok(CompactionJobResult {
+            id: Self::decode_ulid(result.id()),
+            output,
+        })
+    }
+
+    fn unversioned(bytes: &Bytes) -> Result<Bytes, SlateDBError> {
+        if bytes.len() < 2 {
+            return Err(SlateDBError::InvalidDBState);
+        }
+        let version = u16::from_be_bytes([bytes[0], bytes[1]]);
+        if version != COMPACTION_JOB_FORMAT_VERSION {
+            return Err(SlateDBError::InvalidVersion {
+                expected_version: COMPACTION_JOB_FORMAT_VERSION,
+                actual_version: version,
+            });
+        }
+        Ok(bytes.slice(2..))
+    }
+
+    fn decode_ulid(ulid: root_generated::Ulid) -> Ulid {
+        Ulid::from((ulid.high(), ulid.low()))
+    }
+}
+
 impl CompactedSstId<'_> {
     pub(crate) fn ulid(&self) -> Ulid {
         Ulid::from((self.high(), self.low()))
@@ -292,6 +521,43 @@ impl<'b> DbFlatBufferBuilder<'b> {
             None => None,
             Some(first_key_vector) => Some(self.builder.create_vector(first_key_vector)),
         };
+        let range_tombstones = if info.range_tombstones.is_empty() {
+            None
+        } else {
+            let tombstones: Vec<WIPOffset<FbRangeTombstone>> = info
+                .range_tombstones
+                .iter()
+                .map(|tombstone| self.add_range_tombstone(tombstone))
+                .collect();
+            Some(self.builder.create_vector(tombstones.as_ref()))
+        };
+        let prefix_extractor = info
+            .prefix_extractor
+            .as_ref()
+            .map(|name| self.builder.create_string(name));
+        let blob_refs = if info.blob_refs.is_empty() {
+            None
+        } else {
+            let blob_refs: Vec<WIPOffset<FbBlobFileRef>> = info
+                .blob_refs
+                .iter()
+                .map(|blob_ref| {
+                    let id = self.add_ulid(&blob_ref.id);
+                    FbBlobFileRef::create(
+                        &mut self.builder,
+                        &BlobFileRefArgs {
+                            id: Some(id),
+                            live_bytes: blob_ref.live_bytes,
+                        },
+                    )
+                })
+                .collect();
+            Some(self.builder.create_vector(blob_refs.as_ref()))
+        };
+        let encryption_key_id = info
+            .encryption_key_id
+            .as_ref()
+            .map(|key_id| self.builder.create_string(key_id));
 
         FbSsTableInfo::create(
             &mut self.builder,
@@ -302,6 +568,26 @@ impl<'b> DbFlatBufferBuilder<'b> {
                 filter_offset: info.filter_offset,
                 filter_len: info.filter_len,
                 compression_format: info.compression_codec.into(),
+                range_tombstones,
+                column_family_keys: info.column_family_keys,
+                prefix_extractor,
+                blob_refs,
+                encryption_key_id,
+            },
+        )
+    }
+
+    fn add_range_tombstone(
+        &mut self,
+        tombstone: &RangeTombstone,
+    ) -> WIPOffset<FbRangeTombstone<'b>> {
+        let range = self.add_bytes_range(&tombstone.range);
+        FbRangeTombstone::create(
+            &mut self.builder,
+            &RangeTombstoneArgs {
+                range: Some(range),
+                seq: tombstone.seq,
+                create_ts: tombstone.create_ts,
             },
         )
     }
@@ -384,6 +670,71 @@ impl<'b> DbFlatBufferBuilder<'b> {
         self.builder.create_vector(sorted_runs_fbs.as_ref())
     }
 
+    fn add_column_family(
+        &mut self,
+        column_family: &db_state::ColumnFamilyState,
+    ) -> WIPOffset<root_generated::ColumnFamily<'b>> {
+        let name = self.builder.create_string(&column_family.name);
+        let l0_last_compacted = column_family
+            .l0_last_compacted
+            .as_ref()
+            .map(|ulid| self.add_compacted_sst_id(ulid));
+        let l0 = self.add_compacted_ssts(column_family.l0.iter());
+        let compacted = self.add_sorted_runs(&column_family.compacted);
+        root_generated::ColumnFamily::create(
+            &mut self.builder,
+            &root_generated::ColumnFamilyArgs {
+                id: column_family.id,
+                name: Some(name),
+                l0_last_compacted,
+                l0: Some(l0),
+                compacted: Some(compacted),
+            },
+        )
+    }
+
+    fn add_ulid(&mut self, ulid: &Ulid) -> WIPOffset<root_generated::Ulid<'b>> {
+        let (high, low) = (*ulid).into();
+        root_generated::Ulid::create(&mut self.builder, &UlidArgs { high, low })
+    }
+
+    fn add_compaction(
+        &mut self,
+        compaction: &Compaction,
+    ) -> WIPOffset<root_generated::Compaction<'b>> {
+        let id = self.add_ulid(&compaction.id());
+        let sources: Vec<WIPOffset<CompactionSource>> = compaction
+            .spec()
+            .sources()
+            .iter()
+            .map(|source| {
+                let args = match source {
+                    SourceId::Sst(sst_id) => CompactionSourceArgs {
+                        sst_id: Some(self.add_compacted_sst_id(sst_id)),
+                        sorted_run_id: 0,
+                    },
+                    SourceId::SortedRun(sorted_run_id) => CompactionSourceArgs {
+                        sst_id: None,
+                        sorted_run_id: *sorted_run_id,
+                    },
+                };
+                CompactionSource::create(&mut self.builder, &args)
+            })
+            .collect();
+        let sources = self.builder.create_vector(sources.as_ref());
+        let output_ssts = self.add_compacted_ssts(compaction.output_ssts().iter());
+        root_generated::Compaction::create(
+            &mut self.builder,
+            &root_generated::CompactionArgs {
+                id: Some(id),
+                column_family: compaction.column_family(),
+                sources: Some(sources),
+                destination: compaction.spec().destination(),
+                output_ssts: Some(output_ssts),
+            },
+        )
+    }
+
     fn add_uuid(&mut self, uuid: uuid::Uuid) -> WIPOffset<Uuid<'b>> {
         let (high, low) = uuid.as_u64_pair();
         Uuid::create(&mut self.builder, &UuidArgs { high, low })
@@ -456,6 +807,16 @@ impl<'b> DbFlatBufferBuilder<'b> {
             l0_last_compacted = Some(self.add_compacted_sst_id(ulid))
         }
         let compacted = self.add_sorted_runs(&core.compacted);
+        let column_families = if core.column_families.is_empty() {
+            None
+        } else {
+            let column_families: Vec<WIPOffset<root_generated::ColumnFamily>> = core
+                .column_families
+                .iter()
+                .map(|cf| self.add_column_family(cf))
+                .collect();
+            Some(self.builder.create_vector(column_families.as_ref()))
+        };
         let checkpoints = self.add_checkpoints(&core.checkpoints);
         let external_dbs = if manifest.external_dbs.is_empty() {
             None
@@ -482,8 +843,21 @@ impl<'b> DbFlatBufferBuilder<'b> {
             .wal_object_store_uri
             .as_ref()
             .map(|uri| self.builder.create_string(uri));
+        let comparator = core
+            .comparator
+            .as_ref()
+            .map(|name| self.builder.create_string(name));
         let sequence_tracker_data = core.sequence_tracker.to_bytes();
         let sequence_tracker = self.builder.create_vector(sequence_tracker_data.as_slice());
+        let writer_handoff = manifest.writer_handoff.map(|handoff| {
+            root_generated::WriterHandoff::create(
+                &mut self.builder,
+                &root_generated::WriterHandoffArgs {
+                    writer_epoch: handoff.writer_epoch,
+                    last_seq: handoff.last_seq,
+                },
+            )
+        });
 
         let manifest = ManifestV1::create(
             &mut self.builder,
@@ -504,6 +878,9 @@ impl<'b> DbFlatBufferBuilder<'b> {
                 wal_object_store_uri,
                 recent_snapshot_min_seq: core.recent_snapshot_min_seq,
                 sequence_tracker: Some(sequence_tracker),
+                column_families,
+                comparator,
+                writer_handoff,
             },
         );
         self.builder.finish(manifest, None);
@@ -513,6 +890,83 @@ impl<'b> DbFlatBufferBuilder<'b> {
         bytes.into()
     }
 
+    fn create_compactions(&mut self, compactions: &Compactions) -> Bytes {
+        let compactions_fb_vec: Vec<WIPOffset<root_generated::Compaction>> = compactions
+            .compactions
+            .iter()
+            .map(|compaction| self.add_compaction(compaction))
+            .collect();
+        let compactions_fb = self.builder.create_vector(compactions_fb_vec.as_ref());
+        let submitted_fb_vec: Vec<WIPOffset<root_generated::Compaction>> = compactions
+            .submitted
+            .iter()
+            .map(|compaction| self.add_compaction(compaction))
+            .collect();
+        let submitted_fb = self.builder.create_vector(submitted_fb_vec.as_ref());
+        let compactions = CompactionsV1::create(
+            &mut self.builder,
+            &CompactionsV1Args {
+                compactor_epoch: compactions.compactor_epoch,
+                compactions: Some(compactions_fb),
+                submitted: Some(submitted_fb),
+            },
+        );
+        self.builder.finish(compactions, None);
+        let mut bytes = BytesMut::new();
+        bytes.put_u16(COMPACTIONS_FORMAT_VERSION);
+        bytes.put_slice(self.builder.finished_data());
+        bytes.into()
+    }
+
+    fn create_compaction_job(&mut self, job: &StartCompactionJobArgs) -> Bytes {
+        let id = self.add_ulid(&job.id);
+        let compaction_id = self.add_ulid(&job.compaction_id);
+        let ssts = self.add_compacted_ssts(job.ssts.iter());
+        let sorted_runs = self.add_sorted_runs(&job.sorted_runs);
+        let output_ssts = self.add_compacted_ssts(job.output_ssts.iter());
+        let job = CompactionJob::create(
+            &mut self.builder,
+            &CompactionJobArgs {
+                id: Some(id),
+                compaction_id: Some(compaction_id),
+                column_family: job.column_family,
+                destination: job.destination,
+                ssts: Some(ssts),
+                sorted_runs: Some(sorted_runs),
+                compaction_logical_clock_tick: job.compaction_logical_clock_tick,
+                is_dest_last_run: job.is_dest_last_run,
+                retention_min_seq: job.retention_min_seq,
+                output_ssts: Some(output_ssts),
+            },
+        );
+        self.builder.finish(job, None);
+        let mut bytes = BytesMut::new();
+        bytes.put_u16(COMPACTION_JOB_FORMAT_VERSION);
+        bytes.put_slice(self.builder.finished_data());
+        bytes.into()
+    }
+
+    fn create_compaction_job_result(&mut self, result: &CompactionJobResult) -> Bytes {
+        let id = self.add_ulid(&result.id);
+        let (output_ssts, error) = match &result.output {
+            Ok(output_ssts) => (Some(self.add_compacted_ssts(output_ssts.iter())), None),
+            Err(error) => (None, Some(self.builder.create_string(error))),
+        };
+        let result = FbCompactionJobResult::create(
+            &mut self.builder,
+            &CompactionJobResultArgs {
+                id: Some(id),
+                output_ssts,
+                error,
+            },
+        );
+        self.builder.finish(result, None);
+        let mut bytes = BytesMut::new();
+        bytes.put_u16(COMPACTION_JOB_FORMAT_VERSION);
+        bytes.put_slice(self.builder.finished_data());
+        bytes.into()
+    }
+
     fn create_sst_info(&mut self, info: &SsTableInfo) -> Bytes {
         let copy = self.add_sst_info(info);
         self.builder.finish(copy, None);
@@ -566,10 +1020,19 @@ pub(crate) mod test_utils {
 
 #[cfg(test)]
 mod tests {
+    use crate::blob_file::BlobFileRef;
     use crate::bytes_range::BytesRange;
+    use crate::compactor_executor::StartCompactionJobArgs;
+    use crate::compactor_state::{Compaction, CompactionSpec, Compactions, SourceId};
+    use crate::db_state::SsTableInfoCodec;
     use crate::db_state::{CoreDbState, SortedRun, SsTableHandle, SsTableId, SsTableInfo};
-    use crate::flatbuffer_types::{FlatBufferManifestCodec, SsTableIndexOwned};
-    use crate::manifest::{ExternalDb, Manifest};
+    use crate::flatbuffer_types::{
+        FlatBufferCompactionJobCodec, FlatBufferCompactionsCodec, FlatBufferManifestCodec,
+        FlatBufferSsTableInfoCodec, SsTableIndexOwned,
+    };
+    use crate::manifest::{ExternalDb, Manifest, WriterHandoff};
+    use crate::range_tombstone::RangeTombstone;
+    use crate::remote_compaction::CompactionJobResult;
     use crate::transactional_object::ObjectCodec;
     use crate::{checkpoint, error::SlateDBError};
     use std::collections::VecDeque;
@@ -608,7 +1071,7 @@ mod tests {
         let codec = FlatBufferManifestCodec {};
 
         // when:
-        let bytes = codec.encode(&manifest);
+        let bytes = codec.encode(&manifest).unwrap();
         let decoded = codec.decode(&bytes).expect("failed to decode manifest");
 
         // then:
@@ -639,7 +1102,7 @@ mod tests {
         let codec = FlatBufferManifestCodec {};
 
         // when:
-        let bytes = codec.encode(&manifest);
+        let bytes = codec.encode(&manifest).unwrap();
         let decoded = codec.decode(&bytes).expect("failed to decode manifest");
 
         // then:
@@ -686,13 +1149,207 @@ mod tests {
         let codec = FlatBufferManifestCodec {};
 
         // when:
-        let bytes = codec.encode(&manifest);
+        let bytes = codec.encode(&manifest).unwrap();
         let decoded = codec.decode(&bytes).expect("failed to decode manifest");
 
         // then:
         assert_eq!(manifest, decoded);
     }
 
+    #[test]
+    fn test_should_encode_decode_compactions() {
+        // given:
+        let output_sst = SsTableHandle::new_compacted(
+            SsTableId::Compacted(ulid::Ulid::new()),
+            SsTableInfo {
+                first_key: Some(Bytes::from_static(b"a")),
+                ..Default::default()
+            },
+            None,
+        );
+        let compactions = Compactions {
+            compactor_epoch: 3,
+            compactions: vec![
+                Compaction::new(
+                    ulid::Ulid::new(),
+                    CompactionSpec::new(
+                        vec![
+                            SourceId::Sst(ulid::Ulid::new()),
+                            SourceId::SortedRun(2),
+                            SourceId::SortedRun(1),
+                        ],
+                        1,
+                    ),
+                )
+                .with_output_ssts(vec![output_sst]),
+                Compaction::new(
+                    ulid::Ulid::new(),
+                    CompactionSpec::new(vec![SourceId::SortedRun(0)], 0),
+                )
+                .with_column_family(1),
+            ],
+            submitted: vec![Compaction::new(
+                ulid::Ulid::new(),
+                CompactionSpec::new(vec![SourceId::SortedRun(3), SourceId::SortedRun(2)], 2),
+            )],
+        };
+        let codec = FlatBufferCompactionsCodec {};
+
+        // when:
+        let bytes = codec.encode(&compactions).unwrap();
+        let decoded = codec.decode(&bytes).expect("failed to decode compactions");
+
+        // then:
+        assert_eq!(compactions, decoded);
+    }
+
+    #[test]
+    fn test_should_encode_decode_compaction_jobs() {
+        // given:
+        let new_sst = |first_key: &'static [u8]| {
+            SsTableHandle::new_compacted(
+                SsTableId::Compacted(ulid::Ulid::new()),
+                SsTableInfo {
+                    first_key: Some(Bytes::from_static(first_key)),
+                    ..Default::default()
+                },
+                None,
+            )
+        };
+        let job = StartCompactionJobArgs {
+            id: ulid::Ulid::new(),
+            compaction_id: ulid::Ulid::new(),
+            destination: 2,
+            column_family: 1,
+            ssts: vec![new_sst(b"a"), new_sst(b"b")],
+            sorted_runs: vec![SortedRun {
+                id: 2,
+                ssts: vec![new_sst(b"c")],
+            }],
+            compaction_logical_clock_tick: 42,
+            is_dest_last_run: true,
+            retention_min_seq: Some(7),
+            output_ssts: vec![new_sst(b"d")],
+        };
+        let job_without_retention = StartCompactionJobArgs {
+            retention_min_seq: None,
+            ..job.clone()
+        };
+        let succeeded = CompactionJobResult {
+            id: job.id,
+            output: Ok(vec![new_sst(b"e"), new_sst(b"f")]),
+        };
+        let failed = CompactionJobResult {
+            id: job.id,
+            output: Err("object store unavailable".to_string()),
+        };
+
+        // when/then:
+        for job in [job, job_without_retention] {
+            let bytes = FlatBufferCompactionJobCodec::encode_job(&job);
+            assert_eq!(
+                FlatBufferCompactionJobCodec::decode_job(&bytes).unwrap(),
+                job
+            );
+        }
+        for result in [succeeded, failed] {
+            let bytes = FlatBufferCompactionJobCodec::encode_result(&result);
+            assert_eq!(
+                FlatBufferCompactionJobCodec::decode_result(&bytes).unwrap(),
+                result
+            );
+        }
+    }
+
+    #[test]
+    fn test_should_encode_decode_column_families() {
+        // given:
+        let mut manifest = Manifest::initial(CoreDbState::new());
+        let names = ["users".to_string(), "orders".to_string()];
+        manifest.core.add_column_families(names.iter());
+        let users = manifest.core.column_family_mut(1).unwrap();
+        users.l0_last_compacted = Some(ulid::Ulid::new());
+        users.l0 = VecDeque::from(vec![SsTableHandle::new_compacted(
+            SsTableId::Compacted(ulid::Ulid::new()),
+            SsTableInfo {
+                first_key: Some(Bytes::from_static(b"a")),
+                ..Default::default()
+            },
+            None,
+        )]);
+        users.compacted = vec![SortedRun {
+            id: 3,
+            ssts: vec![SsTableHandle::new_compacted(
+                SsTableId::Compacted(ulid::Ulid::new()),
+                SsTableInfo {
+                    first_key: Some(Bytes::from_static(b"b")),
+                    column_family_keys: true,
+                    prefix_extractor: Some("fixed_length:4".to_string()),
+                    ..Default::default()
+                },
+                None,
+            )],
+        }];
+        let codec = FlatBufferManifestCodec {};
+
+        // when:
+        let bytes = codec.encode(&manifest).unwrap();
+        let decoded = codec.decode(&bytes).expect("failed to decode manifest");
+
+        // then:
+        assert_eq!(manifest, decoded);
+    }
+
+    #[test]
+    fn test_should_encode_decode_sst_info_with_range_tombstones() {
+        // given:
+        let info = SsTableInfo {
+            first_key: Some(Bytes::from_static(b"a")),
+            range_tombstones: vec![
+                RangeTombstone::new(BytesRange::from_ref("b".."d"), 7, 100),
+                RangeTombstone::new(BytesRange::from_ref(.."z"), 9, 200),
+            ],
+            ..Default::default()
+        };
+
+        // when:
+        let bytes = FlatBufferSsTableInfoCodec::create_from_sst_info(&info);
+        let decoded = FlatBufferSsTableInfoCodec {}
+            .decode(&bytes)
+            .expect("failed to decode sst info");
+
+        // then:
+        assert_eq!(info, decoded);
+    }
+
+    #[test]
+    fn test_should_encode_decode_sst_info_with_blob_refs() {
+        // given:
+        let info = SsTableInfo {
+            first_key: Some(Bytes::from_static(b"a")),
+            blob_refs: vec![
+                BlobFileRef {
+                    id: ulid::Ulid::from_parts(1, 2),
+                    live_bytes: 4096,
+                },
+                BlobFileRef {
+                    id: ulid::Ulid::from_parts(3, 4),
+                    live_bytes: 100,
+                },
+            ],
+            ..Default::default()
+        };
+
+        // when:
+        let bytes = FlatBufferSsTableInfoCodec::create_from_sst_info(&info);
+        let decoded = FlatBufferSsTableInfoCodec {}
+            .decode(&bytes)
+            .expect("failed to decode sst info");
+
+        // then:
+        assert_eq!(info, decoded);
+    }
+
     #[test]
     fn test_should_clamp_index_alloc() {
         let format = SsTableFormat::default();
@@ -739,6 +1396,9 @@ mod tests {
                 wal_object_store_uri: None,
                 recent_snapshot_min_seq: 0,
                 sequence_tracker: None,
+                column_families: None,
+                comparator: None,
+                writer_handoff: None,
             },
         );
         fbb.finish(manifest, None);
@@ -783,7 +1443,35 @@ mod tests {
         manifest.core.wal_object_store_uri = Some("s3://bucket/path".to_string());
 
         let codec = FlatBufferManifestCodec {};
-        let bytes = codec.encode(&manifest);
+        let bytes = codec.encode(&manifest).unwrap();
+        let decoded = codec.decode(&bytes).unwrap();
+
+        assert_eq!(manifest, decoded);
+    }
+
+    #[test]
+    fn test_should_encode_decode_comparator() {
+        let mut manifest = Manifest::initial(CoreDbState::new());
+        manifest.core.comparator = Some("reverse".to_string());
+
+        let codec = FlatBufferManifestCodec {};
+        let bytes = codec.encode(&manifest).unwrap();
+        let decoded = codec.decode(&bytes).unwrap();
+
+        assert_eq!(manifest, decoded);
+    }
+
+    #[test]
+    fn test_should_encode_decode_writer_handoff() {
+        let mut manifest = Manifest::initial(CoreDbState::new());
+        manifest.writer_epoch = 3;
+        manifest.writer_handoff = Some(WriterHandoff {
+            writer_epoch: 3,
+            last_seq: 42,
+        });
+
+        let codec = FlatBufferManifestCodec {};
+        let bytes = codec.encode(&manifest).unwrap();
         let decoded = codec.decode(&bytes).unwrap();
 
         assert_eq!(manifest, decoded);
@@ -796,7 +1484,7 @@ mod tests {
         manifest.core.recent_snapshot_min_seq = 12345;
 
         let codec = FlatBufferManifestCodec {};
-        let bytes = codec.encode(&manifest);
+        let bytes = codec.encode(&manifest).unwrap();
         let decoded = codec.decode(&bytes).unwrap();
 
         assert_eq!(decoded.core.recent_snapshot_min_seq, 12345);
@@ -805,7 +1493,7 @@ mod tests {
         let mut manifest_none = Manifest::initial(CoreDbState::new());
         manifest_none.core.recent_snapshot_min_seq = 0;
 
-        let bytes_none = codec.encode(&manifest_none);
+        let bytes_none = codec.encode(&manifest_none).unwrap();
         let decoded_none = codec.decode(&bytes_none).unwrap();
 
         assert_eq!(decoded_none.core.recent_snapshot_min_seq, 0);
@@ -6,6 +6,7 @@ use crate::db_state::SsTableHandle;
 use crate::error::SlateDBError;
 use crate::iter::KeyValueIterator;
 use crate::mem_table::KVTable;
+use crate::sst::SsTableFormat;
 
 impl DbInner {
     pub(crate) async fn flush_imm_table(
@@ -14,13 +15,45 @@ impl DbInner {
         imm_table: Arc<KVTable>,
         write_cache: bool,
     ) -> Result<SsTableHandle, SlateDBError> {
-        let mut sst_builder = self.table_store.table_builder();
+        self.flush_imm_table_with_format(id, imm_table, self.table_store.sst_format(), write_cache)
+            .await
+    }
+
+    /// Flushes a table to an SST encoded with `sst_format`, e.g. the format of a
+    /// column family.
+    pub(crate) async fn flush_imm_table_with_format(
+        &self,
+        id: &db_state::SsTableId,
+        imm_table: Arc<KVTable>,
+        sst_format: &SsTableFormat,
+        write_cache: bool,
+    ) -> Result<SsTableHandle, SlateDBError> {
+        let mut sst_builder = match id {
+            db_state::SsTableId::Compacted(ulid) => {
+                sst_format.table_builder().with_blob_file(*ulid)
+            }
+            db_state::SsTableId::Wal(_) => sst_format.table_builder(),
+        };
         let mut iter = imm_table.iter();
         while let Some(entry) = iter.next_entry().await? {
             sst_builder.add(entry)?;
         }
+        for tombstone in imm_table.range_tombstones() {
+            sst_builder.add_range_tombstone(tombstone);
+        }
 
         let encoded_sst = sst_builder.build()?;
+        // only memtable flushes to L0 are rate limited; WAL SSTs are written as writes
+        // arrive, and writes are limited by the write rate limiter instead
+        if let db_state::SsTableId::Compacted(_) = id {
+            let size_bytes = encoded_sst.info.index_offset + encoded_sst.info.index_len;
+            let waited = self.flush_rate_limiter.acquire(size_bytes).await;
+            if !waited.is_zero() {
+                self.db_stats
+                    .flush_rate_limit_wait_us
+                    .record_duration(waited);
+            }
+        }
         let handle = self
             .table_store
             .write_sst(id, encoded_sst, write_cache)
@@ -4,7 +4,9 @@
 //! - Write-ahead log (WAL) SSTs that are no longer referenced by active manifests or
 //!   checkpoints
 //! - Compacted SSTs that are no longer referenced by active manifests or checkpoints
-//! - Old manifests that are not needed for recovery or checkpoints
+//! - Blob files that no compacted SST referenced by active manifests or checkpoints points to
+//! - Old manifests that are not needed for recovery or checkpoints, and old versions of the
+//!   compactor's persisted compactions
 //!
 //! The garbage collector runs periodically in the background, with configurable intervals
 //! and minimum age thresholds for each type of data. This ensures that recently created
@@ -13,9 +15,11 @@
 
 use crate::checkpoint::Checkpoint;
 use crate::clock::SystemClock;
+use crate::compactions_store::CompactionsStore;
 use crate::config::GarbageCollectorOptions;
 use crate::dispatcher::{MessageFactory, MessageHandler};
 use crate::error::SlateDBError;
+use crate::event_listener::EventListeners;
 use crate::garbage_collector::stats::GcStats;
 use crate::manifest::store::{ManifestStore, StoredManifest};
 use crate::manifest::Manifest;
@@ -54,6 +58,8 @@ pub(crate) enum GcMessage {
     GcCompacted,
     GcManifest,
     LogStats,
+    /// Sent by [`crate::Db::update_settings`] to replace the garbage collector options.
+    UpdateOptions(GarbageCollectorOptions),
 }
 
 /// SlateDB's garbage collector.
@@ -80,6 +86,8 @@ pub struct GarbageCollector {
     manifest_gc_task: ManifestGcTask,
     wal_gc_task: WalGcTask,
     compacted_gc_task: CompactedGcTask,
+    /// Whether the intervals changed since the tickers were created.
+    tickers_changed: bool,
 }
 
 #[async_trait]
@@ -108,12 +116,17 @@ impl MessageHandler<GcMessage> for GarbageCollector {
         ]
     }
 
+    fn tickers_changed(&mut self) -> bool {
+        std::mem::take(&mut self.tickers_changed)
+    }
+
     async fn handle(&mut self, message: GcMessage) -> Result<(), SlateDBError> {
         match message {
             GcMessage::GcManifest => self.run_gc_task(&self.manifest_gc_task).await,
             GcMessage::GcWal => self.run_gc_task(&self.wal_gc_task).await,
             GcMessage::GcCompacted => self.run_gc_task(&self.compacted_gc_task).await,
             GcMessage::LogStats => self.log_stats(),
+            GcMessage::UpdateOptions(options) => self.update_options(options),
         }
         Ok(())
     }
@@ -135,10 +148,12 @@ impl GarbageCollector {
     /// # Arguments
     ///
     /// * `manifest_store` - The manifest store to use for garbage collection.
+    /// * `compactions_store` - The store of the compactor's in-progress compactions.
     /// * `table_store` - The table store to use for garbage collection.
     /// * `options` - Configuration options for the garbage collector.
     /// * `stat_registry` - Registry for tracking garbage collection metrics.
     /// * `system_clock` - Clock implementation for time-based decisions.
+    /// * `event_listeners` - Listeners notified of the SSTs the garbage collector deletes.
     /// * `cancellation_token` - Token used to signal cancellation of garbage collection tasks.
     ///
     /// # Returns
@@ -146,10 +161,12 @@ impl GarbageCollector {
     /// A new `GarbageCollector` instance configured with the provided components.
     pub(crate) fn new(
         manifest_store: Arc<ManifestStore>,
+        compactions_store: Arc<CompactionsStore>,
         table_store: Arc<TableStore>,
         options: GarbageCollectorOptions,
         stat_registry: Arc<StatRegistry>,
         system_clock: Arc<dyn SystemClock>,
+        event_listeners: EventListeners,
     ) -> Self {
         let stats = Arc::new(GcStats::new(stat_registry.clone()));
         let wal_gc_task = WalGcTask::new(
@@ -157,16 +174,20 @@ impl GarbageCollector {
             table_store.clone(),
             stats.clone(),
             options.wal_options,
+            event_listeners.clone(),
         );
         let compacted_gc_task = CompactedGcTask::new(
             manifest_store.clone(),
+            compactions_store.clone(),
             table_store.clone(),
             stats.clone(),
             options.compacted_options,
             stat_registry.clone(),
+            event_listeners,
         );
         let manifest_gc_task = ManifestGcTask::new(
             manifest_store.clone(),
+            compactions_store,
             stats.clone(),
             options.manifest_options,
         );
@@ -178,9 +199,33 @@ impl GarbageCollector {
             manifest_gc_task,
             wal_gc_task,
             compacted_gc_task,
+            tickers_changed: false,
         }
     }
 
+    /// Replaces the options of the garbage collector and its tasks. The tickers are
+    /// recreated if an interval changed.
+    fn update_options(&mut self, options: GarbageCollectorOptions) {
+        info!("updating garbage collector options [options={:?}]", options);
+        let intervals = |options: &GarbageCollectorOptions| {
+            [
+                options.manifest_options,
+                options.wal_options,
+                options.compacted_options,
+            ]
+            .map(|o| o.and_then(|o| o.interval))
+        };
+        if intervals(&options) != intervals(&self.options) {
+            self.tickers_changed = true;
+        }
+        self.manifest_gc_task
+            .set_manifest_options(options.manifest_options);
+        self.wal_gc_task.set_wal_options(options.wal_options);
+        self.compacted_gc_task
+            .set_compacted_options(options.compacted_options);
+        self.options = options;
+    }
+
     /// Run the garbage collector once.
     ///
     /// This method runs all three garbage collection tasks:
@@ -257,7 +302,7 @@ mod tests {
     use std::{fs::OpenOptions, sync::Arc};
 
     use chrono::{DateTime, Days, TimeDelta, Utc};
-    use object_store::{local::LocalFileSystem, path::Path};
+    use object_store::{local::LocalFileSystem, memory::InMemory, path::Path};
     use tokio::runtime::Handle;
     use tokio::sync::mpsc;
     use uuid::Uuid;
@@ -943,6 +988,14 @@ mod tests {
         (manifest_store, table_store, local_object_store)
     }
 
+    /// A compactions store without any persisted compactions.
+    fn empty_compactions_store() -> Arc<CompactionsStore> {
+        Arc::new(CompactionsStore::new(
+            &Path::from("/"),
+            Arc::new(InMemory::new()),
+        ))
+    }
+
     /// Create an SSTable with a fixed ULID timestamp and write it to the table store.
     /// # Arguments
     /// * `table_store` - The table store to write the SSTable to
@@ -1036,7 +1089,11 @@ mod tests {
                     .try_into()
                     .expect("out of bounds timestamp"),
             );
-            stats.register(COMPACTION_LOW_WATERMARK_TS, barrier);
+            stats.register(
+                COMPACTION_LOW_WATERMARK_TS,
+                "Oldest timestamp still read by running compactions.",
+                barrier,
+            );
         }
 
         let gc_opts = GarbageCollectorOptions {
@@ -1056,10 +1113,12 @@ mod tests {
 
         let gc = GarbageCollector::new(
             manifest_store.clone(),
+            empty_compactions_store(),
             table_store.clone(),
             gc_opts,
             stats.clone(),
             Arc::new(DefaultSystemClock::default()),
+            EventListeners::default(),
         );
 
         gc.run_gc_once().await;
@@ -1114,10 +1173,12 @@ mod tests {
 
         let mut gc = GarbageCollector::new(
             manifest_store.clone(),
+            empty_compactions_store(),
             table_store.clone(),
             gc_opts,
             stats.clone(),
             Arc::new(DefaultSystemClock::default()),
+            EventListeners::default(),
         );
 
         // Send a WAL GC message. Correct behavior: only WAL GC runs.
@@ -1134,6 +1195,62 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_update_options_recreates_tickers_on_interval_change() {
+        let (manifest_store, table_store, _) = build_objects();
+        let directory_options = GarbageCollectorDirectoryOptions {
+            min_age: Duration::from_secs(3600),
+            interval: Some(Duration::from_secs(10)),
+        };
+        let gc_opts = GarbageCollectorOptions {
+            manifest_options: Some(directory_options),
+            wal_options: Some(directory_options),
+            compacted_options: Some(directory_options),
+        };
+        let mut gc = GarbageCollector::new(
+            manifest_store,
+            empty_compactions_store(),
+            table_store,
+            gc_opts.clone(),
+            Arc::new(StatRegistry::new()),
+            Arc::new(DefaultSystemClock::default()),
+            EventListeners::default(),
+        );
+
+        // changing a min age keeps the tickers
+        let mut new_opts = gc_opts.clone();
+        new_opts.wal_options = Some(GarbageCollectorDirectoryOptions {
+            min_age: Duration::from_secs(60),
+            ..directory_options
+        });
+        gc.handle(GcMessage::UpdateOptions(new_opts)).await.unwrap();
+        assert!(!gc.tickers_changed());
+        assert_eq!(
+            gc.options.wal_options.unwrap().min_age,
+            Duration::from_secs(60)
+        );
+
+        // changing an interval recreates the tickers once
+        let mut new_opts = gc_opts;
+        new_opts.compacted_options = Some(GarbageCollectorDirectoryOptions {
+            interval: Some(Duration::from_secs(1)),
+            ..directory_options
+        });
+        gc.handle(GcMessage::UpdateOptions(new_opts)).await.unwrap();
+        assert!(gc.tickers_changed());
+        assert!(!gc.tickers_changed());
+        let intervals: Vec<Duration> = gc.tickers().into_iter().map(|(d, _)| d).collect();
+        assert_eq!(
+            intervals,
+            vec![
+                Duration::from_secs(10),
+                Duration::from_secs(10),
+                Duration::from_secs(1),
+                Duration::from_secs(60),
+            ]
+        );
+    }
+
     #[tokio::test(start_paused = true)]
     async fn test_gc_shutdown() {
         let (manifest_store, table_store, _) = build_objects();
@@ -1156,10 +1273,12 @@ mod tests {
 
         let gc = GarbageCollector::new(
             manifest_store.clone(),
+            empty_compactions_store(),
             table_store.clone(),
             gc_opts,
             stats.clone(),
             Arc::new(DefaultSystemClock::default()),
+            EventListeners::default(),
         );
         let (_, rx) = mpsc::unbounded_channel();
         let clock = Arc::new(DefaultSystemClock::default());
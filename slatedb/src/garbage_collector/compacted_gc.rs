@@ -1,3 +1,6 @@
+use crate::compactions_store::CompactionsStore;
+use crate::compactor_state::Compactions;
+use crate::event_listener::{EventListeners, SstsDeletedEvent};
 use crate::manifest::Manifest;
 use crate::{
     config::GarbageCollectorDirectoryOptions, db_state::SsTableId, error::SlateDBError,
@@ -6,18 +9,21 @@ use crate::{
 use chrono::{DateTime, Utc};
 use log::error;
 use std::collections::BTreeMap;
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
+use ulid::Ulid;
 
 use super::{GcStats, GcTask, DEFAULT_MIN_AGE};
 use crate::compactor::stats::COMPACTION_LOW_WATERMARK_TS;
 
 pub(crate) struct CompactedGcTask {
     manifest_store: Arc<ManifestStore>,
+    compactions_store: Arc<CompactionsStore>,
     table_store: Arc<TableStore>,
     stats: Arc<GcStats>,
     compacted_options: Option<GarbageCollectorDirectoryOptions>,
     stat_registry: Arc<StatRegistry>,
+    event_listeners: EventListeners,
 }
 
 impl std::fmt::Debug for CompactedGcTask {
@@ -31,20 +37,31 @@ impl std::fmt::Debug for CompactedGcTask {
 impl CompactedGcTask {
     pub fn new(
         manifest_store: Arc<ManifestStore>,
+        compactions_store: Arc<CompactionsStore>,
         table_store: Arc<TableStore>,
         stats: Arc<GcStats>,
         compacted_options: Option<GarbageCollectorDirectoryOptions>,
         stat_registry: Arc<StatRegistry>,
+        event_listeners: EventListeners,
     ) -> Self {
         CompactedGcTask {
             manifest_store,
+            compactions_store,
             table_store,
             stats,
             compacted_options,
             stat_registry,
+            event_listeners,
         }
     }
 
+    pub fn set_compacted_options(
+        &mut self,
+        compacted_options: Option<GarbageCollectorDirectoryOptions>,
+    ) {
+        self.compacted_options = compacted_options;
+    }
+
     fn compacted_sst_min_age(&self) -> chrono::Duration {
         let min_age = self
             .compacted_options
@@ -55,21 +72,57 @@ impl CompactedGcTask {
     async fn list_active_l0_and_compacted_ssts(
         &self,
         active_manifests: &BTreeMap<u64, Manifest>,
+        compactions: Option<&Compactions>,
     ) -> Result<HashSet<SsTableId>, SlateDBError> {
         let mut active_ssts = HashSet::new();
+        // Output SSTs of in-progress compactions are needed to resume them
+        for compaction in compactions.iter().flat_map(|c| c.compactions.iter()) {
+            for sst in compaction.output_ssts() {
+                active_ssts.insert(sst.id);
+            }
+        }
         for manifest in active_manifests.values() {
-            for sr in manifest.core.compacted.iter() {
+            for sr in manifest.core.all_compacted() {
                 for sst in sr.ssts.iter() {
                     active_ssts.insert(sst.id);
                 }
             }
-            for sst in manifest.core.l0.iter() {
+            for sst in manifest.core.all_l0() {
                 active_ssts.insert(sst.id);
             }
         }
         Ok(active_ssts)
     }
 
+    /// Returns the number of bytes of each blob file that the active SSTs point to.
+    fn list_active_blob_files(
+        active_manifests: &BTreeMap<u64, Manifest>,
+        compactions: Option<&Compactions>,
+    ) -> HashMap<Ulid, u64> {
+        let mut active_blobs = HashMap::new();
+        let output_ssts = compactions
+            .iter()
+            .flat_map(|c| c.compactions.iter())
+            .flat_map(|c| c.output_ssts().iter());
+        let manifest_ssts = active_manifests.values().flat_map(|m| {
+            m.core
+                .all_compacted()
+                .flat_map(|sr| sr.ssts.iter())
+                .chain(m.core.all_l0())
+        });
+        let mut seen_ssts = HashSet::new();
+        for sst in output_ssts.chain(manifest_ssts) {
+            // the same SST is usually in several manifests, count its refs once
+            if !seen_ssts.insert(sst.id) {
+                continue;
+            }
+            for blob_ref in &sst.info.blob_refs {
+                *active_blobs.entry(blob_ref.id).or_default() += blob_ref.live_bytes;
+            }
+        }
+        active_blobs
+    }
+
     async fn newest_l0_dt(
         &self,
         active_manifests: &BTreeMap<u64, Manifest>,
@@ -78,15 +131,20 @@ impl CompactedGcTask {
             .values()
             .last()
             .expect("expected at least one manifest");
-        let l0_timestamps = if !manifest.core.l0.is_empty() {
-            // Use active L0's if some exist
+        let l0_last_compacted = manifest
+            .core
+            .column_families
+            .iter()
+            .map(|cf| cf.l0_last_compacted)
+            .fold(manifest.core.l0_last_compacted, |a, b| a.max(b));
+        let l0_timestamps = if manifest.core.all_l0().next().is_some() {
+            // Use active L0's (of any column family) if some exist
             manifest
                 .core
-                .l0
-                .iter()
+                .all_l0()
                 .map(|sst| DateTime::<Utc>::from(sst.id.unwrap_compacted_id().datetime()))
                 .collect::<Vec<_>>()
-        } else if let Some(l0_last_compacted) = manifest.core.l0_last_compacted {
+        } else if let Some(l0_last_compacted) = l0_last_compacted {
             // Else fall back to the last compacted L0, which can serve as a conservative barrier
             vec![DateTime::<Utc>::from(l0_last_compacted.datetime())]
         } else {
@@ -123,11 +181,25 @@ impl CompactedGcTask {
             .unwrap_or(0);
         DateTime::<Utc>::from_timestamp_millis(low_watermark_ts).expect("out of bounds timestamp")
     }
+
+    /// Returns the start time of the oldest compaction persisted by the compactor, if any.
+    /// GC should not delete any compacted SST whose ULID timestamp is greater than or equal
+    /// to it, since it might be an output SST that the compaction has not persisted yet.
+    fn persisted_compactions_low_watermark_dt(
+        compactions: Option<&Compactions>,
+    ) -> Option<DateTime<Utc>> {
+        compactions?
+            .compactions
+            .iter()
+            .map(|c| DateTime::<Utc>::from(c.id().datetime()))
+            .min()
+    }
 }
 
 impl GcTask for CompactedGcTask {
     /// Collect garbage from the compacted SSTs. This will delete any compacted SSTs that are
-    /// older than the minimum age specified in the options and are not active in the manifest.
+    /// older than the minimum age specified in the options and are not active in the manifest,
+    /// and any blob files of the same age that no active SST points to.
     async fn collect(&self, utc_now: DateTime<Utc>) -> Result<(), SlateDBError> {
         // Don't delete any SSTs that are more recent than the oldest actively running compaction job
         // since they might be an output SST from a compaction that hasn't yet been added to the
@@ -139,10 +211,19 @@ impl GcTask for CompactedGcTask {
         // manifest) and the compaction low watermark _after_ the SSTs are added to the manifest.
         // This would allow the GC to delete the latest compaction job output SST since they would
         // not be active, and would be older than the low watermark.
-        let compaction_low_watermark_dt = self.compaction_low_watermark_dt();
+        // The same applies to the compactions persisted by the compactor.
+        let compactions = self
+            .compactions_store
+            .try_read_latest_compactions()
+            .await?
+            .map(|(_, compactions)| compactions);
+        let compaction_low_watermark_dt = self.compaction_low_watermark_dt().min(
+            Self::persisted_compactions_low_watermark_dt(compactions.as_ref())
+                .unwrap_or(DateTime::<Utc>::MAX_UTC),
+        );
         let active_manifests = self.manifest_store.read_active_manifests().await?;
         let active_ssts = self
-            .list_active_l0_and_compacted_ssts(&active_manifests)
+            .list_active_l0_and_compacted_ssts(&active_manifests, compactions.as_ref())
             .await?;
         // Don't delete any SSTs that are newer than the configured minimum age.
         let configured_min_age_dt = utc_now - self.compacted_sst_min_age();
@@ -175,12 +256,49 @@ impl GcTask for CompactedGcTask {
             .filter(|id| !active_ssts.contains(id))
             .collect::<Vec<_>>();
 
+        let mut deleted_ssts = Vec::new();
         for id in sst_ids_to_delete {
             log::info!("deleting SST [id={:?}]", id);
             if let Err(e) = self.table_store.delete_sst(&id).await {
                 error!("error deleting SST [id={:?}, error={}]", id, e);
             } else {
                 self.stats.gc_compacted_count.inc();
+                deleted_ssts.push(id.unwrap_compacted_id());
+            }
+        }
+
+        if !deleted_ssts.is_empty() {
+            let event = SstsDeletedEvent {
+                wal_ssts: Vec::new(),
+                compacted_ssts: deleted_ssts,
+            };
+            self.event_listeners.notify(|l| l.on_ssts_deleted(&event));
+        }
+
+        // Blob files have the ids of the SSTs that wrote them, so the same cutoff applies.
+        // Compactions copy the live values out of sparse blob files, so a blob file is only
+        // deleted once no SST points to it.
+        let active_blobs = Self::list_active_blob_files(&active_manifests, compactions.as_ref());
+        let mut blobs_to_delete = Vec::new();
+        for blob in self.table_store.list_blob_files().await? {
+            if let Some(live_bytes) = active_blobs.get(&blob.id) {
+                log::debug!(
+                    "active blob file [id={}, size={}, live_bytes={}]",
+                    blob.id,
+                    blob.size,
+                    live_bytes,
+                );
+            } else if DateTime::<Utc>::from(blob.id.datetime()) < cutoff_dt {
+                blobs_to_delete.push(blob);
+            }
+        }
+
+        for blob in blobs_to_delete {
+            log::info!("deleting blob file [id={}, size={}]", blob.id, blob.size);
+            if let Err(e) = self.table_store.delete_blob_file(&blob.id).await {
+                error!("error deleting blob file [id={}, error={}]", blob.id, e);
+            } else {
+                self.stats.gc_blob_count.inc();
             }
         }
 
@@ -197,14 +315,19 @@ mod tests {
     use std::time::Duration;
 
     use super::*;
+    use crate::blob_file::BlobFileBuilder;
     use crate::clock::DefaultSystemClock;
+    use crate::compactions_store::{FenceableCompactions, StoredCompactions};
+    use crate::compactor_state::{Compaction, CompactionSpec, SourceId};
     use crate::compactor_stats::RUNNING_COMPACTIONS;
     use crate::db_state::{CoreDbState, SsTableId};
+    use crate::event_listener::tests::RecordingEventListener;
     use crate::manifest::store::StoredManifest;
     use crate::object_stores::ObjectStores;
     use crate::sst::SsTableFormat;
-    use crate::stats::Gauge;
+    use crate::stats::{Gauge, ReadableStat};
     use crate::test_utils::build_test_sst;
+    use crate::types::RowEntry;
     use object_store::{memory::InMemory, path::Path};
 
     #[tokio::test]
@@ -263,10 +386,18 @@ mod tests {
         let stat_registry = Arc::new(StatRegistry::new());
         let running = Arc::new(Gauge::<i64>::default());
         running.set(1);
-        stat_registry.register(RUNNING_COMPACTIONS, running);
+        stat_registry.register(
+            RUNNING_COMPACTIONS,
+            "Number of running compactions.",
+            running,
+        );
         let barrier = Arc::new(Gauge::<u64>::default());
         barrier.set(10_000);
-        stat_registry.register(COMPACTION_LOW_WATERMARK_TS, barrier);
+        stat_registry.register(
+            COMPACTION_LOW_WATERMARK_TS,
+            "Oldest timestamp still read by running compactions.",
+            barrier,
+        );
 
         // GC task with min_age = 5 seconds. Using utc_now at 10 seconds after the epoch
         // yields a configured_min_age_dt of 5 seconds.
@@ -275,12 +406,20 @@ mod tests {
             min_age: Duration::from_secs(5),
         });
         let stats = Arc::new(GcStats::new(stat_registry.clone()));
+        let listener = Arc::new(RecordingEventListener::default());
+        let mut event_listeners = EventListeners::default();
+        event_listeners.add(listener.clone());
         let task = CompactedGcTask::new(
             manifest_store.clone(),
+            Arc::new(CompactionsStore::new(
+                &Path::from("/root"),
+                main_store.clone(),
+            )),
             table_store.clone(),
             stats,
             opts,
             stat_registry.clone(),
+            event_listeners,
         );
 
         let utc_now = DateTime::<Utc>::from_timestamp_millis(10_000).unwrap();
@@ -296,6 +435,107 @@ mod tests {
             .collect();
 
         assert_eq!(remaining, vec![id_within_min_age, id_active_recent]);
+        assert_eq!(
+            *listener.ssts_deleted.lock(),
+            vec![SstsDeletedEvent {
+                wal_ssts: Vec::new(),
+                compacted_ssts: vec![id_to_delete.unwrap_compacted_id()],
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_compacted_gc_deletes_unreferenced_blob_files() {
+        let main_store = Arc::new(InMemory::new());
+        let object_stores = ObjectStores::new(main_store.clone(), None);
+        let format = SsTableFormat {
+            min_blob_value_size: Some(1),
+            ..SsTableFormat::default()
+        };
+        let table_store = Arc::new(TableStore::new(
+            object_stores,
+            format.clone(),
+            Path::from("/root"),
+            None,
+        ));
+        let manifest_store = Arc::new(ManifestStore::new(
+            &Path::from("/root"),
+            main_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        ));
+        let mut stored_manifest =
+            StoredManifest::create_new_db(manifest_store.clone(), CoreDbState::new())
+                .await
+                .unwrap();
+
+        // The active SST points to a blob file written before it, like the output SST of
+        // a compaction that passed the blob refs of its input SSTs through.
+        let referenced_blob_id = ulid::Ulid::from_parts(1_000, 0);
+        let blob_id_to_delete = ulid::Ulid::from_parts(2_000, 0);
+        let blob_id_within_min_age = ulid::Ulid::from_parts(7_000, 0);
+        let mut builder = format.table_builder().with_blob_file(referenced_blob_id);
+        builder
+            .add(RowEntry::new_value(b"key", &[1u8; 32], 0))
+            .unwrap();
+        let active_handle = table_store
+            .write_sst(
+                &SsTableId::Compacted(ulid::Ulid::from_parts(8_000, 0)),
+                builder.build().unwrap(),
+                false,
+            )
+            .await
+            .unwrap();
+        for id in [blob_id_to_delete, blob_id_within_min_age] {
+            let mut blob_builder = BlobFileBuilder::new(id, 1, None);
+            blob_builder.add(&[2u8; 32]).unwrap();
+            table_store
+                .write_blob_file(&blob_builder.build().unwrap())
+                .await
+                .unwrap();
+        }
+
+        let mut dirty = stored_manifest.prepare_dirty().unwrap();
+        dirty.value.core.l0.push_back(active_handle);
+        stored_manifest.update(dirty).await.unwrap();
+
+        let stat_registry = Arc::new(StatRegistry::new());
+        let barrier = Arc::new(Gauge::<u64>::default());
+        barrier.set(10_000);
+        stat_registry.register(
+            COMPACTION_LOW_WATERMARK_TS,
+            "Oldest timestamp still read by running compactions.",
+            barrier,
+        );
+        let stats = Arc::new(GcStats::new(stat_registry.clone()));
+        let task = CompactedGcTask::new(
+            manifest_store.clone(),
+            Arc::new(CompactionsStore::new(
+                &Path::from("/root"),
+                main_store.clone(),
+            )),
+            table_store.clone(),
+            stats.clone(),
+            Some(GarbageCollectorDirectoryOptions {
+                interval: None,
+                min_age: Duration::from_secs(5),
+            }),
+            stat_registry.clone(),
+            EventListeners::default(),
+        );
+
+        task.collect(DateTime::<Utc>::from_timestamp_millis(10_000).unwrap())
+            .await
+            .unwrap();
+        let remaining: Vec<_> = table_store
+            .list_blob_files()
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|b| b.id)
+            .collect();
+
+        assert_eq!(remaining, vec![referenced_blob_id, blob_id_within_min_age]);
+        assert_eq!(stats.gc_blob_count.get(), 1);
     }
 
     #[tokio::test]
@@ -354,10 +594,18 @@ mod tests {
         let stat_registry = Arc::new(StatRegistry::new());
         let running = Arc::new(Gauge::<i64>::default());
         running.set(1);
-        stat_registry.register(RUNNING_COMPACTIONS, running);
+        stat_registry.register(
+            RUNNING_COMPACTIONS,
+            "Number of running compactions.",
+            running,
+        );
         let barrier = Arc::new(Gauge::<u64>::default());
         barrier.set(10_000);
-        stat_registry.register(COMPACTION_LOW_WATERMARK_TS, barrier);
+        stat_registry.register(
+            COMPACTION_LOW_WATERMARK_TS,
+            "Oldest timestamp still read by running compactions.",
+            barrier,
+        );
 
         // min_age = 0, so configured_min_age_dt == utc_now (10 seconds after epoch).
         // The manifest's most recent SST (3 seconds) is the smallest cutoff, so only
@@ -369,10 +617,15 @@ mod tests {
         let stats = Arc::new(GcStats::new(stat_registry.clone()));
         let task = CompactedGcTask::new(
             manifest_store.clone(),
+            Arc::new(CompactionsStore::new(
+                &Path::from("/root"),
+                main_store.clone(),
+            )),
             table_store.clone(),
             stats,
             opts,
             stat_registry.clone(),
+            EventListeners::default(),
         );
 
         let utc_now = DateTime::<Utc>::from_timestamp_millis(10_000).unwrap();
@@ -446,10 +699,18 @@ mod tests {
         let stat_registry = Arc::new(StatRegistry::new());
         let running = Arc::new(Gauge::<i64>::default());
         running.set(1);
-        stat_registry.register(RUNNING_COMPACTIONS, running);
+        stat_registry.register(
+            RUNNING_COMPACTIONS,
+            "Number of running compactions.",
+            running,
+        );
         let barrier = Arc::new(Gauge::<u64>::default());
         barrier.set(2_000);
-        stat_registry.register(COMPACTION_LOW_WATERMARK_TS, barrier);
+        stat_registry.register(
+            COMPACTION_LOW_WATERMARK_TS,
+            "Oldest timestamp still read by running compactions.",
+            barrier,
+        );
 
         // GC task with min_age = 0
         let opts = Some(GarbageCollectorDirectoryOptions {
@@ -459,10 +720,15 @@ mod tests {
         let stats = Arc::new(GcStats::new(stat_registry.clone()));
         let task = CompactedGcTask::new(
             manifest_store.clone(),
+            Arc::new(CompactionsStore::new(
+                &Path::from("/root"),
+                main_store.clone(),
+            )),
             table_store.clone(),
             stats,
             opts,
             stat_registry.clone(),
+            EventListeners::default(),
         );
 
         // Run GC at a fixed time and verify only the SST strictly
@@ -480,4 +746,107 @@ mod tests {
         // Only the barrier and newer SSTs should remain
         assert_eq!(remaining, vec![id_barrier, id_to_newer]);
     }
+
+    #[tokio::test]
+    async fn test_compacted_gc_keeps_persisted_compaction_outputs() {
+        let main_store = Arc::new(InMemory::new());
+        let object_stores = ObjectStores::new(main_store.clone(), None);
+        let format = SsTableFormat::default();
+        let table_store = Arc::new(TableStore::new(
+            object_stores,
+            format.clone(),
+            Path::from("/root"),
+            None,
+        ));
+        let manifest_store = Arc::new(ManifestStore::new(
+            &Path::from("/root"),
+            main_store.clone(),
+            Arc::new(DefaultSystemClock::new()),
+        ));
+        let compactions_store = Arc::new(CompactionsStore::new(
+            &Path::from("/root"),
+            main_store.clone(),
+        ));
+        let mut stored_manifest =
+            StoredManifest::create_new_db(manifest_store.clone(), CoreDbState::new())
+                .await
+                .unwrap();
+
+        let id_to_delete = SsTableId::Compacted(ulid::Ulid::from_parts(1_000, 0));
+        let id_output = SsTableId::Compacted(ulid::Ulid::from_parts(3_000, 0));
+        let id_active = SsTableId::Compacted(ulid::Ulid::from_parts(8_000, 0));
+        table_store
+            .write_sst(&id_to_delete, build_test_sst(&format, 1), false)
+            .await
+            .unwrap();
+        let output_handle = table_store
+            .write_sst(&id_output, build_test_sst(&format, 1), false)
+            .await
+            .unwrap();
+        let active_handle = table_store
+            .write_sst(&id_active, build_test_sst(&format, 1), false)
+            .await
+            .unwrap();
+        let mut dirty = stored_manifest.prepare_dirty().unwrap();
+        dirty.value.core.l0.push_back(active_handle);
+        stored_manifest.update(dirty).await.unwrap();
+
+        // A compaction that started at 2_000ms and has written one output SST
+        let mut compactions = FenceableCompactions::init(
+            StoredCompactions::load_or_create(compactions_store.clone())
+                .await
+                .unwrap(),
+            Duration::from_secs(300),
+            Arc::new(DefaultSystemClock::new()),
+        )
+        .await
+        .unwrap();
+        let mut dirty = compactions.prepare_dirty().unwrap();
+        dirty.value.compactions.push(
+            Compaction::new(
+                ulid::Ulid::from_parts(2_000, 0),
+                CompactionSpec::new(vec![SourceId::SortedRun(0)], 0),
+            )
+            .with_output_ssts(vec![output_handle]),
+        );
+        compactions.update(dirty).await.unwrap();
+
+        // Register a barrier metric that doesn't get in the way
+        let stat_registry = Arc::new(StatRegistry::new());
+        let barrier = Arc::new(Gauge::<u64>::default());
+        barrier.set(10_000);
+        stat_registry.register(
+            COMPACTION_LOW_WATERMARK_TS,
+            "Oldest timestamp still read by running compactions.",
+            barrier,
+        );
+
+        let opts = Some(GarbageCollectorDirectoryOptions {
+            interval: None,
+            min_age: Duration::from_secs(0),
+        });
+        let stats = Arc::new(GcStats::new(stat_registry.clone()));
+        let task = CompactedGcTask::new(
+            manifest_store.clone(),
+            compactions_store,
+            table_store.clone(),
+            stats,
+            opts,
+            stat_registry.clone(),
+            EventListeners::default(),
+        );
+
+        let utc_now = DateTime::<Utc>::from_timestamp_millis(10_000).unwrap();
+        task.collect(utc_now).await.unwrap();
+        let remaining: Vec<_> = table_store
+            .list_compacted_ssts(..)
+            .await
+            .unwrap()
+            .into_iter()
+            .map(|m| m.id)
+            .collect();
+
+        // The output SST of the persisted compaction is kept
+        assert_eq!(remaining, vec![id_output, id_active]);
+    }
 }
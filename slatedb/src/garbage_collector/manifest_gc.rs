@@ -1,3 +1,4 @@
+use crate::compactions_store::CompactionsStore;
 use crate::{
     config::GarbageCollectorDirectoryOptions, error::SlateDBError, manifest::store::ManifestStore,
 };
@@ -10,6 +11,7 @@ use super::{GcStats, GcTask, DEFAULT_MIN_AGE};
 
 pub(crate) struct ManifestGcTask {
     manifest_store: Arc<ManifestStore>,
+    compactions_store: Arc<CompactionsStore>,
     stats: Arc<GcStats>,
     manifest_options: Option<GarbageCollectorDirectoryOptions>,
 }
@@ -25,22 +27,49 @@ impl std::fmt::Debug for ManifestGcTask {
 impl ManifestGcTask {
     pub fn new(
         manifest_store: Arc<ManifestStore>,
+        compactions_store: Arc<CompactionsStore>,
         stats: Arc<GcStats>,
         manifest_options: Option<GarbageCollectorDirectoryOptions>,
     ) -> Self {
         ManifestGcTask {
             manifest_store,
+            compactions_store,
             stats,
             manifest_options,
         }
     }
 
+    pub fn set_manifest_options(
+        &mut self,
+        manifest_options: Option<GarbageCollectorDirectoryOptions>,
+    ) {
+        self.manifest_options = manifest_options;
+    }
+
     fn manifest_min_age(&self) -> chrono::Duration {
         let min_age = self
             .manifest_options
             .map_or(DEFAULT_MIN_AGE, |opts| opts.min_age);
         chrono::Duration::from_std(min_age).expect("invalid duration")
     }
+
+    /// Delete the compactions files, other than the latest one, that are older than the
+    /// minimum age. Only the latest compactions file is ever read.
+    async fn collect_compactions(&self, utc_now: DateTime<Utc>) -> Result<(), SlateDBError> {
+        let min_age = self.manifest_min_age();
+        let mut compactions_metadata_list = self.compactions_store.list_compactions(..).await?;
+        // Remove the last element so we never delete the latest compactions file
+        compactions_metadata_list.pop();
+        for compactions_metadata in compactions_metadata_list {
+            if utc_now.signed_duration_since(compactions_metadata.last_modified) > min_age {
+                let id = compactions_metadata.id.into();
+                if let Err(e) = self.compactions_store.delete_compactions(id).await {
+                    error!("error deleting compactions [id={:?}, error={}]", id, e);
+                }
+            }
+        }
+        Ok(())
+    }
 }
 
 impl GcTask for ManifestGcTask {
@@ -88,7 +117,7 @@ impl GcTask for ManifestGcTask {
             }
         }
 
-        Ok(())
+        self.collect_compactions(utc_now).await
     }
 
     fn resource(&self) -> &str {
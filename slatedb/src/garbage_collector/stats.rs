@@ -10,6 +10,7 @@ macro_rules! gc_stat_name {
 pub const GC_MANIFEST_COUNT: &str = gc_stat_name!("manifest_count");
 pub const GC_WAL_COUNT: &str = gc_stat_name!("wal_count");
 pub const GC_COMPACTED_COUNT: &str = gc_stat_name!("compacted_count");
+pub const GC_BLOB_COUNT: &str = gc_stat_name!("blob_count");
 pub const GC_COUNT: &str = gc_stat_name!("count");
 
 /// Stats for the garbage collector.
@@ -19,6 +20,7 @@ pub struct GcStats {
     pub gc_manifest_count: Arc<Counter>,
     pub gc_wal_count: Arc<Counter>,
     pub gc_compacted_count: Arc<Counter>,
+    pub gc_blob_count: Arc<Counter>,
     pub gc_count: Arc<Counter>,
 }
 
@@ -39,12 +41,34 @@ impl GcStats {
             gc_manifest_count: Arc::new(Counter::default()),
             gc_wal_count: Arc::new(Counter::default()),
             gc_compacted_count: Arc::new(Counter::default()),
+            gc_blob_count: Arc::new(Counter::default()),
             gc_count: Arc::new(Counter::default()),
         };
-        registry.register(GC_MANIFEST_COUNT, stats.gc_manifest_count.clone());
-        registry.register(GC_WAL_COUNT, stats.gc_wal_count.clone());
-        registry.register(GC_COMPACTED_COUNT, stats.gc_compacted_count.clone());
-        registry.register(GC_COUNT, stats.gc_count.clone());
+        registry.register(
+            GC_MANIFEST_COUNT,
+            "Number of manifests deleted by the garbage collector.",
+            stats.gc_manifest_count.clone(),
+        );
+        registry.register(
+            GC_WAL_COUNT,
+            "Number of WAL SSTs deleted by the garbage collector.",
+            stats.gc_wal_count.clone(),
+        );
+        registry.register(
+            GC_COMPACTED_COUNT,
+            "Number of compacted SSTs deleted by the garbage collector.",
+            stats.gc_compacted_count.clone(),
+        );
+        registry.register(
+            GC_BLOB_COUNT,
+            "Number of blob files deleted by the garbage collector.",
+            stats.gc_blob_count.clone(),
+        );
+        registry.register(
+            GC_COUNT,
+            "Number of garbage collector runs.",
+            stats.gc_count.clone(),
+        );
         stats
     }
 }
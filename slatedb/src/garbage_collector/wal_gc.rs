@@ -1,3 +1,4 @@
+use crate::event_listener::{EventListeners, SstsDeletedEvent};
 use crate::manifest::Manifest;
 use crate::tablestore::SstFileMetadata;
 use crate::{
@@ -16,6 +17,7 @@ pub(crate) struct WalGcTask {
     table_store: Arc<TableStore>,
     stats: Arc<GcStats>,
     wal_options: Option<GarbageCollectorDirectoryOptions>,
+    event_listeners: EventListeners,
 }
 
 impl std::fmt::Debug for WalGcTask {
@@ -32,15 +34,21 @@ impl WalGcTask {
         table_store: Arc<TableStore>,
         stats: Arc<GcStats>,
         wal_options: Option<GarbageCollectorDirectoryOptions>,
+        event_listeners: EventListeners,
     ) -> Self {
         WalGcTask {
             manifest_store,
             table_store,
             stats,
             wal_options,
+            event_listeners,
         }
     }
 
+    pub fn set_wal_options(&mut self, wal_options: Option<GarbageCollectorDirectoryOptions>) {
+        self.wal_options = wal_options;
+    }
+
     fn is_wal_sst_eligible_for_deletion(
         utc_now: &DateTime<Utc>,
         wal_sst: &SstFileMetadata,
@@ -96,14 +104,24 @@ impl GcTask for WalGcTask {
             .map(|wal_sst| wal_sst.id)
             .collect::<Vec<_>>();
 
+        let mut deleted_wal_ssts = Vec::new();
         for id in sst_ids_to_delete {
             if let Err(e) = self.table_store.delete_sst(&id).await {
                 error!("error deleting WAL SST [id={:?}, error={}]", id, e);
             } else {
                 self.stats.gc_wal_count.inc();
+                deleted_wal_ssts.push(id.unwrap_wal_id());
             }
         }
 
+        if !deleted_wal_ssts.is_empty() {
+            let event = SstsDeletedEvent {
+                wal_ssts: deleted_wal_ssts,
+                compacted_ssts: Vec::new(),
+            };
+            self.event_listeners.notify(|l| l.on_ssts_deleted(&event));
+        }
+
         Ok(())
     }
 
@@ -308,6 +308,11 @@ impl<'a> SsTableInfo<'a> {
   pub const VT_FILTER_OFFSET: flatbuffers::VOffsetT = 10;
   pub const VT_FILTER_LEN: flatbuffers::VOffsetT = 12;
   pub const VT_COMPRESSION_FORMAT: flatbuffers::VOffsetT = 14;
+  pub const VT_RANGE_TOMBSTONES: flatbuffers::VOffsetT = 16;
+  pub const VT_COLUMN_FAMILY_KEYS: flatbuffers::VOffsetT = 18;
+  pub const VT_PREFIX_EXTRACTOR: flatbuffers::VOffsetT = 20;
+  pub const VT_BLOB_REFS: flatbuffers::VOffsetT = 22;
+  pub const VT_ENCRYPTION_KEY_ID: flatbuffers::VOffsetT = 24;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -323,7 +328,12 @@ impl<'a> SsTableInfo<'a> {
     builder.add_filter_offset(args.filter_offset);
     builder.add_index_len(args.index_len);
     builder.add_index_offset(args.index_offset);
+    if let Some(x) = args.encryption_key_id { builder.add_encryption_key_id(x); }
+    if let Some(x) = args.blob_refs { builder.add_blob_refs(x); }
+    if let Some(x) = args.prefix_extractor { builder.add_prefix_extractor(x); }
+    if let Some(x) = args.range_tombstones { builder.add_range_tombstones(x); }
     if let Some(x) = args.first_key { builder.add_first_key(x); }
+    builder.add_column_family_keys(args.column_family_keys);
     builder.add_compression_format(args.compression_format);
     builder.finish()
   }
@@ -371,6 +381,41 @@ impl<'a> SsTableInfo<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<CompressionFormat>(SsTableInfo::VT_COMPRESSION_FORMAT, Some(CompressionFormat::None)).unwrap()}
   }
+  #[inline]
+  pub fn range_tombstones(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RangeTombstone<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RangeTombstone>>>>(SsTableInfo::VT_RANGE_TOMBSTONES, None)}
+  }
+  #[inline]
+  pub fn column_family_keys(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(SsTableInfo::VT_COLUMN_FAMILY_KEYS, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn prefix_extractor(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(SsTableInfo::VT_PREFIX_EXTRACTOR, None)}
+  }
+  #[inline]
+  pub fn blob_refs(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<BlobFileRef<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<BlobFileRef>>>>(SsTableInfo::VT_BLOB_REFS, None)}
+  }
+  #[inline]
+  pub fn encryption_key_id(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(SsTableInfo::VT_ENCRYPTION_KEY_ID, None)}
+  }
 }
 
 impl flatbuffers::Verifiable for SsTableInfo<'_> {
@@ -386,6 +431,11 @@ impl flatbuffers::Verifiable for SsTableInfo<'_> {
      .visit_field::<u64>("filter_offset", Self::VT_FILTER_OFFSET, false)?
      .visit_field::<u64>("filter_len", Self::VT_FILTER_LEN, false)?
      .visit_field::<CompressionFormat>("compression_format", Self::VT_COMPRESSION_FORMAT, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<RangeTombstone>>>>("range_tombstones", Self::VT_RANGE_TOMBSTONES, false)?
+     .visit_field::<bool>("column_family_keys", Self::VT_COLUMN_FAMILY_KEYS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("prefix_extractor", Self::VT_PREFIX_EXTRACTOR, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<BlobFileRef>>>>("blob_refs", Self::VT_BLOB_REFS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("encryption_key_id", Self::VT_ENCRYPTION_KEY_ID, false)?
      .finish();
     Ok(())
   }
@@ -397,6 +447,11 @@ pub struct SsTableInfoArgs<'a> {
     pub filter_offset: u64,
     pub filter_len: u64,
     pub compression_format: CompressionFormat,
+    pub range_tombstones: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<RangeTombstone<'a>>>>>,
+    pub column_family_keys: bool,
+    pub prefix_extractor: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub blob_refs: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<BlobFileRef<'a>>>>>,
+    pub encryption_key_id: Option<flatbuffers::WIPOffset<&'a str>>,
 }
 impl<'a> Default for SsTableInfoArgs<'a> {
   #[inline]
@@ -408,6 +463,11 @@ impl<'a> Default for SsTableInfoArgs<'a> {
       filter_offset: 0,
       filter_len: 0,
       compression_format: CompressionFormat::None,
+      range_tombstones: None,
+      column_family_keys: false,
+      prefix_extractor: None,
+      blob_refs: None,
+      encryption_key_id: None,
     }
   }
 }
@@ -442,6 +502,26 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> SsTableInfoBuilder<'a, 'b, A> {
     self.fbb_.push_slot::<CompressionFormat>(SsTableInfo::VT_COMPRESSION_FORMAT, compression_format, CompressionFormat::None);
   }
   #[inline]
+  pub fn add_range_tombstones(&mut self, range_tombstones: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<RangeTombstone<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SsTableInfo::VT_RANGE_TOMBSTONES, range_tombstones);
+  }
+  #[inline]
+  pub fn add_column_family_keys(&mut self, column_family_keys: bool) {
+    self.fbb_.push_slot::<bool>(SsTableInfo::VT_COLUMN_FAMILY_KEYS, column_family_keys, false);
+  }
+  #[inline]
+  pub fn add_prefix_extractor(&mut self, prefix_extractor: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SsTableInfo::VT_PREFIX_EXTRACTOR, prefix_extractor);
+  }
+  #[inline]
+  pub fn add_blob_refs(&mut self, blob_refs: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<BlobFileRef<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SsTableInfo::VT_BLOB_REFS, blob_refs);
+  }
+  #[inline]
+  pub fn add_encryption_key_id(&mut self, encryption_key_id: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(SsTableInfo::VT_ENCRYPTION_KEY_ID, encryption_key_id);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> SsTableInfoBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
     SsTableInfoBuilder {
@@ -465,6 +545,258 @@ impl core::fmt::Debug for SsTableInfo<'_> {
       ds.field("filter_offset", &self.filter_offset());
       ds.field("filter_len", &self.filter_len());
       ds.field("compression_format", &self.compression_format());
+      ds.field("range_tombstones", &self.range_tombstones());
+      ds.field("column_family_keys", &self.column_family_keys());
+      ds.field("prefix_extractor", &self.prefix_extractor());
+      ds.field("blob_refs", &self.blob_refs());
+      ds.field("encryption_key_id", &self.encryption_key_id());
+      ds.finish()
+  }
+}
+pub enum RangeTombstoneOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct RangeTombstone<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for RangeTombstone<'a> {
+  type Inner = RangeTombstone<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> RangeTombstone<'a> {
+  pub const VT_RANGE: flatbuffers::VOffsetT = 4;
+  pub const VT_SEQ: flatbuffers::VOffsetT = 6;
+  pub const VT_CREATE_TS: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    RangeTombstone { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args RangeTombstoneArgs<'args>
+  ) -> flatbuffers::WIPOffset<RangeTombstone<'bldr>> {
+    let mut builder = RangeTombstoneBuilder::new(_fbb);
+    builder.add_create_ts(args.create_ts);
+    builder.add_seq(args.seq);
+    if let Some(x) = args.range { builder.add_range(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn range(&self) -> BytesRange<'a> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<BytesRange>>(RangeTombstone::VT_RANGE, None).unwrap()}
+  }
+  #[inline]
+  pub fn seq(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(RangeTombstone::VT_SEQ, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn create_ts(&self) -> i64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i64>(RangeTombstone::VT_CREATE_TS, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for RangeTombstone<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<BytesRange>>("range", Self::VT_RANGE, true)?
+     .visit_field::<u64>("seq", Self::VT_SEQ, false)?
+     .visit_field::<i64>("create_ts", Self::VT_CREATE_TS, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct RangeTombstoneArgs<'a> {
+    pub range: Option<flatbuffers::WIPOffset<BytesRange<'a>>>,
+    pub seq: u64,
+    pub create_ts: i64,
+}
+impl<'a> Default for RangeTombstoneArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    RangeTombstoneArgs {
+      range: None, // required field
+      seq: 0,
+      create_ts: 0,
+    }
+  }
+}
+
+pub struct RangeTombstoneBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> RangeTombstoneBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_range(&mut self, range: flatbuffers::WIPOffset<BytesRange<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<BytesRange>>(RangeTombstone::VT_RANGE, range);
+  }
+  #[inline]
+  pub fn add_seq(&mut self, seq: u64) {
+    self.fbb_.push_slot::<u64>(RangeTombstone::VT_SEQ, seq, 0);
+  }
+  #[inline]
+  pub fn add_create_ts(&mut self, create_ts: i64) {
+    self.fbb_.push_slot::<i64>(RangeTombstone::VT_CREATE_TS, create_ts, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> RangeTombstoneBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    RangeTombstoneBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<RangeTombstone<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, RangeTombstone::VT_RANGE,"range");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for RangeTombstone<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("RangeTombstone");
+      ds.field("range", &self.range());
+      ds.field("seq", &self.seq());
+      ds.field("create_ts", &self.create_ts());
+      ds.finish()
+  }
+}
+pub enum BlobFileRefOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct BlobFileRef<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for BlobFileRef<'a> {
+  type Inner = BlobFileRef<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> BlobFileRef<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_LIVE_BYTES: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    BlobFileRef { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args BlobFileRefArgs<'args>
+  ) -> flatbuffers::WIPOffset<BlobFileRef<'bldr>> {
+    let mut builder = BlobFileRefBuilder::new(_fbb);
+    builder.add_live_bytes(args.live_bytes);
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> Ulid<'a> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Ulid>>(BlobFileRef::VT_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn live_bytes(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(BlobFileRef::VT_LIVE_BYTES, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for BlobFileRef<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Ulid>>("id", Self::VT_ID, true)?
+     .visit_field::<u64>("live_bytes", Self::VT_LIVE_BYTES, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct BlobFileRefArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<Ulid<'a>>>,
+    pub live_bytes: u64,
+}
+impl<'a> Default for BlobFileRefArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    BlobFileRefArgs {
+      id: None, // required field
+      live_bytes: 0,
+    }
+  }
+}
+
+pub struct BlobFileRefBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> BlobFileRefBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<Ulid<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Ulid>>(BlobFileRef::VT_ID, id);
+  }
+  #[inline]
+  pub fn add_live_bytes(&mut self, live_bytes: u64) {
+    self.fbb_.push_slot::<u64>(BlobFileRef::VT_LIVE_BYTES, live_bytes, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> BlobFileRefBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    BlobFileRefBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<BlobFileRef<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, BlobFileRef::VT_ID,"id");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for BlobFileRef<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("BlobFileRef");
+      ds.field("id", &self.id());
+      ds.field("live_bytes", &self.live_bytes());
       ds.finish()
   }
 }
@@ -1684,6 +2016,9 @@ impl<'a> ManifestV1<'a> {
   pub const VT_WAL_OBJECT_STORE_URI: flatbuffers::VOffsetT = 30;
   pub const VT_RECENT_SNAPSHOT_MIN_SEQ: flatbuffers::VOffsetT = 32;
   pub const VT_SEQUENCE_TRACKER: flatbuffers::VOffsetT = 34;
+  pub const VT_COLUMN_FAMILIES: flatbuffers::VOffsetT = 36;
+  pub const VT_COMPARATOR: flatbuffers::VOffsetT = 38;
+  pub const VT_WRITER_HANDOFF: flatbuffers::VOffsetT = 40;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
@@ -1703,6 +2038,9 @@ impl<'a> ManifestV1<'a> {
     builder.add_compactor_epoch(args.compactor_epoch);
     builder.add_writer_epoch(args.writer_epoch);
     builder.add_manifest_id(args.manifest_id);
+    if let Some(x) = args.writer_handoff { builder.add_writer_handoff(x); }
+    if let Some(x) = args.comparator { builder.add_comparator(x); }
+    if let Some(x) = args.column_families { builder.add_column_families(x); }
     if let Some(x) = args.sequence_tracker { builder.add_sequence_tracker(x); }
     if let Some(x) = args.wal_object_store_uri { builder.add_wal_object_store_uri(x); }
     if let Some(x) = args.checkpoints { builder.add_checkpoints(x); }
@@ -1827,6 +2165,27 @@ impl<'a> ManifestV1<'a> {
     // which contains a valid value in this slot
     unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, u8>>>(ManifestV1::VT_SEQUENCE_TRACKER, None)}
   }
+  #[inline]
+  pub fn column_families(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ColumnFamily<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ColumnFamily>>>>(ManifestV1::VT_COLUMN_FAMILIES, None)}
+  }
+  #[inline]
+  pub fn comparator(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ManifestV1::VT_COMPARATOR, None)}
+  }
+  #[inline]
+  pub fn writer_handoff(&self) -> Option<WriterHandoff<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<WriterHandoff>>(ManifestV1::VT_WRITER_HANDOFF, None)}
+  }
 }
 
 impl flatbuffers::Verifiable for ManifestV1<'_> {
@@ -1852,6 +2211,9 @@ impl flatbuffers::Verifiable for ManifestV1<'_> {
      .visit_field::<flatbuffers::ForwardsUOffset<&str>>("wal_object_store_uri", Self::VT_WAL_OBJECT_STORE_URI, false)?
      .visit_field::<u64>("recent_snapshot_min_seq", Self::VT_RECENT_SNAPSHOT_MIN_SEQ, false)?
      .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, u8>>>("sequence_tracker", Self::VT_SEQUENCE_TRACKER, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<ColumnFamily>>>>("column_families", Self::VT_COLUMN_FAMILIES, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("comparator", Self::VT_COMPARATOR, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<WriterHandoff>>("writer_handoff", Self::VT_WRITER_HANDOFF, false)?
      .finish();
     Ok(())
   }
@@ -1873,6 +2235,9 @@ pub struct ManifestV1Args<'a> {
     pub wal_object_store_uri: Option<flatbuffers::WIPOffset<&'a str>>,
     pub recent_snapshot_min_seq: u64,
     pub sequence_tracker: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, u8>>>,
+    pub column_families: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<ColumnFamily<'a>>>>>,
+    pub comparator: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub writer_handoff: Option<flatbuffers::WIPOffset<WriterHandoff<'a>>>,
 }
 impl<'a> Default for ManifestV1Args<'a> {
   #[inline]
@@ -1894,6 +2259,9 @@ impl<'a> Default for ManifestV1Args<'a> {
       wal_object_store_uri: None,
       recent_snapshot_min_seq: 0,
       sequence_tracker: None,
+      column_families: None,
+      comparator: None,
+      writer_handoff: None,
     }
   }
 }
@@ -1968,6 +2336,18 @@ impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ManifestV1Builder<'a, 'b, A> {
     self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ManifestV1::VT_SEQUENCE_TRACKER, sequence_tracker);
   }
   #[inline]
+  pub fn add_column_families(&mut self, column_families: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<ColumnFamily<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ManifestV1::VT_COLUMN_FAMILIES, column_families);
+  }
+  #[inline]
+  pub fn add_comparator(&mut self, comparator: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ManifestV1::VT_COMPARATOR, comparator);
+  }
+  #[inline]
+  pub fn add_writer_handoff(&mut self, writer_handoff: flatbuffers::WIPOffset<WriterHandoff<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<WriterHandoff>>(ManifestV1::VT_WRITER_HANDOFF, writer_handoff);
+  }
+  #[inline]
   pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ManifestV1Builder<'a, 'b, A> {
     let start = _fbb.start_table();
     ManifestV1Builder {
@@ -2004,100 +2384,385 @@ impl core::fmt::Debug for ManifestV1<'_> {
       ds.field("wal_object_store_uri", &self.wal_object_store_uri());
       ds.field("recent_snapshot_min_seq", &self.recent_snapshot_min_seq());
       ds.field("sequence_tracker", &self.sequence_tracker());
+      ds.field("column_families", &self.column_families());
+      ds.field("comparator", &self.comparator());
+      ds.field("writer_handoff", &self.writer_handoff());
       ds.finish()
   }
 }
-pub enum WriterCheckpointOffset {}
+pub enum WriterHandoffOffset {}
 #[derive(Copy, Clone, PartialEq)]
 
-pub struct WriterCheckpoint<'a> {
+pub struct WriterHandoff<'a> {
   pub _tab: flatbuffers::Table<'a>,
 }
 
-impl<'a> flatbuffers::Follow<'a> for WriterCheckpoint<'a> {
-  type Inner = WriterCheckpoint<'a>;
+impl<'a> flatbuffers::Follow<'a> for WriterHandoff<'a> {
+  type Inner = WriterHandoff<'a>;
   #[inline]
   unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
     Self { _tab: flatbuffers::Table::new(buf, loc) }
   }
 }
 
-impl<'a> WriterCheckpoint<'a> {
-  pub const VT_EPOCH: flatbuffers::VOffsetT = 4;
+impl<'a> WriterHandoff<'a> {
+  pub const VT_WRITER_EPOCH: flatbuffers::VOffsetT = 4;
+  pub const VT_LAST_SEQ: flatbuffers::VOffsetT = 6;
 
   #[inline]
   pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
-    WriterCheckpoint { _tab: table }
+    WriterHandoff { _tab: table }
   }
   #[allow(unused_mut)]
   pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
     _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
-    args: &'args WriterCheckpointArgs
-  ) -> flatbuffers::WIPOffset<WriterCheckpoint<'bldr>> {
-    let mut builder = WriterCheckpointBuilder::new(_fbb);
-    builder.add_epoch(args.epoch);
+    args: &'args WriterHandoffArgs
+  ) -> flatbuffers::WIPOffset<WriterHandoff<'bldr>> {
+    let mut builder = WriterHandoffBuilder::new(_fbb);
+    builder.add_last_seq(args.last_seq);
+    builder.add_writer_epoch(args.writer_epoch);
     builder.finish()
   }
 
 
   #[inline]
-  pub fn epoch(&self) -> u64 {
+  pub fn writer_epoch(&self) -> u64 {
     // Safety:
     // Created from valid Table for this object
     // which contains a valid value in this slot
-    unsafe { self._tab.get::<u64>(WriterCheckpoint::VT_EPOCH, Some(0)).unwrap()}
+    unsafe { self._tab.get::<u64>(WriterHandoff::VT_WRITER_EPOCH, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn last_seq(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(WriterHandoff::VT_LAST_SEQ, Some(0)).unwrap()}
   }
 }
 
-impl flatbuffers::Verifiable for WriterCheckpoint<'_> {
+impl flatbuffers::Verifiable for WriterHandoff<'_> {
   #[inline]
   fn run_verifier(
     v: &mut flatbuffers::Verifier, pos: usize
   ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
     use self::flatbuffers::Verifiable;
     v.visit_table(pos)?
-     .visit_field::<u64>("epoch", Self::VT_EPOCH, false)?
+     .visit_field::<u64>("writer_epoch", Self::VT_WRITER_EPOCH, false)?
+     .visit_field::<u64>("last_seq", Self::VT_LAST_SEQ, false)?
      .finish();
     Ok(())
   }
 }
-pub struct WriterCheckpointArgs {
-    pub epoch: u64,
+pub struct WriterHandoffArgs {
+    pub writer_epoch: u64,
+    pub last_seq: u64,
 }
-impl<'a> Default for WriterCheckpointArgs {
+impl<'a> Default for WriterHandoffArgs {
   #[inline]
   fn default() -> Self {
-    WriterCheckpointArgs {
-      epoch: 0,
+    WriterHandoffArgs {
+      writer_epoch: 0,
+      last_seq: 0,
     }
   }
 }
 
-pub struct WriterCheckpointBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+pub struct WriterHandoffBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
   fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
   start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
 }
-impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> WriterCheckpointBuilder<'a, 'b, A> {
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> WriterHandoffBuilder<'a, 'b, A> {
   #[inline]
-  pub fn add_epoch(&mut self, epoch: u64) {
-    self.fbb_.push_slot::<u64>(WriterCheckpoint::VT_EPOCH, epoch, 0);
+  pub fn add_writer_epoch(&mut self, writer_epoch: u64) {
+    self.fbb_.push_slot::<u64>(WriterHandoff::VT_WRITER_EPOCH, writer_epoch, 0);
   }
   #[inline]
-  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> WriterCheckpointBuilder<'a, 'b, A> {
+  pub fn add_last_seq(&mut self, last_seq: u64) {
+    self.fbb_.push_slot::<u64>(WriterHandoff::VT_LAST_SEQ, last_seq, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> WriterHandoffBuilder<'a, 'b, A> {
     let start = _fbb.start_table();
-    WriterCheckpointBuilder {
+    WriterHandoffBuilder {
       fbb_: _fbb,
       start_: start,
     }
   }
   #[inline]
-  pub fn finish(self) -> flatbuffers::WIPOffset<WriterCheckpoint<'a>> {
+  pub fn finish(self) -> flatbuffers::WIPOffset<WriterHandoff<'a>> {
     let o = self.fbb_.end_table(self.start_);
     flatbuffers::WIPOffset::new(o.value())
   }
 }
 
-impl core::fmt::Debug for WriterCheckpoint<'_> {
+impl core::fmt::Debug for WriterHandoff<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("WriterHandoff");
+      ds.field("writer_epoch", &self.writer_epoch());
+      ds.field("last_seq", &self.last_seq());
+      ds.finish()
+  }
+}
+pub enum ColumnFamilyOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct ColumnFamily<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for ColumnFamily<'a> {
+  type Inner = ColumnFamily<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> ColumnFamily<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_NAME: flatbuffers::VOffsetT = 6;
+  pub const VT_L0_LAST_COMPACTED: flatbuffers::VOffsetT = 8;
+  pub const VT_L0: flatbuffers::VOffsetT = 10;
+  pub const VT_COMPACTED: flatbuffers::VOffsetT = 12;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    ColumnFamily { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args ColumnFamilyArgs<'args>
+  ) -> flatbuffers::WIPOffset<ColumnFamily<'bldr>> {
+    let mut builder = ColumnFamilyBuilder::new(_fbb);
+    if let Some(x) = args.compacted { builder.add_compacted(x); }
+    if let Some(x) = args.l0 { builder.add_l0(x); }
+    if let Some(x) = args.l0_last_compacted { builder.add_l0_last_compacted(x); }
+    if let Some(x) = args.name { builder.add_name(x); }
+    builder.add_id(args.id);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(ColumnFamily::VT_ID, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn name(&self) -> &'a str {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(ColumnFamily::VT_NAME, None).unwrap()}
+  }
+  #[inline]
+  pub fn l0_last_compacted(&self) -> Option<CompactedSstId<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<CompactedSstId>>(ColumnFamily::VT_L0_LAST_COMPACTED, None)}
+  }
+  #[inline]
+  pub fn l0(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>(ColumnFamily::VT_L0, None).unwrap()}
+  }
+  #[inline]
+  pub fn compacted(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SortedRun<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SortedRun>>>>(ColumnFamily::VT_COMPACTED, None).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for ColumnFamily<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u32>("id", Self::VT_ID, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("name", Self::VT_NAME, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<CompactedSstId>>("l0_last_compacted", Self::VT_L0_LAST_COMPACTED, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>("l0", Self::VT_L0, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<SortedRun>>>>("compacted", Self::VT_COMPACTED, true)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct ColumnFamilyArgs<'a> {
+    pub id: u32,
+    pub name: Option<flatbuffers::WIPOffset<&'a str>>,
+    pub l0_last_compacted: Option<flatbuffers::WIPOffset<CompactedSstId<'a>>>,
+    pub l0: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>>>>,
+    pub compacted: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SortedRun<'a>>>>>,
+}
+impl<'a> Default for ColumnFamilyArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    ColumnFamilyArgs {
+      id: 0,
+      name: None, // required field
+      l0_last_compacted: None,
+      l0: None, // required field
+      compacted: None, // required field
+    }
+  }
+}
+
+pub struct ColumnFamilyBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> ColumnFamilyBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: u32) {
+    self.fbb_.push_slot::<u32>(ColumnFamily::VT_ID, id, 0);
+  }
+  #[inline]
+  pub fn add_name(&mut self, name: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ColumnFamily::VT_NAME, name);
+  }
+  #[inline]
+  pub fn add_l0_last_compacted(&mut self, l0_last_compacted: flatbuffers::WIPOffset<CompactedSstId<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<CompactedSstId>>(ColumnFamily::VT_L0_LAST_COMPACTED, l0_last_compacted);
+  }
+  #[inline]
+  pub fn add_l0(&mut self, l0: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<CompactedSsTable<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ColumnFamily::VT_L0, l0);
+  }
+  #[inline]
+  pub fn add_compacted(&mut self, compacted: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<SortedRun<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(ColumnFamily::VT_COMPACTED, compacted);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> ColumnFamilyBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    ColumnFamilyBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<ColumnFamily<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, ColumnFamily::VT_NAME,"name");
+    self.fbb_.required(o, ColumnFamily::VT_L0,"l0");
+    self.fbb_.required(o, ColumnFamily::VT_COMPACTED,"compacted");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for ColumnFamily<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("ColumnFamily");
+      ds.field("id", &self.id());
+      ds.field("name", &self.name());
+      ds.field("l0_last_compacted", &self.l0_last_compacted());
+      ds.field("l0", &self.l0());
+      ds.field("compacted", &self.compacted());
+      ds.finish()
+  }
+}
+pub enum WriterCheckpointOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct WriterCheckpoint<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for WriterCheckpoint<'a> {
+  type Inner = WriterCheckpoint<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> WriterCheckpoint<'a> {
+  pub const VT_EPOCH: flatbuffers::VOffsetT = 4;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    WriterCheckpoint { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args WriterCheckpointArgs
+  ) -> flatbuffers::WIPOffset<WriterCheckpoint<'bldr>> {
+    let mut builder = WriterCheckpointBuilder::new(_fbb);
+    builder.add_epoch(args.epoch);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn epoch(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(WriterCheckpoint::VT_EPOCH, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for WriterCheckpoint<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("epoch", Self::VT_EPOCH, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct WriterCheckpointArgs {
+    pub epoch: u64,
+}
+impl<'a> Default for WriterCheckpointArgs {
+  #[inline]
+  fn default() -> Self {
+    WriterCheckpointArgs {
+      epoch: 0,
+    }
+  }
+}
+
+pub struct WriterCheckpointBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> WriterCheckpointBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_epoch(&mut self, epoch: u64) {
+    self.fbb_.push_slot::<u64>(WriterCheckpoint::VT_EPOCH, epoch, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> WriterCheckpointBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    WriterCheckpointBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<WriterCheckpoint<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for WriterCheckpoint<'_> {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     let mut ds = f.debug_struct("WriterCheckpoint");
       ds.field("epoch", &self.epoch());
@@ -2335,3 +3000,804 @@ impl core::fmt::Debug for Checkpoint<'_> {
       ds.finish()
   }
 }
+pub enum CompactionSourceOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CompactionSource<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CompactionSource<'a> {
+  type Inner = CompactionSource<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CompactionSource<'a> {
+  pub const VT_SST_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_SORTED_RUN_ID: flatbuffers::VOffsetT = 6;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CompactionSource { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CompactionSourceArgs<'args>
+  ) -> flatbuffers::WIPOffset<CompactionSource<'bldr>> {
+    let mut builder = CompactionSourceBuilder::new(_fbb);
+    builder.add_sorted_run_id(args.sorted_run_id);
+    if let Some(x) = args.sst_id { builder.add_sst_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn sst_id(&self) -> Option<CompactedSstId<'a>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<CompactedSstId>>(CompactionSource::VT_SST_ID, None)}
+  }
+  #[inline]
+  pub fn sorted_run_id(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(CompactionSource::VT_SORTED_RUN_ID, Some(0)).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for CompactionSource<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<CompactedSstId>>("sst_id", Self::VT_SST_ID, false)?
+     .visit_field::<u32>("sorted_run_id", Self::VT_SORTED_RUN_ID, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CompactionSourceArgs<'a> {
+    pub sst_id: Option<flatbuffers::WIPOffset<CompactedSstId<'a>>>,
+    pub sorted_run_id: u32,
+}
+impl<'a> Default for CompactionSourceArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CompactionSourceArgs {
+      sst_id: None,
+      sorted_run_id: 0,
+    }
+  }
+}
+
+pub struct CompactionSourceBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CompactionSourceBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_sst_id(&mut self, sst_id: flatbuffers::WIPOffset<CompactedSstId<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<CompactedSstId>>(CompactionSource::VT_SST_ID, sst_id);
+  }
+  #[inline]
+  pub fn add_sorted_run_id(&mut self, sorted_run_id: u32) {
+    self.fbb_.push_slot::<u32>(CompactionSource::VT_SORTED_RUN_ID, sorted_run_id, 0);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CompactionSourceBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CompactionSourceBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CompactionSource<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CompactionSource<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CompactionSource");
+      ds.field("sst_id", &self.sst_id());
+      ds.field("sorted_run_id", &self.sorted_run_id());
+      ds.finish()
+  }
+}
+pub enum CompactionOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct Compaction<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for Compaction<'a> {
+  type Inner = Compaction<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> Compaction<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_COLUMN_FAMILY: flatbuffers::VOffsetT = 6;
+  pub const VT_SOURCES: flatbuffers::VOffsetT = 8;
+  pub const VT_DESTINATION: flatbuffers::VOffsetT = 10;
+  pub const VT_OUTPUT_SSTS: flatbuffers::VOffsetT = 12;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    Compaction { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CompactionArgs<'args>
+  ) -> flatbuffers::WIPOffset<Compaction<'bldr>> {
+    let mut builder = CompactionBuilder::new(_fbb);
+    if let Some(x) = args.output_ssts { builder.add_output_ssts(x); }
+    builder.add_destination(args.destination);
+    if let Some(x) = args.sources { builder.add_sources(x); }
+    builder.add_column_family(args.column_family);
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> Ulid<'a> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Ulid>>(Compaction::VT_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn column_family(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Compaction::VT_COLUMN_FAMILY, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn sources(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactionSource<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactionSource>>>>(Compaction::VT_SOURCES, None).unwrap()}
+  }
+  #[inline]
+  pub fn destination(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(Compaction::VT_DESTINATION, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn output_ssts(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>(Compaction::VT_OUTPUT_SSTS, None).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for Compaction<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Ulid>>("id", Self::VT_ID, true)?
+     .visit_field::<u32>("column_family", Self::VT_COLUMN_FAMILY, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CompactionSource>>>>("sources", Self::VT_SOURCES, true)?
+     .visit_field::<u32>("destination", Self::VT_DESTINATION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>("output_ssts", Self::VT_OUTPUT_SSTS, true)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CompactionArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<Ulid<'a>>>,
+    pub column_family: u32,
+    pub sources: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactionSource<'a>>>>>,
+    pub destination: u32,
+    pub output_ssts: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>>>>,
+}
+impl<'a> Default for CompactionArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CompactionArgs {
+      id: None, // required field
+      column_family: 0,
+      sources: None, // required field
+      destination: 0,
+      output_ssts: None, // required field
+    }
+  }
+}
+
+pub struct CompactionBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CompactionBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<Ulid<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Ulid>>(Compaction::VT_ID, id);
+  }
+  #[inline]
+  pub fn add_column_family(&mut self, column_family: u32) {
+    self.fbb_.push_slot::<u32>(Compaction::VT_COLUMN_FAMILY, column_family, 0);
+  }
+  #[inline]
+  pub fn add_sources(&mut self, sources: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<CompactionSource<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Compaction::VT_SOURCES, sources);
+  }
+  #[inline]
+  pub fn add_destination(&mut self, destination: u32) {
+    self.fbb_.push_slot::<u32>(Compaction::VT_DESTINATION, destination, 0);
+  }
+  #[inline]
+  pub fn add_output_ssts(&mut self, output_ssts: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<CompactedSsTable<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(Compaction::VT_OUTPUT_SSTS, output_ssts);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CompactionBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CompactionBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<Compaction<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, Compaction::VT_ID,"id");
+    self.fbb_.required(o, Compaction::VT_SOURCES,"sources");
+    self.fbb_.required(o, Compaction::VT_OUTPUT_SSTS,"output_ssts");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for Compaction<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("Compaction");
+      ds.field("id", &self.id());
+      ds.field("column_family", &self.column_family());
+      ds.field("sources", &self.sources());
+      ds.field("destination", &self.destination());
+      ds.field("output_ssts", &self.output_ssts());
+      ds.finish()
+  }
+}
+pub enum CompactionsV1Offset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CompactionsV1<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CompactionsV1<'a> {
+  type Inner = CompactionsV1<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CompactionsV1<'a> {
+  pub const VT_COMPACTOR_EPOCH: flatbuffers::VOffsetT = 4;
+  pub const VT_COMPACTIONS: flatbuffers::VOffsetT = 6;
+  pub const VT_SUBMITTED: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CompactionsV1 { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CompactionsV1Args<'args>
+  ) -> flatbuffers::WIPOffset<CompactionsV1<'bldr>> {
+    let mut builder = CompactionsV1Builder::new(_fbb);
+    builder.add_compactor_epoch(args.compactor_epoch);
+    if let Some(x) = args.submitted { builder.add_submitted(x); }
+    if let Some(x) = args.compactions { builder.add_compactions(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn compactor_epoch(&self) -> u64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(CompactionsV1::VT_COMPACTOR_EPOCH, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn compactions(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Compaction<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Compaction>>>>(CompactionsV1::VT_COMPACTIONS, None).unwrap()}
+  }
+  #[inline]
+  pub fn submitted(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Compaction<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Compaction>>>>(CompactionsV1::VT_SUBMITTED, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CompactionsV1<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<u64>("compactor_epoch", Self::VT_COMPACTOR_EPOCH, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Compaction>>>>("compactions", Self::VT_COMPACTIONS, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<Compaction>>>>("submitted", Self::VT_SUBMITTED, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CompactionsV1Args<'a> {
+    pub compactor_epoch: u64,
+    pub compactions: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Compaction<'a>>>>>,
+    pub submitted: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<Compaction<'a>>>>>,
+}
+impl<'a> Default for CompactionsV1Args<'a> {
+  #[inline]
+  fn default() -> Self {
+    CompactionsV1Args {
+      compactor_epoch: 0,
+      compactions: None, // required field
+      submitted: None,
+    }
+  }
+}
+
+pub struct CompactionsV1Builder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CompactionsV1Builder<'a, 'b, A> {
+  #[inline]
+  pub fn add_compactor_epoch(&mut self, compactor_epoch: u64) {
+    self.fbb_.push_slot::<u64>(CompactionsV1::VT_COMPACTOR_EPOCH, compactor_epoch, 0);
+  }
+  #[inline]
+  pub fn add_compactions(&mut self, compactions: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Compaction<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CompactionsV1::VT_COMPACTIONS, compactions);
+  }
+  #[inline]
+  pub fn add_submitted(&mut self, submitted: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<Compaction<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CompactionsV1::VT_SUBMITTED, submitted);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CompactionsV1Builder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CompactionsV1Builder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CompactionsV1<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, CompactionsV1::VT_COMPACTIONS,"compactions");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CompactionsV1<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CompactionsV1");
+      ds.field("compactor_epoch", &self.compactor_epoch());
+      ds.field("compactions", &self.compactions());
+      ds.field("submitted", &self.submitted());
+      ds.finish()
+  }
+}
+pub enum CompactionJobOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CompactionJob<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CompactionJob<'a> {
+  type Inner = CompactionJob<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CompactionJob<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_COMPACTION_ID: flatbuffers::VOffsetT = 6;
+  pub const VT_COLUMN_FAMILY: flatbuffers::VOffsetT = 8;
+  pub const VT_DESTINATION: flatbuffers::VOffsetT = 10;
+  pub const VT_SSTS: flatbuffers::VOffsetT = 12;
+  pub const VT_SORTED_RUNS: flatbuffers::VOffsetT = 14;
+  pub const VT_COMPACTION_LOGICAL_CLOCK_TICK: flatbuffers::VOffsetT = 16;
+  pub const VT_IS_DEST_LAST_RUN: flatbuffers::VOffsetT = 18;
+  pub const VT_RETENTION_MIN_SEQ: flatbuffers::VOffsetT = 20;
+  pub const VT_OUTPUT_SSTS: flatbuffers::VOffsetT = 22;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CompactionJob { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CompactionJobArgs<'args>
+  ) -> flatbuffers::WIPOffset<CompactionJob<'bldr>> {
+    let mut builder = CompactionJobBuilder::new(_fbb);
+    if let Some(x) = args.retention_min_seq { builder.add_retention_min_seq(x); }
+    builder.add_compaction_logical_clock_tick(args.compaction_logical_clock_tick);
+    if let Some(x) = args.output_ssts { builder.add_output_ssts(x); }
+    if let Some(x) = args.sorted_runs { builder.add_sorted_runs(x); }
+    if let Some(x) = args.ssts { builder.add_ssts(x); }
+    builder.add_destination(args.destination);
+    builder.add_column_family(args.column_family);
+    if let Some(x) = args.compaction_id { builder.add_compaction_id(x); }
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.add_is_dest_last_run(args.is_dest_last_run);
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> Ulid<'a> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Ulid>>(CompactionJob::VT_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn compaction_id(&self) -> Ulid<'a> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Ulid>>(CompactionJob::VT_COMPACTION_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn column_family(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(CompactionJob::VT_COLUMN_FAMILY, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn destination(&self) -> u32 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u32>(CompactionJob::VT_DESTINATION, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn ssts(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>(CompactionJob::VT_SSTS, None).unwrap()}
+  }
+  #[inline]
+  pub fn sorted_runs(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SortedRun<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SortedRun>>>>(CompactionJob::VT_SORTED_RUNS, None).unwrap()}
+  }
+  #[inline]
+  pub fn compaction_logical_clock_tick(&self) -> i64 {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<i64>(CompactionJob::VT_COMPACTION_LOGICAL_CLOCK_TICK, Some(0)).unwrap()}
+  }
+  #[inline]
+  pub fn is_dest_last_run(&self) -> bool {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<bool>(CompactionJob::VT_IS_DEST_LAST_RUN, Some(false)).unwrap()}
+  }
+  #[inline]
+  pub fn retention_min_seq(&self) -> Option<u64> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<u64>(CompactionJob::VT_RETENTION_MIN_SEQ, None)}
+  }
+  #[inline]
+  pub fn output_ssts(&self) -> flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>(CompactionJob::VT_OUTPUT_SSTS, None).unwrap()}
+  }
+}
+
+impl flatbuffers::Verifiable for CompactionJob<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Ulid>>("id", Self::VT_ID, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Ulid>>("compaction_id", Self::VT_COMPACTION_ID, true)?
+     .visit_field::<u32>("column_family", Self::VT_COLUMN_FAMILY, false)?
+     .visit_field::<u32>("destination", Self::VT_DESTINATION, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>("ssts", Self::VT_SSTS, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<SortedRun>>>>("sorted_runs", Self::VT_SORTED_RUNS, true)?
+     .visit_field::<i64>("compaction_logical_clock_tick", Self::VT_COMPACTION_LOGICAL_CLOCK_TICK, false)?
+     .visit_field::<bool>("is_dest_last_run", Self::VT_IS_DEST_LAST_RUN, false)?
+     .visit_field::<u64>("retention_min_seq", Self::VT_RETENTION_MIN_SEQ, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>("output_ssts", Self::VT_OUTPUT_SSTS, true)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CompactionJobArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<Ulid<'a>>>,
+    pub compaction_id: Option<flatbuffers::WIPOffset<Ulid<'a>>>,
+    pub column_family: u32,
+    pub destination: u32,
+    pub ssts: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>>>>,
+    pub sorted_runs: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<SortedRun<'a>>>>>,
+    pub compaction_logical_clock_tick: i64,
+    pub is_dest_last_run: bool,
+    pub retention_min_seq: Option<u64>,
+    pub output_ssts: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>>>>,
+}
+impl<'a> Default for CompactionJobArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CompactionJobArgs {
+      id: None, // required field
+      compaction_id: None, // required field
+      column_family: 0,
+      destination: 0,
+      ssts: None, // required field
+      sorted_runs: None, // required field
+      compaction_logical_clock_tick: 0,
+      is_dest_last_run: false,
+      retention_min_seq: None,
+      output_ssts: None, // required field
+    }
+  }
+}
+
+pub struct CompactionJobBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CompactionJobBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<Ulid<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Ulid>>(CompactionJob::VT_ID, id);
+  }
+  #[inline]
+  pub fn add_compaction_id(&mut self, compaction_id: flatbuffers::WIPOffset<Ulid<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Ulid>>(CompactionJob::VT_COMPACTION_ID, compaction_id);
+  }
+  #[inline]
+  pub fn add_column_family(&mut self, column_family: u32) {
+    self.fbb_.push_slot::<u32>(CompactionJob::VT_COLUMN_FAMILY, column_family, 0);
+  }
+  #[inline]
+  pub fn add_destination(&mut self, destination: u32) {
+    self.fbb_.push_slot::<u32>(CompactionJob::VT_DESTINATION, destination, 0);
+  }
+  #[inline]
+  pub fn add_ssts(&mut self, ssts: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<CompactedSsTable<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CompactionJob::VT_SSTS, ssts);
+  }
+  #[inline]
+  pub fn add_sorted_runs(&mut self, sorted_runs: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<SortedRun<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CompactionJob::VT_SORTED_RUNS, sorted_runs);
+  }
+  #[inline]
+  pub fn add_compaction_logical_clock_tick(&mut self, compaction_logical_clock_tick: i64) {
+    self.fbb_.push_slot::<i64>(CompactionJob::VT_COMPACTION_LOGICAL_CLOCK_TICK, compaction_logical_clock_tick, 0);
+  }
+  #[inline]
+  pub fn add_is_dest_last_run(&mut self, is_dest_last_run: bool) {
+    self.fbb_.push_slot::<bool>(CompactionJob::VT_IS_DEST_LAST_RUN, is_dest_last_run, false);
+  }
+  #[inline]
+  pub fn add_retention_min_seq(&mut self, retention_min_seq: u64) {
+    self.fbb_.push_slot_always::<u64>(CompactionJob::VT_RETENTION_MIN_SEQ, retention_min_seq);
+  }
+  #[inline]
+  pub fn add_output_ssts(&mut self, output_ssts: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<CompactedSsTable<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CompactionJob::VT_OUTPUT_SSTS, output_ssts);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CompactionJobBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CompactionJobBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CompactionJob<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, CompactionJob::VT_ID,"id");
+    self.fbb_.required(o, CompactionJob::VT_COMPACTION_ID,"compaction_id");
+    self.fbb_.required(o, CompactionJob::VT_SSTS,"ssts");
+    self.fbb_.required(o, CompactionJob::VT_SORTED_RUNS,"sorted_runs");
+    self.fbb_.required(o, CompactionJob::VT_OUTPUT_SSTS,"output_ssts");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CompactionJob<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CompactionJob");
+      ds.field("id", &self.id());
+      ds.field("compaction_id", &self.compaction_id());
+      ds.field("column_family", &self.column_family());
+      ds.field("destination", &self.destination());
+      ds.field("ssts", &self.ssts());
+      ds.field("sorted_runs", &self.sorted_runs());
+      ds.field("compaction_logical_clock_tick", &self.compaction_logical_clock_tick());
+      ds.field("is_dest_last_run", &self.is_dest_last_run());
+      ds.field("retention_min_seq", &self.retention_min_seq());
+      ds.field("output_ssts", &self.output_ssts());
+      ds.finish()
+  }
+}
+pub enum CompactionJobResultOffset {}
+#[derive(Copy, Clone, PartialEq)]
+
+pub struct CompactionJobResult<'a> {
+  pub _tab: flatbuffers::Table<'a>,
+}
+
+impl<'a> flatbuffers::Follow<'a> for CompactionJobResult<'a> {
+  type Inner = CompactionJobResult<'a>;
+  #[inline]
+  unsafe fn follow(buf: &'a [u8], loc: usize) -> Self::Inner {
+    Self { _tab: flatbuffers::Table::new(buf, loc) }
+  }
+}
+
+impl<'a> CompactionJobResult<'a> {
+  pub const VT_ID: flatbuffers::VOffsetT = 4;
+  pub const VT_OUTPUT_SSTS: flatbuffers::VOffsetT = 6;
+  pub const VT_ERROR: flatbuffers::VOffsetT = 8;
+
+  #[inline]
+  pub unsafe fn init_from_table(table: flatbuffers::Table<'a>) -> Self {
+    CompactionJobResult { _tab: table }
+  }
+  #[allow(unused_mut)]
+  pub fn create<'bldr: 'args, 'args: 'mut_bldr, 'mut_bldr, A: flatbuffers::Allocator + 'bldr>(
+    _fbb: &'mut_bldr mut flatbuffers::FlatBufferBuilder<'bldr, A>,
+    args: &'args CompactionJobResultArgs<'args>
+  ) -> flatbuffers::WIPOffset<CompactionJobResult<'bldr>> {
+    let mut builder = CompactionJobResultBuilder::new(_fbb);
+    if let Some(x) = args.error { builder.add_error(x); }
+    if let Some(x) = args.output_ssts { builder.add_output_ssts(x); }
+    if let Some(x) = args.id { builder.add_id(x); }
+    builder.finish()
+  }
+
+
+  #[inline]
+  pub fn id(&self) -> Ulid<'a> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<Ulid>>(CompactionJobResult::VT_ID, None).unwrap()}
+  }
+  #[inline]
+  pub fn output_ssts(&self) -> Option<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>>> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>(CompactionJobResult::VT_OUTPUT_SSTS, None)}
+  }
+  #[inline]
+  pub fn error(&self) -> Option<&'a str> {
+    // Safety:
+    // Created from valid Table for this object
+    // which contains a valid value in this slot
+    unsafe { self._tab.get::<flatbuffers::ForwardsUOffset<&str>>(CompactionJobResult::VT_ERROR, None)}
+  }
+}
+
+impl flatbuffers::Verifiable for CompactionJobResult<'_> {
+  #[inline]
+  fn run_verifier(
+    v: &mut flatbuffers::Verifier, pos: usize
+  ) -> Result<(), flatbuffers::InvalidFlatbuffer> {
+    use self::flatbuffers::Verifiable;
+    v.visit_table(pos)?
+     .visit_field::<flatbuffers::ForwardsUOffset<Ulid>>("id", Self::VT_ID, true)?
+     .visit_field::<flatbuffers::ForwardsUOffset<flatbuffers::Vector<'_, flatbuffers::ForwardsUOffset<CompactedSsTable>>>>("output_ssts", Self::VT_OUTPUT_SSTS, false)?
+     .visit_field::<flatbuffers::ForwardsUOffset<&str>>("error", Self::VT_ERROR, false)?
+     .finish();
+    Ok(())
+  }
+}
+pub struct CompactionJobResultArgs<'a> {
+    pub id: Option<flatbuffers::WIPOffset<Ulid<'a>>>,
+    pub output_ssts: Option<flatbuffers::WIPOffset<flatbuffers::Vector<'a, flatbuffers::ForwardsUOffset<CompactedSsTable<'a>>>>>,
+    pub error: Option<flatbuffers::WIPOffset<&'a str>>,
+}
+impl<'a> Default for CompactionJobResultArgs<'a> {
+  #[inline]
+  fn default() -> Self {
+    CompactionJobResultArgs {
+      id: None, // required field
+      output_ssts: None,
+      error: None,
+    }
+  }
+}
+
+pub struct CompactionJobResultBuilder<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> {
+  fbb_: &'b mut flatbuffers::FlatBufferBuilder<'a, A>,
+  start_: flatbuffers::WIPOffset<flatbuffers::TableUnfinishedWIPOffset>,
+}
+impl<'a: 'b, 'b, A: flatbuffers::Allocator + 'a> CompactionJobResultBuilder<'a, 'b, A> {
+  #[inline]
+  pub fn add_id(&mut self, id: flatbuffers::WIPOffset<Ulid<'b >>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<Ulid>>(CompactionJobResult::VT_ID, id);
+  }
+  #[inline]
+  pub fn add_output_ssts(&mut self, output_ssts: flatbuffers::WIPOffset<flatbuffers::Vector<'b , flatbuffers::ForwardsUOffset<CompactedSsTable<'b >>>>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CompactionJobResult::VT_OUTPUT_SSTS, output_ssts);
+  }
+  #[inline]
+  pub fn add_error(&mut self, error: flatbuffers::WIPOffset<&'b  str>) {
+    self.fbb_.push_slot_always::<flatbuffers::WIPOffset<_>>(CompactionJobResult::VT_ERROR, error);
+  }
+  #[inline]
+  pub fn new(_fbb: &'b mut flatbuffers::FlatBufferBuilder<'a, A>) -> CompactionJobResultBuilder<'a, 'b, A> {
+    let start = _fbb.start_table();
+    CompactionJobResultBuilder {
+      fbb_: _fbb,
+      start_: start,
+    }
+  }
+  #[inline]
+  pub fn finish(self) -> flatbuffers::WIPOffset<CompactionJobResult<'a>> {
+    let o = self.fbb_.end_table(self.start_);
+    self.fbb_.required(o, CompactionJobResult::VT_ID,"id");
+    flatbuffers::WIPOffset::new(o.value())
+  }
+}
+
+impl core::fmt::Debug for CompactionJobResult<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let mut ds = f.debug_struct("CompactionJobResult");
+      ds.field("id", &self.id());
+      ds.field("output_ssts", &self.output_ssts());
+      ds.field("error", &self.error());
+      ds.finish()
+  }
+}
@@ -0,0 +1,192 @@
+//! # SST Ingestion
+//!
+//! This module adds SST ingestion to DbInner. Ingestion adds SSTs that were built outside
+//! of the database (see [`crate::SstWriter`]) to its L0 without going through the WAL and
+//! memtable, which makes bulk loads much cheaper than writing every row with `put`.
+//!
+//! Ingestion happens in three steps:
+//!
+//! 1. The write loop reserves a sequence number for each SST and freezes the memtable.
+//!    Since the loop assigns the sequence numbers of all writes, every write older than
+//!    the ingested SSTs is in the frozen memtable (or an older one) at that point.
+//! 2. Each SST is rewritten into the database's `compacted` directory with its rows at
+//!    the reserved sequence number, and the frozen memtables are flushed to L0.
+//! 3. The memtable flusher, which owns the L0, adds the rewritten SSTs to the manifest in
+//!    a single update and raises `last_l0_seq` to the reserved sequence numbers, so they
+//!    aren't reused after a restart.
+//!
+//! Writes that happen while the SSTs are being rewritten get higher sequence numbers, so
+//! they'd silently shadow the ingested rows. Rather than doing that, the flusher rejects
+//! the ingestion if the SSTs overlap any such write.
+
+use std::cmp;
+use std::ops::Bound::Included;
+
+use bytes::Bytes;
+use fail_parallel::fail_point;
+use object_store::path::Path;
+
+use crate::batch_write::WriteBatchMessage;
+use crate::block_iterator::BlockIterator;
+use crate::bytes_range::BytesRange;
+use crate::db::DbInner;
+use crate::db_state::{SsTableHandle, SsTableId};
+use crate::error::SlateDBError;
+use crate::iter::{IterationOrder, KeyValueIterator};
+use crate::mem_table_flush::MemtableFlushMsg;
+use crate::range_tombstone::RangeTombstone;
+use crate::tablestore::ExternalSsTable;
+use crate::utils::{IdGenerator, SendSafely, WatchableOnceCellReader};
+
+/// The number of blocks read from an ingested SST at a time.
+const INGEST_BLOCKS_PER_READ: usize = 16;
+
+/// The sequence numbers reserved by the write loop for an ingestion.
+pub(crate) struct IngestReservation {
+    /// The sequence number of each SST, in the order the SSTs were passed in.
+    pub(crate) seqs: Vec<u64>,
+    /// Resolves once the memtables holding the writes older than the reservation have
+    /// been flushed to L0. `None` if there weren't any.
+    pub(crate) flushed: Option<WatchableOnceCellReader<Result<(), SlateDBError>>>,
+}
+
+/// An SST rewritten into the database, waiting to be added to L0.
+#[derive(Debug)]
+pub(crate) struct IngestedSst {
+    /// The path of the SST that was ingested.
+    pub(crate) path: Path,
+    pub(crate) handle: SsTableHandle,
+    pub(crate) seq: u64,
+    /// The key ranges covered by the rows and range tombstones of the SST.
+    pub(crate) ranges: Vec<BytesRange>,
+}
+
+impl DbInner {
+    /// Reserves a sequence number for each of `count` SSTs and freezes the memtable. Must
+    /// be called from the write loop.
+    pub(crate) fn reserve_ingest_seqs(
+        &self,
+        count: usize,
+    ) -> Result<IngestReservation, SlateDBError> {
+        let seqs = (0..count).map(|_| self.oracle.last_seq.next()).collect();
+        let last_flushed_wal_id = self.wal_buffer.recent_flushed_wal_id();
+        let mut guard = self.state.write();
+        self.freeze_memtable(&mut guard, last_flushed_wal_id)?;
+        // memtables are flushed in order, so waiting for the newest one is enough.
+        let flushed = guard
+            .state()
+            .imm_memtable
+            .front()
+            .map(|imm| imm.table().durable_watcher());
+        Ok(IngestReservation { seqs, flushed })
+    }
+
+    pub(crate) async fn ingest_ssts(&self, paths: &[Path]) -> Result<(), SlateDBError> {
+        self.check_closed()?;
+        let mut external_ssts = Vec::with_capacity(paths.len());
+        for path in paths {
+            let external_sst = self.table_store.open_external_sst(path).await?;
+            if external_sst.info.first_key.is_some()
+                || !external_sst.info.range_tombstones.is_empty()
+            {
+                external_ssts.push(external_sst);
+            }
+        }
+        if external_ssts.is_empty() {
+            return Ok(());
+        }
+
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.write_notifier.send_safely(
+            self.state.read().closed_result_reader(),
+            WriteBatchMessage::ReserveIngestSeqs {
+                count: external_ssts.len(),
+                done: tx,
+            },
+        )?;
+        let reservation = rx.await??;
+
+        let mut ssts = Vec::with_capacity(external_ssts.len());
+        for (external_sst, seq) in external_ssts.iter().zip(reservation.seqs) {
+            ssts.push(self.rewrite_ingested_sst(external_sst, seq).await?);
+        }
+        if let Some(mut flushed) = reservation.flushed {
+            flushed.await_value().await?;
+        }
+
+        fail_point!(self.fp_registry.clone(), "ingest-ssts-before-publish");
+        let (tx, rx) = tokio::sync::oneshot::channel();
+        self.memtable_flush_notifier.send_safely(
+            self.state.read().closed_result_reader(),
+            MemtableFlushMsg::IngestSsts { ssts, sender: tx },
+        )?;
+        rx.await?
+    }
+
+    /// Copies the rows and range tombstones of `external_sst` to a new SST of the
+    /// database, at sequence number `seq`.
+    async fn rewrite_ingested_sst(
+        &self,
+        external_sst: &ExternalSsTable,
+        seq: u64,
+    ) -> Result<IngestedSst, SlateDBError> {
+        let id = SsTableId::Compacted(self.rand.rng().gen_ulid(self.system_clock.as_ref()));
+        let format = self.table_store.sst_format();
+        let comparator = self.table_store.comparator();
+        if !comparator.is_bytewise() && !external_sst.info.range_tombstones.is_empty() {
+            return Err(SlateDBError::UnsupportedWithComparator("range delete"));
+        }
+        let mut writer = self.table_store.table_writer_with_format(id, format);
+
+        let mut first_key: Option<Bytes> = None;
+        let mut last_key: Option<Bytes> = None;
+        let num_blocks = external_sst.num_blocks();
+        let mut start = 0;
+        while start < num_blocks {
+            let end = cmp::min(start + INGEST_BLOCKS_PER_READ, num_blocks);
+            for block in external_sst.read_blocks(format, start..end).await? {
+                let mut iter = BlockIterator::new(block, IterationOrder::Ascending)
+                    .with_comparator(comparator.clone());
+                while let Some(mut entry) = iter.next_entry().await? {
+                    // rows all get the same seq, so there can only be one per key
+                    if let Some(last_key) = &last_key {
+                        if comparator.compare(&entry.key, last_key).is_le() {
+                            return Err(SlateDBError::InvalidKeyOrder {
+                                last_key: last_key.clone(),
+                                key: entry.key,
+                            });
+                        }
+                    }
+                    first_key.get_or_insert_with(|| entry.key.clone());
+                    last_key = Some(entry.key.clone());
+                    entry.seq = seq;
+                    writer.add(entry).await?;
+                }
+            }
+            start = end;
+        }
+
+        let mut ranges = Vec::new();
+        if let (Some(first_key), Some(last_key)) = (first_key, last_key) {
+            ranges.push(BytesRange::new_by(
+                Included(first_key),
+                Included(last_key),
+                comparator,
+            ));
+        }
+        for tombstone in &external_sst.info.range_tombstones {
+            ranges.push(tombstone.range.clone());
+            writer.add_range_tombstone(RangeTombstone {
+                seq,
+                ..tombstone.clone()
+            });
+        }
+        let handle = writer.close().await?;
+        Ok(IngestedSst {
+            path: external_sst.path().clone(),
+            handle,
+            seq,
+            ranges,
+        })
+    }
+}
@@ -2,12 +2,21 @@ use async_trait::async_trait;
 
 use crate::error::SlateDBError;
 use crate::types::RowEntry;
+#[cfg(test)]
 use crate::types::{KeyValue, ValueDeletable};
 
-#[derive(Clone, Copy, Debug)]
-pub(crate) enum IterationOrder {
+/// The order in which an iterator returns keys.
+///
+/// Regardless of the order, multiple versions of the same key are always
+/// resolved newest-first, so tombstones, TTLs and merge operands behave the
+/// same way in both directions.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum IterationOrder {
+    /// Return keys in increasing lexicographic order.
+    #[default]
     Ascending,
-    #[allow(dead_code)]
+    /// Return keys in decreasing lexicographic order.
     Descending,
 }
 
@@ -25,6 +34,7 @@ pub trait KeyValueIterator: Send + Sync {
     async fn init(&mut self) -> Result<(), SlateDBError>;
 
     /// Returns the next non-deleted key-value pair in the iterator.
+    #[cfg(test)]
     async fn next(&mut self) -> Result<Option<KeyValue>, SlateDBError> {
         loop {
             let entry = self.next_entry().await?;
@@ -44,6 +54,9 @@ pub trait KeyValueIterator: Send + Sync {
                         return Ok(Some(KeyValue { key: kv.key, value }))
                     }
                     ValueDeletable::Tombstone => continue,
+                    ValueDeletable::BlobRef(_) => {
+                        unreachable!("blob refs are resolved by SST iterators")
+                    }
                 }
             } else {
                 return Ok(None);
@@ -65,6 +78,10 @@ pub trait KeyValueIterator: Send + Sync {
 
     /// Seek to the next (inclusive) key
     ///
+    /// "Next" follows the iteration order of the iterator: an ascending iterator
+    /// is positioned at the first key greater than or equal to `next_key`, and a
+    /// descending iterator at the first key less than or equal to `next_key`.
+    ///
     /// Will fail with `SlateDBError::IteratorNotInitialized` if the iterator is
     /// not yet initialized.
     ///
@@ -94,6 +111,7 @@ impl<'a> KeyValueIterator for Box<dyn KeyValueIterator + 'a> {
         self.as_mut().init().await
     }
 
+    #[cfg(test)]
     async fn next(&mut self) -> Result<Option<KeyValue>, SlateDBError> {
         self.as_mut().next().await
     }
@@ -0,0 +1,833 @@
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
+
+use log::warn;
+use ulid::Ulid;
+
+use crate::bytes_range::BytesRange;
+use crate::compactor::{CompactionScheduler, CompactionSchedulerSupplier};
+use crate::compactor_state::{CompactionSpec, CompactorState, SourceId};
+use crate::comparator::KeyComparator;
+use crate::config::{CompactorOptions, LeveledCompactionSchedulerOptions};
+use crate::db_state::{CoreDbState, SortedRun, SsTableHandle};
+use crate::error::Error;
+
+const DEFAULT_MAX_CONCURRENT_COMPACTIONS: usize = 4;
+
+/// Implements a leveled compaction scheduler. Below L0, the database is made of
+/// `options.num_levels` levels. Each level is a single sorted run, partitioned into SSTs
+/// by key range. Level 1 is stored in the sorted run with the highest id
+/// (`num_levels - 1`), and the last level in the sorted run with id 0, so newer data
+/// is always in sorted runs with higher ids.
+///
+/// The scheduler compacts one of:
+/// - all L0 SSTs into level 1, once there are at least options.l0_compaction_trigger L0 SSTs.
+/// - a single SST of level N into level N + 1, once level N is larger than its target size
+///   (`options.level_base_size_bytes * options.level_size_multiplier ^ (N - 1)`).
+///
+/// Only the SSTs of the next level that overlap the compacted SSTs are rewritten. Levels
+/// are compacted in decreasing order of size relative to their target. Two compactions
+/// never use the same level at the same time.
+///
+/// Sorted runs with ids of `num_levels` or more (for example, runs written by the
+/// size-tiered scheduler) are not part of any level. They are compacted into level 1
+/// before anything else is.
+pub(crate) struct LeveledCompactionScheduler {
+    options: LeveledCompactionSchedulerOptions,
+    max_concurrent_compactions: usize,
+}
+
+impl Default for LeveledCompactionScheduler {
+    fn default() -> Self {
+        Self::new(
+            LeveledCompactionSchedulerOptions::default(),
+            DEFAULT_MAX_CONCURRENT_COMPACTIONS,
+        )
+    }
+}
+
+impl CompactionScheduler for LeveledCompactionScheduler {
+    fn maybe_schedule_compaction(&self, state: &CompactorState) -> Vec<CompactionSpec> {
+        let db_state = state.db_state();
+        let mut compactions = Vec::new();
+        let mut busy_levels = self.busy_levels(state);
+        let mut can_schedule = |levels: [usize; 2], compactions: &Vec<CompactionSpec>| {
+            if state.compactions().count() + compactions.len() >= self.max_concurrent_compactions
+                || levels.iter().any(|level| busy_levels.contains(level))
+            {
+                return false;
+            }
+            busy_levels.extend(levels);
+            true
+        };
+
+        let unleveled_runs: Vec<&SortedRun> = db_state
+            .compacted
+            .iter()
+            .filter(|sr| self.level(sr.id).is_none())
+            .collect();
+        if !unleveled_runs.is_empty() {
+            // level 1 can't receive newer data until the unleveled runs are merged into it
+            if can_schedule([0, 1], &compactions) {
+                let level_1 = self.sorted_run(db_state, 1);
+                let sources = unleveled_runs
+                    .iter()
+                    .chain(level_1.iter())
+                    .map(|sr| SourceId::SortedRun(sr.id))
+                    .collect();
+                compactions.push(CompactionSpec::new(sources, self.sorted_run_id(1)));
+            }
+            return compactions;
+        }
+
+        for level in self.levels_to_compact(db_state) {
+            if !can_schedule([level, level + 1], &compactions) {
+                continue;
+            }
+            if let Some(compaction) = self.pick_compaction(db_state, level, state.comparator()) {
+                compactions.push(compaction);
+            }
+        }
+        compactions
+    }
+
+    fn validate_compaction(
+        &self,
+        state: &CompactorState,
+        compaction: &CompactionSpec,
+    ) -> Result<(), Error> {
+        let db_state = state.db_state();
+        let comparator = state.comparator();
+        let Some(destination_level) = self.level(compaction.destination()) else {
+            warn!(
+                "compaction destination is not a level: {:?}",
+                compaction.destination()
+            );
+            return Err(Error::invalid(
+                "destination is not a level sorted run".to_string(),
+            ));
+        };
+
+        let l0_positions: HashMap<Ulid, usize> = db_state
+            .l0
+            .iter()
+            .enumerate()
+            .map(|(pos, sst)| (sst.id.unwrap_compacted_id(), pos))
+            .collect();
+        let sorted_run_ssts: HashMap<Ulid, (&SortedRun, usize)> = db_state
+            .compacted
+            .iter()
+            .flat_map(|sr| {
+                sr.ssts
+                    .iter()
+                    .enumerate()
+                    .map(move |(idx, sst)| (sst.id.unwrap_compacted_id(), (sr, idx)))
+            })
+            .collect();
+        let source_ssts: HashSet<Ulid> = compaction
+            .sources()
+            .iter()
+            .filter_map(|s| s.maybe_unwrap_sst())
+            .collect();
+        let source_runs: HashSet<u32> = compaction
+            .sources()
+            .iter()
+            .filter_map(|s| s.maybe_unwrap_sorted_run())
+            .collect();
+
+        // ranges of the sources that are not in the destination level, and all source SSTs
+        let mut upper_ranges = Vec::new();
+        let mut input_ssts: Vec<&SsTableHandle> = Vec::new();
+        let mut l0_sources = Vec::new();
+        let mut has_sorted_run_ssts = false;
+        for source in compaction.sources() {
+            let level = match source {
+                SourceId::Sst(id) => {
+                    if let Some(pos) = l0_positions.get(id) {
+                        let sst = &db_state.l0[*pos];
+                        l0_sources.push(*pos);
+                        upper_ranges.push(sst.compacted_effective_range().clone());
+                        input_ssts.push(sst);
+                        Some(0)
+                    } else if let Some((sr, idx)) = sorted_run_ssts.get(id) {
+                        has_sorted_run_ssts = true;
+                        let level = self.level(sr.id);
+                        if level != Some(destination_level) {
+                            upper_ranges.push(Self::sst_range(sr, *idx, comparator));
+                        }
+                        input_ssts.push(&sr.ssts[*idx]);
+                        level
+                    } else {
+                        warn!("compaction source not found in db state: {:?}", source);
+                        return Err(Error::invalid("unknown compaction source".to_string()));
+                    }
+                }
+                SourceId::SortedRun(id) => {
+                    let Some(sr) = db_state.compacted.iter().find(|sr| sr.id == *id) else {
+                        warn!("compaction source not found in db state: {:?}", source);
+                        return Err(Error::invalid("unknown compaction source".to_string()));
+                    };
+                    if *id != compaction.destination() {
+                        upper_ranges.push(BytesRange::from(..));
+                    }
+                    input_ssts.extend(sr.ssts.iter());
+                    // unleveled runs can only be compacted into level 1
+                    Some(self.level(*id).unwrap_or(0))
+                }
+            };
+            if !level
+                .is_some_and(|level| level + 1 == destination_level || level == destination_level)
+            {
+                warn!(
+                    "compaction source is not in the destination level or the level above it: {:?} {:?}",
+                    source, destination_level
+                );
+                return Err(Error::invalid(
+                    "sources must be in the destination level or the level above it".to_string(),
+                ));
+            }
+        }
+
+        // newer L0 SSTs can't be moved below older ones
+        l0_sources.sort_unstable();
+        if l0_sources
+            .iter()
+            .enumerate()
+            .any(|(i, pos)| *pos != db_state.l0.len() - l0_sources.len() + i)
+        {
+            warn!(
+                "compaction L0 sources are not the oldest L0 SSTs: {:?}",
+                compaction.sources()
+            );
+            return Err(Error::invalid(
+                "L0 sources must be the oldest L0 SSTs".to_string(),
+            ));
+        }
+
+        if destination_level == 1
+            && db_state
+                .compacted
+                .iter()
+                .any(|sr| self.level(sr.id).is_none() && !source_runs.contains(&sr.id))
+        {
+            warn!("compaction into level 1 does not include all unleveled sorted runs");
+            return Err(Error::invalid(
+                "sorted runs above level 1 must be compacted into level 1".to_string(),
+            ));
+        }
+
+        let destination = db_state
+            .compacted
+            .iter()
+            .find(|sr| sr.id == compaction.destination());
+        let merges_into_destination =
+            destination.is_some() && !source_runs.contains(&compaction.destination());
+        if let (Some(destination), true) = (destination, merges_into_destination) {
+            // the output replaces the overlapping SSTs of the destination level, so it must
+            // include all of them to keep the level's SSTs disjoint
+            if let Some(span) = Self::covering_range(&upper_ranges, comparator) {
+                if destination
+                    .tables_covering_range(&span, comparator)
+                    .iter()
+                    .any(|sst| !source_ssts.contains(&sst.id.unwrap_compacted_id()))
+                {
+                    warn!(
+                        "compaction does not include all overlapping SSTs of the destination level: {:?}",
+                        compaction.sources()
+                    );
+                    return Err(Error::invalid(
+                        "sources must include all overlapping SSTs of the destination level"
+                            .to_string(),
+                    ));
+                }
+            }
+        }
+
+        // a range tombstone may cover keys outside of its SST, which a partial compaction
+        // would not rewrite
+        if (has_sorted_run_ssts || merges_into_destination)
+            && input_ssts
+                .iter()
+                .any(|sst| !sst.info.range_tombstones.is_empty())
+        {
+            warn!(
+                "partial compaction includes SSTs with range tombstones: {:?}",
+                compaction.sources()
+            );
+            return Err(Error::invalid(
+                "partial compactions can't include SSTs with range tombstones".to_string(),
+            ));
+        }
+
+        Ok(())
+    }
+}
+
+impl LeveledCompactionScheduler {
+    pub(crate) fn new(
+        options: LeveledCompactionSchedulerOptions,
+        max_concurrent_compactions: usize,
+    ) -> Self {
+        assert!(options.num_levels > 0, "num_levels must be positive");
+        Self {
+            options,
+            max_concurrent_compactions,
+        }
+    }
+
+    /// Returns the id of the sorted run of a level (1-based).
+    fn sorted_run_id(&self, level: usize) -> u32 {
+        (self.options.num_levels - level) as u32
+    }
+
+    /// Returns the level of a sorted run, or None if the run is above level 1.
+    fn level(&self, sorted_run_id: u32) -> Option<usize> {
+        let id = sorted_run_id as usize;
+        (id < self.options.num_levels).then(|| self.options.num_levels - id)
+    }
+
+    fn sorted_run<'a>(&self, db_state: &'a CoreDbState, level: usize) -> Option<&'a SortedRun> {
+        let id = self.sorted_run_id(level);
+        db_state.compacted.iter().find(|sr| sr.id == id)
+    }
+
+    /// Returns the target size of a level, or None for the last level.
+    fn target_size(&self, level: usize) -> Option<u64> {
+        if level >= self.options.num_levels {
+            return None;
+        }
+        let multiplier =
+            (self.options.level_size_multiplier as u64).saturating_pow(level as u32 - 1);
+        Some(
+            self.options
+                .level_base_size_bytes
+                .saturating_mul(multiplier),
+        )
+    }
+
+    /// Returns the levels used by running compactions. A compaction into level N reads
+    /// level N - 1 and writes level N.
+    fn busy_levels(&self, state: &CompactorState) -> HashSet<usize> {
+        state
+            .compactions()
+            .flat_map(|c| {
+                let level = self.level(c.spec().destination()).unwrap_or(1);
+                [level - 1, level]
+            })
+            .collect()
+    }
+
+    /// Returns the levels that exceed their target, with the most oversized first. L0
+    /// exceeds its target when it has at least `l0_compaction_trigger` SSTs.
+    fn levels_to_compact(&self, db_state: &CoreDbState) -> Vec<usize> {
+        let mut scores = Vec::new();
+        if !db_state.l0.is_empty() && db_state.l0.len() >= self.options.l0_compaction_trigger {
+            let trigger = self.options.l0_compaction_trigger.max(1);
+            scores.push((db_state.l0.len() as f64 / trigger as f64, 0));
+        }
+        for level in 1..self.options.num_levels {
+            let size = self
+                .sorted_run(db_state, level)
+                .map_or(0, |sr| sr.estimate_size());
+            let target = self
+                .target_size(level)
+                .expect("expected target size")
+                .max(1);
+            if size > target {
+                scores.push((size as f64 / target as f64, level));
+            }
+        }
+        scores.sort_by(|a, b| b.0.partial_cmp(&a.0).unwrap_or(Ordering::Equal));
+        scores.into_iter().map(|(_, level)| level).collect()
+    }
+
+    /// Picks a compaction of `level` into the next level.
+    fn pick_compaction(
+        &self,
+        db_state: &CoreDbState,
+        level: usize,
+        comparator: &KeyComparator,
+    ) -> Option<CompactionSpec> {
+        let next_run = self.sorted_run(db_state, level + 1);
+        let destination = self.sorted_run_id(level + 1);
+        if level == 0 {
+            let l0: Vec<&SsTableHandle> = db_state.l0.iter().collect();
+            let ranges: Vec<BytesRange> = l0
+                .iter()
+                .map(|sst| sst.compacted_effective_range().clone())
+                .collect();
+            let span = Self::covering_range(&ranges, comparator)?;
+            return Some(Self::create_compaction(
+                l0,
+                None,
+                &span,
+                next_run,
+                destination,
+                comparator,
+            ));
+        }
+
+        // pick the SST that rewrites the fewest bytes of the next level per byte moved
+        let run = self.sorted_run(db_state, level)?;
+        let (idx, _) = (0..run.ssts.len())
+            .map(|idx| {
+                let overlap = next_run.map_or(0, |next_run| {
+                    next_run
+                        .tables_covering_range(&Self::sst_range(run, idx, comparator), comparator)
+                        .iter()
+                        .map(|sst| sst.estimate_size())
+                        .sum::<u64>()
+                });
+                let size = run.ssts[idx].estimate_size().max(1);
+                (idx, overlap as f64 / size as f64)
+            })
+            .min_by(|a, b| a.1.partial_cmp(&b.1).unwrap_or(Ordering::Equal))?;
+        Some(Self::create_compaction(
+            vec![&run.ssts[idx]],
+            Some(run),
+            &Self::sst_range(run, idx, comparator),
+            next_run,
+            destination,
+            comparator,
+        ))
+    }
+
+    /// Creates a compaction of the `upper` SSTs (from `upper_run`, or from L0 if None)
+    /// and the SSTs of `next_run` that overlap `span`. If any of these SSTs has range
+    /// tombstones, the whole runs are compacted instead.
+    fn create_compaction(
+        upper: Vec<&SsTableHandle>,
+        upper_run: Option<&SortedRun>,
+        span: &BytesRange,
+        next_run: Option<&SortedRun>,
+        destination: u32,
+        comparator: &KeyComparator,
+    ) -> CompactionSpec {
+        let overlapping = next_run.map_or_else(Default::default, |next_run| {
+            next_run.tables_covering_range(span, comparator)
+        });
+        let has_range_tombstones = upper
+            .iter()
+            .chain(overlapping.iter())
+            .any(|sst| !sst.info.range_tombstones.is_empty());
+        let sources = if has_range_tombstones {
+            let upper_sources = match upper_run {
+                Some(upper_run) => vec![SourceId::SortedRun(upper_run.id)],
+                None => upper
+                    .iter()
+                    .map(|sst| SourceId::Sst(sst.id.unwrap_compacted_id()))
+                    .collect(),
+            };
+            upper_sources
+                .into_iter()
+                .chain(next_run.map(|next_run| SourceId::SortedRun(next_run.id)))
+                .collect()
+        } else {
+            upper
+                .iter()
+                .chain(overlapping.iter())
+                .map(|sst| SourceId::Sst(sst.id.unwrap_compacted_id()))
+                .collect()
+        };
+        CompactionSpec::new(sources, destination)
+    }
+
+    /// Returns the key range of the SST at `idx` in a sorted run: from its first key
+    /// to the first key of the next SST.
+    fn sst_range(run: &SortedRun, idx: usize, comparator: &KeyComparator) -> BytesRange {
+        run.ssts[idx]
+            .compacted_intersection(run.ssts.get(idx + 1), &BytesRange::from(..), comparator)
+            .expect("expected non-empty sst range")
+    }
+
+    /// Returns the smallest range that contains all of `ranges`.
+    fn covering_range(ranges: &[BytesRange], comparator: &KeyComparator) -> Option<BytesRange> {
+        ranges
+            .iter()
+            .cloned()
+            .reduce(|span, range| span.span_by(&range, comparator))
+    }
+}
+
+/// Supplies a [`LeveledCompactionScheduler`].
+#[derive(Default)]
+pub struct LeveledCompactionSchedulerSupplier {
+    options: LeveledCompactionSchedulerOptions,
+}
+
+impl LeveledCompactionSchedulerSupplier {
+    pub const fn new(options: LeveledCompactionSchedulerOptions) -> Self {
+        Self { options }
+    }
+}
+
+impl CompactionSchedulerSupplier for LeveledCompactionSchedulerSupplier {
+    fn compaction_scheduler(
+        &self,
+        compactor_options: &CompactorOptions,
+    ) -> Box<dyn CompactionScheduler + Send + Sync> {
+        Box::new(LeveledCompactionScheduler::new(
+            self.options.clone(),
+            compactor_options.max_concurrent_compactions,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::VecDeque;
+
+    use bytes::Bytes;
+
+    use crate::bytes_range::BytesRange;
+    use crate::compactor::CompactionScheduler;
+    use crate::compactor_state::{Compaction, CompactionSpec, CompactorState, SourceId};
+    use crate::config::LeveledCompactionSchedulerOptions;
+    use crate::db_state::{CoreDbState, SortedRun, SsTableHandle, SsTableId, SsTableInfo};
+    use crate::leveled_compaction::LeveledCompactionScheduler;
+    use crate::manifest::store::test_utils::new_dirty_manifest;
+    use crate::range_tombstone::RangeTombstone;
+    use crate::seq_tracker::SequenceTracker;
+
+    const LEVEL_1: u32 = 5;
+    const LEVEL_2: u32 = 4;
+
+    #[test]
+    fn test_should_compact_l0_into_level_1() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let l0 = [
+            create_sst("d", 1),
+            create_sst("c", 1),
+            create_sst("b", 1),
+            create_sst("a", 1),
+        ];
+        let state = create_compactor_state(create_db_state(l0.iter().cloned().collect(), vec![]));
+
+        // when:
+        let compactions = scheduler.maybe_schedule_compaction(&state);
+
+        // then:
+        assert_eq!(
+            compactions,
+            vec![CompactionSpec::new(sst_sources(&l0), LEVEL_1)]
+        );
+    }
+
+    #[test]
+    fn test_should_not_compact_l0_below_trigger() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let l0 = [create_sst("c", 1), create_sst("b", 1), create_sst("a", 1)];
+        let state = create_compactor_state(create_db_state(l0.iter().cloned().collect(), vec![]));
+
+        // when:
+        let compactions = scheduler.maybe_schedule_compaction(&state);
+
+        // then:
+        assert!(compactions.is_empty());
+    }
+
+    #[test]
+    fn test_should_compact_l0_with_overlapping_level_1_ssts() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let l0 = [
+            create_sst("m", 1),
+            create_sst("k", 1),
+            create_sst("j", 1),
+            create_sst("h", 1),
+        ];
+        let level_1 = create_sr(LEVEL_1, &["a", "f", "i", "p"], 1);
+        let state = create_compactor_state(create_db_state(
+            l0.iter().cloned().collect(),
+            vec![level_1.clone()],
+        ));
+
+        // when:
+        let compactions = scheduler.maybe_schedule_compaction(&state);
+
+        // then: the L1 SST starting at "a" ends before the first L0 key
+        let mut expected_sources = sst_sources(&l0);
+        expected_sources.extend(sst_sources(&level_1.ssts[1..]));
+        assert_eq!(
+            compactions,
+            vec![CompactionSpec::new(expected_sources, LEVEL_1)]
+        );
+        scheduler
+            .validate_compaction(&state, &compactions[0])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_should_move_sst_with_least_overlap_into_next_level() {
+        // given:
+        let scheduler = scheduler_with_base_size(10);
+        let level_1 = SortedRun {
+            id: LEVEL_1,
+            ssts: vec![
+                create_sst("a", 10),
+                create_sst("g", 10),
+                create_sst("n", 10),
+            ],
+        };
+        let level_2 = SortedRun {
+            id: LEVEL_2,
+            ssts: vec![
+                create_sst("a", 30),
+                create_sst("c", 30),
+                create_sst("h", 5),
+                create_sst("m", 30),
+            ],
+        };
+        let state = create_compactor_state(create_db_state(
+            VecDeque::new(),
+            vec![level_1.clone(), level_2.clone()],
+        ));
+
+        // when:
+        let compactions = scheduler.maybe_schedule_compaction(&state);
+
+        // then: "g".."n" overlaps the L2 SSTs starting at "c", "h" and "m"; "n".. overlaps
+        // the one starting at "m"; "a".."g" overlaps the ones starting at "a" and "c"
+        let expected_sources = sst_sources(&[level_1.ssts[2].clone(), level_2.ssts[3].clone()]);
+        assert_eq!(
+            compactions,
+            vec![CompactionSpec::new(expected_sources, LEVEL_2)]
+        );
+        scheduler
+            .validate_compaction(&state, &compactions[0])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_should_compact_whole_levels_with_range_tombstones() {
+        // given:
+        let scheduler = scheduler_with_base_size(10);
+        let mut tombstone_sst = create_sst("a", 20);
+        tombstone_sst.info.range_tombstones =
+            vec![RangeTombstone::new(BytesRange::from_ref("a".."z"), 1, 0)];
+        let level_1 = SortedRun {
+            id: LEVEL_1,
+            ssts: vec![tombstone_sst],
+        };
+        let level_2 = create_sr(LEVEL_2, &["a", "m"], 10);
+        let state =
+            create_compactor_state(create_db_state(VecDeque::new(), vec![level_1, level_2]));
+
+        // when:
+        let compactions = scheduler.maybe_schedule_compaction(&state);
+
+        // then:
+        assert_eq!(
+            compactions,
+            vec![CompactionSpec::new(
+                vec![SourceId::SortedRun(LEVEL_1), SourceId::SortedRun(LEVEL_2)],
+                LEVEL_2
+            )]
+        );
+        scheduler
+            .validate_compaction(&state, &compactions[0])
+            .unwrap();
+    }
+
+    #[test]
+    fn test_should_compact_unleveled_runs_into_level_1_first() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let l0 = [
+            create_sst("d", 1),
+            create_sst("c", 1),
+            create_sst("b", 1),
+            create_sst("a", 1),
+        ];
+        let state = create_compactor_state(create_db_state(
+            l0.iter().cloned().collect(),
+            vec![
+                create_sr(9, &["a"], 1),
+                create_sr(7, &["a"], 1),
+                create_sr(LEVEL_1, &["a"], 1),
+            ],
+        ));
+
+        // when:
+        let compactions = scheduler.maybe_schedule_compaction(&state);
+
+        // then:
+        let expected = CompactionSpec::new(
+            vec![
+                SourceId::SortedRun(9),
+                SourceId::SortedRun(7),
+                SourceId::SortedRun(LEVEL_1),
+            ],
+            LEVEL_1,
+        );
+        assert_eq!(compactions, vec![expected]);
+        let l0_compaction = CompactionSpec::new(sst_sources(&l0), LEVEL_1);
+        assert!(scheduler
+            .validate_compaction(&state, &l0_compaction)
+            .is_err());
+    }
+
+    #[test]
+    fn test_should_not_schedule_compaction_for_busy_levels() {
+        // given:
+        let scheduler = scheduler_with_base_size(10);
+        let l0 = [
+            create_sst("d", 1),
+            create_sst("c", 1),
+            create_sst("b", 1),
+            create_sst("a", 1),
+        ];
+        let level_1 = create_sr(LEVEL_1, &["a", "m"], 100);
+        let mut state = create_compactor_state(create_db_state(
+            l0.iter().cloned().collect(),
+            vec![level_1.clone()],
+        ));
+        let level_1_compaction = CompactionSpec::new(sst_sources(&level_1.ssts[..1]), LEVEL_2);
+        state
+            .add_compaction(Compaction::new(ulid::Ulid::new(), level_1_compaction))
+            .unwrap();
+
+        // when:
+        let compactions = scheduler.maybe_schedule_compaction(&state);
+
+        // then: L0 can't be compacted into level 1 while level 1 is compacted
+        assert!(compactions.is_empty());
+    }
+
+    #[test]
+    fn test_should_reject_compaction_skipping_a_level() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let level_1 = create_sr(LEVEL_1, &["a"], 1);
+        let state = create_compactor_state(create_db_state(VecDeque::new(), vec![level_1.clone()]));
+
+        // when:
+        let compaction = CompactionSpec::new(sst_sources(&level_1.ssts), LEVEL_2 - 1);
+        let result = scheduler.validate_compaction(&state, &compaction);
+
+        // then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_should_reject_compaction_into_unleveled_run() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let l0 = [create_sst("a", 1)];
+        let state = create_compactor_state(create_db_state(l0.iter().cloned().collect(), vec![]));
+
+        // when:
+        let compaction = CompactionSpec::new(sst_sources(&l0), 6);
+        let result = scheduler.validate_compaction(&state, &compaction);
+
+        // then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_should_reject_compaction_missing_overlapping_ssts() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let level_1 = create_sr(LEVEL_1, &["a", "m"], 1);
+        let level_2 = create_sr(LEVEL_2, &["a", "f", "p"], 1);
+        let state = create_compactor_state(create_db_state(
+            VecDeque::new(),
+            vec![level_1.clone(), level_2.clone()],
+        ));
+
+        // when: "a".."m" overlaps the L2 SSTs starting at "a" and "f"
+        let compaction = CompactionSpec::new(
+            sst_sources(&[level_1.ssts[0].clone(), level_2.ssts[0].clone()]),
+            LEVEL_2,
+        );
+        let result = scheduler.validate_compaction(&state, &compaction);
+
+        // then:
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_should_reject_compaction_of_newest_l0_only() {
+        // given:
+        let scheduler = LeveledCompactionScheduler::default();
+        let l0 = [create_sst("b", 1), create_sst("a", 1)];
+        let state = create_compactor_state(create_db_state(l0.iter().cloned().collect(), vec![]));
+
+        // when:
+        let compaction = CompactionSpec::new(sst_sources(&l0[..1]), LEVEL_1);
+        let result = scheduler.validate_compaction(&state, &compaction);
+
+        // then:
+        assert!(result.is_err());
+        let compaction = CompactionSpec::new(sst_sources(&l0[1..]), LEVEL_1);
+        scheduler.validate_compaction(&state, &compaction).unwrap();
+    }
+
+    fn scheduler_with_base_size(level_base_size_bytes: u64) -> LeveledCompactionScheduler {
+        LeveledCompactionScheduler::new(
+            LeveledCompactionSchedulerOptions {
+                level_base_size_bytes,
+                ..LeveledCompactionSchedulerOptions::default()
+            },
+            4,
+        )
+    }
+
+    fn create_sst(first_key: &str, size: u64) -> SsTableHandle {
+        let info = SsTableInfo {
+            first_key: Some(Bytes::copy_from_slice(first_key.as_bytes())),
+            index_offset: size,
+            index_len: 0,
+            filter_offset: 0,
+            filter_len: 0,
+            compression_codec: None,
+            range_tombstones: vec![],
+            column_family_keys: false,
+            prefix_extractor: None,
+            blob_refs: vec![],
+            encryption_key_id: None,
+        };
+        SsTableHandle::new_compacted(SsTableId::Compacted(ulid::Ulid::new()), info, None)
+    }
+
+    fn create_sr(id: u32, first_keys: &[&str], sst_size: u64) -> SortedRun {
+        let ssts = first_keys
+            .iter()
+            .map(|key| create_sst(key, sst_size))
+            .collect();
+        SortedRun { id, ssts }
+    }
+
+    fn sst_sources(ssts: &[SsTableHandle]) -> Vec<SourceId> {
+        ssts.iter()
+            .map(|sst| SourceId::Sst(sst.id.unwrap_compacted_id()))
+            .collect()
+    }
+
+    fn create_db_state(l0: VecDeque<SsTableHandle>, srs: Vec<SortedRun>) -> CoreDbState {
+        CoreDbState {
+            initialized: true,
+            l0_last_compacted: None,
+            l0,
+            compacted: srs,
+            next_wal_sst_id: 0,
+            replay_after_wal_id: 0,
+            last_l0_seq: 0,
+            last_l0_clock_tick: 0,
+            checkpoints: vec![],
+            wal_object_store_uri: None,
+            recent_snapshot_min_seq: 0,
+            sequence_tracker: SequenceTracker::new(),
+            column_families: vec![],
+            comparator: None,
+        }
+    }
+
+    fn create_compactor_state(db_state: CoreDbState) -> CompactorState {
+        let mut dirty = new_dirty_manifest();
+        dirty.value.core = db_state;
+        CompactorState::new(dirty)
+    }
+}
@@ -32,11 +32,15 @@ pub use fail_parallel;
 /// without having to depend on the object store crate directly.
 pub use object_store;
 
-pub use batch::WriteBatch;
+pub use batch::{Precondition, WriteBatch};
 pub use cached_object_store::stats as cached_object_store_stats;
+pub use change_stream::{ChangeStream, ChangeValue, RowChange};
 pub use checkpoint::{Checkpoint, CheckpointCreateResult};
+pub use column_family::{ColumnFamily, ColumnFamilyOptions};
+pub use compaction_filter::{CompactionFilter, CompactionFilterDecision};
 pub use compactor::stats as compactor_stats;
-pub use config::{Settings, SstBlockSize};
+pub use comparator::Comparator;
+pub use config::{Settings, SettingsPatch, SstBlockSize};
 pub use db::{Db, DbBuilder};
 pub use db_cache::stats as db_cache_stats;
 pub use db_iter::DbIterator;
@@ -44,13 +48,21 @@ pub use db_read::DbRead;
 pub use db_reader::DbReader;
 pub use db_snapshot::DbSnapshot;
 pub use db_transaction::DBTransaction;
+pub use encryption::{EncryptionError, EncryptionProvider};
 pub use error::{CloseReason, Error, ErrorKind};
+pub use event_listener::{
+    CompactionFinishedEvent, CompactionStartedEvent, DbClosedEvent, EventListener,
+    ManifestWrittenEvent, MemtableFlushedEvent, SstsDeletedEvent, WalFlushedEvent,
+};
 pub use garbage_collector::stats as garbage_collector_stats;
+pub use iter::IterationOrder;
 pub use merge_operator::{MergeOperator, MergeOperatorError};
+pub use prefix_extractor::{FixedLengthPrefixExtractor, PrefixExtractor};
 pub use rand::DbRand;
 pub use seq_tracker::FindOption;
+pub use sst_writer::SstWriter;
 pub use transaction_manager::IsolationLevel;
-pub use types::KeyValue;
+pub use types::{KeyValue, KeyValueWithMetadata};
 
 pub mod admin;
 pub mod clock;
@@ -59,25 +71,33 @@ pub mod compaction_execute_bench;
 pub mod config;
 pub mod db_cache;
 pub mod db_stats;
+pub mod leveled_compaction;
+pub mod remote_compaction;
 pub mod size_tiered_compaction;
 pub mod stats;
 
 mod batch;
 mod batch_write;
 mod blob;
+mod blob_file;
 mod block;
 mod block_iterator;
 #[cfg(any(test, feature = "bencher"))]
 mod bytes_generator;
 mod bytes_range;
 mod cached_object_store;
+mod change_stream;
 mod checkpoint;
 mod clone;
+mod column_family;
+mod compaction_filter;
+mod compactions_store;
 mod compactor;
 mod compactor_executor;
 mod compactor_state;
 #[allow(dead_code)]
 mod comparable_range;
+mod comparator;
 mod db;
 mod db_common;
 mod db_iter;
@@ -86,13 +106,17 @@ mod db_reader;
 mod db_snapshot;
 mod db_state;
 mod db_transaction;
+mod descending_iter;
 mod dispatcher;
+mod encryption;
 mod error;
+mod event_listener;
 mod filter;
 mod filter_iterator;
 mod flatbuffer_types;
 mod flush;
 mod garbage_collector;
+mod ingest;
 mod iter;
 mod manifest;
 mod map_iter;
@@ -104,9 +128,12 @@ mod object_stores;
 mod oracle;
 mod partitioned_keyspace;
 mod paths;
+mod prefix_extractor;
 #[cfg(test)]
 mod proptest_util;
 mod rand;
+mod range_tombstone;
+mod rate_limiter;
 mod reader;
 mod retention_iterator;
 mod retrying_object_store;
@@ -115,6 +142,7 @@ mod seq_tracker;
 mod sorted_run_iterator;
 mod sst;
 mod sst_iter;
+mod sst_writer;
 mod store_provider;
 mod tablestore;
 #[cfg(test)]
@@ -126,3 +154,4 @@ mod utils;
 mod wal_buffer;
 mod wal_id;
 mod wal_replay;
+mod write_stall;
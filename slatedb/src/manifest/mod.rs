@@ -5,6 +5,7 @@ use std::ops::Bound;
 use std::sync::Arc;
 
 use crate::bytes_range::BytesRange;
+use crate::comparator::KeyComparator;
 use crate::db_state::{CoreDbState, SortedRun, SsTableHandle, SsTableId};
 use crate::rand::DbRand;
 use crate::utils::IdGenerator;
@@ -23,6 +24,8 @@ pub(crate) struct Manifest {
     // todo: try to make this writable only from module
     pub(crate) writer_epoch: u64,
     pub(crate) compactor_epoch: u64,
+    /// Set by a writer that handed off the database with `Db::transfer_leadership`.
+    pub(crate) writer_handoff: Option<WriterHandoff>,
 }
 
 impl Manifest {
@@ -32,6 +35,7 @@ impl Manifest {
             core,
             writer_epoch: 0,
             compactor_epoch: 0,
+            writer_handoff: None,
         }
     }
 
@@ -57,13 +61,23 @@ impl Manifest {
             });
         }
 
-        let parent_owned_sst_ids = parent_manifest
+        // blob files have the ids of the SSTs that wrote them, so the blob files the
+        // parent's SSTs point to are resolved to the parent along with its SSTs
+        let parent_ssts: Vec<_> = parent_manifest
             .core
-            .compacted
+            .all_compacted()
+            .flat_map(|sr| sr.ssts.iter())
+            .chain(parent_manifest.core.all_l0())
+            .collect();
+        let parent_blob_ids = parent_ssts
+            .iter()
+            .flat_map(|s| s.info.blob_refs.iter().map(|r| SsTableId::Compacted(r.id)));
+        let mut seen_ids = HashSet::new();
+        let parent_owned_sst_ids = parent_ssts
             .iter()
-            .flat_map(|sr| sr.ssts.iter().map(|s| s.id))
-            .chain(parent_manifest.core.l0.iter().map(|s| s.id))
-            .filter(|id| !parent_external_sst_ids.contains(id))
+            .map(|s| s.id)
+            .chain(parent_blob_ids)
+            .filter(|id| !parent_external_sst_ids.contains(id) && seen_ids.insert(*id))
             .collect();
 
         clone_external_dbs.push(ExternalDb {
@@ -78,6 +92,7 @@ impl Manifest {
             core: parent_manifest.core.init_clone_db(),
             writer_epoch: parent_manifest.writer_epoch,
             compactor_epoch: parent_manifest.compactor_epoch,
+            writer_handoff: None,
         }
     }
 
@@ -112,9 +127,12 @@ impl Manifest {
             } else {
                 iter.peek().copied()
             };
-            if let Some(intersection) =
-                current_handle.compacted_intersection(next_handle, projection_range)
-            {
+            // projections are only supported for keys ordered by their bytes
+            if let Some(intersection) = current_handle.compacted_intersection(
+                next_handle,
+                projection_range,
+                &KeyComparator::default(),
+            ) {
                 filtered_handles.push(current_handle.with_visible_range(intersection));
             }
         }
@@ -170,6 +188,7 @@ impl Manifest {
                 core,
                 writer_epoch: 0,
                 compactor_epoch: 0,
+                writer_handoff: None,
             }
         }
     }
@@ -202,13 +221,32 @@ pub(crate) struct ExternalDb {
     pub(crate) path: String,
     pub(crate) source_checkpoint_id: Uuid,
     pub(crate) final_checkpoint_id: Option<Uuid>,
+    /// The ids of the SSTs of the external database, and of the blob files they point to.
     pub(crate) sst_ids: Vec<SsTableId>,
 }
 
+/// A cooperative handoff of the database by a writer. The writer flushed all of its
+/// writes to L0 before the handoff, so the next writer has no WAL to replay.
+#[derive(Clone, Copy, Serialize, PartialEq, Debug)]
+pub(crate) struct WriterHandoff {
+    /// The epoch of the writer that handed off the database.
+    pub(crate) writer_epoch: u64,
+    /// The seq of the last write of the writer.
+    pub(crate) last_seq: u64,
+}
+
 impl Manifest {
     pub(crate) fn has_wal_sst_reference(&self, wal_sst_id: u64) -> bool {
         wal_sst_id > self.core.replay_after_wal_id && wal_sst_id < self.core.next_wal_sst_id
     }
+
+    /// Returns the handoff of the current writer, if it handed off the database and no
+    /// other writer has opened it since.
+    pub(crate) fn pending_handoff(&self) -> Option<&WriterHandoff> {
+        self.writer_handoff
+            .as_ref()
+            .filter(|handoff| handoff.writer_epoch == self.writer_epoch)
+    }
 }
 
 #[cfg(test)]
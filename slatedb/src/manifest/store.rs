@@ -2,6 +2,7 @@ use crate::checkpoint::Checkpoint;
 use crate::clock::SystemClock;
 use crate::config::CheckpointOptions;
 use crate::db_state::CoreDbState;
+use crate::encryption::{EncryptedObjectCodec, EncryptionProviderType};
 use crate::error::SlateDBError;
 use crate::error::SlateDBError::{
     CheckpointMissing, InvalidDBState, LatestTransactionalObjectVersionMissing, ManifestMissing,
@@ -52,7 +53,11 @@ impl FenceableManifest {
             manifest_update_timeout,
             system_clock,
             |m: &Manifest| m.writer_epoch,
-            |m: &mut Manifest, e: u64| m.writer_epoch = e,
+            |m: &mut Manifest, e: u64| {
+                m.writer_epoch = e;
+                // the handoff marker of the previous writer is consumed by the new writer
+                m.writer_handoff = None;
+            },
         )
         .await?;
         Ok(Self { inner: fr, clock })
@@ -79,6 +84,10 @@ impl FenceableManifest {
         Ok(self.inner.refresh().await?)
     }
 
+    pub(crate) fn id(&self) -> u64 {
+        self.inner.id().into()
+    }
+
     pub(crate) fn prepare_dirty(&self) -> Result<DirtyObject<Manifest>, SlateDBError> {
         Ok(self.inner.prepare_dirty()?)
     }
@@ -470,17 +479,33 @@ pub(crate) struct ManifestStore {
 }
 
 impl ManifestStore {
+    #[cfg_attr(not(test), allow(dead_code))]
     pub(crate) fn new(
         root_path: &Path,
         object_store: Arc<dyn ObjectStore>,
         clock: Arc<dyn SystemClock>,
+    ) -> Self {
+        Self::new_with_encryption_provider(root_path, object_store, clock, None)
+    }
+
+    /// Creates a manifest store that encrypts manifests with the current key of
+    /// `encryption_provider`, if set. Unencrypted manifests are read with or without a
+    /// provider.
+    pub(crate) fn new_with_encryption_provider(
+        root_path: &Path,
+        object_store: Arc<dyn ObjectStore>,
+        clock: Arc<dyn SystemClock>,
+        encryption_provider: Option<EncryptionProviderType>,
     ) -> Self {
         let inner = Arc::new(ObjectStoreSequencedStorageProtocol::<Manifest>::new(
             root_path,
             object_store,
             "manifest",
             "manifest",
-            Box::new(FlatBufferManifestCodec {}),
+            Box::new(EncryptedObjectCodec::new(
+                Box::new(FlatBufferManifestCodec {}),
+                encryption_provider,
+            )),
         ));
         Self { inner, clock }
     }
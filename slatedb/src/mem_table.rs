@@ -1,4 +1,5 @@
 use std::cell::Cell;
+use std::collections::BTreeMap;
 use std::ops::{Bound, RangeBounds};
 use std::sync::atomic::{AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
@@ -14,29 +15,52 @@ use std::sync::atomic::Ordering::SeqCst;
 use chrono::{DateTime, Utc};
 use parking_lot::Mutex;
 
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+use crate::comparator::KeyComparator;
 use crate::error::SlateDBError;
 use crate::iter::{IterationOrder, KeyValueIterator};
+use crate::range_tombstone::RangeTombstone;
 use crate::seq_tracker::{SequenceTracker, TrackedSeq};
 use crate::types::RowEntry;
 use crate::utils::{WatchableOnceCell, WatchableOnceCellReader};
 
 /// Memtable may contains multiple versions of a single user key, with a monotonically increasing sequence number.
-#[derive(Debug, Clone, Eq, PartialEq)]
+///
+/// The key carries the comparator of its table, since the skip map of the table orders
+/// keys by their [`Ord`] implementation.
+#[derive(Debug, Clone)]
 pub(crate) struct SequencedKey {
     pub(crate) user_key: Bytes,
     pub(crate) seq: u64,
+    comparator: KeyComparator,
 }
 
 impl SequencedKey {
     pub fn new(user_key: Bytes, seq: u64) -> Self {
-        Self { user_key, seq }
+        Self::new_by(user_key, seq, &KeyComparator::default())
+    }
+
+    pub(crate) fn new_by(user_key: Bytes, seq: u64, comparator: &KeyComparator) -> Self {
+        Self {
+            user_key,
+            seq,
+            comparator: comparator.clone(),
+        }
     }
 }
 
+impl PartialEq for SequencedKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.user_key == other.user_key && self.seq == other.seq
+    }
+}
+
+impl Eq for SequencedKey {}
+
 impl Ord for SequencedKey {
     fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        self.user_key
-            .cmp(&other.user_key)
+        self.comparator
+            .compare(&self.user_key, &other.user_key)
             .then(self.seq.cmp(&other.seq).reverse())
     }
 }
@@ -71,14 +95,22 @@ impl RangeBounds<SequencedKey> for KVTableInternalKeyRange {
 /// `(key001, u64::MAX) ..= (key001, 0)`.
 impl<T: RangeBounds<Bytes>> From<T> for KVTableInternalKeyRange {
     fn from(range: T) -> Self {
+        Self::new_by(range, &KeyComparator::default())
+    }
+}
+
+impl KVTableInternalKeyRange {
+    /// Converts a user key range to an internal key range of a table ordered by `comparator`.
+    pub(crate) fn new_by<T: RangeBounds<Bytes>>(range: T, comparator: &KeyComparator) -> Self {
+        let key = |key: &Bytes, seq| SequencedKey::new_by(key.clone(), seq, comparator);
         let start_bound = match range.start_bound() {
-            Bound::Included(key) => Bound::Included(SequencedKey::new(key.clone(), u64::MAX)),
-            Bound::Excluded(key) => Bound::Excluded(SequencedKey::new(key.clone(), 0)),
+            Bound::Included(k) => Bound::Included(key(k, u64::MAX)),
+            Bound::Excluded(k) => Bound::Excluded(key(k, 0)),
             Bound::Unbounded => Bound::Unbounded,
         };
         let end_bound = match range.end_bound() {
-            Bound::Included(key) => Bound::Included(SequencedKey::new(key.clone(), 0)),
-            Bound::Excluded(key) => Bound::Excluded(SequencedKey::new(key.clone(), u64::MAX)),
+            Bound::Included(k) => Bound::Included(key(k, 0)),
+            Bound::Excluded(k) => Bound::Excluded(key(k, u64::MAX)),
             Bound::Unbounded => Bound::Unbounded,
         };
         Self {
@@ -90,6 +122,11 @@ impl<T: RangeBounds<Bytes>> From<T> for KVTableInternalKeyRange {
 
 pub(crate) struct KVTable {
     map: Arc<SkipMap<SequencedKey, RowEntry>>,
+    /// The order of the keys of the table.
+    comparator: KeyComparator,
+    /// Range tombstones written by `delete_range`. They are kept apart from `map`
+    /// because they cover a key range rather than a single key.
+    range_tombstones: Mutex<Vec<RangeTombstone>>,
     durable: WatchableOnceCell<Result<(), SlateDBError>>,
     entries_size_in_bytes: AtomicUsize,
     /// this corresponds to the timestamp of the most recent
@@ -116,12 +153,35 @@ pub(crate) struct KVTableMetadata {
 
 pub(crate) struct WritableKVTable {
     table: Arc<KVTable>,
+    /// The tables of the named column families, by column family id. The default
+    /// column family is stored in `table`.
+    column_families: BTreeMap<u32, Arc<KVTable>>,
 }
 
 impl WritableKVTable {
     pub(crate) fn new() -> Self {
+        Self::new_with_comparator(&KeyComparator::default())
+    }
+
+    pub(crate) fn new_with_comparator(comparator: &KeyComparator) -> Self {
+        Self::with_column_families([], comparator)
+    }
+
+    pub(crate) fn with_column_families(
+        column_families: impl IntoIterator<Item = u32>,
+        comparator: &KeyComparator,
+    ) -> Self {
         Self {
-            table: Arc::new(KVTable::new()),
+            table: Arc::new(KVTable::new_with_comparator(comparator.clone())),
+            column_families: column_families
+                .into_iter()
+                .map(|id| {
+                    (
+                        id,
+                        Arc::new(KVTable::new_with_comparator(comparator.clone())),
+                    )
+                })
+                .collect(),
         }
     }
 
@@ -129,16 +189,41 @@ impl WritableKVTable {
         &self.table
     }
 
+    pub(crate) fn column_families(&self) -> &BTreeMap<u32, Arc<KVTable>> {
+        &self.column_families
+    }
+
     pub(crate) fn put(&self, row: RowEntry) {
         self.table.put(row);
     }
 
+    /// Put a row into the table of a named column family. Rows of column families
+    /// the table was not created with are dropped.
+    pub(crate) fn put_column_family(&self, column_family: u32, row: RowEntry) {
+        if let Some(table) = self.column_families.get(&column_family) {
+            table.put(row);
+        }
+    }
+
+    pub(crate) fn put_range_tombstone(&self, tombstone: RangeTombstone) {
+        self.table.put_range_tombstone(tombstone);
+    }
+
+    /// Returns the metadata of all column families combined.
     pub(crate) fn metadata(&self) -> KVTableMetadata {
-        self.table.metadata()
+        self.column_families
+            .values()
+            .map(|table| table.metadata())
+            .fold(self.table.metadata(), |acc, meta| KVTableMetadata {
+                entry_num: acc.entry_num + meta.entry_num,
+                entries_size_in_bytes: acc.entries_size_in_bytes + meta.entries_size_in_bytes,
+                last_tick: acc.last_tick.max(meta.last_tick),
+                last_seq: acc.last_seq.max(meta.last_seq),
+            })
     }
 
     pub(crate) fn is_empty(&self) -> bool {
-        self.table.is_empty()
+        self.table.is_empty() && self.column_families.values().all(|table| table.is_empty())
     }
 
     pub(crate) fn record_sequence(&self, seq: u64, ts: DateTime<Utc>) {
@@ -157,6 +242,7 @@ pub(crate) struct ImmutableMemtable {
     /// that already contained in the last L0 SST.
     recent_flushed_wal_id: u64,
     table: Arc<KVTable>,
+    column_families: BTreeMap<u32, Arc<KVTable>>,
     /// This flushed watchable cell is useful for users who enable `await_durable` on the writes.
     flushed: WatchableOnceCell<Result<(), SlateDBError>>,
     /// A snapshot of the sequence tracker taken when this immutable memtable was created.
@@ -172,6 +258,7 @@ pub(crate) struct MemTableIteratorInner<T: RangeBounds<SequencedKey>> {
     #[not_covariant]
     inner: Range<'this, SequencedKey, T, SequencedKey, RowEntry>,
     ordering: IterationOrder,
+    comparator: KeyComparator,
     item: Option<RowEntry>,
 }
 pub(crate) type MemTableIterator = MemTableIteratorInner<KVTableInternalKeyRange>;
@@ -187,9 +274,14 @@ impl KeyValueIterator for MemTableIterator {
     }
 
     async fn seek(&mut self, next_key: &[u8]) -> Result<(), SlateDBError> {
+        let ordering = *self.borrow_ordering();
+        let comparator = self.borrow_comparator().clone();
         loop {
             let front = self.borrow_item().clone();
-            if front.is_some_and(|record| record.key < next_key) {
+            if front.is_some_and(|record| match ordering {
+                IterationOrder::Ascending => comparator.compare(&record.key, next_key).is_lt(),
+                IterationOrder::Descending => comparator.compare(&record.key, next_key).is_gt(),
+            }) {
                 self.next_entry_sync();
             } else {
                 return Ok(());
@@ -218,6 +310,7 @@ impl ImmutableMemtable {
         let sequence_tracker = table.table.sequence_tracker_snapshot();
         Self {
             table: table.table,
+            column_families: table.column_families,
             recent_flushed_wal_id,
             flushed: WatchableOnceCell::new(),
             sequence_tracker,
@@ -228,6 +321,36 @@ impl ImmutableMemtable {
         self.table.clone()
     }
 
+    /// Returns the table of a column family, or None if the memtable has no table for it.
+    pub(crate) fn column_family_table(&self, column_family: u32) -> Option<Arc<KVTable>> {
+        if column_family == DEFAULT_COLUMN_FAMILY_ID {
+            return Some(self.table());
+        }
+        self.column_families.get(&column_family).cloned()
+    }
+
+    /// Returns the tables of the named column families, by column family id.
+    pub(crate) fn column_families(&self) -> &BTreeMap<u32, Arc<KVTable>> {
+        &self.column_families
+    }
+
+    /// Returns the sequence number of the most recent write to any column family.
+    pub(crate) fn last_seq(&self) -> Option<u64> {
+        std::iter::once(&self.table)
+            .chain(self.column_families.values())
+            .filter_map(|table| table.last_seq())
+            .max()
+    }
+
+    /// Returns the tick of the most recent write to any column family.
+    pub(crate) fn last_tick(&self) -> i64 {
+        std::iter::once(&self.table)
+            .chain(self.column_families.values())
+            .map(|table| table.last_tick())
+            .max()
+            .unwrap_or(i64::MIN)
+    }
+
     pub(crate) fn recent_flushed_wal_id(&self) -> u64 {
         self.recent_flushed_wal_id
     }
@@ -247,8 +370,14 @@ impl ImmutableMemtable {
 
 impl KVTable {
     pub(crate) fn new() -> Self {
+        Self::new_with_comparator(KeyComparator::default())
+    }
+
+    pub(crate) fn new_with_comparator(comparator: KeyComparator) -> Self {
         Self {
             map: Arc::new(SkipMap::new()),
+            comparator,
+            range_tombstones: Mutex::new(Vec::new()),
             entries_size_in_bytes: AtomicUsize::new(0),
             durable: WatchableOnceCell::new(),
             last_tick: AtomicI64::new(i64::MIN),
@@ -271,7 +400,7 @@ impl KVTable {
     }
 
     pub(crate) fn is_empty(&self) -> bool {
-        self.map.is_empty()
+        self.map.is_empty() && self.range_tombstones.lock().is_empty()
     }
 
     pub(crate) fn last_tick(&self) -> i64 {
@@ -300,11 +429,12 @@ impl KVTable {
         range: T,
         ordering: IterationOrder,
     ) -> MemTableIterator {
-        let internal_range = KVTableInternalKeyRange::from(range);
+        let internal_range = KVTableInternalKeyRange::new_by(range, &self.comparator);
         let mut iterator = MemTableIteratorInnerBuilder {
             map: self.map.clone(),
             inner_builder: |map| map.range(internal_range),
             ordering,
+            comparator: self.comparator.clone(),
             item: None,
         }
         .build();
@@ -313,7 +443,7 @@ impl KVTable {
     }
 
     pub(crate) fn put(&self, row: RowEntry) {
-        let internal_key = SequencedKey::new(row.key.clone(), row.seq);
+        let internal_key = SequencedKey::new_by(row.key.clone(), row.seq, &self.comparator);
         let previous_size = Cell::new(None);
 
         // it is safe to use fetch_max here to update the last tick
@@ -346,6 +476,18 @@ impl KVTable {
         }
     }
 
+    pub(crate) fn put_range_tombstone(&self, tombstone: RangeTombstone) {
+        self.last_tick
+            .fetch_max(tombstone.create_ts, atomic::Ordering::SeqCst);
+        self.last_seq
+            .fetch_max(tombstone.seq, atomic::Ordering::SeqCst);
+        self.range_tombstones.lock().push(tombstone);
+    }
+
+    pub(crate) fn range_tombstones(&self) -> Vec<RangeTombstone> {
+        self.range_tombstones.lock().clone()
+    }
+
     pub(crate) fn durable_watcher(&self) -> WatchableOnceCellReader<Result<(), SlateDBError>> {
         self.durable.reader()
     }
@@ -374,6 +516,7 @@ mod tests {
 
     use super::*;
     use crate::bytes_range::BytesRange;
+    use crate::comparator::tests::reverse_comparator;
     use crate::merge_iterator::MergeIterator;
     use crate::proptest_util::{arbitrary, sample};
     use crate::test_utils::assert_iterator;
@@ -405,6 +548,31 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_memtable_range_with_comparator() {
+        let table = WritableKVTable::new_with_comparator(&reverse_comparator());
+        table.put(RowEntry::new_value(b"abc333", b"value3", 1));
+        table.put(RowEntry::new_value(b"abc111", b"value1", 2));
+        table.put(RowEntry::new_value(b"abc555", b"value5", 3));
+        table.put(RowEntry::new_value(b"abc333", b"value3-new", 4));
+
+        let range = BytesRange::new_by(
+            Bound::Included(Bytes::from_static(b"abc444")),
+            Bound::Unbounded,
+            &reverse_comparator(),
+        );
+        let mut iter = table.table().range_ascending(range);
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"abc333", b"value3-new", 4),
+                RowEntry::new_value(b"abc333", b"value3", 1),
+                RowEntry::new_value(b"abc111", b"value1", 2),
+            ],
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn test_memtable_iter_entry_attrs() {
         let table = WritableKVTable::new();
@@ -467,6 +635,28 @@ mod tests {
         .await;
     }
 
+    #[tokio::test]
+    async fn test_memtable_seek_descending() {
+        let table = WritableKVTable::new();
+        table.put(RowEntry::new_value(b"abc333", b"value3", 1));
+        table.put(RowEntry::new_value(b"abc111", b"value1", 2));
+        table.put(RowEntry::new_value(b"abc555", b"value5", 3));
+        table.put(RowEntry::new_value(b"abc444", b"value4", 4));
+        table.put(RowEntry::new_value(b"abc222", b"value2", 5));
+
+        let mut iter = table.table().range(.., IterationOrder::Descending);
+        iter.seek(b"abc334").await.unwrap();
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"abc333", b"value3", 1),
+                RowEntry::new_value(b"abc222", b"value2", 5),
+                RowEntry::new_value(b"abc111", b"value1", 2),
+            ],
+        )
+        .await;
+    }
+
     #[tokio::test]
     async fn test_memtable_iter_delete() {
         let table = WritableKVTable::new();
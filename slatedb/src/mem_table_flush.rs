@@ -1,10 +1,14 @@
 use crate::checkpoint::CheckpointCreateResult;
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
 use crate::config::CheckpointOptions;
 use crate::db::DbInner;
-use crate::db_state::SsTableId;
+use crate::db_state::{DbState, SsTableId};
 use crate::dispatcher::{MessageFactory, MessageHandler};
 use crate::error::SlateDBError;
+use crate::event_listener::{ManifestWrittenEvent, MemtableFlushedEvent};
+use crate::ingest::IngestedSst;
 use crate::manifest::store::FenceableManifest;
+use crate::manifest::WriterHandoff;
 use crate::utils::IdGenerator;
 use async_trait::async_trait;
 use fail_parallel::fail_point;
@@ -12,6 +16,8 @@ use futures::stream::BoxStream;
 use futures::StreamExt;
 use log::{debug, error, info, warn};
 use std::cmp;
+use std::collections::{HashMap, HashSet};
+use std::ops::Bound::Unbounded;
 use std::sync::Arc;
 use std::time::Duration;
 use tokio::sync::oneshot::Sender;
@@ -29,16 +35,30 @@ pub(crate) enum MemtableFlushMsg {
         sender: Sender<Result<CheckpointCreateResult, SlateDBError>>,
     },
     PollManifest,
+    IngestSsts {
+        ssts: Vec<IngestedSst>,
+        sender: Sender<Result<(), SlateDBError>>,
+    },
+    WriteHandoff {
+        sender: Sender<Result<(), SlateDBError>>,
+    },
 }
 
 pub(crate) struct MemtableFlusher {
     db_inner: Arc<DbInner>,
     manifest: FenceableManifest,
+    /// The last seq of the L0 SSTs of the default column family added by this flusher,
+    /// used to find the SSTs written after an ingestion reserved its seqs.
+    l0_last_seqs: HashMap<SsTableId, u64>,
 }
 
 impl MemtableFlusher {
     pub(crate) fn new(db_inner: Arc<DbInner>, manifest: FenceableManifest) -> Self {
-        Self { db_inner, manifest }
+        Self {
+            db_inner,
+            manifest,
+            l0_last_seqs: HashMap::new(),
+        }
     }
 
     pub(crate) async fn load_manifest(&mut self) -> Result<(), SlateDBError> {
@@ -61,6 +81,7 @@ impl MemtableFlusher {
         let manifest_id = checkpoint.manifest_id;
         dirty.value.core.checkpoints.push(checkpoint);
         self.manifest.update(dirty).await?;
+        self.notify_manifest_written();
         Ok(CheckpointCreateResult { id, manifest_id })
     }
 
@@ -69,7 +90,18 @@ impl MemtableFlusher {
             let rguard_state = self.db_inner.state.read();
             rguard_state.state().manifest.clone()
         };
-        self.manifest.update(dirty).await
+        self.manifest.update(dirty).await?;
+        self.notify_manifest_written();
+        Ok(())
+    }
+
+    fn notify_manifest_written(&self) {
+        let event = ManifestWrittenEvent {
+            id: self.manifest.id(),
+        };
+        self.db_inner
+            .event_listeners
+            .notify(|l| l.on_manifest_written(&event));
     }
 
     pub(crate) async fn write_checkpoint_safely(
@@ -99,15 +131,40 @@ impl MemtableFlusher {
         }
     }
 
+    /// Flushes the remaining immutable memtables to L0 and writes the handoff marker of
+    /// this writer to the manifest.
+    async fn write_handoff(&mut self) -> Result<(), SlateDBError> {
+        self.flush_and_record().await?;
+        {
+            let mut wguard_state = self.db_inner.state.write();
+            wguard_state.modify(|modifier| {
+                let manifest = &mut modifier.state.manifest.value;
+                manifest.writer_handoff = Some(WriterHandoff {
+                    writer_epoch: manifest.writer_epoch,
+                    last_seq: manifest.core.last_l0_seq,
+                });
+            });
+        }
+        self.write_manifest_safely().await?;
+        info!(
+            "wrote writer handoff to manifest [manifest_id={}]",
+            self.manifest.id()
+        );
+        Ok(())
+    }
+
     #[instrument(level = "trace", skip_all)]
     async fn flush_imm_memtables_to_l0(&mut self) -> Result<(), SlateDBError> {
         while let Some(imm_memtable) = {
             let rguard = self.db_inner.state.read();
-            if rguard.state().core().l0.len() >= self.db_inner.settings.l0_max_ssts {
+            let state = rguard.state();
+            // the L0 of every column family is bounded by l0_max_ssts
+            let l0_len = state.core().max_l0_len();
+            if l0_len >= self.db_inner.settings.read().l0_max_ssts {
                 warn!(
                     "won't flush imm to l0 because too many l0 files [l0_len={}, l0_max_ssts={}]",
-                    rguard.state().core().l0.len(),
-                    self.db_inner.settings.l0_max_ssts
+                    l0_len,
+                    self.db_inner.settings.read().l0_max_ssts
                 );
                 rguard.state().core().log_db_runs();
                 None
@@ -115,22 +172,54 @@ impl MemtableFlusher {
                 rguard.state().imm_memtable.back().cloned()
             }
         } {
-            let id = SsTableId::Compacted(
-                self.db_inner
-                    .rand
-                    .rng()
-                    .gen_ulid(self.db_inner.system_clock.as_ref()),
+            // each column family with data is flushed to an SST in its own L0. the
+            // default column family is always flushed unless only other column
+            // families have data.
+            let mut tables = Vec::new();
+            let column_families_empty = imm_memtable
+                .column_families()
+                .values()
+                .all(|table| table.is_empty());
+            if !imm_memtable.table().is_empty() || column_families_empty {
+                tables.push((DEFAULT_COLUMN_FAMILY_ID, imm_memtable.table()));
+            }
+            tables.extend(
+                imm_memtable
+                    .column_families()
+                    .iter()
+                    .filter(|(_, table)| !table.is_empty())
+                    .map(|(id, table)| (*id, table.clone())),
             );
-            let sst_handle = self
+            let mut sst_handles = Vec::with_capacity(tables.len());
+            let timer = self
                 .db_inner
-                .flush_imm_table(&id, imm_memtable.table(), true)
-                .await?;
+                .db_stats
+                .memtable_flush_latency_us
+                .start_timer(self.db_inner.system_clock.as_ref());
+            for (column_family, table) in tables {
+                let id = SsTableId::Compacted(
+                    self.db_inner
+                        .rand
+                        .rng()
+                        .gen_ulid(self.db_inner.system_clock.as_ref()),
+                );
+                let sst_handle = match self.db_inner.column_families.options(column_family) {
+                    Some(options) => {
+                        let sst_format = options.sst_format(self.db_inner.table_store.sst_format());
+                        self.db_inner
+                            .flush_imm_table_with_format(&id, table, &sst_format, true)
+                            .await?
+                    }
+                    None => self.db_inner.flush_imm_table(&id, table, true).await?,
+                };
+                sst_handles.push((column_family, sst_handle));
+            }
+            drop(timer);
             fail_point!(
                 Arc::clone(&self.db_inner.fp_registry),
                 "after-flush-imm-to-l0-before-manifest"
             );
             let last_seq = imm_memtable
-                .table()
                 .last_seq()
                 .expect("flush of l0 with no entries");
             {
@@ -144,12 +233,19 @@ impl MemtableFlusher {
                         .pop_back()
                         .expect("expected imm memtable");
                     assert!(Arc::ptr_eq(&popped, &imm_memtable));
-                    modifier.state.manifest.value.core.l0.push_front(sst_handle);
+                    for (column_family, sst_handle) in sst_handles.iter() {
+                        let core = &mut modifier.state.manifest.value.core;
+                        if *column_family == DEFAULT_COLUMN_FAMILY_ID {
+                            core.l0.push_front(sst_handle.clone());
+                        } else if let Some(cf) = core.column_family_mut(*column_family) {
+                            cf.l0.push_front(sst_handle.clone());
+                        }
+                    }
                     modifier.state.manifest.value.core.replay_after_wal_id =
                         imm_memtable.recent_flushed_wal_id();
 
                     // ensure the persisted manifest tick never goes backwards in time
-                    let memtable_tick = imm_memtable.table().last_tick();
+                    let memtable_tick = imm_memtable.last_tick();
                     modifier.state.manifest.value.core.last_l0_clock_tick = cmp::max(
                         modifier.state.manifest.value.core.last_l0_clock_tick,
                         memtable_tick,
@@ -182,6 +278,12 @@ impl MemtableFlusher {
 
                     Ok(())
                 })?;
+                for (column_family, sst_handle) in sst_handles.iter() {
+                    if *column_family == DEFAULT_COLUMN_FAMILY_ID {
+                        self.l0_last_seqs.insert(sst_handle.id, last_seq);
+                    }
+                }
+                Self::retain_l0_last_seqs(&mut self.l0_last_seqs, &guard);
             }
             imm_memtable.notify_flush_to_l0(Ok(()));
             self.db_inner.db_stats.immutable_memtable_flushes.inc();
@@ -194,14 +296,32 @@ impl MemtableFlusher {
                         .oracle
                         .last_remote_persisted_seq
                         .store_if_greater(last_seq);
+                    let event = MemtableFlushedEvent {
+                        ssts: sst_handles
+                            .iter()
+                            .map(|(_, sst_handle)| sst_handle.id.unwrap_compacted_id())
+                            .collect(),
+                        bytes: sst_handles
+                            .iter()
+                            .map(|(_, sst_handle)| sst_handle.estimate_size())
+                            .sum(),
+                        last_seq,
+                    };
+                    self.db_inner
+                        .event_listeners
+                        .notify(|l| l.on_memtable_flushed(&event));
                 }
                 Err(err) => {
                     if matches!(err, SlateDBError::Fenced) {
-                        if let Err(delete_err) = self.db_inner.table_store.delete_sst(&id).await {
-                            warn!(
-                                "failed to delete fenced SST [id={:?}, error={:?}]",
-                                id, delete_err
-                            );
+                        for (_, sst_handle) in sst_handles.iter() {
+                            let id = &sst_handle.id;
+                            if let Err(delete_err) = self.db_inner.table_store.delete_sst(id).await
+                            {
+                                warn!(
+                                    "failed to delete fenced SST [id={:?}, error={:?}]",
+                                    id, delete_err
+                                );
+                            }
                         }
                         // refresh manifest and state so that local state reflects remote
                         self.load_manifest().await?;
@@ -214,6 +334,86 @@ impl MemtableFlusher {
         Ok(())
     }
 
+    /// Adds SSTs rewritten by an ingestion to L0, unless they overlap data written after
+    /// the ingestion reserved their seqs. Every write with a lower seq is already in L0.
+    async fn ingest_ssts(&mut self, ssts: &[IngestedSst]) -> Result<(), SlateDBError> {
+        let max_seq = ssts
+            .iter()
+            .map(|sst| sst.seq)
+            .max()
+            .expect("ingestion with no SSTs");
+        {
+            let mut guard = self.db_inner.state.write();
+            if let Some(sst) = ssts
+                .iter()
+                .find(|sst| self.overlaps_newer_data(&guard, sst))
+            {
+                return Err(SlateDBError::IngestConflict(sst.path.clone()));
+            }
+            guard.modify(|modifier| {
+                let core = &mut modifier.state.manifest.value.core;
+                for sst in ssts {
+                    core.l0.push_front(sst.handle.clone());
+                }
+                // the ingested seqs must not be reused after a restart. it's safe to skip
+                // them during WAL replay since all older writes are already in L0.
+                core.last_l0_seq = cmp::max(core.last_l0_seq, max_seq);
+            });
+            for sst in ssts {
+                self.l0_last_seqs.insert(sst.handle.id, sst.seq);
+            }
+            Self::retain_l0_last_seqs(&mut self.l0_last_seqs, &guard);
+        }
+        match self.write_manifest_safely().await {
+            Ok(_) => {
+                let oracle = &self.db_inner.oracle;
+                oracle.last_committed_seq.store_if_greater(max_seq);
+                oracle.last_remote_persisted_seq.store_if_greater(max_seq);
+                Ok(())
+            }
+            Err(err) => {
+                if matches!(err, SlateDBError::Fenced) {
+                    self.load_manifest().await?;
+                }
+                Err(err)
+            }
+        }
+    }
+
+    /// Returns true if the SST overlaps a row with a higher seq, which is either in a
+    /// memtable or in an L0 SST added after the SST's seq was reserved.
+    fn overlaps_newer_data(&self, state: &DbState, sst: &IngestedSst) -> bool {
+        let cow_state = state.state();
+        let comparator = self.db_inner.table_store.comparator();
+        let mut tables = vec![state.memtable().table().clone()];
+        tables.extend(cow_state.imm_memtable.iter().map(|imm| imm.table()));
+        sst.ranges.iter().any(|range| {
+            let in_memtables = tables.iter().any(|table| {
+                table
+                    .range_ascending(range.clone())
+                    .next_entry_sync()
+                    .is_some()
+                    || table
+                        .range_tombstones()
+                        .iter()
+                        .any(|tombstone| tombstone.range.intersect(range).is_some())
+            });
+            let in_l0 = cow_state.core().l0.iter().any(|l0| {
+                self.l0_last_seqs
+                    .get(&l0.id)
+                    .is_some_and(|last_seq| *last_seq > sst.seq)
+                    && l0.intersects_range(Unbounded, range, comparator)
+            });
+            in_memtables || in_l0
+        })
+    }
+
+    /// Forgets the last seqs of SSTs that are no longer in L0.
+    fn retain_l0_last_seqs(l0_last_seqs: &mut HashMap<SsTableId, u64>, state: &DbState) {
+        let l0: HashSet<_> = state.state().core().l0.iter().map(|sst| sst.id).collect();
+        l0_last_seqs.retain(|id, _| l0.contains(id));
+    }
+
     async fn flush_and_record(&mut self) -> Result<(), SlateDBError> {
         fail_point!(
             Arc::clone(&self.db_inner.fp_registry),
@@ -231,7 +431,7 @@ impl MemtableFlusher {
 impl MessageHandler<MemtableFlushMsg> for MemtableFlusher {
     fn tickers(&mut self) -> Vec<(Duration, Box<MessageFactory<MemtableFlushMsg>>)> {
         vec![(
-            self.db_inner.settings.manifest_poll_interval,
+            self.db_inner.settings.read().manifest_poll_interval,
             Box::new(|| MemtableFlushMsg::PollManifest),
         )]
     }
@@ -259,6 +459,38 @@ impl MessageHandler<MemtableFlushMsg> for MemtableFlusher {
                 }
                 write_result.map(|_| ())
             }
+            MemtableFlushMsg::IngestSsts { ssts, sender } => {
+                let result = self.ingest_ssts(&ssts).await;
+                if matches!(
+                    result,
+                    Err(SlateDBError::IngestConflict(_) | SlateDBError::Fenced)
+                ) {
+                    for sst in ssts.iter() {
+                        let id = &sst.handle.id;
+                        if let Err(delete_err) = self.db_inner.table_store.delete_sst(id).await {
+                            warn!(
+                                "failed to delete ingested SST [id={:?}, error={:?}]",
+                                id, delete_err
+                            );
+                        }
+                    }
+                }
+                if let Err(Err(e)) = sender.send(result.clone()) {
+                    error!("failed to send ingestion result [error={:?}]", e);
+                }
+                // a conflict only fails the ingestion
+                match result {
+                    Err(SlateDBError::IngestConflict(_)) => Ok(()),
+                    result => result,
+                }
+            }
+            MemtableFlushMsg::WriteHandoff { sender } => {
+                let result = self.write_handoff().await;
+                if let Err(Err(e)) = sender.send(result.clone()) {
+                    error!("failed to send handoff result [error={:?}]", e);
+                }
+                result
+            }
         }
     }
 
@@ -274,6 +506,12 @@ impl MessageHandler<MemtableFlushMsg> for MemtableFlusher {
                 MemtableFlushMsg::CreateCheckpoint { options: _, sender } => {
                     let _ = sender.send(Err(error.clone()));
                 }
+                MemtableFlushMsg::IngestSsts { ssts: _, sender } => {
+                    let _ = sender.send(Err(error.clone()));
+                }
+                MemtableFlushMsg::WriteHandoff { sender } => {
+                    let _ = sender.send(Err(error.clone()));
+                }
                 MemtableFlushMsg::FlushImmutableMemtables {
                     sender: Some(sender),
                 } => {
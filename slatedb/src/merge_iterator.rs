@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 
+use crate::comparator::KeyComparator;
 use crate::error::SlateDBError;
-use crate::iter::KeyValueIterator;
+use crate::iter::{IterationOrder, KeyValueIterator};
 use crate::types::{RowEntry, ValueDeletable};
 use std::cmp::{Ordering, Reverse};
 use std::collections::{BinaryHeap, VecDeque};
@@ -10,6 +11,8 @@ struct MergeIteratorHeapEntry<'a> {
     next_kv: RowEntry,
     index: usize,
     iterator: Box<dyn KeyValueIterator + 'a>,
+    order: IterationOrder,
+    comparator: KeyComparator,
 }
 
 impl<'a> MergeIteratorHeapEntry<'a> {
@@ -18,7 +21,12 @@ impl<'a> MergeIteratorHeapEntry<'a> {
         mut self,
         next_key: &[u8],
     ) -> Result<Option<MergeIteratorHeapEntry<'a>>, SlateDBError> {
-        if self.next_kv.key >= next_key {
+        let key_ord = self.comparator.compare(&self.next_kv.key, next_key);
+        let at_or_after_next_key = match self.order {
+            IterationOrder::Ascending => key_ord.is_ge(),
+            IterationOrder::Descending => key_ord.is_le(),
+        };
+        if at_or_after_next_key {
             Ok(Some(self))
         } else {
             self.iterator.seek(next_key).await?;
@@ -27,6 +35,8 @@ impl<'a> MergeIteratorHeapEntry<'a> {
                     next_kv,
                     index: self.index,
                     iterator: self.iterator,
+                    order: self.order,
+                    comparator: self.comparator,
                 }))
             } else {
                 Ok(None)
@@ -53,10 +63,19 @@ impl Ord for MergeIteratorHeapEntry<'_> {
     fn cmp(&self, other: &Self) -> Ordering {
         // we'll wrap a Reverse in the BinaryHeap, so the cmp here is in increasing order.
         // the desired behavior is to return the entires with the lowest key first across keys
-        // but the highest seqnum first within a key.
-        match self.next_kv.key.cmp(&other.next_kv.key) {
+        // (or the highest key first when iterating in descending order) but the highest
+        // seqnum first within a key.
+        let key_ord = match self.order {
+            IterationOrder::Ascending => self
+                .comparator
+                .compare(&self.next_kv.key, &other.next_kv.key),
+            IterationOrder::Descending => self
+                .comparator
+                .compare(&other.next_kv.key, &self.next_kv.key),
+        };
+        match key_ord {
             Ordering::Equal => other.next_kv.seq.cmp(&self.next_kv.seq), // descending seq
-            ord => ord,                                                  // ascending key
+            ord => ord,
         }
     }
 }
@@ -72,6 +91,11 @@ pub(crate) struct MergeIterator<'a> {
     /// default, but it is useful to disable when we want to have some merge logics during
     /// compaction.
     dedup: bool,
+    /// The order in which the merged iterators return keys. All iterators must use the
+    /// same order, returning the versions of each key from newest to oldest.
+    order: IterationOrder,
+    /// The order of the keys returned by the merged iterators.
+    comparator: KeyComparator,
     /// Tracks whether the iterator has performed its heavy initialization step.
     initialized: bool,
 }
@@ -91,6 +115,8 @@ impl<'a> MergeIterator<'a> {
                 })
                 .collect(),
             dedup: true,
+            order: IterationOrder::Ascending,
+            comparator: KeyComparator::default(),
             initialized: false,
         })
     }
@@ -100,6 +126,16 @@ impl<'a> MergeIterator<'a> {
         self
     }
 
+    pub(crate) fn with_order(mut self, order: IterationOrder) -> Self {
+        self.order = order;
+        self
+    }
+
+    pub(crate) fn with_comparator(mut self, comparator: KeyComparator) -> Self {
+        self.comparator = comparator;
+        self
+    }
+
     async fn initialize(&mut self) -> Result<(), SlateDBError> {
         if self.initialized {
             return Ok(());
@@ -112,6 +148,8 @@ impl<'a> MergeIterator<'a> {
                     next_kv,
                     index,
                     iterator,
+                    order: self.order,
+                    comparator: self.comparator.clone(),
                 }));
             }
         }
@@ -165,9 +203,10 @@ impl KeyValueIterator for MergeIterator<'_> {
             None => return Ok(None),
         };
 
-        // the iterators are stored in order of increasing key and decreasing
-        // seqnum, which means that the first entry in the heap is the one with
-        // the highest seqnum for a given key. we want to advance other iterators
+        // the iterators are stored in key order (increasing or decreasing, depending
+        // on the iteration order) and decreasing seqnum, which means that the first
+        // entry in the heap is the one with the highest seqnum for a given key. we
+        // want to advance other iterators
         // to skip their current value if the current value is not a merge oepration
         // (we can ignore merge values after seeing the first non-merge value
         // because tombstones/values serve as "barriers" in the merge operation)
@@ -7,6 +7,7 @@ use thiserror::Error;
 use crate::{
     error::SlateDBError,
     iter::KeyValueIterator,
+    tablestore::TableStore,
     types::{RowEntry, ValueDeletable},
     utils::{is_not_expired, merge_options},
 };
@@ -179,6 +180,9 @@ pub(crate) struct MergeOperatorIterator<T: KeyValueIterator> {
     /// Whether to merge entries with different expire timestamps.
     merge_different_expire_ts: bool,
     now: i64,
+    /// Reads base values that are still blob refs. Only compactions leave blob refs
+    /// unresolved, so that values that are not merged stay in their blob files.
+    table_store: Option<Arc<TableStore>>,
 }
 
 /// Tracks metadata across multiple entries during merge operations.
@@ -247,6 +251,15 @@ impl<T: KeyValueIterator> MergeOperatorIterator<T> {
             buffered_entry: None,
             merge_different_expire_ts,
             now,
+            table_store: None,
+        }
+    }
+
+    /// Reads the base values that are blob refs from the given table store.
+    pub(crate) fn with_table_store(self, table_store: Arc<TableStore>) -> Self {
+        Self {
+            table_store: Some(table_store),
+            ..self
         }
     }
 }
@@ -343,7 +356,12 @@ impl<T: KeyValueIterator> MergeOperatorIterator<T> {
             results.push(self.process_batch(&key, &mut batch, &mut merge_tracker)?);
         }
 
-        let base_value = base.as_ref().and_then(|b| b.value.as_bytes());
+        let base_value = match (base.as_ref().map(|b| &b.value), &self.table_store) {
+            (Some(ValueDeletable::BlobRef(blob_ref)), Some(table_store)) => {
+                Some(table_store.read_blob_value(blob_ref).await?)
+            }
+            _ => base.as_ref().and_then(|b| b.value.as_bytes()),
+        };
         let found_base = base.is_some();
 
         // If we have no results and either no base or a tombstone base, return None
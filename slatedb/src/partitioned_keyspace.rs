@@ -1,9 +1,11 @@
+use crate::comparator::KeyComparator;
+
 /// Represents a set of keys that are partitioned into multiple partitions, where each
 /// partition stores some range of keys and the keys in a given partition are greater than
 /// or equal to all keys from the previous partitions. The space is a multiset, so a given key
 /// can be present in multiple contiguous partitions. Each partition specifies a min key that
 /// is less than or equal to all its keys, and greater than or equal to all keys in the
-/// previous partition.
+/// previous partition. Keys are ordered by the comparator passed to the functions below.
 pub(crate) trait RangePartitionedKeySpace {
     fn partitions(&self) -> usize;
 
@@ -41,9 +43,10 @@ fn partition_point<T: RangePartitionedKeySpace, P: Fn(&[u8]) -> bool>(
 pub(crate) fn first_partition_including_or_after_key<T: RangePartitionedKeySpace>(
     keyspace: &T,
     key: &[u8],
+    comparator: &KeyComparator,
 ) -> usize {
     // If the whole keyspace is larger than the key, then just return the first partition
-    first_partition_including_key(keyspace, key).unwrap_or(0)
+    first_partition_including_key(keyspace, key, comparator).unwrap_or(0)
 }
 
 /// Returns the first partition that could include the given key. Returns None if all partitions
@@ -51,8 +54,11 @@ pub(crate) fn first_partition_including_or_after_key<T: RangePartitionedKeySpace
 pub(crate) fn first_partition_including_key<T: RangePartitionedKeySpace>(
     keyspace: &T,
     key: &[u8],
+    comparator: &KeyComparator,
 ) -> Option<usize> {
-    let part_point = partition_point(keyspace, |first_key| first_key < key);
+    let part_point = partition_point(keyspace, |first_key| {
+        comparator.compare(first_key, key).is_lt()
+    });
     if part_point > 0 {
         // Some partition after the first has first_key >= key, so return the previous partition
         return Some(part_point - 1);
@@ -70,8 +76,11 @@ pub(crate) fn first_partition_including_key<T: RangePartitionedKeySpace>(
 pub(crate) fn last_partition_including_key<T: RangePartitionedKeySpace>(
     keyspace: &T,
     key: &[u8],
+    comparator: &KeyComparator,
 ) -> Option<usize> {
-    let part_point = partition_point(keyspace, |first_key| first_key <= key);
+    let part_point = partition_point(keyspace, |first_key| {
+        comparator.compare(first_key, key).is_le()
+    });
     if part_point == 0 {
         // If the partition point is 0, that means the first partition's first_key is strictly
         // greater than the key, so no partitions include the key.
@@ -82,6 +91,7 @@ pub(crate) fn last_partition_including_key<T: RangePartitionedKeySpace>(
 
 #[cfg(test)]
 mod tests {
+    use crate::comparator::KeyComparator;
     use crate::partitioned_keyspace::{
         first_partition_including_key, last_partition_including_key, partition_point,
         RangePartitionedKeySpace,
@@ -133,7 +143,8 @@ mod tests {
         };
 
         // do a search where the key is earlier than the first key
-        let found_partition = first_partition_including_key(&keyspace, b"\x00\x00\x00");
+        let found_partition =
+            first_partition_including_key(&keyspace, b"\x00\x00\x00", &KeyComparator::default());
         assert_eq!(None, found_partition);
 
         let mut prev_key = None;
@@ -141,7 +152,8 @@ mod tests {
         for i in 0..first_keys.len() {
             // do a search where the key matches this partition's first key
             let key = first_keys[i];
-            let found_partition = first_partition_including_key(&keyspace, key);
+            let found_partition =
+                first_partition_including_key(&keyspace, key, &KeyComparator::default());
             expected_found_partition = match prev_key {
                 Some(prev_key) if prev_key == key => expected_found_partition,
                 _ => {
@@ -161,7 +173,8 @@ mod tests {
                 // we could do something more robust here, but its fine since the test cases are
                 // static.
                 let key = [key, b"bla"].concat();
-                let found_partition = first_partition_including_key(&keyspace, &key);
+                let found_partition =
+                    first_partition_including_key(&keyspace, &key, &KeyComparator::default());
                 assert_eq!(Some(i), found_partition);
             }
         }
@@ -197,13 +210,15 @@ mod tests {
         };
 
         // do a search where the key is earlier than the first key
-        let found_partition = last_partition_including_key(&keyspace, b"\x00\x00\x00");
+        let found_partition =
+            last_partition_including_key(&keyspace, b"\x00\x00\x00", &KeyComparator::default());
         assert_eq!(None, found_partition);
 
         for i in 0..first_keys.len() {
             // do a search where the key matches this partition's first key
             let key = first_keys[i];
-            let found_partition = last_partition_including_key(&keyspace, key);
+            let found_partition =
+                last_partition_including_key(&keyspace, key, &KeyComparator::default());
             let mut expected_found_partition = i;
             for p in i..first_keys.len() {
                 if keyspace.partition_first_key(p) == key {
@@ -216,7 +231,8 @@ mod tests {
             // where the key is between this partition and the next one
             if i == first_keys.len() - 1 || key != first_keys[i + 1] {
                 let key = [key, b"bla"].concat();
-                let found_partition = last_partition_including_key(&keyspace, &key);
+                let found_partition =
+                    last_partition_including_key(&keyspace, &key, &KeyComparator::default());
                 assert_eq!(Some(i), found_partition);
             }
         }
@@ -8,11 +8,13 @@ use ulid::Ulid;
 
 const WAL_PATH: &str = "wal";
 const COMPACTED_PATH: &str = "compacted";
+const BLOBS_PATH: &str = "blobs";
 
 #[derive(Clone, Debug)]
 pub(crate) struct PathResolver {
     root_path: Path,
     external_ssts: HashMap<SsTableId, Path>,
+    external_blobs: HashMap<Ulid, Path>,
 }
 
 impl PathResolver {
@@ -20,6 +22,7 @@ impl PathResolver {
         Self {
             root_path: root_path.into(),
             external_ssts: HashMap::new(),
+            external_blobs: HashMap::new(),
         }
     }
 
@@ -33,9 +36,23 @@ impl PathResolver {
                 .into_iter()
                 .map(|(k, v)| (k, v.into()))
                 .collect(),
+            external_blobs: HashMap::new(),
         }
     }
 
+    /// Resolves the given blob files to the root of an external database instead of
+    /// this database, e.g. the blob files the SSTs of a clone's parent point to.
+    pub(crate) fn with_external_blobs<P: Into<Path>>(
+        mut self,
+        external_blobs: HashMap<Ulid, P>,
+    ) -> Self {
+        self.external_blobs = external_blobs
+            .into_iter()
+            .map(|(k, v)| (k, v.into()))
+            .collect();
+        self
+    }
+
     pub(crate) fn wal_path(&self) -> Path {
         Path::from(format!("{}/{}/", &self.root_path, WAL_PATH))
     }
@@ -44,6 +61,10 @@ impl PathResolver {
         Path::from(format!("{}/{}/", &self.root_path, COMPACTED_PATH))
     }
 
+    pub(crate) fn blobs_path(&self) -> Path {
+        Path::from(format!("{}/{}/", &self.root_path, BLOBS_PATH))
+    }
+
     pub(crate) fn parse_table_id(&self, path: &Path) -> Result<Option<SsTableId>, SlateDBError> {
         if let Some(mut suffix_iter) = path.prefix_match(&self.root_path) {
             match suffix_iter.next() {
@@ -66,6 +87,30 @@ impl PathResolver {
         }
     }
 
+    pub(crate) fn parse_blob_id(&self, path: &Path) -> Result<Option<Ulid>, SlateDBError> {
+        let Some(mut suffix_iter) = path.prefix_match(&self.root_path) else {
+            return Ok(None);
+        };
+        match suffix_iter.next() {
+            Some(a) if a.as_ref() == BLOBS_PATH => suffix_iter
+                .next()
+                .and_then(|s| s.as_ref().split('.').next().map(Ulid::from_string))
+                .transpose()
+                .map_err(|_| SlateDBError::InvalidDBState),
+            _ => Ok(None),
+        }
+    }
+
+    pub(crate) fn blob_path(&self, blob_id: &Ulid) -> Path {
+        let root_path = self.external_blobs.get(blob_id).unwrap_or(&self.root_path);
+        Path::from(format!(
+            "{}/{}/{}.blob",
+            root_path,
+            BLOBS_PATH,
+            blob_id.to_string()
+        ))
+    }
+
     pub(crate) fn table_path(&self, table_id: &SsTableId) -> Path {
         let root_path = match self.external_ssts.get(table_id) {
             Some(external_path) => external_path,
@@ -106,6 +151,17 @@ mod tests {
             assert_eq!(Some(table_id), parsed_table_id);
         }
 
+        #[test]
+        fn should_serialize_and_deserialize_blob_paths(
+            blob_id in any::<u128>(),
+        ) {
+            let path_resolver = PathResolver::new(Path::from(ROOT));
+            let blob_id = Ulid::from(blob_id);
+            let path = path_resolver.blob_path(&blob_id);
+            let parsed_blob_id = path_resolver.parse_blob_id(&path).unwrap();
+            assert_eq!(Some(blob_id), parsed_blob_id);
+        }
+
         #[test]
         fn should_serialize_and_deserialize_compacted_paths(
             compacted_id in any::<u128>(),
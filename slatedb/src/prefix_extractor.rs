@@ -0,0 +1,94 @@
+use std::sync::Arc;
+
+/// A trait for extracting the prefix of a key that is added to the bloom filters of SSTs.
+///
+/// When the database has a prefix extractor, the prefix of every key written to an L0 SST
+/// or a sorted run is added to the SST's bloom filter alongside the key itself. Scans with
+/// [`crate::config::ScanOptions::prefix`] then skip the SSTs whose filter doesn't contain
+/// the prefix.
+///
+/// Extracted prefixes must be stable under extension: if `prefix(key)` returns `Some(p)`,
+/// then `prefix` must also return `Some(p)` for every key that starts with `key`. A scan
+/// for a prefix `q` is only filtered when `prefix(q)` returns `Some`.
+///
+/// The name of the extractor is recorded in every SST it was used for. SSTs written
+/// without an extractor, or with an extractor of a different name, are never skipped, so
+/// the name must change whenever the prefixes it extracts change.
+///
+/// # Examples
+/// Here's an example of an extractor that extracts `user:<id>:` from keys like
+/// `user:<id>:<field>`:
+/// ```
+/// use slatedb::PrefixExtractor;
+///
+/// struct UserPrefixExtractor;
+///
+/// impl PrefixExtractor for UserPrefixExtractor {
+///     fn name(&self) -> &str {
+///         "user_prefix"
+///     }
+///
+///     fn prefix<'a>(&self, key: &'a [u8]) -> Option<&'a [u8]> {
+///         let rest = key.strip_prefix(b"user:")?;
+///         let end = rest.iter().position(|b| *b == b':')?;
+///         Some(&key[..b"user:".len() + end + 1])
+///     }
+/// }
+/// ```
+pub trait PrefixExtractor {
+    /// Returns the name of the extractor, which is recorded in the SSTs it's used for.
+    fn name(&self) -> &str;
+
+    /// Returns the prefix of `key`, or `None` if `key` has no prefix.
+    ///
+    /// # Arguments
+    /// * `key` - The key to extract the prefix of
+    ///
+    /// # Returns
+    /// * The prefix of `key`, which must be a prefix of `key` itself
+    fn prefix<'a>(&self, key: &'a [u8]) -> Option<&'a [u8]>;
+}
+
+pub(crate) type PrefixExtractorType = Arc<dyn PrefixExtractor + Send + Sync>;
+
+/// A [`PrefixExtractor`] that extracts the first `len` bytes of a key. Keys shorter than
+/// `len` bytes have no prefix.
+#[derive(Clone, Debug)]
+pub struct FixedLengthPrefixExtractor {
+    len: usize,
+    name: String,
+}
+
+impl FixedLengthPrefixExtractor {
+    /// Creates an extractor of the first `len` bytes of keys.
+    pub fn new(len: usize) -> Self {
+        Self {
+            len,
+            name: format!("fixed_length:{}", len),
+        }
+    }
+}
+
+impl PrefixExtractor for FixedLengthPrefixExtractor {
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn prefix<'a>(&self, key: &'a [u8]) -> Option<&'a [u8]> {
+        key.get(..self.len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_fixed_length_prefix_extractor() {
+        let extractor = FixedLengthPrefixExtractor::new(4);
+        assert_eq!(extractor.name(), "fixed_length:4");
+        assert_eq!(extractor.prefix(b"user:123"), Some(b"user".as_ref()));
+        assert_eq!(extractor.prefix(b"user"), Some(b"user".as_ref()));
+        assert_eq!(extractor.prefix(b"use"), None);
+    }
+}
@@ -0,0 +1,265 @@
+use std::collections::HashMap;
+use std::ops::RangeBounds;
+use std::sync::{Arc, OnceLock};
+
+use async_trait::async_trait;
+use serde::Serialize;
+
+use crate::bytes_range::BytesRange;
+use crate::db_state::CoreDbState;
+use crate::error::SlateDBError;
+use crate::iter::KeyValueIterator;
+use crate::types::{RowEntry, ValueDeletable};
+
+/// A tombstone written by `delete_range`. It hides every entry whose key falls
+/// inside `range` and whose sequence number is lower than `seq`.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub(crate) struct RangeTombstone {
+    pub(crate) range: BytesRange,
+    pub(crate) seq: u64,
+    pub(crate) create_ts: i64,
+}
+
+impl RangeTombstone {
+    pub(crate) fn new(range: BytesRange, seq: u64, create_ts: i64) -> Self {
+        Self {
+            range,
+            seq,
+            create_ts,
+        }
+    }
+
+    /// Returns true if the tombstone hides a version of `key` written at `seq`.
+    pub(crate) fn covers(&self, key: &[u8], seq: u64) -> bool {
+        seq < self.seq && self.range.contains(key)
+    }
+}
+
+/// The set of range tombstones visible to a read or a compaction.
+#[derive(Clone, Debug, Default)]
+pub(crate) struct RangeTombstones {
+    tombstones: Vec<RangeTombstone>,
+}
+
+impl RangeTombstones {
+    pub(crate) fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the tombstones that overlap `range` and are visible at `max_seq`.
+    pub(crate) fn extend_visible<'a>(
+        &mut self,
+        tombstones: impl IntoIterator<Item = &'a RangeTombstone>,
+        range: &BytesRange,
+        max_seq: Option<u64>,
+    ) {
+        self.tombstones.extend(
+            tombstones
+                .into_iter()
+                .filter(|t| max_seq.is_none_or(|max_seq| t.seq <= max_seq))
+                .filter(|t| t.range.intersect(range).is_some())
+                .cloned(),
+        );
+    }
+
+    pub(crate) fn push(&mut self, tombstone: RangeTombstone) {
+        self.tombstones.push(tombstone);
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.tombstones.is_empty()
+    }
+
+    pub(crate) fn covers(&self, entry: &RowEntry) -> bool {
+        self.tombstones
+            .iter()
+            .any(|t| t.covers(&entry.key, entry.seq))
+    }
+}
+
+/// Collects the range tombstones stored in the L0 SSTs and sorted runs of a column family.
+pub(crate) fn sst_range_tombstones(core: &CoreDbState, column_family: u32) -> Vec<RangeTombstone> {
+    core.column_family_l0(column_family)
+        .iter()
+        .chain(
+            core.column_family_compacted(column_family)
+                .iter()
+                .flat_map(|sr| sr.ssts.iter()),
+        )
+        .flat_map(|sst| sst.range_tombstones())
+        .collect()
+}
+
+/// A lazily built per-column-family index of the range tombstones stored in the SSTs
+/// of a db state, so reads don't have to walk every SST to find them. Tombstones are
+/// carried over to the first output SST of a compaction, so they can cover keys
+/// outside of the key range of the SST that stores them.
+///
+/// Cloning returns an empty index, because db states are only cloned to be modified.
+#[derive(Debug, Default)]
+pub(crate) struct SstRangeTombstoneIndex {
+    column_families: OnceLock<HashMap<u32, Vec<RangeTombstone>>>,
+}
+
+impl Clone for SstRangeTombstoneIndex {
+    fn clone(&self) -> Self {
+        Self::default()
+    }
+}
+
+impl SstRangeTombstoneIndex {
+    /// Returns the range tombstones stored in the SSTs of a column family of `core`.
+    /// The index is built on first use, so `core` must be the state it belongs to.
+    pub(crate) fn column_family(
+        &self,
+        core: &CoreDbState,
+        column_family: u32,
+    ) -> &[RangeTombstone] {
+        let column_families = self.column_families.get_or_init(|| {
+            std::iter::once(crate::column_family::DEFAULT_COLUMN_FAMILY_ID)
+                .chain(core.column_families.iter().map(|cf| cf.id))
+                .map(|id| (id, sst_range_tombstones(core, id)))
+                .filter(|(_, tombstones)| !tombstones.is_empty())
+                .collect()
+        });
+        column_families
+            .get(&column_family)
+            .map_or(&[], |tombstones| tombstones.as_slice())
+    }
+}
+
+/// An iterator adapter that turns every entry covered by a range tombstone into
+/// a point tombstone with the same key and sequence number. Keeping the entry
+/// (rather than dropping it) lets the merge and get iterators stop at the
+/// deletion exactly as they would for a point delete.
+pub(crate) struct RangeTombstoneIterator<T: KeyValueIterator> {
+    iterator: T,
+    tombstones: Arc<RangeTombstones>,
+}
+
+impl<T: KeyValueIterator> RangeTombstoneIterator<T> {
+    pub(crate) fn new(iterator: T, tombstones: Arc<RangeTombstones>) -> Self {
+        Self {
+            iterator,
+            tombstones,
+        }
+    }
+}
+
+#[async_trait]
+impl<T: KeyValueIterator> KeyValueIterator for RangeTombstoneIterator<T> {
+    async fn init(&mut self) -> Result<(), SlateDBError> {
+        self.iterator.init().await
+    }
+
+    async fn next_entry(&mut self) -> Result<Option<RowEntry>, SlateDBError> {
+        let Some(entry) = self.iterator.next_entry().await? else {
+            return Ok(None);
+        };
+        if self.tombstones.covers(&entry) {
+            return Ok(Some(RowEntry {
+                key: entry.key,
+                value: ValueDeletable::Tombstone,
+                seq: entry.seq,
+                create_ts: entry.create_ts,
+                expire_ts: None,
+            }));
+        }
+        Ok(Some(entry))
+    }
+
+    async fn seek(&mut self, next_key: &[u8]) -> Result<(), SlateDBError> {
+        self.iterator.seek(next_key).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::db_state::{SortedRun, SsTableHandle, SsTableId, SsTableInfo};
+    use crate::test_utils::{assert_iterator, TestIterator};
+    use bytes::Bytes;
+
+    #[test]
+    fn should_cover_only_older_entries_in_range() {
+        let tombstone = RangeTombstone::new(BytesRange::from_ref("b".."d"), 10, 0);
+
+        assert!(tombstone.covers(b"b", 9));
+        assert!(tombstone.covers(b"c", 1));
+        assert!(!tombstone.covers(b"c", 10));
+        assert!(!tombstone.covers(b"c", 11));
+        assert!(!tombstone.covers(b"a", 1));
+        assert!(!tombstone.covers(b"d", 1));
+    }
+
+    #[test]
+    fn should_only_keep_visible_overlapping_tombstones() {
+        let candidates = [
+            RangeTombstone::new(BytesRange::from_ref("a".."c"), 5, 0),
+            RangeTombstone::new(BytesRange::from_ref("x".."z"), 6, 0),
+            RangeTombstone::new(BytesRange::from_ref("b".."d"), 20, 0),
+        ];
+        let mut tombstones = RangeTombstones::new();
+
+        tombstones.extend_visible(&candidates, &BytesRange::from_ref("b".."e"), Some(10));
+
+        assert_eq!(tombstones.tombstones, vec![candidates[0].clone()]);
+    }
+
+    #[test]
+    fn should_index_sst_range_tombstones_by_column_family() {
+        let tombstone = RangeTombstone::new(BytesRange::from_ref("a".."m"), 5, 0);
+        let sst_with_tombstone = |first_key: &'static [u8]| {
+            SsTableHandle::new_compacted(
+                SsTableId::Compacted(ulid::Ulid::new()),
+                SsTableInfo {
+                    first_key: Some(Bytes::from_static(first_key)),
+                    range_tombstones: vec![tombstone.clone()],
+                    ..SsTableInfo::default()
+                },
+                None,
+            )
+        };
+        let mut core = CoreDbState::new();
+        core.l0.push_back(sst_with_tombstone(b"x"));
+        core.compacted.push(SortedRun {
+            id: 0,
+            ssts: vec![sst_with_tombstone(b"a")],
+        });
+        let index = SstRangeTombstoneIndex::default();
+
+        assert_eq!(
+            index.column_family(&core, 0),
+            &[tombstone.clone(), tombstone]
+        );
+        assert!(index.column_family(&core, 1).is_empty());
+        // a clone is made to modify the state, so it must not reuse the index
+        core.l0.clear();
+        assert_eq!(index.clone().column_family(&core, 0).len(), 1);
+    }
+
+    #[tokio::test]
+    async fn should_convert_covered_entries_to_tombstones() {
+        let mut tombstones = RangeTombstones::new();
+        tombstones.push(RangeTombstone::new(BytesRange::from_ref("b"..="c"), 5, 0));
+        let inner = TestIterator::new()
+            .with_entry(b"a", b"1", 1)
+            .with_entry(b"b", b"2", 6)
+            .with_entry(b"b", b"3", 2)
+            .with_entry(b"c", b"4", 3)
+            .with_entry(b"d", b"5", 4);
+        let mut iter = RangeTombstoneIterator::new(inner, Arc::new(tombstones));
+
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"a", b"1", 1),
+                RowEntry::new_value(b"b", b"2", 6),
+                RowEntry::new_tombstone(b"b", 2),
+                RowEntry::new_tombstone(b"c", 3),
+                RowEntry::new_value(b"d", b"5", 4),
+            ],
+        )
+        .await;
+    }
+}
@@ -0,0 +1,191 @@
+//! # Rate limiter
+//!
+//! A token bucket that limits the bytes per second written by a component of the
+//! database, such as client writes, memtable flushes or compactions.
+//!
+//! The bucket holds up to one second of tokens, so a component can burst up to its rate
+//! after it has been idle. A request is admitted as soon as the bucket has any tokens,
+//! even if it is larger than the tokens available; the bucket then goes into debt, and
+//! the following requests wait until the debt is repaid. This lets requests that are
+//! larger than the rate through, while the average rate stays bounded.
+
+use std::sync::Arc;
+use std::time::Duration;
+
+use chrono::{DateTime, Utc};
+use parking_lot::Mutex;
+
+use crate::clock::SystemClock;
+
+/// The shortest time a request waits for tokens, so that waiters don't spin while the
+/// bucket is refilled by less than a token.
+const MIN_WAIT: Duration = Duration::from_millis(1);
+
+pub(crate) struct RateLimiter {
+    clock: Arc<dyn SystemClock>,
+    state: Mutex<RateLimiterState>,
+}
+
+struct RateLimiterState {
+    /// The rate of the bucket. `None` admits every request.
+    bytes_per_second: Option<u64>,
+    /// The tokens in the bucket. Negative when the bucket is in debt.
+    tokens: f64,
+    last_refill: DateTime<Utc>,
+}
+
+impl RateLimiterState {
+    fn refill(&mut self, now: DateTime<Utc>) {
+        if let Some(bytes_per_second) = self.bytes_per_second {
+            let elapsed = (now - self.last_refill).to_std().unwrap_or_default();
+            self.tokens = (self.tokens + elapsed.as_secs_f64() * bytes_per_second as f64)
+                .min(bytes_per_second as f64);
+        }
+        self.last_refill = self.last_refill.max(now);
+    }
+
+    /// Takes `bytes` tokens from the bucket if it has any, or returns how long to wait
+    /// until it does.
+    fn take(&mut self, bytes: u64, now: DateTime<Utc>) -> Option<Duration> {
+        let bytes_per_second = self.bytes_per_second?;
+        self.refill(now);
+        if self.tokens > 0.0 {
+            self.tokens -= bytes as f64;
+            return None;
+        }
+        Some(Duration::from_secs_f64(-self.tokens / bytes_per_second as f64).max(MIN_WAIT))
+    }
+}
+
+impl RateLimiter {
+    /// Creates a rate limiter with a full bucket. A rate of `None` or zero disables the
+    /// limiter.
+    pub(crate) fn new(bytes_per_second: Option<u64>, clock: Arc<dyn SystemClock>) -> Self {
+        let bytes_per_second = bytes_per_second.filter(|rate| *rate > 0);
+        let last_refill = clock.now();
+        Self {
+            clock,
+            state: Mutex::new(RateLimiterState {
+                bytes_per_second,
+                tokens: bytes_per_second.unwrap_or_default() as f64,
+                last_refill,
+            }),
+        }
+    }
+
+    /// Changes the rate of the limiter. The tokens in the bucket are kept, up to one
+    /// second of the new rate.
+    pub(crate) fn set_bytes_per_second(&self, bytes_per_second: Option<u64>) {
+        let bytes_per_second = bytes_per_second.filter(|rate| *rate > 0);
+        let mut state = self.state.lock();
+        state.refill(self.clock.now());
+        if state.bytes_per_second.is_none() {
+            // a disabled limiter doesn't track its tokens, so start with a full bucket
+            state.tokens = f64::MAX;
+        }
+        state.bytes_per_second = bytes_per_second;
+        state.tokens = state
+            .tokens
+            .min(bytes_per_second.unwrap_or_default() as f64);
+    }
+
+    /// Takes `bytes` tokens if the bucket has any. Returns false, without taking any
+    /// tokens, if the caller would have to wait.
+    pub(crate) fn try_acquire(&self, bytes: u64) -> bool {
+        self.state.lock().take(bytes, self.clock.now()).is_none()
+    }
+
+    /// Waits until the bucket has tokens and takes `bytes` tokens. Returns how long the
+    /// caller waited.
+    pub(crate) async fn acquire(&self, bytes: u64) -> Duration {
+        let mut waited = Duration::ZERO;
+        loop {
+            let wait = self.state.lock().take(bytes, self.clock.now());
+            let Some(wait) = wait else {
+                return waited;
+            };
+            self.clock.sleep(wait).await;
+            waited += wait;
+        }
+    }
+}
+
+#[cfg(all(test, feature = "test-util"))]
+mod tests {
+    use super::*;
+    use crate::clock::MockSystemClock;
+
+    #[test]
+    fn test_should_admit_everything_when_disabled() {
+        let clock = Arc::new(MockSystemClock::new());
+        let limiter = RateLimiter::new(None, clock.clone());
+        assert!(limiter.try_acquire(u64::MAX));
+        assert!(limiter.try_acquire(u64::MAX));
+
+        let limiter = RateLimiter::new(Some(0), clock);
+        assert!(limiter.try_acquire(u64::MAX));
+    }
+
+    #[tokio::test]
+    async fn test_should_limit_rate_after_burst() {
+        let clock = Arc::new(MockSystemClock::new());
+        let limiter = RateLimiter::new(Some(1000), clock.clone());
+
+        // the bucket starts full, and the request that empties it goes into debt
+        assert!(limiter.try_acquire(600));
+        assert!(limiter.try_acquire(600));
+        assert!(!limiter.try_acquire(1));
+
+        // 100ms refill 100 tokens, which repays the debt of 200
+        clock.advance(Duration::from_millis(100)).await;
+        assert!(!limiter.try_acquire(1));
+        clock.advance(Duration::from_millis(101)).await;
+        assert!(limiter.try_acquire(1));
+    }
+
+    #[tokio::test]
+    async fn test_should_refill_up_to_one_second() {
+        let clock = Arc::new(MockSystemClock::new());
+        let limiter = RateLimiter::new(Some(1000), clock.clone());
+        assert!(limiter.try_acquire(1000));
+
+        clock.advance(Duration::from_secs(10)).await;
+        assert!(limiter.try_acquire(1000));
+        assert!(!limiter.try_acquire(1));
+    }
+
+    #[tokio::test]
+    async fn test_should_wait_for_tokens() {
+        let clock = Arc::new(MockSystemClock::new());
+        let limiter = Arc::new(RateLimiter::new(Some(1000), clock.clone()));
+        assert_eq!(limiter.acquire(1500).await, Duration::ZERO);
+
+        let waiter = tokio::spawn({
+            let limiter = limiter.clone();
+            async move { limiter.acquire(1).await }
+        });
+        while !waiter.is_finished() {
+            clock.advance(Duration::from_millis(10)).await;
+        }
+        // the debt of 500 bytes is repaid after 500ms
+        assert!(waiter.await.unwrap() > Duration::ZERO);
+        assert!(clock.now().timestamp_millis() >= 500);
+    }
+
+    #[tokio::test]
+    async fn test_should_change_rate() {
+        let clock = Arc::new(MockSystemClock::new());
+        let limiter = RateLimiter::new(None, clock.clone());
+
+        limiter.set_bytes_per_second(Some(100));
+        assert!(limiter.try_acquire(100));
+        assert!(!limiter.try_acquire(1));
+
+        limiter.set_bytes_per_second(Some(1000));
+        clock.advance(Duration::from_millis(10)).await;
+        assert!(limiter.try_acquire(1));
+
+        limiter.set_bytes_per_second(None);
+        assert!(limiter.try_acquire(u64::MAX));
+    }
+}
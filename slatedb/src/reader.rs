@@ -1,28 +1,56 @@
 use crate::batch::{WriteBatch, WriteBatchIterator};
+use crate::block::Block;
+use crate::block_iterator::BlockIterator;
 use crate::bytes_range::BytesRange;
-use crate::clock::MonotonicClock;
+use crate::clock::{MonotonicClock, SystemClock};
+use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+use crate::comparator::KeyComparator;
 use crate::config::{DurabilityLevel, ReadOptions, ScanOptions};
-use crate::db_state::CoreDbState;
+use crate::db_state::{CoreDbState, SsTableHandle};
 use crate::db_stats::DbStats;
+use crate::filter;
 use crate::iter::{IterationOrder, KeyValueIterator};
 use crate::mem_table::{ImmutableMemtable, KVTable};
+use crate::merge_operator::MergeOperatorType;
 use crate::oracle::Oracle;
+use crate::partitioned_keyspace;
+use crate::prefix_extractor::PrefixExtractorType;
+use crate::range_tombstone::{self, RangeTombstone, RangeTombstoneIterator, RangeTombstones};
+use crate::seq_tracker::{FindOption, SequenceTracker};
 use crate::sorted_run_iterator::SortedRunIterator;
 use crate::sst_iter::{SstIterator, SstIteratorOptions};
 use crate::tablestore::TableStore;
+use crate::types::{KeyValueWithMetadata, RowEntry, ValueDeletable};
 use crate::utils::get_now_for_read;
 use crate::utils::{build_concurrent, compute_max_parallel};
 use crate::{db_iter::DbIteratorRangeTracker, error::SlateDBError, DbIterator};
 
+use async_trait::async_trait;
 use bytes::Bytes;
+use chrono::{DateTime, TimeDelta, Utc};
 use futures::future::join;
-use std::collections::VecDeque;
+use parking_lot::RwLock;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+use std::ops::Range;
 use std::sync::Arc;
+use std::time::Duration;
 
 pub(crate) trait DbStateReader {
     fn memtable(&self) -> Arc<KVTable>;
+    /// Returns the memtable of a named column family, or None if there is none.
+    fn column_family_memtable(&self, _column_family: u32) -> Option<Arc<KVTable>> {
+        None
+    }
     fn imm_memtable(&self) -> &VecDeque<Arc<ImmutableMemtable>>;
     fn core(&self) -> &CoreDbState;
+    /// Returns the range tombstones stored in the SSTs of a column family.
+    fn sst_range_tombstones(&self, column_family: u32) -> Cow<'_, [RangeTombstone]> {
+        Cow::Owned(range_tombstone::sst_range_tombstones(
+            self.core(),
+            column_family,
+        ))
+    }
 }
 
 struct IteratorSources {
@@ -32,15 +60,193 @@ struct IteratorSources {
     sr_iters: VecDeque<Box<dyn KeyValueIterator + 'static>>,
 }
 
+impl IteratorSources {
+    /// Turns the entries covered by `range_tombstones` into point tombstones before the
+    /// sources are merged, so the rest of the read path handles them like regular deletes.
+    fn with_range_tombstones(self, range_tombstones: RangeTombstones) -> Self {
+        if range_tombstones.is_empty() {
+            return self;
+        }
+        let range_tombstones = Arc::new(range_tombstones);
+        let cover = |iter: Box<dyn KeyValueIterator + 'static>| {
+            Box::new(RangeTombstoneIterator::new(iter, range_tombstones.clone()))
+                as Box<dyn KeyValueIterator + 'static>
+        };
+        IteratorSources {
+            write_batch_iter: self.write_batch_iter,
+            mem_iters: self.mem_iters.into_iter().map(cover).collect(),
+            l0_iters: self.l0_iters.into_iter().map(cover).collect(),
+            sr_iters: self.sr_iters.into_iter().map(cover).collect(),
+        }
+    }
+}
+
+/// Iterates over the versions of a single key in the blocks fetched for it by a
+/// multi-get. The blocks are in the order they appear in the SST.
+struct PointBlocksIterator {
+    key: Bytes,
+    blocks: VecDeque<Arc<Block>>,
+    current: Option<BlockIterator<Arc<Block>>>,
+    comparator: KeyComparator,
+    /// The table store to read the values that blob refs point to from, or None if
+    /// blob refs are returned as they are.
+    blob_table_store: Option<Arc<TableStore>>,
+}
+
+impl PointBlocksIterator {
+    fn new(key: Bytes, blocks: Vec<Arc<Block>>, comparator: KeyComparator) -> Self {
+        Self {
+            key,
+            blocks: blocks.into(),
+            current: None,
+            comparator,
+            blob_table_store: None,
+        }
+    }
+
+    /// Resolves the blob refs of the returned entries to their values.
+    fn with_blob_refs_resolved(mut self, table_store: Arc<TableStore>) -> Self {
+        self.blob_table_store = Some(table_store);
+        self
+    }
+}
+
+#[async_trait]
+impl KeyValueIterator for PointBlocksIterator {
+    async fn init(&mut self) -> Result<(), SlateDBError> {
+        Ok(())
+    }
+
+    async fn next_entry(&mut self) -> Result<Option<RowEntry>, SlateDBError> {
+        loop {
+            if let Some(iter) = self.current.as_mut() {
+                match iter.next_entry().await? {
+                    Some(mut entry) if entry.key == self.key => {
+                        if let (ValueDeletable::BlobRef(blob_ref), Some(table_store)) =
+                            (&entry.value, &self.blob_table_store)
+                        {
+                            let value = table_store.read_blob_value(blob_ref).await?;
+                            entry.value = ValueDeletable::Value(value);
+                        }
+                        return Ok(Some(entry));
+                    }
+                    // the rest of the blocks start after the key too
+                    Some(_) => self.blocks.clear(),
+                    None => {}
+                }
+                self.current = None;
+            }
+            let Some(block) = self.blocks.pop_front() else {
+                return Ok(None);
+            };
+            let mut iter = BlockIterator::new(block, IterationOrder::Ascending)
+                .with_comparator(self.comparator.clone());
+            iter.seek(&self.key).await?;
+            self.current = Some(iter);
+        }
+    }
+
+    async fn seek(&mut self, next_key: &[u8]) -> Result<(), SlateDBError> {
+        if self.comparator.compare(next_key, &self.key).is_gt() {
+            self.blocks.clear();
+            self.current = None;
+        }
+        Ok(())
+    }
+}
+
+/// The prefix of a scan with [`ScanOptions::prefix`], as extracted by the database's
+/// prefix extractor. Only the filters of SSTs written with the same extractor hold the
+/// prefixes of their keys, so the other SSTs are never skipped.
+#[derive(Clone)]
+struct ScanPrefixFilter {
+    extractor_name: String,
+    prefix_hash: u64,
+    db_stats: DbStats,
+}
+
+impl ScanPrefixFilter {
+    /// Returns false if `sst` can't contain a key with the prefix.
+    async fn may_contain(
+        &self,
+        sst: &SsTableHandle,
+        table_store: &TableStore,
+    ) -> Result<bool, SlateDBError> {
+        if sst.info.prefix_extractor.as_deref() != Some(self.extractor_name.as_str()) {
+            return Ok(true);
+        }
+        let Some(filter) = table_store.read_filter(sst).await? else {
+            return Ok(true);
+        };
+        if filter.might_contain(self.prefix_hash) {
+            self.db_stats.sst_prefix_filter_positives.inc();
+            Ok(true)
+        } else {
+            self.db_stats.sst_prefix_filter_negatives.inc();
+            Ok(false)
+        }
+    }
+}
+
 pub(crate) struct Reader {
     pub(crate) table_store: Arc<TableStore>,
     pub(crate) db_stats: DbStats,
     pub(crate) mono_clock: Arc<MonotonicClock>,
     pub(crate) oracle: Arc<dyn Oracle>,
     pub(crate) merge_operator: Option<crate::merge_operator::MergeOperatorType>,
+    /// The merge operators of the named column families, by column family id.
+    pub(crate) column_family_merge_operators: HashMap<u32, MergeOperatorType>,
+    /// The prefix extractor of the database, used to skip SSTs in prefix scans.
+    pub(crate) prefix_extractor: Option<PrefixExtractorType>,
+    /// How long compactions keep old versions, see
+    /// [`crate::config::CompactorOptions::history_retention`]. As-of reads older than it
+    /// fail, unless an active snapshot keeps their versions.
+    pub(crate) history_retention: RwLock<Option<Duration>>,
+    pub(crate) system_clock: Arc<dyn SystemClock>,
 }
 
 impl Reader {
+    fn merge_operator(&self, column_family: u32) -> Option<MergeOperatorType> {
+        if column_family == DEFAULT_COLUMN_FAMILY_ID {
+            self.merge_operator.clone()
+        } else {
+            self.column_family_merge_operators
+                .get(&column_family)
+                .cloned()
+        }
+    }
+
+    /// Returns the filter for the SSTs of a scan with the given [`ScanOptions::prefix`],
+    /// or None if the prefix extractor doesn't extract a prefix from it.
+    fn scan_prefix_filter(&self, prefix: Option<&Bytes>) -> Option<ScanPrefixFilter> {
+        let prefix_extractor = self.prefix_extractor.as_ref()?;
+        let prefix = prefix_extractor.prefix(prefix?)?;
+        Some(ScanPrefixFilter {
+            extractor_name: prefix_extractor.name().to_string(),
+            prefix_hash: filter::filter_hash(prefix),
+            db_stats: self.db_stats.clone(),
+        })
+    }
+
+    /// Returns the memtables of a column family, from the newest to the oldest.
+    fn memtables(
+        column_family: u32,
+        db_state: &(dyn DbStateReader + Sync),
+    ) -> VecDeque<Arc<KVTable>> {
+        let mut memtables = VecDeque::new();
+        if column_family == DEFAULT_COLUMN_FAMILY_ID {
+            memtables.push_back(db_state.memtable());
+        } else if let Some(memtable) = db_state.column_family_memtable(column_family) {
+            memtables.push_back(memtable);
+        }
+        for memtable in db_state.imm_memtable() {
+            if let Some(table) = memtable.column_family_table(column_family) {
+                memtables.push_back(table);
+            }
+        }
+        memtables
+    }
+
     /// Determines the maximum sequence number for read operations (get and scan). Read operations will filter
     /// out entries with sequence numbers greater than the returned value.
     ///
@@ -81,41 +287,158 @@ impl Reader {
         max_seq
     }
 
+    /// Resolves the `as_of_seq` and `as_of_timestamp` read options into the highest sequence
+    /// number the read may see, or None if neither option is set. Timestamps are looked up in
+    /// the sequence trackers of the memtables and the manifest.
+    ///
+    /// Fails with [`SlateDBError::VersionsNotRetained`] if compactions may have dropped the
+    /// versions visible at that sequence number.
+    fn resolve_as_of_seq(
+        &self,
+        as_of_seq: Option<u64>,
+        as_of_timestamp: Option<DateTime<Utc>>,
+        db_state: &(dyn DbStateReader + Sync),
+    ) -> Result<Option<u64>, SlateDBError> {
+        let seq_for_timestamp = as_of_timestamp.map(|ts| {
+            Self::sequence_trackers(db_state)
+                .into_iter()
+                .filter_map(|tracker| tracker.find_seq(ts, FindOption::RoundDown))
+                .max()
+                // nothing was recorded before the timestamp, so no write is visible
+                .unwrap_or(0)
+        });
+        let Some(seq) = as_of_seq.into_iter().chain(seq_for_timestamp).min() else {
+            return Ok(None);
+        };
+        let min_retained_seq = self.min_retained_seq(db_state);
+        if seq < min_retained_seq {
+            return Err(SlateDBError::VersionsNotRetained {
+                seq,
+                min_retained_seq,
+            });
+        }
+        Ok(Some(seq))
+    }
+
+    /// Returns the sequence trackers of the manifest and of the memtables.
+    fn sequence_trackers(db_state: &(dyn DbStateReader + Sync)) -> Vec<SequenceTracker> {
+        std::iter::once(db_state.core().sequence_tracker.clone())
+            .chain(std::iter::once(
+                db_state.memtable().sequence_tracker_snapshot(),
+            ))
+            .chain(
+                db_state
+                    .imm_memtable()
+                    .iter()
+                    .map(|imm| imm.sequence_tracker().clone()),
+            )
+            .collect()
+    }
+
+    /// Returns the lowest sequence number whose visible versions compactions are guaranteed
+    /// to keep. Compactions keep the versions visible at the oldest active snapshot, which
+    /// the manifest records as `recent_snapshot_min_seq`, and the versions overwritten
+    /// within the history retention.
+    fn min_retained_seq(&self, db_state: &(dyn DbStateReader + Sync)) -> u64 {
+        let snapshot_min_seq = db_state.core().recent_snapshot_min_seq;
+        let Some(retention) = *self.history_retention.read() else {
+            return snapshot_min_seq;
+        };
+        let retention = TimeDelta::from_std(retention).unwrap_or(TimeDelta::MAX);
+        let Some(window_start) = self.system_clock.now().checked_sub_signed(retention) else {
+            return 0;
+        };
+        // the first write recorded within the retention window. Without one, only the
+        // latest versions are kept.
+        let history_min_seq = Self::sequence_trackers(db_state)
+            .into_iter()
+            .filter_map(|tracker| tracker.find_seq(window_start, FindOption::RoundUp))
+            .min()
+            .unwrap_or_else(|| self.oracle.last_committed_seq());
+        snapshot_min_seq.min(history_min_seq)
+    }
+
+    /// Collects the range tombstones overlapping `range` that are visible at `max_seq`.
+    /// Range deletes of an uncommitted `write_batch` hide every committed entry.
+    fn collect_range_tombstones(
+        column_family: u32,
+        range: &BytesRange,
+        db_state: &(dyn DbStateReader + Sync),
+        write_batch: Option<&WriteBatch>,
+        max_seq: Option<u64>,
+    ) -> RangeTombstones {
+        let mut tombstones = RangeTombstones::new();
+        if let Some(batch) = write_batch {
+            for batch_range in &batch.range_deletes {
+                if let Some(batch_range) = batch_range.intersect(range) {
+                    tombstones.push(RangeTombstone::new(batch_range, u64::MAX, 0));
+                }
+            }
+        }
+        for memtable in Self::memtables(column_family, db_state) {
+            tombstones.extend_visible(&memtable.range_tombstones(), range, max_seq);
+        }
+        tombstones.extend_visible(
+            db_state.sst_range_tombstones(column_family).iter(),
+            range,
+            max_seq,
+        );
+        tombstones
+    }
+
+    #[allow(clippy::too_many_arguments)]
     async fn build_iterator_sources(
         &self,
+        column_family: u32,
         range: &BytesRange,
         db_state: &(dyn DbStateReader + Sync),
         write_batch: Option<WriteBatch>,
         sst_iter_options: SstIteratorOptions,
         point_lookup_stats: Option<DbStats>,
+        prefix_filter: Option<ScanPrefixFilter>,
+        max_seq: Option<u64>,
     ) -> Result<IteratorSources, SlateDBError> {
-        let write_batch_iter = write_batch
-            .map(|batch| WriteBatchIterator::new(batch, range.clone(), IterationOrder::Ascending));
+        let order = sst_iter_options.order;
+        let write_batch = write_batch.and_then(|batch| batch.into_column_family(column_family));
+        let range_tombstones = Self::collect_range_tombstones(
+            column_family,
+            range,
+            db_state,
+            write_batch.as_ref(),
+            max_seq,
+        );
+        let write_batch_iter = write_batch.map(|batch| {
+            WriteBatchIterator::new_with_comparator(
+                batch,
+                range.clone(),
+                order,
+                self.table_store.comparator().clone(),
+            )
+        });
 
-        let mut memtables = VecDeque::new();
-        memtables.push_back(db_state.memtable());
-        for memtable in db_state.imm_memtable() {
-            memtables.push_back(memtable.table());
-        }
-        let mem_iters = memtables
+        let mem_iters = Self::memtables(column_family, db_state)
             .iter()
             .map(|table| {
-                Box::new(table.range_ascending(range.clone()))
-                    as Box<dyn KeyValueIterator + 'static>
+                Box::new(table.range(range.clone(), order)) as Box<dyn KeyValueIterator + 'static>
             })
             .collect::<Vec<_>>();
 
-        let max_parallel =
-            compute_max_parallel(db_state.core().l0.len(), &db_state.core().compacted, 4);
+        let max_parallel = compute_max_parallel(
+            db_state.core().column_family_l0(column_family).len(),
+            db_state.core().column_family_compacted(column_family),
+            4,
+        );
 
         let (l0_iters, sr_iters) = if let Some(point_key) = range.as_point().cloned() {
             let l0 = self.build_point_l0_iters(
+                column_family,
                 range,
                 db_state,
                 sst_iter_options,
                 point_lookup_stats.clone(),
             )?;
             let sr = self.build_point_sr_iters(
+                column_family,
                 range,
                 &point_key,
                 db_state,
@@ -124,10 +447,22 @@ impl Reader {
             )?;
             (l0, sr)
         } else {
-            let l0_future =
-                self.build_range_l0_iters(range, db_state, sst_iter_options, max_parallel);
-            let sr_future =
-                self.build_range_sr_iters(range, db_state, sst_iter_options, max_parallel);
+            let l0_future = self.build_range_l0_iters(
+                column_family,
+                range,
+                db_state,
+                sst_iter_options,
+                prefix_filter.clone(),
+                max_parallel,
+            );
+            let sr_future = self.build_range_sr_iters(
+                column_family,
+                range,
+                db_state,
+                sst_iter_options,
+                prefix_filter,
+                max_parallel,
+            );
             let (l0_res, sr_res) = join(l0_future, sr_future).await;
             (l0_res?, sr_res?)
         };
@@ -137,18 +472,20 @@ impl Reader {
             mem_iters,
             l0_iters,
             sr_iters,
-        })
+        }
+        .with_range_tombstones(range_tombstones))
     }
 
     fn build_point_l0_iters<'a>(
         &self,
+        column_family: u32,
         range: &BytesRange,
         db_state: &(dyn DbStateReader + Sync),
         sst_iter_options: SstIteratorOptions,
         db_stats: Option<DbStats>,
     ) -> Result<VecDeque<Box<dyn KeyValueIterator + 'a>>, SlateDBError> {
         let mut iters = VecDeque::new();
-        for sst in &db_state.core().l0 {
+        for sst in db_state.core().column_family_l0(column_family) {
             let iterator = SstIterator::new_owned_with_stats(
                 range.clone(),
                 sst.clone(),
@@ -165,6 +502,7 @@ impl Reader {
 
     fn build_point_sr_iters<'a>(
         &self,
+        column_family: u32,
         range: &BytesRange,
         key: &Bytes,
         db_state: &(dyn DbStateReader + Sync),
@@ -172,8 +510,10 @@ impl Reader {
         db_stats: Option<DbStats>,
     ) -> Result<VecDeque<Box<dyn KeyValueIterator + 'a>>, SlateDBError> {
         let mut iters = VecDeque::new();
-        for sr in &db_state.core().compacted {
-            if let Some(handle) = sr.find_sst_with_range_covering_key(key.as_ref()) {
+        for sr in db_state.core().column_family_compacted(column_family) {
+            if let Some(handle) =
+                sr.find_sst_with_range_covering_key(key.as_ref(), self.table_store.comparator())
+            {
                 let iterator = SstIterator::new_owned_with_stats(
                     range.clone(),
                     handle.clone(),
@@ -191,20 +531,32 @@ impl Reader {
 
     async fn build_range_l0_iters<'a>(
         &self,
+        column_family: u32,
         range: &BytesRange,
         db_state: &(dyn DbStateReader + Sync),
         sst_iter_options: SstIteratorOptions,
+        prefix_filter: Option<ScanPrefixFilter>,
         max_parallel: usize,
     ) -> Result<VecDeque<Box<dyn KeyValueIterator + 'a>>, SlateDBError> {
         let range_clone = range.clone();
         let table_store = self.table_store.clone();
         build_concurrent(
-            db_state.core().l0.iter().cloned(),
+            db_state
+                .core()
+                .column_family_l0(column_family)
+                .iter()
+                .cloned(),
             max_parallel,
             move |sst| {
                 let table_store = table_store.clone();
                 let range = range_clone.clone();
+                let prefix_filter = prefix_filter.clone();
                 async move {
+                    if let Some(prefix_filter) = &prefix_filter {
+                        if !prefix_filter.may_contain(&sst, &table_store).await? {
+                            return Ok(None);
+                        }
+                    }
                     SstIterator::new_owned_initialized(
                         range.clone(),
                         sst,
@@ -223,20 +575,41 @@ impl Reader {
 
     async fn build_range_sr_iters<'a>(
         &self,
+        column_family: u32,
         range: &BytesRange,
         db_state: &(dyn DbStateReader + Sync),
         sst_iter_options: SstIteratorOptions,
+        prefix_filter: Option<ScanPrefixFilter>,
         max_parallel: usize,
     ) -> Result<VecDeque<Box<dyn KeyValueIterator + 'a>>, SlateDBError> {
         let range_clone = range.clone();
         let table_store = self.table_store.clone();
         build_concurrent(
-            db_state.core().compacted.iter().cloned(),
+            db_state
+                .core()
+                .column_family_compacted(column_family)
+                .iter()
+                .cloned(),
             max_parallel,
             move |sr| {
                 let table_store = table_store.clone();
                 let range = range_clone.clone();
+                let prefix_filter = prefix_filter.clone();
                 async move {
+                    // the sorted run is skipped if none of its SSTs in the range can
+                    // contain the prefix
+                    if let Some(prefix_filter) = &prefix_filter {
+                        let mut may_contain = false;
+                        for sst in sr.tables_covering_range(&range, table_store.comparator()) {
+                            if prefix_filter.may_contain(sst, &table_store).await? {
+                                may_contain = true;
+                                break;
+                            }
+                        }
+                        if !may_contain {
+                            return Ok(None);
+                        }
+                    }
                     SortedRunIterator::new_owned_initialized(
                         range.clone(),
                         sr,
@@ -258,6 +631,7 @@ impl Reader {
     /// and an error if the read fails.
     ///
     /// Arguments:
+    /// - `column_family`: The id of the column family to read from.
     /// - `key`: The user key to read. Any type that can be viewed as a byte
     ///   slice is accepted.
     /// - `options`: Options for the read, including durability constraint or
@@ -272,13 +646,36 @@ impl Reader {
     ///   and the bound derived from `options` (e.g., durability, dirty read).
     pub(crate) async fn get_with_options<K: AsRef<[u8]>>(
         &self,
+        column_family: u32,
         key: K,
         options: &ReadOptions,
         db_state: &(dyn DbStateReader + Sync + Send),
         write_batch: Option<WriteBatch>,
         max_seq: Option<u64>,
     ) -> Result<Option<Bytes>, SlateDBError> {
+        let row = self
+            .get_row_with_options(column_family, key, options, db_state, write_batch, max_seq)
+            .await?;
+        Ok(row.map(|row| row.value))
+    }
+
+    /// Get the value for the given key, along with the sequence number and timestamps
+    /// of the row it was read from.
+    ///
+    /// Arguments are the same as for [`Reader::get_with_options`].
+    pub(crate) async fn get_row_with_options<K: AsRef<[u8]>>(
+        &self,
+        column_family: u32,
+        key: K,
+        options: &ReadOptions,
+        db_state: &(dyn DbStateReader + Sync + Send),
+        write_batch: Option<WriteBatch>,
+        max_seq: Option<u64>,
+    ) -> Result<Option<KeyValueWithMetadata>, SlateDBError> {
         let now = get_now_for_read(self.mono_clock.clone(), options.durability_filter).await?;
+        let as_of_seq =
+            self.resolve_as_of_seq(options.as_of_seq, options.as_of_timestamp, db_state)?;
+        let max_seq = max_seq.into_iter().chain(as_of_seq).min();
         let max_seq = self.prepare_max_seq(max_seq, options.durability_filter, options.dirty);
         let key_slice = key.as_ref();
         let target_key = Bytes::copy_from_slice(key_slice);
@@ -297,11 +694,14 @@ impl Reader {
             sr_iters,
         } = self
             .build_iterator_sources(
+                column_family,
                 &range,
                 db_state,
                 write_batch,
                 sst_iter_options,
                 Some(self.db_stats.clone()),
+                None,
+                max_seq,
             )
             .await?;
 
@@ -314,19 +714,271 @@ impl Reader {
             max_seq,
             None,
             now,
-            self.merge_operator.clone(),
+            self.merge_operator(column_family),
+            IterationOrder::Ascending,
+            self.table_store.comparator().clone(),
         )
         .await?;
 
-        if let Some(entry) = iterator.next_key_value().await? {
-            if entry.key == target_key {
-                return Ok(Some(entry.value));
+        if let Some(row) = iterator.next_row().await? {
+            if row.key == target_key {
+                return Ok(Some(row));
             }
         }
 
         Ok(None)
     }
 
+    /// Get the values of several keys from a single, consistent view of the database.
+    ///
+    /// Returns one result per key, in the order of `keys`, with the same meaning as the
+    /// result of [`Reader::get_with_options`]. Rather than looking the keys up one by one,
+    /// the keys are grouped by the SSTs that may hold them, so that the filter and index
+    /// of each SST are read once and the blocks of all its keys are fetched together.
+    /// SSTs are read concurrently.
+    ///
+    /// Arguments are the same as for [`Reader::get_with_options`], except for `keys`,
+    /// which may contain duplicates.
+    pub(crate) async fn get_many_with_options(
+        &self,
+        column_family: u32,
+        keys: &[Bytes],
+        options: &ReadOptions,
+        db_state: &(dyn DbStateReader + Sync + Send),
+        write_batch: Option<WriteBatch>,
+        max_seq: Option<u64>,
+    ) -> Result<Vec<Option<Bytes>>, SlateDBError> {
+        let now = get_now_for_read(self.mono_clock.clone(), options.durability_filter).await?;
+        let as_of_seq =
+            self.resolve_as_of_seq(options.as_of_seq, options.as_of_timestamp, db_state)?;
+        let max_seq = max_seq.into_iter().chain(as_of_seq).min();
+        let max_seq = self.prepare_max_seq(max_seq, options.durability_filter, options.dirty);
+        let write_batch = write_batch.and_then(|batch| batch.into_column_family(column_family));
+        let unique_keys: BTreeSet<Bytes> = keys.iter().cloned().collect();
+
+        // Every L0 SST may hold any of the keys, while each sorted run has at most one
+        // SST covering a given key.
+        let core = db_state.core();
+        let l0 = core.column_family_l0(column_family);
+        let mut lookups: Vec<(SsTableHandle, Vec<Bytes>)> = l0
+            .iter()
+            .map(|sst| (sst.clone(), unique_keys.iter().cloned().collect()))
+            .collect();
+        for sr in core.column_family_compacted(column_family) {
+            let mut sr_lookups: BTreeMap<usize, Vec<Bytes>> = BTreeMap::new();
+            for key in &unique_keys {
+                if let Some(idx) =
+                    sr.find_sst_with_range_covering_key_idx(key, self.table_store.comparator())
+                {
+                    sr_lookups.entry(idx).or_default().push(key.clone());
+                }
+            }
+            lookups.extend(
+                sr_lookups
+                    .into_iter()
+                    .map(|(idx, keys)| (sr.ssts[idx].clone(), keys)),
+            );
+        }
+
+        let max_parallel =
+            compute_max_parallel(l0.len(), core.column_family_compacted(column_family), 4);
+        let table_store = self.table_store.clone();
+        let db_stats = self.db_stats.clone();
+        let mut sst_blocks: Vec<HashMap<Bytes, Vec<Arc<Block>>>> =
+            vec![HashMap::new(); lookups.len()];
+        let fetched = build_concurrent(
+            lookups.iter().cloned().enumerate(),
+            max_parallel,
+            move |(position, (sst, keys))| {
+                let table_store = table_store.clone();
+                let db_stats = db_stats.clone();
+                async move {
+                    let blocks = Self::read_point_blocks(table_store, sst, keys, db_stats).await?;
+                    Ok(Some((position, blocks)))
+                }
+            },
+        )
+        .await?;
+        for (position, blocks) in fetched {
+            sst_blocks[position] = blocks;
+        }
+
+        let mut values = HashMap::with_capacity(unique_keys.len());
+        for key in unique_keys {
+            let range = BytesRange::from(key.clone()..=key.clone());
+            let range_tombstones = Self::collect_range_tombstones(
+                column_family,
+                &range,
+                db_state,
+                write_batch.as_ref(),
+                max_seq,
+            );
+            let mut l0_iters = VecDeque::new();
+            let mut sr_iters = VecDeque::new();
+            for (position, blocks) in sst_blocks.iter_mut().enumerate() {
+                if let Some(blocks) = blocks.remove(&key) {
+                    let iter = Box::new(
+                        PointBlocksIterator::new(
+                            key.clone(),
+                            blocks,
+                            self.table_store.comparator().clone(),
+                        )
+                        .with_blob_refs_resolved(Arc::clone(&self.table_store)),
+                    ) as Box<dyn KeyValueIterator + 'static>;
+                    if position < l0.len() {
+                        l0_iters.push_back(iter);
+                    } else {
+                        sr_iters.push_back(iter);
+                    }
+                }
+            }
+            let IteratorSources {
+                write_batch_iter,
+                mem_iters,
+                l0_iters,
+                sr_iters,
+            } = IteratorSources {
+                write_batch_iter: write_batch.clone().map(|batch| {
+                    WriteBatchIterator::new_with_comparator(
+                        batch,
+                        range.clone(),
+                        IterationOrder::Ascending,
+                        self.table_store.comparator().clone(),
+                    )
+                }),
+                mem_iters: Self::memtables(column_family, db_state)
+                    .iter()
+                    .map(|table| {
+                        Box::new(table.range(range.clone(), IterationOrder::Ascending))
+                            as Box<dyn KeyValueIterator + 'static>
+                    })
+                    .collect(),
+                l0_iters,
+                sr_iters,
+            }
+            .with_range_tombstones(range_tombstones);
+
+            let mut iterator = DbIterator::new(
+                range,
+                write_batch_iter,
+                mem_iters,
+                l0_iters,
+                sr_iters,
+                max_seq,
+                None,
+                now,
+                self.merge_operator(column_family),
+                IterationOrder::Ascending,
+                self.table_store.comparator().clone(),
+            )
+            .await?;
+            let value = match iterator.next_key_value().await? {
+                Some(entry) if entry.key == key => Some(entry.value),
+                _ => None,
+            };
+            values.insert(key, value);
+        }
+
+        Ok(keys.iter().map(|key| values[key].clone()).collect())
+    }
+
+    /// Reads the blocks of `sst` that may hold versions of `keys`. The SST's filter and
+    /// index are read once for all the keys, and adjacent blocks are fetched together.
+    /// Returns the blocks of each key that's in the SST.
+    async fn read_point_blocks(
+        table_store: Arc<TableStore>,
+        sst: SsTableHandle,
+        keys: Vec<Bytes>,
+        db_stats: DbStats,
+    ) -> Result<HashMap<Bytes, Vec<Arc<Block>>>, SlateDBError> {
+        let comparator = table_store.comparator().clone();
+        let mut keys: Vec<Bytes> = keys
+            .into_iter()
+            .filter(|key| {
+                sst.calculate_view_range(BytesRange::from(key.clone()..=key.clone()), &comparator)
+                    .is_some()
+            })
+            .collect();
+        if keys.is_empty() {
+            return Ok(HashMap::new());
+        }
+        let filter = table_store.read_filter(&sst).await?;
+        if let Some(filter) = &filter {
+            keys.retain(|key| {
+                let positive = filter.might_contain(filter::filter_hash(key));
+                if positive {
+                    db_stats.sst_filter_positives.inc();
+                } else {
+                    db_stats.sst_filter_negatives.inc();
+                }
+                positive
+            });
+            if keys.is_empty() {
+                return Ok(HashMap::new());
+            }
+        }
+
+        let index = table_store.read_index(&sst).await?;
+        let key_blocks: Vec<(Bytes, Range<usize>)> = {
+            let index = index.borrow();
+            keys.into_iter()
+                .map(|key| {
+                    let start = partitioned_keyspace::first_partition_including_or_after_key(
+                        &index,
+                        &key,
+                        &comparator,
+                    );
+                    let end = partitioned_keyspace::last_partition_including_key(
+                        &index,
+                        &key,
+                        &comparator,
+                    )
+                    .map(|block| block + 1)
+                    .unwrap_or(start);
+                    (key, start..end)
+                })
+                .filter(|(_, blocks)| !blocks.is_empty())
+                .collect()
+        };
+
+        // fetch each run of contiguous blocks with a single read
+        let block_ids: BTreeSet<usize> = key_blocks
+            .iter()
+            .flat_map(|(_, blocks)| blocks.clone())
+            .collect();
+        let mut runs: Vec<Range<usize>> = Vec::new();
+        for block_id in block_ids {
+            match runs.last_mut() {
+                Some(run) if run.end == block_id => run.end += 1,
+                _ => runs.push(block_id..block_id + 1),
+            }
+        }
+        let mut blocks = HashMap::new();
+        for run in runs {
+            let fetched = table_store
+                .read_blocks_using_index(&sst, index.clone(), run.clone(), true)
+                .await?;
+            blocks.extend(run.zip(fetched));
+        }
+
+        let mut result = HashMap::with_capacity(key_blocks.len());
+        for (key, block_ids) in key_blocks {
+            let key_blocks: Vec<Arc<Block>> = block_ids
+                .map(|block_id| blocks[&block_id].clone())
+                .collect();
+            let mut iter =
+                PointBlocksIterator::new(key.clone(), key_blocks.clone(), comparator.clone());
+            if iter.next_entry().await?.is_none() {
+                if filter.is_some() {
+                    db_stats.sst_filter_false_positives.inc();
+                }
+                continue;
+            }
+            result.insert(key, key_blocks);
+        }
+        Ok(result)
+    }
+
     /// Create an iterator over a key range.
     ///
     /// Produces a merged iterator over the provided `write_batch` (if any),
@@ -335,6 +987,7 @@ impl Reader {
     /// expired, non-tombstone values.
     ///
     /// Arguments
+    /// - `column_family`: The id of the column family to scan.
     /// - `range`: The half-open key range to scan (start inclusive, end
     ///   exclusive).
     /// - `options`: Options for the scan, including read-ahead, caching, and the
@@ -346,8 +999,10 @@ impl Reader {
     /// - `max_seq`: Optional upper bound on the sequence number visibility for
     ///   the scan. If provided, entries with a greater sequence number are
     ///   filtered out by the iterator construction.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn scan_with_options(
         &self,
+        column_family: u32,
         range: BytesRange,
         options: &ScanOptions,
         db_state: &(dyn DbStateReader + Sync),
@@ -355,9 +1010,23 @@ impl Reader {
         max_seq: Option<u64>,
         range_tracker: Option<Arc<DbIteratorRangeTracker>>,
     ) -> Result<DbIterator, SlateDBError> {
+        let as_of_seq =
+            self.resolve_as_of_seq(options.as_of_seq, options.as_of_timestamp, db_state)?;
+        let max_seq = max_seq.into_iter().chain(as_of_seq).min();
         let max_seq = self.prepare_max_seq(max_seq, options.durability_filter, options.dirty);
         let now = get_now_for_read(self.mono_clock.clone(), options.durability_filter).await?;
 
+        if options.prefix.is_some() && !self.table_store.comparator().is_bytewise() {
+            return Err(SlateDBError::UnsupportedWithComparator("prefix scan"));
+        }
+        let range = match &options.prefix {
+            Some(prefix) => range
+                .intersect(&BytesRange::from_prefix(prefix))
+                .unwrap_or_else(BytesRange::new_empty),
+            None => range,
+        };
+        let prefix_filter = self.scan_prefix_filter(options.prefix.as_ref());
+
         let read_ahead_blocks = self.table_store.bytes_to_blocks(options.read_ahead_bytes);
 
         let sst_iter_options = SstIteratorOptions {
@@ -365,6 +1034,8 @@ impl Reader {
             blocks_to_fetch: read_ahead_blocks,
             cache_blocks: options.cache_blocks,
             eager_spawn: true,
+            order: options.order,
+            ..SstIteratorOptions::default()
         };
 
         let IteratorSources {
@@ -373,7 +1044,16 @@ impl Reader {
             l0_iters,
             sr_iters,
         } = self
-            .build_iterator_sources(&range, db_state, write_batch, sst_iter_options, None)
+            .build_iterator_sources(
+                column_family,
+                &range,
+                db_state,
+                write_batch,
+                sst_iter_options,
+                None,
+                prefix_filter,
+                max_seq,
+            )
             .await?;
 
         DbIterator::new(
@@ -385,7 +1065,9 @@ impl Reader {
             max_seq,
             range_tracker,
             now,
-            self.merge_operator.clone(),
+            self.merge_operator(column_family),
+            options.order,
+            self.table_store.comparator().clone(),
         )
         .await
     }
@@ -399,7 +1081,7 @@ mod tests {
     use rstest::rstest;
 
     use crate::batch::WriteBatch;
-    use crate::clock::{LogicalClock, MonotonicClock};
+    use crate::clock::{DefaultSystemClock, LogicalClock, MonotonicClock};
     use crate::db_state::{SortedRun, SsTableHandle, SsTableId};
     use crate::object_stores::ObjectStores;
     use crate::oracle::DbReaderOracle;
@@ -645,6 +1327,9 @@ mod tests {
                             ValueDeletable::Merge(v) => {
                                 batch.merge(entry.key, v.as_ref());
                             }
+                            ValueDeletable::BlobRef(_) => {
+                                unreachable!("test entries don't point to blob files")
+                            }
                         }
                     }
                 }
@@ -1270,6 +1955,7 @@ mod tests {
         // Create test database state and populate it
         let mut test_db_state = TestDbState::new().await;
         let write_batch = populate_db_state(&mut test_db_state, test_case.entries).await?;
+        let write_batch_for_get_many = write_batch.clone();
 
         // Create Reader with test clock
         let stat_registry = StatRegistry::new();
@@ -1300,12 +1986,17 @@ mod tests {
             mono_clock,
             oracle,
             merge_operator,
+            column_family_merge_operators: HashMap::new(),
+            prefix_extractor: None,
+            history_retention: RwLock::new(None),
+            system_clock: Arc::new(DefaultSystemClock::new()),
         };
 
         // Call the actual get_with_options method
         let read_options = ReadOptions::default().with_dirty(test_case.dirty);
         let result = reader
             .get_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
                 test_case.query_key,
                 &read_options,
                 &test_db_state,
@@ -1325,6 +2016,29 @@ mod tests {
             expected.map(|b| String::from_utf8_lossy(b))
         );
 
+        // a multi-get of the key should see the same value, whatever the other keys are
+        let keys = [
+            Bytes::from_static(test_case.query_key),
+            Bytes::from_static(b"missing"),
+            Bytes::from_static(test_case.query_key),
+        ];
+        let results = reader
+            .get_many_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
+                &keys,
+                &read_options,
+                &test_db_state,
+                write_batch_for_get_many,
+                test_case.max_seq,
+            )
+            .await?;
+        assert_eq!(
+            results,
+            vec![result.clone(), None, result],
+            "Failed test: {}",
+            test_case.description
+        );
+
         Ok(())
     }
 
@@ -1769,6 +2483,10 @@ mod tests {
             mono_clock,
             oracle,
             merge_operator,
+            column_family_merge_operators: HashMap::new(),
+            prefix_extractor: None,
+            history_retention: RwLock::new(None),
+            system_clock: Arc::new(DefaultSystemClock::new()),
         };
 
         // Create range
@@ -1778,6 +2496,7 @@ mod tests {
         let scan_options = ScanOptions::default().with_dirty(test_case.dirty);
         let mut iter = reader
             .scan_with_options(
+                DEFAULT_COLUMN_FAMILY_ID,
                 range,
                 &scan_options,
                 &test_db_state,
@@ -0,0 +1,882 @@
+//! Running compaction jobs outside of the compactor's process.
+//!
+//! By default the compactor runs its jobs on a local tokio runtime. When it is given a
+//! [`CompactionJobRunner`], it instead hands each job to the runner as a serializable
+//! [`CompactionJob`] and waits for the runner to return the job's [`CompactionJobResult`].
+//! Compactions can then be spread over any number of worker processes, while the writer
+//! and the compactor's scheduling stay unchanged.
+//!
+//! [`ObjectStoreCompactionJobRunner`] is a runner that uses the DB's object store as the job
+//! queue, and [`CompactionWorker`] is the worker that serves it:
+//!
+//! 1. The runner writes the job to `compaction_jobs/jobs/<job id>.job`.
+//! 2. A worker claims the job by creating `compaction_jobs/claims/<job id>.claim`, which
+//!    fails if another worker already claimed it. While it runs the job, the worker renews
+//!    the claim by rewriting it with the current time.
+//! 3. The worker writes the job's result to `compaction_jobs/results/<job id>.result`.
+//! 4. The runner reads the result and deletes the job's files.
+//!
+//! If a claim is not renewed within [`CompactionJobQueueOptions::claim_timeout`], the runner
+//! deletes it so that another worker can take the job over. Two workers may then both
+//! finish the job; only the first result is kept, and the output SSTs of the other worker
+//! are never referenced and get garbage collected.
+
+use std::collections::HashMap;
+use std::sync::atomic::{self, AtomicBool};
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use bytes::Bytes;
+use chrono::{DateTime, Utc};
+use futures::future::join_all;
+use futures::StreamExt;
+use log::{debug, error, info, warn};
+use object_store::path::Path;
+use object_store::prefix::PrefixStore;
+use object_store::{ObjectStore, PutMode, PutOptions, PutPayload};
+use parking_lot::Mutex;
+use tokio::runtime::Handle;
+use tokio::task::JoinHandle;
+use ulid::Ulid;
+
+use crate::clock::{DefaultSystemClock, SystemClock};
+use crate::column_family::ColumnFamilyOptions;
+use crate::compaction_filter::CompactionFilterType;
+use crate::compactor::stats::CompactionStats;
+use crate::compactor::CompactorMessage;
+use crate::compactor_executor::{
+    CompactionExecutor, StartCompactionJobArgs, TokioCompactionExecutor,
+};
+use crate::config::{CompactionJobQueueOptions, CompactorOptions};
+use crate::db_state::{SortedRun, SsTableHandle};
+use crate::error::SlateDBError;
+use crate::flatbuffer_types::FlatBufferCompactionJobCodec;
+use crate::manifest::store::{ManifestStore, StoredManifest};
+use crate::merge_operator::MergeOperatorType;
+use crate::rand::DbRand;
+use crate::tablestore::TableStore;
+use crate::utils::spawn_bg_task;
+
+pub use crate::db::builder::CompactionWorkerBuilder;
+
+const COMPACTION_JOBS_PATH: &str = "compaction_jobs";
+const JOBS_PATH: &str = "jobs";
+const CLAIMS_PATH: &str = "claims";
+const RESULTS_PATH: &str = "results";
+
+/// A single attempt at running a compaction. The job carries all of its inputs, and can be
+/// encoded to bytes to be run by another process.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompactionJob {
+    pub(crate) args: StartCompactionJobArgs,
+}
+
+impl CompactionJob {
+    /// Returns the id of the job.
+    pub fn id(&self) -> Ulid {
+        self.args.id
+    }
+
+    /// Returns the id of the compaction the job belongs to.
+    pub fn compaction_id(&self) -> Ulid {
+        self.args.compaction_id
+    }
+
+    /// Encodes the job.
+    pub fn encode(&self) -> Bytes {
+        FlatBufferCompactionJobCodec::encode_job(&self.args)
+    }
+
+    /// Decodes a job encoded with [`CompactionJob::encode`].
+    pub fn decode(bytes: &Bytes) -> Result<Self, crate::Error> {
+        Ok(Self {
+            args: FlatBufferCompactionJobCodec::decode_job(bytes)?,
+        })
+    }
+}
+
+/// The result of a [`CompactionJob`]: either the SSTs of the sorted run the job wrote, or
+/// the reason the job failed.
+#[derive(Clone, Debug, PartialEq)]
+pub struct CompactionJobResult {
+    pub(crate) id: Ulid,
+    pub(crate) output: Result<Vec<SsTableHandle>, String>,
+}
+
+impl CompactionJobResult {
+    /// Returns the id of the job.
+    pub fn id(&self) -> Ulid {
+        self.id
+    }
+
+    /// Returns the reason the job failed, or `None` if the job succeeded.
+    pub fn error(&self) -> Option<&str> {
+        self.output.as_ref().err().map(|error| error.as_str())
+    }
+
+    /// Encodes the result.
+    pub fn encode(&self) -> Bytes {
+        FlatBufferCompactionJobCodec::encode_result(self)
+    }
+
+    /// Decodes a result encoded with [`CompactionJobResult::encode`].
+    pub fn decode(bytes: &Bytes) -> Result<Self, crate::Error> {
+        Ok(FlatBufferCompactionJobCodec::decode_result(bytes)?)
+    }
+}
+
+/// Runs compaction jobs on behalf of the compactor, typically by handing them to workers
+/// in other processes (see [`CompactionWorker::run_job`]).
+#[async_trait]
+pub trait CompactionJobRunner: Send + Sync {
+    /// Runs the job to completion and returns its result.
+    ///
+    /// Returning an error, or a failed result, fails the compaction. The compactor may then
+    /// schedule it again.
+    async fn run(&self, job: CompactionJob) -> Result<CompactionJobResult, crate::Error>;
+}
+
+/// A [`CompactionExecutor`] that runs each job with a [`CompactionJobRunner`].
+pub(crate) struct RemoteCompactionExecutor {
+    inner: Arc<RemoteCompactionExecutorInner>,
+}
+
+impl RemoteCompactionExecutor {
+    pub(crate) fn new(
+        handle: Handle,
+        runner: Arc<dyn CompactionJobRunner>,
+        worker_tx: tokio::sync::mpsc::UnboundedSender<CompactorMessage>,
+        stats: Arc<CompactionStats>,
+    ) -> Self {
+        Self {
+            inner: Arc::new(RemoteCompactionExecutorInner {
+                handle,
+                runner,
+                worker_tx,
+                tasks: Mutex::new(HashMap::new()),
+                stats,
+                is_stopped: AtomicBool::new(false),
+            }),
+        }
+    }
+}
+
+impl CompactionExecutor for RemoteCompactionExecutor {
+    fn start_compaction_job(&self, compaction: StartCompactionJobArgs) {
+        self.inner.start_compaction_job(compaction);
+    }
+
+    fn stop(&self) {
+        self.inner.stop()
+    }
+
+    fn is_stopped(&self) -> bool {
+        self.inner.is_stopped.load(atomic::Ordering::SeqCst)
+    }
+}
+
+struct RemoteCompactionTask {
+    task: JoinHandle<Result<SortedRun, SlateDBError>>,
+}
+
+struct RemoteCompactionExecutorInner {
+    handle: Handle,
+    runner: Arc<dyn CompactionJobRunner>,
+    worker_tx: tokio::sync::mpsc::UnboundedSender<CompactorMessage>,
+    /// Running jobs by column family and destination sorted run id.
+    tasks: Mutex<HashMap<(u32, u32), RemoteCompactionTask>>,
+    stats: Arc<CompactionStats>,
+    is_stopped: AtomicBool,
+}
+
+impl RemoteCompactionExecutorInner {
+    /// Hands the job to the runner and converts its result to the destination [`SortedRun`].
+    async fn run_compaction_job(
+        &self,
+        args: StartCompactionJobArgs,
+    ) -> Result<SortedRun, SlateDBError> {
+        let id = args.id;
+        let destination = args.destination;
+        debug!("handing compaction job to runner [job_args={:?}]", args);
+        let result = self
+            .runner
+            .run(CompactionJob { args })
+            .await
+            .map_err(|err| SlateDBError::CompactionJobFailed {
+                id,
+                message: err.to_string(),
+            })?;
+        if result.id != id {
+            return Err(SlateDBError::CompactionJobFailed {
+                id,
+                message: format!("runner returned the result of job {}", result.id),
+            });
+        }
+        let ssts = result
+            .output
+            .map_err(|message| SlateDBError::CompactionJobFailed { id, message })?;
+        for sst in ssts.iter() {
+            self.stats.bytes_compacted.add(sst.info.filter_offset);
+        }
+        Ok(SortedRun {
+            id: destination,
+            ssts,
+        })
+    }
+
+    /// Starts a background task that waits for the runner to finish the job.
+    fn start_compaction_job(self: &Arc<Self>, args: StartCompactionJobArgs) {
+        let mut tasks = self.tasks.lock();
+        if self.is_stopped.load(atomic::Ordering::SeqCst) {
+            return;
+        }
+        let dst = (args.column_family, args.destination);
+        self.stats.running_compactions.inc();
+        assert!(!tasks.contains_key(&dst));
+
+        let id = args.id;
+
+        let this = self.clone();
+        let this_cleanup = self.clone();
+        let task = spawn_bg_task(
+            "remote_compaction_executor".to_string(),
+            &self.handle,
+            move |result| {
+                let result = result.clone();
+                {
+                    let mut tasks = this_cleanup.tasks.lock();
+                    tasks.remove(&dst);
+                }
+                // See TokioCompactionExecutor for why send() is allowed.
+                #[allow(clippy::disallowed_methods)]
+                this_cleanup
+                    .worker_tx
+                    .send(CompactorMessage::CompactionJobFinished { id, result })
+                    .expect("failed to send compaction finished msg");
+                this_cleanup.stats.running_compactions.dec();
+            },
+            async move { this.run_compaction_job(args).await },
+        );
+        tasks.insert(dst, RemoteCompactionTask { task });
+    }
+
+    /// Stops waiting for the running jobs. The jobs themselves are left to the runner.
+    fn stop(&self) {
+        let task_handles = {
+            let mut tasks = self.tasks.lock();
+            for task in tasks.values() {
+                task.task.abort();
+            }
+            tasks.drain().map(|(_, task)| task.task).collect::<Vec<_>>()
+        };
+
+        self.handle.block_on(async {
+            let results = join_all(task_handles).await;
+            for result in results {
+                match result {
+                    Err(e) if !e.is_cancelled() => {
+                        error!("shutdown error in compaction task [error={:?}]", e);
+                    }
+                    _ => {}
+                }
+            }
+        });
+
+        self.is_stopped.store(true, atomic::Ordering::SeqCst);
+    }
+}
+
+/// The files of the object store compaction job queue of a DB.
+pub(crate) struct CompactionJobQueue {
+    object_store: Arc<dyn ObjectStore>,
+}
+
+impl CompactionJobQueue {
+    pub(crate) fn new(root_path: &Path, object_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            object_store: Arc::new(PrefixStore::new(
+                object_store,
+                root_path.child(COMPACTION_JOBS_PATH),
+            )),
+        }
+    }
+
+    fn job_path(id: Ulid) -> Path {
+        Path::from(format!("{}/{}.job", JOBS_PATH, id))
+    }
+
+    fn claim_path(id: Ulid) -> Path {
+        Path::from(format!("{}/{}.claim", CLAIMS_PATH, id))
+    }
+
+    fn result_path(id: Ulid) -> Path {
+        Path::from(format!("{}/{}.result", RESULTS_PATH, id))
+    }
+
+    pub(crate) async fn submit(&self, job: &CompactionJob) -> Result<(), SlateDBError> {
+        self.object_store
+            .put(
+                &Self::job_path(job.id()),
+                PutPayload::from_bytes(job.encode()),
+            )
+            .await?;
+        Ok(())
+    }
+
+    /// Lists the ids of the queued jobs, oldest first.
+    pub(crate) async fn list_jobs(&self) -> Result<Vec<Ulid>, SlateDBError> {
+        let mut files_stream = self.object_store.list(Some(&Path::from(JOBS_PATH)));
+        let mut ids = Vec::new();
+        while let Some(file) = files_stream.next().await.transpose()? {
+            match file
+                .location
+                .filename()
+                .and_then(|name| name.strip_suffix(".job"))
+                .map(Ulid::from_string)
+            {
+                Some(Ok(id)) => ids.push(id),
+                _ => warn!(
+                    "unknown file in compaction job queue [location={}]",
+                    file.location
+                ),
+            }
+        }
+        ids.sort();
+        Ok(ids)
+    }
+
+    pub(crate) async fn read_job(&self, id: Ulid) -> Result<Option<CompactionJob>, SlateDBError> {
+        self.try_get(&Self::job_path(id))
+            .await?
+            .map(|bytes| {
+                Ok(CompactionJob {
+                    args: FlatBufferCompactionJobCodec::decode_job(&bytes)?,
+                })
+            })
+            .transpose()
+    }
+
+    pub(crate) async fn has_job(&self, id: Ulid) -> Result<bool, SlateDBError> {
+        self.exists(&Self::job_path(id)).await
+    }
+
+    /// Claims a job for a worker. Returns false if the job has already been claimed.
+    pub(crate) async fn try_claim(
+        &self,
+        id: Ulid,
+        now: DateTime<Utc>,
+    ) -> Result<bool, SlateDBError> {
+        match self
+            .object_store
+            .put_opts(
+                &Self::claim_path(id),
+                Self::encode_claim(now),
+                PutOptions::from(PutMode::Create),
+            )
+            .await
+        {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::AlreadyExists { .. }) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub(crate) async fn renew_claim(
+        &self,
+        id: Ulid,
+        now: DateTime<Utc>,
+    ) -> Result<(), SlateDBError> {
+        self.object_store
+            .put(&Self::claim_path(id), Self::encode_claim(now))
+            .await?;
+        Ok(())
+    }
+
+    /// Returns the time a job's claim was last renewed, or `None` if the job is unclaimed.
+    pub(crate) async fn read_claim(&self, id: Ulid) -> Result<Option<DateTime<Utc>>, SlateDBError> {
+        let Some(bytes) = self.try_get(&Self::claim_path(id)).await? else {
+            return Ok(None);
+        };
+        let millis = <[u8; 8]>::try_from(bytes.as_ref())
+            .map(i64::from_be_bytes)
+            .map_err(|_| SlateDBError::InvalidDBState)?;
+        DateTime::<Utc>::from_timestamp_millis(millis)
+            .map(Some)
+            .ok_or(SlateDBError::InvalidDBState)
+    }
+
+    pub(crate) async fn release_claim(&self, id: Ulid) -> Result<(), SlateDBError> {
+        self.delete_if_exists(&Self::claim_path(id)).await
+    }
+
+    /// Writes the result of a job. If another worker already wrote a result for the job,
+    /// that result is kept.
+    pub(crate) async fn write_result(
+        &self,
+        result: &CompactionJobResult,
+    ) -> Result<(), SlateDBError> {
+        match self
+            .object_store
+            .put_opts(
+                &Self::result_path(result.id),
+                PutPayload::from_bytes(result.encode()),
+                PutOptions::from(PutMode::Create),
+            )
+            .await
+        {
+            Ok(_) | Err(object_store::Error::AlreadyExists { .. }) => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    pub(crate) async fn has_result(&self, id: Ulid) -> Result<bool, SlateDBError> {
+        self.exists(&Self::result_path(id)).await
+    }
+
+    pub(crate) async fn read_result(
+        &self,
+        id: Ulid,
+    ) -> Result<Option<CompactionJobResult>, SlateDBError> {
+        self.try_get(&Self::result_path(id))
+            .await?
+            .map(|bytes| FlatBufferCompactionJobCodec::decode_result(&bytes))
+            .transpose()
+    }
+
+    /// Deletes the files of a job. The job file goes first, so that no worker picks the
+    /// job up again.
+    pub(crate) async fn remove_job(&self, id: Ulid) -> Result<(), SlateDBError> {
+        self.delete_if_exists(&Self::job_path(id)).await?;
+        self.delete_if_exists(&Self::result_path(id)).await?;
+        self.delete_if_exists(&Self::claim_path(id)).await
+    }
+
+    fn encode_claim(now: DateTime<Utc>) -> PutPayload {
+        PutPayload::from(now.timestamp_millis().to_be_bytes().to_vec())
+    }
+
+    async fn try_get(&self, path: &Path) -> Result<Option<Bytes>, SlateDBError> {
+        match self.object_store.get(path).await {
+            Ok(result) => Ok(Some(result.bytes().await?)),
+            Err(object_store::Error::NotFound { .. }) => Ok(None),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn exists(&self, path: &Path) -> Result<bool, SlateDBError> {
+        match self.object_store.head(path).await {
+            Ok(_) => Ok(true),
+            Err(object_store::Error::NotFound { .. }) => Ok(false),
+            Err(err) => Err(err.into()),
+        }
+    }
+
+    async fn delete_if_exists(&self, path: &Path) -> Result<(), SlateDBError> {
+        match self.object_store.delete(path).await {
+            Ok(_) | Err(object_store::Error::NotFound { .. }) => Ok(()),
+            Err(err) => Err(err.into()),
+        }
+    }
+}
+
+/// A [`CompactionJobRunner`] that queues jobs in the DB's object store, to be run by
+/// [`CompactionWorker`]s.
+pub struct ObjectStoreCompactionJobRunner {
+    queue: CompactionJobQueue,
+    options: CompactionJobQueueOptions,
+    system_clock: Arc<dyn SystemClock>,
+}
+
+impl ObjectStoreCompactionJobRunner {
+    /// Creates a runner for the DB at `path` in `object_store`.
+    pub fn new<P: Into<Path>>(
+        path: P,
+        object_store: Arc<dyn ObjectStore>,
+        options: CompactionJobQueueOptions,
+    ) -> Self {
+        // the queue is polled, so missing jobs and results must not be retried
+        Self {
+            queue: CompactionJobQueue::new(&path.into(), object_store),
+            options,
+            system_clock: Arc::new(DefaultSystemClock::default()),
+        }
+    }
+
+    /// Sets the system clock used to poll for results and to expire claims.
+    pub fn with_system_clock(mut self, system_clock: Arc<dyn SystemClock>) -> Self {
+        self.system_clock = system_clock;
+        self
+    }
+
+    /// Deletes the claim on a job if its worker has not renewed it within the claim
+    /// timeout, so that another worker can take the job over.
+    async fn expire_stale_claim(&self, id: Ulid) -> Result<(), SlateDBError> {
+        let Some(renewed) = self.queue.read_claim(id).await? else {
+            return Ok(());
+        };
+        let claim_timeout = chrono::Duration::from_std(self.options.claim_timeout)
+            .expect("claim timeout out of range");
+        if renewed + claim_timeout < self.system_clock.now() {
+            info!(
+                "compaction job claim expired, releasing job [id={}, renewed={}]",
+                id, renewed
+            );
+            self.queue.release_claim(id).await?;
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl CompactionJobRunner for ObjectStoreCompactionJobRunner {
+    async fn run(&self, job: CompactionJob) -> Result<CompactionJobResult, crate::Error> {
+        let id = job.id();
+        self.queue.submit(&job).await?;
+        loop {
+            self.system_clock.sleep(self.options.poll_interval).await;
+            if let Some(result) = self.queue.read_result(id).await? {
+                self.queue.remove_job(id).await?;
+                return Ok(result);
+            }
+            self.expire_stale_claim(id).await?;
+        }
+    }
+}
+
+/// Runs the compaction jobs that compactors queue in the object store of a DB with an
+/// [`ObjectStoreCompactionJobRunner`]. Any number of workers can serve the same DB.
+///
+/// Workers must be configured with the same [`CompactorOptions`], merge operator,
+/// compaction filter, and column family options as the compactor.
+pub struct CompactionWorker {
+    queue: CompactionJobQueue,
+    queue_options: CompactionJobQueueOptions,
+    options: Arc<CompactorOptions>,
+    manifest_store: Arc<ManifestStore>,
+    table_store: Arc<TableStore>,
+    rand: Arc<DbRand>,
+    stats: Arc<CompactionStats>,
+    system_clock: Arc<dyn SystemClock>,
+    merge_operator: Option<MergeOperatorType>,
+    compaction_filter: Option<CompactionFilterType>,
+    column_families: HashMap<String, ColumnFamilyOptions>,
+}
+
+impl CompactionWorker {
+    pub(crate) fn new(
+        queue: CompactionJobQueue,
+        queue_options: CompactionJobQueueOptions,
+        options: CompactorOptions,
+        manifest_store: Arc<ManifestStore>,
+        table_store: Arc<TableStore>,
+        rand: Arc<DbRand>,
+        stats: Arc<CompactionStats>,
+        system_clock: Arc<dyn SystemClock>,
+        merge_operator: Option<MergeOperatorType>,
+        compaction_filter: Option<CompactionFilterType>,
+        column_families: HashMap<String, ColumnFamilyOptions>,
+    ) -> Self {
+        Self {
+            queue,
+            queue_options,
+            options: Arc::new(options),
+            manifest_store,
+            table_store,
+            rand,
+            stats,
+            system_clock,
+            merge_operator,
+            compaction_filter,
+            column_families,
+        }
+    }
+
+    /// Creates a builder for a worker serving the DB at `path` in `object_store`.
+    pub fn builder<P: Into<Path>>(
+        path: P,
+        object_store: Arc<dyn ObjectStore>,
+    ) -> CompactionWorkerBuilder<P> {
+        CompactionWorkerBuilder::new(path, object_store)
+    }
+
+    /// Runs a compaction job in this process and returns its result. Custom
+    /// [`CompactionJobRunner`]s can use this to run the jobs they receive.
+    pub async fn run_job(&self, job: CompactionJob) -> CompactionJobResult {
+        let id = job.id();
+        let output = self.execute_job(job).await.map_err(|err| {
+            error!(
+                "error executing compaction job [id={}, error={:?}]",
+                id, err
+            );
+            err.to_string()
+        });
+        CompactionJobResult { id, output }
+    }
+
+    async fn execute_job(&self, job: CompactionJob) -> Result<Vec<SsTableHandle>, SlateDBError> {
+        // merging with another comparator than the database's would write SSTs with
+        // keys in the wrong order
+        let manifest = StoredManifest::load(self.manifest_store.clone()).await?;
+        self.table_store
+            .comparator()
+            .check_matches(manifest.db_state().comparator.as_deref())?;
+        // the executor reports progress and output SSTs on the channel, which only the
+        // compactor is interested in
+        let (tx, _rx) = tokio::sync::mpsc::unbounded_channel();
+        let executor = TokioCompactionExecutor::new(
+            Handle::current(),
+            self.options.clone(),
+            tx,
+            self.table_store.clone(),
+            self.rand.clone(),
+            self.stats.clone(),
+            self.system_clock.clone(),
+            self.manifest_store.clone(),
+            self.merge_operator.clone(),
+            self.compaction_filter.clone(),
+            self.column_families.clone(),
+        );
+        executor
+            .execute_compaction_job(job.args)
+            .await
+            .map(|sorted_run| sorted_run.ssts)
+    }
+
+    /// Claims the oldest unclaimed job in the queue, runs it, and writes its result.
+    ///
+    /// ## Returns
+    /// - `Ok(true)` if a job was run, or `Ok(false)` if there was no job to claim.
+    pub async fn run_once(&self) -> Result<bool, crate::Error> {
+        for id in self.queue.list_jobs().await? {
+            if self.queue.has_result(id).await?
+                || !self.queue.try_claim(id, self.system_clock.now()).await?
+            {
+                continue;
+            }
+            let Some(job) = self.queue.read_job(id).await? else {
+                // the compactor removed the job after we listed it
+                self.queue.release_claim(id).await?;
+                continue;
+            };
+            info!("running compaction job [id={}]", id);
+            let result = self.run_claimed_job(job).await?;
+            if self.queue.has_job(id).await? {
+                self.queue.write_result(&result).await?;
+            } else {
+                debug!("compaction job removed while running [id={}]", id);
+            }
+            return Ok(true);
+        }
+        Ok(false)
+    }
+
+    /// Runs jobs from the queue until an error occurs, polling for new jobs while idle.
+    pub async fn run(&self) -> Result<(), crate::Error> {
+        loop {
+            if !self.run_once().await? {
+                self.system_clock
+                    .sleep(self.queue_options.poll_interval)
+                    .await;
+            }
+        }
+    }
+
+    /// Runs a job, renewing its claim four times per claim timeout.
+    async fn run_claimed_job(
+        &self,
+        job: CompactionJob,
+    ) -> Result<CompactionJobResult, SlateDBError> {
+        let id = job.id();
+        let job_future = self.run_job(job);
+        tokio::pin!(job_future);
+        let mut ticker = self
+            .system_clock
+            .ticker(self.queue_options.claim_timeout / 4);
+        // the first tick completes immediately, and the claim is fresh
+        ticker.tick().await;
+        loop {
+            tokio::select! {
+                result = &mut job_future => return Ok(result),
+                _ = ticker.tick() => {
+                    self.queue.renew_claim(id, self.system_clock.now()).await?;
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::column_family::DEFAULT_COLUMN_FAMILY_ID;
+    use crate::config::{FlushOptions, FlushType};
+    use crate::db::Db;
+    use crate::iter::KeyValueIterator;
+    use crate::object_stores::ObjectStores;
+    use crate::sst::SsTableFormat;
+    use crate::sst_iter::{SstIterator, SstIteratorOptions};
+    use object_store::memory::InMemory;
+    use std::time::Duration;
+
+    const PATH: &str = "/test/db";
+
+    fn queue_options() -> CompactionJobQueueOptions {
+        CompactionJobQueueOptions {
+            poll_interval: Duration::from_millis(10),
+            claim_timeout: Duration::from_secs(60),
+        }
+    }
+
+    /// Writes two overlapping L0 SSTs and returns a job that compacts them.
+    async fn write_l0_job(os: Arc<dyn ObjectStore>) -> CompactionJob {
+        let db = Db::open(PATH, os).await.unwrap();
+        for round in 0..2u8 {
+            for key in [b"a", b"b", b"c"] {
+                db.put(key, &[round]).await.unwrap();
+            }
+            db.flush_with_options(FlushOptions {
+                flush_type: FlushType::MemTable,
+            })
+            .await
+            .unwrap();
+        }
+        let l0 = db.inner.state.read().state().core().l0.clone();
+        db.close().await.unwrap();
+        let id = Ulid::new();
+        CompactionJob {
+            args: StartCompactionJobArgs {
+                id,
+                compaction_id: id,
+                destination: 0,
+                column_family: DEFAULT_COLUMN_FAMILY_ID,
+                ssts: l0.into_iter().collect(),
+                sorted_runs: vec![],
+                compaction_logical_clock_tick: 0,
+                is_dest_last_run: true,
+                retention_min_seq: None,
+                output_ssts: vec![],
+            },
+        }
+    }
+
+    async fn read_entries(os: Arc<dyn ObjectStore>, ssts: &[SsTableHandle]) -> Vec<(Bytes, Bytes)> {
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(os, None),
+            SsTableFormat::default(),
+            Path::from(PATH),
+            None,
+        ));
+        let mut entries = Vec::new();
+        for sst in ssts {
+            let mut iter = SstIterator::new_borrowed_initialized(
+                ..,
+                sst,
+                table_store.clone(),
+                SstIteratorOptions::default(),
+            )
+            .await
+            .unwrap()
+            .expect("Expected Some(iter) but got None");
+            while let Some(kv) = iter.next().await.unwrap() {
+                entries.push((kv.key, kv.value));
+            }
+        }
+        entries
+    }
+
+    fn new_worker(os: Arc<dyn ObjectStore>) -> CompactionWorker {
+        CompactionWorker::builder(PATH, os)
+            .with_queue_options(queue_options())
+            .build()
+    }
+
+    #[tokio::test]
+    async fn test_worker_should_run_queued_job_once() {
+        // given:
+        let os: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let job = write_l0_job(os.clone()).await;
+        let queue = CompactionJobQueue::new(&Path::from(PATH), os.clone());
+        queue.submit(&job).await.unwrap();
+        let worker = new_worker(os.clone());
+
+        // when:
+        let ran = worker.run_once().await.unwrap();
+        let ran_again = worker.run_once().await.unwrap();
+
+        // then:
+        assert!(ran);
+        assert!(!ran_again);
+        let result = queue.read_result(job.id()).await.unwrap().unwrap();
+        assert_eq!(result.id(), job.id());
+        let ssts = result.output.expect("job failed");
+        let entries = read_entries(os, &ssts).await;
+        let expected: Vec<(Bytes, Bytes)> = [b"a", b"b", b"c"]
+            .into_iter()
+            .map(|key| (Bytes::from_static(key), Bytes::from_static(&[1])))
+            .collect();
+        assert_eq!(entries, expected);
+    }
+
+    #[tokio::test]
+    async fn test_worker_should_skip_claimed_job() {
+        // given:
+        let os: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let job = write_l0_job(os.clone()).await;
+        let queue = CompactionJobQueue::new(&Path::from(PATH), os.clone());
+        queue.submit(&job).await.unwrap();
+        let clock = DefaultSystemClock::default();
+        assert!(queue.try_claim(job.id(), clock.now()).await.unwrap());
+
+        // when:
+        let ran = new_worker(os.clone()).run_once().await.unwrap();
+
+        // then:
+        assert!(!ran);
+        assert!(queue.read_result(job.id()).await.unwrap().is_none());
+    }
+
+    #[tokio::test]
+    async fn test_runner_should_release_expired_claim() {
+        // given:
+        let os: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let runner = ObjectStoreCompactionJobRunner::new(PATH, os.clone(), queue_options());
+        let queue = CompactionJobQueue::new(&Path::from(PATH), os.clone());
+        let now = runner.system_clock.now();
+        let (expired, fresh) = (Ulid::new(), Ulid::new());
+        queue
+            .try_claim(expired, now - chrono::Duration::seconds(61))
+            .await
+            .unwrap();
+        queue.try_claim(fresh, now).await.unwrap();
+
+        // when:
+        runner.expire_stale_claim(expired).await.unwrap();
+        runner.expire_stale_claim(fresh).await.unwrap();
+
+        // then:
+        assert_eq!(queue.read_claim(expired).await.unwrap(), None);
+        assert!(queue.read_claim(fresh).await.unwrap().is_some());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 2)]
+    async fn test_runner_should_return_result_of_worker() {
+        // given:
+        let os: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let job = write_l0_job(os.clone()).await;
+        let runner = ObjectStoreCompactionJobRunner::new(PATH, os.clone(), queue_options());
+        let worker = new_worker(os.clone());
+        let worker_task = tokio::spawn(async move { worker.run().await });
+
+        // when:
+        let result = runner.run(job.clone()).await.unwrap();
+
+        // then:
+        worker_task.abort();
+        assert_eq!(result.id(), job.id());
+        assert_eq!(result.error(), None);
+        let queue = CompactionJobQueue::new(&Path::from(PATH), os.clone());
+        assert!(queue.list_jobs().await.unwrap().is_empty());
+        assert!(!queue.has_result(job.id()).await.unwrap());
+        assert_eq!(queue.read_claim(job.id()).await.unwrap(), None);
+    }
+}
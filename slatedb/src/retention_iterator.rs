@@ -5,11 +5,15 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use crate::clock::SystemClock;
+use crate::compaction_filter::{CompactionFilterDecision, CompactionFilterType};
+use crate::compactor::stats::CompactionStats;
 use crate::error::SlateDBError;
 use crate::iter::KeyValueIterator;
+use crate::range_tombstone::RangeTombstones;
 use crate::seq_tracker::{FindOption, SequenceTracker};
+use crate::tablestore::TableStore;
 use crate::types::RowEntry;
-use crate::types::ValueDeletable::Tombstone;
+use crate::types::ValueDeletable::{self, Tombstone};
 
 /// A retention iterator that filters entries based on retention time and handles expired/tombstoned keys.
 ///
@@ -36,6 +40,15 @@ pub(crate) struct RetentionIterator<T: KeyValueIterator> {
     system_clock: Arc<dyn SystemClock>,
     /// Historical sequence metadata used to translate sequence numbers into wall-clock timestamps.
     sequence_tracker: Arc<SequenceTracker>,
+    /// Range tombstones that are visible to every active snapshot. The entries they cover can
+    /// never be read again, so they are dropped before applying the retention policy.
+    range_tombstones: Arc<RangeTombstones>,
+    /// The application filter called for each value that survives the retention policy
+    compaction_filter: Option<CompactionFilterType>,
+    /// The compactor stats the compaction filter calls are counted in
+    stats: Option<Arc<CompactionStats>>,
+    /// Reads the values of blob refs the compaction filter is called for
+    table_store: Option<Arc<TableStore>>,
     /// The total number of bytes processed so far
     total_bytes_processed: u64,
 }
@@ -59,11 +72,46 @@ impl<T: KeyValueIterator> RetentionIterator<T> {
             compaction_start_ts,
             system_clock,
             sequence_tracker,
+            range_tombstones: Arc::new(RangeTombstones::new()),
+            compaction_filter: None,
+            stats: None,
+            table_store: None,
             buffer: RetentionBuffer::new(),
             total_bytes_processed: 0,
         })
     }
 
+    /// Drops every entry covered by one of the given range tombstones.
+    pub(crate) fn with_range_tombstones(self, range_tombstones: Arc<RangeTombstones>) -> Self {
+        Self {
+            range_tombstones,
+            ..self
+        }
+    }
+
+    /// Passes each value that survives the retention policy to the given compaction filter,
+    /// and counts the calls in `stats`.
+    pub(crate) fn with_compaction_filter(
+        self,
+        compaction_filter: CompactionFilterType,
+        stats: Arc<CompactionStats>,
+    ) -> Self {
+        Self {
+            compaction_filter: Some(compaction_filter),
+            stats: Some(stats),
+            ..self
+        }
+    }
+
+    /// Reads the values of blob refs from the given table store when the compaction filter
+    /// is called for them. The blob refs of the values the filter keeps are left in place.
+    pub(crate) fn with_table_store(self, table_store: Arc<TableStore>) -> Self {
+        Self {
+            table_store: Some(table_store),
+            ..self
+        }
+    }
+
     /// Applies retention filtering to a collection of versions for the same key
     ///
     /// This function implements the following retention logic:
@@ -157,20 +205,73 @@ impl<T: KeyValueIterator> RetentionIterator<T> {
         }
 
         if filter_tombstone {
-            // remove the tombstones in the tail
-            while filtered_versions
-                .iter()
-                .last()
-                .map(|(_, entry)| entry.value.is_tombstone())
-                .unwrap_or(false)
-            {
-                filtered_versions.pop_last();
-            }
+            Self::remove_tail_tombstones(&mut filtered_versions);
         }
 
         filtered_versions
     }
 
+    /// Applies the compaction filter to the values that survived the retention policy.
+    ///
+    /// Dropped values are turned into tombstones for the same reason as expired entries, and
+    /// the tombstones in the tail are recycled if filter_tombstone is true. Values still in
+    /// blob files are read from `table_store`.
+    async fn apply_compaction_filter(
+        mut versions: BTreeMap<Reverse<u64>, RowEntry>,
+        compaction_filter: &CompactionFilterType,
+        stats: Option<&CompactionStats>,
+        table_store: Option<&TableStore>,
+        filter_tombstone: bool,
+    ) -> Result<BTreeMap<Reverse<u64>, RowEntry>, SlateDBError> {
+        for entry in versions.values_mut() {
+            let value = match (&entry.value, table_store) {
+                (ValueDeletable::Value(value), _) => value.clone(),
+                (ValueDeletable::BlobRef(blob_ref), Some(table_store)) => {
+                    table_store.read_blob_value(blob_ref).await?
+                }
+                _ => continue,
+            };
+            let decision = compaction_filter.filter(&entry.key, &value);
+            if let Some(stats) = stats {
+                stats.compaction_filter_calls.inc();
+            }
+            match decision {
+                CompactionFilterDecision::Keep => {}
+                CompactionFilterDecision::Drop => {
+                    if let Some(stats) = stats {
+                        stats.compaction_filter_dropped.inc();
+                    }
+                    entry.value = Tombstone;
+                    entry.expire_ts = None;
+                }
+                CompactionFilterDecision::Replace(value) => {
+                    if let Some(stats) = stats {
+                        stats.compaction_filter_replaced.inc();
+                    }
+                    entry.value = ValueDeletable::Value(value);
+                }
+            }
+        }
+
+        if filter_tombstone {
+            Self::remove_tail_tombstones(&mut versions);
+        }
+
+        Ok(versions)
+    }
+
+    /// Removes the tombstones in the tail of the versions of a key.
+    fn remove_tail_tombstones(versions: &mut BTreeMap<Reverse<u64>, RowEntry>) {
+        while versions
+            .iter()
+            .last()
+            .map(|(_, entry)| entry.value.is_tombstone())
+            .unwrap_or(false)
+        {
+            versions.pop_last();
+        }
+    }
+
     pub(crate) fn total_bytes_processed(&self) -> u64 {
         self.total_bytes_processed
     }
@@ -229,7 +330,9 @@ impl<T: KeyValueIterator> KeyValueIterator for RetentionIterator<T> {
                     let retention_timeout = self.retention_timeout;
                     let retention_min_seq = self.retention_min_seq;
                     let system_clock = self.system_clock.clone();
-                    self.buffer.process_retention(|versions| {
+                    let range_tombstones = self.range_tombstones.clone();
+                    self.buffer.process_retention(|mut versions| {
+                        versions.retain(|_, entry| !range_tombstones.covers(entry));
                         Self::apply_retention_filter(
                             versions,
                             compaction_start_ts,
@@ -240,6 +343,19 @@ impl<T: KeyValueIterator> KeyValueIterator for RetentionIterator<T> {
                             self.sequence_tracker.clone(),
                         )
                     })?;
+                    // the filter runs on the survivors only, so the blob values of the
+                    // dropped versions are never read
+                    if let Some(compaction_filter) = self.compaction_filter.as_ref() {
+                        let versions = std::mem::take(&mut self.buffer.current_versions);
+                        self.buffer.current_versions = Self::apply_compaction_filter(
+                            versions,
+                            compaction_filter,
+                            self.stats.as_deref(),
+                            self.table_store.as_deref(),
+                            self.filter_tombstone,
+                        )
+                        .await?;
+                    }
                 }
             }
         }
@@ -407,7 +523,9 @@ impl RetentionBuffer {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::stats::ReadableStat;
     use crate::types::RowEntry;
+    use bytes::Bytes;
     use rstest::rstest;
 
     #[cfg(feature = "test-util")]
@@ -1078,4 +1196,103 @@ mod tests {
             filtered, entry_seq, derived_ts, clock_now
         );
     }
+
+    #[tokio::test]
+    async fn should_drop_entries_covered_by_range_tombstones() {
+        use crate::bytes_range::BytesRange;
+        use crate::clock::DefaultSystemClock;
+        use crate::range_tombstone::RangeTombstone;
+        use crate::test_utils::{assert_iterator, TestIterator};
+
+        let inner = TestIterator::new()
+            .with_entry(b"a", b"1", 1)
+            .with_entry(b"b", b"3", 6)
+            .with_entry(b"b", b"2", 2)
+            .with_entry(b"c", b"4", 3)
+            .with_entry(b"d", b"5", 4);
+        let mut tombstones = RangeTombstones::new();
+        tombstones.push(RangeTombstone::new(BytesRange::from_ref("b"..="c"), 5, 0));
+        let mut iter = RetentionIterator::new(
+            inner,
+            None,
+            None,
+            false,
+            0,
+            Arc::new(DefaultSystemClock::new()),
+            Arc::new(SequenceTracker::new()),
+        )
+        .await
+        .unwrap()
+        .with_range_tombstones(Arc::new(tombstones));
+        iter.init().await.unwrap();
+
+        assert_iterator(
+            &mut iter,
+            vec![
+                RowEntry::new_value(b"a", b"1", 1),
+                RowEntry::new_value(b"b", b"3", 6),
+                RowEntry::new_value(b"d", b"5", 4),
+            ],
+        )
+        .await;
+    }
+
+    struct PrefixCompactionFilter;
+
+    impl crate::compaction_filter::CompactionFilter for PrefixCompactionFilter {
+        fn filter(&self, key: &Bytes, _value: &Bytes) -> CompactionFilterDecision {
+            match key.first() {
+                Some(b'a') => CompactionFilterDecision::Drop,
+                Some(b'b') => CompactionFilterDecision::Replace(Bytes::from_static(b"new")),
+                _ => CompactionFilterDecision::Keep,
+            }
+        }
+    }
+
+    #[rstest]
+    #[case::keep_tombstones(false)]
+    #[case::filter_tombstones(true)]
+    #[tokio::test]
+    async fn should_apply_compaction_filter_to_surviving_values(#[case] filter_tombstone: bool) {
+        use crate::clock::DefaultSystemClock;
+        use crate::stats::StatRegistry;
+        use crate::test_utils::{assert_iterator, TestIterator};
+
+        let inner = TestIterator::new()
+            .with_entry(b"a", b"1", 5)
+            .with_entry(b"a", b"0", 1)
+            .with_entry(b"b", b"2", 2)
+            .with_entry(b"c", b"3", 3)
+            .with_row_entry(RowEntry::new_tombstone(b"d", 4));
+        let stats = Arc::new(CompactionStats::new(Arc::new(StatRegistry::new())));
+        let mut iter = RetentionIterator::new(
+            inner,
+            None,
+            None,
+            filter_tombstone,
+            0,
+            Arc::new(DefaultSystemClock::new()),
+            Arc::new(SequenceTracker::new()),
+        )
+        .await
+        .unwrap()
+        .with_compaction_filter(Arc::new(PrefixCompactionFilter), stats.clone());
+        iter.init().await.unwrap();
+
+        // the older version of "a" is outside the retention window, so only the latest
+        // version is filtered
+        let mut expected = vec![
+            RowEntry::new_tombstone(b"a", 5),
+            RowEntry::new_value(b"b", b"new", 2),
+            RowEntry::new_value(b"c", b"3", 3),
+            RowEntry::new_tombstone(b"d", 4),
+        ];
+        if filter_tombstone {
+            expected.retain(|entry| !entry.value.is_tombstone());
+        }
+        assert_iterator(&mut iter, expected).await;
+        assert_eq!(stats.compaction_filter_calls.get(), 3);
+        assert_eq!(stats.compaction_filter_dropped.get(), 1);
+        assert_eq!(stats.compaction_filter_replaced.get(), 1);
+    }
 }
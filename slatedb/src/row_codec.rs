@@ -1,5 +1,6 @@
 use std::fmt::Debug;
 
+use crate::blob_file::BlobRef;
 use crate::error::SlateDBError;
 use crate::types::ValueDeletable;
 use bitflags::bitflags;
@@ -12,6 +13,7 @@ bitflags! {
         const HAS_EXPIRE_TS = 0b00000010;
         const HAS_CREATE_TS = 0b00000100;
         const MERGE_OPERAND = 0b00001000;
+        const BLOB_REF = 0b00010000;
     }
 }
 
@@ -48,7 +50,7 @@ bitflags! {
 /// | `expire_ts`      | `u64` | Optional, only has value when flags & HAS_EXPIRE_TS    |
 /// | `create_ts`      | `u64` | Optional, only has value when flags & HAS_CREATE_TS    |
 /// | `value_len`      | `u32` | Length of the value                                    |
-/// | `value`          | `var` | Value bytes                                            |
+/// | `value`          | `var` | Value bytes, or an encoded `BlobRef` if flags & BLOB_REF |
 
 #[derive(Debug, Clone)]
 pub(crate) struct SstRowEntry {
@@ -98,6 +100,7 @@ impl SstRowEntry {
             ValueDeletable::Value(_) => RowFlags::default(),
             ValueDeletable::Merge(_) => RowFlags::MERGE_OPERAND,
             ValueDeletable::Tombstone => RowFlags::TOMBSTONE,
+            ValueDeletable::BlobRef(_) => RowFlags::BLOB_REF,
         };
         if self.expire_ts.is_some() {
             flags |= RowFlags::HAS_EXPIRE_TS;
@@ -190,6 +193,10 @@ impl SstRowCodecV0 {
                 output.put_u32(value_len);
                 output.put(v.as_ref());
             }
+            ValueDeletable::BlobRef(blob_ref) => {
+                output.put_u32(BlobRef::ENCODED_LEN as u32);
+                blob_ref.encode(output);
+            }
             ValueDeletable::Tombstone => {
                 // skip encoding value for tombstone
             }
@@ -232,7 +239,7 @@ impl SstRowCodecV0 {
 
         // decode value
         let value_len = data.get_u32() as usize;
-        let value = data.slice(..value_len);
+        let mut value = data.slice(..value_len);
         Ok(SstRowEntry {
             key_prefix_len,
             key_suffix,
@@ -241,6 +248,8 @@ impl SstRowCodecV0 {
             create_ts,
             value: if flags.contains(RowFlags::MERGE_OPERAND) {
                 ValueDeletable::Merge(value)
+            } else if flags.contains(RowFlags::BLOB_REF) {
+                ValueDeletable::BlobRef(BlobRef::decode(&mut value)?)
             } else {
                 ValueDeletable::Value(value)
             },
@@ -261,6 +270,15 @@ impl SstRowCodecV0 {
                 message: "Tombstone and Merge Operand are mutually exclusive.".to_string(),
             });
         }
+        if parsed.contains(RowFlags::BLOB_REF)
+            && parsed.intersects(RowFlags::TOMBSTONE | RowFlags::MERGE_OPERAND)
+        {
+            return Err(SlateDBError::InvalidRowFlags {
+                encoded_bits: parsed.bits(),
+                known_bits: RowFlags::all().bits(),
+                message: "Blob refs can't be tombstones or merge operands.".to_string(),
+            });
+        }
         Ok(parsed)
     }
 }
@@ -470,8 +488,10 @@ mod tests {
 
         // Tombstone and Merge Operand are mutually exclusive
         tests.push(0b00001001);
+        // Blob refs can't be tombstones or merge operands
+        tests.push(0b00010001);
+        tests.push(0b00011000);
         // Unknown bits
-        tests.push(0b00010000);
         tests.push(0b00100000);
         tests.push(0b01000000);
         tests.push(0b10000000);
@@ -544,6 +564,33 @@ mod tests {
         assert_eq!(decoded.size(), 43);
     }
 
+    #[test]
+    fn test_encode_decode_blob_ref_row() {
+        let mut encoded_data = Vec::new();
+        let blob_ref = BlobRef {
+            blob_id: ulid::Ulid::from_parts(1, 2),
+            offset: 100,
+            len: 200,
+        };
+
+        let codec = SstRowCodecV0::new();
+        let row = SstRowEntry::new(
+            0,
+            Bytes::from_static(b"blob"),
+            1,
+            ValueDeletable::BlobRef(blob_ref),
+            None,
+            None,
+        );
+        codec.encode(&mut encoded_data, &row);
+        assert_eq!(encoded_data.len(), row.size());
+
+        let mut data = Bytes::from(encoded_data);
+        let decoded = codec.decode(&mut data).expect("decoding failed");
+        assert_eq!(decoded.flags(), RowFlags::BLOB_REF);
+        assert_eq!(decoded.value, ValueDeletable::BlobRef(blob_ref));
+    }
+
     #[test]
     fn test_estimate_encoded_size() {
         // Test with zero entries
@@ -227,11 +227,22 @@ impl CompactionScheduler for SizeTieredCompactionScheduler {
             )
             .collect();
 
-        // Validate if the compaction sources are strictly consecutive elements in the db_state sources
-        if !sources_logical_order
-            .windows(compaction.sources().len())
-            .any(|w| w == compaction.sources().as_slice())
-        {
+        // Validate if the compaction sources are consecutive elements in the db_state sources.
+        // Sorted runs between the sources may be skipped by manual compactions, such as
+        // those of `Admin::compact_range`, which check that the skipped runs hold none of the
+        // keys of the newer sources. L0 SSTs can't be skipped.
+        let l0_len = state.db_state().l0.len();
+        let positions: Option<Vec<usize>> = compaction
+            .sources()
+            .iter()
+            .map(|source| sources_logical_order.iter().position(|s| s == source))
+            .collect();
+        let is_consecutive = positions.is_some_and(|positions| {
+            positions
+                .windows(2)
+                .all(|pair| pair[0] < pair[1] && (pair[0] + 1 == pair[1] || pair[0] + 1 >= l0_len))
+        });
+        if !is_consecutive {
             warn!("submitted compaction is not a consecutive series of sources from db state: {:?} {:?}",
             compaction.sources(), sources_logical_order);
             return Err(Error::invalid(
@@ -805,6 +816,26 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn test_should_submit_valid_compaction_skipping_sr() {
+        // given:
+        let scheduler = SizeTieredCompactionScheduler::default();
+        let l0 = VecDeque::from(vec![create_sst(1)]);
+        let state = create_compactor_state(create_db_state(
+            l0.clone(),
+            vec![create_sr2(2, 2), create_sr2(1, 2), create_sr2(0, 2)],
+        ));
+
+        // when:
+        let mut sources = create_l0_compaction(&Vec::from(l0), 0).sources().clone();
+        sources.extend([SourceId::SortedRun(1), SourceId::SortedRun(0)]);
+        let compaction = CompactionSpec::new(sources, 0);
+        let result = scheduler.validate_compaction(&state, &compaction);
+
+        // then:
+        assert!(result.is_ok());
+    }
+
     fn create_sst(size: u64) -> SsTableHandle {
         let info = SsTableInfo {
             first_key: None,
@@ -813,6 +844,11 @@ mod tests {
             filter_offset: 0,
             filter_len: 0,
             compression_codec: None,
+            range_tombstones: vec![],
+            column_family_keys: false,
+            prefix_extractor: None,
+            blob_refs: vec![],
+            encryption_key_id: None,
         };
         SsTableHandle::new(SsTableId::Compacted(ulid::Ulid::new()), info)
     }
@@ -844,6 +880,8 @@ mod tests {
             wal_object_store_uri: None,
             recent_snapshot_min_seq: 0,
             sequence_tracker: SequenceTracker::new(),
+            column_families: vec![],
+            comparator: None,
         }
     }
 
@@ -1,34 +1,39 @@
 use crate::bytes_range::BytesRange;
 use crate::db_state::{SortedRun, SsTableHandle};
 use crate::error::SlateDBError;
-use crate::iter::KeyValueIterator;
+use crate::iter::{IterationOrder, KeyValueIterator};
 use crate::sst_iter::{SstIterator, SstIteratorOptions, SstView};
 use crate::tablestore::TableStore;
 use crate::types::RowEntry;
 use async_trait::async_trait;
 use bytes::Bytes;
 use std::collections::VecDeque;
-use std::ops::{Bound, RangeBounds};
+use std::ops::RangeBounds;
 use std::sync::Arc;
 
 #[derive(Debug)]
 enum SortedRunView<'a> {
     Owned(VecDeque<SsTableHandle>, BytesRange),
-    Borrowed(
-        VecDeque<&'a SsTableHandle>,
-        (Bound<&'a [u8]>, Bound<&'a [u8]>),
-    ),
+    Borrowed(VecDeque<&'a SsTableHandle>, BytesRange),
 }
 
 impl<'a> SortedRunView<'a> {
-    fn pop_sst(&mut self) -> Option<SstView<'a>> {
+    fn pop_sst(&mut self, order: IterationOrder) -> Option<SstView<'a>> {
         match self {
-            SortedRunView::Owned(tables, r) => tables
-                .pop_front()
-                .map(|table| SstView::Owned(Box::new(table), r.clone())),
-            SortedRunView::Borrowed(tables, r) => tables
-                .pop_front()
-                .map(|table| SstView::Borrowed(table, BytesRange::from_slice(*r))),
+            SortedRunView::Owned(tables, r) => {
+                let table = match order {
+                    IterationOrder::Ascending => tables.pop_front(),
+                    IterationOrder::Descending => tables.pop_back(),
+                };
+                table.map(|table| SstView::Owned(Box::new(table), r.clone()))
+            }
+            SortedRunView::Borrowed(tables, r) => {
+                let table = match order {
+                    IterationOrder::Ascending => tables.pop_front(),
+                    IterationOrder::Descending => tables.pop_back(),
+                };
+                table.map(|table| SstView::Borrowed(table, r.clone()))
+            }
         }
     }
 
@@ -37,7 +42,7 @@ impl<'a> SortedRunView<'a> {
         table_store: Arc<TableStore>,
         sst_iterator_options: SstIteratorOptions,
     ) -> Result<Option<SstIterator<'a>>, SlateDBError> {
-        let next_iter = if let Some(view) = self.pop_sst() {
+        let next_iter = if let Some(view) = self.pop_sst(sst_iterator_options.order) {
             Some(SstIterator::new(
                 view,
                 table_store.clone(),
@@ -88,8 +93,13 @@ impl<'a> SortedRunIterator<'a> {
         table_store: Arc<TableStore>,
         sst_iter_options: SstIteratorOptions,
     ) -> Result<Self, SlateDBError> {
-        let range = BytesRange::from(range);
-        let tables = sorted_run.into_tables_covering_range(&range);
+        let comparator = table_store.comparator();
+        let range = BytesRange::new_by(
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+            comparator,
+        );
+        let tables = sorted_run.into_tables_covering_range(&range, comparator);
         let view = SortedRunView::Owned(tables, range);
         SortedRunIterator::new(view, table_store, sst_iter_options).await
     }
@@ -112,9 +122,13 @@ impl<'a> SortedRunIterator<'a> {
         table_store: Arc<TableStore>,
         sst_iter_options: SstIteratorOptions,
     ) -> Result<Self, SlateDBError> {
-        let range = (range.start_bound().cloned(), range.end_bound().cloned());
-        // todo remove conversion to bytesrange
-        let tables = sorted_run.tables_covering_range(&BytesRange::from_slice(range));
+        let comparator = table_store.comparator();
+        let range = BytesRange::new_by(
+            range.start_bound().map(|b| Bytes::copy_from_slice(b)),
+            range.end_bound().map(|b| Bytes::copy_from_slice(b)),
+            comparator,
+        );
+        let tables = sorted_run.tables_covering_range(&range, comparator);
         let view = SortedRunView::Borrowed(tables, range);
         SortedRunIterator::new(view, table_store, sst_iter_options).await
     }
@@ -177,11 +191,32 @@ impl KeyValueIterator for SortedRunIterator<'_> {
         if !self.initialized {
             return Err(SlateDBError::IteratorNotInitialized);
         }
-        while let Some(next_table) = self.view.peek_next_table() {
-            if next_table.compacted_effective_start_key() < next_key {
-                self.advance_table().await?;
-            } else {
-                break;
+        match self.sst_iter_options.order {
+            IterationOrder::Ascending => {
+                while let Some(next_table) = self.view.peek_next_table() {
+                    if self
+                        .table_store
+                        .comparator()
+                        .compare(next_table.compacted_effective_start_key(), next_key)
+                        .is_lt()
+                    {
+                        self.advance_table().await?;
+                    } else {
+                        break;
+                    }
+                }
+            }
+            IterationOrder::Descending => {
+                // every key in the current table is at least its start key, so skip
+                // tables that start after next_key
+                let comparator = self.table_store.comparator().clone();
+                while self.current_iter.as_ref().is_some_and(|iter| {
+                    comparator
+                        .compare(iter.table().compacted_effective_start_key(), next_key)
+                        .is_gt()
+                }) {
+                    self.advance_table().await?;
+                }
             }
         }
         if let Some(iter) = &mut self.current_iter {
@@ -468,6 +503,56 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_seek_through_sorted_run_descending() {
+        let root_path = Path::from("");
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(object_store, None),
+            SsTableFormat::default(),
+            root_path.clone(),
+            None,
+        ));
+
+        let mut rng = proptest_util::rng::new_test_rng(None);
+        let table = sample::table(&mut rng, 400, 10);
+        let max_entries_per_sst = 20u64;
+        let entries_per_sst = 1..max_entries_per_sst;
+        let sr =
+            build_sorted_run_from_table(&table, table_store.clone(), entries_per_sst, &mut rng)
+                .await;
+        let mut sr_iter = SortedRunIterator::new_owned_initialized(
+            ..,
+            sr,
+            table_store.clone(),
+            SstIteratorOptions {
+                order: IterationOrder::Descending,
+                ..SstIteratorOptions::default()
+            },
+        )
+        .await
+        .unwrap();
+        let entries: Vec<(&Bytes, &Bytes)> = table.iter().rev().collect();
+        let mut idx = 0;
+        loop {
+            let skip = rng.random::<u64>() % (max_entries_per_sst * 2);
+            let run = rng.random::<u64>() % (max_entries_per_sst * 2);
+
+            idx += skip as usize;
+            let Some((seek_key, _)) = entries.get(idx) else {
+                break;
+            };
+            sr_iter.seek(seek_key).await.unwrap();
+
+            for (key, value) in entries.iter().skip(idx).take(run as usize) {
+                let kv = sr_iter.next().await.unwrap().unwrap();
+                assert_eq!(**key, kv.key);
+                assert_eq!(**value, kv.value);
+                idx += 1;
+            }
+        }
+    }
+
     fn increment_length(b: &[u8]) -> Bytes {
         let mut buf = BytesMut::from(b);
         buf.put_u8(u8::MIN);
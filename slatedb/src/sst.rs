@@ -1,26 +1,36 @@
 use std::collections::VecDeque;
 #[cfg(feature = "zlib")]
 use std::io::{Read, Write};
-use std::ops::Range;
+use std::ops::Bound::{Excluded, Included, Unbounded};
+use std::ops::{Range, RangeBounds};
 use std::sync::Arc;
 
 use bytes::{Buf, BufMut, Bytes};
 use flatbuffers::DefaultAllocator;
+use ulid::Ulid;
 
+use crate::blob_file::{BlobFileBuilder, BlobRefCounter, EncodedBlobFile};
 use crate::block::Block;
+use crate::comparator::KeyComparator;
 use crate::db_state::{SsTableInfo, SsTableInfoCodec};
+use crate::encryption::{self, EncryptionProviderType, Encryptor};
 use crate::filter::{BloomFilter, BloomFilterBuilder};
 use crate::flatbuffer_types::{
     BlockMeta, BlockMetaArgs, FlatBufferSsTableInfoCodec, SsTableIndex, SsTableIndexArgs,
     SsTableIndexOwned,
 };
+use crate::prefix_extractor::PrefixExtractorType;
+use crate::range_tombstone::RangeTombstone;
 use crate::row_codec;
-use crate::types::RowEntry;
+use crate::types::{RowEntry, ValueDeletable};
 use crate::utils::compute_index_key;
 use crate::{blob::ReadOnlyBlob, config::CompressionCodec};
 use crate::{block::BlockBuilder, error::SlateDBError};
 
 pub(crate) const SST_FORMAT_VERSION: u16 = 1;
+/// The format version of encrypted SSTs, whose metadata block is sealed in an
+/// encryption envelope.
+pub(crate) const ENCRYPTED_SST_FORMAT_VERSION: u16 = 2;
 
 // 8 bytes for the metadata offset + 2 bytes for the version
 const NUM_FOOTER_BYTES: usize = 10;
@@ -41,6 +51,15 @@ pub(crate) struct SsTableFormat {
     pub(crate) sst_codec: Box<dyn SsTableInfoCodec>,
     pub(crate) filter_bits_per_key: u32,
     pub(crate) compression_codec: Option<CompressionCodec>,
+    pub(crate) prefix_extractor: Option<PrefixExtractorType>,
+    /// The order of the keys of the SSTs.
+    pub(crate) comparator: KeyComparator,
+    /// The minimum size of the values that L0 and compacted SSTs store in blob files
+    /// instead of their rows. None keeps every value in the rows.
+    pub(crate) min_blob_value_size: Option<usize>,
+    /// The provider of the keys SSTs are encrypted with. None writes SSTs unencrypted,
+    /// and fails reads of encrypted SSTs.
+    pub(crate) encryption_provider: Option<EncryptionProviderType>,
 }
 
 impl Default for SsTableFormat {
@@ -51,6 +70,10 @@ impl Default for SsTableFormat {
             sst_codec: Box::new(FlatBufferSsTableInfoCodec {}),
             filter_bits_per_key: 10,
             compression_codec: None,
+            prefix_extractor: None,
+            comparator: KeyComparator::default(),
+            min_blob_value_size: None,
+            encryption_provider: None,
         }
     }
 }
@@ -73,7 +96,7 @@ impl SsTableFormat {
         // Last 2 bytes of the header represent the version
         let version = header.slice(8..NUM_FOOTER_BYTES).get_u16();
         // TODO: Support older and newer versions
-        if version != SST_FORMAT_VERSION {
+        if version != SST_FORMAT_VERSION && version != ENCRYPTED_SST_FORMAT_VERSION {
             return Err(SlateDBError::InvalidVersion {
                 expected_version: SST_FORMAT_VERSION,
                 actual_version: version,
@@ -85,7 +108,15 @@ impl SsTableFormat {
         let sst_metadata_bytes = obj
             .read_range(sst_metadata_offset..obj_len - NUM_FOOTER_BYTES_LONG)
             .await?;
-        SsTableInfo::decode(sst_metadata_bytes, &*self.sst_codec)
+        if version == ENCRYPTED_SST_FORMAT_VERSION {
+            SsTableInfo::decode_sealed(
+                sst_metadata_bytes,
+                &*self.sst_codec,
+                self.encryption_provider.as_ref(),
+            )
+        } else {
+            SsTableInfo::decode(sst_metadata_bytes, &*self.sst_codec)
+        }
     }
 
     pub(crate) async fn read_filter(
@@ -98,10 +129,7 @@ impl SsTableFormat {
             let filter_end = info.filter_offset + info.filter_len;
             let filter_offset_range = info.filter_offset..filter_end;
             let filter_bytes = obj.read_range(filter_offset_range).await?;
-            let compression_codec = info.compression_codec;
-            filter = Some(Arc::new(
-                self.decode_filter(filter_bytes, compression_codec)?,
-            ));
+            filter = Some(Arc::new(self.decode_filter(filter_bytes, info)?));
         }
         Ok(filter)
     }
@@ -118,23 +146,16 @@ impl SsTableFormat {
         let filter_end = info.filter_offset + info.filter_len;
         let filter_offset_range = info.filter_offset as usize..filter_end as usize;
         let filter_bytes = sst_bytes.slice(filter_offset_range);
-        let compression_codec = info.compression_codec;
-        Ok(Some(Arc::new(
-            self.decode_filter(filter_bytes, compression_codec)?,
-        )))
+        Ok(Some(Arc::new(self.decode_filter(filter_bytes, info)?)))
     }
 
     pub(crate) fn decode_filter(
         &self,
         bytes: Bytes,
-        compression_codec: Option<CompressionCodec>,
+        info: &SsTableInfo,
     ) -> Result<BloomFilter, SlateDBError> {
-        let filter_bytes = self.validate_checksum(bytes)?;
-        let decompressed_bytes = match compression_codec {
-            Some(c) => Self::decompress(filter_bytes, c)?,
-            None => filter_bytes,
-        };
-        Ok(BloomFilter::decode(&decompressed_bytes))
+        let decoded_bytes = self.decode_section(bytes, info)?;
+        Ok(BloomFilter::decode(&decoded_bytes))
     }
 
     pub(crate) async fn read_index(
@@ -145,8 +166,7 @@ impl SsTableFormat {
         let index_off = info.index_offset;
         let index_end = index_off + info.index_len;
         let index_bytes = obj.read_range(index_off..index_end).await?;
-        let compression_codec = info.compression_codec;
-        self.decode_index(index_bytes, compression_codec)
+        self.decode_index(index_bytes, info)
     }
 
     #[cfg(test)]
@@ -158,21 +178,32 @@ impl SsTableFormat {
         let index_off = info.index_offset as usize;
         let index_end = index_off + info.index_len as usize;
         let index_bytes: Bytes = sst_bytes.slice(index_off..index_end);
-        let compression_codec = info.compression_codec;
-        self.decode_index(index_bytes, compression_codec)
+        self.decode_index(index_bytes, info)
     }
 
     fn decode_index(
         &self,
         bytes: Bytes,
-        compression_codec: Option<CompressionCodec>,
+        info: &SsTableInfo,
     ) -> Result<SsTableIndexOwned, SlateDBError> {
-        let index_bytes = self.validate_checksum(bytes)?;
-        let decompressed_bytes = match compression_codec {
-            Some(c) => Self::decompress(index_bytes, c)?,
-            None => index_bytes,
+        let decoded_bytes = self.decode_section(bytes, info)?;
+        Ok(SsTableIndexOwned::new(decoded_bytes)?)
+    }
+
+    /// Validates the checksum of a block, filter or index of an SST, then decrypts and
+    /// decompresses it.
+    fn decode_section(&self, bytes: Bytes, info: &SsTableInfo) -> Result<Bytes, SlateDBError> {
+        let data_bytes = self.validate_checksum(bytes)?;
+        let decrypted_bytes = match &info.encryption_key_id {
+            Some(key_id) => {
+                encryption::decrypt(self.encryption_provider.as_ref(), key_id, &data_bytes)?
+            }
+            None => data_bytes,
         };
-        Ok(SsTableIndexOwned::new(decompressed_bytes)?)
+        match info.compression_codec {
+            Some(c) => Self::decompress(decrypted_bytes, c),
+            None => Ok(decrypted_bytes),
+        }
     }
 
     /// Decompresses the compressed data using the specified compression codec.
@@ -243,7 +274,6 @@ impl SsTableFormat {
         let start_range = range.start;
         let bytes: Bytes = obj.read_range(range).await?;
         let mut decoded_blocks = VecDeque::new();
-        let compression_codec = info.compression_codec;
         for block in blocks {
             let block_meta = index.block_meta().get(block);
             let block_bytes_start = usize::try_from(block_meta.offset() - start_range).expect(
@@ -261,22 +291,14 @@ impl SsTableFormat {
                     );
                 bytes.slice(block_bytes_start..block_bytes_end)
             };
-            decoded_blocks.push_back(self.decode_block(block_bytes, compression_codec)?);
+            decoded_blocks.push_back(self.decode_block(block_bytes, info)?);
         }
         Ok(decoded_blocks)
     }
 
-    fn decode_block(
-        &self,
-        bytes: Bytes,
-        compression_codec: Option<CompressionCodec>,
-    ) -> Result<Block, SlateDBError> {
-        let block_bytes = self.validate_checksum(bytes)?;
-        let decompressed_bytes = match compression_codec {
-            Some(c) => Self::decompress(block_bytes, c)?,
-            None => block_bytes,
-        };
-        Ok(Block::decode(decompressed_bytes))
+    fn decode_block(&self, bytes: Bytes, info: &SsTableInfo) -> Result<Block, SlateDBError> {
+        let decoded_bytes = self.decode_section(bytes, info)?;
+        Ok(Block::decode(decoded_bytes))
     }
 
     pub(crate) async fn read_block(
@@ -302,17 +324,20 @@ impl SsTableFormat {
         let range = self.block_range(block..block + 1, info, &index);
         let range = range.start as usize..range.end as usize;
         let bytes: Bytes = sst_bytes.slice(range);
-        let compression_codec = info.compression_codec;
-        self.decode_block(bytes, compression_codec)
+        self.decode_block(bytes, info)
     }
 
-    pub(crate) fn table_builder(&self) -> EncodedSsTableBuilder<'_> {
+    pub(crate) fn table_builder<'b>(&self) -> EncodedSsTableBuilder<'b> {
         EncodedSsTableBuilder::new(
             self.block_size,
             self.min_filter_keys,
             self.sst_codec.clone(),
             self.filter_bits_per_key,
             self.compression_codec,
+            self.prefix_extractor.clone(),
+            self.comparator.is_bytewise(),
+            self.min_blob_value_size,
+            self.encryption_provider.clone().map(Encryptor::new),
         )
     }
 
@@ -373,10 +398,40 @@ impl SsTableInfo {
         buf.put_u32(crc32fast::hash(data));
     }
 
+    /// Encodes the info sealed in an encryption envelope, for the metadata block of
+    /// encrypted SSTs.
+    pub(crate) fn encode_sealed(
+        info: &SsTableInfo,
+        buf: &mut Vec<u8>,
+        sst_codec: &dyn SsTableInfoCodec,
+        encryptor: &Encryptor,
+    ) -> Result<(), SlateDBError> {
+        let data = &encryptor.seal(&sst_codec.encode(info))?;
+        buf.extend_from_slice(data);
+        buf.put_u32(crc32fast::hash(data));
+        Ok(())
+    }
+
     pub(crate) fn decode(
         raw_info: Bytes,
         sst_codec: &dyn SsTableInfoCodec,
     ) -> Result<SsTableInfo, SlateDBError> {
+        let data = Self::validate_checksum(raw_info)?;
+        let info = sst_codec.decode(&data)?;
+        Ok(info)
+    }
+
+    pub(crate) fn decode_sealed(
+        raw_info: Bytes,
+        sst_codec: &dyn SsTableInfoCodec,
+        provider: Option<&EncryptionProviderType>,
+    ) -> Result<SsTableInfo, SlateDBError> {
+        let data = Self::validate_checksum(raw_info)?;
+        let info = sst_codec.decode(&encryption::unseal(provider, data)?)?;
+        Ok(info)
+    }
+
+    fn validate_checksum(raw_info: Bytes) -> Result<Bytes, SlateDBError> {
         if raw_info.len() <= 4 {
             return Err(SlateDBError::EmptyBlockMeta);
         }
@@ -385,9 +440,7 @@ impl SsTableInfo {
         if checksum != crc32fast::hash(&data) {
             return Err(SlateDBError::ChecksumMismatch);
         }
-
-        let info = sst_codec.decode(&data)?;
-        Ok(info)
+        Ok(data)
     }
 }
 
@@ -409,6 +462,9 @@ pub(crate) struct EncodedSsTable {
     pub(crate) filter: Option<Arc<BloomFilter>>,
     pub(crate) unconsumed_blocks: VecDeque<EncodedSsTableBlock>,
     pub(crate) footer: Bytes,
+    /// The blob file holding the values separated from the rows of the SST, if any. It
+    /// must be written before the SST.
+    pub(crate) blob_file: Option<EncodedBlobFile>,
 }
 
 impl EncodedSsTable {
@@ -448,6 +504,19 @@ pub(crate) struct EncodedSsTableBuilder<'a> {
     filter_builder: BloomFilterBuilder,
     sst_codec: Box<dyn SsTableInfoCodec>,
     compression_codec: Option<CompressionCodec>,
+    range_tombstones: Vec<RangeTombstone>,
+    column_family_keys: bool,
+    prefix_extractor: Option<PrefixExtractorType>,
+    last_prefix: Option<Bytes>,
+    /// Whether the index keys of blocks are shortened to the shortest key between the
+    /// blocks. Shortened keys only preserve the lexicographic order of keys, so the first
+    /// key of each block is used as is when keys are ordered by a comparator.
+    shorten_index_keys: bool,
+    min_blob_value_size: Option<usize>,
+    blob_file: Option<BlobFileBuilder>,
+    blob_refs: BlobRefCounter,
+    /// Encrypts the blocks, filter, index and metadata of the SST, if set.
+    encryptor: Option<Encryptor>,
 }
 
 impl EncodedSsTableBuilder<'_> {
@@ -458,6 +527,10 @@ impl EncodedSsTableBuilder<'_> {
         sst_codec: Box<dyn SsTableInfoCodec>,
         filter_bits_per_key: u32,
         compression_codec: Option<CompressionCodec>,
+        prefix_extractor: Option<PrefixExtractorType>,
+        shorten_index_keys: bool,
+        min_blob_value_size: Option<usize>,
+        encryptor: Option<Encryptor>,
     ) -> Self {
         Self {
             current_len: 0,
@@ -474,6 +547,46 @@ impl EncodedSsTableBuilder<'_> {
             index_builder: flatbuffers::FlatBufferBuilder::new(),
             sst_codec,
             compression_codec,
+            range_tombstones: Vec::new(),
+            column_family_keys: false,
+            prefix_extractor,
+            last_prefix: None,
+            shorten_index_keys,
+            min_blob_value_size,
+            blob_file: None,
+            blob_refs: BlobRefCounter::default(),
+            encryptor,
+        }
+    }
+
+    /// Marks the row keys of the SST as prefixed with the id of their column family.
+    /// The prefix extractor only applies to user keys, so it's disabled for the SST.
+    pub(crate) fn with_column_family_keys(self) -> Self {
+        Self {
+            column_family_keys: true,
+            prefix_extractor: None,
+            ..self
+        }
+    }
+
+    /// Separates values of at least the format's minimum blob value size into the blob
+    /// file `id`, and stores pointers to them in the rows of the SST instead.
+    pub(crate) fn with_blob_file(self, id: Ulid) -> Self {
+        let blob_file = self
+            .min_blob_value_size
+            .map(|min_value_size| BlobFileBuilder::new(id, min_value_size, self.encryptor.clone()));
+        Self { blob_file, ..self }
+    }
+
+    /// Compresses and encrypts a block, filter or index of the SST.
+    fn encode_section(&self, data: Bytes) -> Result<Bytes, SlateDBError> {
+        let compressed = match self.compression_codec {
+            Some(c) => Self::compress(data, c)?,
+            None => data,
+        };
+        match &self.encryptor {
+            Some(encryptor) => encryptor.encrypt(&compressed),
+            None => Ok(compressed),
         }
     }
 
@@ -520,12 +633,27 @@ impl EncodedSsTableBuilder<'_> {
     /// Adds an entry to the SSTable and returns the size of the block that was finished if any.
     /// The block size is calculated after applying any compression if enabled.
     /// The block size is None if the builder has not finished compacting a block yet.
-    pub fn add(&mut self, entry: RowEntry) -> Result<Option<usize>, SlateDBError> {
+    pub fn add(&mut self, mut entry: RowEntry) -> Result<Option<usize>, SlateDBError> {
+        if let (Some(blob_file), ValueDeletable::Value(value)) =
+            (self.blob_file.as_mut(), &entry.value)
+        {
+            if blob_file.should_separate(value) {
+                let blob_ref = blob_file.add(value)?;
+                entry.value = ValueDeletable::BlobRef(blob_ref);
+            }
+        }
+        if let ValueDeletable::BlobRef(blob_ref) = &entry.value {
+            self.blob_refs.add(blob_ref);
+        }
         self.num_keys += 1;
         let key = entry.key.clone();
 
-        let index_key = compute_index_key(self.current_block_max_key.take(), &key);
-        self.current_block_max_key = Some(key.clone());
+        let prev_block_max_key = self.current_block_max_key.replace(key.clone());
+        let index_key = if self.shorten_index_keys {
+            compute_index_key(prev_block_max_key, &key)
+        } else {
+            key.clone()
+        };
 
         let mut block_size = None;
         if !self.builder.add(entry.clone()) {
@@ -543,6 +671,7 @@ impl EncodedSsTableBuilder<'_> {
         }
 
         self.filter_builder.add_key(&key);
+        self.add_prefix_to_filter(&key);
 
         Ok(block_size)
     }
@@ -556,7 +685,7 @@ impl EncodedSsTableBuilder<'_> {
     ) -> Result<Option<usize>, SlateDBError> {
         let entry = RowEntry::new(
             key.to_vec().into(),
-            crate::types::ValueDeletable::Value(Bytes::copy_from_slice(val)),
+            ValueDeletable::Value(Bytes::copy_from_slice(val)),
             0,
             attrs.ts,
             attrs.expire_ts,
@@ -564,6 +693,27 @@ impl EncodedSsTableBuilder<'_> {
         self.add(entry)
     }
 
+    /// Adds the prefix of `key` to the filter. Keys are added in order, so keys with the
+    /// same prefix are adjacent and each prefix only needs to be added once.
+    fn add_prefix_to_filter(&mut self, key: &Bytes) {
+        let Some(prefix_extractor) = &self.prefix_extractor else {
+            return;
+        };
+        let Some(prefix) = prefix_extractor.prefix(key) else {
+            return;
+        };
+        if self.last_prefix.as_deref() != Some(prefix) {
+            self.filter_builder.add_key(prefix);
+            self.last_prefix = Some(Bytes::copy_from_slice(prefix));
+        }
+    }
+
+    /// Adds a range tombstone to the SSTable. Range tombstones are stored in the
+    /// SST's metadata rather than in its data blocks.
+    pub(crate) fn add_range_tombstone(&mut self, tombstone: RangeTombstone) {
+        self.range_tombstones.push(tombstone);
+    }
+
     pub fn next_block(&mut self) -> Option<EncodedSsTableBlock> {
         self.blocks.pop_front()
     }
@@ -605,11 +755,7 @@ impl EncodedSsTableBuilder<'_> {
         offset: u64,
     ) -> Result<EncodedSsTableBlock, SlateDBError> {
         let block = builder.build()?;
-        let encoded_block = block.encode();
-        let compressed_block = match self.compression_codec {
-            Some(c) => Self::compress(encoded_block, c)?,
-            None => encoded_block,
-        };
+        let compressed_block = self.encode_section(block.encode())?;
         let checksum = crc32fast::hash(&compressed_block);
         let total_block_size = compressed_block.len() + std::mem::size_of::<u32>();
         let mut encoded_bytes = Vec::with_capacity(total_block_size);
@@ -654,6 +800,10 @@ impl EncodedSsTableBuilder<'_> {
     /// +---------------------------------------------------+
     /// * Only present if num_keys >= min_filter_keys.
     ///
+    /// If the SST is encrypted, the data blocks, filter and index are encrypted after
+    /// they're compressed, the metadata block is sealed in an encryption envelope, and
+    /// the version is [`ENCRYPTED_SST_FORMAT_VERSION`].
+    ///
     pub fn build(mut self) -> Result<EncodedSsTable, SlateDBError> {
         self.finish_block()?;
         let mut buf = Vec::new();
@@ -662,11 +812,7 @@ impl EncodedSsTableBuilder<'_> {
         let filter_offset = self.current_len + buf.len() as u64;
         if self.num_keys >= self.min_filter_keys {
             let filter = Arc::new(self.filter_builder.build());
-            let encoded_filter = filter.encode();
-            let compressed_filter = match self.compression_codec {
-                None => encoded_filter,
-                Some(c) => Self::compress(encoded_filter, c)?,
-            };
+            let compressed_filter = self.encode_section(filter.encode())?;
             let checksum = crc32fast::hash(&compressed_filter);
             filter_len = compressed_filter.len() + std::mem::size_of::<u32>();
             buf.put(compressed_filter);
@@ -685,38 +831,67 @@ impl EncodedSsTableBuilder<'_> {
         self.index_builder.finish(index_wip, None);
         let index_block = Bytes::from(self.index_builder.finished_data().to_vec());
         let index = SsTableIndexOwned::new(index_block.clone())?;
-        let index_block = match self.compression_codec {
-            None => index_block,
-            Some(c) => Self::compress(index_block, c)?,
-        };
+        let index_block = self.encode_section(index_block)?;
         let checksum = crc32fast::hash(&index_block);
         let index_offset = self.current_len + buf.len() as u64;
         let index_len = index_block.len() + std::mem::size_of::<u32>();
         buf.put(index_block);
         buf.put_u32(checksum);
 
+        // An SST holding only range tombstones has no rows, so its first key is
+        // the lowest key any of its tombstones can cover.
+        let first_key = self.sst_first_key.or_else(|| {
+            self.range_tombstones
+                .iter()
+                .map(|tombstone| match tombstone.range.start_bound() {
+                    Included(key) | Excluded(key) => key.clone(),
+                    Unbounded => Bytes::from_static(&[u8::MIN]),
+                })
+                .min()
+        });
         let meta_offset = self.current_len + buf.len() as u64;
         let info = SsTableInfo {
-            first_key: self.sst_first_key,
+            first_key,
             index_offset,
             index_len: index_len as u64,
             filter_offset,
             filter_len: filter_len as u64,
             compression_codec: self.compression_codec,
+            range_tombstones: self.range_tombstones,
+            column_family_keys: self.column_family_keys,
+            prefix_extractor: self
+                .prefix_extractor
+                .as_ref()
+                .map(|extractor| extractor.name().to_string()),
+            blob_refs: self.blob_refs.build(),
+            encryption_key_id: self
+                .encryptor
+                .as_ref()
+                .map(|encryptor| encryptor.key_id().to_string()),
+        };
+        let version = match &self.encryptor {
+            Some(encryptor) => {
+                SsTableInfo::encode_sealed(&info, &mut buf, &*self.sst_codec, encryptor)?;
+                ENCRYPTED_SST_FORMAT_VERSION
+            }
+            None => {
+                SsTableInfo::encode(&info, &mut buf, &*self.sst_codec);
+                SST_FORMAT_VERSION
+            }
         };
-        SsTableInfo::encode(&info, &mut buf, &*self.sst_codec);
 
         // write the metadata offset at the end of the file. FlatBuffer internal
         // representation is not intended to be used directly.
         buf.put_u64(meta_offset);
         // write the version at the end of the file.
-        buf.put_u16(SST_FORMAT_VERSION);
+        buf.put_u16(version);
         Ok(EncodedSsTable {
             info,
             index,
             filter: maybe_filter,
             unconsumed_blocks: self.blocks,
             footer: Bytes::from(buf),
+            blob_file: self.blob_file.and_then(BlobFileBuilder::build),
         })
     }
 
@@ -741,9 +916,11 @@ mod tests {
     use crate::block_iterator::BlockIterator;
     use crate::bytes_range::BytesRange;
     use crate::db_state::SsTableId;
+    use crate::encryption::tests::XorEncryptionProvider;
     use crate::filter::filter_hash;
     use crate::iter::IterationOrder::Ascending;
     use crate::object_stores::ObjectStores;
+    use crate::prefix_extractor::FixedLengthPrefixExtractor;
     use crate::sst_iter::{SstIterator, SstIteratorOptions};
     use crate::tablestore::TableStore;
     use crate::test_utils::{assert_iterator, build_test_sst, gen_attrs, gen_empty_attrs};
@@ -995,6 +1172,137 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_sstable_filter_with_prefixes() {
+        let root_path = Path::from("");
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let format = SsTableFormat {
+            prefix_extractor: Some(Arc::new(FixedLengthPrefixExtractor::new(3))),
+            ..SsTableFormat::default()
+        };
+        let table_store = TableStore::new(
+            ObjectStores::new(object_store, None),
+            format,
+            root_path,
+            None,
+        );
+        let mut builder = table_store.table_builder();
+        builder.add_value(b"aaa1", b"value1", gen_attrs(1)).unwrap();
+        builder.add_value(b"aaa2", b"value2", gen_attrs(2)).unwrap();
+        builder.add_value(b"bbb1", b"value3", gen_attrs(3)).unwrap();
+        let encoded = builder.build().unwrap();
+        table_store
+            .write_sst(&SsTableId::Wal(0), encoded, false)
+            .await
+            .unwrap();
+        let sst_handle = table_store.open_sst(&SsTableId::Wal(0)).await.unwrap();
+        let filter = table_store.read_filter(&sst_handle).await.unwrap().unwrap();
+
+        assert_eq!(
+            sst_handle.info.prefix_extractor.as_deref(),
+            Some("fixed_length:3")
+        );
+        assert!(filter.might_contain(filter_hash(b"aaa1")));
+        assert!(filter.might_contain(filter_hash(b"aaa")));
+        assert!(filter.might_contain(filter_hash(b"bbb")));
+        assert!(!filter.might_contain(filter_hash(b"ccc")));
+    }
+
+    #[tokio::test]
+    async fn test_sstable_with_column_family_keys_has_no_prefixes() {
+        let format = SsTableFormat {
+            prefix_extractor: Some(Arc::new(FixedLengthPrefixExtractor::new(3))),
+            ..SsTableFormat::default()
+        };
+        let mut builder = format.table_builder().with_column_family_keys();
+        builder.add_value(b"aaa1", b"value1", gen_attrs(1)).unwrap();
+        let encoded = builder.build().unwrap();
+
+        assert_eq!(encoded.info.prefix_extractor, None);
+        assert!(!encoded.filter.unwrap().might_contain(filter_hash(b"aaa")));
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_sstable() {
+        let root_path = Path::from("");
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let format = SsTableFormat {
+            encryption_provider: Some(Arc::new(XorEncryptionProvider::new("k1", 0x5a))),
+            ..SsTableFormat::default()
+        };
+        let table_store = TableStore::new(
+            ObjectStores::new(object_store.clone(), None),
+            format,
+            root_path.clone(),
+            None,
+        );
+        let mut builder = table_store.table_builder();
+        builder
+            .add_value(b"secret-key", b"secret-value", gen_attrs(1))
+            .unwrap();
+        let encoded = builder.build().unwrap();
+        let encoded_info = encoded.info.clone();
+        let data = encoded.remaining_as_bytes();
+        for plaintext in [&b"secret-key"[..], b"secret-value"] {
+            assert!(!data.windows(plaintext.len()).any(|w| w == plaintext));
+        }
+        assert_eq!(
+            (&data[data.len() - VERSION_SIZE..]).get_u16(),
+            ENCRYPTED_SST_FORMAT_VERSION
+        );
+        table_store
+            .write_sst(&SsTableId::Wal(0), encoded, false)
+            .await
+            .unwrap();
+
+        // when:
+        let sst_handle = table_store.open_sst(&SsTableId::Wal(0)).await.unwrap();
+        let filter = table_store.read_filter(&sst_handle).await.unwrap().unwrap();
+        let mut blocks = table_store.read_blocks(&sst_handle, 0..1).await.unwrap();
+
+        // then:
+        assert_eq!(encoded_info, sst_handle.info);
+        assert_eq!(sst_handle.info.encryption_key_id.as_deref(), Some("k1"));
+        assert!(filter.might_contain(filter_hash(b"secret-key")));
+        let mut iter = BlockIterator::new(blocks.pop_front().unwrap(), Ascending);
+        assert_iterator(
+            &mut iter,
+            vec![RowEntry::new_value(b"secret-key", b"secret-value", 0).with_create_ts(1)],
+        )
+        .await;
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_sstable_fails_to_open_without_key() {
+        let root_path = Path::from("");
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let format = SsTableFormat {
+            encryption_provider: Some(Arc::new(XorEncryptionProvider::new("k1", 0x5a))),
+            ..SsTableFormat::default()
+        };
+        let mut builder = format.table_builder();
+        builder.add_value(b"key", b"value", gen_attrs(1)).unwrap();
+        let table_store = TableStore::new(
+            ObjectStores::new(object_store, None),
+            SsTableFormat::default(),
+            root_path,
+            None,
+        );
+        table_store
+            .write_sst(&SsTableId::Wal(0), builder.build().unwrap(), false)
+            .await
+            .unwrap();
+
+        // when:
+        let result = table_store.open_sst(&SsTableId::Wal(0)).await;
+
+        // then:
+        assert!(matches!(
+            result,
+            Err(SlateDBError::EncryptionKeyUnavailable { key_id }) if key_id == "k1"
+        ));
+    }
+
     #[rstest]
     #[case::none(None)]
     #[cfg_attr(feature = "snappy", case::snappy(Some(CompressionCodec::Snappy)))]
@@ -8,6 +8,7 @@ use std::sync::Arc;
 use tokio::task::JoinHandle;
 
 use crate::bytes_range::BytesRange;
+use crate::comparator::KeyComparator;
 use crate::db_state::{SsTableHandle, SsTableId};
 use crate::db_stats::DbStats;
 use crate::error::SlateDBError;
@@ -16,10 +17,10 @@ use crate::flatbuffer_types::{SsTableIndex, SsTableIndexOwned};
 use crate::{
     block::Block,
     block_iterator::BlockIterator,
-    iter::{init_optional_iterator, KeyValueIterator},
+    iter::{init_optional_iterator, IterationOrder, KeyValueIterator},
     partitioned_keyspace,
     tablestore::TableStore,
-    types::RowEntry,
+    types::{RowEntry, ValueDeletable},
 };
 
 enum FetchTask {
@@ -33,6 +34,10 @@ pub(crate) struct SstIteratorOptions {
     pub(crate) blocks_to_fetch: usize,
     pub(crate) cache_blocks: bool,
     pub(crate) eager_spawn: bool,
+    pub(crate) order: IterationOrder,
+    /// Whether rows pointing to values in blob files are returned with the values.
+    /// Compactions that don't need the values leave the pointers in place.
+    pub(crate) resolve_blob_refs: bool,
 }
 
 impl Default for SstIteratorOptions {
@@ -42,6 +47,8 @@ impl Default for SstIteratorOptions {
             blocks_to_fetch: 1,
             cache_blocks: true,
             eager_spawn: false,
+            order: IterationOrder::Ascending,
+            resolve_blob_refs: true,
         }
     }
 }
@@ -82,20 +89,30 @@ impl SstView<'_> {
         }
     }
 
-    /// Check whether a key is contained within this view.
-    fn contains(&self, key: &[u8]) -> bool {
+    fn range(&self) -> &BytesRange {
         match self {
-            SstView::Owned(_, r) => r.contains(key),
-            SstView::Borrowed(_, r) => r.contains(key),
+            SstView::Owned(_, r) | SstView::Borrowed(_, r) => r,
         }
     }
 
-    /// Check whether a key exceeds the range of this view.
-    fn key_exceeds(&self, key: &[u8]) -> bool {
-        match self.end_key() {
-            Included(end) => key > end,
-            Excluded(end) => key >= end,
-            Unbounded => false,
+    /// Check whether a key is contained within this view.
+    fn contains(&self, key: &[u8], comparator: &KeyComparator) -> bool {
+        self.range().contains_by(key, comparator)
+    }
+
+    /// Check whether a key is past the range of this view in the given iteration order.
+    fn key_beyond(&self, key: &[u8], order: IterationOrder, comparator: &KeyComparator) -> bool {
+        match order {
+            IterationOrder::Ascending => self.range().key_exceeds(key, comparator),
+            IterationOrder::Descending => self.range().key_precedes(key, comparator),
+        }
+    }
+
+    /// The bound of this view at which iteration in the given order begins.
+    fn first_key(&self, order: IterationOrder) -> Bound<&[u8]> {
+        match order {
+            IterationOrder::Ascending => self.start_key(),
+            IterationOrder::Descending => self.end_key(),
         }
     }
 }
@@ -142,7 +159,8 @@ enum FilterState {
 
 struct BloomFilterEvaluator {
     key: Bytes,
-    db_stats: Option<DbStats>,
+    /// Boxed, since the stats are large compared to the rest of the iterator.
+    db_stats: Option<Box<DbStats>>,
     state: FilterState,
     found_key: bool,
     false_positive_recorded: bool,
@@ -152,7 +170,7 @@ impl BloomFilterEvaluator {
     fn new(key: Bytes, db_stats: Option<DbStats>) -> Self {
         Self {
             key,
-            db_stats,
+            db_stats: db_stats.map(Box::new),
             state: FilterState::NotChecked,
             found_key: false,
             false_positive_recorded: false,
@@ -219,11 +237,13 @@ pub(crate) struct InternalSstIterator<'a> {
     view: SstView<'a>,
     index: Option<Arc<SsTableIndexOwned>>,
     state: IteratorState,
-    next_block_idx_to_fetch: usize,
-    block_idx_range: Range<usize>,
+    /// The blocks covering the view that have not been fetched yet. Ascending iterators
+    /// fetch from the front of the range, descending iterators from the back.
+    blocks_to_fetch: Range<usize>,
     fetch_tasks: VecDeque<FetchTask>,
     table_store: Arc<TableStore>,
     options: SstIteratorOptions,
+    comparator: KeyComparator,
 }
 
 impl<'a> InternalSstIterator<'a> {
@@ -239,9 +259,9 @@ impl<'a> InternalSstIterator<'a> {
             view,
             index: None,
             state: IteratorState::new(),
-            next_block_idx_to_fetch: 0,
-            block_idx_range: 0..0,
+            blocks_to_fetch: 0..0,
             fetch_tasks: VecDeque::new(),
+            comparator: table_store.comparator().clone(),
             table_store,
             options,
         })
@@ -265,7 +285,7 @@ impl<'a> InternalSstIterator<'a> {
         table_store: Arc<TableStore>,
         options: SstIteratorOptions,
     ) -> Result<Option<Self>, SlateDBError> {
-        let Some(view_range) = table.calculate_view_range(BytesRange::from(range)) else {
+        let Some(view_range) = Self::view_range(range, &table, &table_store) else {
             return Ok(None);
         };
         let view = SstView::Owned(Box::new(table), view_range);
@@ -288,7 +308,7 @@ impl<'a> InternalSstIterator<'a> {
         table_store: Arc<TableStore>,
         options: SstIteratorOptions,
     ) -> Result<Option<Self>, SlateDBError> {
-        let Some(view_range) = table.calculate_view_range(BytesRange::from(range)) else {
+        let Some(view_range) = Self::view_range(range, table, &table_store) else {
             return Ok(None);
         };
         let view = SstView::Borrowed(table, view_range);
@@ -305,6 +325,21 @@ impl<'a> InternalSstIterator<'a> {
         init_optional_iterator(iter).await
     }
 
+    /// Returns the part of `range` that's visible in `table`, or None if none of it is.
+    fn view_range<T: RangeBounds<Bytes>>(
+        range: T,
+        table: &SsTableHandle,
+        table_store: &TableStore,
+    ) -> Option<BytesRange> {
+        let comparator = table_store.comparator();
+        let range = BytesRange::new_by(
+            range.start_bound().cloned(),
+            range.end_bound().cloned(),
+            comparator,
+        );
+        table.calculate_view_range(range, comparator)
+    }
+
     fn for_key(
         table: &'a SsTableHandle,
         key: &'a [u8],
@@ -329,28 +364,40 @@ impl<'a> InternalSstIterator<'a> {
         init_optional_iterator(iter).await
     }
 
-    fn last_block_with_data_including_key(index: &SsTableIndex, key: &[u8]) -> Option<usize> {
-        partitioned_keyspace::last_partition_including_key(index, key)
+    fn last_block_with_data_including_key(
+        index: &SsTableIndex,
+        key: &[u8],
+        comparator: &KeyComparator,
+    ) -> Option<usize> {
+        partitioned_keyspace::last_partition_including_key(index, key, comparator)
     }
 
-    fn first_block_with_data_including_or_after_key(index: &SsTableIndex, key: &[u8]) -> usize {
-        partitioned_keyspace::first_partition_including_or_after_key(index, key)
+    fn first_block_with_data_including_or_after_key(
+        index: &SsTableIndex,
+        key: &[u8],
+        comparator: &KeyComparator,
+    ) -> usize {
+        partitioned_keyspace::first_partition_including_or_after_key(index, key, comparator)
     }
 
-    fn blocks_covering_view(index: &SsTableIndex, view: &SstView) -> Range<usize> {
+    fn blocks_covering_view(
+        index: &SsTableIndex,
+        view: &SstView,
+        comparator: &KeyComparator,
+    ) -> Range<usize> {
         let start_block_id = match view.start_key() {
             Included(k) | Excluded(k) => {
-                Self::first_block_with_data_including_or_after_key(index, k)
+                Self::first_block_with_data_including_or_after_key(index, k, comparator)
             }
             Unbounded => 0,
         };
 
         let end_block_id_exclusive = match view.end_key() {
-            Included(k) => Self::last_block_with_data_including_key(index, k)
+            Included(k) => Self::last_block_with_data_including_key(index, k, comparator)
                 .map(|b| b + 1)
                 .unwrap_or(start_block_id),
             Excluded(k) => {
-                let block_index = Self::last_block_with_data_including_key(index, k);
+                let block_index = Self::last_block_with_data_including_key(index, k, comparator);
                 match block_index {
                     None => start_block_id,
                     Some(block_index) => {
@@ -374,30 +421,41 @@ impl<'a> InternalSstIterator<'a> {
             return;
         };
         while self.fetch_tasks.len() < self.options.max_fetch_tasks
-            && self.block_idx_range.contains(&self.next_block_idx_to_fetch)
+            && !self.blocks_to_fetch.is_empty()
         {
-            let blocks_to_fetch = min(
-                self.options.blocks_to_fetch,
-                self.block_idx_range.end - self.next_block_idx_to_fetch,
-            );
+            let blocks_to_fetch = min(self.options.blocks_to_fetch, self.blocks_to_fetch.len());
+            let (blocks_start, blocks_end) = match self.options.order {
+                IterationOrder::Ascending => {
+                    let start = self.blocks_to_fetch.start;
+                    self.blocks_to_fetch.start += blocks_to_fetch;
+                    (start, start + blocks_to_fetch)
+                }
+                IterationOrder::Descending => {
+                    let end = self.blocks_to_fetch.end;
+                    self.blocks_to_fetch.end -= blocks_to_fetch;
+                    (end - blocks_to_fetch, end)
+                }
+            };
             let table = self.view.table_as_ref().clone();
             let table_store = self.table_store.clone();
-            let blocks_start = self.next_block_idx_to_fetch;
-            let blocks_end = self.next_block_idx_to_fetch + blocks_to_fetch;
             let index = index.clone();
             let cache_blocks = self.options.cache_blocks;
+            let order = self.options.order;
             self.fetch_tasks
                 .push_back(FetchTask::InFlight(tokio::spawn(async move {
-                    table_store
+                    let blocks = table_store
                         .read_blocks_using_index(
                             &table,
                             index,
                             blocks_start..blocks_end,
                             cache_blocks,
                         )
-                        .await
+                        .await?;
+                    Ok(match order {
+                        IterationOrder::Ascending => blocks,
+                        IterationOrder::Descending => blocks.into_iter().rev().collect(),
+                    })
                 })));
-            self.next_block_idx_to_fetch = blocks_end;
         }
     }
 
@@ -420,7 +478,10 @@ impl<'a> InternalSstIterator<'a> {
                     }
                     FetchTask::Finished(blocks) => {
                         if let Some(block) = blocks.pop_front() {
-                            return Ok(Some(BlockIterator::new_ascending(block)));
+                            return Ok(Some(
+                                BlockIterator::new(block, self.options.order)
+                                    .with_comparator(self.comparator.clone()),
+                            ));
                         } else {
                             self.fetch_tasks.pop_front();
                         }
@@ -428,7 +489,9 @@ impl<'a> InternalSstIterator<'a> {
                 }
             } else {
                 assert!(self.fetch_tasks.is_empty());
-                assert_eq!(self.next_block_idx_to_fetch, self.block_idx_range.end);
+                // when draining without spawning (see `seek`), there may still be
+                // blocks left to fetch
+                assert!(!spawn_fetches || self.blocks_to_fetch.is_empty());
                 return Ok(None);
             }
         }
@@ -438,8 +501,8 @@ impl<'a> InternalSstIterator<'a> {
         self.fetch_index().await?;
         if !self.state.is_finished() {
             if let Some(mut iter) = self.next_iter(true).await? {
-                match self.view.start_key() {
-                    Included(start_key) | Excluded(start_key) => iter.seek(start_key).await?,
+                match self.view.first_key(self.options.order) {
+                    Included(first_key) | Excluded(first_key) => iter.seek(first_key).await?,
                     Unbounded => (),
                 }
                 self.state.advance(iter);
@@ -451,10 +514,7 @@ impl<'a> InternalSstIterator<'a> {
     }
 
     fn stop(&mut self) {
-        if let Some(index) = self.index.as_ref() {
-            let num_blocks = index.borrow().block_meta().len();
-            self.next_block_idx_to_fetch = num_blocks;
-        }
+        self.blocks_to_fetch = self.blocks_to_fetch.start..self.blocks_to_fetch.start;
         self.state.stop();
     }
 
@@ -464,10 +524,11 @@ impl<'a> InternalSstIterator<'a> {
                 .table_store
                 .read_index(self.view.table_as_ref())
                 .await?;
-            let block_idx_range =
-                InternalSstIterator::blocks_covering_view(&index.borrow(), &self.view);
-            self.block_idx_range = block_idx_range.clone();
-            self.next_block_idx_to_fetch = block_idx_range.start;
+            self.blocks_to_fetch = InternalSstIterator::blocks_covering_view(
+                &index.borrow(),
+                &self.view,
+                &self.comparator,
+            );
             self.index = Some(index);
             if self.options.eager_spawn {
                 self.spawn_fetches();
@@ -498,10 +559,19 @@ impl KeyValueIterator for InternalSstIterator<'_> {
             };
 
             match next_entry {
-                Some(kv) => {
-                    if self.view.contains(&kv.key) {
+                Some(mut kv) => {
+                    if self.view.contains(&kv.key, &self.comparator) {
+                        if let ValueDeletable::BlobRef(blob_ref) = &kv.value {
+                            if self.options.resolve_blob_refs {
+                                let value = self.table_store.read_blob_value(blob_ref).await?;
+                                kv.value = ValueDeletable::Value(value);
+                            }
+                        }
                         return Ok(Some(kv));
-                    } else if self.view.key_exceeds(&kv.key) {
+                    } else if self
+                        .view
+                        .key_beyond(&kv.key, self.options.order, &self.comparator)
+                    {
                         self.stop()
                     }
                 }
@@ -515,7 +585,7 @@ impl KeyValueIterator for InternalSstIterator<'_> {
         if !self.state.is_initialized() {
             return Err(SlateDBError::IteratorNotInitialized);
         }
-        if !self.view.contains(next_key) {
+        if !self.view.contains(next_key, &self.comparator) {
             return Err(SlateDBError::SeekKeyOutOfKeyRange {
                 key: next_key.to_vec(),
                 start_key: self.view.start_key().map(|b| b.to_vec()),
@@ -535,9 +605,37 @@ impl KeyValueIterator for InternalSstIterator<'_> {
                 .as_ref()
                 .expect("metadata must be initialized")
                 .clone();
-            let block_idx =
-                Self::first_block_with_data_including_or_after_key(&index.borrow(), next_key);
-            if block_idx < self.next_block_idx_to_fetch {
+            // whether the block containing next_key has already been fetched (or is
+            // being fetched), in which case we should drain the fetched blocks instead
+            // of scheduling new fetches
+            let already_fetched = match self.options.order {
+                IterationOrder::Ascending => {
+                    let block_idx = Self::first_block_with_data_including_or_after_key(
+                        &index.borrow(),
+                        next_key,
+                        &self.comparator,
+                    );
+                    let already_fetched = block_idx < self.blocks_to_fetch.start;
+                    if !already_fetched {
+                        self.blocks_to_fetch.start = block_idx;
+                    }
+                    already_fetched
+                }
+                IterationOrder::Descending => {
+                    let block_idx_end = Self::last_block_with_data_including_key(
+                        &index.borrow(),
+                        next_key,
+                        &self.comparator,
+                    )
+                    .map_or(0, |block_idx| block_idx + 1);
+                    let already_fetched = block_idx_end > self.blocks_to_fetch.end;
+                    if !already_fetched {
+                        self.blocks_to_fetch.end = block_idx_end;
+                    }
+                    already_fetched
+                }
+            };
+            if already_fetched {
                 while let Some(mut block_iter) = self.next_iter(false).await? {
                     block_iter.seek(next_key).await?;
                     if !block_iter.is_empty() {
@@ -548,7 +646,6 @@ impl KeyValueIterator for InternalSstIterator<'_> {
             }
 
             self.fetch_tasks.clear();
-            self.next_block_idx_to_fetch = block_idx;
             if let Some(mut block_iter) = self.next_iter(true).await? {
                 block_iter.seek(next_key).await?;
                 self.state.advance(block_iter);
@@ -796,6 +893,13 @@ impl<'a> SstIterator<'a> {
         }
     }
 
+    pub(crate) fn table(&self) -> &SsTableHandle {
+        match &self.delegate {
+            SstIteratorDelegate::Direct(inner) => inner.view().table_as_ref(),
+            SstIteratorDelegate::Bloom(inner) => inner.inner.view().table_as_ref(),
+        }
+    }
+
     #[allow(dead_code)]
     pub(crate) fn is_filtered_out(&self) -> bool {
         match &self.delegate {
@@ -1101,6 +1205,71 @@ mod tests {
         assert!(next.is_none());
     }
 
+    #[tokio::test]
+    async fn test_many_block_sst_iter_descending() {
+        let root_path = Path::from("");
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let format = SsTableFormat {
+            min_filter_keys: 3,
+            ..SsTableFormat::default()
+        };
+        let table_store = Arc::new(TableStore::new(
+            ObjectStores::new(object_store, None),
+            format,
+            root_path.clone(),
+            None,
+        ));
+        let mut builder = table_store.table_builder();
+
+        for i in 0..1000 {
+            builder
+                .add_value(
+                    format!("key{:04}", i).as_bytes(),
+                    format!("value{}", i).as_bytes(),
+                    gen_attrs(i),
+                )
+                .unwrap();
+        }
+
+        let encoded = builder.build().unwrap();
+        table_store
+            .write_sst(&SsTableId::Wal(0), encoded, false)
+            .await
+            .unwrap();
+        let sst_handle = table_store.open_sst(&SsTableId::Wal(0)).await.unwrap();
+
+        let sst_iter_options = SstIteratorOptions {
+            max_fetch_tasks: 3,
+            blocks_to_fetch: 3,
+            order: IterationOrder::Descending,
+            ..SstIteratorOptions::default()
+        };
+        let mut iter = SstIterator::new_owned_initialized(
+            BytesRange::from_ref("key0100"..="key0899"),
+            sst_handle,
+            table_store.clone(),
+            sst_iter_options,
+        )
+        .await
+        .unwrap()
+        .expect("Expected Some(iter) but got None");
+        for i in (500..900).rev() {
+            let kv = iter.next().await.unwrap().unwrap();
+            assert_eq!(kv.key, format!("key{:04}", i));
+            assert_eq!(kv.value, format!("value{}", i));
+        }
+
+        // seek across several blocks towards the start of the range
+        iter.seek(b"key0250").await.unwrap();
+        for i in (100..=250).rev() {
+            let kv = iter.next().await.unwrap().unwrap();
+            assert_eq!(kv.key, format!("key{:04}", i));
+        }
+
+        let next = iter.next().await.unwrap();
+        assert!(next.is_none());
+    }
+
     #[tokio::test]
     async fn test_iter_from_key() {
         let root_path = Path::from("");
@@ -1260,6 +1429,7 @@ mod tests {
                 blocks_to_fetch: 256,
                 cache_blocks: true,
                 eager_spawn: false,
+                ..SstIteratorOptions::default()
             },
         )
         .await
@@ -1275,6 +1445,7 @@ mod tests {
                 blocks_to_fetch: 1,
                 cache_blocks: true,
                 eager_spawn: false,
+                ..SstIteratorOptions::default()
             },
         )
         .await
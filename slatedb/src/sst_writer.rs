@@ -0,0 +1,177 @@
+use std::sync::Arc;
+
+use bytes::Bytes;
+use object_store::buffered::BufWriter;
+use object_store::path::Path;
+use object_store::ObjectStore;
+use tokio::io::AsyncWriteExt;
+
+use crate::comparator::{Comparator, KeyComparator};
+use crate::config::{CompressionCodec, SstBlockSize};
+use crate::error::SlateDBError;
+use crate::sst::{EncodedSsTableBuilder, SsTableFormat};
+use crate::types::{RowEntry, ValueDeletable};
+
+/// Writes an SST outside of a database, so that large datasets can be built offline and
+/// bulk loaded with [`crate::Db::ingest_ssts`] instead of going through the WAL and
+/// memtable.
+///
+/// Rows must be added in strictly increasing key order, as defined by the comparator set
+/// with [`SstWriter::with_comparator`] if the database has one. Blocks are uploaded to the object
+/// store as they fill up, and the SST is complete once [`SstWriter::close`] returns. The
+/// rows don't carry sequence numbers; they're assigned when the SST is ingested.
+///
+/// # Examples
+/// ```
+/// use slatedb::{Db, Error, SstWriter};
+/// use slatedb::object_store::{ObjectStore, memory::InMemory, path::Path};
+/// use std::sync::Arc;
+///
+/// #[tokio::main]
+/// async fn main() -> Result<(), Error> {
+///     let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+///     let path = Path::from("ingest/000001.sst");
+///     let mut writer = SstWriter::new(path.clone(), object_store.clone());
+///     writer.put(b"key1", b"value1").await?;
+///     writer.put(b"key2", b"value2").await?;
+///     writer.close().await?;
+///
+///     let db = Db::open("test_db", object_store).await?;
+///     db.ingest_ssts(&[path]).await?;
+///     assert_eq!(db.get(b"key1").await?, Some("value1".into()));
+///     Ok(())
+/// }
+/// ```
+pub struct SstWriter {
+    format: SsTableFormat,
+    builder: Option<EncodedSsTableBuilder<'static>>,
+    writer: BufWriter,
+    last_key: Option<Bytes>,
+}
+
+impl SstWriter {
+    /// Creates a writer for an SST at `path` in `object_store`.
+    pub fn new<P: Into<Path>>(path: P, object_store: Arc<dyn ObjectStore>) -> Self {
+        Self {
+            format: SsTableFormat::default(),
+            builder: None,
+            writer: BufWriter::new(object_store, path.into()),
+            last_key: None,
+        }
+    }
+
+    /// Sets the size of the SST's blocks. Must be called before any row is added.
+    pub fn with_sst_block_size(mut self, sst_block_size: SstBlockSize) -> Self {
+        assert!(self.builder.is_none(), "rows were already added");
+        self.format.block_size = sst_block_size.as_bytes();
+        self
+    }
+
+    /// Sets the codec used to compress the SST's blocks. Must be called before any row
+    /// is added.
+    pub fn with_compression_codec(mut self, compression_codec: CompressionCodec) -> Self {
+        assert!(self.builder.is_none(), "rows were already added");
+        self.format.compression_codec = Some(compression_codec);
+        self
+    }
+
+    /// Sets the comparator that orders the rows of the SST, which must be the comparator
+    /// of the database the SST is ingested into. Must be called before any row is added.
+    pub fn with_comparator(mut self, comparator: Arc<dyn Comparator + Send + Sync>) -> Self {
+        assert!(self.builder.is_none(), "rows were already added");
+        self.format.comparator = KeyComparator::new(Some(comparator));
+        self
+    }
+
+    /// Adds a value for `key`, which must be greater than the key of the previous row.
+    pub async fn put<K, V>(&mut self, key: K, value: V) -> Result<(), crate::Error>
+    where
+        K: AsRef<[u8]>,
+        V: AsRef<[u8]>,
+    {
+        let value = ValueDeletable::Value(Bytes::copy_from_slice(value.as_ref()));
+        self.add(RowEntry::new(
+            Bytes::copy_from_slice(key.as_ref()),
+            value,
+            0,
+            None,
+            None,
+        ))
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Adds a tombstone for `key`, which must be greater than the key of the previous row.
+    /// Once ingested, the tombstone hides older values of the key in the database.
+    pub async fn delete<K: AsRef<[u8]>>(&mut self, key: K) -> Result<(), crate::Error> {
+        let value = ValueDeletable::Tombstone;
+        self.add(RowEntry::new(
+            Bytes::copy_from_slice(key.as_ref()),
+            value,
+            0,
+            None,
+            None,
+        ))
+        .await
+        .map_err(Into::into)
+    }
+
+    /// Writes the remaining blocks, the index, and the filter of the SST, and completes
+    /// the upload.
+    pub async fn close(mut self) -> Result<(), crate::Error> {
+        self.finish().await.map_err(Into::into)
+    }
+
+    async fn add(&mut self, entry: RowEntry) -> Result<(), SlateDBError> {
+        if let Some(last_key) = &self.last_key {
+            if self.format.comparator.compare(&entry.key, last_key).is_le() {
+                return Err(SlateDBError::InvalidKeyOrder {
+                    last_key: last_key.clone(),
+                    key: entry.key,
+                });
+            }
+        }
+        self.last_key = Some(entry.key.clone());
+        let format = &self.format;
+        let builder = self.builder.get_or_insert_with(|| format.table_builder());
+        builder.add(entry)?;
+        while let Some(block) = builder.next_block() {
+            self.writer.write_all(block.encoded_bytes.as_ref()).await?;
+        }
+        Ok(())
+    }
+
+    async fn finish(&mut self) -> Result<(), SlateDBError> {
+        let builder = match self.builder.take() {
+            Some(builder) => builder,
+            None => self.format.table_builder(),
+        };
+        let mut encoded_sst = builder.build()?;
+        while let Some(block) = encoded_sst.unconsumed_blocks.pop_front() {
+            self.writer.write_all(block.encoded_bytes.as_ref()).await?;
+        }
+        self.writer.write_all(encoded_sst.footer.as_ref()).await?;
+        self.writer.shutdown().await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ErrorKind;
+    use object_store::memory::InMemory;
+
+    #[tokio::test]
+    async fn test_should_reject_unsorted_keys() {
+        let object_store: Arc<dyn ObjectStore> = Arc::new(InMemory::new());
+        let mut writer = SstWriter::new("ingest/unsorted.sst", object_store);
+        writer.put(b"key2", b"value2").await.unwrap();
+
+        let err = writer.put(b"key1", b"value1").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Invalid);
+        let err = writer.delete(b"key2").await.unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::Invalid);
+        writer.put(b"key3", b"value3").await.unwrap();
+    }
+}
@@ -3,6 +3,8 @@
 //! Rather than integrate with observability platforms such as Prometheus or InfluxDB,
 //! SlateDB exposes metrics through [`Db::metrics`]. Applications can get the registry
 //! and poll it periodically to expose SlateDB metrics to their observability systems.
+//! With the `openmetrics` feature, the `openmetrics` module renders the registry in the
+//! OpenMetrics text format that Prometheus scrapes.
 //!
 //! This module provides a flexible and thread-safe metrics collection system for tracking
 //! and monitoring various runtime statistics in SlateDB.
@@ -10,10 +12,10 @@
 //! ## Components
 //!
 //! * [`ReadableStat`]: Core trait implemented by all metric types, providing a way to read
-//!   the current value as an `i64`.
+//!   the current value as an `i64`, and the [`StatType`] of the metric.
 //!
 //! * [`StatRegistry`]: Central repository for registering and looking up metrics by name.
-//!   Provides atomic, thread-safe access to all registered metrics.
+//!   Provides atomic, thread-safe access to all registered metrics and their help text.
 //!
 //! * [`Counter`]: Atomic counter for tracking incrementing values.
 //!
@@ -21,6 +23,10 @@
 //!   Special implementations exist for common types like `i64`, `u64`, `i32`, and `bool`.
 //!   Gauges for numeric types provide additional operations like [`add()`], [`sub()`], etc.
 //!
+//! * [`Histogram`]: Lock-free distribution of values, such as request latencies, counted in
+//!   buckets with power-of-two upper bounds. Read its buckets through
+//!   [`ReadableStat::histogram`].
+//!
 //! * [`stat_name!`]: Macro for standardizing metric name formats by combining a prefix
 //!   and suffix with a separator.
 //!
@@ -57,17 +63,51 @@
 //! ```
 use std::collections::BTreeMap;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 
 use atomic::{Atomic, Ordering};
 use bytemuck::NoUninit;
+use chrono::{DateTime, Utc};
 use log::warn;
 
+use crate::clock::SystemClock;
+
+#[cfg(feature = "openmetrics")]
+pub mod openmetrics;
+
+/// The type of a metric, which tells observability systems how to interpret its value.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StatType {
+    /// A value that only ever increases, such as the number of requests.
+    Counter,
+    /// A value that can go up and down, such as the number of bytes in a buffer.
+    Gauge,
+    /// A distribution of values, such as request latencies.
+    Histogram,
+}
+
 pub trait ReadableStat: Send + Sync + std::fmt::Debug {
     fn get(&self) -> i64;
+
+    /// Returns the type of the metric. Metrics are gauges unless they say otherwise.
+    fn stat_type(&self) -> StatType {
+        StatType::Gauge
+    }
+
+    /// Returns the buckets of the metric if it is a [`StatType::Histogram`].
+    fn histogram(&self) -> Option<HistogramSnapshot> {
+        None
+    }
+}
+
+struct RegisteredStat {
+    help: &'static str,
+    stat: Arc<dyn ReadableStat>,
 }
 
 pub struct StatRegistry {
-    stats: Mutex<BTreeMap<&'static str, Arc<dyn ReadableStat>>>,
+    stats: Mutex<BTreeMap<&'static str, RegisteredStat>>,
 }
 
 impl StatRegistry {
@@ -81,7 +121,14 @@ impl StatRegistry {
     /// for the name.
     pub fn lookup(&self, name: &'static str) -> Option<Arc<dyn ReadableStat>> {
         let guard = self.stats.lock().expect("lock poisoned");
-        guard.get(name).cloned()
+        guard.get(name).map(|registered| registered.stat.clone())
+    }
+
+    /// Get the help text that describes a metric, or `None` if no metric was registered
+    /// for the name.
+    pub fn help(&self, name: &'static str) -> Option<&'static str> {
+        let guard = self.stats.lock().expect("lock poisoned");
+        guard.get(name).map(|registered| registered.help)
     }
 
     pub fn names(&self) -> Vec<&'static str> {
@@ -89,8 +136,24 @@ impl StatRegistry {
         guard.keys().copied().collect()
     }
 
-    /// Register a new metric with the registry.
-    pub(crate) fn register(&self, name: &'static str, stat: Arc<dyn ReadableStat>) {
+    /// Returns the name, help text and value of every registered metric, ordered by name.
+    #[cfg_attr(not(feature = "openmetrics"), allow(dead_code))]
+    pub(crate) fn entries(&self) -> Vec<(&'static str, &'static str, Arc<dyn ReadableStat>)> {
+        let guard = self.stats.lock().expect("lock poisoned");
+        guard
+            .iter()
+            .map(|(name, registered)| (*name, registered.help, registered.stat.clone()))
+            .collect()
+    }
+
+    /// Register a new metric with the registry. The help text is a sentence that
+    /// describes the metric to the users of observability systems.
+    pub(crate) fn register(
+        &self,
+        name: &'static str,
+        help: &'static str,
+        stat: Arc<dyn ReadableStat>,
+    ) {
         let mut guard = self.stats.lock().expect("lock poisoned");
         debug_assert!(!guard.contains_key(name));
         if guard.contains_key(name) {
@@ -100,7 +163,7 @@ impl StatRegistry {
             );
             return;
         }
-        guard.insert(name, stat);
+        guard.insert(name, RegisteredStat { help, stat });
     }
 }
 
@@ -119,6 +182,10 @@ impl ReadableStat for Counter {
     fn get(&self) -> i64 {
         self.value.load(Ordering::Relaxed) as i64
     }
+
+    fn stat_type(&self) -> StatType {
+        StatType::Counter
+    }
 }
 
 impl Counter {
@@ -210,6 +277,154 @@ impl Gauge<i64> {
     }
 }
 
+/// The number of buckets of a [`Histogram`], excluding its overflow bucket. The upper
+/// bounds of the buckets are the powers of two from 1 to 2^27, which is about 134 seconds
+/// for latencies in microseconds.
+pub const HISTOGRAM_BUCKET_COUNT: usize = 28;
+
+/// A lock-free histogram of `u64` values. Each value is counted in the first bucket whose
+/// upper bound is at least the value, or in an overflow bucket if the value is larger than
+/// every bound. [`ReadableStat::get`] returns the number of recorded values.
+#[derive(Clone)]
+pub struct Histogram {
+    buckets: Arc<[Atomic<u64>; HISTOGRAM_BUCKET_COUNT + 1]>,
+    sum: Arc<Atomic<u64>>,
+}
+
+impl std::fmt::Debug for Histogram {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{:?}", self.snapshot())
+    }
+}
+
+impl ReadableStat for Histogram {
+    fn get(&self) -> i64 {
+        self.snapshot().count() as i64
+    }
+
+    fn stat_type(&self) -> StatType {
+        StatType::Histogram
+    }
+
+    fn histogram(&self) -> Option<HistogramSnapshot> {
+        Some(self.snapshot())
+    }
+}
+
+impl Histogram {
+    pub fn record(&self, value: u64) {
+        self.buckets[Self::bucket_index(value)].fetch_add(1, Ordering::Relaxed);
+        self.sum.fetch_add(value, Ordering::Relaxed);
+    }
+
+    /// Returns a point-in-time copy of the buckets of the histogram.
+    pub fn snapshot(&self) -> HistogramSnapshot {
+        HistogramSnapshot {
+            buckets: self
+                .buckets
+                .iter()
+                .map(|bucket| bucket.load(Ordering::Relaxed))
+                .collect(),
+            sum: self.sum.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Records a duration in microseconds.
+    pub(crate) fn record_duration(&self, duration: Duration) {
+        self.record(u64::try_from(duration.as_micros()).unwrap_or(u64::MAX));
+    }
+
+    /// Starts timing an operation with `clock`. The elapsed time in microseconds is recorded
+    /// when the returned timer is dropped, so operations that return early are timed too.
+    pub(crate) fn start_timer<'a>(&'a self, clock: &'a dyn SystemClock) -> HistogramTimer<'a> {
+        HistogramTimer {
+            histogram: self,
+            clock,
+            start: clock.now(),
+        }
+    }
+
+    fn bucket_index(value: u64) -> usize {
+        // the index of the smallest power of two that is at least the value
+        let index = match value {
+            0 | 1 => 0,
+            _ => (u64::BITS - (value - 1).leading_zeros()) as usize,
+        };
+        index.min(HISTOGRAM_BUCKET_COUNT)
+    }
+}
+
+impl Default for Histogram {
+    fn default() -> Self {
+        Self {
+            buckets: Arc::new(std::array::from_fn(|_| Atomic::<u64>::default())),
+            sum: Arc::new(Atomic::<u64>::default()),
+        }
+    }
+}
+
+/// Records the time elapsed since it was created in a [`Histogram`] when it is dropped.
+pub(crate) struct HistogramTimer<'a> {
+    histogram: &'a Histogram,
+    clock: &'a dyn SystemClock,
+    start: DateTime<Utc>,
+}
+
+impl Drop for HistogramTimer<'_> {
+    fn drop(&mut self) {
+        // the clock may go backwards, in which case no time elapsed
+        let elapsed = (self.clock.now() - self.start).to_std().unwrap_or_default();
+        self.histogram.record_duration(elapsed);
+    }
+}
+
+/// A point-in-time copy of the buckets of a [`Histogram`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct HistogramSnapshot {
+    buckets: Vec<u64>,
+    sum: u64,
+}
+
+impl HistogramSnapshot {
+    /// Returns the number of recorded values.
+    pub fn count(&self) -> u64 {
+        self.buckets.iter().sum()
+    }
+
+    /// Returns the sum of the recorded values.
+    pub fn sum(&self) -> u64 {
+        self.sum
+    }
+
+    /// Returns the upper bound and the number of values of each bucket, in increasing order
+    /// of bounds. The overflow bucket comes last and has no upper bound.
+    pub fn buckets(&self) -> impl Iterator<Item = (Option<u64>, u64)> + '_ {
+        self.buckets
+            .iter()
+            .enumerate()
+            .map(|(i, count)| ((i < HISTOGRAM_BUCKET_COUNT).then(|| 1 << i), *count))
+    }
+
+    /// Estimates the value at the quantile `q`, between 0.0 and 1.0, as the upper bound of
+    /// the bucket holding it. Returns `None` if no values were recorded, and `u64::MAX` if
+    /// the value is in the overflow bucket.
+    pub fn quantile(&self, q: f64) -> Option<u64> {
+        let count = self.count();
+        if count == 0 {
+            return None;
+        }
+        let rank = ((q.clamp(0.0, 1.0) * count as f64).ceil() as u64).max(1);
+        let mut seen = 0;
+        for (bound, bucket_count) in self.buckets() {
+            seen += bucket_count;
+            if seen >= rank {
+                return Some(bound.unwrap_or(u64::MAX));
+            }
+        }
+        unreachable!("rank is at most the number of recorded values")
+    }
+}
+
 #[macro_export]
 macro_rules! stat_name {
     ($prefix:expr, $suffix:expr) => {
@@ -220,6 +435,8 @@ macro_rules! stat_name {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::clock::DefaultSystemClock;
+    use std::time::Duration;
 
     macro_rules! test_stat_name {
         ($suffix:expr) => {
@@ -237,25 +454,35 @@ mod tests {
         let registry = StatRegistry::new();
         let stat1 = Arc::new(Gauge::<i32>::default());
         stat1.set(1);
-        registry.register("stat1", stat1);
-        let stat2 = Arc::new(Gauge::<i32>::default());
-        stat2.set(2);
-        registry.register("stat2", stat2);
+        registry.register("stat1", "The first stat", stat1);
+        let stat2 = Arc::new(Counter::default());
+        stat2.add(2);
+        registry.register("stat2", "The second stat", stat2);
 
         assert_eq!(registry.lookup("stat1").unwrap().get(), 1);
         assert_eq!(registry.lookup("stat2").unwrap().get(), 2);
         assert!(registry.lookup("stat3").is_none());
+        assert_eq!(registry.help("stat2"), Some("The second stat"));
+        assert!(registry.help("stat3").is_none());
+        assert_eq!(
+            registry.lookup("stat1").unwrap().stat_type(),
+            StatType::Gauge
+        );
+        assert_eq!(
+            registry.lookup("stat2").unwrap().stat_type(),
+            StatType::Counter
+        );
     }
 
     #[test]
     fn test_should_list_registered_stats() {
         let registry = StatRegistry::new();
         let stat1 = Arc::new(Gauge::<i32>::default());
-        registry.register("stat1", stat1);
+        registry.register("stat1", "The first stat", stat1);
         let stat2 = Arc::new(Gauge::<i32>::default());
-        registry.register("stat2", stat2);
+        registry.register("stat2", "The second stat", stat2);
         let stat3 = Arc::new(Gauge::<i32>::default());
-        registry.register("stat3", stat3);
+        registry.register("stat3", "The third stat", stat3);
 
         let names = registry.names();
         assert_eq!(names, vec!["stat1", "stat2", "stat3"]);
@@ -298,4 +525,51 @@ mod tests {
         gauge.sub(42);
         assert_eq!(gauge.get(), 158);
     }
+
+    #[test]
+    fn test_histogram() {
+        let histogram = Histogram::default();
+        assert_eq!(histogram.get(), 0);
+        assert_eq!(histogram.stat_type(), StatType::Histogram);
+        assert_eq!(histogram.snapshot().quantile(0.5), None);
+        for value in [0, 1, 2, 3, 4, 5, 1000, u64::MAX / 2] {
+            histogram.record(value);
+        }
+
+        let snapshot = histogram.histogram().unwrap();
+        assert_eq!(histogram.get(), 8);
+        assert_eq!(snapshot.count(), 8);
+        assert_eq!(snapshot.sum(), 1015 + u64::MAX / 2);
+        let buckets: Vec<_> = snapshot.buckets().filter(|(_, count)| *count > 0).collect();
+        assert_eq!(
+            buckets,
+            vec![
+                (Some(1), 2),
+                (Some(2), 1),
+                (Some(4), 2),
+                (Some(8), 1),
+                (Some(1024), 1),
+                (None, 1),
+            ]
+        );
+        assert_eq!(snapshot.buckets().count(), HISTOGRAM_BUCKET_COUNT + 1);
+        assert_eq!(snapshot.quantile(0.0), Some(1));
+        assert_eq!(snapshot.quantile(0.5), Some(4));
+        assert_eq!(snapshot.quantile(0.75), Some(8));
+        assert_eq!(snapshot.quantile(1.0), Some(u64::MAX));
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn test_histogram_timer_records_elapsed_micros() {
+        let clock = DefaultSystemClock::new();
+        let histogram = Histogram::default();
+        {
+            let _timer = histogram.start_timer(&clock);
+            clock.sleep(Duration::from_millis(3)).await;
+        }
+
+        let snapshot = histogram.snapshot();
+        assert_eq!(snapshot.count(), 1);
+        assert_eq!(snapshot.sum(), 3000);
+    }
 }
@@ -0,0 +1,182 @@
+//! Renders the metrics of a [`StatRegistry`] in the [OpenMetrics] text format, so that
+//! Prometheus and compatible systems can scrape them.
+//!
+//! Each metric is named after its registry name, prefixed with `slatedb_` and with every
+//! character that is not a letter, a digit or an underscore replaced with an underscore.
+//! For example, `db/get_requests` is exported as `slatedb_db_get_requests`. Counters are
+//! exported with the `_total` suffix that OpenMetrics requires of counter samples, and
+//! histograms with a cumulative `_bucket` sample per bucket followed by `_count` and `_sum`.
+//!
+//! Applications serve the output of [`render`] from their metrics endpoint with the
+//! [`CONTENT_TYPE`] content type.
+//!
+//! [OpenMetrics]: https://github.com/OpenObservability/OpenMetrics/blob/main/specification/OpenMetrics.md
+//!
+//! # Example
+//!
+//! ```
+//! use slatedb::{Db, Error};
+//! use slatedb::object_store::memory::InMemory;
+//! use slatedb::stats::openmetrics;
+//! use std::sync::Arc;
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), Error> {
+//!     let object_store = Arc::new(InMemory::new());
+//!     let db = Db::open("test_db", object_store).await?;
+//!     let body = openmetrics::render(&db.metrics());
+//!     assert!(body.contains("# TYPE slatedb_db_get_requests counter"));
+//!     assert!(body.ends_with("# EOF\n"));
+//!     Ok(())
+//! }
+//! ```
+
+use std::fmt::Write;
+
+use crate::stats::{HistogramSnapshot, StatRegistry, StatType};
+
+/// The content type of the OpenMetrics text format.
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+const NAME_PREFIX: &str = "slatedb_";
+
+/// Renders every metric of the registry in the OpenMetrics text format.
+pub fn render(registry: &StatRegistry) -> String {
+    let mut output = String::new();
+    for (name, help, stat) in registry.entries() {
+        let name = metric_name(name);
+        let type_name = match stat.stat_type() {
+            StatType::Counter => "counter",
+            StatType::Gauge => "gauge",
+            StatType::Histogram => "histogram",
+        };
+        writeln!(output, "# TYPE {name} {type_name}").expect("write to string failed");
+        writeln!(output, "# HELP {name} {}", escape_help(help)).expect("write to string failed");
+        match stat.stat_type() {
+            StatType::Counter => {
+                writeln!(output, "{name}_total {}", stat.get()).expect("write to string failed")
+            }
+            StatType::Histogram => {
+                let histogram = stat.histogram().expect("histogram stat without buckets");
+                render_histogram(&mut output, &name, &histogram);
+            }
+            _ => writeln!(output, "{name} {}", stat.get()).expect("write to string failed"),
+        }
+    }
+    output.push_str("# EOF\n");
+    output
+}
+
+fn render_histogram(output: &mut String, name: &str, histogram: &HistogramSnapshot) {
+    let mut cumulative_count = 0;
+    for (bound, count) in histogram.buckets() {
+        cumulative_count += count;
+        let le = bound.map_or_else(|| "+Inf".to_string(), |bound| bound.to_string());
+        writeln!(output, "{name}_bucket{{le=\"{le}\"}} {cumulative_count}")
+            .expect("write to string failed");
+    }
+    writeln!(output, "{name}_count {cumulative_count}").expect("write to string failed");
+    writeln!(output, "{name}_sum {}", histogram.sum()).expect("write to string failed");
+}
+
+/// Converts a registry name to a valid OpenMetrics metric name.
+fn metric_name(name: &str) -> String {
+    let mut metric_name = String::with_capacity(NAME_PREFIX.len() + name.len());
+    metric_name.push_str(NAME_PREFIX);
+    metric_name.extend(name.chars().map(|c| {
+        if c.is_ascii_alphanumeric() || c == '_' {
+            c
+        } else {
+            '_'
+        }
+    }));
+    metric_name
+}
+
+fn escape_help(help: &str) -> String {
+    help.replace('\\', "\\\\")
+        .replace('\n', "\\n")
+        .replace('"', "\\\"")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::stats::{Counter, Gauge, Histogram, HISTOGRAM_BUCKET_COUNT};
+
+    #[test]
+    fn test_should_render_registry() {
+        let registry = StatRegistry::new();
+        let requests = Arc::new(Counter::default());
+        requests.add(3);
+        registry.register("db/get_requests", "Number of get requests.", requests);
+        let bytes = Arc::new(Gauge::<i64>::default());
+        bytes.set(-42);
+        registry.register("cache/bytes", "Size of the\ncache \"in\" bytes\\.", bytes);
+
+        assert_eq!(
+            render(&registry),
+            "# TYPE slatedb_cache_bytes gauge\n\
+             # HELP slatedb_cache_bytes Size of the\\ncache \\\"in\\\" bytes\\\\.\n\
+             slatedb_cache_bytes -42\n\
+             # TYPE slatedb_db_get_requests counter\n\
+             # HELP slatedb_db_get_requests Number of get requests.\n\
+             slatedb_db_get_requests_total 3\n\
+             # EOF\n"
+        );
+    }
+
+    #[test]
+    fn test_should_render_histogram() {
+        let registry = StatRegistry::new();
+        let latency = Arc::new(Histogram::default());
+        latency.record(1);
+        latency.record(3);
+        latency.record(u64::MAX / 2);
+        registry.register("db/get_latency_us", "Latency of gets.", latency);
+
+        let output = render(&registry);
+        let lines: Vec<_> = output.lines().collect();
+        assert_eq!(lines.len(), HISTOGRAM_BUCKET_COUNT + 1 + 5);
+        assert_eq!(lines[0], "# TYPE slatedb_db_get_latency_us histogram");
+        assert_eq!(
+            lines[1],
+            "# HELP slatedb_db_get_latency_us Latency of gets."
+        );
+        assert_eq!(lines[2], "slatedb_db_get_latency_us_bucket{le=\"1\"} 1");
+        assert_eq!(lines[3], "slatedb_db_get_latency_us_bucket{le=\"2\"} 1");
+        assert_eq!(lines[4], "slatedb_db_get_latency_us_bucket{le=\"4\"} 2");
+        assert_eq!(
+            lines[HISTOGRAM_BUCKET_COUNT + 1],
+            "slatedb_db_get_latency_us_bucket{le=\"134217728\"} 2"
+        );
+        assert_eq!(
+            lines[HISTOGRAM_BUCKET_COUNT + 2],
+            "slatedb_db_get_latency_us_bucket{le=\"+Inf\"} 3"
+        );
+        assert_eq!(
+            lines[HISTOGRAM_BUCKET_COUNT + 3],
+            "slatedb_db_get_latency_us_count 3"
+        );
+        assert_eq!(
+            lines[HISTOGRAM_BUCKET_COUNT + 4],
+            format!("slatedb_db_get_latency_us_sum {}", 4 + u64::MAX / 2)
+        );
+        assert_eq!(lines[HISTOGRAM_BUCKET_COUNT + 5], "# EOF");
+    }
+
+    #[test]
+    fn test_should_render_empty_registry() {
+        assert_eq!(render(&StatRegistry::new()), "# EOF\n");
+    }
+
+    #[test]
+    fn test_should_sanitize_metric_names() {
+        assert_eq!(
+            metric_name("object_store_cache/part-hits.v2"),
+            "slatedb_object_store_cache_part_hits_v2"
+        );
+    }
+}
@@ -1,5 +1,7 @@
 use crate::clock::SystemClock;
+use crate::comparator::KeyComparator;
 use crate::db_cache::DbCache;
+use crate::encryption::EncryptionProviderType;
 use crate::manifest::store::ManifestStore;
 use crate::object_stores::ObjectStores;
 use crate::sst::SsTableFormat;
@@ -18,23 +20,30 @@ pub(crate) struct DefaultStoreProvider {
     pub(crate) object_store: Arc<dyn ObjectStore>,
     pub(crate) block_cache: Option<Arc<dyn DbCache>>,
     pub(crate) system_clock: Arc<dyn SystemClock>,
+    pub(crate) comparator: KeyComparator,
+    pub(crate) encryption_provider: Option<EncryptionProviderType>,
 }
 
 impl StoreProvider for DefaultStoreProvider {
     fn table_store(&self) -> Arc<TableStore> {
         Arc::new(TableStore::new(
             ObjectStores::new(Arc::clone(&self.object_store), None),
-            SsTableFormat::default(),
+            SsTableFormat {
+                comparator: self.comparator.clone(),
+                encryption_provider: self.encryption_provider.clone(),
+                ..SsTableFormat::default()
+            },
             self.path.clone(),
             self.block_cache.clone(),
         ))
     }
 
     fn manifest_store(&self) -> Arc<ManifestStore> {
-        Arc::new(ManifestStore::new(
+        Arc::new(ManifestStore::new_with_encryption_provider(
             &self.path,
             Arc::clone(&self.object_store),
             Arc::clone(&self.system_clock),
+            self.encryption_provider.clone(),
         ))
     }
 }
@@ -13,13 +13,16 @@ use object_store::{ObjectStore, PutMode, PutOptions};
 use tokio::io::AsyncWriteExt;
 use ulid::Ulid;
 
+use crate::blob_file::{read_blob_value, BlobRef, EncodedBlobFile};
+use crate::comparator::KeyComparator;
 use crate::db_cache::{CachedEntry, DbCache};
-use crate::db_state::{SsTableHandle, SsTableId};
+use crate::db_state::{SsTableHandle, SsTableId, SsTableInfo};
 use crate::error::SlateDBError;
 use crate::filter::BloomFilter;
 use crate::flatbuffer_types::SsTableIndexOwned;
 use crate::object_stores::{ObjectStoreType, ObjectStores};
 use crate::paths::PathResolver;
+use crate::range_tombstone::RangeTombstone;
 use crate::sst::{EncodedSsTable, EncodedSsTableBuilder, SsTableFormat};
 use crate::types::RowEntry;
 use crate::{blob::ReadOnlyBlob, block::Block};
@@ -58,6 +61,40 @@ impl ReadOnlyBlob for ReadOnlyObject {
 }
 
 /// Represents the metadata of an SST file in the compacted directory.
+/// An SST stored outside of the table store's layout, e.g. one produced by
+/// [`crate::SstWriter`] for ingestion. It's always read using the table store's format,
+/// which only determines how the SST info is encoded.
+pub(crate) struct ExternalSsTable {
+    obj: ReadOnlyObject,
+    pub(crate) info: SsTableInfo,
+    index: SsTableIndexOwned,
+}
+
+impl ExternalSsTable {
+    pub(crate) fn path(&self) -> &Path {
+        &self.obj.path
+    }
+
+    pub(crate) fn num_blocks(&self) -> usize {
+        self.index.borrow().block_meta().len()
+    }
+
+    pub(crate) async fn read_blocks(
+        &self,
+        format: &SsTableFormat,
+        blocks: Range<usize>,
+    ) -> Result<VecDeque<Block>, SlateDBError> {
+        format
+            .read_blocks(&self.info, &self.index, blocks, &self.obj)
+            .await
+    }
+}
+
+pub(crate) struct BlobFileMetadata {
+    pub(crate) id: Ulid,
+    pub(crate) size: u64,
+}
+
 pub(crate) struct SstFileMetadata {
     pub(crate) id: SsTableId,
     #[allow(dead_code)]
@@ -159,12 +196,27 @@ impl TableStore {
             + 1)
     }
 
+    #[cfg(any(test, feature = "bencher"))]
     pub(crate) fn table_writer(&self, id: SsTableId) -> EncodedSsTableWriter<'_> {
+        self.table_writer_with_format(id, &self.sst_format)
+    }
+
+    /// Returns a writer for an SST encoded with `format` instead of the table store's
+    /// format, e.g. for the SSTs of a column family.
+    pub(crate) fn table_writer_with_format(
+        &self,
+        id: SsTableId,
+        format: &SsTableFormat,
+    ) -> EncodedSsTableWriter<'_> {
         let object_store = self.object_stores.store_for(&id);
         let path = self.path(&id);
+        let builder = match id {
+            SsTableId::Compacted(ulid) => format.table_builder().with_blob_file(ulid),
+            SsTableId::Wal(_) => format.table_builder(),
+        };
         EncodedSsTableWriter {
             id,
-            builder: self.sst_format.table_builder(),
+            builder,
             writer: BufWriter::new(object_store, path),
             table_store: self,
             #[cfg(test)]
@@ -176,6 +228,15 @@ impl TableStore {
         self.sst_format.table_builder()
     }
 
+    pub(crate) fn sst_format(&self) -> &SsTableFormat {
+        &self.sst_format
+    }
+
+    /// Returns the order of the keys of the SSTs.
+    pub(crate) fn comparator(&self) -> &KeyComparator {
+        &self.sst_format.comparator
+    }
+
     pub(crate) async fn write_sst(
         &self,
         id: &SsTableId,
@@ -195,6 +256,9 @@ impl TableStore {
             |_| Result::Err(slatedb_io_error())
         );
 
+        if let Some(blob_file) = &encoded_sst.blob_file {
+            self.write_blob_file(blob_file).await?;
+        }
         let object_store = self.object_stores.store_for(id);
         let data = encoded_sst.remaining_as_bytes();
         let path = self.path(id);
@@ -290,6 +354,71 @@ impl TableStore {
         Ok(sst_list)
     }
 
+    /// Writes a blob file to the main object store. Blob files are written before the
+    /// SSTs pointing to them.
+    pub(crate) async fn write_blob_file(&self, blob: &EncodedBlobFile) -> Result<(), SlateDBError> {
+        let path = self.path_resolver.blob_path(&blob.id);
+        self.object_stores
+            .store_of(ObjectStoreType::Main)
+            .put(&path, blob.data.clone().into())
+            .await?;
+        Ok(())
+    }
+
+    /// Reads the value a blob ref points to.
+    pub(crate) async fn read_blob_value(&self, blob_ref: &BlobRef) -> Result<Bytes, SlateDBError> {
+        let obj = ReadOnlyObject {
+            object_store: self.object_stores.store_of(ObjectStoreType::Main).clone(),
+            path: self.path_resolver.blob_path(&blob_ref.blob_id),
+        };
+        read_blob_value(&obj, blob_ref, self.sst_format.encryption_provider.as_ref()).await
+    }
+
+    /// Delete a blob file from the object store.
+    pub(crate) async fn delete_blob_file(&self, id: &Ulid) -> Result<(), SlateDBError> {
+        let path = self.path_resolver.blob_path(id);
+        debug!("deleting blob file [path={}]", path);
+        self.object_stores
+            .store_of(ObjectStoreType::Main)
+            .delete(&path)
+            .await
+            .map_err(SlateDBError::from)
+    }
+
+    /// List all blob files of the database, in ascending order of their IDs.
+    pub(crate) async fn list_blob_files(&self) -> Result<Vec<BlobFileMetadata>, SlateDBError> {
+        let mut blob_list = Vec::new();
+        let blobs_path = self.path_resolver.blobs_path();
+        let mut files_stream = self
+            .object_stores
+            .store_of(ObjectStoreType::Main)
+            .list(Some(&blobs_path));
+
+        while let Some(file) = files_stream.next().await.transpose()? {
+            match self.path_resolver.parse_blob_id(&file.location) {
+                Ok(Some(id)) => blob_list.push(BlobFileMetadata {
+                    id,
+                    size: file.size,
+                }),
+                Ok(None) => {
+                    warn!(
+                        "unexpected file found in blobs directory [location={}]",
+                        file.location
+                    );
+                }
+                Err(e) => {
+                    warn!(
+                        "error while parsing file id [location={}, error={}]",
+                        file.location, e
+                    );
+                }
+            }
+        }
+
+        blob_list.sort_by_key(|m| m.id);
+        Ok(blob_list)
+    }
+
     pub(crate) async fn open_sst(&self, id: &SsTableId) -> Result<SsTableHandle, SlateDBError> {
         let object_store = self.object_stores.store_for(id);
         let path = self.path(id);
@@ -298,6 +427,31 @@ impl TableStore {
         Ok(SsTableHandle::new(*id, info))
     }
 
+    /// Opens the SST at `path` in the main object store. Unlike SSTs of the database,
+    /// the object might not exist, so it's looked up first rather than relying on the
+    /// object store retrying reads until it appears.
+    pub(crate) async fn open_external_sst(
+        &self,
+        path: &Path,
+    ) -> Result<ExternalSsTable, SlateDBError> {
+        let object_store = self.object_stores.store_of(ObjectStoreType::Main).clone();
+        let parts: Vec<_> = path.parts().collect();
+        let parent = Path::from_iter(parts.iter().take(parts.len().saturating_sub(1)).cloned());
+        let listing = object_store
+            .list_with_delimiter(Some(&parent).filter(|p| !p.as_ref().is_empty()))
+            .await?;
+        if !listing.objects.iter().any(|o| o.location == *path) {
+            return Err(SlateDBError::IngestSstMissing(path.clone()));
+        }
+        let obj = ReadOnlyObject {
+            object_store,
+            path: path.clone(),
+        };
+        let info = self.sst_format.read_info(&obj).await?;
+        let index = self.sst_format.read_index(&info, &obj).await?;
+        Ok(ExternalSsTable { obj, info, index })
+    }
+
     pub(crate) async fn read_filter(
         &self,
         handle: &SsTableHandle,
@@ -541,8 +695,15 @@ impl EncodedSsTableWriter<'_> {
         Ok(block_size)
     }
 
+    pub(crate) fn add_range_tombstone(&mut self, tombstone: RangeTombstone) {
+        self.builder.add_range_tombstone(tombstone);
+    }
+
     pub async fn close(mut self) -> Result<SsTableHandle, SlateDBError> {
         let mut encoded_sst = self.builder.build()?;
+        if let Some(blob_file) = &encoded_sst.blob_file {
+            self.table_store.write_blob_file(blob_file).await?;
+        }
         while let Some(block) = encoded_sst.unconsumed_blocks.pop_front() {
             self.writer.write_all(block.encoded_bytes.as_ref()).await?;
         }
@@ -593,6 +754,7 @@ mod tests {
     use std::collections::VecDeque;
     use std::sync::Arc;
 
+    use crate::blob_file::{BlobFileRef, BlobRef};
     use crate::clock::DefaultSystemClock;
     use crate::db_cache::test_utils::TestCache;
     use crate::db_cache::SplitCache;
@@ -703,6 +865,89 @@ mod tests {
         }
     }
 
+    #[tokio::test]
+    async fn test_sst_writer_should_separate_large_values_into_blob_file() {
+        // given:
+        let main_store = make_store();
+        let format = SsTableFormat {
+            block_size: 32,
+            min_blob_value_size: Some(16),
+            ..SsTableFormat::default()
+        };
+        let ts = Arc::new(TableStore::new(
+            ObjectStores::new(main_store.clone(), None),
+            format,
+            Path::from(ROOT),
+            None,
+        ));
+        let blob_id = ulid::Ulid::new();
+
+        // when:
+        let mut writer = ts.table_writer(SsTableId::Compacted(blob_id));
+        writer
+            .add(RowEntry::new_value(&[b'a'; 16], &[1u8; 8], 0))
+            .await
+            .unwrap();
+        writer
+            .add(RowEntry::new_value(&[b'b'; 16], &[2u8; 16], 0))
+            .await
+            .unwrap();
+        writer
+            .add(RowEntry::new_value(&[b'c'; 16], &[3u8; 32], 0))
+            .await
+            .unwrap();
+        let sst = writer.close().await.unwrap();
+
+        // then:
+        assert_eq!(
+            sst.info.blob_refs,
+            vec![BlobFileRef {
+                id: blob_id,
+                live_bytes: 48
+            }]
+        );
+        let blobs = ts.list_blob_files().await.unwrap();
+        assert_eq!(blobs.len(), 1);
+        assert_eq!(blobs[0].id, blob_id);
+        assert_eq!(blobs[0].size, 48 + 2 * (1 + 4));
+
+        let expected = vec![
+            RowEntry::new_value(&[b'a'; 16], &[1u8; 8], 0),
+            RowEntry::new_value(&[b'b'; 16], &[2u8; 16], 0),
+            RowEntry::new_value(&[b'c'; 16], &[3u8; 32], 0),
+        ];
+        let mut iter = SstIterator::new_owned_initialized(
+            ..,
+            sst.clone(),
+            ts.clone(),
+            SstIteratorOptions::default(),
+        )
+        .await
+        .unwrap()
+        .expect("Expected Some(iter) but got None");
+        assert_iterator(&mut iter, expected).await;
+
+        // unresolved rows point to the blob file
+        let mut iter = SstIterator::new_owned_initialized(
+            ..,
+            sst,
+            ts.clone(),
+            SstIteratorOptions {
+                resolve_blob_refs: false,
+                ..SstIteratorOptions::default()
+            },
+        )
+        .await
+        .unwrap()
+        .expect("Expected Some(iter) but got None");
+        iter.next_entry().await.unwrap().unwrap();
+        let entry = iter.next_entry().await.unwrap().unwrap();
+        assert!(matches!(
+            entry.value,
+            ValueDeletable::BlobRef(BlobRef { blob_id: id, len: 16, .. }) if id == blob_id
+        ));
+    }
+
     #[rstest]
     #[case::main_only(make_store(), None)]
     #[case::main_and_wal(make_store(), Some(make_store()))]
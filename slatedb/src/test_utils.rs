@@ -206,11 +206,15 @@ pub(crate) fn decode_codec_entries(
 pub(crate) async fn assert_ranged_db_scan<T: RangeBounds<Bytes>>(
     table: &BTreeMap<Bytes, Bytes>,
     range: T,
+    ordering: IterationOrder,
     iter: &mut DbIterator,
 ) {
     let mut expected = table.range(range);
     loop {
-        let expected_next = expected.next();
+        let expected_next = match ordering {
+            IterationOrder::Ascending => expected.next(),
+            IterationOrder::Descending => expected.next_back(),
+        };
         let actual_next = iter.next().await.unwrap();
         if expected_next.is_none() && actual_next.is_none() {
             return;
@@ -271,7 +275,10 @@ pub(crate) async fn seed_database(
     await_durable: bool,
 ) -> Result<(), crate::Error> {
     let put_options = PutOptions::default();
-    let write_options = WriteOptions { await_durable };
+    let write_options = WriteOptions {
+        await_durable,
+        ..WriteOptions::default()
+    };
 
     for (key, value) in table.iter() {
         db.put_with_options(key, value, &put_options, &write_options)
@@ -1,11 +1,11 @@
 use crate::bytes_range::BytesRange;
+use crate::comparator::KeyComparator;
 use crate::rand::DbRand;
 use crate::utils::IdGenerator;
 use bytes::Bytes;
 use log::warn;
 use parking_lot::RwLock;
 use std::collections::{HashMap, HashSet, VecDeque};
-use std::ops::RangeBounds;
 use std::sync::Arc;
 use uuid::Uuid;
 
@@ -96,14 +96,17 @@ struct TransactionManagerInner {
     ///   committed_seq` and follow the same GC rule.
     /// - If there are no active non-readonly transactions, this deque can be fully drained.
     recent_committed_txns: VecDeque<TransactionState>,
+    /// The order of keys, used to check whether read ranges contain written keys.
+    comparator: KeyComparator,
 }
 
 impl TransactionManager {
-    pub fn new(db_rand: Arc<DbRand>) -> Self {
+    pub(crate) fn new(db_rand: Arc<DbRand>, comparator: KeyComparator) -> Self {
         Self {
             inner: Arc::new(RwLock::new(TransactionManagerInner {
                 active_txns: HashMap::new(),
                 recent_committed_txns: VecDeque::new(),
+                comparator,
             })),
             db_rand,
         }
@@ -389,7 +392,7 @@ impl TransactionManagerInner {
                     if committed_txn
                         .write_keys
                         .iter()
-                        .any(|write_key| read_range.contains(write_key))
+                        .any(|write_key| read_range.contains_by(write_key, &self.comparator))
                     {
                         return true;
                     }
@@ -409,6 +412,7 @@ mod tests {
     use parking_lot::Mutex;
     use rstest::rstest;
     use std::collections::HashSet;
+    use std::ops::RangeBounds;
 
     struct CheckConflictTestCase {
         name: &'static str,
@@ -421,7 +425,7 @@ mod tests {
     #[test]
     fn test_drop_txn_removes_active_transaction() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create a transaction
         let txn_id = txn_manager.new_txn(100, false);
@@ -439,7 +443,7 @@ mod tests {
     #[test]
     fn test_drop_txn_nonexistent_transaction_safe() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Try to drop a non-existent transaction - should not panic
         let fake_id = Uuid::new_v4();
@@ -453,7 +457,7 @@ mod tests {
     #[test]
     fn test_drop_txn_triggers_garbage_collection() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create an active transaction first to ensure recent_committed_txns tracking
         let txn_id = txn_manager.new_txn(100, false);
@@ -608,7 +612,7 @@ mod tests {
     })]
     fn test_check_conflict_table_driven(#[case] case: CheckConflictTestCase) {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Set up recent_committed_txns directly
         {
@@ -665,7 +669,7 @@ mod tests {
     })]
     fn test_min_active_seq_table_driven(#[case] case: MinActiveSeqTestCase) {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create transactions according to the test case
         for (seq_no, read_only) in case.transactions {
@@ -803,7 +807,7 @@ mod tests {
         #[case] test_case: TrackRecentCommittedTxnTestCase,
     ) {
         let db_rand = Arc::new(DbRand::new(0));
-        let mut txn_manager = TransactionManager::new(db_rand);
+        let mut txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Run the setup
         (test_case.setup)(&mut txn_manager);
@@ -863,7 +867,7 @@ mod tests {
     #[test]
     fn test_recycle_recent_committed_txns_filters_by_min_seq() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create an active write transaction first to enable tracking
         let active_txn1 = txn_manager.new_txn(120, false);
@@ -893,7 +897,7 @@ mod tests {
     #[test]
     fn test_recycle_recent_committed_txns_clears_all_when_no_active_writers() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create a write transaction first to enable tracking
         let txn_id = txn_manager.new_txn(300, false);
@@ -916,7 +920,7 @@ mod tests {
     #[test]
     fn test_recycle_recent_committed_txns_boundary_condition_equal_seq() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create active write transactions first to enable tracking
         let _active_txn1 = txn_manager.new_txn(100, false); // This sets min to 100
@@ -947,7 +951,7 @@ mod tests {
     #[test]
     fn test_recycle_recent_committed_txns_handles_none_committed_seq() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Manually create a transaction state with None committed_seq (edge case)
         {
@@ -986,7 +990,7 @@ mod tests {
     #[test]
     fn test_transaction_lifecycle_complete_flow() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Step 1: Create a transaction
         let txn_id = txn_manager.new_txn(100, false);
@@ -1025,7 +1029,7 @@ mod tests {
     #[test]
     fn test_concurrent_transactions_conflict_detection() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create three concurrent transactions
         let keys_a: HashSet<Bytes> = ["keyA"].into_iter().map(Bytes::from).collect();
@@ -1069,7 +1073,7 @@ mod tests {
     #[test]
     fn test_garbage_collection_timing_with_multiple_operations() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create active transactions first
         let long_txn = txn_manager.new_txn(100, false); // Long-running transaction
@@ -1106,7 +1110,7 @@ mod tests {
     #[test]
     fn test_readonly_vs_write_transaction_interactions() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Create mixed read-only and write transactions
         let readonly_txn1 = txn_manager.new_txn(50, true); // Read-only at seq 50
@@ -1152,7 +1156,7 @@ mod tests {
     #[test]
     fn test_ssi_phantom_read_conflict_on_range() {
         let db_rand = Arc::new(DbRand::new(0));
-        let txn_manager = TransactionManager::new(db_rand);
+        let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
 
         // Reader transaction under SSI that scans a key range
         let reader_txn = txn_manager.new_txn(100, true);
@@ -1375,7 +1379,7 @@ mod tests {
         #[test]
         fn prop_inv_disjoint_active_and_committed_sets(ops in operation_sequence_strategy()) {
             let db_rand = Arc::new(DbRand::new(0));
-            let txn_manager = TransactionManager::new(db_rand);
+            let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
             let mut exec_state = ExecutionState::new();
 
             for op in ops {
@@ -1404,7 +1408,7 @@ mod tests {
         #[test]
         fn prop_all_write_without_conflict_should_be_committed(ops in operation_sequence_strategy()) {
             let db_rand = Arc::new(DbRand::new(0));
-            let txn_manager = TransactionManager::new(db_rand);
+            let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
             let mut exec_state = ExecutionState::new();
 
             for op in ops {
@@ -1419,7 +1423,7 @@ mod tests {
         #[test]
         fn prop_inv_min_active_seq_correctness(ops in operation_sequence_strategy()) {
             let db_rand = Arc::new(DbRand::new(0));
-            let txn_manager = TransactionManager::new(db_rand);
+            let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
             let mut exec_state = ExecutionState::new();
 
             for op in ops {
@@ -1451,7 +1455,7 @@ mod tests {
         #[test]
         fn prop_inv_garbage_collection_correctness(ops in operation_sequence_strategy()) {
             let db_rand = Arc::new(DbRand::new(0));
-            let txn_manager = TransactionManager::new(db_rand);
+            let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
             let mut exec_state = ExecutionState::new();
 
             for op in ops {
@@ -1484,7 +1488,7 @@ mod tests {
         #[test]
         fn prop_inv_ssi_read_write_conflict_detection(ops in operation_sequence_strategy()) {
             let db_rand = Arc::new(DbRand::new(0));
-            let txn_manager = TransactionManager::new(db_rand);
+            let txn_manager = TransactionManager::new(db_rand, KeyComparator::default());
             let mut exec_state = ExecutionState::new();
 
             for op in ops {
@@ -98,7 +98,7 @@ pub(crate) enum TransactionalObjectError {
 
 // Generic codec to serialize/deserialize versioned records stored as files
 pub(crate) trait ObjectCodec<T>: Send + Sync {
-    fn encode(&self, value: &T) -> Bytes;
+    fn encode(&self, value: &T) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>>;
     fn decode(&self, bytes: &Bytes) -> Result<T, Box<dyn std::error::Error + Send + Sync>>;
 }
 
@@ -516,9 +516,12 @@ mod tests {
     pub(in crate::transactional_object) struct TestValCodec;
 
     impl ObjectCodec<TestVal> for TestValCodec {
-        fn encode(&self, value: &TestVal) -> Bytes {
+        fn encode(
+            &self,
+            value: &TestVal,
+        ) -> Result<Bytes, Box<dyn std::error::Error + Send + Sync>> {
             // simple "epoch:payload" encoding
-            Bytes::from(format!("{}:{}", value.epoch, value.payload))
+            Ok(Bytes::from(format!("{}:{}", value.epoch, value.payload)))
         }
 
         fn decode(
@@ -81,10 +81,11 @@ impl<T: Send + Sync> TransactionalStorageProtocol<T, MonotonicId>
             .map(|id| id.next())
             .unwrap_or(MonotonicId::initial());
         let path = self.path_for(id);
+        let bytes = self.codec.encode(new_value).map_err(CallbackError)?;
         self.object_store
             .put_opts(
                 &path,
-                PutPayload::from_bytes(self.codec.encode(new_value)),
+                PutPayload::from_bytes(bytes),
                 PutOptions::from(PutMode::Create),
             )
             .await
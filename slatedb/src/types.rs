@@ -1,5 +1,7 @@
 use bytes::Bytes;
 
+use crate::blob_file::BlobRef;
+
 /// Represents a key-value pair known not to be a tombstone.
 #[non_exhaustive]
 #[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
@@ -20,6 +22,54 @@ where
     }
 }
 
+/// Represents a key-value pair known not to be a tombstone, along with the metadata of
+/// the row it was read from.
+#[non_exhaustive]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct KeyValueWithMetadata {
+    pub key: Bytes,
+    pub value: Bytes,
+    /// The sequence number of the write the value was read from. For a value resolved
+    /// from merge operands, the sequence number of the newest operand.
+    pub seq: u64,
+    /// The time the value was written at, if known.
+    pub create_ts: Option<i64>,
+    /// The time the value expires at, if it has a TTL.
+    pub expire_ts: Option<i64>,
+}
+
+impl KeyValueWithMetadata {
+    /// Returns the value of a row with its metadata, or None if the row is a tombstone.
+    pub(crate) fn from_row(entry: RowEntry) -> Option<Self> {
+        let value = match entry.value {
+            ValueDeletable::Value(value) => value,
+            // merge operands are fully resolved by the time a row gets here, so one
+            // without a base value is the value itself
+            ValueDeletable::Merge(value) => value,
+            ValueDeletable::Tombstone => return None,
+            ValueDeletable::BlobRef(_) => {
+                unreachable!("blob refs are resolved before rows are returned")
+            }
+        };
+        Some(Self {
+            key: entry.key,
+            value,
+            seq: entry.seq,
+            create_ts: entry.create_ts,
+            expire_ts: entry.expire_ts,
+        })
+    }
+}
+
+impl From<KeyValueWithMetadata> for KeyValue {
+    fn from(row: KeyValueWithMetadata) -> Self {
+        KeyValue {
+            key: row.key,
+            value: row.value,
+        }
+    }
+}
+
 /// Represents a key-value pair that may be a tombstone.
 #[derive(Debug, Clone, PartialEq)]
 pub(crate) struct RowEntry {
@@ -136,6 +186,10 @@ pub(crate) enum ValueDeletable {
     Value(Bytes),
     Merge(Bytes),
     Tombstone,
+    /// A value stored in a blob file. Only rows of L0 and compacted SSTs point to blob
+    /// files, and the pointers are resolved to the values when the SSTs are read, except
+    /// by compactions that leave the values in place.
+    BlobRef(BlobRef),
 }
 
 impl ValueDeletable {
@@ -143,6 +197,7 @@ impl ValueDeletable {
         match self {
             ValueDeletable::Value(v) | ValueDeletable::Merge(v) => v.len(),
             ValueDeletable::Tombstone => 0,
+            ValueDeletable::BlobRef(_) => BlobRef::ENCODED_LEN,
         }
     }
 
@@ -154,6 +209,9 @@ impl ValueDeletable {
         match self {
             ValueDeletable::Value(v) | ValueDeletable::Merge(v) => Some(v.clone()),
             ValueDeletable::Tombstone => None,
+            ValueDeletable::BlobRef(_) => {
+                unreachable!("blob refs are resolved before their values are read")
+            }
         }
     }
 }
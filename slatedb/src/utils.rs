@@ -71,6 +71,16 @@ impl<T: Clone> WatchableOnceCellReader<T> {
             .clone()
             .expect("no value found")
     }
+
+    /// Waits for the value to be written. Returns `None` if the cell is dropped without
+    /// a value.
+    pub(crate) async fn try_await_value(&mut self) -> Option<T> {
+        self.rx
+            .wait_for(|v| v.is_some())
+            .await
+            .ok()
+            .and_then(|v| v.clone())
+    }
 }
 
 /// Spawn a background tokio task. The task must return a Result<T, SlateDBError>.
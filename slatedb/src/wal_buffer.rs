@@ -1,4 +1,11 @@
-use std::{collections::VecDeque, sync::Arc, time::Duration};
+use std::{
+    collections::VecDeque,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc,
+    },
+    time::Duration,
+};
 
 use async_trait::async_trait;
 use futures::{stream::BoxStream, StreamExt};
@@ -16,14 +23,17 @@ use tracing::instrument;
 
 use crate::oracle::Oracle;
 use crate::{
-    clock::MonotonicClock,
+    clock::{MonotonicClock, SystemClock},
+    column_family::{self, DEFAULT_COLUMN_FAMILY_ID},
     db_state::{DbState, SsTableId},
     db_stats::DbStats,
     dispatcher::{MessageFactory, MessageHandler, MessageHandlerExecutor},
     error::SlateDBError,
+    event_listener::{EventListeners, WalFlushedEvent},
     iter::KeyValueIterator,
     mem_table::KVTable,
     oracle::DbOracle,
+    range_tombstone::RangeTombstone,
     tablestore::TableStore,
     types::RowEntry,
     utils::SendSafely,
@@ -61,9 +71,14 @@ pub(crate) struct WalBufferManager {
     db_state: Arc<RwLock<DbState>>,
     db_stats: DbStats,
     mono_clock: Arc<MonotonicClock>,
+    system_clock: Arc<dyn SystemClock>,
+    event_listeners: EventListeners,
     table_store: Arc<TableStore>,
-    max_wal_bytes_size: usize,
+    max_wal_bytes_size: AtomicUsize,
     max_flush_interval: Option<Duration>,
+    /// Whether row keys are prefixed with their column family id. This is set for
+    /// databases with column families, see [`crate::column_family::encode_key`].
+    column_family_keys: bool,
 }
 
 struct WalBufferManagerInner {
@@ -93,8 +108,11 @@ impl WalBufferManager {
         oracle: Arc<DbOracle>,
         table_store: Arc<TableStore>,
         mono_clock: Arc<MonotonicClock>,
+        system_clock: Arc<dyn SystemClock>,
+        event_listeners: EventListeners,
         max_wal_bytes_size: usize,
         max_flush_interval: Option<Duration>,
+        column_family_keys: bool,
     ) -> Self {
         let current_wal = Arc::new(KVTable::new());
         let immutable_wals = VecDeque::new();
@@ -114,8 +132,11 @@ impl WalBufferManager {
             db_stats,
             table_store,
             mono_clock,
-            max_wal_bytes_size,
+            system_clock,
+            event_listeners,
+            max_wal_bytes_size: AtomicUsize::new(max_wal_bytes_size),
             max_flush_interval,
+            column_family_keys,
         }
     }
 
@@ -131,6 +152,7 @@ impl WalBufferManager {
         let wal_flush_handler = WalFlushHandler {
             max_flush_interval: self.max_flush_interval,
             wal_buffer: self.clone(),
+            tickers_changed: false,
         };
         let result = task_executor.add_handler(
             WAL_BUFFER_TASK_NAME.to_string(),
@@ -189,14 +211,41 @@ impl WalBufferManager {
         Ok(current_wal_size + imm_wal_size)
     }
 
-    /// Append row entries to the current WAL. return the last seq number of the WAL.
+    /// Append row entries, the row entries of named column families and range tombstones to the
+    /// current WAL. return the last seq number of the WAL.
     /// TODO: validate the seq number is always increasing.
-    pub fn append(&self, entries: &[RowEntry]) -> Result<Arc<KVTable>, SlateDBError> {
+    pub fn append(
+        &self,
+        entries: &[RowEntry],
+        column_family_entries: &[(u32, Vec<RowEntry>)],
+        range_tombstones: &[RangeTombstone],
+    ) -> Result<Arc<KVTable>, SlateDBError> {
         // TODO: check if the wal buffer is in a fatal error state.
 
         let inner = self.inner.write();
-        for entry in entries {
-            inner.current_wal.put(entry.clone());
+        if self.column_family_keys {
+            let all_entries = entries
+                .iter()
+                .map(|entry| (DEFAULT_COLUMN_FAMILY_ID, entry))
+                .chain(
+                    column_family_entries
+                        .iter()
+                        .flat_map(|(id, entries)| entries.iter().map(move |entry| (*id, entry))),
+                );
+            for (id, entry) in all_entries {
+                inner.current_wal.put(RowEntry {
+                    key: column_family::encode_key(id, &entry.key),
+                    ..entry.clone()
+                });
+            }
+        } else {
+            assert!(column_family_entries.is_empty());
+            for entry in entries {
+                inner.current_wal.put(entry.clone());
+            }
+        }
+        for tombstone in range_tombstones {
+            inner.current_wal.put_range_tombstone(tombstone.clone());
         }
         Ok(inner.current_wal.clone())
     }
@@ -213,12 +262,13 @@ impl WalBufferManager {
                 inner.current_wal.metadata().entry_num,
                 inner.current_wal.metadata().entries_size_in_bytes,
             );
+            let max_wal_bytes_size = self.max_wal_bytes_size.load(Ordering::SeqCst);
             trace!(
                 "checking flush trigger [current_wal_size={}, max_wal_bytes_size={}]",
                 current_wal_size,
-                self.max_wal_bytes_size,
+                max_wal_bytes_size,
             );
-            let need_flush = current_wal_size >= self.max_wal_bytes_size;
+            let need_flush = current_wal_size >= max_wal_bytes_size;
             (
                 inner.current_wal.clone(),
                 need_flush,
@@ -231,7 +281,7 @@ impl WalBufferManager {
                 .expect("flush_tx not initialized, please call start_background first.")
                 .send_safely(
                     self.db_state.read().closed_result_reader(),
-                    WalFlushWork { result_tx: None },
+                    WalFlushWork::Flush { result_tx: None },
                 )?
         }
 
@@ -275,7 +325,7 @@ impl WalBufferManager {
         let (result_tx, result_rx) = oneshot::channel();
         flush_tx.send_safely(
             self.db_state.read().closed_result_reader(),
-            WalFlushWork {
+            WalFlushWork::Flush {
                 result_tx: Some(result_tx),
             },
         )?;
@@ -336,17 +386,33 @@ impl WalBufferManager {
 
     async fn do_flush_one_wal(&self, wal_id: u64, wal: Arc<KVTable>) -> Result<(), SlateDBError> {
         self.db_stats.wal_buffer_flushes.inc();
+        let _timer = self
+            .db_stats
+            .wal_flush_latency_us
+            .start_timer(self.system_clock.as_ref());
 
         let mut sst_builder = self.table_store.table_builder();
+        if self.column_family_keys {
+            sst_builder = sst_builder.with_column_family_keys();
+        }
         let mut iter = wal.iter();
         while let Some(entry) = iter.next_entry().await? {
             sst_builder.add(entry)?;
         }
+        for tombstone in wal.range_tombstones() {
+            sst_builder.add_range_tombstone(tombstone);
+        }
 
         let encoded_sst = sst_builder.build()?;
-        self.table_store
+        let sst_handle = self
+            .table_store
             .write_sst(&SsTableId::Wal(wal_id), encoded_sst, false)
             .await?;
+        let event = WalFlushedEvent {
+            wal_id,
+            bytes: sst_handle.estimate_size(),
+        };
+        self.event_listeners.notify(|l| l.on_wal_flushed(&event));
 
         self.mono_clock.fetch_max_last_durable_tick(wal.last_tick());
         Ok(())
@@ -410,6 +476,29 @@ impl WalBufferManager {
         }
     }
 
+    /// Changes the size of the current WAL that triggers a flush.
+    pub(crate) fn set_max_wal_bytes_size(&self, max_wal_bytes_size: usize) {
+        self.max_wal_bytes_size
+            .store(max_wal_bytes_size, Ordering::SeqCst);
+    }
+
+    /// Changes the interval of the automatic flushes. `None` disables them.
+    pub(crate) fn set_max_flush_interval(
+        &self,
+        max_flush_interval: Option<Duration>,
+    ) -> Result<(), SlateDBError> {
+        let flush_tx = self
+            .inner
+            .read()
+            .flush_tx
+            .clone()
+            .expect("flush_tx not initialized, please call start_background first.");
+        flush_tx.send_safely(
+            self.db_state.read().closed_result_reader(),
+            WalFlushWork::SetFlushInterval(max_flush_interval),
+        )
+    }
+
     #[allow(dead_code)]
     pub async fn close(&self) -> Result<(), SlateDBError> {
         let task_executor = {
@@ -424,13 +513,20 @@ impl WalBufferManager {
 }
 
 #[derive(Debug)]
-struct WalFlushWork {
-    result_tx: Option<oneshot::Sender<Result<(), SlateDBError>>>,
+enum WalFlushWork {
+    /// Flushes the WALs, and sends the result to `result_tx` if set.
+    Flush {
+        result_tx: Option<oneshot::Sender<Result<(), SlateDBError>>>,
+    },
+    /// Changes the interval of the automatic flushes.
+    SetFlushInterval(Option<Duration>),
 }
 
 struct WalFlushHandler {
     max_flush_interval: Option<Duration>,
     wal_buffer: Arc<WalBufferManager>,
+    /// Whether `max_flush_interval` changed since the tickers were created.
+    tickers_changed: bool,
 }
 
 #[async_trait]
@@ -439,22 +535,33 @@ impl MessageHandler<WalFlushWork> for WalFlushHandler {
         if let Some(max_flush_interval) = self.max_flush_interval {
             return vec![(
                 max_flush_interval,
-                Box::new(|| WalFlushWork { result_tx: None }),
+                Box::new(|| WalFlushWork::Flush { result_tx: None }),
             )];
         }
         vec![]
     }
 
+    fn tickers_changed(&mut self) -> bool {
+        std::mem::take(&mut self.tickers_changed)
+    }
+
     async fn handle(&mut self, message: WalFlushWork) -> Result<(), SlateDBError> {
-        let WalFlushWork { result_tx } = message;
-        if let Some(result_tx) = result_tx {
-            let result = self.wal_buffer.do_flush().await;
-            result_tx
-                .send(result.clone())
-                .expect("failed to send flush result");
-            result
-        } else {
-            self.wal_buffer.do_flush().await
+        match message {
+            WalFlushWork::Flush {
+                result_tx: Some(result_tx),
+            } => {
+                let result = self.wal_buffer.do_flush().await;
+                result_tx
+                    .send(result.clone())
+                    .expect("failed to send flush result");
+                result
+            }
+            WalFlushWork::Flush { result_tx: None } => self.wal_buffer.do_flush().await,
+            WalFlushWork::SetFlushInterval(max_flush_interval) => {
+                self.max_flush_interval = max_flush_interval;
+                self.tickers_changed = true;
+                Ok(())
+            }
         }
     }
 
@@ -466,8 +573,11 @@ impl MessageHandler<WalFlushWork> for WalFlushHandler {
         let error = result.err().unwrap_or(SlateDBError::Closed);
 
         // drain remaining messages
-        while let Some(WalFlushWork { result_tx }) = messages.next().await {
-            if let Some(result_tx) = result_tx {
+        while let Some(work) = messages.next().await {
+            if let WalFlushWork::Flush {
+                result_tx: Some(result_tx),
+            } = work
+            {
                 result_tx
                     .send(Err(error.clone()))
                     .expect("failed to send flush result");
@@ -491,6 +601,7 @@ impl MessageHandler<WalFlushWork> for WalFlushHandler {
 mod tests {
     use super::*;
     use crate::clock::{DefaultSystemClock, MonotonicClock};
+    use crate::comparator::KeyComparator;
     use crate::manifest::store::test_utils::new_dirty_manifest;
     use crate::object_stores::ObjectStores;
     use crate::sst::SsTableFormat;
@@ -535,7 +646,10 @@ mod tests {
             MonotonicSeq::new(0),
             MonotonicSeq::new(0),
         ));
-        let db_state = Arc::new(RwLock::new(DbState::new(new_dirty_manifest())));
+        let db_state = Arc::new(RwLock::new(DbState::new(
+            new_dirty_manifest(),
+            KeyComparator::default(),
+        )));
         let wal_buffer = Arc::new(WalBufferManager::new(
             wal_id_store,
             db_state.clone(),
@@ -544,8 +658,11 @@ mod tests {
             oracle,
             table_store.clone(),
             mono_clock,
+            system_clock.clone(),
+            EventListeners::default(),
             1000,                            // max_wal_bytes_size
             Some(Duration::from_millis(10)), // max_flush_interval
+            false,                           // column_family_keys
         ));
         let task_executor = Arc::new(MessageHandlerExecutor::new(
             db_state.read().closed_result(),
@@ -578,8 +695,12 @@ mod tests {
             None,
         );
 
-        wal_buffer.append(std::slice::from_ref(&entry1)).unwrap();
-        wal_buffer.append(std::slice::from_ref(&entry2)).unwrap();
+        wal_buffer
+            .append(std::slice::from_ref(&entry1), &[], &[])
+            .unwrap();
+        wal_buffer
+            .append(std::slice::from_ref(&entry2), &[], &[])
+            .unwrap();
 
         // Flush the buffer
         wal_buffer.flush().await.unwrap();
@@ -626,7 +747,7 @@ mod tests {
                 None,
                 None,
             );
-            wal_buffer.append(&[entry]).unwrap();
+            wal_buffer.append(&[entry], &[], &[]).unwrap();
             wal_buffer
                 .maybe_trigger_flush()
                 .unwrap()
@@ -654,7 +775,7 @@ mod tests {
                 None,
                 None,
             );
-            wal_buffer.append(&[entry]).unwrap();
+            wal_buffer.append(&[entry], &[], &[]).unwrap();
             wal_buffer.flush().await.unwrap();
         }
         assert_eq!(wal_buffer.recent_flushed_wal_id(), 100);
@@ -678,7 +799,7 @@ mod tests {
                 None,
                 None,
             );
-            wal_buffer.append(&[entry]).unwrap();
+            wal_buffer.append(&[entry], &[], &[]).unwrap();
             wal_buffer.flush().await.unwrap();
         }
         wal_buffer.track_last_applied_seq(50);
@@ -1,7 +1,9 @@
+use crate::column_family::{self, DEFAULT_COLUMN_FAMILY_ID};
 use crate::db_state::{CoreDbState, SsTableId};
 use crate::error::SlateDBError;
 use crate::iter::KeyValueIterator;
 use crate::mem_table::WritableKVTable;
+use crate::range_tombstone::RangeTombstone;
 use crate::sst_iter::{SstIterator, SstIteratorOptions};
 use crate::tablestore::TableStore;
 use crate::types::RowEntry;
@@ -70,6 +72,7 @@ impl<T> IteratorHolder<T> {
 }
 
 struct ReplayedRow {
+    column_family: u32,
     row_entry: RowEntry,
     wal_id: u64,
 }
@@ -81,6 +84,12 @@ pub(crate) struct WalReplayIterator<'a> {
     current_iter: IteratorHolder<SstIterator<'a>>,
     next_iters: VecDeque<JoinHandle<Result<Option<SstIterator<'a>>, SlateDBError>>>,
     overflow_row: Option<ReplayedRow>,
+    /// Range tombstones of the current WAL SST that are not yet replayed into a table.
+    pending_range_tombstones: Vec<RangeTombstone>,
+    /// Whether the row keys of the current WAL SST are prefixed with their column family.
+    column_family_keys: bool,
+    /// The ids of the column families rows are replayed into.
+    column_families: Vec<u32>,
     last_tick: i64,
     last_seq: u64,
     min_seq: u64,
@@ -115,6 +124,9 @@ impl WalReplayIterator<'_> {
             current_iter: IteratorHolder::new(),
             next_iters: VecDeque::new(),
             overflow_row: None,
+            pending_range_tombstones: Vec::new(),
+            column_family_keys: false,
+            column_families: db_state.column_families.iter().map(|cf| cf.id).collect(),
             last_tick,
             last_seq,
             min_seq,
@@ -191,6 +203,18 @@ impl WalReplayIterator<'_> {
         } else {
             None
         };
+        if let Some(sst_iter) = &next_iter {
+            let min_seq = self.min_seq;
+            self.pending_range_tombstones = sst_iter
+                .table()
+                .info
+                .range_tombstones
+                .iter()
+                .filter(|tombstone| tombstone.seq > min_seq)
+                .cloned()
+                .collect();
+            self.column_family_keys = sst_iter.table().info.column_family_keys;
+        }
         self.current_iter.advance(next_iter);
         Ok(())
     }
@@ -206,7 +230,10 @@ impl WalReplayIterator<'_> {
             return Ok(None);
         }
 
-        let table = WritableKVTable::new();
+        let table = WritableKVTable::with_column_families(
+            self.column_families.iter().copied(),
+            self.table_store.comparator(),
+        );
         let mut last_wal_id = 0;
 
         if let Some(overflow_row) = self.overflow_row.take() {
@@ -215,19 +242,31 @@ impl WalReplayIterator<'_> {
                 self.last_tick = self.last_tick.max(ts);
             }
             self.last_seq = self.last_seq.max(row_entry.seq);
-            table.put(row_entry);
+            Self::put_row(&table, overflow_row.column_family, row_entry);
             last_wal_id = overflow_row.wal_id;
         }
 
         while !self.current_iter.is_finished() {
             if let Some(sst_iter) = &mut self.current_iter.current_iter {
                 let wal_id = sst_iter.table_id().unwrap_wal_id();
+                for tombstone in self.pending_range_tombstones.drain(..) {
+                    self.last_tick = self.last_tick.max(tombstone.create_ts);
+                    self.last_seq = self.last_seq.max(tombstone.seq);
+                    table.put_range_tombstone(tombstone);
+                }
                 while let Some(row_entry) = sst_iter.next_entry().await? {
                     // skip the entries that are already in the L0 SST.
                     if row_entry.seq <= self.min_seq {
                         continue;
                     }
 
+                    let (column_family, row_entry) = if self.column_family_keys {
+                        let (column_family, key) = column_family::decode_key(&row_entry.key)?;
+                        (column_family, RowEntry { key, ..row_entry })
+                    } else {
+                        (DEFAULT_COLUMN_FAMILY_ID, row_entry)
+                    };
+
                     // if the table is full, we'll overflow the row to the next iterator.
                     let meta = table.metadata();
                     if self.table_store.estimate_encoded_size(
@@ -235,7 +274,11 @@ impl WalReplayIterator<'_> {
                         meta.entries_size_in_bytes + row_entry.estimated_size(),
                     ) > self.options.max_memtable_bytes
                     {
-                        self.overflow_row.replace(ReplayedRow { row_entry, wal_id });
+                        self.overflow_row.replace(ReplayedRow {
+                            column_family,
+                            row_entry,
+                            wal_id,
+                        });
                         break;
                     }
 
@@ -243,7 +286,7 @@ impl WalReplayIterator<'_> {
                         self.last_tick = self.last_tick.max(ts);
                     }
                     self.last_seq = self.last_seq.max(row_entry.seq);
-                    table.put(row_entry);
+                    Self::put_row(&table, column_family, row_entry);
                 }
 
                 let table_overflowed = self.overflow_row.is_some();
@@ -277,6 +320,15 @@ impl WalReplayIterator<'_> {
             Ok(None)
         }
     }
+
+    /// Rows of column families that are not replayed are dropped.
+    fn put_row(table: &WritableKVTable, column_family: u32, row_entry: RowEntry) {
+        if column_family == DEFAULT_COLUMN_FAMILY_ID {
+            table.put(row_entry);
+        } else {
+            table.put_column_family(column_family, row_entry);
+        }
+    }
 }
 
 #[cfg(test)]
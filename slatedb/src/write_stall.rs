@@ -0,0 +1,122 @@
+//! # Write stalls
+//!
+//! Decides whether writes are slowed down or stopped while memtable flushes and
+//! compactions catch up, following the [`WriteStallOptions`] of the database. Writes
+//! stopped by [`Settings::max_unflushed_bytes`] are handled separately, by the
+//! backpressure of the writer.
+//!
+//! [`Settings::max_unflushed_bytes`]: crate::config::Settings::max_unflushed_bytes
+
+use std::time::Duration;
+
+use crate::config::WriteStallOptions;
+
+/// What a write must do before it's applied.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub(crate) enum WriteStall {
+    /// The write is applied right away.
+    None,
+    /// The write is delayed by the duration.
+    Delay(Duration),
+    /// The write waits until the number of L0 SSTs drops below the stop limit.
+    Stop,
+}
+
+/// Returns what a write must do given the number of L0 SSTs of the column family with
+/// the most L0 SSTs and the number of unflushed bytes.
+pub(crate) fn write_stall(
+    options: &WriteStallOptions,
+    l0_ssts: usize,
+    l0_max_ssts: usize,
+    unflushed_bytes: usize,
+    max_unflushed_bytes: usize,
+) -> WriteStall {
+    if options.l0_stop_ssts.is_some_and(|stop| l0_ssts >= stop) {
+        return WriteStall::Stop;
+    }
+    let l0_fraction = options.l0_slowdown_ssts.map_or(0.0, |slowdown| {
+        slowdown_fraction(
+            l0_ssts,
+            slowdown,
+            options.l0_stop_ssts.unwrap_or(l0_max_ssts),
+        )
+    });
+    let unflushed_fraction = options.unflushed_bytes_slowdown.map_or(0.0, |slowdown| {
+        slowdown_fraction(unflushed_bytes, slowdown, max_unflushed_bytes)
+    });
+    let fraction = l0_fraction.max(unflushed_fraction);
+    if fraction > 0.0 {
+        WriteStall::Delay(options.max_write_delay.mul_f64(fraction))
+    } else {
+        WriteStall::None
+    }
+}
+
+/// Returns the fraction of the max write delay for `value`, which grows linearly from
+/// the `slowdown` limit, where it's already positive, to 1 at the `stop` limit.
+fn slowdown_fraction(value: usize, slowdown: usize, stop: usize) -> f64 {
+    if value < slowdown {
+        0.0
+    } else if value >= stop {
+        1.0
+    } else {
+        (value - slowdown + 1) as f64 / (stop - slowdown + 1) as f64
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options() -> WriteStallOptions {
+        WriteStallOptions {
+            l0_slowdown_ssts: Some(4),
+            l0_stop_ssts: Some(8),
+            unflushed_bytes_slowdown: Some(1000),
+            max_write_delay: Duration::from_secs(1),
+        }
+    }
+
+    #[test]
+    fn test_should_not_stall_below_slowdown_limits() {
+        assert_eq!(write_stall(&options(), 3, 8, 999, 2000), WriteStall::None);
+        assert_eq!(
+            write_stall(&WriteStallOptions::default(), 100, 8, 1999, 2000),
+            WriteStall::None
+        );
+    }
+
+    #[test]
+    fn test_should_delay_writes_between_limits() {
+        assert_eq!(
+            write_stall(&options(), 4, 8, 0, 2000),
+            WriteStall::Delay(Duration::from_millis(200))
+        );
+        assert_eq!(
+            write_stall(&options(), 7, 8, 0, 2000),
+            WriteStall::Delay(Duration::from_millis(800))
+        );
+        // the larger of the delays is used
+        assert_eq!(
+            write_stall(&options(), 4, 8, 2000, 2000),
+            WriteStall::Delay(Duration::from_secs(1))
+        );
+    }
+
+    #[test]
+    fn test_should_stop_writes_at_l0_stop_limit() {
+        assert_eq!(write_stall(&options(), 8, 8, 0, 2000), WriteStall::Stop);
+    }
+
+    #[test]
+    fn test_should_delay_writes_up_to_l0_max_ssts_without_stop_limit() {
+        let options = WriteStallOptions {
+            l0_stop_ssts: None,
+            ..options()
+        };
+        assert_eq!(
+            write_stall(&options, 12, 8, 0, 2000),
+            WriteStall::Delay(Duration::from_secs(1))
+        );
+    }
+}
@@ -151,6 +151,7 @@ async fn test_concurrent_writers_and_readers() {
                         &PutOptions::default(),
                         &WriteOptions {
                             await_durable: false,
+                            ..WriteOptions::default()
                         },
                     )
                     .await